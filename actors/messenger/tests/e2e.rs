@@ -493,6 +493,9 @@ impl RawClient {
             listen: vec![],
             bootstrap: vec![bootstrap],
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let mut node = VosNode::with_prefix(local_prefix);
         node.attach_network(net);
@@ -8,7 +8,9 @@
 //! into the credential accepted by another root service.
 
 use vos::prelude::*;
-use vos::registry::{invite_signed_bytes, role_grant_supersedes};
+use vos::registry::{
+    AuditPage, AuditRow, auth_role_name, invite_signed_bytes, role_grant_supersedes,
+};
 use vos::v2::{
     Origin, RoleAuthorityInviteRedemptionV2, RoleAuthorityInviteRevocationV2,
     RoleAuthorityMutationV2, RoleAuthorizationClaimV2, SpaceId, SubjectId, V2Wire,
@@ -49,6 +51,7 @@ pub fn initial_state(
             authority_replication_id,
             root_peer_id,
             revoked_invites: Vec::new(),
+            audit: Vec::new(),
             grants: vec![GrantRow {
                 holder_kind: 0,
                 holder: root.0,
@@ -71,8 +74,16 @@ pub struct SpaceAuthority {
     root_peer_id: Vec<u8>,
     revoked_invites: Vec<[u8; 32]>,
     grants: Vec<GrantRow>,
+    /// Hash-chained log of every accepted mutation, in commit order —
+    /// the same [`AuditRow`] chain the registry keeps, so one
+    /// [`verify_audit_chain`](vos::registry::verify_audit_chain) checks
+    /// either.
+    audit: Vec<AuditRow>,
 }
 
+/// Row cap for one [`SpaceAuthority::audit`] page.
+const AUDIT_PAGE_ROWS: u64 = 64;
+
 #[messages]
 impl SpaceAuthority {
     /// The empty constructor is fail-closed. Production installation supplies
@@ -84,45 +95,74 @@ impl SpaceAuthority {
             root_peer_id: Vec::new(),
             revoked_invites: Vec::new(),
             grants: Vec::new(),
+            audit: Vec::new(),
         }
     }
 
     /// Apply one root-signed grant or revoke. Epochs are strictly monotonic
     /// per holder, making retries idempotent and stale signed operations inert.
     #[msg]
-    fn mutate_role(&mut self, mutation: Vec<u8>, signature: Vec<u8>) -> bool {
+    fn mutate_role(
+        &mut self,
+        mutation: Vec<u8>,
+        signature: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> bool {
         let Ok(mutation) = RoleAuthorityMutationV2::decode(&mutation) else {
             return false;
         };
-        if mutation.space().0 != self.space
-            || !self.verify_root_signature(&mutation.encode(), &signature)
-        {
+        let op = mutation.encode();
+        if mutation.space().0 != self.space || !self.verify_root_signature(&op, &signature) {
             return false;
         }
-        match mutation {
+        let (applied, action, detail) = match mutation {
             RoleAuthorityMutationV2::Grant {
                 holder,
                 role,
                 epoch,
                 ..
-            } => self.apply_grant(
-                holder,
-                role,
-                epoch,
-                self.root_origin(),
-                self.root_peer_id.clone(),
+            } => (
+                self.apply_grant(
+                    holder,
+                    role,
+                    epoch,
+                    self.root_origin(),
+                    self.root_peer_id.clone(),
+                ),
+                "grant_role",
+                format!("role={} epoch={epoch}", auth_role_name(role.as_u8())),
             ),
-            RoleAuthorityMutationV2::Revoke { holder, epoch, .. } => {
-                self.apply_revoke(holder, epoch)
-            }
+            RoleAuthorityMutationV2::Revoke { holder, epoch, .. } => (
+                self.apply_revoke(holder, epoch),
+                "revoke_role",
+                format!("epoch={epoch}"),
+            ),
+        };
+        if applied {
+            let target = holder_key(mutation.holder())
+                .map(|(_, holder)| holder.to_vec())
+                .unwrap_or_default();
+            self.record_audit(
+                ctx,
+                AuditRow::new(
+                    None,
+                    self.root_peer_id.clone(),
+                    action.into(),
+                    target,
+                    detail,
+                    op,
+                    signature,
+                ),
+            );
         }
+        applied
     }
 
     /// Apply an offline invite only after verifying the complete delegated
     /// chain: current admin → token → controlled node peer. Expiry is checked
     /// by the serving host before this deterministic method is admitted.
     #[msg]
-    fn redeem_invite(&mut self, redemption: Vec<u8>) -> bool {
+    fn redeem_invite(&mut self, redemption: Vec<u8>, ctx: &mut Context<Self>) -> bool {
         let Ok(redemption) = RoleAuthorityInviteRedemptionV2::decode(&redemption) else {
             return false;
         };
@@ -169,20 +209,44 @@ impl SpaceAuthority {
         {
             return false;
         }
-        self.apply_grant(
+        let applied = self.apply_grant(
             redemption.holder(),
             redemption.role,
             redemption.expires_at,
             grantor,
-            redemption.admin_peer_id,
-        )
+            redemption.admin_peer_id.clone(),
+        );
+        if applied {
+            self.record_audit(
+                ctx,
+                AuditRow::new(
+                    None,
+                    redemption.admin_peer_id,
+                    "redeem_invite".into(),
+                    redemption.holder_peer_id,
+                    format!(
+                        "role={} expires_at={}",
+                        auth_role_name(redemption.role.as_u8()),
+                        redemption.expires_at,
+                    ),
+                    invite,
+                    redemption.admin_signature.to_vec(),
+                ),
+            );
+        }
+        applied
     }
 
     /// Permanently cancel an offline bearer. The authority authenticates the
     /// admin independently of the legacy registry and stores a sorted,
     /// grow-only token set, so replay and merge order cannot resurrect it.
     #[msg]
-    fn revoke_invite(&mut self, revocation: Vec<u8>, signature: Vec<u8>) -> bool {
+    fn revoke_invite(
+        &mut self,
+        revocation: Vec<u8>,
+        signature: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> bool {
         let Ok(revocation) = RoleAuthorityInviteRevocationV2::decode(&revocation) else {
             return false;
         };
@@ -194,13 +258,25 @@ impl SpaceAuthority {
         let Ok(signature) = <[u8; 64]>::try_from(signature) else {
             return false;
         };
-        if !Self::verify_peer_signature(&revocation.admin_peer_id, &revocation.encode(), &signature)
-        {
+        let op = revocation.encode();
+        if !Self::verify_peer_signature(&revocation.admin_peer_id, &op, &signature) {
             return false;
         }
         if let Err(index) = self.revoked_invites.binary_search(&revocation.token_pub) {
             self.revoked_invites.insert(index, revocation.token_pub);
         }
+        self.record_audit(
+            ctx,
+            AuditRow::new(
+                None,
+                revocation.admin_peer_id,
+                "revoke_invite".into(),
+                revocation.token_pub.to_vec(),
+                String::new(),
+                op,
+                signature.to_vec(),
+            ),
+        );
         true
     }
 
@@ -225,6 +301,45 @@ impl SpaceAuthority {
         claim.encode()
     }
 
+    /// One encoded [`AuditPage`] of the mutation log from seq `from`,
+    /// framed as `Value::Bytes` like [`authorize_role`](Self::authorize_role).
+    /// `budget` caps the rows (0 = the page maximum).
+    #[msg]
+    fn audit(&self, from: u64, budget: u32) -> Vec<u8> {
+        let cap = match budget {
+            0 => AUDIT_PAGE_ROWS,
+            budget => u64::from(budget).min(AUDIT_PAGE_ROWS),
+        };
+        let len = self.audit.len() as u64;
+        let start = from.min(len);
+        let end = start.saturating_add(cap).min(len);
+        AuditPage {
+            rows: self.audit[start as usize..end as usize].to_vec(),
+            more: end < len,
+        }
+        .encode()
+    }
+
+    /// Stamp `row` with the work item's admission timeslot and append it
+    /// chained to the tail, unless this exact `(action, target, signature)`
+    /// is already logged — a replayed mutation returns `true` again but is
+    /// not a second event.
+    fn record_audit(&mut self, ctx: &Context<Self>, row: AuditRow) {
+        if self.audit.iter().any(|logged| {
+            logged.action == row.action
+                && logged.target == row.target
+                && logged.signature == row.signature
+        }) {
+            return;
+        }
+        let row = AuditRow {
+            at_ms: ctx.admitted_at_ms(),
+            ..row
+        };
+        let row = row.chained_after(self.audit.last());
+        self.audit.push(row);
+    }
+
     fn root_origin(&self) -> Origin {
         Origin::Member(SubjectId::of_authenticated_peer(&self.root_peer_id))
     }
@@ -458,10 +573,23 @@ mod tests {
     }
 
     fn dispatch<M>(actor: &mut SpaceAuthority, message: M) -> <SpaceAuthority as Message<M>>::Output
+    where
+        SpaceAuthority: Message<M>,
+    {
+        dispatch_at(actor, message, None)
+    }
+
+    /// [`dispatch`] inside a work item admitted at `at_ms`.
+    fn dispatch_at<M>(
+        actor: &mut SpaceAuthority,
+        message: M,
+        at_ms: Option<u64>,
+    ) -> <SpaceAuthority as Message<M>>::Output
     where
         SpaceAuthority: Message<M>,
     {
         let mut context = Context::new(ServiceId(0));
+        context.__set_admitted_at_ms(at_ms);
         vos::block_on(<SpaceAuthority as Message<M>>::handle(
             actor,
             message,
@@ -829,4 +957,90 @@ mod tests {
         tampered.space = SpaceId([46; 32]);
         assert!(!redeem(&mut authority, &tampered));
     }
+
+    #[test]
+    fn accepted_mutations_extend_a_verifiable_audit_chain() {
+        let root = SigningKey::from_bytes(&[51; 32]);
+        let admin = SigningKey::from_bytes(&[52; 32]);
+        let token = SigningKey::from_bytes(&[53; 32]);
+        let holder_key = SigningKey::from_bytes(&[54; 32]);
+        let space = SpaceId([55; 32]);
+        let admin_holder = Origin::Member(SubjectId::of_authenticated_peer(&root_peer(&admin)));
+        let holder = SubjectId::of_authenticated_peer(&root_peer(&holder_key));
+        let mut authority = actor(space, &root);
+        assert!(apply(
+            &mut authority,
+            &root,
+            RoleAuthorityMutationV2::Grant {
+                space,
+                holder: admin_holder,
+                role: SpaceRole::Admin,
+                epoch: 2,
+            },
+        ));
+        let redemption =
+            invite_redemption(space, &admin, &token, &holder_key, SpaceRole::Developer, 50);
+        assert!(redeem(&mut authority, &redemption));
+        assert!(
+            redeem(&mut authority, &redemption),
+            "retry logs nothing new"
+        );
+        // A forged mutation is refused and leaves no row.
+        assert!(!apply(
+            &mut authority,
+            &admin,
+            RoleAuthorityMutationV2::Revoke {
+                space,
+                holder: admin_holder,
+                epoch: 3,
+            },
+        ));
+        let revoke = RoleAuthorityMutationV2::Revoke {
+            space,
+            holder: Origin::Member(holder),
+            epoch: 60,
+        };
+        assert!(dispatch_at(
+            &mut authority,
+            MutateRole {
+                mutation: revoke.encode(),
+                signature: root.sign(&revoke.encode()).to_bytes().to_vec(),
+            },
+            Some(1_700_000_000_000),
+        ));
+
+        let mut rows = Vec::new();
+        loop {
+            let page = AuditPage::decode(&dispatch(
+                &mut authority,
+                Audit {
+                    from: rows.len() as u64,
+                    budget: 2,
+                },
+            ));
+            rows.extend(page.rows);
+            if !page.more {
+                break;
+            }
+        }
+        assert_eq!(vos::registry::verify_audit_chain(&rows), Ok(()));
+        let actions: Vec<&str> = rows.iter().map(|row| row.action.as_str()).collect();
+        assert_eq!(actions, ["grant_role", "redeem_invite", "revoke_role"]);
+        assert_eq!(rows[0].actor, root_peer(&root));
+        assert_eq!(rows[0].detail, "role=admin epoch=2");
+        assert_eq!(rows[1].actor, root_peer(&admin));
+        assert_eq!(rows[1].target, root_peer(&holder_key));
+        assert_eq!(rows[2].target, holder.0);
+        assert_eq!(rows[2].at_ms, Some(1_700_000_000_000));
+        // Every row carries the exact bytes its signature covers.
+        for row in &rows {
+            let signature: [u8; 64] = row.signature.as_slice().try_into().unwrap();
+            assert!(
+                SpaceAuthority::verify_peer_signature(&row.actor, &row.op, &signature),
+                "{}",
+                row.action
+            );
+            assert!(vos::registry::audit_op_matches(row), "{}", row.action);
+        }
+    }
 }
@@ -53,13 +53,14 @@ pub const SERVICE_ID_RAW: u32 = 0;
 // the moved `ed25519_pubkey_from_peer_id`.
 pub use vos::registry::{
     AUTH_ROLE_ADMIN, AUTH_ROLE_DEVELOPER, AUTH_ROLE_NONE, AUTH_ROLE_READONLY, ActorAclPage,
-    ActorAclRow, AgentNamePage, AgentPage, AgentRow, AuthGrantPage, AuthGrantRow, BINDING_DOMAIN,
-    InvitePage, InviteRow, MEMBER_KIND_IDENTITY, MEMBER_KIND_NODE, MemberPage, MemberRow,
-    NODE_ROLE_OBSERVER, NODE_ROLE_VOTER, OP_SIG_LEN, PROOF_KIND_MERKLE_INCLUSION, PROOF_KIND_ZK,
-    ProgramPage, ProgramRow, REGISTRY_OP_DOMAIN, SPACE_ID_DOMAIN_TAG, Status, SyncFloor,
-    binding_signed_bytes, canonical_op_bytes, ed25519_pubkey_from_peer_id, instance_service_id,
-    invite_signed_bytes, pack_auth, role_authority_cutover_signed_bytes,
-    role_authority_invite_attestation_signed_bytes, role_grant_supersedes,
+    ActorAclRow, AgentNamePage, AgentPage, AgentRow, AuditPage, AuditRow, AuthGrantPage,
+    AuthGrantRow, BINDING_DOMAIN, InvitePage, InviteRow, MEMBER_KIND_IDENTITY, MEMBER_KIND_NODE,
+    MemberPage, MemberRow, NODE_ROLE_OBSERVER, NODE_ROLE_VOTER, NODE_ROLE_WITNESS, OP_SIG_LEN,
+    PROOF_KIND_MERKLE_INCLUSION, PROOF_KIND_ZK, ProgramPage, ProgramRow, ProvenanceRow,
+    REGISTRY_OP_DOMAIN, SPACE_ID_DOMAIN_TAG, Status, SyncFloor, binding_signed_bytes,
+    canonical_op_bytes, ed25519_pubkey_from_peer_id, instance_service_id, invite_signed_bytes,
+    pack_auth, role_authority_cutover_signed_bytes, role_authority_invite_attestation_signed_bytes,
+    role_grant_supersedes,
 };
 
 // ── Programs ──────────────────────────────────────────────────────
@@ -209,7 +210,8 @@ pub struct HostMappingPage {
 // ── Actor ─────────────────────────────────────────────────────────
 
 use vos::prelude::*;
-use vos::storage::{StorageMap, StorageSet, StorageValue, fill_page};
+use vos::registry::auth_role_name;
+use vos::storage::{StorageMap, StorageSet, StorageValue, StorageVec, fill_page};
 
 /// Per-actor SpaceRole map (M7) — declared as a `pub const` so
 /// it survives the `#[actor(space_role_map = ...)]` expansion.
@@ -332,6 +334,8 @@ pub struct SpaceRegistry {
     /// other admin's authority delegates from it through `auth_grants`.
     /// Pinned into `space_id` (it rides the genesis DAG), so a joiner
     /// verifies it via the same `space new`/`space verify` root recompute.
+    /// [`rotate_identity`](SpaceRegistry::rotate_identity) may later hand
+    /// it to a successor key; `space_id` keeps pinning the genesis one.
     /// A `#[storage]` value so the anchor every grant chain bottoms out
     /// at resets (or survives) together with the grant rows and revoke
     /// floors it governs — see `revoke_epochs`.
@@ -367,6 +371,23 @@ pub struct SpaceRegistry {
     /// token.
     #[storage]
     invites: StorageMap<[u8; 32], InviteRow>,
+    /// Import provenance, one write-once row per imported installation,
+    /// keyed by the `replication_id` it installed under. Like
+    /// `used_replication_ids` it outlives the `AgentRow`, so an uninstall
+    /// never erases where a state history came from.
+    #[storage]
+    provenance: StorageMap<[u8; 32], ProvenanceRow>,
+    /// The admin audit log: one hash-chained [`AuditRow`] per accepted
+    /// signed mutation, in this replica's acceptance order. Append-only —
+    /// no handler rewrites or removes a row — so `space audit` can re-walk
+    /// it with [`verify_audit_chain`].
+    #[storage]
+    audit_log: StorageVec<AuditRow>,
+    /// Grow-only dedupe set for `audit_log`, keyed by `audit_key`. A
+    /// replayed or re-delivered op re-verifies and re-applies
+    /// idempotently; this keeps it from logging a second row.
+    #[storage]
+    audited: StorageSet<[u8; 32]>,
 }
 
 #[messages]
@@ -390,6 +411,9 @@ impl SpaceRegistry {
             space_id: StorageValue::default(),
             used_replication_ids: StorageSet::default(),
             invites: StorageMap::default(),
+            provenance: StorageMap::default(),
+            audit_log: StorageVec::default(),
+            audited: StorageSet::default(),
         }
     }
 
@@ -489,16 +513,13 @@ impl SpaceRegistry {
         &mut self,
         authority_replication_id: Vec<u8>,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
         let Some(authority_replication_id) = bytes_to_32(&authority_replication_id) else {
             return Status::BadHash;
         };
-        if authority_replication_id == [0; 32]
-            || !self.authorize_root_op(
-                &role_authority_cutover_signed_bytes(&authority_replication_id),
-                &auth,
-            )
-        {
+        let op = role_authority_cutover_signed_bytes(&authority_replication_id);
+        if authority_replication_id == [0; 32] || !self.authorize_root_op(&op, &auth) {
             return Status::Forbidden;
         }
         if let Some(current) = self.role_authority_cutover_id() {
@@ -516,6 +537,14 @@ impl SpaceRegistry {
                 blob: authority_replication_id.to_vec(),
             },
         );
+        self.record_audit(
+            ctx,
+            "seal_role_authority",
+            &authority_replication_id,
+            String::new(),
+            &op,
+            &auth,
+        );
         Status::Ok
     }
 
@@ -533,14 +562,13 @@ impl SpaceRegistry {
         hash: Vec<u8>,
         crdt: bool,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes(
-                "publish",
-                &[name.as_bytes(), version.as_bytes(), &hash, &[crdt as u8]],
-            ),
-            &auth,
-        ) {
+        let op = canonical_op_bytes(
+            "publish",
+            &[name.as_bytes(), version.as_bytes(), &hash, &[crdt as u8]],
+        );
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         // An empty program name is the `programs` pager's start-of-table
@@ -567,6 +595,14 @@ impl SpaceRegistry {
             }
             idx += 1;
         }
+        self.record_audit(
+            ctx,
+            "publish",
+            name.as_bytes(),
+            format!("version={version}"),
+            &op,
+            &auth,
+        );
         self.programs.insert(
             idx,
             ProgramRow {
@@ -582,11 +618,15 @@ impl SpaceRegistry {
     /// Remove a program from the catalog. Errors with
     /// `Status::InUse` if any agent still references the version.
     #[msg(role = SpaceRegistryRole::Admin)]
-    async fn unpublish(&mut self, name: String, version: String, auth: Vec<u8>) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes("unpublish", &[name.as_bytes(), version.as_bytes()]),
-            &auth,
-        ) {
+    async fn unpublish(
+        &mut self,
+        name: String,
+        version: String,
+        auth: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> Status {
+        let op = canonical_op_bytes("unpublish", &[name.as_bytes(), version.as_bytes()]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         let mut idx = 0usize;
@@ -602,6 +642,14 @@ impl SpaceRegistry {
                     ai += 1;
                 }
                 self.programs.remove(idx);
+                self.record_audit(
+                    ctx,
+                    "unpublish",
+                    name.as_bytes(),
+                    format!("version={version}"),
+                    &op,
+                    &auth,
+                );
                 return Status::Ok;
             }
             idx += 1;
@@ -670,11 +718,10 @@ impl SpaceRegistry {
         program_hash: Vec<u8>,
         blob: Vec<u8>,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes("register_meta", &[&program_hash, &blob]),
-            &auth,
-        ) {
+        let op = canonical_op_bytes("register_meta", &[&program_hash, &blob]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         let Some(program_hash) = bytes_to_32(&program_hash) else {
@@ -686,6 +733,14 @@ impl SpaceRegistry {
         if program_hash == role_authority_cutover_key() {
             return Status::Forbidden;
         }
+        self.record_audit(
+            ctx,
+            "register_meta",
+            &program_hash,
+            format!("{} bytes", blob.len()),
+            &op,
+            &auth,
+        );
         // Upsert: one point write, keyed by the program hash.
         self.metas
             .insert(&program_hash, &MetaRow { program_hash, blob });
@@ -755,16 +810,28 @@ impl SpaceRegistry {
         instance_name: String,
         blob: Vec<u8>,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes(
-                "register_extension_meta",
-                &[instance_name.as_bytes(), &blob],
-            ),
-            &auth,
-        ) {
+        let op = canonical_op_bytes(
+            "register_extension_meta",
+            &[instance_name.as_bytes(), &blob],
+        );
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
+        let detail = if blob.is_empty() {
+            "removed".into()
+        } else {
+            format!("{} bytes", blob.len())
+        };
+        self.record_audit(
+            ctx,
+            "register_extension_meta",
+            instance_name.as_bytes(),
+            detail,
+            &op,
+            &auth,
+        );
         // An empty blob removes the row; otherwise upsert. Both are one
         // point op, keyed by the instance name.
         let key = name_key(&instance_name);
@@ -804,25 +871,24 @@ impl SpaceRegistry {
         network_reachable: bool,
         sync_role: u8,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes(
-                "install",
-                &[
-                    instance_name.as_bytes(),
-                    program_name.as_bytes(),
-                    program_version.as_bytes(),
-                    &program_hash,
-                    &replication_id,
-                    &[consistency],
-                    &install_args,
-                    &install_payloads,
-                    &[network_reachable as u8],
-                    &[sync_role],
-                ],
-            ),
-            &auth,
-        ) {
+        let op = canonical_op_bytes(
+            "install",
+            &[
+                instance_name.as_bytes(),
+                program_name.as_bytes(),
+                program_version.as_bytes(),
+                &program_hash,
+                &replication_id,
+                &[consistency],
+                &install_args,
+                &install_payloads,
+                &[network_reachable as u8],
+                &[sync_role],
+            ],
+        );
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         // An empty instance name is the `agents`/`agent_names` pagers'
@@ -906,6 +972,14 @@ impl SpaceRegistry {
             }
         }
 
+        self.record_audit(
+            ctx,
+            "install",
+            instance_name.as_bytes(),
+            format!("program={program_name}@{program_version} consistency={consistency}"),
+            &op,
+            &auth,
+        );
         self.agents.insert(
             idx,
             AgentRow {
@@ -929,17 +1003,28 @@ impl SpaceRegistry {
     /// Tombstone an agent. Local data on each replica moves to
     /// trash on the host side; the registry just removes the row.
     #[msg(role = SpaceRegistryRole::Admin)]
-    async fn uninstall(&mut self, instance_name: String, auth: Vec<u8>) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes("uninstall", &[instance_name.as_bytes()]),
-            &auth,
-        ) {
+    async fn uninstall(
+        &mut self,
+        instance_name: String,
+        auth: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> Status {
+        let op = canonical_op_bytes("uninstall", &[instance_name.as_bytes()]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         let mut idx = 0usize;
         while idx < self.agents.len() {
             if self.agents[idx].instance_name == instance_name {
                 self.agents.remove(idx);
+                self.record_audit(
+                    ctx,
+                    "uninstall",
+                    instance_name.as_bytes(),
+                    String::new(),
+                    &op,
+                    &auth,
+                );
                 return Status::Ok;
             }
             idx += 1;
@@ -947,6 +1032,92 @@ impl SpaceRegistry {
         Status::NotFound
     }
 
+    /// Record where an installed agent's state was imported from
+    /// (`space agent-import`). The row is keyed by the agent's live
+    /// `replication_id`, so it describes exactly this installation, and is
+    /// write-once: a second record for the same id is
+    /// `Status::ReplicationIdReused`. The registry stores the archive's
+    /// claims as signed by the importing admin; verifying the archive
+    /// itself is the importer's job.
+    #[allow(clippy::too_many_arguments)]
+    #[msg(role = SpaceRegistryRole::Admin)]
+    async fn record_provenance(
+        &mut self,
+        instance_name: String,
+        source_space: Vec<u8>,
+        source_instance: String,
+        source_replication_id: Vec<u8>,
+        archive_digest: Vec<u8>,
+        exporter: Vec<u8>,
+        auth: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> Status {
+        let op = canonical_op_bytes(
+            "record_provenance",
+            &[
+                instance_name.as_bytes(),
+                &source_space,
+                source_instance.as_bytes(),
+                &source_replication_id,
+                &archive_digest,
+                &exporter,
+            ],
+        );
+        if !self.authorize_op(&op, &auth) {
+            return Status::Forbidden;
+        }
+        let (Some(source_space), Some(source_replication_id), Some(archive_digest)) = (
+            bytes_to_32(&source_space),
+            bytes_to_32(&source_replication_id),
+            bytes_to_32(&archive_digest),
+        ) else {
+            return Status::BadHash;
+        };
+        let Some(replication_id) = self
+            .agents
+            .iter()
+            .find(|a| a.instance_name == instance_name)
+            .map(|a| a.replication_id)
+        else {
+            return Status::NotFound;
+        };
+        if self.provenance.get(&replication_id).is_some() {
+            return Status::ReplicationIdReused;
+        }
+        self.record_audit(
+            ctx,
+            "record_provenance",
+            instance_name.as_bytes(),
+            format!("source={source_instance}"),
+            &op,
+            &auth,
+        );
+        self.provenance.insert(
+            &replication_id,
+            &ProvenanceRow {
+                instance_name,
+                replication_id,
+                source_space,
+                source_instance,
+                source_replication_id,
+                archive_digest,
+                exporter,
+            },
+        );
+        Status::Ok
+    }
+
+    /// The import provenance of the installed `instance_name`. `None`
+    /// when no such agent is installed or it was installed directly.
+    #[msg]
+    async fn provenance(&self, instance_name: String) -> Option<ProvenanceRow> {
+        let agent = self
+            .agents
+            .iter()
+            .find(|a| a.instance_name == instance_name)?;
+        self.provenance.get(&agent.replication_id)
+    }
+
     /// Repoint an agent at a different program version. State
     /// is preserved (same `replication_id`, same redb); replicas
     /// restart their agent thread on the next sync.
@@ -967,20 +1138,19 @@ impl SpaceRegistry {
         new_program_hash: Vec<u8>,
         from_hash: Vec<u8>,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes(
-                "upgrade",
-                &[
-                    instance_name.as_bytes(),
-                    new_program_name.as_bytes(),
-                    new_program_version.as_bytes(),
-                    &new_program_hash,
-                    &from_hash,
-                ],
-            ),
-            &auth,
-        ) {
+        let op = canonical_op_bytes(
+            "upgrade",
+            &[
+                instance_name.as_bytes(),
+                new_program_name.as_bytes(),
+                new_program_version.as_bytes(),
+                &new_program_hash,
+                &from_hash,
+            ],
+        );
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         let Some(new_program_hash) = bytes_to_32(&new_program_hash) else {
@@ -1023,6 +1193,14 @@ impl SpaceRegistry {
                         return Status::CrdtOptInRequired;
                     }
                 }
+                self.record_audit(
+                    ctx,
+                    "upgrade",
+                    instance_name.as_bytes(),
+                    format!("program={new_program_name}@{new_program_version}"),
+                    &op,
+                    &auth,
+                );
                 self.agents[idx].program_name = new_program_name;
                 self.agents[idx].program_version = new_program_version;
                 self.agents[idx].program_hash = new_program_hash;
@@ -1196,18 +1374,44 @@ impl SpaceRegistry {
 
     // ── Members ────────────────────────────────────────────────
 
-    /// Add a Node member. Idempotent in `prefix` — re-adding
-    /// updates `peer_id` and `role`. `role` is
-    /// `NODE_ROLE_VOTER` or `NODE_ROLE_OBSERVER`.
+    /// Add a Node member. Idempotent in `(prefix, peer_id)` —
+    /// re-adding the same node updates its `role`. `role` is
+    /// `NODE_ROLE_VOTER`, `NODE_ROLE_OBSERVER` or `NODE_ROLE_WITNESS`.
+    ///
+    /// Returns `Status::BadPrefix` when `prefix` doesn't fit in a u16
+    /// and `Status::PrefixCollision` when the prefix is already
+    /// enrolled for a different peer — remove that node first.
     #[msg(role = SpaceRegistryRole::Admin)]
-    async fn add_node(&mut self, prefix: u32, peer_id: Vec<u8>, role: u8, auth: Vec<u8>) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes("add_node", &[&prefix.to_le_bytes(), &peer_id, &[role]]),
-            &auth,
-        ) {
+    async fn add_node(
+        &mut self,
+        prefix: u32,
+        peer_id: Vec<u8>,
+        role: u8,
+        auth: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> Status {
+        let op = canonical_op_bytes("add_node", &[&prefix.to_le_bytes(), &peer_id, &[role]]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
-        let prefix = prefix as u16;
+        let Ok(prefix) = u16::try_from(prefix) else {
+            return Status::BadPrefix;
+        };
+        if self
+            .nodes
+            .get(&prefix)
+            .is_some_and(|existing| existing.key != peer_id)
+        {
+            return Status::PrefixCollision;
+        }
+        self.record_audit(
+            ctx,
+            "add_node",
+            &peer_id,
+            format!("prefix={prefix} role={role}"),
+            &op,
+            &auth,
+        );
         // Idempotent upsert keyed by the node prefix.
         self.nodes.insert(
             &prefix,
@@ -1224,14 +1428,24 @@ impl SpaceRegistry {
     }
 
     #[msg(role = SpaceRegistryRole::Admin)]
-    async fn remove_node(&mut self, prefix: u32, auth: Vec<u8>) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes("remove_node", &[&prefix.to_le_bytes()]),
-            &auth,
-        ) {
+    async fn remove_node(&mut self, prefix: u32, auth: Vec<u8>, ctx: &mut Context<Self>) -> Status {
+        let op = canonical_op_bytes("remove_node", &[&prefix.to_le_bytes()]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
-        if self.nodes.remove(&(prefix as u16)) {
+        let Ok(prefix) = u16::try_from(prefix) else {
+            return Status::NotFound;
+        };
+        if let Some(node) = self.nodes.get(&prefix) {
+            self.nodes.remove(&prefix);
+            self.record_audit(
+                ctx,
+                "remove_node",
+                &node.key,
+                format!("prefix={prefix}"),
+                &op,
+                &auth,
+            );
             Status::Ok
         } else {
             Status::NotFound
@@ -1250,11 +1464,10 @@ impl SpaceRegistry {
         proof_kind: u8,
         proof_data: Vec<u8>,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes("add_identity", &[&public_key, &[proof_kind], &proof_data]),
-            &auth,
-        ) {
+        let op = canonical_op_bytes("add_identity", &[&public_key, &[proof_kind], &proof_data]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         // An empty key is not an identity — and defense in depth for
@@ -1262,6 +1475,7 @@ impl SpaceRegistry {
         if public_key.is_empty() {
             return Status::BadHash;
         }
+        self.record_audit(ctx, "add_identity", &public_key, String::new(), &op, &auth);
         // Idempotent upsert keyed by the identity public key.
         self.identities.insert(
             &identity_key(&public_key),
@@ -1278,14 +1492,25 @@ impl SpaceRegistry {
     }
 
     #[msg(role = SpaceRegistryRole::Admin)]
-    async fn remove_identity(&mut self, public_key: Vec<u8>, auth: Vec<u8>) -> Status {
-        if !self.authorize_op(
-            &canonical_op_bytes("remove_identity", &[&public_key]),
-            &auth,
-        ) {
+    async fn remove_identity(
+        &mut self,
+        public_key: Vec<u8>,
+        auth: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> Status {
+        let op = canonical_op_bytes("remove_identity", &[&public_key]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         if self.identities.remove(&identity_key(&public_key)) {
+            self.record_audit(
+                ctx,
+                "remove_identity",
+                &public_key,
+                String::new(),
+                &op,
+                &auth,
+            );
             Status::Ok
         } else {
             Status::NotFound
@@ -1383,7 +1608,8 @@ impl SpaceRegistry {
     /// Raft-join admission probe: the role of the NODE member enrolled at
     /// `prefix`, encoded `role + 1` so the byte is self-describing —
     /// `0` = not enrolled, `1` = VOTER ([`NODE_ROLE_VOTER`]), `2` =
-    /// OBSERVER ([`NODE_ROLE_OBSERVER`]). The Raft leader's host calls this
+    /// OBSERVER ([`NODE_ROLE_OBSERVER`]), `3` = WITNESS
+    /// ([`NODE_ROLE_WITNESS`]). The Raft leader's host calls this
     /// (as `Caller::System`) before admitting a `RaftJoinReq`, so a peer
     /// that an admin never enrolled cannot make itself a voter. An ungated
     /// read — the answer is non-secret membership metadata, and enrollment
@@ -1413,14 +1639,13 @@ impl SpaceRegistry {
         role: u8,
         epoch: u64,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
         if self.role_authority_cutover_id().is_some() {
             return Status::Forbidden;
         }
-        if !self.authorize_op(
-            &canonical_op_bytes("grant_role", &[&peer_id, &[role], &epoch.to_le_bytes()]),
-            &auth,
-        ) {
+        let op = canonical_op_bytes("grant_role", &[&peer_id, &[role], &epoch.to_le_bytes()]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         if peer_id.is_empty() {
@@ -1429,6 +1654,14 @@ impl SpaceRegistry {
         let Some((grantor, _)) = unpack_auth(&auth) else {
             return Status::Forbidden;
         };
+        self.record_audit(
+            ctx,
+            "grant_role",
+            &peer_id,
+            format!("role={} epoch={epoch}", auth_role_name(role)),
+            &op,
+            &auth,
+        );
         self.store_role_grant(peer_id, role, epoch, grantor.to_vec());
         Status::Ok
     }
@@ -1445,17 +1678,18 @@ impl SpaceRegistry {
         epoch: u64,
         authority_replication_id: Vec<u8>,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
         let Some(authority) = bytes_to_32(&authority_replication_id) else {
             return Status::BadHash;
         };
-        let canonical = canonical_op_bytes(
+        let op = canonical_op_bytes(
             "grant_role_v2",
             &[&peer_id, &[role], &epoch.to_le_bytes(), &authority],
         );
         if peer_id.is_empty()
             || self.role_authority_cutover_id() != Some(authority)
-            || !self.authorize_root_op(&canonical, &auth)
+            || !self.authorize_root_op(&op, &auth)
         {
             return Status::Forbidden;
         }
@@ -1466,6 +1700,14 @@ impl SpaceRegistry {
         if row.role == role && row.epoch == epoch && row.grantor == self.root_bytes() {
             self.bind_authority_grant(authority, &row);
         }
+        self.record_audit(
+            ctx,
+            "grant_role_v2",
+            &row.peer_id,
+            format!("role={} epoch={epoch}", auth_role_name(role)),
+            &op,
+            &auth,
+        );
         Status::Ok
     }
 
@@ -1477,17 +1719,29 @@ impl SpaceRegistry {
     /// Always `Status::Ok`: revoking sets a floor even with no live grant
     /// (it blocks a future replayed grant).
     #[msg(role = SpaceRegistryRole::Admin)]
-    async fn revoke_role(&mut self, peer_id: Vec<u8>, epoch: u64, auth: Vec<u8>) -> Status {
+    async fn revoke_role(
+        &mut self,
+        peer_id: Vec<u8>,
+        epoch: u64,
+        auth: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> Status {
         if self.role_authority_cutover_id().is_some() {
             return Status::Forbidden;
         }
-        if !self.authorize_op(
-            &canonical_op_bytes("revoke_role", &[&peer_id, &epoch.to_le_bytes()]),
-            &auth,
-        ) {
+        let op = canonical_op_bytes("revoke_role", &[&peer_id, &epoch.to_le_bytes()]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         self.raise_revoke_floor(&peer_id, epoch);
+        self.record_audit(
+            ctx,
+            "revoke_role",
+            &peer_id,
+            format!("epoch={epoch}"),
+            &op,
+            &auth,
+        );
         Status::Ok
     }
 
@@ -1498,21 +1752,162 @@ impl SpaceRegistry {
         epoch: u64,
         authority_replication_id: Vec<u8>,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
         let Some(authority) = bytes_to_32(&authority_replication_id) else {
             return Status::BadHash;
         };
-        let canonical = canonical_op_bytes(
+        let op = canonical_op_bytes(
             "revoke_role_v2",
             &[&peer_id, &epoch.to_le_bytes(), &authority],
         );
         if peer_id.is_empty()
             || self.role_authority_cutover_id() != Some(authority)
-            || !self.authorize_root_op(&canonical, &auth)
+            || !self.authorize_root_op(&op, &auth)
         {
             return Status::Forbidden;
         }
         self.raise_revoke_floor(&peer_id, epoch);
+        self.record_audit(
+            ctx,
+            "revoke_role_v2",
+            &peer_id,
+            format!("epoch={epoch}"),
+            &op,
+            &auth,
+        );
+        Status::Ok
+    }
+
+    /// Hand everything `old_peer` holds to `new_peer` and revoke
+    /// `old_peer`, in one op — `vosx whoami rotate`. Signed by the old
+    /// key (`auth`) and by the new one (`new_sig`, over the same
+    /// canonical), so nobody can rotate a key they don't hold onto one
+    /// they don't hold either. Ungated like `redeem_invite`: the holder
+    /// may be any role, and both signatures are checked here.
+    ///
+    /// The successor's grants keep the *original* grantors, so the chain
+    /// never runs through the retired key; grants `old_peer` delegated
+    /// are re-pointed to `new_peer` so revoking it voids nothing but
+    /// itself. When `old_peer` is the root, `new_peer` becomes the root.
+    /// `epoch` must be above both peers' [`peer_epoch`](Self::peer_epoch).
+    /// Refused past the role-authority cutover, whose root is immutable.
+    #[msg]
+    async fn rotate_identity(
+        &mut self,
+        old_peer: Vec<u8>,
+        new_peer: Vec<u8>,
+        epoch: u64,
+        new_sig: Vec<u8>,
+        auth: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> Status {
+        if self.role_authority_cutover_id().is_some() {
+            return Status::Forbidden;
+        }
+        if old_peer.is_empty() || new_peer.is_empty() || old_peer == new_peer {
+            return Status::BadHash;
+        }
+        let op = canonical_op_bytes(
+            "rotate_identity",
+            &[&old_peer, &new_peer, &epoch.to_le_bytes()],
+        );
+        let Some((signer, sig)) = unpack_auth(&auth) else {
+            return Status::Forbidden;
+        };
+        let Some(new_sig) = bytes_to_64(&new_sig) else {
+            return Status::Forbidden;
+        };
+        if signer != old_peer.as_slice()
+            || !verify_op_sig(&old_peer, &op, &sig)
+            || !verify_op_sig(&new_peer, &op, &new_sig)
+        {
+            return Status::Forbidden;
+        }
+        if epoch <= self.peer_epoch_of(&old_peer) || epoch <= self.peer_epoch_of(&new_peer) {
+            return Status::Forbidden;
+        }
+
+        let was_root = self.root_bytes() == old_peer;
+        if !was_root
+            && self.effective_role(&old_peer) != AUTH_ROLE_NONE
+            && let Some(grant) = self.auth_grants.get(&peer_key(&old_peer))
+        {
+            self.store_role_grant(new_peer.clone(), grant.role, epoch, grant.grantor);
+        }
+        let delegated: Vec<AuthGrantRow> = self
+            .auth_grants
+            .iter_from(&[0; 32])
+            .map(|(_, grant)| grant)
+            .filter(|grant| grant.grantor == old_peer && grant.peer_id != old_peer)
+            .collect();
+        for grant in delegated {
+            self.auth_grants.insert(
+                &peer_key(&grant.peer_id),
+                &AuthGrantRow {
+                    grantor: new_peer.clone(),
+                    ..grant
+                },
+            );
+        }
+
+        // Actor-local grants: carry the old key's own rows over (each at
+        // a fresh epoch for the successor), revoke them at their current
+        // epoch, and re-point the rows it granted.
+        let acls: Vec<ActorAclRow> = self
+            .actor_acls
+            .iter_from(&[0; 32])
+            .map(|(_, acl)| acl)
+            .filter(|acl| acl.peer_id == old_peer || acl.grantor == old_peer)
+            .collect();
+        for acl in acls {
+            if acl.peer_id == old_peer {
+                if self
+                    .effective_actor_role(&old_peer, &acl.agent_name)
+                    .is_some()
+                {
+                    let key = acl_key(&new_peer, &acl.agent_name);
+                    let current = self.actor_acls.get(&key).map(|row| row.epoch).unwrap_or(0);
+                    let floor = self.actor_revoke_floor(&new_peer, &acl.agent_name);
+                    self.actor_acls.insert(
+                        &key,
+                        &ActorAclRow {
+                            peer_id: new_peer.clone(),
+                            agent_name: acl.agent_name.clone(),
+                            role: acl.role,
+                            epoch: current.max(floor) + 1,
+                            grantor: if acl.grantor == old_peer {
+                                new_peer.clone()
+                            } else {
+                                acl.grantor.clone()
+                            },
+                        },
+                    );
+                }
+                self.raise_actor_revoke_floor(&old_peer, &acl.agent_name, acl.epoch);
+            } else {
+                self.actor_acls.insert(
+                    &acl_key(&acl.peer_id, &acl.agent_name),
+                    &ActorAclRow {
+                        grantor: new_peer.clone(),
+                        ..acl
+                    },
+                );
+            }
+        }
+
+        if was_root {
+            self.root.set(&new_peer);
+        }
+        self.raise_revoke_floor(&old_peer, epoch);
+        self.record_audit(
+            ctx,
+            "rotate_identity",
+            &old_peer,
+            format!("to={} epoch={epoch}", hex_lower(&new_peer)),
+            &op,
+            &auth,
+        );
         Status::Ok
     }
 
@@ -1534,12 +1929,7 @@ impl SpaceRegistry {
     /// metadata is non-secret).
     #[msg]
     async fn peer_epoch(&self, peer_id: Vec<u8>) -> u64 {
-        let grant_hw = self
-            .auth_grants
-            .get(&peer_key(&peer_id))
-            .map(|g| g.epoch)
-            .unwrap_or(0);
-        grant_hw.max(self.revoke_floor(&peer_id))
+        self.peer_epoch_of(&peer_id)
     }
 
     /// One page of grants, resolved to *effective* roles — for
@@ -1635,6 +2025,7 @@ impl SpaceRegistry {
         redeem_sig: Vec<u8>,
         node_sig: Vec<u8>,
         authority_attestation: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
         let Some(token_pub_key) = bytes_to_32(&token_pub) else {
             return Status::BadHash;
@@ -1750,6 +2141,20 @@ impl SpaceRegistry {
         if grant.role == role && grant.epoch == epoch && grant.grantor == admin_peer_id {
             self.bind_authority_grant(authority_replication_id, &grant);
         }
+        // The admin's invite signature is what authorized the grant; the
+        // redeeming node is the target.
+        self.append_audit(
+            ctx,
+            AuditRow::new(
+                None,
+                admin_peer_id,
+                "redeem_invite".into(),
+                grant.peer_id,
+                format!("role={} expires_at={expires_at}", auth_role_name(role)),
+                invite_canon,
+                admin_sig.to_vec(),
+            ),
+        );
         Status::Ok
     }
 
@@ -1761,8 +2166,14 @@ impl SpaceRegistry {
     /// row). Existing already-granted roles are NOT clawed back here —
     /// that is `revoke_role`'s job (decision 6).
     #[msg(role = SpaceRegistryRole::Admin)]
-    async fn revoke_invite(&mut self, token_pub: Vec<u8>, auth: Vec<u8>) -> Status {
-        if !self.authorize_op(&canonical_op_bytes("revoke_invite", &[&token_pub]), &auth) {
+    async fn revoke_invite(
+        &mut self,
+        token_pub: Vec<u8>,
+        auth: Vec<u8>,
+        ctx: &mut Context<Self>,
+    ) -> Status {
+        let op = canonical_op_bytes("revoke_invite", &[&token_pub]);
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         let Some(token_pub_key) = bytes_to_32(&token_pub) else {
@@ -1779,6 +2190,7 @@ impl SpaceRegistry {
             row.revoked = true;
             self.invites.insert(&token_pub_key, &row);
         }
+        self.record_audit(ctx, "revoke_invite", &token_pub, String::new(), &op, &auth);
         Status::Ok
     }
 
@@ -1830,22 +2242,21 @@ impl SpaceRegistry {
         role: u8,
         epoch: u64,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
         if self.role_authority_cutover_id().is_some() {
             return Status::Forbidden;
         }
-        if !self.authorize_op(
-            &canonical_op_bytes(
-                "grant_actor_role",
-                &[
-                    &peer_id,
-                    agent_name.as_bytes(),
-                    &[role],
-                    &epoch.to_le_bytes(),
-                ],
-            ),
-            &auth,
-        ) {
+        let op = canonical_op_bytes(
+            "grant_actor_role",
+            &[
+                &peer_id,
+                agent_name.as_bytes(),
+                &[role],
+                &epoch.to_le_bytes(),
+            ],
+        );
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         if peer_id.is_empty() || agent_name.is_empty() {
@@ -1871,6 +2282,14 @@ impl SpaceRegistry {
             ),
             None => true,
         };
+        self.record_audit(
+            ctx,
+            "grant_actor_role",
+            &peer_id,
+            format!("agent={agent_name} role={role} epoch={epoch}"),
+            &op,
+            &auth,
+        );
         if supersedes {
             self.actor_acls.insert(
                 &key,
@@ -1897,20 +2316,27 @@ impl SpaceRegistry {
         agent_name: String,
         epoch: u64,
         auth: Vec<u8>,
+        ctx: &mut Context<Self>,
     ) -> Status {
         if self.role_authority_cutover_id().is_some() {
             return Status::Forbidden;
         }
-        if !self.authorize_op(
-            &canonical_op_bytes(
-                "revoke_actor_role",
-                &[&peer_id, agent_name.as_bytes(), &epoch.to_le_bytes()],
-            ),
-            &auth,
-        ) {
+        let op = canonical_op_bytes(
+            "revoke_actor_role",
+            &[&peer_id, agent_name.as_bytes(), &epoch.to_le_bytes()],
+        );
+        if !self.authorize_op(&op, &auth) {
             return Status::Forbidden;
         }
         self.raise_actor_revoke_floor(&peer_id, &agent_name, epoch);
+        self.record_audit(
+            ctx,
+            "revoke_actor_role",
+            &peer_id,
+            format!("agent={agent_name} epoch={epoch}"),
+            &op,
+            &auth,
+        );
         Status::Ok
     }
 
@@ -1999,6 +2425,19 @@ impl SpaceRegistry {
             next_agent,
         }
     }
+
+    // ── Audit log ───────────────────────────────────────────────
+
+    /// One page of the admin audit log, from slot `from` in chain order.
+    /// Continue from the last row's `slot + 1` while `more` is set.
+    /// `budget` caps the page. An ungated read: rows carry only what the
+    /// signed ops themselves already replicate.
+    #[msg]
+    async fn audit(&self, from: u64, budget: u32) -> AuditPage {
+        let mut it = (from..self.audit_log.len()).filter_map(|slot| self.audit_log.get(slot));
+        let (rows, more) = fill_page(&mut it, page_rows(budget), PAGE_BYTE_BUDGET);
+        AuditPage { rows, more }
+    }
 }
 
 // ── Signed-op authorization ────────────────────────────────────────
@@ -2123,6 +2562,59 @@ impl SpaceRegistry {
         !root.is_empty() && signer == root && verify_op_sig(signer, canonical, &signature)
     }
 
+    /// Log an accepted mutation authorized by the `auth` blob it carried
+    /// over the signed bytes `op`. Call only once the handler is committed
+    /// to `Status::Ok`.
+    fn record_audit(
+        &mut self,
+        ctx: &Context<Self>,
+        action: &str,
+        target: &[u8],
+        detail: String,
+        op: &[u8],
+        auth: &[u8],
+    ) {
+        let Some((signer, signature)) = unpack_auth(auth) else {
+            return;
+        };
+        self.append_audit(
+            ctx,
+            AuditRow::new(
+                None,
+                signer.to_vec(),
+                action.into(),
+                target.to_vec(),
+                detail,
+                op.to_vec(),
+                signature.to_vec(),
+            ),
+        );
+    }
+
+    /// Stamp `row` with the dispatch's admission time and append it to
+    /// `audit_log`, chained to the current tail, unless this exact
+    /// `(action, target, signature)` is already logged.
+    fn append_audit(&mut self, ctx: &Context<Self>, row: AuditRow) {
+        if !self
+            .audited
+            .insert(&audit_key(&row.action, &row.target, &row.signature))
+        {
+            return;
+        }
+        let tail = self
+            .audit_log
+            .len()
+            .checked_sub(1)
+            .and_then(|last| self.audit_log.get(last));
+        self.audit_log.push(
+            &AuditRow {
+                at_ms: ctx.admitted_at_ms(),
+                ..row
+            }
+            .chained_after(tail.as_ref()),
+        );
+    }
+
     /// The anchored genesis root PeerId, or empty before genesis.
     /// A point read; the dispatch read-cache makes repeated calls
     /// (one per delegation-chain hop) cost a single row.
@@ -2135,6 +2627,17 @@ impl SpaceRegistry {
         self.space_id.get().unwrap_or_default()
     }
 
+    /// The higher of `peer_id`'s stored grant epoch and its revoke
+    /// high-water — see [`peer_epoch`](SpaceRegistry::peer_epoch).
+    fn peer_epoch_of(&self, peer_id: &[u8]) -> u64 {
+        let grant_hw = self
+            .auth_grants
+            .get(&peer_key(peer_id))
+            .map(|g| g.epoch)
+            .unwrap_or(0);
+        grant_hw.max(self.revoke_floor(peer_id))
+    }
+
     /// Grow-only revoke high-water for `peer_id`, or 0 if never revoked.
     fn revoke_floor(&self, peer_id: &[u8]) -> u64 {
         self.revoke_epochs.get(&peer_key(peer_id)).unwrap_or(0)
@@ -2269,6 +2772,11 @@ fn bytes_to_32(b: &[u8]) -> Option<[u8; 32]> {
     Some(out)
 }
 
+/// Lowercase hex, for audit-row details that name a second peer.
+fn hex_lower(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
 fn bytes_to_64(b: &[u8]) -> Option<[u8; OP_SIG_LEN]> {
     if b.len() != OP_SIG_LEN {
         return None;
@@ -2327,6 +2835,22 @@ fn peer_key(peer_id: &[u8]) -> [u8; 32] {
     vos::crypto::blake2b_hash::<32>(b"space-registry/peer-key/v1", &[peer_id])
 }
 
+/// Dedupe key for an audit row. The signature alone doesn't identify an
+/// op — one invite signature authorizes every redemption of its token —
+/// so the action and target ride along, each length-prefixed.
+fn audit_key(action: &str, target: &[u8], signature: &[u8]) -> [u8; 32] {
+    vos::crypto::blake2b_hash::<32>(
+        b"space-registry/audit-key/v1",
+        &[
+            &(action.len() as u32).to_be_bytes(),
+            action.as_bytes(),
+            &(target.len() as u32).to_be_bytes(),
+            target,
+            signature,
+        ],
+    )
+}
+
 /// Reserved rows in the existing metadata keyspace avoid changing the
 /// archived `SpaceRegistry` struct while still making cutover and per-grant
 /// bindings guest-owned durable state. Domain-separated keys cannot collide
@@ -3212,6 +3736,73 @@ mod tests {
         );
     }
 
+    fn record_provenance(r: &mut SpaceRegistry, name: &str, source: &[u8]) -> Status {
+        let (space, digest, exporter) = ([1u8; 32], [2u8; 32], alloc::vec![3u8; 38]);
+        dispatch(
+            r,
+            RecordProvenance {
+                instance_name: String::from(name),
+                source_space: space.to_vec(),
+                source_instance: String::from("origin"),
+                source_replication_id: source.to_vec(),
+                archive_digest: digest.to_vec(),
+                exporter: exporter.clone(),
+                auth: root_auth(
+                    "record_provenance",
+                    &[
+                        name.as_bytes(),
+                        &space,
+                        b"origin",
+                        source,
+                        &digest,
+                        &exporter,
+                    ],
+                ),
+            },
+        )
+    }
+
+    #[test]
+    fn provenance_is_write_once_per_installation_and_survives_uninstall() {
+        let mut r = registry();
+        let source = [9u8; 32];
+        assert_eq!(
+            record_provenance(&mut r, "imported", &source),
+            Status::NotFound
+        );
+        assert_eq!(install_at(&mut r, "imported", 2 /* Crdt */), Status::Ok);
+        assert_eq!(record_provenance(&mut r, "imported", &source), Status::Ok);
+        assert_eq!(
+            record_provenance(&mut r, "imported", &[8u8; 32]),
+            Status::ReplicationIdReused,
+        );
+        let row = dispatch(
+            &mut r,
+            Provenance {
+                instance_name: String::from("imported"),
+            },
+        )
+        .expect("provenance recorded");
+        assert_eq!(row.source_replication_id, source);
+        assert_eq!(row.source_instance, "origin");
+
+        // A reinstall under the same name is a new installation with a
+        // fresh replication id: it has no provenance of its own, and the
+        // imported one is retained under the old id.
+        assert_eq!(uninstall(&mut r, "imported"), Status::Ok);
+        assert_eq!(install_at(&mut r, "imported", 2 /* Crdt */), Status::Ok);
+        assert!(
+            dispatch(
+                &mut r,
+                Provenance {
+                    instance_name: String::from("imported"),
+                },
+            )
+            .is_none()
+        );
+        assert!(r.provenance.get(&row.replication_id).is_some());
+    }
+
     // ── Signed registry ops ────────────────────────────────
 
     use ed25519_dalek::{Signer, SigningKey};
@@ -3538,6 +4129,177 @@ mod tests {
         );
     }
 
+    // ── rotate_identity ──────────────────────────────────────────
+
+    /// `rotate_identity` from `old` to `new`, signed by both, at the
+    /// epoch the CLI picks.
+    fn rotate(r: &mut SpaceRegistry, old: &SigningKey, new: &SigningKey) -> Status {
+        let old_peer = peer_id_for(&old.verifying_key().to_bytes());
+        let new_peer = peer_id_for(&new.verifying_key().to_bytes());
+        let epoch = dispatch(
+            r,
+            PeerEpoch {
+                peer_id: old_peer.clone(),
+            },
+        )
+        .max(dispatch(
+            r,
+            PeerEpoch {
+                peer_id: new_peer.clone(),
+            },
+        )) + 1;
+        let canonical = canonical_op_bytes(
+            "rotate_identity",
+            &[&old_peer, &new_peer, &epoch.to_le_bytes()],
+        );
+        dispatch(
+            r,
+            RotateIdentity {
+                old_peer: old_peer.clone(),
+                new_peer,
+                epoch,
+                new_sig: new.sign(&canonical).to_bytes().to_vec(),
+                auth: pack_auth(&old_peer, &old.sign(&canonical).to_bytes()),
+            },
+        )
+    }
+
+    fn peer_role(r: &mut SpaceRegistry, peer: &[u8]) -> u8 {
+        dispatch(
+            r,
+            PeerRole {
+                peer_id: peer.to_vec(),
+            },
+        )
+    }
+
+    #[test]
+    fn rotation_moves_grants_off_the_old_key_and_revokes_it() {
+        // root → A (ADMIN) → B (DEVELOPER), plus an actor-local grant
+        // for A. Rotating A to A2 must leave nothing chained through A,
+        // so A's revocation voids only A.
+        let mut r = registry();
+        let a_key = SigningKey::from_bytes(&[31u8; 32]);
+        let a2_key = SigningKey::from_bytes(&[32u8; 32]);
+        let a_peer = peer_id_for(&a_key.verifying_key().to_bytes());
+        let a2_peer = peer_id_for(&a2_key.verifying_key().to_bytes());
+        let b_peer = alloc::vec![0xbb; 4];
+        assert_eq!(grant_space(&mut r, &a_peer, AUTH_ROLE_ADMIN), Status::Ok);
+        assert_eq!(
+            grant_space_signed(&mut r, &a_key, &b_peer, AUTH_ROLE_DEVELOPER),
+            Status::Ok,
+        );
+        assert_eq!(grant_actor(&mut r, &a_peer, "counter", 2), Status::Ok);
+
+        assert_eq!(rotate(&mut r, &a_key, &a2_key), Status::Ok);
+        assert_eq!(peer_role(&mut r, &a2_peer), AUTH_ROLE_ADMIN);
+        assert_eq!(
+            peer_role(&mut r, &a_peer),
+            AUTH_ROLE_NONE,
+            "old key revoked"
+        );
+        assert_eq!(
+            peer_role(&mut r, &b_peer),
+            AUTH_ROLE_DEVELOPER,
+            "A's delegation moved to A2 instead of dying with A",
+        );
+        let grants = dispatch(
+            &mut r,
+            AuthGrants {
+                after_peer: Vec::new(),
+                budget: 0,
+            },
+        )
+        .grants;
+        let a2_row = grants.iter().find(|g| g.peer_id == a2_peer).unwrap();
+        assert_eq!(a2_row.grantor, root_peer_id(), "A2 keeps A's grantor");
+        let actor = |r: &mut SpaceRegistry, peer: &[u8]| {
+            dispatch(
+                r,
+                ActorRole {
+                    peer_id: peer.to_vec(),
+                    agent_name: String::from("counter"),
+                },
+            )
+        };
+        assert_eq!(actor(&mut r, &a2_peer), 2);
+        assert_eq!(actor(&mut r, &a_peer), AUTH_ROLE_NONE);
+
+        // The retired key can no longer author anything.
+        let c_peer = alloc::vec![0xcc; 4];
+        assert_eq!(
+            grant_space_signed(&mut r, &a_key, &c_peer, AUTH_ROLE_READONLY),
+            Status::Forbidden,
+        );
+        assert_eq!(
+            grant_space_signed(&mut r, &a2_key, &c_peer, AUTH_ROLE_READONLY),
+            Status::Ok,
+        );
+    }
+
+    #[test]
+    fn root_rotation_re_roots_and_retires_the_old_root() {
+        let mut r = registry();
+        let x_peer = alloc::vec![0x77; 4];
+        assert_eq!(grant_space(&mut r, &x_peer, AUTH_ROLE_ADMIN), Status::Ok);
+        let successor = SigningKey::from_bytes(&[33u8; 32]);
+        let successor_peer = peer_id_for(&successor.verifying_key().to_bytes());
+
+        assert_eq!(rotate(&mut r, &root_key(), &successor), Status::Ok);
+        assert_eq!(dispatch(&mut r, Root), successor_peer);
+        assert_eq!(
+            peer_role(&mut r, &x_peer),
+            AUTH_ROLE_ADMIN,
+            "root-granted peers now chain to the successor",
+        );
+        let y_peer = alloc::vec![0x78; 4];
+        assert_eq!(
+            grant_space(&mut r, &y_peer, AUTH_ROLE_READONLY),
+            Status::Forbidden,
+            "the old root is no longer root",
+        );
+        assert_eq!(
+            grant_space_signed(&mut r, &successor, &y_peer, AUTH_ROLE_READONLY),
+            Status::Ok,
+        );
+    }
+
+    #[test]
+    fn rotation_needs_both_keys_and_the_legacy_authority() {
+        let mut r = registry();
+        let a_key = SigningKey::from_bytes(&[34u8; 32]);
+        let a_peer = peer_id_for(&a_key.verifying_key().to_bytes());
+        let b_key = SigningKey::from_bytes(&[35u8; 32]);
+        let b_peer = peer_id_for(&b_key.verifying_key().to_bytes());
+        let thief = SigningKey::from_bytes(&[36u8; 32]);
+        assert_eq!(grant_space(&mut r, &a_peer, AUTH_ROLE_ADMIN), Status::Ok);
+        let epoch = 2u64;
+        let canonical =
+            canonical_op_bytes("rotate_identity", &[&a_peer, &b_peer, &epoch.to_le_bytes()]);
+        assert_eq!(
+            dispatch(
+                &mut r,
+                RotateIdentity {
+                    old_peer: a_peer.clone(),
+                    new_peer: b_peer.clone(),
+                    epoch,
+                    new_sig: thief.sign(&canonical).to_bytes().to_vec(),
+                    auth: pack_auth(&a_peer, &a_key.sign(&canonical).to_bytes()),
+                },
+            ),
+            Status::Forbidden,
+            "the successor must prove it holds its key",
+        );
+        assert_eq!(peer_role(&mut r, &a_peer), AUTH_ROLE_ADMIN);
+
+        let mut v2 = authority_registry();
+        assert_eq!(
+            rotate(&mut v2, &root_key(), &b_key),
+            Status::Forbidden,
+            "the canonical authority's root is immutable",
+        );
+    }
+
     #[test]
     fn peer_id_pubkey_extraction_round_trips() {
         let pk = root_key().verifying_key().to_bytes();
@@ -3864,6 +4626,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn witness_enrollment_reports_its_own_node_role() {
+        // Witnesses are admitted to Raft groups alongside voters, so the
+        // join probe must tell them apart from both voters and observers.
+        let mut r = registry();
+        let key = SigningKey::from_bytes(&[31u8; 32]);
+        let node = peer_id_for(&key.verifying_key().to_bytes());
+        let ok = root_auth(
+            "add_node",
+            &[&6u32.to_le_bytes(), &node, &[NODE_ROLE_WITNESS]],
+        );
+        assert_eq!(
+            dispatch(
+                &mut r,
+                AddNode {
+                    prefix: 6,
+                    peer_id: node,
+                    role: NODE_ROLE_WITNESS,
+                    auth: ok,
+                },
+            ),
+            Status::Ok,
+        );
+        assert_eq!(
+            dispatch(&mut r, NodeRole { prefix: 6 }),
+            NODE_ROLE_WITNESS + 1
+        );
+    }
+
+    #[test]
+    fn add_node_rejects_a_prefix_held_by_another_peer() {
+        let mut r = registry();
+        let first = peer_id_for(
+            &SigningKey::from_bytes(&[41u8; 32])
+                .verifying_key()
+                .to_bytes(),
+        );
+        let second = peer_id_for(
+            &SigningKey::from_bytes(&[42u8; 32])
+                .verifying_key()
+                .to_bytes(),
+        );
+        let add = |r: &mut SpaceRegistry, prefix: u32, node: &[u8], role: u8| {
+            let auth = root_auth("add_node", &[&prefix.to_le_bytes(), node, &[role]]);
+            dispatch(
+                r,
+                AddNode {
+                    prefix,
+                    peer_id: node.to_vec(),
+                    role,
+                    auth,
+                },
+            )
+        };
+
+        assert_eq!(add(&mut r, 7, &first, NODE_ROLE_VOTER), Status::Ok);
+        assert_eq!(
+            add(&mut r, 7, &second, NODE_ROLE_VOTER),
+            Status::PrefixCollision
+        );
+        // Re-enrolling the same node is still an idempotent role update.
+        assert_eq!(add(&mut r, 7, &first, NODE_ROLE_OBSERVER), Status::Ok);
+        assert_eq!(
+            dispatch(&mut r, NodeRole { prefix: 7 }),
+            NODE_ROLE_OBSERVER + 1
+        );
+        // A prefix outside the 16-bit space used to truncate silently.
+        assert_eq!(
+            add(&mut r, 0x1_0007, &second, NODE_ROLE_VOTER),
+            Status::BadPrefix
+        );
+    }
+
     #[test]
     fn forged_install_rejected_on_system_replay_path() {
         // The catalog-forgery guard: a non-admin peer forges an `install` op (a
@@ -5828,4 +6663,110 @@ mod tests {
             .is_none()
         );
     }
+
+    fn audit_all(r: &mut SpaceRegistry) -> Vec<AuditRow> {
+        let mut out: Vec<AuditRow> = Vec::new();
+        loop {
+            let page = dispatch(
+                r,
+                Audit {
+                    from: out.len() as u64,
+                    budget: 2,
+                },
+            );
+            out.extend(page.rows);
+            if !page.more {
+                return out;
+            }
+        }
+    }
+
+    #[test]
+    fn accepted_signed_ops_extend_a_verifiable_audit_chain() {
+        let mut r = registry();
+        let bob_key = SigningKey::from_bytes(&[11u8; 32]);
+        let bob = peer_id_for(&bob_key.verifying_key().to_bytes());
+        assert_eq!(grant_space(&mut r, &bob, AUTH_ROLE_ADMIN), Status::Ok);
+        let carol = alloc::vec![3, 3, 3];
+        let grant = || GrantRole {
+            peer_id: carol.clone(),
+            role: AUTH_ROLE_READONLY,
+            epoch: 1,
+            auth: auth_as(
+                &bob_key,
+                "grant_role",
+                &[&carol, &[AUTH_ROLE_READONLY], &1u64.to_le_bytes()],
+            ),
+        };
+        let mut ctx: vos::Context<SpaceRegistry> = vos::Context::new(ServiceId(0));
+        ctx.__set_admitted_at_ms(Some(1_700_000_000_000));
+        assert_eq!(
+            run(<SpaceRegistry as Message<GrantRole>>::handle(
+                &mut r,
+                grant(),
+                &mut ctx
+            )),
+            Status::Ok
+        );
+        // A refused op leaves no row.
+        let dave_key = SigningKey::from_bytes(&[12u8; 32]);
+        assert_eq!(
+            dispatch(
+                &mut r,
+                RevokeRole {
+                    peer_id: bob.clone(),
+                    epoch: 9,
+                    auth: auth_as(&dave_key, "revoke_role", &[&bob, &9u64.to_le_bytes()]),
+                },
+            ),
+            Status::Forbidden,
+        );
+        assert_eq!(install_at(&mut r, "logged", 1 /* Local */), Status::Ok);
+        assert_eq!(revoke_space(&mut r, &carol), Status::Ok);
+
+        let rows = audit_all(&mut r);
+        assert_eq!(vos::registry::verify_audit_chain(&rows), Ok(()));
+        let actions: Vec<&str> = rows.iter().map(|row| row.action.as_str()).collect();
+        assert_eq!(
+            actions,
+            [
+                "grant_role",
+                "grant_role",
+                "publish",
+                "install",
+                "revoke_role"
+            ]
+        );
+        assert_eq!(rows[0].actor, root_peer_id());
+        assert_eq!(rows[0].target, bob);
+        assert_eq!(rows[0].detail, "role=admin epoch=1");
+        assert_eq!(rows[1].actor, bob, "logged under the delegate that signed");
+        assert_eq!(rows[1].target, carol);
+        assert_eq!(rows[3].target, b"logged");
+        // Each row is stamped with its dispatch's admission time, not the
+        // reader's position, and carries the exact bytes its signature
+        // covers — a reader re-verifies authorization from the row alone.
+        assert_eq!(rows[0].at_ms, None, "native dispatch records no time");
+        assert_eq!(rows[1].at_ms, Some(1_700_000_000_000));
+        for row in &rows {
+            let sig: [u8; OP_SIG_LEN] = row.signature.as_slice().try_into().unwrap();
+            assert!(verify_op_sig(&row.actor, &row.op, &sig), "{}", row.action);
+            assert!(vos::registry::audit_op_matches(row), "{}", row.action);
+        }
+
+        // A replayed op re-applies idempotently but logs once.
+        assert_eq!(dispatch_as_system(&mut r, grant()), Status::Ok);
+        assert_eq!(audit_all(&mut r), rows);
+        assert!(
+            dispatch(
+                &mut r,
+                Audit {
+                    from: rows.len() as u64,
+                    budget: 0,
+                },
+            )
+            .rows
+            .is_empty()
+        );
+    }
 }
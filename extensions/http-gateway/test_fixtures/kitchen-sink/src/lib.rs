@@ -4,6 +4,7 @@
 //! - String / u32 / bool / Vec<u32> / Vec<String> / unit-reply
 //! - One handler that always panics, for the upstream-error path
 //! - One handler that takes no args, for the no-args path
+//! - Member- and admin-gated handlers, for the ssh-console role tests
 //!
 //! All handlers are intentionally simple — verify dispatch +
 //! arg/reply codec, not any business logic. State on `&mut self`
@@ -110,6 +111,18 @@ impl KitchenSink {
     #[msg]
     async fn ping(&self, _ctx: &mut Context<Self>) {}
 
+    /// Member-gated. Reached only by a caller relayed with a space role.
+    #[msg(space_role = SpaceRole::Member)]
+    async fn members_only(&self, _ctx: &mut Context<Self>) -> u32 {
+        1
+    }
+
+    /// Admin-gated. Refused to a relay capped below admin.
+    #[msg(space_role = SpaceRole::Admin)]
+    async fn admins_only(&self, _ctx: &mut Context<Self>) -> u32 {
+        3
+    }
+
     /// Always panics. Exercises the upstream-error path: dispatch
     /// runs, handler panics, host catches and replies empty bytes,
     /// gateway maps to 502.
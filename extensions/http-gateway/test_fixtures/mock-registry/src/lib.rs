@@ -1,8 +1,10 @@
 //! Stand-in for the bundled space-registry, used by the
-//! http-gateway dispatch tests. Implements just the handlers
-//! the gateway calls during a request:
+//! http-gateway dispatch tests and the ssh-console OpenSSH tests.
+//! Implements just the handlers those call:
 //!   - `resolve(name) -> u32`             — name → ServiceId
 //!   - `meta_for_instance(name) -> Vec<u8>` — schema blob
+//!   - `agent_names(after_name, budget)`  — the one schema'd agent
+//!   - `peer_role(peer_id) -> u8`         — admin, for every peer
 //!
 //! The actor is hardcoded against the fixture install order
 //! (`counter` at id 1, `kitchen` at id 2); the schema blob for
@@ -186,6 +188,30 @@ const KITCHEN_META: ActorMeta = ActorMeta {
             space_role: None,
             actor_role: None,
         },
+        MessageMeta {
+            name: "members_only",
+            is_query: true,
+            fields: &[],
+            returns: "u32",
+            doc: "",
+            timeout_ms: 0,
+            mode: 0,
+            attested: false,
+            space_role: Some(1),
+            actor_role: None,
+        },
+        MessageMeta {
+            name: "admins_only",
+            is_query: true,
+            fields: &[],
+            returns: "u32",
+            doc: "",
+            timeout_ms: 0,
+            mode: 0,
+            attested: false,
+            space_role: Some(3),
+            actor_role: None,
+        },
         MessageMeta {
             name: "boom",
             is_query: true,
@@ -212,7 +238,7 @@ const KITCHEN_META: ActorMeta = ActorMeta {
 /// `.vos_meta` section carries on PVM actors. Generated at const
 /// eval; `LEN` is the actual byte count, `BUF` holds it plus
 /// trailing zeros up to the fixed-size buffer.
-const KITCHEN_META_ENCODED: ([u8; 1024], usize) = encode::<1024>(&KITCHEN_META);
+const KITCHEN_META_ENCODED: ([u8; 2048], usize) = encode::<2048>(&KITCHEN_META);
 
 #[actor]
 #[derive(Default)]
@@ -249,4 +275,28 @@ impl MockRegistry {
             _ => Vec::new(),
         }
     }
+
+    /// The installed agents a console can list. Only `kitchen`: the
+    /// schema-less `counter` would register no commands.
+    #[msg]
+    async fn agent_names(
+        &self,
+        after_name: String,
+        _budget: u32,
+        _ctx: &mut Context<Self>,
+    ) -> vos::registry::AgentNamePage {
+        let names = if after_name.as_str() < "kitchen" {
+            vec!["kitchen".to_string()]
+        } else {
+            Vec::new()
+        };
+        vos::registry::AgentNamePage { names, more: false }
+    }
+
+    /// Every peer holds the admin role, so a relayed principal reaches
+    /// exactly what its relay's `intra_caps` ceiling allows.
+    #[msg]
+    async fn peer_role(&self, _peer_id: Vec<u8>, _ctx: &mut Context<Self>) -> u8 {
+        vos::registry::AUTH_ROLE_ADMIN
+    }
 }
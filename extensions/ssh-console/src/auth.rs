@@ -0,0 +1,226 @@
+//! User authentication (RFC 4252): `publickey` with `ssh-ed25519` keys
+//! from the configured `authorized_keys`, nothing else. The key — not
+//! the user name the client sends — decides who the session acts as, and
+//! the signed request is kept as the [`UserAuthProof`] the host re-checks
+//! on every call the session makes.
+
+use ed25519_dalek::Verifier;
+use vos::Context;
+use vos::effects::UserAuthProof;
+
+use crate::SshConsole;
+use crate::config::{AuthorizedKey, Keys, parse_ed25519_blob};
+use crate::transport::{End, Transport};
+use crate::wire::{self, Reader, Writer};
+
+/// `USERAUTH_REQUEST`s tolerated before the connection is dropped. A
+/// client tries each key it holds, twice per key (query, then sign).
+const MAX_AUTH_REQUESTS: u32 = 20;
+
+/// Run the `ssh-userauth` service to a verified key and its proof.
+pub(crate) async fn authenticate(
+    t: &mut Transport<'_>,
+    ctx: &mut Context<SshConsole>,
+    keys: &Keys,
+) -> Result<(AuthorizedKey, UserAuthProof), End> {
+    let request = t.read_packet(ctx).await?;
+    let mut r = Reader::new(&request[1..]);
+    if request[0] != wire::MSG_SERVICE_REQUEST || r.string() != Some(&b"ssh-userauth"[..]) {
+        return Err(End::Refuse(
+            wire::DISCONNECT_SERVICE_NOT_AVAILABLE,
+            "expected the ssh-userauth service",
+        ));
+    }
+    let accept = Writer::new(wire::MSG_SERVICE_ACCEPT)
+        .string("ssh-userauth")
+        .finish();
+    t.write_packet(ctx, &accept).await?;
+
+    for _ in 0..MAX_AUTH_REQUESTS {
+        let request = t.read_packet(ctx).await?;
+        if request[0] != wire::MSG_USERAUTH_REQUEST {
+            return Err(End::Refuse(
+                wire::DISCONNECT_PROTOCOL_ERROR,
+                "expected USERAUTH_REQUEST",
+            ));
+        }
+        let mut r = Reader::new(&request[1..]);
+        let (Some(user), Some(service), Some(method)) = (r.string(), r.string(), r.string()) else {
+            return Err(End::Refuse(
+                wire::DISCONNECT_PROTOCOL_ERROR,
+                "bad USERAUTH_REQUEST",
+            ));
+        };
+        if service != b"ssh-connection" {
+            return Err(End::Refuse(
+                wire::DISCONNECT_SERVICE_NOT_AVAILABLE,
+                "only ssh-connection is served",
+            ));
+        }
+        if method == b"publickey" {
+            match check_publickey(&mut r, keys, t.session_id(), user) {
+                Check::Accepted(key, proof) => {
+                    t.write_packet(ctx, &[wire::MSG_USERAUTH_SUCCESS]).await?;
+                    return Ok((key, proof));
+                }
+                Check::WouldAccept { alg, blob } => {
+                    let ok = Writer::new(wire::MSG_USERAUTH_PK_OK)
+                        .string(alg)
+                        .string(blob)
+                        .finish();
+                    t.write_packet(ctx, &ok).await?;
+                    continue;
+                }
+                Check::Rejected => {}
+            }
+        }
+        let failure = Writer::new(wire::MSG_USERAUTH_FAILURE)
+            .name_list(&["publickey"])
+            .bool(false)
+            .finish();
+        t.write_packet(ctx, &failure).await?;
+    }
+    Err(End::Refuse(
+        wire::DISCONNECT_NO_MORE_AUTH_METHODS,
+        "too many authentication attempts",
+    ))
+}
+
+enum Check<'a> {
+    /// Signed by an authorized key.
+    Accepted(AuthorizedKey, UserAuthProof),
+    /// An unsigned query for an authorized key: answer `PK_OK`.
+    WouldAccept {
+        alg: &'a [u8],
+        blob: &'a [u8],
+    },
+    Rejected,
+}
+
+/// Judge the `publickey` fields left in `r`. The signature covers the
+/// session id and the request itself (RFC 4252 §7), so it can't be
+/// replayed into another connection or for another user. It is checked
+/// over the request as [`UserAuthProof::signed_message`] rebuilds it, so a
+/// key accepted here is one the host accepts too.
+fn check_publickey<'a>(
+    r: &mut Reader<'a>,
+    keys: &Keys,
+    session_id: &[u8],
+    user: &[u8],
+) -> Check<'a> {
+    let (Some(signed), Some(alg), Some(blob)) = (r.bool(), r.string(), r.string()) else {
+        return Check::Rejected;
+    };
+    if alg != b"ssh-ed25519" {
+        return Check::Rejected;
+    }
+    let Some(authorized) = parse_ed25519_blob(blob).and_then(|key| keys.lookup(&key)) else {
+        return Check::Rejected;
+    };
+    if !signed {
+        return Check::WouldAccept { alg, blob };
+    }
+    let Some(signature) = r.string().and_then(parse_signature) else {
+        return Check::Rejected;
+    };
+    let Ok(key) = ed25519_dalek::VerifyingKey::from_bytes(&authorized.key) else {
+        return Check::Rejected;
+    };
+    let proof = UserAuthProof {
+        session_id: session_id.to_vec(),
+        user: user.to_vec(),
+        key: authorized.key,
+        signature: signature.to_bytes(),
+    };
+    match key.verify(&proof.signed_message(), &signature) {
+        Ok(()) => Check::Accepted(authorized.clone(), proof),
+        Err(_) => Check::Rejected,
+    }
+}
+
+/// `string "ssh-ed25519" || string sig` (RFC 8709 §6).
+fn parse_signature(blob: &[u8]) -> Option<ed25519_dalek::Signature> {
+    let mut r = Reader::new(blob);
+    if r.string()? != b"ssh-ed25519" {
+        return None;
+    }
+    let bytes: [u8; 64] = r.string()?.try_into().ok()?;
+    Some(ed25519_dalek::Signature::from_bytes(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::Signer;
+
+    use super::*;
+    use crate::config::peer_id_of;
+    use crate::transport::host_key_blob;
+
+    /// The `publickey` fields of a `USERAUTH_REQUEST`.
+    fn request(blob: &[u8], sig: Option<&[u8]>) -> Vec<u8> {
+        let w = Writer::new(0)
+            .bool(sig.is_some())
+            .string("ssh-ed25519")
+            .string(blob);
+        let fields = match sig {
+            Some(sig) => w.string(sig).finish(),
+            None => w.finish(),
+        };
+        fields[1..].to_vec()
+    }
+
+    #[test]
+    fn only_a_signature_over_this_session_is_accepted() {
+        let member = ed25519_dalek::SigningKey::from_bytes(&[5; 32]);
+        let public = member.verifying_key().to_bytes();
+        let keys = Keys {
+            host: ed25519_dalek::SigningKey::from_bytes(&[1; 32]),
+            authorized: vec![AuthorizedKey {
+                key: public,
+                peer: peer_id_of(&public),
+                comment: String::new(),
+            }],
+        };
+        let blob = host_key_blob(&member.verifying_key());
+        let session = [7u8; 32];
+        let signed_for = |session: &[u8]| {
+            let mut message = Vec::new();
+            wire::put_string(&mut message, session);
+            message.push(wire::MSG_USERAUTH_REQUEST);
+            wire::put_string(&mut message, b"alice");
+            wire::put_string(&mut message, b"ssh-connection");
+            wire::put_string(&mut message, b"publickey");
+            message.push(1);
+            wire::put_string(&mut message, b"ssh-ed25519");
+            wire::put_string(&mut message, &blob);
+            let mut sig = Vec::new();
+            wire::put_string(&mut sig, b"ssh-ed25519");
+            wire::put_string(&mut sig, &member.sign(&message).to_bytes());
+            sig
+        };
+        let judge =
+            |req: &[u8]| match check_publickey(&mut Reader::new(req), &keys, &session, b"alice") {
+                Check::Accepted(k, proof) => {
+                    assert_eq!(
+                        proof.peer_id(),
+                        k.peer,
+                        "the host relays the key's own PeerId"
+                    );
+                    Some(Some(k.peer))
+                }
+                Check::WouldAccept { .. } => Some(None),
+                Check::Rejected => None,
+            };
+
+        let query = request(&blob, None);
+        assert_eq!(judge(&query), Some(None));
+        let good = request(&blob, Some(&signed_for(&session)));
+        assert_eq!(judge(&good), Some(Some(peer_id_of(&public))));
+        let replayed = request(&blob, Some(&signed_for(&[8; 32])));
+        assert_eq!(judge(&replayed), None);
+
+        let stranger = ed25519_dalek::SigningKey::from_bytes(&[6; 32]);
+        let unknown = request(&host_key_blob(&stranger.verifying_key()), None);
+        assert_eq!(judge(&unknown), None);
+    }
+}
@@ -0,0 +1,518 @@
+//! The seam between the synchronous console engine and the async
+//! connection task.
+//!
+//! `vos-shell`'s [`ConsoleEngine`] evaluates a line in one blocking call
+//! and reaches the space through the blocking [`SpaceClient`] trait, while
+//! a transport extension can only talk to the space from its connection
+//! task, by awaiting host effects. So each session runs its engine on a
+//! thread of its own with an [`SshSpaceClient`] that turns every backend
+//! call into a [`Call`] sent back to the connection task; the task serves
+//! it over `ctx.ask_dispatch_as`, with the session's sign-in proof, and
+//! hands the answer back.
+//!
+//! While a line evaluates, the task waits on the engine thread between
+//! calls — and the host's executor, shared by every connection, waits
+//! with it. The wait is for nushell's own work between actor calls,
+//! bounded by [`COMPUTE_BUDGET`]: a script that computes longer is
+//! interrupted.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use vos::Context;
+use vos::Encode;
+use vos::actors::context::ServiceId;
+use vos::effects::UserAuthProof;
+use vos::value::{Msg, Value};
+use vos_shell::{
+    AgentInfo, BackendError, ConsoleEngine, EvalResult, SchemaCache, SpaceClient,
+    is_forbidden_envelope,
+};
+
+use crate::SshConsole;
+
+/// Longest the engine may compute without calling into the space before
+/// its line is interrupted.
+const COMPUTE_BUDGET: Duration = Duration::from_secs(10);
+
+/// How long an interrupted line gets to unwind before the session gives
+/// up on its engine.
+const INTERRUPT_GRACE: Duration = Duration::from_secs(2);
+
+/// nushell recurses on deep expressions; give the engine thread room.
+const ENGINE_STACK: usize = 8 << 20;
+
+/// A backend call the engine needs the connection task to make.
+pub(crate) enum Call {
+    Agents,
+    Resolve(String),
+    Meta(String),
+    Invoke(ServiceId, Msg),
+}
+
+pub(crate) enum Answer {
+    Agents(Vec<AgentInfo>),
+    Target(ServiceId),
+    Meta(Vec<u8>),
+    Reply(Value),
+}
+
+/// Work for the engine thread.
+enum Job {
+    Eval(String),
+    Schemas,
+}
+
+/// What a finished job produced.
+pub(crate) enum Done {
+    /// The engine is built and its commands registered.
+    Ready,
+    Eval(EvalResult),
+    Schemas(SchemaCache),
+}
+
+enum Event {
+    Call(Call, mpsc::Sender<Result<Answer, BackendError>>),
+    Done(Result<Done, BackendError>),
+}
+
+/// [`SpaceClient`] for one SSH session: every call is made by the
+/// connection task, as the session's principal.
+struct SshSpaceClient {
+    peer: Vec<u8>,
+    events: mpsc::Sender<Event>,
+}
+
+impl SshSpaceClient {
+    fn call(&self, call: Call) -> Result<Answer, BackendError> {
+        let (tx, rx) = mpsc::channel();
+        self.events
+            .send(Event::Call(call, tx))
+            .map_err(|_| BackendError::Unreachable)?;
+        rx.recv().map_err(|_| BackendError::Unreachable)?
+    }
+}
+
+fn mismatch() -> BackendError {
+    BackendError::Other("console bridge: mismatched answer".into())
+}
+
+impl SpaceClient for SshSpaceClient {
+    fn list_agents(&self) -> Result<Vec<AgentInfo>, BackendError> {
+        match self.call(Call::Agents)? {
+            Answer::Agents(agents) => Ok(agents),
+            _ => Err(mismatch()),
+        }
+    }
+
+    fn resolve_target(&self, name: &str) -> Result<ServiceId, BackendError> {
+        match self.call(Call::Resolve(name.to_string()))? {
+            Answer::Target(id) => Ok(id),
+            _ => Err(mismatch()),
+        }
+    }
+
+    fn raw_meta(&self, name: &str) -> Result<Vec<u8>, BackendError> {
+        match self.call(Call::Meta(name.to_string()))? {
+            Answer::Meta(meta) => Ok(meta),
+            _ => Err(mismatch()),
+        }
+    }
+
+    fn invoke(&self, target: ServiceId, msg: &Msg) -> Result<Value, BackendError> {
+        match self.call(Call::Invoke(target, msg.clone()))? {
+            Answer::Reply(value) => Ok(value),
+            _ => Err(mismatch()),
+        }
+    }
+
+    fn caller(&self) -> Option<vos::Caller> {
+        Some(vos::Caller::Peer(self.peer.clone()))
+    }
+}
+
+/// A session's engine thread, seen from the connection task.
+pub(crate) struct Console {
+    /// The session's sign-in, which the host checks on every call.
+    proof: UserAuthProof,
+    jobs: mpsc::Sender<Job>,
+    events: mpsc::Receiver<Event>,
+    /// The engine's interrupt flag, once it's built.
+    interrupt: Arc<AtomicBool>,
+    /// An interrupted line never came back: the engine is still busy and
+    /// its next answer would belong to a stale job.
+    wedged: bool,
+    /// [`COMPUTE_BUDGET`], shortened by tests.
+    budget: Duration,
+}
+
+impl Console {
+    /// Start the engine thread for the member who signed in with `proof`
+    /// and wait until its commands are registered (which already takes
+    /// registry calls).
+    pub(crate) async fn open(
+        ctx: &mut Context<SshConsole>,
+        proof: UserAuthProof,
+    ) -> Result<Self, BackendError> {
+        let (jobs, job_rx) = mpsc::channel::<Job>();
+        let (event_tx, events) = mpsc::channel();
+        let (flag_tx, flag_rx) = mpsc::channel();
+        let client = Arc::new(SshSpaceClient {
+            peer: proof.peer_id(),
+            events: event_tx.clone(),
+        });
+        std::thread::Builder::new()
+            .name("ssh-console".into())
+            .stack_size(ENGINE_STACK)
+            .spawn(move || {
+                let mut engine = match ConsoleEngine::new(client) {
+                    Ok(engine) => engine,
+                    Err(e) => {
+                        let _ = event_tx.send(Event::Done(Err(e)));
+                        return;
+                    }
+                };
+                let _ = flag_tx.send(engine.interrupt_flag());
+                if event_tx.send(Event::Done(Ok(Done::Ready))).is_err() {
+                    return;
+                }
+                // Ends when the session drops its `Console`.
+                while let Ok(job) = job_rx.recv() {
+                    let done = match job {
+                        Job::Eval(src) => Ok(Done::Eval(engine.eval(&src))),
+                        Job::Schemas => {
+                            SchemaCache::load(engine.client().as_ref()).map(Done::Schemas)
+                        }
+                    };
+                    if event_tx.send(Event::Done(done)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .map_err(|e| BackendError::Other(format!("start console engine: {e}")))?;
+
+        let mut console = Self {
+            proof,
+            jobs,
+            events,
+            interrupt: Arc::default(),
+            wedged: false,
+            budget: COMPUTE_BUDGET,
+        };
+        console.finish(ctx).await?;
+        if let Ok(flag) = flag_rx.try_recv() {
+            console.interrupt = flag;
+        }
+        Ok(console)
+    }
+
+    /// The engine stopped answering; the session must end.
+    pub(crate) fn wedged(&self) -> bool {
+        self.wedged
+    }
+
+    /// Evaluate one command as the session's principal.
+    pub(crate) async fn eval(&mut self, ctx: &mut Context<SshConsole>, src: &str) -> EvalResult {
+        let outcome = match self.jobs.send(Job::Eval(src.to_string())) {
+            Ok(()) => self.finish(ctx).await,
+            Err(_) => Err(BackendError::Unreachable),
+        };
+        match outcome {
+            Ok(Done::Eval(result)) => result,
+            Ok(_) => failed(mismatch()),
+            Err(e) => failed(e),
+        }
+    }
+
+    /// The agents and schemas, for tab completion.
+    pub(crate) async fn schemas(
+        &mut self,
+        ctx: &mut Context<SshConsole>,
+    ) -> Result<SchemaCache, BackendError> {
+        self.jobs
+            .send(Job::Schemas)
+            .map_err(|_| BackendError::Unreachable)?;
+        match self.finish(ctx).await? {
+            Done::Schemas(cache) => Ok(cache),
+            _ => Err(mismatch()),
+        }
+    }
+
+    /// Serve the engine's calls until its job is done.
+    async fn finish(&mut self, ctx: &mut Context<SshConsole>) -> Result<Done, BackendError> {
+        loop {
+            match self.next_event()? {
+                Event::Call(call, reply) => {
+                    let answer = serve(ctx, &self.proof, call).await;
+                    let _ = reply.send(answer);
+                }
+                Event::Done(done) => return done,
+            }
+        }
+    }
+
+    /// Wait for the engine's next call or result. A wait longer than the
+    /// compute budget interrupts the line; one that outlasts the grace
+    /// after that wedges the console.
+    fn next_event(&mut self) -> Result<Event, BackendError> {
+        let mut wait = self.budget;
+        let mut interrupted = false;
+        loop {
+            match self.events.recv_timeout(wait) {
+                Ok(event) => return Ok(event),
+                Err(RecvTimeoutError::Timeout) if !interrupted => {
+                    self.interrupt.store(true, Ordering::Relaxed);
+                    interrupted = true;
+                    wait = INTERRUPT_GRACE;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    self.wedged = true;
+                    return Err(BackendError::Other(
+                        "the console engine stopped responding".into(),
+                    ));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.wedged = true;
+                    return Err(BackendError::Other("the console engine exited".into()));
+                }
+            }
+        }
+    }
+}
+
+fn failed(e: BackendError) -> EvalResult {
+    EvalResult {
+        output: e.to_string(),
+        is_error: true,
+        forbidden: e == BackendError::Forbidden,
+    }
+}
+
+/// Make one engine call from the connection task. Registry reads are the
+/// public discovery surface the HTTP gateway also uses; actor invokes go
+/// as the principal, through the target's role gate.
+async fn serve(
+    ctx: &mut Context<SshConsole>,
+    proof: &UserAuthProof,
+    call: Call,
+) -> Result<Answer, BackendError> {
+    match call {
+        Call::Agents => {
+            let names = agent_names(ctx).await.ok_or(BackendError::Unreachable)?;
+            Ok(Answer::Agents(
+                names
+                    .into_iter()
+                    .map(|name| AgentInfo {
+                        instance_name: name.clone(),
+                        program_name: name,
+                    })
+                    .collect(),
+            ))
+        }
+        Call::Resolve(name) => resolve(ctx, &name).await.map(Answer::Target),
+        Call::Meta(name) => meta_for_instance(ctx, &name).await.map(Answer::Meta),
+        Call::Invoke(target, msg) => {
+            let reply = ctx
+                .ask_dispatch_as(proof, target, &dynamic(&msg))
+                .await
+                .ok_or_else(|| {
+                    BackendError::Other(format!(
+                        "no reply from {target} (the call failed or timed out, or the \
+                         manifest doesn't grant this console dispatch_as)"
+                    ))
+                })?;
+            if is_forbidden_envelope(&reply) {
+                return Err(BackendError::Forbidden);
+            }
+            if reply.is_empty() {
+                return Ok(Answer::Reply(Value::Unit));
+            }
+            <Value as vos::Decode>::try_decode(&reply)
+                .map(Answer::Reply)
+                .ok_or_else(|| BackendError::Decode(format!("reply from {target}")))
+        }
+    }
+}
+
+/// `[TAG_DYNAMIC] ++ msg` — the dynamic invoke payload.
+fn dynamic(msg: &Msg) -> Vec<u8> {
+    let encoded = msg.encode();
+    let mut payload = Vec::with_capacity(1 + encoded.len());
+    payload.push(vos::value::TAG_DYNAMIC);
+    payload.extend_from_slice(&encoded);
+    payload
+}
+
+async fn ask_registry(ctx: &mut Context<SshConsole>, msg: Msg) -> Option<Value> {
+    let bytes = ctx
+        .ask_dispatch(ServiceId::REGISTRY, &dynamic(&msg))
+        .await?;
+    if bytes.is_empty() {
+        return Some(Value::Unit);
+    }
+    <Value as vos::Decode>::try_decode(&bytes)
+}
+
+/// Drain the registry's paginated `agent_names`. `None` when the registry
+/// didn't answer.
+async fn agent_names(ctx: &mut Context<SshConsole>) -> Option<Vec<String>> {
+    let mut names: Vec<String> = Vec::new();
+    loop {
+        let msg = Msg::new("agent_names")
+            .with("after_name", names.last().cloned().unwrap_or_default())
+            .with("budget", 0u32);
+        let page = match ask_registry(ctx, msg).await? {
+            Value::Bytes(inner) if !inner.is_empty() => {
+                match <vos::registry::AgentNamePage as vos::Decode>::try_decode(&inner) {
+                    Some(page) => page,
+                    None => break,
+                }
+            }
+            _ => break,
+        };
+        let more = page.more;
+        names.extend(page.names);
+        if !more {
+            break;
+        }
+    }
+    Some(names)
+}
+
+/// Accepts the same forms as `vosx`: `registry`, `0x<hex id>`, or an
+/// installed instance name.
+async fn resolve(ctx: &mut Context<SshConsole>, name: &str) -> Result<ServiceId, BackendError> {
+    if name == "registry" {
+        return Ok(ServiceId::REGISTRY);
+    }
+    if let Some(hex) = name.strip_prefix("0x") {
+        return u32::from_str_radix(hex, 16)
+            .map(ServiceId)
+            .map_err(|_| BackendError::NotFound(name.to_string()));
+    }
+    let msg = Msg::new("resolve")
+        .with("name", name.to_string())
+        .with("caller_prefix", (ctx.id().0 >> 16) as u64);
+    let value = ask_registry(ctx, msg)
+        .await
+        .ok_or(BackendError::Unreachable)?;
+    match value.as_u32().unwrap_or(0) {
+        0 => Err(BackendError::NotFound(name.to_string())),
+        id => Ok(ServiceId(id)),
+    }
+}
+
+/// The raw `.vos_meta` blob, empty when the registry has none.
+async fn meta_for_instance(
+    ctx: &mut Context<SshConsole>,
+    name: &str,
+) -> Result<Vec<u8>, BackendError> {
+    let msg = Msg::new("meta_for_instance").with("name", name.to_string());
+    let value = ask_registry(ctx, msg)
+        .await
+        .ok_or(BackendError::Unreachable)?;
+    Ok(value.as_bytes().map(<[u8]>::to_vec).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    /// A `Console` whose engine is `engine`, run on a thread of its own
+    /// with the interrupt flag and event sender, and a short budget.
+    fn console(
+        engine: impl FnOnce(Arc<AtomicBool>, mpsc::Sender<Event>) + Send + 'static,
+    ) -> Console {
+        let (events_tx, events) = mpsc::channel();
+        let (jobs, _) = mpsc::channel();
+        let interrupt = Arc::new(AtomicBool::new(false));
+        let flag = interrupt.clone();
+        std::thread::spawn(move || engine(flag, events_tx));
+        Console {
+            proof: UserAuthProof {
+                session_id: vec![1; 32],
+                user: b"alice".to_vec(),
+                key: [2; 32],
+                signature: [3; 64],
+            },
+            jobs,
+            events,
+            interrupt,
+            wedged: false,
+            budget: Duration::from_millis(200),
+        }
+    }
+
+    fn eval_done(done: Result<Event, BackendError>) -> Option<EvalResult> {
+        match done {
+            Ok(Event::Done(Ok(Done::Eval(result)))) => Some(result),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn a_line_computing_past_the_budget_is_interrupted() {
+        let mut console = console(|interrupt, events| {
+            while !interrupt.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            let interrupted = EvalResult {
+                output: "interrupted".into(),
+                is_error: true,
+                forbidden: false,
+            };
+            let _ = events.send(Event::Done(Ok(Done::Eval(interrupted))));
+        });
+        let result = eval_done(console.next_event()).expect("the line unwinds");
+        assert!(result.is_error);
+        assert!(console.interrupt.load(Ordering::Relaxed));
+        assert!(!console.wedged());
+    }
+
+    #[test]
+    fn calls_into_the_space_restart_the_budget() {
+        let mut console = console(|_, events| {
+            for _ in 0..3 {
+                std::thread::sleep(Duration::from_millis(100));
+                let (reply, _) = mpsc::channel();
+                let _ = events.send(Event::Call(Call::Agents, reply));
+            }
+        });
+        for _ in 0..3 {
+            assert!(matches!(
+                console.next_event(),
+                Ok(Event::Call(Call::Agents, _))
+            ));
+        }
+        assert!(
+            !console.interrupt.load(Ordering::Relaxed),
+            "300 ms of compute in 100 ms steps stays within a 200 ms budget"
+        );
+    }
+
+    #[test]
+    fn an_engine_ignoring_the_interrupt_wedges_the_console() {
+        let (hold, held) = mpsc::channel::<()>();
+        let mut console = console(move |_, events| {
+            // Keep the sender alive without ever answering.
+            let _ = held.recv();
+            drop(events);
+        });
+        let started = Instant::now();
+        assert!(console.next_event().is_err());
+        assert!(started.elapsed() >= INTERRUPT_GRACE);
+        assert!(console.wedged());
+        drop(hold);
+    }
+
+    #[test]
+    fn an_exited_engine_wedges_the_console() {
+        let mut console = console(|_, events| drop(events));
+        assert!(console.next_event().is_err());
+        assert!(console.wedged());
+    }
+}
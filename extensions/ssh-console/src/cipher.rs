@@ -0,0 +1,201 @@
+//! The binary packet protocol (RFC 4253 §6): plaintext until the first
+//! `NEWKEYS`, then `chacha20-poly1305@openssh.com` — the one cipher this
+//! server negotiates.
+//!
+//! That AEAD construction (OpenSSH `PROTOCOL.chacha20poly1305`) keys the
+//! original 64-bit-nonce ChaCha20 twice from 64 bytes of key material:
+//! `K_2` (the first 32 bytes) encrypts the body and yields the Poly1305
+//! key, `K_1` (the second 32) encrypts the 4-byte length on its own so a
+//! receiver can frame a packet before authenticating it. The nonce is the
+//! packet sequence number; the tag covers the encrypted length and body.
+
+use chacha20::ChaCha20Legacy;
+use chacha20::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+use poly1305::Poly1305;
+use poly1305::universal_hash::KeyInit;
+use subtle::ConstantTimeEq;
+
+/// Poly1305 tag length.
+const TAG_LEN: usize = 16;
+
+/// Padding alignment. ChaCha20 is a stream cipher, so the RFC minimum of
+/// 8 applies to both the plaintext and the encrypted framing.
+const BLOCK: usize = 8;
+
+/// Largest `packet_length` accepted. RFC 4253 §6.1 requires 35000;
+/// the slack covers clients that fill a full 32 KiB channel packet plus
+/// framing, without letting a peer make us buffer megabytes.
+pub(crate) const MAX_PACKET: usize = 256 * 1024;
+
+/// Why an incoming packet was refused. Either one ends the connection.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum PacketError {
+    /// A length or padding field that can't be right.
+    Malformed,
+    /// The Poly1305 tag didn't verify.
+    BadTag,
+}
+
+/// One direction's packet protection.
+#[derive(Clone)]
+pub(crate) enum Cipher {
+    /// Before the first `NEWKEYS`.
+    Plain,
+    ChaCha {
+        /// `K_2`: body + Poly1305 key.
+        main: [u8; 32],
+        /// `K_1`: the length field.
+        header: [u8; 32],
+    },
+}
+
+impl Cipher {
+    /// Key `chacha20-poly1305@openssh.com` from the 64 bytes of material
+    /// the key exchange derived for this direction.
+    pub(crate) fn chacha(material: &[u8; 64]) -> Self {
+        let (main, header) = material.split_at(32);
+        Cipher::ChaCha {
+            main: main.try_into().unwrap(),
+            header: header.try_into().unwrap(),
+        }
+    }
+
+    /// Frame, pad and (once keyed) encrypt + tag `payload` as packet `seq`.
+    pub(crate) fn seal(&self, seq: u32, payload: &[u8]) -> Vec<u8> {
+        // The length field only counts towards the alignment in the
+        // plaintext framing; the AEAD keeps it out (it's encrypted apart).
+        let framed = match self {
+            Cipher::Plain => 4 + 1 + payload.len(),
+            Cipher::ChaCha { .. } => 1 + payload.len(),
+        };
+        let mut pad = BLOCK - framed % BLOCK;
+        if pad < 4 {
+            pad += BLOCK;
+        }
+        let packet_len = 1 + payload.len() + pad;
+        let mut packet = Vec::with_capacity(4 + packet_len + TAG_LEN);
+        packet.extend_from_slice(&(packet_len as u32).to_be_bytes());
+        packet.push(pad as u8);
+        packet.extend_from_slice(payload);
+        let mut padding = [0u8; 2 * BLOCK];
+        let _ = getrandom::getrandom(&mut padding[..pad]);
+        packet.extend_from_slice(&padding[..pad]);
+
+        if let Cipher::ChaCha { main, header } = self {
+            let nonce = u64::from(seq).to_be_bytes();
+            let (len, body) = packet.split_at_mut(4);
+            length_cipher(header, &nonce).apply_keystream(len);
+            let (mut body_cipher, poly_key) = body_cipher(main, &nonce);
+            body_cipher.apply_keystream(body);
+            let tag = Poly1305::new(poly1305::Key::from_slice(&poly_key)).compute_unpadded(&packet);
+            packet.extend_from_slice(&tag);
+        }
+        packet
+    }
+
+    /// Take packet `seq` off the front of `buf`: `Ok(None)` until it has
+    /// fully arrived, then its payload and how many bytes it used.
+    pub(crate) fn open(
+        &self,
+        seq: u32,
+        buf: &[u8],
+    ) -> Result<Option<(Vec<u8>, usize)>, PacketError> {
+        let Some(head) = buf.first_chunk::<4>() else {
+            return Ok(None);
+        };
+        let nonce = u64::from(seq).to_be_bytes();
+        let mut len_bytes = *head;
+        let (aligned, tag_len) = match self {
+            Cipher::Plain => (4, 0),
+            Cipher::ChaCha { header, .. } => {
+                length_cipher(header, &nonce).apply_keystream(&mut len_bytes);
+                (0, TAG_LEN)
+            }
+        };
+        let packet_len = u32::from_be_bytes(len_bytes) as usize;
+        if !(5..=MAX_PACKET).contains(&packet_len) || (aligned + packet_len) % BLOCK != 0 {
+            return Err(PacketError::Malformed);
+        }
+        let total = 4 + packet_len + tag_len;
+        if buf.len() < total {
+            return Ok(None);
+        }
+
+        let mut body = buf[4..4 + packet_len].to_vec();
+        if let Cipher::ChaCha { main, .. } = self {
+            let (mut body_cipher, poly_key) = body_cipher(main, &nonce);
+            let tag = Poly1305::new(poly1305::Key::from_slice(&poly_key))
+                .compute_unpadded(&buf[..4 + packet_len]);
+            if !bool::from(tag.as_slice().ct_eq(&buf[4 + packet_len..total])) {
+                return Err(PacketError::BadTag);
+            }
+            body_cipher.apply_keystream(&mut body);
+        }
+
+        let pad = usize::from(body[0]);
+        if pad < 4 || pad + 1 > packet_len {
+            return Err(PacketError::Malformed);
+        }
+        body.truncate(packet_len - pad);
+        body.remove(0);
+        Ok(Some((body, total)))
+    }
+}
+
+fn length_cipher(header: &[u8; 32], nonce: &[u8; 8]) -> ChaCha20Legacy {
+    ChaCha20Legacy::new(
+        chacha20::Key::from_slice(header),
+        chacha20::LegacyNonce::from_slice(nonce),
+    )
+}
+
+/// The body keystream positioned at block 1, and the Poly1305 key taken
+/// from the first 32 bytes of block 0.
+fn body_cipher(main: &[u8; 32], nonce: &[u8; 8]) -> (ChaCha20Legacy, [u8; 32]) {
+    let mut cipher = ChaCha20Legacy::new(
+        chacha20::Key::from_slice(main),
+        chacha20::LegacyNonce::from_slice(nonce),
+    );
+    let mut poly_key = [0u8; 32];
+    cipher.apply_keystream(&mut poly_key);
+    cipher.seek(64u64);
+    (cipher, poly_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_packets_open_in_both_framings() {
+        let keyed = Cipher::chacha(&[7u8; 64]);
+        for cipher in [Cipher::Plain, keyed] {
+            for payload in [&b"x"[..], &[0xAB; 100], &[]] {
+                let mut stream = cipher.seal(3, payload);
+                let one = stream.len();
+                stream.extend_from_slice(&cipher.seal(4, b"next"));
+                assert_eq!(cipher.open(3, &stream[..one - 1]), Ok(None));
+                assert_eq!(cipher.open(3, &stream), Ok(Some((payload.to_vec(), one))));
+                assert_eq!(
+                    cipher.open(4, &stream[one..]),
+                    Ok(Some((b"next".to_vec(), stream.len() - one)))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn a_tampered_or_replayed_packet_fails_the_tag() {
+        let cipher = Cipher::chacha(&[9u8; 64]);
+        let mut packet = cipher.seal(0, b"hello");
+        // The same bytes under another sequence number decrypt to a
+        // different length or fail the tag — never to the payload.
+        assert_ne!(
+            cipher.open(1, &packet),
+            Ok(Some((b"hello".to_vec(), packet.len())))
+        );
+        let last = packet.len() - 1;
+        packet[last] ^= 1;
+        assert_eq!(cipher.open(0, &packet), Err(PacketError::BadTag));
+    }
+}
@@ -0,0 +1,240 @@
+//! Operator-controlled config carried as actor init args, and its parsed
+//! form.
+//!
+//! ## Manifest example
+//!
+//! ```toml
+//! [[extension]]
+//! name = "ssh"
+//! path = "target/release/libssh_console.so"
+//! intra_caps = ["*:member"]
+//! dispatch_as = true
+//! init = {
+//!     bind_addr       = "0.0.0.0",
+//!     port            = 2222,
+//!     host_key        = "9f0c…",   # 32-byte ed25519 seed, hex (`openssl rand -hex 32`)
+//!     authorized_keys = """
+//! ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAI… alice@laptop
+//! ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAI… bob (bob's vosx device key)
+//! """,
+//! }
+//! ```
+//!
+//! `dispatch_as = true` is the operator's grant that lets the console act
+//! for its members at all; without it the host refuses every call.
+//!
+//! `authorized_keys` takes OpenSSH-style lines, `ssh-ed25519` keys only,
+//! without options. Each key signs in as the libp2p PeerId of the key
+//! itself, so a member reuses their device key and its registry role
+//! applies: the host checks each session's sign-in signature and relays
+//! only the identity that key proves. Blank lines and `#` comments are
+//! skipped.
+//!
+//! `bind_addr`/`port` are read host-side (in `vosx` reconcile) to
+//! configure `serves(..)`; the console itself uses `host_key` and
+//! `authorized_keys`. Neither has a usable default: a missing host key or
+//! an empty/malformed key list is a config error, and every connection is
+//! refused with it rather than served with weakened auth.
+
+use base64ct::{Base64, Encoding};
+
+use crate::wire::Reader;
+
+/// Init args carried into [`SshConsole`](crate::SshConsole). Auto-derives
+/// rkyv via the actor macro; empty string means unset.
+#[derive(vos::rkyv::Archive, vos::rkyv::Serialize, vos::rkyv::Deserialize, Clone, Default)]
+#[rkyv(crate = vos::rkyv)]
+pub(crate) struct ConsoleConfig {
+    /// Hex-encoded 32-byte ed25519 seed of the server's host key.
+    pub(crate) host_key: String,
+    /// Newline-separated `authorized_keys` lines.
+    pub(crate) authorized_keys: String,
+}
+
+/// One key allowed to sign in, and who it signs in as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AuthorizedKey {
+    /// Raw ed25519 public key.
+    pub(crate) key: [u8; 32],
+    /// Multihash PeerId bytes the session acts as.
+    pub(crate) peer: Vec<u8>,
+    /// Trailing comment, for the greeting.
+    pub(crate) comment: String,
+}
+
+/// The parsed, ready-to-serve config.
+pub(crate) struct Keys {
+    pub(crate) host: ed25519_dalek::SigningKey,
+    pub(crate) authorized: Vec<AuthorizedKey>,
+}
+
+impl ConsoleConfig {
+    /// Parse both keys. The error lists every problem found, one per line.
+    pub(crate) fn parse(&self) -> Result<Keys, String> {
+        let mut errors = Vec::new();
+        let host = match parse_host_key(&self.host_key) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                errors.push(e);
+                None
+            }
+        };
+        let authorized = match parse_authorized_keys(&self.authorized_keys) {
+            Ok(keys) if keys.is_empty() => {
+                errors.push("authorized_keys: no keys configured".into());
+                Vec::new()
+            }
+            Ok(keys) => keys,
+            Err(e) => {
+                errors.push(e);
+                Vec::new()
+            }
+        };
+        match host {
+            Some(host) if errors.is_empty() => Ok(Keys { host, authorized }),
+            _ => Err(errors.join("\n")),
+        }
+    }
+}
+
+impl Keys {
+    pub(crate) fn lookup(&self, key: &[u8; 32]) -> Option<&AuthorizedKey> {
+        self.authorized.iter().find(|k| &k.key == key)
+    }
+}
+
+fn parse_host_key(hex_seed: &str) -> Result<ed25519_dalek::SigningKey, String> {
+    let hex_seed = hex_seed.trim();
+    if hex_seed.is_empty() {
+        return Err(
+            "host_key: not set (want a hex ed25519 seed, e.g. `openssl rand -hex 32`)".into(),
+        );
+    }
+    let seed: [u8; 32] = hex::decode(hex_seed)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or("host_key: want 64 hex digits (a 32-byte ed25519 seed)")?;
+    Ok(ed25519_dalek::SigningKey::from_bytes(&seed))
+}
+
+/// Parse `authorized_keys` text. Fails on any malformed line — a typo
+/// must not silently lock a member out or, worse, map them to someone
+/// else.
+pub(crate) fn parse_authorized_keys(text: &str) -> Result<Vec<AuthorizedKey>, String> {
+    let mut keys = Vec::new();
+    let mut errors = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_line(line) {
+            Ok(key) => keys.push(key),
+            Err(e) => errors.push(format!("authorized_keys line {}: {e}", idx + 1)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(keys)
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+fn parse_line(line: &str) -> Result<AuthorizedKey, String> {
+    let mut fields = line.split_whitespace();
+    let first = fields.next().unwrap_or_default();
+    if first.contains('=') {
+        return Err(format!(
+            "options are not supported ({first:?}): a key signs in as its own PeerId"
+        ));
+    }
+    if first != "ssh-ed25519" {
+        return Err(format!(
+            "{first:?} keys are not supported, only ssh-ed25519"
+        ));
+    }
+    let blob = fields
+        .next()
+        .and_then(|b| Base64::decode_vec(b).ok())
+        .ok_or("key is not valid base64")?;
+    let key = parse_ed25519_blob(&blob).ok_or("not an ssh-ed25519 public key blob")?;
+    Ok(AuthorizedKey {
+        key,
+        peer: peer_id_of(&key),
+        comment: fields.collect::<Vec<_>>().join(" "),
+    })
+}
+
+/// The raw key inside a `string "ssh-ed25519" || string key` blob.
+pub(crate) fn parse_ed25519_blob(blob: &[u8]) -> Option<[u8; 32]> {
+    let mut r = Reader::new(blob);
+    if r.string()? != b"ssh-ed25519" {
+        return None;
+    }
+    r.string()?.try_into().ok()
+}
+
+/// The libp2p PeerId of an ed25519 key: the identity multihash of its
+/// protobuf encoding (`KeyType::Ed25519`, 32 data bytes).
+pub(crate) fn peer_id_of(key: &[u8; 32]) -> Vec<u8> {
+    let mut peer = vec![0x00, 0x24, 0x08, 0x01, 0x12, 0x20];
+    peer.extend_from_slice(key);
+    peer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(key: &[u8; 32]) -> String {
+        let mut b = Vec::new();
+        crate::wire::put_string(&mut b, b"ssh-ed25519");
+        crate::wire::put_string(&mut b, key);
+        Base64::encode_string(&b)
+    }
+
+    #[test]
+    fn keys_sign_in_as_their_own_peer_id() {
+        let text = format!(
+            "# members\n\nssh-ed25519 {} alice@laptop\nssh-ed25519 {}\n",
+            blob(&[1; 32]),
+            blob(&[2; 32]),
+        );
+        let keys = parse_authorized_keys(&text).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].peer, peer_id_of(&[1; 32]));
+        assert_eq!(keys[0].comment, "alice@laptop");
+        assert_eq!(keys[1].peer, peer_id_of(&[2; 32]));
+        // The derived id is the base58 "12D3KooW…" form libp2p prints.
+        let peer = bs58::encode(&keys[1].peer).into_string();
+        assert!(peer.starts_with("12D3KooW"), "{peer}");
+    }
+
+    #[test]
+    fn malformed_lines_fail_the_whole_list() {
+        let good = format!("ssh-ed25519 {}", blob(&[1; 32]));
+        let peer = bs58::encode(peer_id_of(&[9; 32])).into_string();
+        let text = format!("{good}\nssh-rsa AAAAB3Nza rsa-key\nas={peer} {good}\nssh-ed25519 !!\n");
+        let err = parse_authorized_keys(&text).unwrap_err();
+        assert!(
+            err.contains("line 2") && err.contains("only ssh-ed25519"),
+            "{err}"
+        );
+        // A key can't be mapped to another identity the host couldn't verify.
+        assert!(err.contains("line 3: options are not supported"), "{err}");
+        assert!(err.contains("line 4: key is not valid base64"), "{err}");
+    }
+
+    #[test]
+    fn missing_host_key_or_keys_is_a_config_error() {
+        let cfg = ConsoleConfig::default();
+        let err = cfg.parse().err().unwrap();
+        assert!(err.contains("host_key: not set"), "{err}");
+        assert!(err.contains("no keys configured"), "{err}");
+        let cfg = ConsoleConfig {
+            host_key: "11".repeat(32),
+            authorized_keys: format!("ssh-ed25519 {}", blob(&[1; 32])),
+        };
+        assert!(cfg.parse().unwrap().lookup(&[1; 32]).is_some());
+    }
+}
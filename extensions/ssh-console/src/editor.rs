@@ -0,0 +1,355 @@
+//! A small line editor for the session's terminal — the SSH server side
+//! of a REPL, since the client's pty is in raw mode and echoes nothing.
+//!
+//! Interactive (pty) sessions get echo, backspace, `Ctrl-C` (drop the
+//! line), `Ctrl-D` (leave, on an empty line), `Ctrl-U` (erase the line),
+//! `Ctrl-L` (clear the screen), `Up`/`Down` history and `Tab` completion;
+//! other escape sequences are swallowed. Without a pty the input is
+//! plain lines and nothing is echoed. Either way a trailing `\` continues
+//! the command on the next line, as in the local console.
+
+use vos_shell::SchemaCache;
+
+pub(crate) const PROMPT: &str = "> ";
+const CONTINUATION: &str = "  ";
+
+/// What a byte of input completed.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    /// A full command, continuation lines joined with `\n`.
+    Line(String),
+    /// `Ctrl-D` on an empty line.
+    Eof,
+    /// `Tab`: the caller completes against the space's schema, then calls
+    /// [`LineEditor::complete`].
+    Complete,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    /// Just saw `ESC`.
+    Start,
+    /// Inside `ESC [` / `ESC O`, waiting for the final byte.
+    Sequence,
+}
+
+pub(crate) struct LineEditor {
+    interactive: bool,
+    /// The line being typed, as raw UTF-8.
+    line: Vec<u8>,
+    /// Earlier lines of a `\`-continued command.
+    continued: Vec<String>,
+    escape: Escape,
+    /// The last byte was `\r`, so a following `\n` is the same Enter.
+    after_cr: bool,
+    history: Vec<String>,
+    /// Position while walking `history` with `Up`/`Down`.
+    recall: Option<usize>,
+}
+
+impl LineEditor {
+    pub(crate) fn new(interactive: bool) -> Self {
+        Self {
+            interactive,
+            line: Vec::new(),
+            continued: Vec::new(),
+            escape: Escape::None,
+            after_cr: false,
+            history: Vec::new(),
+            recall: None,
+        }
+    }
+
+    /// The partial command, for a script that ends without a newline.
+    pub(crate) fn take_pending(&mut self) -> Option<String> {
+        if self.line.is_empty() && self.continued.is_empty() {
+            return None;
+        }
+        let mut lines = std::mem::take(&mut self.continued);
+        lines.push(String::from_utf8_lossy(&std::mem::take(&mut self.line)).into_owned());
+        Some(lines.join("\n"))
+    }
+
+    /// The line being typed, for completion.
+    pub(crate) fn current_line(&self) -> String {
+        String::from_utf8_lossy(&self.line).into_owned()
+    }
+
+    /// Write the prompt for the current state.
+    pub(crate) fn prompt(&self, echo: &mut Vec<u8>) {
+        if self.interactive {
+            let prompt = if self.continued.is_empty() {
+                PROMPT
+            } else {
+                CONTINUATION
+            };
+            echo.extend_from_slice(prompt.as_bytes());
+        }
+    }
+
+    /// Feed one byte of input. Whatever the terminal should show goes to
+    /// `echo`.
+    pub(crate) fn feed(&mut self, byte: u8, echo: &mut Vec<u8>) -> Option<Outcome> {
+        let after_cr = std::mem::replace(&mut self.after_cr, byte == b'\r');
+        if !self.interactive {
+            return match byte {
+                b'\n' => self.enter(echo),
+                b'\r' => None,
+                _ => {
+                    self.line.push(byte);
+                    None
+                }
+            };
+        }
+        match self.escape {
+            Escape::Start => {
+                self.escape = if matches!(byte, b'[' | b'O') {
+                    Escape::Sequence
+                } else {
+                    Escape::None
+                };
+                return None;
+            }
+            Escape::Sequence => {
+                if (0x40..=0x7e).contains(&byte) {
+                    self.escape = Escape::None;
+                    match byte {
+                        b'A' => self.recall_older(echo),
+                        b'B' => self.recall_newer(echo),
+                        _ => {}
+                    }
+                }
+                return None;
+            }
+            Escape::None => {}
+        }
+        match byte {
+            b'\n' if after_cr => None,
+            b'\r' | b'\n' => self.enter(echo),
+            0x1b => {
+                self.escape = Escape::Start;
+                None
+            }
+            // Backspace / DEL: drop one UTF-8 character.
+            0x7f | 0x08 => {
+                if !self.line.is_empty() {
+                    while let Some(b) = self.line.pop() {
+                        if b & 0xC0 != 0x80 {
+                            break;
+                        }
+                    }
+                    echo.extend_from_slice(b"\x08 \x08");
+                }
+                None
+            }
+            // Ctrl-C: abandon the command.
+            0x03 => {
+                self.line.clear();
+                self.continued.clear();
+                self.recall = None;
+                echo.extend_from_slice(b"^C\r\n");
+                self.prompt(echo);
+                None
+            }
+            // Ctrl-D: leave, but only from an empty prompt.
+            0x04 => (self.line.is_empty() && self.continued.is_empty()).then_some(Outcome::Eof),
+            // Ctrl-U: erase the line.
+            0x15 => {
+                self.line.clear();
+                self.redraw(echo);
+                None
+            }
+            // Ctrl-L: clear the screen.
+            0x0c => {
+                echo.extend_from_slice(b"\x1b[H\x1b[2J");
+                self.redraw(echo);
+                None
+            }
+            b'\t' => Some(Outcome::Complete),
+            b if b < 0x20 => None,
+            b => {
+                self.line.push(b);
+                echo.push(b);
+                None
+            }
+        }
+    }
+
+    /// Apply a completion: append `insert` to the line, or list the
+    /// `candidates` when there's nothing unambiguous to add.
+    pub(crate) fn complete(&mut self, completion: Completion, echo: &mut Vec<u8>) {
+        if !completion.insert.is_empty() {
+            self.line.extend_from_slice(completion.insert.as_bytes());
+            echo.extend_from_slice(completion.insert.as_bytes());
+        } else if completion.candidates.len() > 1 {
+            echo.extend_from_slice(b"\r\n");
+            echo.extend_from_slice(completion.candidates.join("  ").as_bytes());
+            echo.extend_from_slice(b"\r\n");
+            self.prompt(echo);
+            echo.extend_from_slice(&self.line);
+        }
+    }
+
+    fn enter(&mut self, echo: &mut Vec<u8>) -> Option<Outcome> {
+        if self.interactive {
+            echo.extend_from_slice(b"\r\n");
+        }
+        let mut line = String::from_utf8_lossy(&std::mem::take(&mut self.line)).into_owned();
+        if let Some(head) = line.strip_suffix('\\') {
+            self.continued.push(head.to_string());
+            self.prompt(echo);
+            return None;
+        }
+        if !self.continued.is_empty() {
+            self.continued.push(line);
+            line = std::mem::take(&mut self.continued).join("\n");
+        }
+        self.recall = None;
+        if self.interactive && !line.trim().is_empty() && self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+        }
+        Some(Outcome::Line(line))
+    }
+
+    fn recall_older(&mut self, echo: &mut Vec<u8>) {
+        let at = match self.recall {
+            Some(0) => return,
+            Some(at) => at - 1,
+            None if self.history.is_empty() => return,
+            None => self.history.len() - 1,
+        };
+        self.recall = Some(at);
+        self.line = self.history[at].as_bytes().to_vec();
+        self.redraw(echo);
+    }
+
+    fn recall_newer(&mut self, echo: &mut Vec<u8>) {
+        let Some(at) = self.recall else { return };
+        if at + 1 < self.history.len() {
+            self.recall = Some(at + 1);
+            self.line = self.history[at + 1].as_bytes().to_vec();
+        } else {
+            self.recall = None;
+            self.line.clear();
+        }
+        self.redraw(echo);
+    }
+
+    /// Rewrite the current terminal row: prompt, then the line.
+    fn redraw(&self, echo: &mut Vec<u8>) {
+        echo.extend_from_slice(b"\r\x1b[2K");
+        self.prompt(echo);
+        echo.extend_from_slice(&self.line);
+    }
+}
+
+/// A `Tab` result: text to append, or the choices when it's ambiguous.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Completion {
+    pub(crate) insert: String,
+    pub(crate) candidates: Vec<String>,
+}
+
+/// Complete the last word of `line`: an agent name in first position, one
+/// of that agent's messages in second. A unique match gets a trailing
+/// space; several extend to their longest common prefix.
+pub(crate) fn complete(line: &str, schemas: &SchemaCache) -> Completion {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let fresh_word = line.is_empty() || line.ends_with(char::is_whitespace);
+    let (position, partial) = match (words.len(), fresh_word) {
+        (0, _) => (0, ""),
+        (n, true) => (n, ""),
+        (n, false) => (n - 1, words[n - 1]),
+    };
+    let pool: Vec<&str> = match position {
+        0 => schemas
+            .agents
+            .iter()
+            .map(|a| a.instance_name.as_str())
+            .collect(),
+        1 => schemas.methods(words[0]),
+        _ => Vec::new(),
+    };
+    let mut candidates: Vec<String> = pool
+        .into_iter()
+        .filter(|c| c.starts_with(partial))
+        .map(str::to_string)
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    let insert = match candidates.as_slice() {
+        [] => String::new(),
+        [only] => format!("{} ", &only[partial.len()..]),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.len(), |n, c| {
+                first
+                    .bytes()
+                    .zip(c.bytes())
+                    .take(n)
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            });
+            first[partial.len()..common].to_string()
+        }
+    };
+    Completion { insert, candidates }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vos_shell::AgentInfo;
+
+    fn feed(editor: &mut LineEditor, input: &[u8]) -> (Vec<Outcome>, String) {
+        let mut echo = Vec::new();
+        let outcomes = input
+            .iter()
+            .filter_map(|&b| editor.feed(b, &mut echo))
+            .collect();
+        (outcomes, String::from_utf8_lossy(&echo).into_owned())
+    }
+
+    #[test]
+    fn interactive_editing_echoes_and_submits() {
+        let mut editor = LineEditor::new(true);
+        let (out, echo) = feed(&mut editor, b"countr\x7fer\x1b[Dx\r\n");
+        assert_eq!(out, [Outcome::Line("counterx".into())]);
+        assert_eq!(echo, "countr\x08 \x08erx\r\n");
+        // Up recalls it; Ctrl-C drops it again.
+        let (out, echo) = feed(&mut editor, b"\x1b[A\x03\x04");
+        assert_eq!(out, [Outcome::Eof]);
+        assert!(
+            echo.contains("> counterx") && echo.ends_with("^C\r\n> "),
+            "{echo:?}"
+        );
+    }
+
+    #[test]
+    fn a_trailing_backslash_continues_the_command() {
+        let mut editor = LineEditor::new(false);
+        let (out, echo) = feed(&mut editor, b"if true {\\\n  1\\\r\n}\nlast");
+        assert_eq!(out, [Outcome::Line("if true {\n  1\n}".into())]);
+        assert!(echo.is_empty());
+        assert_eq!(editor.take_pending().as_deref(), Some("last"));
+    }
+
+    #[test]
+    fn completion_extends_agents_then_methods() {
+        let schemas = SchemaCache {
+            agents: ["counter", "counter-v2", "greeter"]
+                .map(|n| AgentInfo {
+                    instance_name: n.into(),
+                    program_name: n.into(),
+                })
+                .to_vec(),
+            schemas: Default::default(),
+        };
+        assert_eq!(complete("gr", &schemas).insert, "eeter ");
+        let ambiguous = complete("co", &schemas);
+        assert_eq!(ambiguous.insert, "unter");
+        assert_eq!(ambiguous.candidates, ["counter", "counter-v2"]);
+        // No schema for `counter`: nothing to offer after it.
+        assert_eq!(complete("counter ", &schemas), Completion::default());
+    }
+}
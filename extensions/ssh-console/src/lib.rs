@@ -0,0 +1,116 @@
+//! SshConsole extension — the sandboxed `vos-shell` console over SSH.
+//!
+//! ```text
+//! $ ssh -p 2222 space.example                 # interactive console
+//! > counter add a=2
+//! $ ssh -p 2222 space.example 'counter get'   # one command, exit status
+//! ```
+//!
+//! Members sign in with an `ssh-ed25519` key listed in the operator's
+//! `authorized_keys`; the key's own libp2p PeerId is their space identity
+//! (see [`config`]). The session is the same nushell console `vosx space
+//! console` opens locally — agents as commands, no filesystem, network
+//! or externals — and every actor call it makes goes through the
+//! target's role gate *as that identity*, via `ctx.ask_dispatch_as`. Each
+//! call carries the member's signed sign-in request, which the host
+//! verifies before relaying anything, and only if the manifest grants the
+//! console `dispatch_as = true`.
+//!
+//! ## Roles
+//!
+//! The host bounds the identity's registry role, and any per-actor grant,
+//! by this extension's `intra_caps`: with `intra_caps = ["*:member"]`, an
+//! admin's key signs in as an admin's *member* self, and without any
+//! `intra_caps` every call arrives unauthenticated. Grant the highest role the console
+//! should ever exercise. A refusal prints `permission denied`, and a
+//! command run non-interactively exits with status 77.
+//!
+//! ## Transport mode
+//!
+//! The **host** owns the TCP listener + accept loop (`bind_addr`/`port`,
+//! read in `vosx` reconcile) and drives one
+//! [`SshConsole::handle_connection`] per connection. The SSH-2 server on
+//! the byte stream is deliberately small: `curve25519-sha256`,
+//! `ssh-ed25519`, `chacha20-poly1305@openssh.com` (see [`transport`]),
+//! `publickey` auth ([`auth`]) and one `session` channel per connection
+//! ([`session`]). No port forwarding, subsystems (so no `sftp`/`scp`),
+//! agent forwarding or `env`.
+//!
+//! Each session's engine runs on its own thread ([`bridge`]); the
+//! connection task waits on it while a line evaluates, which holds up the
+//! host's cooperative executor for nushell's compute between actor calls.
+//! A line that computes for more than ten seconds without calling an
+//! actor is interrupted.
+//!
+//! The console is a line editor ([`editor`]), not the local TUI: history,
+//! tab completion of agents and methods, `\` continuation.
+//!
+//! ## Operator config (manifest init args)
+//!
+//! | Field             | Default                                    |
+//! |-------------------|--------------------------------------------|
+//! | `bind_addr`       | `127.0.0.1` (loopback)                     |
+//! | `port`            | `8080` — set it (e.g. `2222`)              |
+//! | `host_key`        | none — required, hex ed25519 seed          |
+//! | `authorized_keys` | none — required, one key per line          |
+//!
+//! A missing or malformed `host_key`/`authorized_keys` is logged at boot
+//! and every connection is closed unserved.
+
+mod auth;
+mod bridge;
+mod cipher;
+mod config;
+mod editor;
+mod session;
+mod transport;
+mod wire;
+
+use std::cell::OnceCell;
+
+use vos::prelude::*;
+
+#[actor(kind = "transport", caps = ["net.tcp.bind", "thread.spawn"])]
+struct SshConsole {
+    /// Operator config, parsed from the init args — the persistable part
+    /// of the actor's state.
+    cfg: config::ConsoleConfig,
+    /// The parsed keys, or the config error. **Skipped by rkyv** (a
+    /// transport extension never warm-restarts); built eagerly in `new()`.
+    #[rkyv(with = vos::rkyv::with::Skip)]
+    keys: OnceCell<Result<config::Keys, String>>,
+}
+
+#[messages]
+impl SshConsole {
+    /// Constructor invoked by `vos_extension_create` with the raw
+    /// rkyv-encoded `vos::value::Args` init blob. Parses the keys at boot
+    /// so a config error is logged once, up front.
+    fn new(args: &[u8]) -> Self {
+        use vos::Decode;
+        let parsed: vos::value::Args = if args.is_empty() {
+            vos::value::Args::default()
+        } else {
+            vos::value::Args::decode(args)
+        };
+        let cfg = config::ConsoleConfig {
+            host_key: parsed.get_str("host_key").unwrap_or_default(),
+            authorized_keys: parsed.get_str("authorized_keys").unwrap_or_default(),
+        };
+        let keys = OnceCell::new();
+        let parsed_keys = cfg.parse();
+        if let Err(e) = &parsed_keys {
+            log::error!("ssh-console: refusing all connections — config error:\n{e}");
+        }
+        let _ = keys.set(parsed_keys);
+        SshConsole { cfg, keys }
+    }
+
+    /// Serve one SSH connection to completion, then close it.
+    async fn handle_connection(&self, ctx: &mut Context<Self>, conn_id: u64) {
+        if let Ok(keys) = self.keys.get_or_init(|| self.cfg.parse()) {
+            session::serve(ctx, conn_id, keys).await;
+        }
+        ctx.close(conn_id).await;
+    }
+}
@@ -0,0 +1,547 @@
+//! The connection protocol (RFC 4254) for one authenticated client: a
+//! single `session` channel that either runs one command
+//! (`ssh space.example 'counter get'`) or opens the console.
+//!
+//! Everything else a client may ask for — more channels, port
+//! forwarding, subsystems, `env` — is declined. Input is flow-controlled
+//! by when the console consumes it, so a client pasting into a busy
+//! session stalls rather than buffering here.
+
+use vos::Context;
+use vos_shell::{EvalResult, SchemaCache};
+
+use crate::SshConsole;
+use crate::auth;
+use crate::bridge::Console;
+use crate::config::{AuthorizedKey, Keys};
+use crate::editor::{self, LineEditor, Outcome};
+use crate::transport::{End, Transport};
+use crate::wire::{self, Reader, Writer};
+
+/// Receive window granted to the client, and the largest data packet
+/// accepted.
+const LOCAL_WINDOW: u32 = 1 << 20;
+const LOCAL_MAX_PACKET: u32 = 32 * 1024;
+
+/// Exit status of a command refused by a role gate (`EX_NOPERM`), so
+/// scripts can tell "not allowed" from "failed".
+const EXIT_FORBIDDEN: u32 = 77;
+
+/// Serve one connection: handshake, authenticate, run the session. A
+/// refusal is reported to the client before the caller closes.
+pub(crate) async fn serve(ctx: &mut Context<SshConsole>, conn: u64, keys: &Keys) {
+    let mut t = Transport::new(conn, &keys.host);
+    if let Err(End::Refuse(reason, text)) = run(&mut t, ctx, keys).await {
+        t.disconnect(ctx, reason, text).await;
+    }
+}
+
+async fn run(t: &mut Transport<'_>, ctx: &mut Context<SshConsole>, keys: &Keys) -> Result<(), End> {
+    t.handshake(ctx).await?;
+    let (user, proof) = auth::authenticate(t, ctx, keys).await?;
+    t.enable_keepalive();
+
+    let mut channel = Channel::accept(t, ctx).await?;
+    while channel.start.is_none() {
+        if channel.closed {
+            return Err(End::Gone);
+        }
+        channel.pump(t, ctx).await?;
+    }
+    let mut console = match Console::open(ctx, proof).await {
+        Ok(console) => console,
+        Err(e) => {
+            let text = format!("ssh-console: the console is unavailable: {e}\r\n");
+            channel.send(t, ctx, text.as_bytes(), true).await?;
+            return channel.close(t, ctx, 1).await;
+        }
+    };
+    let status = match channel.start.take() {
+        Some(Start::Exec(command)) => {
+            let result = console.eval(ctx, &command).await;
+            channel.print(t, ctx, &result).await?;
+            exit_status(&result)
+        }
+        _ => shell(t, ctx, &mut channel, &mut console, &user).await?,
+    };
+    channel.close(t, ctx, status).await
+}
+
+/// The interactive console: read lines through the [`LineEditor`],
+/// evaluate each, print the result. Ends on `exit`, `Ctrl-D` or end of
+/// input; the exit status is the last command's.
+async fn shell(
+    t: &mut Transport<'_>,
+    ctx: &mut Context<SshConsole>,
+    channel: &mut Channel,
+    console: &mut Console,
+    user: &AuthorizedKey,
+) -> Result<u32, End> {
+    let mut editor = LineEditor::new(channel.pty);
+    let mut schemas: Option<SchemaCache> = None;
+    let mut status = 0;
+
+    if channel.pty {
+        let who = bs58::encode(&user.peer).into_string();
+        let comment = if user.comment.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", user.comment)
+        };
+        let greeting = format!(
+            "vos console — signed in as {who}{comment}.\r\n\
+             Agents are commands: `<agent> <method> key=value`. Tab completes; \
+             `exit` leaves.\r\n"
+        );
+        channel.send(t, ctx, greeting.as_bytes(), false).await?;
+    }
+    let mut echo = Vec::new();
+    editor.prompt(&mut echo);
+    channel.send(t, ctx, &echo, false).await?;
+
+    loop {
+        let Some(input) = channel.read(t, ctx).await? else {
+            // End of input: a script piped without a trailing newline
+            // still runs its last command.
+            if let Some(command) = editor.take_pending() {
+                let result = console.eval(ctx, &command).await;
+                channel.print(t, ctx, &result).await?;
+                status = exit_status(&result);
+            }
+            return Ok(status);
+        };
+        for byte in input {
+            let mut echo = Vec::new();
+            let outcome = editor.feed(byte, &mut echo);
+            match outcome {
+                None => {}
+                Some(Outcome::Eof) => {
+                    channel.send(t, ctx, b"\r\n", false).await?;
+                    return Ok(status);
+                }
+                Some(Outcome::Complete) => {
+                    if schemas.is_none() {
+                        schemas = console.schemas(ctx).await.ok();
+                    }
+                    if let Some(schemas) = &schemas {
+                        let completion = editor::complete(&editor.current_line(), schemas);
+                        editor.complete(completion, &mut echo);
+                    }
+                }
+                Some(Outcome::Line(line)) => {
+                    channel.send(t, ctx, &echo, false).await?;
+                    echo.clear();
+                    match line.trim() {
+                        "" => {}
+                        "exit" | "quit" => return Ok(status),
+                        "clear" if channel.pty => echo.extend_from_slice(b"\x1b[H\x1b[2J"),
+                        command => {
+                            let result = console.eval(ctx, command).await;
+                            channel.print(t, ctx, &result).await?;
+                            status = exit_status(&result);
+                            if console.wedged() {
+                                let text =
+                                    b"ssh-console: the console engine stopped responding\r\n";
+                                channel.send(t, ctx, text, true).await?;
+                                return Ok(1);
+                            }
+                        }
+                    }
+                    editor.prompt(&mut echo);
+                }
+            }
+            channel.send(t, ctx, &echo, false).await?;
+        }
+    }
+}
+
+fn exit_status(result: &EvalResult) -> u32 {
+    match (result.is_error, result.forbidden) {
+        (false, _) => 0,
+        (true, true) => EXIT_FORBIDDEN,
+        (true, false) => 1,
+    }
+}
+
+/// How the client started the session.
+enum Start {
+    Shell,
+    Exec(String),
+}
+
+/// The session channel, from both sides.
+struct Channel {
+    /// The client's channel number, which every message we send names.
+    remote_id: u32,
+    /// Bytes we may still send before the client adjusts the window.
+    remote_window: u32,
+    remote_max_packet: u32,
+    /// Bytes the client may still send.
+    local_window: u32,
+    /// Received and not yet consumed.
+    input: Vec<u8>,
+    /// A `pty-req` was granted: echo, `\r\n` line endings, colour.
+    pty: bool,
+    start: Option<Start>,
+    /// The client sent `EOF`: no more input.
+    eof: bool,
+    /// The client sent `CLOSE`.
+    closed: bool,
+}
+
+impl Channel {
+    /// Wait for the client to open its `session` channel and confirm it.
+    async fn accept(t: &mut Transport<'_>, ctx: &mut Context<SshConsole>) -> Result<Self, End> {
+        loop {
+            let packet = t.read_packet(ctx).await?;
+            match packet[0] {
+                wire::MSG_CHANNEL_OPEN => {
+                    let mut r = Reader::new(&packet[1..]);
+                    let (Some(kind), Some(remote_id), Some(window), Some(max_packet)) =
+                        (r.string(), r.u32(), r.u32(), r.u32())
+                    else {
+                        return Err(End::Refuse(
+                            wire::DISCONNECT_PROTOCOL_ERROR,
+                            "bad CHANNEL_OPEN",
+                        ));
+                    };
+                    if kind != b"session" {
+                        refuse_open(
+                            t,
+                            ctx,
+                            remote_id,
+                            wire::OPEN_UNKNOWN_CHANNEL_TYPE,
+                            "only session channels are served",
+                        )
+                        .await?;
+                        continue;
+                    }
+                    let confirm = Writer::new(wire::MSG_CHANNEL_OPEN_CONFIRMATION)
+                        .u32(remote_id)
+                        .u32(0)
+                        .u32(LOCAL_WINDOW)
+                        .u32(LOCAL_MAX_PACKET)
+                        .finish();
+                    t.write_packet(ctx, &confirm).await?;
+                    return Ok(Self {
+                        remote_id,
+                        remote_window: window,
+                        remote_max_packet: max_packet.clamp(1, LOCAL_MAX_PACKET),
+                        local_window: LOCAL_WINDOW,
+                        input: Vec::new(),
+                        pty: false,
+                        start: None,
+                        eof: false,
+                        closed: false,
+                    });
+                }
+                wire::MSG_GLOBAL_REQUEST => decline_global(t, ctx, &packet).await?,
+                wire::MSG_REQUEST_SUCCESS | wire::MSG_REQUEST_FAILURE => {}
+                _ => t.unimplemented(ctx).await?,
+            }
+        }
+    }
+
+    /// The next input bytes, or `None` once the client has sent `EOF` (or
+    /// closed the channel). Consuming input reopens the window.
+    async fn read(
+        &mut self,
+        t: &mut Transport<'_>,
+        ctx: &mut Context<SshConsole>,
+    ) -> Result<Option<Vec<u8>>, End> {
+        while self.input.is_empty() {
+            if self.eof || self.closed {
+                return Ok(None);
+            }
+            self.pump(t, ctx).await?;
+        }
+        let input = std::mem::take(&mut self.input);
+        if self.local_window < LOCAL_WINDOW / 2 {
+            let adjust = Writer::new(wire::MSG_CHANNEL_WINDOW_ADJUST)
+                .u32(self.remote_id)
+                .u32(LOCAL_WINDOW - self.local_window)
+                .finish();
+            t.write_packet(ctx, &adjust).await?;
+            self.local_window = LOCAL_WINDOW;
+        }
+        Ok(Some(input))
+    }
+
+    /// Send `data` on stdout (or stderr), as the client's window allows.
+    async fn send(
+        &mut self,
+        t: &mut Transport<'_>,
+        ctx: &mut Context<SshConsole>,
+        mut data: &[u8],
+        stderr: bool,
+    ) -> Result<(), End> {
+        while !data.is_empty() {
+            while self.remote_window == 0 {
+                if self.closed {
+                    return Err(End::Gone);
+                }
+                self.pump(t, ctx).await?;
+            }
+            let n = data
+                .len()
+                .min(self.remote_window as usize)
+                .min(self.remote_max_packet as usize);
+            let (chunk, rest) = data.split_at(n);
+            let packet = if stderr {
+                Writer::new(wire::MSG_CHANNEL_EXTENDED_DATA)
+                    .u32(self.remote_id)
+                    .u32(wire::EXTENDED_DATA_STDERR)
+                    .string(chunk)
+                    .finish()
+            } else {
+                Writer::new(wire::MSG_CHANNEL_DATA)
+                    .u32(self.remote_id)
+                    .string(chunk)
+                    .finish()
+            };
+            t.write_packet(ctx, &packet).await?;
+            self.remote_window -= n as u32;
+            data = rest;
+        }
+        Ok(())
+    }
+
+    /// Show a command's result: output on stdout, errors on stderr (in
+    /// red on a terminal).
+    async fn print(
+        &mut self,
+        t: &mut Transport<'_>,
+        ctx: &mut Context<SshConsole>,
+        result: &EvalResult,
+    ) -> Result<(), End> {
+        if result.output.is_empty() {
+            return Ok(());
+        }
+        let mut text = if self.pty {
+            result.output.replace('\n', "\r\n")
+        } else {
+            result.output.clone()
+        };
+        if self.pty && result.is_error {
+            text = format!("\x1b[31m{text}\x1b[0m");
+        }
+        text.push_str(if self.pty { "\r\n" } else { "\n" });
+        self.send(t, ctx, text.as_bytes(), result.is_error).await
+    }
+
+    /// Report `status`, close our side, and wait for the client's `CLOSE`.
+    async fn close(
+        &mut self,
+        t: &mut Transport<'_>,
+        ctx: &mut Context<SshConsole>,
+        status: u32,
+    ) -> Result<(), End> {
+        if !self.closed {
+            let exit = Writer::new(wire::MSG_CHANNEL_REQUEST)
+                .u32(self.remote_id)
+                .string("exit-status")
+                .bool(false)
+                .u32(status)
+                .finish();
+            t.write_packet(ctx, &exit).await?;
+            let eof = Writer::new(wire::MSG_CHANNEL_EOF)
+                .u32(self.remote_id)
+                .finish();
+            t.write_packet(ctx, &eof).await?;
+        }
+        let close = Writer::new(wire::MSG_CHANNEL_CLOSE)
+            .u32(self.remote_id)
+            .finish();
+        t.write_packet(ctx, &close).await?;
+        while !self.closed {
+            self.pump(t, ctx).await?;
+        }
+        Ok(())
+    }
+
+    /// Read and handle one packet: buffer data, track the windows, answer
+    /// requests.
+    async fn pump(
+        &mut self,
+        t: &mut Transport<'_>,
+        ctx: &mut Context<SshConsole>,
+    ) -> Result<(), End> {
+        let packet = t.read_packet(ctx).await?;
+        let mut r = Reader::new(&packet[1..]);
+        match packet[0] {
+            wire::MSG_CHANNEL_DATA | wire::MSG_CHANNEL_EXTENDED_DATA => {
+                let extended = packet[0] == wire::MSG_CHANNEL_EXTENDED_DATA;
+                let data = channel_data(&mut r, extended).ok_or(End::Refuse(
+                    wire::DISCONNECT_PROTOCOL_ERROR,
+                    "bad CHANNEL_DATA",
+                ))?;
+                let len = data.len() as u32;
+                if len > self.local_window || len > LOCAL_MAX_PACKET {
+                    return Err(End::Refuse(
+                        wire::DISCONNECT_PROTOCOL_ERROR,
+                        "channel data beyond the window",
+                    ));
+                }
+                self.local_window -= len;
+                // Stderr from the client means nothing to a console.
+                if !extended && !self.eof {
+                    self.input.extend_from_slice(data);
+                }
+            }
+            wire::MSG_CHANNEL_WINDOW_ADJUST => {
+                let grant = r.u32().and_then(|_| r.u32()).unwrap_or(0);
+                self.remote_window = self.remote_window.saturating_add(grant);
+            }
+            wire::MSG_CHANNEL_EOF => self.eof = true,
+            wire::MSG_CHANNEL_CLOSE => self.closed = true,
+            wire::MSG_CHANNEL_REQUEST => self.request(t, ctx, &mut r).await?,
+            wire::MSG_CHANNEL_OPEN => {
+                let remote_id = r.string().and_then(|_| r.u32()).unwrap_or(0);
+                refuse_open(
+                    t,
+                    ctx,
+                    remote_id,
+                    wire::OPEN_ADMINISTRATIVELY_PROHIBITED,
+                    "one session per connection",
+                )
+                .await?;
+            }
+            wire::MSG_GLOBAL_REQUEST => decline_global(t, ctx, &packet).await?,
+            // Keepalive replies.
+            wire::MSG_REQUEST_SUCCESS | wire::MSG_REQUEST_FAILURE => {}
+            _ => t.unimplemented(ctx).await?,
+        }
+        Ok(())
+    }
+
+    /// Answer one `CHANNEL_REQUEST`. A terminal, and the first `shell` or
+    /// `exec`, are granted; `window-change` needs no answer (output isn't
+    /// laid out to the width) and everything else is declined.
+    async fn request(
+        &mut self,
+        t: &mut Transport<'_>,
+        ctx: &mut Context<SshConsole>,
+        r: &mut Reader<'_>,
+    ) -> Result<(), End> {
+        let (Some(_), Some(kind), Some(want_reply)) = (r.u32(), r.string(), r.bool()) else {
+            return Err(End::Refuse(
+                wire::DISCONNECT_PROTOCOL_ERROR,
+                "bad CHANNEL_REQUEST",
+            ));
+        };
+        let granted = match kind {
+            b"pty-req" if self.start.is_none() => {
+                self.pty = true;
+                true
+            }
+            b"shell" if self.start.is_none() => {
+                self.start = Some(Start::Shell);
+                true
+            }
+            b"exec" if self.start.is_none() => match r.string() {
+                Some(command) => {
+                    let command = String::from_utf8_lossy(command).into_owned();
+                    self.start = Some(Start::Exec(command));
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        };
+        if want_reply {
+            let msg = if granted {
+                wire::MSG_CHANNEL_SUCCESS
+            } else {
+                wire::MSG_CHANNEL_FAILURE
+            };
+            let reply = Writer::new(msg).u32(self.remote_id).finish();
+            t.write_packet(ctx, &reply).await?;
+        }
+        Ok(())
+    }
+}
+
+/// The payload of `CHANNEL_DATA`, or of `CHANNEL_EXTENDED_DATA` after its
+/// type code.
+fn channel_data<'a>(r: &mut Reader<'a>, extended: bool) -> Option<&'a [u8]> {
+    r.u32()?;
+    if extended {
+        r.u32()?;
+    }
+    r.string()
+}
+
+async fn refuse_open(
+    t: &mut Transport<'_>,
+    ctx: &mut Context<SshConsole>,
+    remote_id: u32,
+    reason: u32,
+    text: &str,
+) -> Result<(), End> {
+    let failure = Writer::new(wire::MSG_CHANNEL_OPEN_FAILURE)
+        .u32(remote_id)
+        .u32(reason)
+        .string(text)
+        .string("")
+        .finish();
+    t.write_packet(ctx, &failure).await
+}
+
+/// Decline a global request (port forwarding, host-key rotation, the
+/// client's own keepalives), answering if it asked for an answer.
+async fn decline_global(
+    t: &mut Transport<'_>,
+    ctx: &mut Context<SshConsole>,
+    packet: &[u8],
+) -> Result<(), End> {
+    let mut r = Reader::new(&packet[1..]);
+    if r.string().and_then(|_| r.bool()) == Some(true) {
+        t.write_packet(ctx, &[wire::MSG_REQUEST_FAILURE]).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(is_error: bool, forbidden: bool) -> EvalResult {
+        EvalResult {
+            output: String::new(),
+            is_error,
+            forbidden,
+        }
+    }
+
+    #[test]
+    fn a_role_refusal_exits_with_ex_noperm() {
+        assert_eq!(exit_status(&result(false, false)), 0);
+        assert_eq!(exit_status(&result(true, false)), 1);
+        assert_eq!(exit_status(&result(true, true)), EXIT_FORBIDDEN);
+        assert_eq!(EXIT_FORBIDDEN, 77);
+        // Only a failed call counts as refused.
+        assert_eq!(exit_status(&result(false, true)), 0);
+    }
+
+    #[test]
+    fn channel_data_skips_the_recipient_and_stream_type() {
+        let data = Writer::new(wire::MSG_CHANNEL_DATA)
+            .u32(0)
+            .string("ls\n")
+            .finish();
+        assert_eq!(
+            channel_data(&mut Reader::new(&data[1..]), false),
+            Some(&b"ls\n"[..])
+        );
+        let extended = Writer::new(wire::MSG_CHANNEL_EXTENDED_DATA)
+            .u32(0)
+            .u32(wire::EXTENDED_DATA_STDERR)
+            .string("oops")
+            .finish();
+        assert_eq!(
+            channel_data(&mut Reader::new(&extended[1..]), true),
+            Some(&b"oops"[..])
+        );
+        assert_eq!(channel_data(&mut Reader::new(&data[1..5]), false), None);
+    }
+}
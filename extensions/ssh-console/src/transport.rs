@@ -0,0 +1,577 @@
+//! The SSH transport layer (RFC 4253) over one host connection: version
+//! exchange, `curve25519-sha256` key exchange signed by the `ssh-ed25519`
+//! host key, `chacha20-poly1305@openssh.com` packet protection, rekeying
+//! when the client asks, and keepalives. A connection whose packet
+//! sequence numbers run to [`MAX_PACKETS`] without a strict rekey
+//! resetting them is disconnected, so a number never wraps under one key.
+//!
+//! One algorithm per slot keeps the negotiation trivial: any OpenSSH
+//! since 6.5 (and PuTTY, Dropbear, libssh) offers all three. Strict key
+//! exchange (`kex-strict-*-v00@openssh.com`, the Terrapin countermeasure)
+//! is honoured when the client offers it.
+//!
+//! Incoming bytes are buffered raw and deciphered one packet at a time,
+//! so a packet that arrives in the same read as the client's `NEWKEYS`
+//! is opened under the new keys.
+
+use ed25519_dalek::Signer;
+use sha2::{Digest, Sha256};
+use vos::prelude::*;
+
+use crate::SshConsole;
+use crate::cipher::{Cipher, PacketError};
+use crate::wire::{self, Reader, Writer};
+
+/// Sent before anything else; `V_S` in the exchange hash.
+const SERVER_VERSION: &[u8] = b"SSH-2.0-vos_ssh_console";
+
+const KEX_ALGORITHMS: &[&str] = &["curve25519-sha256", "curve25519-sha256@libssh.org"];
+const HOST_KEY_ALGORITHM: &str = "ssh-ed25519";
+const CIPHER: &str = "chacha20-poly1305@openssh.com";
+/// Advertised for clients that insist on a MAC list; never used — the
+/// AEAD cipher authenticates packets itself.
+const MACS: &[&str] = &["hmac-sha2-256", "hmac-sha2-512"];
+const STRICT_KEX_SERVER: &str = "kex-strict-s-v00@openssh.com";
+const STRICT_KEX_CLIENT: &str = "kex-strict-c-v00@openssh.com";
+
+/// Read size for one `ctx.read`.
+const READ_CHUNK: u32 = 32 * 1024;
+
+/// Bytes of preamble (lines before the client's version string) tolerated.
+const MAX_PREAMBLE: usize = 8 * 1024;
+
+/// Packets either direction may carry under one run of sequence numbers
+/// (the ChaCha20 nonce). Only a strict-mode rekey starts a new run; a
+/// connection that reaches the limit is dropped. OpenSSH rekeys at the
+/// same count, so a strict-KEX client never reaches it; any other is
+/// disconnected long before the `u32` would wrap and repeat a nonce.
+const MAX_PACKETS: u32 = 1 << 31;
+
+/// Host idle timeouts (each 30 s) in a row before an authenticated
+/// session is dropped. Each one sends a keepalive first, so a live
+/// client's reply resets the count.
+const MAX_IDLE_READS: u32 = 3;
+
+/// How a connection ends. `?` carries it up to `handle_connection`.
+#[derive(Debug)]
+pub(crate) enum End {
+    /// The peer is gone (EOF, read error, its own `DISCONNECT`) or the
+    /// session finished — nothing left to say.
+    Gone,
+    /// We're ending it: send `DISCONNECT` with this reason first.
+    Refuse(u32, &'static str),
+}
+
+pub(crate) struct Transport<'k> {
+    conn: u64,
+    host_key: &'k ed25519_dalek::SigningKey,
+    /// Raw bytes read but not yet consumed as a packet.
+    rx: Vec<u8>,
+    seq_in: u32,
+    seq_out: u32,
+    cipher_in: Cipher,
+    cipher_out: Cipher,
+    /// `V_C`, without the line ending.
+    client_version: Vec<u8>,
+    /// `H` of the first key exchange; fixed for the connection.
+    session_id: Option<[u8; 32]>,
+    /// Strict key exchange agreed on the first KEXINIT.
+    strict: bool,
+    /// Answer host idle timeouts with a keepalive instead of hanging up.
+    /// Set once the session is authenticated — before that, an idle peer
+    /// is exactly what the timeout is for.
+    keepalive: bool,
+    idle_reads: u32,
+}
+
+impl<'k> Transport<'k> {
+    pub(crate) fn new(conn: u64, host_key: &'k ed25519_dalek::SigningKey) -> Self {
+        Self {
+            conn,
+            host_key,
+            rx: Vec::new(),
+            seq_in: 0,
+            seq_out: 0,
+            cipher_in: Cipher::Plain,
+            cipher_out: Cipher::Plain,
+            client_version: Vec::new(),
+            session_id: None,
+            strict: false,
+            keepalive: false,
+            idle_reads: 0,
+        }
+    }
+
+    /// Exchange versions and run the first key exchange.
+    pub(crate) async fn handshake(&mut self, ctx: &mut Context<SshConsole>) -> Result<(), End> {
+        let mut hello = SERVER_VERSION.to_vec();
+        hello.extend_from_slice(b"\r\n");
+        self.write_raw(ctx, &hello).await?;
+        self.client_version = self.read_version(ctx).await?;
+        self.key_exchange(ctx, None).await
+    }
+
+    pub(crate) fn session_id(&self) -> &[u8] {
+        self.session_id.as_ref().map_or(&[], |id| &id[..])
+    }
+
+    pub(crate) fn enable_keepalive(&mut self) {
+        self.keepalive = true;
+    }
+
+    /// The next packet for the layers above. Transport noise (`IGNORE`,
+    /// `DEBUG`, `UNIMPLEMENTED`) is skipped and a client `KEXINIT` is
+    /// served inline, so callers only see their own messages.
+    pub(crate) async fn read_packet(
+        &mut self,
+        ctx: &mut Context<SshConsole>,
+    ) -> Result<Vec<u8>, End> {
+        loop {
+            let payload = self.next_packet(ctx).await?;
+            match payload[0] {
+                wire::MSG_IGNORE | wire::MSG_DEBUG | wire::MSG_UNIMPLEMENTED => {}
+                wire::MSG_DISCONNECT => return Err(End::Gone),
+                wire::MSG_KEXINIT => self.key_exchange(ctx, Some(payload)).await?,
+                _ => return Ok(payload),
+            }
+        }
+    }
+
+    pub(crate) async fn write_packet(
+        &mut self,
+        ctx: &mut Context<SshConsole>,
+        payload: &[u8],
+    ) -> Result<(), End> {
+        // The DISCONNECT reporting an exhausted counter still goes out.
+        if payload.first() != Some(&wire::MSG_DISCONNECT) {
+            check_sequence(self.seq_out)?;
+        }
+        let packet = self.cipher_out.seal(self.seq_out, payload);
+        self.seq_out = self.seq_out.wrapping_add(1);
+        self.write_raw(ctx, &packet).await
+    }
+
+    /// Answer the packet just read with `UNIMPLEMENTED` (RFC 4253 §11.4).
+    pub(crate) async fn unimplemented(&mut self, ctx: &mut Context<SshConsole>) -> Result<(), End> {
+        let msg = Writer::new(wire::MSG_UNIMPLEMENTED)
+            .u32(self.seq_in.wrapping_sub(1))
+            .finish();
+        self.write_packet(ctx, &msg).await
+    }
+
+    /// Best-effort `DISCONNECT` on the way out. A no-op before the version
+    /// exchange is done, when the peer couldn't parse it anyway.
+    pub(crate) async fn disconnect(
+        &mut self,
+        ctx: &mut Context<SshConsole>,
+        reason: u32,
+        text: &str,
+    ) {
+        if self.client_version.is_empty() {
+            return;
+        }
+        let msg = Writer::new(wire::MSG_DISCONNECT)
+            .u32(reason)
+            .string(text)
+            .string("")
+            .finish();
+        let _ = self.write_packet(ctx, &msg).await;
+    }
+
+    /// The client's version line, skipping any preamble lines before it.
+    async fn read_version(&mut self, ctx: &mut Context<SshConsole>) -> Result<Vec<u8>, End> {
+        loop {
+            if let Some(nl) = self.rx.iter().position(|&b| b == b'\n') {
+                let mut line: Vec<u8> = self.rx.drain(..=nl).collect();
+                line.pop();
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                if line.starts_with(b"SSH-") {
+                    if !(line.starts_with(b"SSH-2.0-") || line.starts_with(b"SSH-1.99-")) {
+                        let _ = self
+                            .write_raw(ctx, b"Protocol major versions differ.\r\n")
+                            .await;
+                        return Err(End::Gone);
+                    }
+                    return Ok(line);
+                }
+                continue;
+            }
+            if self.rx.len() > MAX_PREAMBLE {
+                return Err(End::Gone);
+            }
+            self.fill(ctx).await?;
+        }
+    }
+
+    /// Negotiate, exchange ECDH keys, sign, and switch both directions to
+    /// the new keys. `client_init` is the client's `KEXINIT` when it opened
+    /// a re-exchange; on the first exchange it is read here.
+    async fn key_exchange(
+        &mut self,
+        ctx: &mut Context<SshConsole>,
+        client_init: Option<Vec<u8>>,
+    ) -> Result<(), End> {
+        let first = self.session_id.is_none();
+        let server_init = self.kexinit(first);
+        self.write_packet(ctx, &server_init).await?;
+        let client_init = match client_init {
+            Some(init) => init,
+            None => self.next_kex_packet(ctx, wire::MSG_KEXINIT).await?,
+        };
+
+        let offer = parse_kexinit(&client_init)
+            .ok_or(End::Refuse(wire::DISCONNECT_PROTOCOL_ERROR, "bad KEXINIT"))?;
+        if first && offer.kex.contains(&STRICT_KEX_CLIENT) {
+            // Strict mode also demands the client's KEXINIT be its very
+            // first packet.
+            if self.seq_in != 1 {
+                return Err(End::Refuse(
+                    wire::DISCONNECT_PROTOCOL_ERROR,
+                    "strict KEX: KEXINIT was not the first packet",
+                ));
+            }
+            self.strict = true;
+        }
+        let kex = offer
+            .kex
+            .iter()
+            .copied()
+            .find(|k| KEX_ALGORITHMS.contains(k));
+        let agreed = kex.is_some()
+            && offer.host_key.contains(&HOST_KEY_ALGORITHM)
+            && offer.cipher_c2s.contains(&CIPHER)
+            && offer.cipher_s2c.contains(&CIPHER)
+            && offer.compression_c2s.contains(&"none")
+            && offer.compression_s2c.contains(&"none");
+        if !agreed {
+            return Err(End::Refuse(
+                wire::DISCONNECT_KEY_EXCHANGE_FAILED,
+                "no common algorithms: this server speaks curve25519-sha256, \
+                 ssh-ed25519 and chacha20-poly1305@openssh.com",
+            ));
+        }
+        // A wrong guess means the packet after the KEXINIT is for a method
+        // we didn't pick (RFC 4253 §7).
+        if offer.first_kex_follows
+            && (offer.kex.first() != kex.as_ref()
+                || offer.host_key.first() != Some(&HOST_KEY_ALGORITHM))
+        {
+            self.next_packet(ctx).await?;
+        }
+
+        let init = self.next_kex_packet(ctx, wire::MSG_KEX_ECDH_INIT).await?;
+        let q_c: [u8; 32] = Reader::new(&init[1..])
+            .string()
+            .and_then(|q| q.try_into().ok())
+            .ok_or(End::Refuse(
+                wire::DISCONNECT_KEY_EXCHANGE_FAILED,
+                "bad ECDH_INIT",
+            ))?;
+        let mut ephemeral = [0u8; 32];
+        getrandom::getrandom(&mut ephemeral)
+            .map_err(|_| End::Refuse(wire::DISCONNECT_KEY_EXCHANGE_FAILED, "no entropy"))?;
+        let secret = x25519_dalek::StaticSecret::from(ephemeral);
+        let q_s = x25519_dalek::PublicKey::from(&secret).to_bytes();
+        let shared = secret.diffie_hellman(&x25519_dalek::PublicKey::from(q_c));
+        if !shared.was_contributory() {
+            return Err(End::Refuse(
+                wire::DISCONNECT_KEY_EXCHANGE_FAILED,
+                "degenerate ECDH key",
+            ));
+        }
+        let mut k = Vec::with_capacity(37);
+        wire::put_mpint(&mut k, shared.as_bytes());
+
+        let host_blob = host_key_blob(&self.host_key.verifying_key());
+        let mut hashed = Vec::new();
+        wire::put_string(&mut hashed, &self.client_version);
+        wire::put_string(&mut hashed, SERVER_VERSION);
+        wire::put_string(&mut hashed, &client_init);
+        wire::put_string(&mut hashed, &server_init);
+        wire::put_string(&mut hashed, &host_blob);
+        wire::put_string(&mut hashed, &q_c);
+        wire::put_string(&mut hashed, &q_s);
+        hashed.extend_from_slice(&k);
+        let h: [u8; 32] = Sha256::digest(&hashed).into();
+        let session_id = *self.session_id.get_or_insert(h);
+
+        let signature = self.host_key.sign(&h).to_bytes();
+        let mut sig_blob = Vec::new();
+        wire::put_string(&mut sig_blob, HOST_KEY_ALGORITHM.as_bytes());
+        wire::put_string(&mut sig_blob, &signature);
+        let reply = Writer::new(wire::MSG_KEX_ECDH_REPLY)
+            .string(&host_blob)
+            .string(q_s)
+            .string(&sig_blob)
+            .finish();
+        self.write_packet(ctx, &reply).await?;
+
+        // Each direction switches right after its own NEWKEYS.
+        self.write_packet(ctx, &[wire::MSG_NEWKEYS]).await?;
+        self.cipher_out = Cipher::chacha(&derive_key(&k, &h, b'D', &session_id));
+        if self.strict {
+            self.seq_out = 0;
+        }
+        self.next_kex_packet(ctx, wire::MSG_NEWKEYS).await?;
+        self.cipher_in = Cipher::chacha(&derive_key(&k, &h, b'C', &session_id));
+        if self.strict {
+            self.seq_in = 0;
+        }
+        Ok(())
+    }
+
+    /// The next packet of a key exchange, which must be `expected`. Outside
+    /// strict mode the generic transport messages may interleave.
+    async fn next_kex_packet(
+        &mut self,
+        ctx: &mut Context<SshConsole>,
+        expected: u8,
+    ) -> Result<Vec<u8>, End> {
+        loop {
+            let payload = self.next_packet(ctx).await?;
+            match payload[0] {
+                msg if msg == expected => return Ok(payload),
+                wire::MSG_DISCONNECT => return Err(End::Gone),
+                wire::MSG_IGNORE | wire::MSG_DEBUG | wire::MSG_UNIMPLEMENTED if !self.strict => {}
+                _ => {
+                    return Err(End::Refuse(
+                        wire::DISCONNECT_PROTOCOL_ERROR,
+                        "unexpected message during key exchange",
+                    ));
+                }
+            }
+        }
+    }
+
+    /// One packet's payload, reading until it has fully arrived.
+    async fn next_packet(&mut self, ctx: &mut Context<SshConsole>) -> Result<Vec<u8>, End> {
+        loop {
+            check_sequence(self.seq_in)?;
+            match self.cipher_in.open(self.seq_in, &self.rx) {
+                Ok(Some((payload, used))) => {
+                    self.rx.drain(..used);
+                    self.seq_in += 1;
+                    if payload.is_empty() {
+                        return Err(End::Refuse(wire::DISCONNECT_PROTOCOL_ERROR, "empty packet"));
+                    }
+                    return Ok(payload);
+                }
+                Ok(None) => self.fill(ctx).await?,
+                Err(PacketError::Malformed) => {
+                    return Err(End::Refuse(
+                        wire::DISCONNECT_PROTOCOL_ERROR,
+                        "bad packet length",
+                    ));
+                }
+                Err(PacketError::BadTag) => {
+                    return Err(End::Refuse(wire::DISCONNECT_MAC_ERROR, "corrupt packet"));
+                }
+            }
+        }
+    }
+
+    /// Read more bytes into `rx`. A host idle timeout on an authenticated
+    /// session sends a keepalive (a global request the client must answer)
+    /// and keeps waiting, up to [`MAX_IDLE_READS`].
+    async fn fill(&mut self, ctx: &mut Context<SshConsole>) -> Result<(), End> {
+        loop {
+            match ctx.read(self.conn, READ_CHUNK).await {
+                Some(data) if !data.is_empty() => {
+                    self.rx.extend_from_slice(&data);
+                    self.idle_reads = 0;
+                    return Ok(());
+                }
+                Some(_) => return Err(End::Gone),
+                None if self.keepalive && self.idle_reads < MAX_IDLE_READS => {
+                    self.idle_reads += 1;
+                    let ping = Writer::new(wire::MSG_GLOBAL_REQUEST)
+                        .string("keepalive@openssh.com")
+                        .bool(true)
+                        .finish();
+                    self.write_packet(ctx, &ping).await?;
+                }
+                None => return Err(End::Gone),
+            }
+        }
+    }
+
+    async fn write_raw(
+        &mut self,
+        ctx: &mut Context<SshConsole>,
+        mut bytes: &[u8],
+    ) -> Result<(), End> {
+        while !bytes.is_empty() {
+            match ctx.write(self.conn, bytes).await {
+                Some(n) if n > 0 => bytes = &bytes[n..],
+                _ => return Err(End::Gone),
+            }
+        }
+        Ok(())
+    }
+
+    fn kexinit(&self, first: bool) -> Vec<u8> {
+        let mut cookie = [0u8; 16];
+        let _ = getrandom::getrandom(&mut cookie);
+        let mut kex = KEX_ALGORITHMS.to_vec();
+        // Only the first KEXINIT advertises strict mode.
+        if first {
+            kex.push(STRICT_KEX_SERVER);
+        }
+        Writer::new(wire::MSG_KEXINIT)
+            .raw(&cookie)
+            .name_list(&kex)
+            .name_list(&[HOST_KEY_ALGORITHM])
+            .name_list(&[CIPHER])
+            .name_list(&[CIPHER])
+            .name_list(MACS)
+            .name_list(MACS)
+            .name_list(&["none"])
+            .name_list(&["none"])
+            .name_list(&[])
+            .name_list(&[])
+            .bool(false)
+            .u32(0)
+            .finish()
+    }
+}
+
+/// Refuse to use sequence number `seq` once [`MAX_PACKETS`] have gone by
+/// under it.
+fn check_sequence(seq: u32) -> Result<(), End> {
+    if seq >= MAX_PACKETS {
+        return Err(End::Refuse(
+            wire::DISCONNECT_BY_APPLICATION,
+            "packet sequence number exhausted: rekey (strict KEX) or reconnect",
+        ));
+    }
+    Ok(())
+}
+
+/// The client's `KEXINIT` lists this server consults.
+struct KexOffer<'a> {
+    kex: Vec<&'a str>,
+    host_key: Vec<&'a str>,
+    cipher_c2s: Vec<&'a str>,
+    cipher_s2c: Vec<&'a str>,
+    compression_c2s: Vec<&'a str>,
+    compression_s2c: Vec<&'a str>,
+    first_kex_follows: bool,
+}
+
+fn parse_kexinit(payload: &[u8]) -> Option<KexOffer<'_>> {
+    let mut r = Reader::new(payload.get(1..)?);
+    r.bytes(16)?;
+    let kex = r.name_list()?;
+    let host_key = r.name_list()?;
+    let cipher_c2s = r.name_list()?;
+    let cipher_s2c = r.name_list()?;
+    r.name_list()?;
+    r.name_list()?;
+    let compression_c2s = r.name_list()?;
+    let compression_s2c = r.name_list()?;
+    r.name_list()?;
+    r.name_list()?;
+    let first_kex_follows = r.bool()?;
+    r.u32()?;
+    Some(KexOffer {
+        kex,
+        host_key,
+        cipher_c2s,
+        cipher_s2c,
+        compression_c2s,
+        compression_s2c,
+        first_kex_follows,
+    })
+}
+
+/// `string "ssh-ed25519" || string key` — the public key blob (RFC 8709).
+pub(crate) fn host_key_blob(key: &ed25519_dalek::VerifyingKey) -> Vec<u8> {
+    let mut blob = Vec::with_capacity(51);
+    wire::put_string(&mut blob, HOST_KEY_ALGORITHM.as_bytes());
+    wire::put_string(&mut blob, key.as_bytes());
+    blob
+}
+
+/// RFC 4253 §7.2 key derivation, extended to the 64 bytes the cipher
+/// takes: `HASH(K || H || letter || session_id)`, then
+/// `HASH(K || H || first)`. `k` is already `mpint`-encoded.
+fn derive_key(k: &[u8], h: &[u8; 32], letter: u8, session_id: &[u8; 32]) -> [u8; 64] {
+    let first: [u8; 32] = Sha256::new()
+        .chain_update(k)
+        .chain_update(h)
+        .chain_update([letter])
+        .chain_update(session_id)
+        .finalize()
+        .into();
+    let second: [u8; 32] = Sha256::new()
+        .chain_update(k)
+        .chain_update(h)
+        .chain_update(first)
+        .finalize()
+        .into();
+    let mut out = [0u8; 64];
+    out[..32].copy_from_slice(&first);
+    out[32..].copy_from_slice(&second);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kexinit_offer_parses_the_lists_we_consult() {
+        let payload = Writer::new(wire::MSG_KEXINIT)
+            .raw(&[0; 16])
+            .name_list(&[
+                "sntrup761x25519-sha512",
+                "curve25519-sha256",
+                STRICT_KEX_CLIENT,
+            ])
+            .name_list(&["ssh-ed25519"])
+            .name_list(&[CIPHER, "aes128-gcm@openssh.com"])
+            .name_list(&[CIPHER])
+            .name_list(MACS)
+            .name_list(MACS)
+            .name_list(&["none", "zlib@openssh.com"])
+            .name_list(&["none"])
+            .name_list(&[])
+            .name_list(&[])
+            .bool(true)
+            .u32(0)
+            .finish();
+        let offer = parse_kexinit(&payload).unwrap();
+        assert_eq!(offer.kex[1], "curve25519-sha256");
+        assert!(offer.kex.contains(&STRICT_KEX_CLIENT));
+        assert_eq!(offer.cipher_c2s, [CIPHER, "aes128-gcm@openssh.com"]);
+        assert_eq!(offer.compression_c2s, ["none", "zlib@openssh.com"]);
+        assert!(offer.first_kex_follows);
+        assert!(parse_kexinit(&payload[..payload.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn sequence_numbers_stop_well_before_they_wrap() {
+        assert!(check_sequence(0).is_ok());
+        assert!(check_sequence(MAX_PACKETS - 1).is_ok());
+        assert!(matches!(
+            check_sequence(MAX_PACKETS),
+            Err(End::Refuse(wire::DISCONNECT_BY_APPLICATION, _))
+        ));
+        assert!(check_sequence(u32::MAX).is_err());
+    }
+
+    #[test]
+    fn derived_keys_depend_on_the_letter() {
+        let k = [0, 0, 0, 1, 5];
+        let (h, id) = ([1; 32], [2; 32]);
+        let c = derive_key(&k, &h, b'C', &id);
+        let d = derive_key(&k, &h, b'D', &id);
+        assert_ne!(c, d);
+        assert_eq!(
+            &c[..32],
+            Sha256::digest([&k[..], &h, b"C", &id].concat()).as_slice()
+        );
+    }
+}
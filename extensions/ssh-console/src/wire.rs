@@ -0,0 +1,198 @@
+//! SSH-2 data types (RFC 4251 §5) and the message numbers this server
+//! speaks. [`Writer`] builds a payload; [`Reader`] walks one, returning
+//! `None` on a short or malformed field so a hostile packet ends the
+//! connection instead of panicking the task.
+
+// Transport layer generic (RFC 4253).
+pub(crate) const MSG_DISCONNECT: u8 = 1;
+pub(crate) const MSG_IGNORE: u8 = 2;
+pub(crate) const MSG_UNIMPLEMENTED: u8 = 3;
+pub(crate) const MSG_DEBUG: u8 = 4;
+pub(crate) const MSG_SERVICE_REQUEST: u8 = 5;
+pub(crate) const MSG_SERVICE_ACCEPT: u8 = 6;
+pub(crate) const MSG_KEXINIT: u8 = 20;
+pub(crate) const MSG_NEWKEYS: u8 = 21;
+// curve25519-sha256 (RFC 8731, reusing the RFC 5656 ECDH numbers).
+pub(crate) const MSG_KEX_ECDH_INIT: u8 = 30;
+pub(crate) const MSG_KEX_ECDH_REPLY: u8 = 31;
+// User authentication (RFC 4252).
+pub(crate) const MSG_USERAUTH_REQUEST: u8 = 50;
+pub(crate) const MSG_USERAUTH_FAILURE: u8 = 51;
+pub(crate) const MSG_USERAUTH_SUCCESS: u8 = 52;
+pub(crate) const MSG_USERAUTH_PK_OK: u8 = 60;
+// Connection protocol (RFC 4254).
+pub(crate) const MSG_GLOBAL_REQUEST: u8 = 80;
+pub(crate) const MSG_REQUEST_SUCCESS: u8 = 81;
+pub(crate) const MSG_REQUEST_FAILURE: u8 = 82;
+pub(crate) const MSG_CHANNEL_OPEN: u8 = 90;
+pub(crate) const MSG_CHANNEL_OPEN_CONFIRMATION: u8 = 91;
+pub(crate) const MSG_CHANNEL_OPEN_FAILURE: u8 = 92;
+pub(crate) const MSG_CHANNEL_WINDOW_ADJUST: u8 = 93;
+pub(crate) const MSG_CHANNEL_DATA: u8 = 94;
+pub(crate) const MSG_CHANNEL_EXTENDED_DATA: u8 = 95;
+pub(crate) const MSG_CHANNEL_EOF: u8 = 96;
+pub(crate) const MSG_CHANNEL_CLOSE: u8 = 97;
+pub(crate) const MSG_CHANNEL_REQUEST: u8 = 98;
+pub(crate) const MSG_CHANNEL_SUCCESS: u8 = 99;
+pub(crate) const MSG_CHANNEL_FAILURE: u8 = 100;
+
+// Disconnect reason codes (RFC 4253 §11.1).
+pub(crate) const DISCONNECT_PROTOCOL_ERROR: u32 = 2;
+pub(crate) const DISCONNECT_KEY_EXCHANGE_FAILED: u32 = 3;
+pub(crate) const DISCONNECT_MAC_ERROR: u32 = 5;
+pub(crate) const DISCONNECT_SERVICE_NOT_AVAILABLE: u32 = 7;
+pub(crate) const DISCONNECT_BY_APPLICATION: u32 = 11;
+pub(crate) const DISCONNECT_NO_MORE_AUTH_METHODS: u32 = 14;
+
+// Channel open failure reason codes (RFC 4254 §5.1).
+pub(crate) const OPEN_ADMINISTRATIVELY_PROHIBITED: u32 = 1;
+pub(crate) const OPEN_UNKNOWN_CHANNEL_TYPE: u32 = 3;
+
+/// `SSH_EXTENDED_DATA_STDERR` (RFC 4254 §5.2).
+pub(crate) const EXTENDED_DATA_STDERR: u32 = 1;
+
+/// Builds one message payload, message number first.
+pub(crate) struct Writer(Vec<u8>);
+
+impl Writer {
+    pub(crate) fn new(msg: u8) -> Self {
+        Self(vec![msg])
+    }
+
+    pub(crate) fn u8(mut self, v: u8) -> Self {
+        self.0.push(v);
+        self
+    }
+
+    pub(crate) fn bool(self, v: bool) -> Self {
+        self.u8(v as u8)
+    }
+
+    pub(crate) fn u32(mut self, v: u32) -> Self {
+        self.0.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+
+    pub(crate) fn raw(mut self, bytes: &[u8]) -> Self {
+        self.0.extend_from_slice(bytes);
+        self
+    }
+
+    pub(crate) fn string(mut self, bytes: impl AsRef<[u8]>) -> Self {
+        put_string(&mut self.0, bytes.as_ref());
+        self
+    }
+
+    pub(crate) fn name_list(self, names: &[&str]) -> Self {
+        self.string(names.join(","))
+    }
+
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Append an SSH `string`: `uint32` length, then the bytes.
+pub(crate) fn put_string(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+/// Append `magnitude` (big-endian, unsigned) as an SSH `mpint`: leading
+/// zero bytes stripped, and one zero byte prepended when the high bit is
+/// set so the value stays positive.
+pub(crate) fn put_mpint(out: &mut Vec<u8>, magnitude: &[u8]) {
+    let start = magnitude
+        .iter()
+        .position(|&b| b != 0)
+        .unwrap_or(magnitude.len());
+    let digits = &magnitude[start..];
+    let pad = digits.first().is_some_and(|&b| b & 0x80 != 0);
+    out.extend_from_slice(&((digits.len() + pad as usize) as u32).to_be_bytes());
+    if pad {
+        out.push(0);
+    }
+    out.extend_from_slice(digits);
+}
+
+/// Walks a received payload.
+pub(crate) struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    pub(crate) fn u8(&mut self) -> Option<u8> {
+        let (&b, rest) = self.buf.split_first()?;
+        self.buf = rest;
+        Some(b)
+    }
+
+    pub(crate) fn bool(&mut self) -> Option<bool> {
+        self.u8().map(|b| b != 0)
+    }
+
+    pub(crate) fn u32(&mut self) -> Option<u32> {
+        let (head, rest) = self.buf.split_first_chunk::<4>()?;
+        self.buf = rest;
+        Some(u32::from_be_bytes(*head))
+    }
+
+    pub(crate) fn bytes(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.buf.len() < n {
+            return None;
+        }
+        let (head, rest) = self.buf.split_at(n);
+        self.buf = rest;
+        Some(head)
+    }
+
+    pub(crate) fn string(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.bytes(len)
+    }
+
+    pub(crate) fn utf8(&mut self) -> Option<&'a str> {
+        core::str::from_utf8(self.string()?).ok()
+    }
+
+    pub(crate) fn name_list(&mut self) -> Option<Vec<&'a str>> {
+        let list = self.utf8()?;
+        Some(if list.is_empty() {
+            Vec::new()
+        } else {
+            list.split(',').collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mpint_strips_zeros_and_keeps_the_value_positive() {
+        let enc = |m: &[u8]| {
+            let mut out = Vec::new();
+            put_mpint(&mut out, m);
+            out
+        };
+        // RFC 4251 §5 examples.
+        assert_eq!(enc(&[0, 0]), [0, 0, 0, 0]);
+        assert_eq!(
+            enc(&[0x09, 0xa3, 0x78, 0xf9, 0xb2, 0xe3, 0x32, 0xa7]),
+            [0, 0, 0, 8, 0x09, 0xa3, 0x78, 0xf9, 0xb2, 0xe3, 0x32, 0xa7]
+        );
+        assert_eq!(enc(&[0x00, 0x80]), [0, 0, 0, 2, 0x00, 0x80]);
+    }
+
+    #[test]
+    fn reader_refuses_a_string_longer_than_the_payload() {
+        let payload = Writer::new(MSG_IGNORE).u32(9).raw(b"short").finish();
+        let mut r = Reader::new(&payload[1..]);
+        assert_eq!(r.string(), None);
+    }
+}
@@ -0,0 +1,430 @@
+//! Interop tests against the OpenSSH client.
+//!
+//! Stands the console up in a `VosNode` next to the http-gateway's
+//! mock-registry + counter + kitchen-sink fixtures, then runs
+//! `/usr/bin/ssh` at it. Covers the full chain:
+//!
+//!   ssh → host accept loop → handle_connection (kex, publickey auth,
+//!     session channel) → console engine → ctx.ask_dispatch_as → host
+//!     proof check + intra_caps ceiling → kitchen-sink role gate
+//!     → exit-status → ssh's own exit code
+//!
+//! Each test starts its own node on its own port. Skips with a build
+//! hint if the console or a fixture `.so` is missing, and skips when
+//! the machine has no OpenSSH client.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use vos::abi::service::ServiceId;
+use vos::actors::IntraCap;
+use vos::node::{ExtensionConfig, VosNode};
+use vos::value::Args;
+
+const SSH: &str = "/usr/bin/ssh";
+const SSH_KEYGEN: &str = "/usr/bin/ssh-keygen";
+
+/// Host key seed; the client never checks it (`StrictHostKeyChecking=no`).
+const HOST_KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+
+// ── Path resolution ──────────────────────────────────────────────
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+struct FixturePaths {
+    console: PathBuf,
+    mock_registry: PathBuf,
+    counter: PathBuf,
+    kitchen: PathBuf,
+}
+
+impl FixturePaths {
+    fn discover() -> Self {
+        let profile = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+        let fixture = |name: &str, lib: &str| {
+            workspace_root()
+                .join("extensions/http-gateway/test_fixtures")
+                .join(name)
+                .join("target/debug")
+                .join(lib)
+        };
+        Self {
+            console: workspace_root()
+                .join("target")
+                .join(profile)
+                .join("libssh_console.so"),
+            mock_registry: fixture("mock-registry", "libmock_registry_extension.so"),
+            counter: fixture("counter", "libcounter_extension.so"),
+            kitchen: fixture("kitchen-sink", "libkitchen_sink_extension.so"),
+        }
+    }
+
+    /// `false` after printing what to build or install first.
+    fn ready(&self) -> bool {
+        let mut ready = true;
+        for tool in [SSH, SSH_KEYGEN] {
+            if !Path::new(tool).exists() {
+                eprintln!("skipping: {tool} is not installed");
+                ready = false;
+            }
+        }
+        if !self.console.exists() {
+            eprintln!("skipping: build the console first: cargo build -p ssh-console");
+            ready = false;
+        }
+        for so in [&self.mock_registry, &self.counter, &self.kitchen] {
+            if !so.exists() {
+                eprintln!(
+                    "skipping: cargo build --manifest-path {}/Cargo.toml",
+                    so.parent()
+                        .unwrap()
+                        .parent()
+                        .unwrap()
+                        .parent()
+                        .unwrap()
+                        .display(),
+                );
+                ready = false;
+            }
+        }
+        ready
+    }
+}
+
+// ── Client key ───────────────────────────────────────────────────
+
+/// A fresh `ssh-keygen` ed25519 key pair in a directory of its own.
+struct ClientKey {
+    dir: PathBuf,
+}
+
+impl ClientKey {
+    fn generate(tag: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "ssh-console-e2e-{}-{tag}-{}",
+            std::process::id(),
+            NEXT_PORT.load(Ordering::Relaxed),
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let status = Command::new(SSH_KEYGEN)
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", "member", "-f"])
+            .arg(dir.join("id"))
+            .status()
+            .expect("run ssh-keygen");
+        assert!(status.success(), "ssh-keygen failed");
+        Self { dir }
+    }
+
+    fn private(&self) -> PathBuf {
+        self.dir.join("id")
+    }
+
+    /// The `authorized_keys` line for this key.
+    fn authorized_line(&self) -> String {
+        std::fs::read_to_string(self.dir.join("id.pub"))
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+}
+
+impl Drop for ClientKey {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+// ── TestNode ─────────────────────────────────────────────────────
+
+/// Ports above the gateway suite's range, so both can run at once.
+static NEXT_PORT: AtomicU16 = AtomicU16::new(29220);
+
+/// Running VosNode + console. Drop signals shutdown and joins.
+struct TestNode {
+    port: u16,
+    shutdown: Arc<AtomicBool>,
+    node_thread: Option<thread::JoinHandle<()>>,
+}
+
+impl TestNode {
+    /// The fixture layout of the gateway suite (mock-registry at
+    /// `REGISTRY`, counter at 1, kitchen at 2) plus the console, whose
+    /// config `configure` finishes (the `dispatch_as` grant, `intra_caps`).
+    fn start(
+        paths: &FixturePaths,
+        authorized_keys: &str,
+        configure: impl FnOnce(ExtensionConfig) -> ExtensionConfig,
+    ) -> Self {
+        let port = NEXT_PORT.fetch_add(1, Ordering::Relaxed);
+        let mut node = VosNode::new();
+        let shutdown = node.shutdown_handle();
+        node.register_extension_at_id(
+            ExtensionConfig::new(&paths.mock_registry),
+            ServiceId::REGISTRY,
+        );
+        assert_eq!(
+            node.register_extension(ExtensionConfig::new(&paths.counter))
+                .0,
+            1
+        );
+        assert_eq!(
+            node.register_extension(ExtensionConfig::new(&paths.kitchen))
+                .0,
+            2
+        );
+        let args = Args::new()
+            .with("host_key", HOST_KEY)
+            .with("authorized_keys", authorized_keys);
+        let console = ExtensionConfig::with_args(&paths.console, &args)
+            .serves(format!("127.0.0.1:{port}"), false);
+        node.register_extension(configure(console));
+
+        let node_thread = thread::spawn(move || node.run_forever());
+        let deadline = Instant::now() + Duration::from_secs(3);
+        while std::net::TcpStream::connect(("127.0.0.1", port)).is_err() {
+            assert!(
+                Instant::now() < deadline,
+                "console never bound to 127.0.0.1:{port}"
+            );
+            thread::sleep(Duration::from_millis(20));
+        }
+        Self {
+            port,
+            shutdown,
+            node_thread: Some(node_thread),
+        }
+    }
+
+    /// Run `ssh` with `key`, as `member`, feeding it `stdin`; `command`
+    /// empty opens the console instead of running one command.
+    fn ssh(&self, key: &ClientKey, command: &str, stdin: &[u8]) -> Output {
+        let mut ssh = Command::new(SSH);
+        ssh.args(["-F", "/dev/null", "-T", "-p"])
+            .arg(self.port.to_string())
+            .arg("-i")
+            .arg(key.private())
+            .args([
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "IdentitiesOnly=yes",
+                "-o",
+                "StrictHostKeyChecking=no",
+                "-o",
+                "UserKnownHostsFile=/dev/null",
+                "-o",
+                "LogLevel=ERROR",
+                "-o",
+                "ConnectTimeout=10",
+                "member@127.0.0.1",
+            ]);
+        if !command.is_empty() {
+            ssh.arg(command);
+        }
+        let mut child = ssh
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("run ssh");
+        child.stdin.take().unwrap().write_all(stdin).unwrap();
+        child.wait_with_output().expect("wait for ssh")
+    }
+
+    fn exec(&self, key: &ClientKey, command: &str) -> Output {
+        self.ssh(key, command, b"")
+    }
+}
+
+impl Drop for TestNode {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.node_thread.take() {
+            let deadline = Instant::now() + Duration::from_secs(8);
+            while !handle.is_finished() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(20));
+            }
+            if handle.is_finished() {
+                let _ = handle.join();
+            } else {
+                eprintln!("TestNode drop: node thread didn't exit in 8s; leaking");
+            }
+        }
+    }
+}
+
+fn member_caps() -> Vec<IntraCap> {
+    vec![IntraCap::parse("*:member").unwrap()]
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+// ── Tests ────────────────────────────────────────────────────────
+
+#[test]
+fn openssh_runs_one_command_and_gets_its_exit_status() {
+    let paths = FixturePaths::discover();
+    if !paths.ready() {
+        return;
+    }
+    let key = ClientKey::generate("exec");
+    let node = TestNode::start(&paths, &key.authorized_line(), |c| {
+        c.allow_dispatch_as().with_intra_caps(member_caps())
+    });
+
+    let sum = node.exec(&key, "1 + 1");
+    assert_eq!(sum.status.code(), Some(0), "stderr: {}", stderr(&sum));
+    assert_eq!(stdout(&sum).trim(), "2");
+
+    let echo = node.exec(&key, "kitchen echo text=hi");
+    assert_eq!(echo.status.code(), Some(0), "stderr: {}", stderr(&echo));
+    assert_eq!(stdout(&echo).trim(), "hi");
+
+    let failed = node.exec(&key, "kitchen no_such_method");
+    assert_eq!(failed.status.code(), Some(1));
+}
+
+#[test]
+fn openssh_console_reads_lines_until_exit() {
+    let paths = FixturePaths::discover();
+    if !paths.ready() {
+        return;
+    }
+    let key = ClientKey::generate("shell");
+    let node = TestNode::start(&paths, &key.authorized_line(), |c| {
+        c.allow_dispatch_as().with_intra_caps(member_caps())
+    });
+
+    let session = node.ssh(&key, "", b"1 + 1\nkitchen echo text=there\nexit\n2 + 2\n");
+    assert_eq!(
+        session.status.code(),
+        Some(0),
+        "stderr: {}",
+        stderr(&session)
+    );
+    let out = stdout(&session);
+    assert!(out.contains('2') && out.contains("there"), "stdout: {out}");
+    assert!(!out.contains('4'), "nothing runs after `exit`: {out}");
+}
+
+#[test]
+fn a_key_missing_from_authorized_keys_cannot_sign_in() {
+    let paths = FixturePaths::discover();
+    if !paths.ready() {
+        return;
+    }
+    let member = ClientKey::generate("member");
+    let stranger = ClientKey::generate("stranger");
+    let node = TestNode::start(&paths, &member.authorized_line(), |c| {
+        c.allow_dispatch_as().with_intra_caps(member_caps())
+    });
+
+    let refused = node.exec(&stranger, "1 + 1");
+    // OpenSSH's own failure code: the session never opened.
+    assert_eq!(refused.status.code(), Some(255));
+    assert!(stdout(&refused).is_empty());
+    assert!(
+        stderr(&refused).contains("Permission denied"),
+        "stderr: {}",
+        stderr(&refused)
+    );
+}
+
+#[test]
+fn actor_calls_need_the_dispatch_as_grant() {
+    let paths = FixturePaths::discover();
+    if !paths.ready() {
+        return;
+    }
+    let key = ClientKey::generate("nogrant");
+    let node = TestNode::start(&paths, &key.authorized_line(), |c| {
+        c.with_intra_caps(member_caps())
+    });
+
+    // The console itself still runs; only calls made as the member fail.
+    let sum = node.exec(&key, "1 + 1");
+    assert_eq!(sum.status.code(), Some(0), "stderr: {}", stderr(&sum));
+    let echo = node.exec(&key, "kitchen echo text=hi");
+    assert_eq!(echo.status.code(), Some(1));
+    assert!(
+        stderr(&echo).contains("dispatch_as"),
+        "stderr: {}",
+        stderr(&echo)
+    );
+}
+
+#[test]
+fn relayed_roles_stop_at_the_intra_caps_ceiling() {
+    let paths = FixturePaths::discover();
+    if !paths.ready() {
+        return;
+    }
+    let key = ClientKey::generate("caps");
+
+    // The mock registry makes every peer an admin; `*:member` relays
+    // the member's admin role as member.
+    let capped = TestNode::start(&paths, &key.authorized_line(), |c| {
+        c.allow_dispatch_as().with_intra_caps(member_caps())
+    });
+    let member = capped.exec(&key, "kitchen members_only");
+    assert_eq!(member.status.code(), Some(0), "stderr: {}", stderr(&member));
+    assert_eq!(stdout(&member).trim(), "1");
+    let admin = capped.exec(&key, "kitchen admins_only");
+    assert_eq!(admin.status.code(), Some(77), "stderr: {}", stderr(&admin));
+    assert!(stderr(&admin).contains("permission denied"));
+    drop(capped);
+
+    // Without any `intra_caps` every call arrives unauthenticated.
+    let uncapped = TestNode::start(&paths, &key.authorized_line(), |c| c.allow_dispatch_as());
+    let member = uncapped.exec(&key, "kitchen members_only");
+    assert_eq!(
+        member.status.code(),
+        Some(77),
+        "stderr: {}",
+        stderr(&member)
+    );
+}
+
+#[test]
+fn a_line_that_computes_too_long_is_interrupted() {
+    let paths = FixturePaths::discover();
+    if !paths.ready() {
+        return;
+    }
+    let key = ClientKey::generate("loop");
+    let node = TestNode::start(&paths, &key.authorized_line(), |c| {
+        c.allow_dispatch_as().with_intra_caps(member_caps())
+    });
+
+    let started = Instant::now();
+    let spin = node.exec(&key, "loop { }");
+    let elapsed = started.elapsed();
+    assert_eq!(spin.status.code(), Some(1), "stderr: {}", stderr(&spin));
+    assert!(
+        elapsed >= Duration::from_secs(10) && elapsed < Duration::from_secs(30),
+        "interrupted after {elapsed:?}"
+    );
+}
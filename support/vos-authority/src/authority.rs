@@ -0,0 +1,156 @@
+//! Served authority state: the active policy and the slot clock.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use vos::v2::Hash;
+
+use crate::policy::{Decision, Policy};
+
+pub struct Authority {
+    policy: RwLock<Arc<Policy>>,
+    /// Highest slot reported so far. A stepped-back wall clock must not move
+    /// the observed slot behind state the daemon already committed.
+    slot_high_water: Mutex<Option<u64>>,
+    /// `<policy id hex> <sequence>` of the newest policy ever served, so a
+    /// restart cannot roll back to an older signed policy.
+    state: Option<PathBuf>,
+}
+
+impl Authority {
+    pub fn new(policy: Policy, state: Option<PathBuf>) -> Self {
+        Self {
+            policy: RwLock::new(Arc::new(policy)),
+            slot_high_water: Mutex::new(None),
+            state,
+        }
+    }
+
+    /// Start serving `policy`, refusing it when the state file records a
+    /// newer sequence or a different authority.
+    pub fn open(policy: Policy, state: PathBuf) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(&state) {
+            Ok(recorded) => {
+                let (id, sequence) = parse_state(&recorded).ok_or_else(|| {
+                    anyhow::anyhow!("{} is not a vos-authority state file", state.display())
+                })?;
+                if id != policy.id {
+                    anyhow::bail!(
+                        "{} records policy {}, not {}; remove it only when deliberately replacing the authority",
+                        state.display(),
+                        hex::encode(id.0),
+                        hex::encode(policy.id.0),
+                    );
+                }
+                if policy.sequence < sequence {
+                    anyhow::bail!(
+                        "policy sequence {} is older than the recorded {sequence}",
+                        policy.sequence
+                    );
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => anyhow::bail!("read {}: {e}", state.display()),
+        }
+        let authority = Self::new(policy, Some(state));
+        authority.record(&authority.policy())?;
+        Ok(authority)
+    }
+
+    pub fn policy(&self) -> Arc<Policy> {
+        self.policy
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replace the served policy with a newer one from the same authority.
+    pub fn rotate(&self, next: Policy) -> anyhow::Result<()> {
+        let mut policy = self.policy.write().unwrap_or_else(|e| e.into_inner());
+        policy.check_rotation(&next)?;
+        self.record(&next)?;
+        *policy = Arc::new(next);
+        Ok(())
+    }
+
+    fn record(&self, policy: &Policy) -> anyhow::Result<()> {
+        let Some(state) = &self.state else {
+            return Ok(());
+        };
+        let tmp = state.with_extension("tmp");
+        std::fs::write(
+            &tmp,
+            format!("{} {}\n", hex::encode(policy.id.0), policy.sequence),
+        )
+        .and_then(|()| std::fs::rename(&tmp, state))
+        .map_err(|e| anyhow::anyhow!("record policy sequence in {}: {e}", state.display()))
+    }
+
+    pub fn current_timeslot(&self) -> Option<u64> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        let slot = self.policy().timeslot_at(now)?;
+        let mut high_water = self
+            .slot_high_water
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let slot = high_water.map_or(slot, |seen| seen.max(slot));
+        *high_water = Some(slot);
+        Some(slot)
+    }
+
+    /// A slot at or before the current one is history this authority has
+    /// reached. A later slot cannot be vouched for yet, which is an
+    /// availability failure rather than a denial.
+    pub fn verify_timeslot(&self, slot: u64) -> Decision {
+        match self.current_timeslot() {
+            Some(current) if slot <= current => Decision::Authorized,
+            _ => Decision::Unavailable,
+        }
+    }
+}
+
+fn parse_state(recorded: &str) -> Option<(Hash, u64)> {
+    let (id, sequence) = recorded.trim().split_once(' ')?;
+    let id = crate::policy::hash32("state", id).ok()?;
+    Some((Hash(id), sequence.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vos::v2::ProducerId;
+
+    #[test]
+    fn restart_refuses_a_policy_older_than_the_recorded_sequence() {
+        let state = std::env::temp_dir().join(format!(
+            "vos-authority-state-{}-{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos(),
+        ));
+        let policy = |sequence: u64| {
+            Policy::parse(
+                &format!("name = \"prod\"\nsequence = {sequence}\n"),
+                ProducerId([1; 32]),
+            )
+            .unwrap()
+        };
+        let authority = Authority::open(policy(1), state.clone()).unwrap();
+        authority.rotate(policy(3)).unwrap();
+        assert!(authority.rotate(policy(2)).is_err());
+        drop(authority);
+
+        assert!(Authority::open(policy(2), state.clone()).is_err());
+        assert_eq!(
+            Authority::open(policy(3), state.clone())
+                .unwrap()
+                .policy()
+                .sequence,
+            3
+        );
+        let _ = std::fs::remove_file(state);
+    }
+}
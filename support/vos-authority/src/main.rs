@@ -0,0 +1,208 @@
+//! `vos-authority` — reference production trust authority for VOS v2 roots.
+//!
+//! `vosx space up --production-trust-socket <path>` opens every v2 root
+//! through a fail-closed authority on a local Unix socket. This daemon is
+//! that authority for teams that do not run their own: it serves the
+//! `VTA1`/`VTR1` protocol from a signed policy file (service program and
+//! package allowlists, role authorities, receipt and proof verifier
+//! commands), logs every decision, and picks up re-signed policy rotations
+//! without changing its policy ID.
+//!
+//! ```text
+//! vos-authority sign --key operator.key policy.toml
+//! vos-authority check --signer <producer-id> policy.toml
+//! vos-authority serve --signer <producer-id> --policy policy.toml \
+//!     --socket /run/vos-authority/authority.sock
+//! ```
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+#[cfg(unix)]
+mod authority;
+mod policy;
+#[cfg(unix)]
+mod protocol;
+#[cfg(unix)]
+mod server;
+mod verifier;
+
+#[derive(Parser, Debug)]
+#[command(name = "vos-authority", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Serve the production trust protocol on a Unix socket.
+    Serve {
+        /// Signed policy file; its signature is read from `<policy>.sig`.
+        #[arg(long)]
+        policy: PathBuf,
+        /// Hex `ProducerId` of the only key allowed to sign the policy.
+        #[arg(long)]
+        signer: String,
+        #[arg(long, default_value = "/run/vos-authority/authority.sock")]
+        socket: PathBuf,
+        /// Socket permissions; the daemon user's group may connect.
+        #[arg(long, default_value = "660", value_parser = parse_mode)]
+        socket_mode: u32,
+        /// Records the newest policy sequence served, refusing rollback
+        /// across restarts.
+        #[arg(long, default_value = "/var/lib/vos-authority/state")]
+        state: PathBuf,
+        /// Connections served at once; later ones wait to be accepted.
+        #[arg(long, default_value_t = 32)]
+        max_connections: usize,
+        /// Largest request frame accepted, in bytes. Proof verification
+        /// carries the whole proof artifact, so raise this for large proofs
+        /// (the protocol caps it at 64 MiB).
+        #[arg(long, default_value_t = 8 * 1024 * 1024, value_parser = parse_frame_limit)]
+        max_frame_bytes: usize,
+    },
+    /// Sign a policy file with a libp2p protobuf keypair, writing
+    /// `<policy>.sig`.
+    Sign {
+        #[arg(long)]
+        key: PathBuf,
+        policy: PathBuf,
+    },
+    /// Verify a signed policy and print its ID and sequence.
+    Check {
+        #[arg(long)]
+        signer: String,
+        policy: PathBuf,
+    },
+}
+
+/// Limits on what one client may make the authority hold at once.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct Limits {
+    /// Connections served concurrently. Further connections wait in the
+    /// listen backlog; the client's five-second deadline bounds the wait.
+    pub max_connections: usize,
+    /// Largest request frame accepted, at most the protocol's
+    /// [`vos::v2::trust_socket::MAX_FRAME_BYTES`].
+    pub max_frame_bytes: usize,
+}
+
+fn parse_mode(value: &str) -> Result<u32, String> {
+    u32::from_str_radix(value, 8).map_err(|e| format!("octal mode: {e}"))
+}
+
+fn parse_frame_limit(value: &str) -> Result<usize, String> {
+    let bytes: usize = value.parse().map_err(|e| format!("byte count: {e}"))?;
+    if bytes > vos::v2::trust_socket::MAX_FRAME_BYTES {
+        return Err(format!(
+            "at most {} bytes",
+            vos::v2::trust_socket::MAX_FRAME_BYTES
+        ));
+    }
+    Ok(bytes)
+}
+
+fn parse_signer(signer: &str) -> anyhow::Result<vos::v2::ProducerId> {
+    policy::hash32("--signer", signer).map(vos::v2::ProducerId)
+}
+
+fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+    if let Err(error) = run(Cli::parse().command) {
+        eprintln!("error: {error:#}");
+        std::process::exit(1);
+    }
+}
+
+fn run(command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::Serve {
+            policy,
+            signer,
+            socket,
+            socket_mode,
+            state,
+            max_connections,
+            max_frame_bytes,
+        } => serve(
+            policy,
+            parse_signer(&signer)?,
+            socket,
+            socket_mode,
+            state,
+            Limits {
+                max_connections,
+                max_frame_bytes,
+            },
+        ),
+        Command::Sign { key, policy } => {
+            let bytes =
+                std::fs::read(&key).map_err(|e| anyhow::anyhow!("read {}: {e}", key.display()))?;
+            let keypair = libp2p_identity::Keypair::from_protobuf_encoding(&bytes)
+                .map_err(|e| anyhow::anyhow!("decode {}: {e}", key.display()))?;
+            let signer = policy::sign(&policy, &keypair)?;
+            let loaded = policy::Policy::load(&policy, signer)?;
+            println!("signer     {}", hex::encode(signer.0));
+            println!("policy_id  {}", hex::encode(loaded.id.0));
+            println!("sequence   {}", loaded.sequence);
+            Ok(())
+        }
+        Command::Check { signer, policy } => {
+            let loaded = policy::Policy::load(&policy, parse_signer(&signer)?)?;
+            println!("policy_id  {}", hex::encode(loaded.id.0));
+            println!("name       {}", loaded.name);
+            println!("sequence   {}", loaded.sequence);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(unix)]
+fn serve(
+    policy_path: PathBuf,
+    signer: vos::v2::ProducerId,
+    socket: PathBuf,
+    socket_mode: u32,
+    state: PathBuf,
+    limits: Limits,
+) -> anyhow::Result<()> {
+    use std::sync::Arc;
+
+    let policy = policy::Policy::load(&policy_path, signer)?;
+    let authority = Arc::new(authority::Authority::open(policy, state)?);
+    let listener = server::bind(&socket, socket_mode)?;
+    let served = authority.policy();
+    tracing::info!(
+        socket = %socket.display(),
+        policy_id = %hex::encode(served.id.0),
+        name = %served.name,
+        sequence = served.sequence,
+        "serving production trust"
+    );
+    {
+        let authority = authority.clone();
+        std::thread::spawn(move || server::watch(policy_path, signer, authority));
+    }
+    server::serve(listener, authority, limits)
+}
+
+#[cfg(not(unix))]
+fn serve(
+    _policy_path: PathBuf,
+    _signer: vos::v2::ProducerId,
+    _socket: PathBuf,
+    _socket_mode: u32,
+    _state: PathBuf,
+    _limits: Limits,
+) -> anyhow::Result<()> {
+    anyhow::bail!("vos-authority serves a Unix-domain socket and requires a Unix host")
+}
@@ -0,0 +1,560 @@
+//! Signed authority policy.
+//!
+//! A policy is a TOML file plus a detached `<policy>.sig` written by
+//! `vos-authority sign`. The daemon pins the signer's `ProducerId`, so a
+//! policy file edited without re-signing is refused rather than served.
+//!
+//! The policy ID the daemon reports to `vosx` is derived from the signer and
+//! the policy `name` only. Rotating a policy (new allowlists, keys or
+//! verifiers under a higher `sequence`) therefore keeps the ID that every
+//! production image already sealed, while a different signer or name is a
+//! different authority and fails closed.
+
+use std::path::{Path, PathBuf};
+
+use libp2p_identity::{Keypair, PublicKey};
+use serde::{Deserialize, Serialize};
+use vos::v2::{
+    ActorUpgradeV2, DeploymentId, Hash, ProducerId, ProgramId, RoleCredentialV2,
+    RoleCredentialVerificationRequestV2, ServiceGenesisV2, SpaceId, V2Wire,
+};
+
+use crate::verifier::CommandVerifier;
+
+const POLICY_SIGNATURE_DOMAIN: &[u8] = b"vos-authority/policy/v1";
+const POLICY_ID_DOMAIN: &[u8] = b"vos-authority/policy-id/v1";
+const ROLE_CREDENTIAL_DOMAIN: &[u8] = b"vos-authority/role-credential/v1";
+
+/// Start of the JAM common era (2025-01-01 12:00:00 UTC).
+const JAM_COMMON_ERA_UNIX: u64 = 1_735_732_800;
+const JAM_SLOT_SECONDS: u64 = 6;
+
+/// Three-way decision, mirroring the sidecar protocol's result bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Authorized,
+    Denied,
+    Unavailable,
+}
+
+impl Decision {
+    fn from_bool(authorized: bool) -> Self {
+        if authorized {
+            Self::Authorized
+        } else {
+            Self::Denied
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyFile {
+    name: String,
+    sequence: u64,
+    #[serde(default)]
+    timeslot: TimeslotFile,
+    #[serde(default)]
+    service_programs: Vec<String>,
+    #[serde(default)]
+    packages: Vec<PackageFile>,
+    #[serde(default)]
+    role_authorities: Vec<RoleAuthorityFile>,
+    receipt_verifier: Option<CommandVerifier>,
+    proof_verifier: Option<CommandVerifier>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TimeslotFile {
+    #[serde(default = "default_genesis_unix")]
+    genesis_unix: u64,
+    #[serde(default = "default_slot_seconds")]
+    slot_seconds: u64,
+}
+
+impl Default for TimeslotFile {
+    fn default() -> Self {
+        Self {
+            genesis_unix: default_genesis_unix(),
+            slot_seconds: default_slot_seconds(),
+        }
+    }
+}
+
+fn default_genesis_unix() -> u64 {
+    JAM_COMMON_ERA_UNIX
+}
+
+fn default_slot_seconds() -> u64 {
+    JAM_SLOT_SECONDS
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackageFile {
+    producer: Option<String>,
+    deployment: Option<String>,
+    #[serde(default)]
+    spaces: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoleAuthorityFile {
+    /// Hex of the libp2p protobuf-encoded public key.
+    public_key: String,
+    #[serde(default)]
+    spaces: Vec<String>,
+}
+
+/// Detached signature written next to the policy as `<policy>.sig`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SignatureFile {
+    public_key: String,
+    signature: String,
+}
+
+/// One allowlisted package. Every named field must match; an empty
+/// `spaces` list admits the package in any space.
+#[derive(Debug, Clone)]
+struct PackageRule {
+    producer: Option<ProducerId>,
+    deployment: Option<DeploymentId>,
+    spaces: Vec<SpaceId>,
+}
+
+impl PackageRule {
+    fn admits(&self, space: SpaceId, producer: ProducerId, deployment: DeploymentId) -> bool {
+        self.producer.is_none_or(|allowed| allowed == producer)
+            && self.deployment.is_none_or(|allowed| allowed == deployment)
+            && (self.spaces.is_empty() || self.spaces.contains(&space))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RoleAuthority {
+    key: PublicKey,
+    spaces: Vec<SpaceId>,
+}
+
+/// A verified, parsed policy.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    pub id: Hash,
+    pub name: String,
+    pub sequence: u64,
+    genesis_unix: u64,
+    slot_seconds: u64,
+    service_programs: Vec<ProgramId>,
+    packages: Vec<PackageRule>,
+    role_authorities: Vec<RoleAuthority>,
+    receipt_verifier: Option<CommandVerifier>,
+    proof_verifier: Option<CommandVerifier>,
+}
+
+/// `<policy>.sig`, the detached signature location for `policy`.
+pub fn signature_path(policy: &Path) -> PathBuf {
+    let mut path = policy.as_os_str().to_owned();
+    path.push(".sig");
+    PathBuf::from(path)
+}
+
+/// Stable policy ID for `name` under `signer`.
+pub fn policy_id(signer: ProducerId, name: &str) -> Hash {
+    Hash::digest(POLICY_ID_DOMAIN, &[&signer.0, name.as_bytes()])
+}
+
+/// Sign the exact bytes of `policy` and write `<policy>.sig`.
+pub fn sign(policy: &Path, keypair: &Keypair) -> anyhow::Result<ProducerId> {
+    let bytes =
+        std::fs::read(policy).map_err(|e| anyhow::anyhow!("read {}: {e}", policy.display()))?;
+    let public_key = keypair.public().encode_protobuf();
+    let signature = keypair
+        .sign(&signing_message(&bytes))
+        .map_err(|e| anyhow::anyhow!("sign policy: {e}"))?;
+    let file = SignatureFile {
+        public_key: hex::encode(&public_key),
+        signature: hex::encode(signature),
+    };
+    let path = signature_path(policy);
+    std::fs::write(&path, toml::to_string(&file)?)
+        .map_err(|e| anyhow::anyhow!("write {}: {e}", path.display()))?;
+    Ok(ProducerId::of_public_key(&public_key))
+}
+
+fn signing_message(policy: &[u8]) -> Vec<u8> {
+    Hash::digest(POLICY_SIGNATURE_DOMAIN, &[policy]).0.to_vec()
+}
+
+impl Policy {
+    /// Read `path` and its detached signature, require the signature to come
+    /// from `signer`, then parse the policy.
+    pub fn load(path: &Path, signer: ProducerId) -> anyhow::Result<Self> {
+        let bytes =
+            std::fs::read(path).map_err(|e| anyhow::anyhow!("read {}: {e}", path.display()))?;
+        let signature_path = signature_path(path);
+        let signature = std::fs::read_to_string(&signature_path)
+            .map_err(|e| anyhow::anyhow!("read {}: {e}", signature_path.display()))?;
+        let signature: SignatureFile = toml::from_str(&signature)
+            .map_err(|e| anyhow::anyhow!("parse {}: {e}", signature_path.display()))?;
+        let public_key = hex::decode(&signature.public_key)
+            .map_err(|e| anyhow::anyhow!("policy signature public_key: {e}"))?;
+        if ProducerId::of_public_key(&public_key) != signer {
+            anyhow::bail!(
+                "{} is signed by {}, not the pinned signer {}",
+                path.display(),
+                hex::encode(ProducerId::of_public_key(&public_key).0),
+                hex::encode(signer.0),
+            );
+        }
+        let key = PublicKey::try_decode_protobuf(&public_key)
+            .map_err(|e| anyhow::anyhow!("policy signature public_key: {e}"))?;
+        let sig = hex::decode(&signature.signature)
+            .map_err(|e| anyhow::anyhow!("policy signature: {e}"))?;
+        if !key.verify(&signing_message(&bytes), &sig) {
+            anyhow::bail!("{} does not match its signature", path.display());
+        }
+        let source = std::str::from_utf8(&bytes)
+            .map_err(|_| anyhow::anyhow!("{} is not UTF-8", path.display()))?;
+        Self::parse(source, signer)
+    }
+
+    pub(crate) fn parse(source: &str, signer: ProducerId) -> anyhow::Result<Self> {
+        let file: PolicyFile =
+            toml::from_str(source).map_err(|e| anyhow::anyhow!("parse policy: {e}"))?;
+        if file.name.is_empty() {
+            anyhow::bail!("policy name must not be empty");
+        }
+        if file.timeslot.slot_seconds == 0 {
+            anyhow::bail!("timeslot.slot_seconds must be positive");
+        }
+        let service_programs = file
+            .service_programs
+            .iter()
+            .map(|program| hash32("service_programs", program).map(ProgramId))
+            .collect::<anyhow::Result<_>>()?;
+        let packages = file
+            .packages
+            .iter()
+            .map(|package| {
+                if package.producer.is_none() && package.deployment.is_none() {
+                    anyhow::bail!(
+                        "each [[packages]] entry names a producer, a deployment, or both"
+                    );
+                }
+                Ok(PackageRule {
+                    producer: package
+                        .producer
+                        .as_deref()
+                        .map(|producer| hash32("packages.producer", producer).map(ProducerId))
+                        .transpose()?,
+                    deployment: package
+                        .deployment
+                        .as_deref()
+                        .map(|deployment| {
+                            hash32("packages.deployment", deployment).map(DeploymentId)
+                        })
+                        .transpose()?,
+                    spaces: spaces("packages.spaces", &package.spaces)?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let role_authorities = file
+            .role_authorities
+            .iter()
+            .map(|authority| {
+                let bytes = hex::decode(&authority.public_key)
+                    .map_err(|e| anyhow::anyhow!("role_authorities.public_key: {e}"))?;
+                Ok(RoleAuthority {
+                    key: PublicKey::try_decode_protobuf(&bytes)
+                        .map_err(|e| anyhow::anyhow!("role_authorities.public_key: {e}"))?,
+                    spaces: spaces("role_authorities.spaces", &authority.spaces)?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        for verifier in [&file.receipt_verifier, &file.proof_verifier]
+            .into_iter()
+            .flatten()
+        {
+            verifier.validate()?;
+        }
+        Ok(Self {
+            id: policy_id(signer, &file.name),
+            name: file.name,
+            sequence: file.sequence,
+            genesis_unix: file.timeslot.genesis_unix,
+            slot_seconds: file.timeslot.slot_seconds,
+            service_programs,
+            packages,
+            role_authorities,
+            receipt_verifier: file.receipt_verifier,
+            proof_verifier: file.proof_verifier,
+        })
+    }
+
+    /// Whether `next` may replace this policy: same authority, strictly
+    /// newer sequence.
+    pub fn check_rotation(&self, next: &Policy) -> anyhow::Result<()> {
+        if next.id != self.id {
+            anyhow::bail!(
+                "policy '{}' has a different ID than the served '{}'; rotation keeps the signer and name",
+                next.name,
+                self.name,
+            );
+        }
+        if next.sequence <= self.sequence {
+            anyhow::bail!(
+                "policy sequence {} does not advance past {}",
+                next.sequence,
+                self.sequence,
+            );
+        }
+        Ok(())
+    }
+
+    /// JAM slot at `unix_seconds`, or `None` before the configured genesis.
+    pub fn timeslot_at(&self, unix_seconds: u64) -> Option<u64> {
+        unix_seconds
+            .checked_sub(self.genesis_unix)
+            .map(|elapsed| elapsed / self.slot_seconds)
+    }
+
+    pub fn verify_install(&self, genesis: &ServiceGenesisV2) -> Decision {
+        let space = genesis.service.space;
+        Decision::from_bool(
+            self.service_programs
+                .contains(&genesis.service.service_program)
+                && !genesis.actors.is_empty()
+                && genesis
+                    .actors
+                    .iter()
+                    .all(|actor| self.admits_package(space, actor.producer, actor.deployment)),
+        )
+    }
+
+    pub fn verify_upgrade(&self, upgrade: &ActorUpgradeV2) -> Decision {
+        Decision::from_bool(
+            self.service_programs
+                .contains(&upgrade.service.service_program)
+                && self.admits_package(
+                    upgrade.service.space,
+                    upgrade.producer,
+                    upgrade.replacement_deployment,
+                ),
+        )
+    }
+
+    fn admits_package(
+        &self,
+        space: SpaceId,
+        producer: ProducerId,
+        deployment: DeploymentId,
+    ) -> bool {
+        self.packages
+            .iter()
+            .any(|rule| rule.admits(space, producer, deployment))
+    }
+
+    /// A credential is authorized when it is the exact committed credential
+    /// for the request's scope and one role authority for the space signed
+    /// [`role_credential_message`].
+    pub fn verify_role_credential(
+        &self,
+        request: &RoleCredentialVerificationRequestV2,
+    ) -> Decision {
+        let Ok(credential) = RoleCredentialV2::decode(&request.credential) else {
+            return Decision::Denied;
+        };
+        if credential.commitment() != request.credential_commitment
+            || credential.scope != request.scope
+        {
+            return Decision::Denied;
+        }
+        let space = request.service.space;
+        let message = role_credential_message(request, &credential);
+        Decision::from_bool(self.role_authorities.iter().any(|authority| {
+            (authority.spaces.is_empty() || authority.spaces.contains(&space))
+                && authority.key.verify(&message, &credential.authenticator)
+        }))
+    }
+
+    pub fn receipt_verifier(&self) -> Option<&CommandVerifier> {
+        self.receipt_verifier.as_ref()
+    }
+
+    pub fn proof_verifier(&self) -> Option<&CommandVerifier> {
+        self.proof_verifier.as_ref()
+    }
+}
+
+/// Bytes a role authority signs to issue `credential` for `request`'s
+/// space, actor and policy: the credential with an empty authenticator.
+pub fn role_credential_message(
+    request: &RoleCredentialVerificationRequestV2,
+    credential: &RoleCredentialV2,
+) -> Vec<u8> {
+    let unsigned = RoleCredentialV2 {
+        authenticator: Vec::new(),
+        ..credential.clone()
+    };
+    Hash::digest(
+        ROLE_CREDENTIAL_DOMAIN,
+        &[
+            &request.service.space.0,
+            &request.actor.0,
+            &request.policy.0,
+            &unsigned.encode(),
+        ],
+    )
+    .0
+    .to_vec()
+}
+
+pub fn hash32(field: &str, value: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = hex::decode(value).map_err(|e| anyhow::anyhow!("{field}: {e}"))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("{field}: expected 32 bytes of hex"))
+}
+
+fn spaces(field: &str, values: &[String]) -> anyhow::Result<Vec<SpaceId>> {
+    values
+        .iter()
+        .map(|space| hash32(field, space).map(SpaceId))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_policy(label: &str, source: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "vos-authority-{label}-{}-{}.toml",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos(),
+        ));
+        std::fs::write(&path, source).unwrap();
+        path
+    }
+
+    fn remove(path: &Path) {
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(signature_path(path));
+    }
+
+    #[test]
+    fn rotation_keeps_the_policy_id_and_requires_a_newer_sequence() {
+        let signer = Keypair::generate_ed25519();
+        let first = temp_policy("rotate-1", "name = \"prod\"\nsequence = 1\n");
+        let producer = sign(&first, &signer).unwrap();
+        let current = Policy::load(&first, producer).unwrap();
+        assert_eq!(current.id, policy_id(producer, "prod"));
+
+        let second = temp_policy(
+            "rotate-2",
+            "name = \"prod\"\nsequence = 2\nservice_programs = [\"0101010101010101010101010101010101010101010101010101010101010101\"]\n",
+        );
+        sign(&second, &signer).unwrap();
+        let next = Policy::load(&second, producer).unwrap();
+        assert_eq!(next.id, current.id, "rotation keeps the sealed policy ID");
+        current.check_rotation(&next).unwrap();
+        assert!(next.check_rotation(&current).is_err(), "no rollback");
+
+        let renamed = Policy::parse("name = \"other\"\nsequence = 3\n", producer).unwrap();
+        assert!(current.check_rotation(&renamed).is_err());
+        remove(&first);
+        remove(&second);
+    }
+
+    #[test]
+    fn unsigned_edits_and_foreign_signers_are_refused() {
+        let signer = Keypair::generate_ed25519();
+        let path = temp_policy("tamper", "name = \"prod\"\nsequence = 1\n");
+        let producer = sign(&path, &signer).unwrap();
+        let other =
+            ProducerId::of_public_key(&Keypair::generate_ed25519().public().encode_protobuf());
+        assert!(Policy::load(&path, other).is_err());
+
+        std::fs::write(&path, "name = \"prod\"\nsequence = 9\n").unwrap();
+        assert!(Policy::load(&path, producer).is_err());
+        remove(&path);
+    }
+
+    #[test]
+    fn the_shipped_example_policy_parses() {
+        let policy =
+            Policy::parse(include_str!("../policy.example.toml"), ProducerId([1; 32])).unwrap();
+        assert_eq!(policy.sequence, 1);
+        assert!(policy.receipt_verifier().is_some());
+    }
+
+    #[test]
+    fn timeslots_count_from_the_configured_genesis() {
+        let policy = Policy::parse(
+            "name = \"prod\"\nsequence = 1\n[timeslot]\ngenesis_unix = 100\nslot_seconds = 6\n",
+            ProducerId([1; 32]),
+        )
+        .unwrap();
+        assert_eq!(policy.timeslot_at(99), None);
+        assert_eq!(policy.timeslot_at(100), Some(0));
+        assert_eq!(policy.timeslot_at(112), Some(2));
+    }
+
+    #[test]
+    fn role_credentials_need_a_matching_authority_signature() {
+        let authority = Keypair::generate_ed25519();
+        let policy = Policy::parse(
+            &format!(
+                "name = \"prod\"\nsequence = 1\n[[role_authorities]]\npublic_key = \"{}\"\n",
+                hex::encode(authority.public().encode_protobuf()),
+            ),
+            ProducerId([1; 32]),
+        )
+        .unwrap();
+        let mut credential = RoleCredentialV2 {
+            holder: vos::v2::Origin::Member(vos::v2::SubjectId([3; 32])),
+            scope: Hash([4; 32]),
+            space_role: None,
+            actor_role: Some(1),
+            authenticator: vec![0],
+        };
+        let mut request = RoleCredentialVerificationRequestV2 {
+            service: vos::v2::ServiceIdentityV2 {
+                space: SpaceId([5; 32]),
+                root_service: vos::v2::RootServiceId([6; 32]),
+                deployment: DeploymentId([7; 32]),
+                service_program: ProgramId([8; 32]),
+                service_abi: vos::v2::ABI_VERSION,
+                execution_semantics: vos::v2::EXECUTION_SEMANTICS_ID,
+                gas_schedule: vos::v2::GasScheduleV2::new(1, 1),
+            },
+            actor: vos::v2::ActorId([9; 32]),
+            policy: Hash([10; 32]),
+            scope: credential.scope,
+            credential_commitment: Hash::ZERO,
+            credential: Vec::new(),
+        };
+        credential.authenticator = authority
+            .sign(&role_credential_message(&request, &credential))
+            .unwrap();
+        request.credential = credential.encode();
+        request.credential_commitment = credential.commitment();
+        assert_eq!(
+            policy.verify_role_credential(&request),
+            Decision::Authorized
+        );
+
+        request.policy = Hash([11; 32]);
+        assert_eq!(
+            policy.verify_role_credential(&request),
+            Decision::Denied,
+            "a credential signed for one policy does not authorize another"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! The `VTA1`/`VTR1` production trust protocol, server side.
+//!
+//! `docs/runtime-v2.md` is the specification; `vosx`'s
+//! `production_trust.rs` is the client. One connection carries one
+//! length-prefixed request and one length-prefixed response. Structured
+//! payloads are canonical `V2Wire` bytes, and a payload that does not decode
+//! is denied rather than guessed at.
+
+use vos::v2::trust_socket::{
+    AUTHORIZED, CURRENT_TIMESLOT, DENIED, NO_TIMESLOT, POLICY, PROTOCOL_VERSION, QUERY_POLICY,
+    REQUEST_HEADER_BYTES, REQUEST_MAGIC, RESPONSE_MAGIC, RESPONSE_WITH_SLOT_BYTES, TIMESLOT,
+    UNAVAILABLE, VERIFY_INSTALL, VERIFY_PROOF, VERIFY_RECEIPT, VERIFY_ROLE, VERIFY_TIMESLOT,
+    VERIFY_UPGRADE, request_hash,
+};
+use vos::v2::{
+    ActorUpgradeV2, ProofVerificationRequestV2, ReceiptVerificationRequestV2,
+    RoleCredentialVerificationRequestV2, ServiceGenesisV2, V2Wire,
+};
+
+use crate::authority::Authority;
+use crate::policy::Decision;
+
+/// Answer one complete request frame (without its outer length). `None`
+/// means the frame is not a `VTA1` request at all; the connection is closed
+/// without a response and the client fails closed.
+pub fn respond(authority: &Authority, request: &[u8]) -> Option<Vec<u8>> {
+    if request.len() < REQUEST_HEADER_BYTES
+        || request[..4] != REQUEST_MAGIC
+        || request[4..6] != PROTOCOL_VERSION.to_le_bytes()
+    {
+        return None;
+    }
+    let tag = request[6];
+    let payload_len = u32::from_le_bytes(request[7..11].try_into().ok()?) as usize;
+    if payload_len != request.len() - REQUEST_HEADER_BYTES {
+        return None;
+    }
+    let payload = &request[REQUEST_HEADER_BYTES..];
+    let request_hash = request_hash(request);
+    let policy = authority.policy();
+
+    let (result, timeslot) = match tag {
+        QUERY_POLICY if payload.is_empty() => (POLICY, None),
+        CURRENT_TIMESLOT if payload.is_empty() => match authority.current_timeslot() {
+            Some(slot) => (TIMESLOT, Some(slot)),
+            None => (NO_TIMESLOT, None),
+        },
+        VERIFY_TIMESLOT => match <[u8; 8]>::try_from(payload) {
+            Ok(slot) => (
+                result_byte(authority.verify_timeslot(u64::from_le_bytes(slot))),
+                None,
+            ),
+            Err(_) => (DENIED, None),
+        },
+        VERIFY_PROOF => (result_byte(verify_proof(&policy, payload)), None),
+        VERIFY_INSTALL => (
+            result_byte(
+                ServiceGenesisV2::decode(payload)
+                    .map_or(Decision::Denied, |genesis| policy.verify_install(&genesis)),
+            ),
+            None,
+        ),
+        VERIFY_UPGRADE => (
+            result_byte(
+                ActorUpgradeV2::decode(payload)
+                    .map_or(Decision::Denied, |upgrade| policy.verify_upgrade(&upgrade)),
+            ),
+            None,
+        ),
+        VERIFY_ROLE => (
+            result_byte(
+                RoleCredentialVerificationRequestV2::decode(payload)
+                    .map_or(Decision::Denied, |request| {
+                        policy.verify_role_credential(&request)
+                    }),
+            ),
+            None,
+        ),
+        VERIFY_RECEIPT => (
+            result_byte(match ReceiptVerificationRequestV2::decode(payload) {
+                Ok(_) => policy
+                    .receipt_verifier()
+                    .map_or(Decision::Denied, |verifier| verifier.verify(payload)),
+                Err(_) => Decision::Denied,
+            }),
+            None,
+        ),
+        _ => (DENIED, None),
+    };
+    tracing::info!(
+        kind = kind_name(tag),
+        request = %hex::encode(request_hash.0),
+        result = result_name(result),
+        policy_sequence = policy.sequence,
+        "decision"
+    );
+
+    let mut response = Vec::with_capacity(RESPONSE_WITH_SLOT_BYTES);
+    response.extend_from_slice(&RESPONSE_MAGIC);
+    response.extend_from_slice(&PROTOCOL_VERSION.to_le_bytes());
+    response.extend_from_slice(&request_hash.0);
+    response.extend_from_slice(&policy.id.0);
+    response.push(result);
+    if let Some(slot) = timeslot {
+        response.extend_from_slice(&slot.to_le_bytes());
+    }
+    Some(response)
+}
+
+/// A proof payload is the request and the proof artifact, each
+/// length-prefixed. The artifact must be the blob the request names before
+/// the configured verifier sees it.
+fn verify_proof(policy: &crate::policy::Policy, payload: &[u8]) -> Decision {
+    let Some((request, proof)) = decode_pair(payload) else {
+        return Decision::Denied;
+    };
+    let Ok(request) = ProofVerificationRequestV2::decode(request) else {
+        return Decision::Denied;
+    };
+    if !request.proof_blob.matches(proof) {
+        return Decision::Denied;
+    }
+    policy
+        .proof_verifier()
+        .map_or(Decision::Denied, |verifier| verifier.verify(payload))
+}
+
+fn decode_pair(payload: &[u8]) -> Option<(&[u8], &[u8])> {
+    let left_len = u32::from_le_bytes(payload.get(..4)?.try_into().ok()?) as usize;
+    let left_end = 4_usize.checked_add(left_len)?;
+    let right_len_end = left_end.checked_add(4)?;
+    let right_len =
+        u32::from_le_bytes(payload.get(left_end..right_len_end)?.try_into().ok()?) as usize;
+    let right_end = right_len_end.checked_add(right_len)?;
+    (right_end == payload.len())
+        .then(|| (&payload[4..left_end], &payload[right_len_end..right_end]))
+}
+
+fn result_byte(decision: Decision) -> u8 {
+    match decision {
+        Decision::Authorized => AUTHORIZED,
+        Decision::Denied => DENIED,
+        Decision::Unavailable => UNAVAILABLE,
+    }
+}
+
+fn kind_name(tag: u8) -> &'static str {
+    match tag {
+        QUERY_POLICY => "policy",
+        CURRENT_TIMESLOT => "current-slot",
+        VERIFY_TIMESLOT => "verify-slot",
+        VERIFY_PROOF => "proof",
+        VERIFY_INSTALL => "install",
+        VERIFY_UPGRADE => "upgrade",
+        VERIFY_ROLE => "role",
+        VERIFY_RECEIPT => "receipt",
+        _ => "unknown",
+    }
+}
+
+fn result_name(result: u8) -> &'static str {
+    match result {
+        AUTHORIZED => "authorized",
+        DENIED => "denied",
+        UNAVAILABLE => "unavailable",
+        NO_TIMESLOT => "no-slot",
+        TIMESLOT => "slot",
+        _ => "policy",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::Policy;
+
+    fn authority(source: &str) -> Authority {
+        Authority::new(
+            Policy::parse(source, vos::v2::ProducerId([1; 32])).unwrap(),
+            None,
+        )
+    }
+
+    #[test]
+    fn policy_query_answers_the_documented_request_hash() {
+        let authority = authority("name = \"prod\"\nsequence = 1\n");
+        let request = hex::decode("5654413101000000000000").unwrap();
+        let response = respond(&authority, &request).unwrap();
+        assert_eq!(response.len(), 71);
+        assert_eq!(response[..4], RESPONSE_MAGIC);
+        assert_eq!(
+            hex::encode(&response[6..38]),
+            "a5ee8be4abb996fd3735970cd7b5a53632afef7cb7a548e1314d9c6ef39ece35",
+        );
+        assert_eq!(response[38..70], authority.policy().id.0);
+        assert_eq!(response[70], POLICY);
+    }
+
+    #[test]
+    fn malformed_frames_get_no_response_and_unknown_payloads_are_denied() {
+        let authority = authority("name = \"prod\"\nsequence = 1\n");
+        assert!(respond(&authority, b"VTA1").is_none());
+        let mut wrong_length = hex::decode("5654413101000000000000").unwrap();
+        wrong_length.push(0);
+        assert!(respond(&authority, &wrong_length).is_none());
+
+        let mut install = b"VTA1".to_vec();
+        install.extend_from_slice(&PROTOCOL_VERSION.to_le_bytes());
+        install.push(VERIFY_INSTALL);
+        install.extend_from_slice(&3u32.to_le_bytes());
+        install.extend_from_slice(b"bad");
+        assert_eq!(respond(&authority, &install).unwrap()[70], DENIED);
+    }
+
+    #[test]
+    fn proof_pairs_must_fill_the_payload_exactly() {
+        assert_eq!(
+            decode_pair(&[1, 0, 0, 0, 9, 0, 0, 0, 0]),
+            Some((&[9][..], &[][..]))
+        );
+        assert_eq!(decode_pair(&[1, 0, 0, 0, 9, 0, 0, 0, 0, 7]), None);
+        assert_eq!(decode_pair(&[5, 0, 0, 0, 9]), None);
+    }
+}
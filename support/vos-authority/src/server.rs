@@ -0,0 +1,202 @@
+//! Unix socket listener and policy reloader.
+
+use std::io::{Read, Write};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use vos::v2::ProducerId;
+
+use crate::Limits;
+use crate::authority::Authority;
+use crate::policy::{Policy, signature_path};
+use crate::protocol;
+
+/// Matches the client's single five-second deadline per exchange.
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+const RELOAD_POLL: Duration = Duration::from_secs(2);
+
+/// Bind `socket`, replacing a stale socket file but never a regular file.
+pub fn bind(socket: &Path, mode: u32) -> anyhow::Result<UnixListener> {
+    match std::fs::symlink_metadata(socket) {
+        Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(socket)
+            .map_err(|e| anyhow::anyhow!("remove stale {}: {e}", socket.display()))?,
+        Ok(_) => anyhow::bail!("{} exists and is not a socket", socket.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => anyhow::bail!("inspect {}: {e}", socket.display()),
+    }
+    let listener = UnixListener::bind(socket)
+        .map_err(|e| anyhow::anyhow!("bind {}: {e}", socket.display()))?;
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(mode))
+        .map_err(|e| anyhow::anyhow!("chmod {}: {e}", socket.display()))?;
+    Ok(listener)
+}
+
+/// Serve up to `limits.max_connections` connections at a time, one thread
+/// each, until the listener fails.
+pub fn serve(
+    listener: UnixListener,
+    authority: Arc<Authority>,
+    limits: Limits,
+) -> anyhow::Result<()> {
+    let slots = Arc::new(Slots::new(limits.max_connections));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                tracing::warn!(%error, "accept");
+                continue;
+            }
+        };
+        let slot = slots.acquire();
+        let authority = authority.clone();
+        std::thread::spawn(move || {
+            let _slot = slot;
+            if let Err(error) = exchange(stream, &authority, limits.max_frame_bytes) {
+                tracing::debug!(%error, "connection closed without a decision");
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Counting semaphore over connection threads.
+struct Slots {
+    free: Mutex<usize>,
+    released: Condvar,
+}
+
+struct Slot(Arc<Slots>);
+
+impl Slots {
+    fn new(count: usize) -> Self {
+        Self {
+            free: Mutex::new(count.max(1)),
+            released: Condvar::new(),
+        }
+    }
+
+    fn acquire(self: &Arc<Self>) -> Slot {
+        let mut free = self.free.lock().unwrap_or_else(|e| e.into_inner());
+        while *free == 0 {
+            free = self.released.wait(free).unwrap_or_else(|e| e.into_inner());
+        }
+        *free -= 1;
+        Slot(self.clone())
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        *self.0.free.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+        self.0.released.notify_one();
+    }
+}
+
+fn exchange(
+    mut stream: UnixStream,
+    authority: &Authority,
+    max_frame_bytes: usize,
+) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let request = read_frame(&mut stream, max_frame_bytes)?;
+    let Some(response) = protocol::respond(authority, &request) else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "not a VTA1 request",
+        ));
+    };
+    let response_len = u32::try_from(response.len()).expect("responses are at most 79 bytes");
+    stream.write_all(&response_len.to_le_bytes())?;
+    stream.write_all(&response)
+}
+
+/// Read one length-prefixed frame. The buffer grows with the bytes that
+/// actually arrive rather than with the declared length, so a client that
+/// announces a large frame and stalls pins no more than it sent.
+fn read_frame(stream: &mut impl Read, max_frame_bytes: usize) -> std::io::Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > max_frame_bytes {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("request frame of {len} bytes exceeds the {max_frame_bytes}-byte limit"),
+        ));
+    }
+    let mut request = Vec::with_capacity(len.min(64 * 1024));
+    stream.take(len as u64).read_to_end(&mut request)?;
+    if request.len() != len {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(request)
+}
+
+/// Poll the policy and its signature, rotating the served policy when a
+/// newer signed version appears. A bad edit is logged and the current
+/// policy keeps serving.
+pub fn watch(policy_path: PathBuf, signer: ProducerId, authority: Arc<Authority>) {
+    let stamp = |path: &Path| {
+        std::fs::metadata(path)
+            .ok()
+            .map(|meta| (meta.modified().ok(), meta.len()))
+    };
+    let signature = signature_path(&policy_path);
+    let mut seen = (stamp(&policy_path), stamp(&signature));
+    loop {
+        std::thread::sleep(RELOAD_POLL);
+        let current = (stamp(&policy_path), stamp(&signature));
+        if current == seen {
+            continue;
+        }
+        seen = current;
+        let result = Policy::load(&policy_path, signer).and_then(|next| {
+            let sequence = next.sequence;
+            authority.rotate(next).map(|()| sequence)
+        });
+        match result {
+            Ok(sequence) => tracing::info!(sequence, "rotated policy"),
+            Err(error) => tracing::warn!(
+                %error,
+                serving = authority.policy().sequence,
+                "policy change not applied"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_over_the_limit_are_refused_before_any_allocation() {
+        let mut frame = 9u32.to_le_bytes().to_vec();
+        frame.extend_from_slice(b"VTA1");
+        let error = read_frame(&mut frame.as_slice(), 8).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+        let error = read_frame(&mut frame.as_slice(), 16).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+
+        frame.extend_from_slice(b"01234");
+        assert_eq!(read_frame(&mut frame.as_slice(), 16).unwrap(), b"VTA101234");
+    }
+
+    #[test]
+    fn connection_slots_block_until_one_is_released() {
+        let slots = Arc::new(Slots::new(1));
+        let held = slots.acquire();
+        let waiter = {
+            let slots = slots.clone();
+            std::thread::spawn(move || drop(slots.acquire()))
+        };
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!waiter.is_finished());
+        drop(held);
+        waiter.join().unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+//! External receipt and proof verifiers.
+//!
+//! Receipt finality and proof validity need a consensus view or a proving
+//! system the authority does not embed. The policy names a command for each;
+//! the authority writes the exact protocol payload to its stdin and maps the
+//! exit status: `0` authorizes, `1` denies, anything else (including a
+//! timeout or a spawn failure) is unavailable.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::policy::Decision;
+
+/// Must leave room inside the daemon's five-second sidecar deadline.
+const MAX_TIMEOUT_MS: u64 = 4_000;
+
+fn default_timeout_ms() -> u64 {
+    3_000
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommandVerifier {
+    /// Program and arguments, e.g. `["/usr/libexec/vos/verify-receipt"]`.
+    command: Vec<String>,
+    #[serde(default = "default_timeout_ms")]
+    timeout_ms: u64,
+}
+
+impl CommandVerifier {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.command.is_empty() {
+            anyhow::bail!("verifier command must name a program");
+        }
+        if self.timeout_ms == 0 || self.timeout_ms > MAX_TIMEOUT_MS {
+            anyhow::bail!("verifier timeout_ms must be between 1 and {MAX_TIMEOUT_MS}");
+        }
+        Ok(())
+    }
+
+    pub fn verify(&self, payload: &[u8]) -> Decision {
+        let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+        let mut child = match Command::new(&self.command[0])
+            .args(&self.command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(error) => {
+                tracing::warn!(command = %self.command[0], %error, "spawn verifier");
+                return Decision::Unavailable;
+            }
+        };
+        // A verifier that exits without reading its input must not block the
+        // authority on a full pipe, so the payload is written off-thread.
+        if let Some(mut stdin) = child.stdin.take() {
+            let payload = payload.to_vec();
+            std::thread::spawn(move || {
+                let _ = stdin.write_all(&payload);
+            });
+        }
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    return match status.code() {
+                        Some(0) => Decision::Authorized,
+                        Some(1) => Decision::Denied,
+                        _ => Decision::Unavailable,
+                    };
+                }
+                Ok(None) if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+                Ok(None) => {
+                    tracing::warn!(command = %self.command[0], "verifier timed out");
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Decision::Unavailable;
+                }
+                Err(error) => {
+                    tracing::warn!(command = %self.command[0], %error, "wait for verifier");
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Decision::Unavailable;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str, timeout_ms: u64) -> CommandVerifier {
+        CommandVerifier {
+            command: vec!["/bin/sh".into(), "-c".into(), script.into()],
+            timeout_ms,
+        }
+    }
+
+    #[test]
+    fn exit_status_maps_to_the_three_way_decision() {
+        assert_eq!(
+            shell("cat >/dev/null; exit 0", 1_000).verify(b"x"),
+            Decision::Authorized
+        );
+        assert_eq!(shell("exit 1", 1_000).verify(b"x"), Decision::Denied);
+        assert_eq!(shell("exit 7", 1_000).verify(b"x"), Decision::Unavailable);
+        assert_eq!(shell("sleep 5", 50).verify(b"x"), Decision::Unavailable);
+    }
+}
@@ -130,6 +130,27 @@ pub struct Config<N: NodeId> {
     /// Default: `3` — enough to absorb a transient network blip
     /// but quick enough to surface a stuck node.
     pub pre_candidate_misses_before_revert: u32,
+    /// Members that vote but never hold application data.
+    ///
+    /// A witness counts toward every quorum (elections, commit,
+    /// read barriers, joint consensus) like any other voter, but
+    /// it never starts an election, so it can never lead. It keeps
+    /// only term, vote and the `(index, term)` identity of each
+    /// log entry: the leader strips `Data` payloads before
+    /// replicating to it, sends it empty snapshots, and the witness
+    /// compacts its own id-only log up to `commit_index`. That is
+    /// enough for the §5.4.1 up-to-date check, so a witness can
+    /// break the tie in a two-node group without a third full
+    /// replica.
+    ///
+    /// The trade-off is durability: an entry acknowledged by the
+    /// leader plus a witness is committed while its payload lives
+    /// on a single disk. Losing that disk loses the entry.
+    ///
+    /// Every replica of the group must list the same witnesses.
+    /// Ids absent from the active configuration are ignored.
+    /// Default: empty.
+    pub witnesses: Vec<N>,
 }
 
 impl<N: NodeId> Config<N> {
@@ -151,6 +172,7 @@ impl<N: NodeId> Config<N> {
             max_pending_reads: 1024,
             max_snapshot_bytes: 512 * 1024 * 1024,
             pre_candidate_misses_before_revert: 3,
+            witnesses: Vec::new(),
         }
     }
 }
@@ -163,6 +185,12 @@ impl<N: NodeId> Config<N> {
     pub fn quorum(&self) -> usize {
         self.members.len() / 2 + 1
     }
+
+    /// Whether `id` is configured as a log-less witness. See
+    /// [`Config::witnesses`].
+    pub fn is_witness(&self, id: N) -> bool {
+        self.witnesses.contains(&id)
+    }
 }
 
 #[cfg(test)]
@@ -182,4 +210,14 @@ mod tests {
         assert_eq!(cfg(vec![0xA, 0xB, 0xC, 0xD, 0xE]).quorum(), 3);
         assert_eq!(cfg((0..7).collect()).quorum(), 4);
     }
+
+    #[test]
+    fn witnesses_default_empty_and_count_toward_quorum() {
+        let mut c = cfg(vec![0xA, 0xB, 0xC]);
+        assert!(!c.is_witness(0xC));
+        c.witnesses = vec![0xC];
+        assert!(c.is_witness(0xC));
+        assert!(!c.is_witness(0xA));
+        assert_eq!(c.quorum(), 2, "a witness is a full voter for quorum");
+    }
 }
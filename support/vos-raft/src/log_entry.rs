@@ -103,4 +103,15 @@ impl<N: NodeId> LogEntry<N> {
             _ => None,
         }
     }
+
+    /// Drop a `Data` entry's payload, keeping its `(index, term)`
+    /// identity. `ConfigChange` entries are returned unchanged.
+    /// This is the form replicated to and stored by witnesses
+    /// (see [`Config::witnesses`](crate::Config::witnesses)).
+    pub(crate) fn without_payload(mut self) -> Self {
+        if let EntryKind::Data { payload } = &mut self.kind {
+            *payload = Vec::new();
+        }
+        self
+    }
 }
@@ -162,6 +162,10 @@ pub struct WorkerSnapshot<N: NodeId> {
     /// last term change. Followers use this to redirect clients
     /// addressing them by mistake.
     pub leader_hint: Option<N>,
+    /// Configured log-less witnesses (see [`Config::witnesses`]).
+    /// Witnesses appear in `members` like any voter; this list
+    /// tells them apart.
+    pub witnesses: Vec<N>,
 }
 
 /// Inbound message processed by the worker loop.
@@ -1138,6 +1142,7 @@ where
             joint_old: self.effective_cfg.joint_old.clone(),
             active_config_index: self.active_config_index,
             leader_hint: self.seen_leader,
+            witnesses: self.cfg.witnesses.clone(),
         }
     }
 
@@ -1582,6 +1587,12 @@ async fn on_timer<N, S, T, C, R, A>(
     A: ApplySink,
 {
     match state.role {
+        // A witness never campaigns: it holds no payloads, so a
+        // witness leader could not serve or replicate data. Re-arm
+        // the timer and keep waiting for a full voter to win.
+        Role::Follower if state.cfg.is_witness(state.cfg.me) => {
+            state.reset_election_timer();
+        }
         // Follower whose timer expired: start the pre-election
         // phase (or skip it if `cfg.pre_vote == false`).
         Role::Follower => {
@@ -1806,15 +1817,25 @@ where
         }
     }
     let truncate_after = conflict_at.map(|idx| idx - 1);
+    let witness = state.cfg.is_witness(state.cfg.me);
     let appends: Vec<LogEntry<N>> = req
         .entries
         .iter()
         .enumerate()
         .skip(already_present)
-        .map(|(i, e)| LogEntry {
-            index: req.prev_log_index + 1 + i as u64,
-            term: e.term,
-            kind: e.kind.clone(),
+        .map(|(i, e)| {
+            let entry = LogEntry {
+                index: req.prev_log_index + 1 + i as u64,
+                term: e.term,
+                kind: e.kind.clone(),
+            };
+            // A leader that predates our witness status may still
+            // ship payloads; never persist them.
+            if witness {
+                entry.without_payload()
+            } else {
+                entry
+            }
         })
         .collect();
 
@@ -1952,6 +1973,12 @@ where
         rebuild_leader_tracking(state);
     }
 
+    if witness && commit_advanced {
+        // Best effort: the append is already durable, and a failed
+        // compaction only leaves a few id-only entries behind.
+        let _ = compact_witness_log(state).await;
+    }
+
     Ok(AppendEntriesResp {
         term: state.meta.current_term,
         success: true,
@@ -2206,8 +2233,12 @@ where
         });
     }
 
-    // Final chunk — commit the assembled snapshot atomically.
-    let snapshot = state.incoming_snapshot.take().expect("set above").buffer;
+    // Final chunk — commit the assembled snapshot atomically. A
+    // witness keeps only the snapshot's identity and membership.
+    let mut snapshot = state.incoming_snapshot.take().expect("set above").buffer;
+    if state.cfg.is_witness(state.cfg.me) {
+        snapshot = Vec::new();
+    }
 
     // Membership and its provenance are one atomic claim. Reject incomplete
     // or future provenance before advancing any durable snapshot boundary.
@@ -3098,6 +3129,7 @@ where
             continue;
         }
         let next_idx = leader.next_index.get(&peer).copied().unwrap_or(1);
+        let peer_is_witness = state.cfg.is_witness(peer);
 
         if next_idx <= snap_idx && snap_idx > 0 {
             // Resume from the per-peer cursor if it points at the
@@ -3110,7 +3142,13 @@ where
                 .map(|s| s.offset)
                 .unwrap_or(0);
 
-            let snapshot = state.storage.read_state().await.unwrap_or_default();
+            // Witnesses only need the snapshot identity: send a
+            // single empty `done` chunk instead of the state.
+            let snapshot = if peer_is_witness {
+                Vec::new()
+            } else {
+                state.storage.read_state().await.unwrap_or_default()
+            };
             let total_len = snapshot.len() as u64;
             // Cap chunk size, but never produce a 0-byte non-final
             // chunk (would loop forever). For an empty snapshot
@@ -3182,11 +3220,14 @@ where
 
         let prev_log_index = next_idx.saturating_sub(1);
         let prev_log_term = state.storage.term_at(prev_log_index).await?.unwrap_or(0);
-        let entries = if next_idx <= leader_last_index {
+        let mut entries = if next_idx <= leader_last_index {
             state.storage.entries(next_idx, leader_last_index).await?
         } else {
             Vec::new()
         };
+        if peer_is_witness {
+            entries = entries.into_iter().map(LogEntry::without_payload).collect();
+        }
         let req = AppendEntriesReq {
             leader: me,
             term,
@@ -3283,6 +3324,53 @@ where
     Ok(())
 }
 
+/// Witness-side log compaction. A witness has no state machine
+/// to snapshot, so once its committed prefix grows past
+/// `compact_hysteresis` it folds the id-only entries up to
+/// `commit_index` into the snap pointer with an empty state
+/// image. Committed entries are present in every future leader's
+/// log (Leader Completeness), so a later consistency check still
+/// anchors at or above the new snap pointer.
+async fn compact_witness_log<N, S, T, C, R, A>(
+    state: &mut WorkerState<N, S, T, C, R, A>,
+) -> Result<(), S::Error>
+where
+    N: NodeId,
+    S: Storage<N>,
+    T: Transport<N>,
+    C: Clock,
+    R: Rng,
+    A: ApplySink,
+{
+    let floor = state.meta.commit_index.min(state.storage.last_index());
+    let snap = state.storage.snap_last_index();
+    if floor <= snap || floor.saturating_sub(snap) < state.cfg.compact_hysteresis {
+        return Ok(());
+    }
+    let Some(term_at_floor) = state.storage.term_at(floor).await? else {
+        return Ok(());
+    };
+    let prev_snap_index = state.meta.snap_last_index;
+    let prev_snap_term = state.meta.snap_last_term;
+    state.meta.snap_last_index = floor;
+    state.meta.snap_last_term = term_at_floor;
+    if let Err(e) = state
+        .storage
+        .commit_batch(WriteBatch {
+            compact_to: Some((floor, term_at_floor)),
+            state: Some(Vec::new()),
+            meta: Some(state.meta.clone()),
+            ..Default::default()
+        })
+        .await
+    {
+        state.meta.snap_last_index = prev_snap_index;
+        state.meta.snap_last_term = prev_snap_term;
+        return Err(e);
+    }
+    Ok(())
+}
+
 /// Pre-election phase. Sets role to `PreCandidate`, sends
 /// `PreVote` to every peer asking "would you grant a real vote
 /// at `current_term + 1`?". Does NOT bump `current_term` or
@@ -3506,6 +3594,7 @@ mod tests {
             joint_old: None,
             active_config_index: Some(11),
             leader_hint: Some(0xAAAA),
+            witnesses: Vec::new(),
         };
         let handle = WorkerHandle {
             inbox: Inbox { inner: tx },
@@ -4062,4 +4151,91 @@ mod tests {
 
         worker.shutdown();
     }
+
+    #[test]
+    fn witness_never_campaigns() {
+        let transport = Arc::new(RecordingTransport::default());
+        let mut cfg = cfg(0xCCCC, alloc::vec![0xAAAA, 0xBBBB, 0xCCCC]);
+        cfg.election_timeout_ms = (10, 20);
+        cfg.witnesses = alloc::vec![0xCCCC];
+        let worker = Worker::spawn_with(
+            MemStorage::<u16>::new(),
+            transport.clone(),
+            cfg,
+            (),
+            StdClock,
+            StdRng::from_entropy(),
+        );
+        let h = worker.handler();
+        // Several election timeouts elapse with no leader around.
+        std::thread::sleep(Duration::from_millis(150));
+        let snap = block_on(h.snapshot()).unwrap();
+        assert_eq!(snap.role, Role::Follower);
+        assert_eq!(snap.current_term, 0, "a witness must not bump its term");
+        assert_eq!(snap.witnesses, alloc::vec![0xCCCC]);
+        assert!(transport.votes.lock().unwrap().is_empty());
+        assert!(transport.prevotes.lock().unwrap().is_empty());
+        worker.shutdown();
+    }
+
+    #[test]
+    fn witness_compacts_its_committed_id_log() {
+        let transport = Arc::new(RecordingTransport::default());
+        let mut cfg = cfg(0xCCCC, alloc::vec![0xAAAA, 0xBBBB, 0xCCCC]);
+        cfg.compact_hysteresis = 2;
+        cfg.witnesses = alloc::vec![0xCCCC];
+        let worker = Worker::spawn_with(
+            MemStorage::<u16>::new(),
+            transport,
+            cfg,
+            (),
+            StdClock,
+            StdRng::from_entropy(),
+        );
+        let h = worker.handler();
+        let resp = block_on(
+            h.handle_inbound_append(
+                0xAAAA,
+                AppendEntriesReq {
+                    leader: 0xAAAA,
+                    term: 3,
+                    prev_log_index: 0,
+                    prev_log_term: 0,
+                    leader_commit: 3,
+                    entries: (1..=4)
+                        .map(|i| LogEntry::data(i, 3, alloc::vec![i as u8; 32]))
+                        .collect(),
+                },
+            ),
+        );
+        assert!(resp.success);
+        assert_eq!(resp.match_index, 4);
+        let snap = block_on(h.snapshot()).unwrap();
+        assert_eq!(snap.commit_index, 3);
+        assert_eq!(
+            snap.snap_last_index, 3,
+            "committed ids fold into the snap pointer"
+        );
+        assert_eq!(
+            snap.last_log_index, 4,
+            "the uncommitted id stays in the log"
+        );
+
+        // The retained identity still anchors the next consistency
+        // check, so replication continues past the compaction.
+        let resp = block_on(h.handle_inbound_append(
+            0xAAAA,
+            AppendEntriesReq {
+                leader: 0xAAAA,
+                term: 3,
+                prev_log_index: 4,
+                prev_log_term: 3,
+                leader_commit: 4,
+                entries: alloc::vec![LogEntry::data(5, 3, alloc::vec![5; 32])],
+            },
+        ));
+        assert!(resp.success);
+        assert_eq!(resp.match_index, 5);
+        worker.shutdown();
+    }
 }
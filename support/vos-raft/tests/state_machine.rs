@@ -69,6 +69,7 @@ enum RpcRecord {
         to: u16,
         term: u64,
         count: usize,
+        payload_bytes: usize,
     },
     Vote {
         from: u16,
@@ -160,6 +161,12 @@ impl Transport<u16> for MockTransport {
             to: peer,
             term: req.term,
             count: req.entries.len(),
+            payload_bytes: req
+                .entries
+                .iter()
+                .filter_map(LogEntry::payload)
+                .map(<[u8]>::len)
+                .sum(),
         });
         let handle = {
             let routes = self.routes.lock().unwrap();
@@ -2211,3 +2218,97 @@ fn cross_snapshot_membership_recovery_via_persisted_view() {
 
     worker.shutdown();
 }
+
+/// Two data voters plus a witness: the witness never leads, but
+/// its vote lets the surviving data voter take over when the
+/// leader is cut off, and its ack commits the new leader's
+/// proposals. A plain two-voter group would stall here.
+#[test]
+fn witness_lets_two_data_voters_survive_a_leader_loss() {
+    let routes: Routes = Arc::new(Mutex::new(BTreeMap::new()));
+    let transport = Arc::new(MockTransport::new(routes.clone()));
+
+    let members = vec![1u16, 2, 3];
+    let witness = 3u16;
+    let mut workers: std::collections::BTreeMap<u16, Worker<u16>> =
+        std::collections::BTreeMap::new();
+    for me in members.iter().copied() {
+        let mut c = cfg(me, members.clone());
+        c.witnesses = vec![witness];
+        let worker = Worker::spawn_with(
+            MemStorage::<u16>::new(),
+            transport.clone(),
+            c,
+            (),
+            StdClock,
+            StdRng::from_entropy(),
+        );
+        routes.lock().unwrap().insert(me, worker.handler());
+        workers.insert(me, worker);
+    }
+
+    wait_until(
+        || members.iter().any(|p| workers[p].role() == Role::Leader),
+        Duration::from_secs(5),
+        "leader emerges",
+    );
+    let first = *members
+        .iter()
+        .find(|p| workers[p].role() == Role::Leader)
+        .expect("leader exists");
+    assert_ne!(first, witness, "a witness must never lead");
+    let first_idx = block_on(workers[&first].handler().propose(vec![1])).expect("propose");
+    let survivor = if first == 1 { 2 } else { 1 };
+    // Leader + witness alone would already commit `first_idx`; wait
+    // until the payload also reached the other data voter so the
+    // witness's up-to-date check lets it win.
+    wait_until(
+        || {
+            block_on(workers[&survivor].handler().snapshot())
+                .map(|s| s.last_log_index >= first_idx)
+                .unwrap_or(false)
+        },
+        Duration::from_secs(5),
+        "payload reaches the second data voter",
+    );
+
+    // Cut the leader off; the other data voter must win with the
+    // witness's vote alone.
+    transport.isolate(first, &members);
+    wait_until(
+        || workers[&survivor].role() == Role::Leader,
+        Duration::from_secs(5),
+        "surviving data voter takes over",
+    );
+    let h = workers[&survivor].handler();
+    let idx = block_on(h.propose(vec![2])).expect("propose on new leader");
+    wait_until(
+        || {
+            block_on(h.snapshot())
+                .map(|s| s.commit_index >= idx)
+                .unwrap_or(false)
+        },
+        Duration::from_secs(5),
+        "leader + witness commit the new entry",
+    );
+    assert_ne!(workers[&witness].role(), Role::Leader);
+
+    // Every append to the witness carried entry ids only, while
+    // the data voters received the payloads.
+    let payload_to = |peer: u16| -> usize {
+        transport
+            .log
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|r| match r {
+                RpcRecord::Append {
+                    to, payload_bytes, ..
+                } if *to == peer => Some(*payload_bytes),
+                _ => None,
+            })
+            .sum()
+    };
+    assert_eq!(payload_to(witness), 0, "witnesses never receive payloads");
+    assert!(payload_to(survivor) > 0, "data voters receive payloads");
+}
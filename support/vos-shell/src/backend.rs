@@ -4,7 +4,7 @@
 //! [`SpaceClient`]. Two implementations exist (outside this crate):
 //!
 //! * `DaemonClientBackend` in `vosx` — the local console, over libp2p.
-//! * `SshSpaceClient` in the ssh-console extension — over `ServiceCtx::ask_raw_as`.
+//! * `SshSpaceClient` in the ssh-console extension — over `Context::ask_dispatch_as`.
 
 use vos::abi::service::ServiceId;
 use vos::metadata::ParsedMeta;
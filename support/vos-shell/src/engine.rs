@@ -6,10 +6,11 @@
 //! on one line is visible on the next, exactly as nushell's own REPL does.
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use nu_protocol::debugger::WithoutDebug;
 use nu_protocol::engine::{EngineState, Stack, StateWorkingSet};
-use nu_protocol::{PipelineData, Span};
+use nu_protocol::{PipelineData, Signals, Span};
 
 use crate::actor_cmd::ActorCommand;
 use crate::backend::{BackendError, SpaceClient};
@@ -49,15 +50,21 @@ pub struct ConsoleEngine {
     engine_state: EngineState,
     stack: Stack,
     client: Arc<dyn SpaceClient>,
+    /// Raised to stop the line being evaluated; cleared before each line.
+    interrupt: Arc<AtomicBool>,
 }
 
 impl ConsoleEngine {
     /// Build an engine, discovering + registering the space's actor commands.
     pub fn new(client: Arc<dyn SpaceClient>) -> Result<Self, BackendError> {
+        let interrupt = Arc::new(AtomicBool::new(false));
+        let mut engine_state = sandbox::base_engine_state();
+        engine_state.set_signals(Signals::new(interrupt.clone()));
         let mut me = Self {
-            engine_state: sandbox::base_engine_state(),
+            engine_state,
             stack: Stack::new(),
             client,
+            interrupt,
         };
         // Override nushell's `help` with our space-specific one (registered
         // after the base context so it wins).
@@ -78,6 +85,14 @@ impl ConsoleEngine {
         &self.client
     }
 
+    /// A flag that interrupts the line being evaluated when set — loops
+    /// and long pipelines check it between steps and fail with
+    /// "interrupted". A host that evaluates on another thread uses it to
+    /// bound a runaway script.
+    pub fn interrupt_flag(&self) -> Arc<AtomicBool> {
+        self.interrupt.clone()
+    }
+
     /// Syntax-highlight a line of input against the live command set (used by
     /// the TUI prompt). Returns coloured runs covering the whole string.
     pub fn highlight(&self, line: &str) -> Vec<crate::highlight::HlSpan> {
@@ -128,6 +143,7 @@ impl ConsoleEngine {
 
     /// Evaluate one line of nu-script.
     pub fn eval(&mut self, src: &str) -> EvalResult {
+        self.interrupt.store(false, Ordering::Relaxed);
         // Parse against a working set derived from the live engine; capture the
         // delta (new vars/defs) so REPL state persists across lines.
         let (block, delta) = {
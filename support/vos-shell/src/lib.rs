@@ -8,7 +8,7 @@
 //!
 //! The engine is backend-agnostic: it talks to a space only through the
 //! [`SpaceClient`] trait, so a host wires it to whatever transport it has
-//! (the SSH console implements it over `ServiceCtx::ask_raw_as`). Nothing
+//! (the SSH console implements it over `Context::ask_dispatch_as`). Nothing
 //! in this crate is libp2p- or transport-aware.
 //!
 //! [nushell]: https://www.nushell.sh/
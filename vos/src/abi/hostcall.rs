@@ -112,11 +112,13 @@ pub const BOOT_CONTEXT: u32 = 120;
 /// Host wall-clock in Unix-epoch milliseconds. The deterministic PVM has no
 /// clock and there is no time precompile; a `Local` actor that needs real time
 /// (e.g. the messenger, for MLS KeyPackage/commit `Lifetime` validity that
-/// remote peers check against their own clock) reads it here. Like
-/// [`BOOT_CONTEXT`] the value is intentionally non-deterministic, so it is sound
-/// ONLY for non-replicated (`Local`) actors and must never feed a replicated
-/// state transition — replicated actors take time from the `chronos` beacon
-/// (sampled once at the raft leader and committed), never from this hostcall.
+/// remote peers check against their own clock) reads it here. For a `Local`
+/// actor the value is unrecorded and non-deterministic. Under a replicated
+/// strategy the top-level dispatch's first reading is pinned in its effect
+/// log as the admission time and every replica replays that value (see
+/// `EffectLog::admitted_at_ms`). Nested children are never recorded and must
+/// not feed replicated state from it; business-time schedules still take
+/// time from the `chronos` beacon.
 pub const NOW_MS: u32 = 121;
 
 /// Consensus-authenticated logical timeslot observed by guest Accumulate.
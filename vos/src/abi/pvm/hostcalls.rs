@@ -17,8 +17,9 @@ pub fn gas() -> u64 {
 
 /// Host wall-clock in Unix-epoch milliseconds (see [`hostcall::NOW_MS`]). The
 /// deterministic PVM has no clock; a `Local` actor that needs real wall time
-/// (the messenger, for MLS `Lifetime` validity) reads it here. Non-deterministic
-/// — sound only for non-replicated actors; replicated state must use `chronos`.
+/// (the messenger, for MLS `Lifetime` validity) reads it here. A replicated
+/// actor's top-level handler gets the admission time pinned in its effect
+/// log instead — prefer [`Context::admitted_at_ms`](crate::Context::admitted_at_ms).
 #[inline]
 pub fn now_ms() -> u64 {
     ecall0(hostcall::NOW_MS)
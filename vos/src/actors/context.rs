@@ -40,6 +40,9 @@ pub struct Context<A: Actor> {
     /// dispatch boundary; new service code sets it directly from the work
     /// envelope.
     origin: crate::v2::Origin,
+    /// Consensus admission timeslot of the v2 work item carrying this
+    /// slice (Unix-epoch ms). `None` outside the generic service.
+    admitted_at_ms: Option<u64>,
 
     /// Caller's space-wide role byte — a
     /// [`SpaceRole`](super::auth::SpaceRole) discriminant. `None`
@@ -120,6 +123,7 @@ impl<A: Actor> Context<A> {
             invocation_id: crate::v2::InvocationId::ZERO,
             caller: Caller::Unauthenticated,
             origin: crate::v2::Origin::Anonymous,
+            admitted_at_ms: None,
             space_role: None,
             actor_local_role: None,
             forbidden: false,
@@ -269,6 +273,33 @@ impl<A: Actor> Context<A> {
         self.origin = origin;
     }
 
+    /// Consensus-recorded admission time of the running dispatch, in
+    /// Unix-epoch milliseconds — identical on every replica.
+    ///
+    /// Under the generic service this is the work envelope's logical
+    /// timeslot. A v1 replicated actor gets the time its effect log pinned
+    /// when the dispatch was first recorded. `None` when the host supplied
+    /// no time (native host tests, or a replayed log that pinned none).
+    pub fn admitted_at_ms(&self) -> Option<u64> {
+        if self.admitted_at_ms.is_some() || self.actor_id.is_some() {
+            return self.admitted_at_ms;
+        }
+        #[cfg(feature = "pvm")]
+        {
+            let now = crate::abi::pvm::hostcalls::now_ms();
+            (now != crate::abi::error::HOST_NONE).then_some(now)
+        }
+        #[cfg(not(feature = "pvm"))]
+        {
+            None
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn __set_admitted_at_ms(&mut self, at_ms: Option<u64>) {
+        self.admitted_at_ms = at_ms;
+    }
+
     /// Overwrite the per-invocation role bytes. Mirrors
     /// [`Self::set_caller`] — called by host glue before each
     /// dispatch so the caller's grants are visible to handler
@@ -989,6 +1020,37 @@ impl<A: Actor> Context<A> {
         crate::effects::bytestream::decode_resp_bytes(&resp)
     }
 
+    /// [`ask_dispatch`](Self::ask_dispatch) on behalf of the SSH client whose
+    /// `publickey` sign-in `proof` the extension verified (the SSH console's
+    /// member). The host re-verifies the proof and the target sees
+    /// `Caller::Peer` for the signing key's PeerId, with the peer's registry
+    /// role capped by this extension's `intra_caps`, so its own role gate
+    /// decides as it would for that member bounded by the relay's ceiling.
+    /// A refusal comes back as the 5-byte `STATUS_FORBIDDEN` envelope rather
+    /// than `None`, so the caller can report "permission denied". Only the
+    /// native transport host fulfils this, and only for an extension the
+    /// operator granted `dispatch_as`; elsewhere it resolves to `None`.
+    #[cfg(feature = "extension")]
+    pub async fn ask_dispatch_as(
+        &mut self,
+        proof: &crate::effects::UserAuthProof,
+        target: ServiceId,
+        payload: &[u8],
+    ) -> Option<Vec<u8>> {
+        let proof = proof.encode()?;
+        let proof_len = u16::try_from(proof.len()).ok()?;
+        // Wire format:
+        // [tag=EFFECT_ASK_DISPATCH_AS][proof_len:u16 LE][proof][target:u32 LE][payload].
+        let mut request = Vec::with_capacity(7 + proof.len() + payload.len());
+        request.push(crate::effects::EFFECT_ASK_DISPATCH_AS);
+        request.extend_from_slice(&proof_len.to_le_bytes());
+        request.extend_from_slice(&proof);
+        request.extend_from_slice(&target.0.to_le_bytes());
+        request.extend_from_slice(payload);
+        let resp = self.host_call(request).await;
+        crate::effects::bytestream::decode_resp_bytes(&resp)
+    }
+
     /// Resolve an installed agent's name to its node-local
     /// `ServiceId` (packed as u32) by asking the well-known
     /// `ServiceId::REGISTRY` service. Returns 0 when no agent
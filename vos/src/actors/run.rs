@@ -806,6 +806,7 @@ pub fn run_nested_actor_service<A: super::Actor>(
         actor_tree,
         external_actors,
         input: work_input,
+        logical_timeslot,
         change,
         state,
         causal_states,
@@ -845,6 +846,7 @@ pub fn run_nested_actor_service<A: super::Actor>(
         active_actor_mask,
     );
     ctx.__set_origin(origin);
+    ctx.__set_admitted_at_ms(Some(logical_timeslot));
     ctx.set_caller_roles(space_role, actor_role);
 
     assert_eq!(
@@ -492,6 +492,7 @@ mod local {
                     node_appended: false,
                 });
             }
+            let barrier = crate::write_barrier::enter();
             let txn = self.db.begin_write()?;
             {
                 if state_changed && let Some(state) = state {
@@ -513,6 +514,7 @@ mod local {
                 }
             }
             txn.commit()?;
+            drop(barrier);
             if let Some(state) = state {
                 self.last = state.to_vec();
             }
@@ -526,6 +528,7 @@ mod local {
             state: &[u8],
             rows: &[(Vec<u8>, Vec<u8>)],
         ) -> Result<CommitReceipt, CommitError> {
+            let barrier = crate::write_barrier::enter();
             let txn = self.db.begin_write()?;
             {
                 let mut table = txn.open_table(STATE_TABLE)?;
@@ -533,6 +536,7 @@ mod local {
             }
             swap_kv_rows(&txn, rows)?;
             txn.commit()?;
+            drop(barrier);
             self.last = state.to_vec();
             Ok(CommitReceipt {
                 node_appended: false,
@@ -828,12 +832,14 @@ mod crdt {
             // thread so we never split the (DAG node + ROOTS_KEY)
             // pair across an agent write.
             let _guard = self.commit_lock.lock().expect("commit_lock poisoned");
+            let barrier = crate::write_barrier::enter();
             let txn = self.db.begin_write()?;
             {
                 let mut table = txn.open_table(DAG_TABLE)?;
                 table.insert(cid.as_slice(), node_bytes)?;
             }
             txn.commit()?;
+            drop(barrier);
             self.clock.add_roots(core::iter::once(Cid::<Blake2b>(*cid)));
             Ok(true)
         }
@@ -913,12 +919,14 @@ mod crdt {
 
         fn persist_roots(&self) -> Result<(), CommitError> {
             let bytes = encode_roots(self.clock.roots());
+            let barrier = crate::write_barrier::enter();
             let txn = self.db.begin_write()?;
             {
                 let mut table = txn.open_table(STATE_TABLE)?;
                 table.insert(ROOTS_KEY, bytes.as_slice())?;
             }
             txn.commit()?;
+            drop(barrier);
             Ok(())
         }
     }
@@ -994,6 +1002,7 @@ mod crdt {
             // already carries, so ROOTS/NEXT_SEQ stay untouched and no
             // node appends.
             let _guard = self.commit_lock.lock().expect("commit_lock poisoned");
+            let barrier = crate::write_barrier::enter();
             let txn = self.db.begin_write()?;
             {
                 let mut table = txn.open_table(STATE_TABLE)?;
@@ -1001,6 +1010,7 @@ mod crdt {
             }
             super::swap_kv_rows(&txn, rows)?;
             txn.commit()?;
+            drop(barrier);
             self.last_state = state.to_vec();
             Ok(CommitReceipt {
                 node_appended: false,
@@ -1191,6 +1201,7 @@ mod crdt {
 
             let next_seq_after = self.next_seq + new_cid_bytes_seq.is_some() as u64;
 
+            let barrier = crate::write_barrier::enter();
             let txn = self.db.begin_write()?;
             {
                 let mut state_table = txn.open_table(STATE_TABLE)?;
@@ -1219,6 +1230,7 @@ mod crdt {
                 }
             }
             txn.commit()?;
+            drop(barrier);
 
             // Update in-memory clock to reflect the newly committed
             // roots. For a node-less commit the roots are unchanged.
@@ -1276,12 +1288,14 @@ mod crdt {
             return Err(retired_replay_format());
         }
 
+        let barrier = crate::write_barrier::enter();
         let txn = db.begin_write()?;
         {
             let mut table = txn.open_table(STATE_TABLE)?;
             table.insert(REPLAY_FORMAT_KEY, REPLAY_FORMAT)?;
         }
         txn.commit()?;
+        drop(barrier);
         Ok(())
     }
 
@@ -38,6 +38,7 @@ pub type CallerPrefix = [u8; 5];
 /// dispatch identity is stamped.
 pub const CALLER_SYSTEM: CallerPrefix = [1, 0, 0, 0, 0];
 const INVOCATION_ID_EXTENSION: u8 = 0x01;
+const ADMITTED_AT_EXTENSION: u8 = 0x02;
 
 /// Default size cap for a single `ctx.ask` reply, in bytes.
 ///
@@ -103,6 +104,11 @@ pub struct EffectLog {
     /// Legacy CRDT logs reconstruct it from the enclosing
     /// [`CrdtEvent`]'s `(origin, seq)`.
     invocation_id: crate::v2::InvocationId,
+    /// Wall-clock admission time (Unix-epoch ms) the proposer pinned the
+    /// first time the handler read `NOW_MS`. Replay hands every replica
+    /// this value instead of its own clock. `None` when the handler never
+    /// asked.
+    admitted_at_ms: Option<u64>,
 }
 
 impl EffectLog {
@@ -116,6 +122,7 @@ impl EffectLog {
             caller_prefix: CALLER_SYSTEM,
             invoke_effects: Vec::new(),
             invocation_id: crate::v2::InvocationId::ZERO,
+            admitted_at_ms: None,
         }
     }
 
@@ -143,6 +150,11 @@ impl EffectLog {
         self.invocation_id
     }
 
+    /// Admission time pinned for this dispatch, if the handler read one.
+    pub fn admitted_at_ms(&self) -> Option<u64> {
+        self.admitted_at_ms
+    }
+
     /// Append the next reply captured during dispatch.
     pub fn record_reply(&mut self, reply: Vec<u8>) {
         self.replies.push(reply);
@@ -177,11 +189,14 @@ impl EffectLog {
     /// [n_invoke_effects:u64 LE]
     /// ( [reply_idx:u64 LE][svc_id:u32 LE][len:u64 LE][effects] )*
     /// [INVOCATION_ID_EXTENSION:u8][invocation_id:32B]
+    /// [ADMITTED_AT_EXTENSION:u8][admitted_at_ms:u64 LE]
     /// ```
     ///
     /// The invoke-effect count is mandatory. The invocation extension is
     /// omitted only for the zero identity used by non-durable recording
-    /// sessions; its absence is not a legacy-format fallback.
+    /// sessions; its absence is not a legacy-format fallback. The
+    /// admission-time extension is present only when the handler read the
+    /// clock, and always follows the invocation extension.
     ///
     /// The encoding is deterministic and unambiguous, so two
     /// replicas observing the same dispatch produce the same bytes
@@ -211,6 +226,10 @@ impl EffectLog {
             buf.push(INVOCATION_ID_EXTENSION);
             buf.extend_from_slice(self.invocation_id.as_bytes());
         }
+        if let Some(at_ms) = self.admitted_at_ms {
+            buf.push(ADMITTED_AT_EXTENSION);
+            buf.extend_from_slice(&at_ms.to_le_bytes());
+        }
         buf
     }
 
@@ -255,16 +274,19 @@ impl EffectLog {
                 effects,
             });
         }
-        let invocation_id = if pos == bytes.len() {
-            crate::v2::InvocationId::ZERO
-        } else {
-            if *bytes.get(pos)? != INVOCATION_ID_EXTENSION {
-                return None;
-            }
+        let invocation_id = if bytes.get(pos) == Some(&INVOCATION_ID_EXTENSION) {
             pos += 1;
             let mut invocation_id = [0u8; 32];
             invocation_id.copy_from_slice(take(bytes, &mut pos, 32)?);
             crate::v2::InvocationId::new(invocation_id)
+        } else {
+            crate::v2::InvocationId::ZERO
+        };
+        let admitted_at_ms = if bytes.get(pos) == Some(&ADMITTED_AT_EXTENSION) {
+            pos += 1;
+            Some(read_u64(bytes, &mut pos)?)
+        } else {
+            None
         };
         if pos != bytes.len() {
             return None;
@@ -277,6 +299,7 @@ impl EffectLog {
             caller_prefix,
             invoke_effects,
             invocation_id,
+            admitted_at_ms,
         })
     }
 }
@@ -515,6 +538,12 @@ impl EffectSession {
         self.log.reply_count()
     }
 
+    /// Pin the dispatch's admission time on first use and return the
+    /// pinned value; later reads in the same dispatch ignore `now_ms`.
+    pub fn admit_at(&mut self, now_ms: u64) -> u64 {
+        *self.log.admitted_at_ms.get_or_insert(now_ms)
+    }
+
     /// Consume the session and return the finished log.
     pub fn into_log(self) -> EffectLog {
         self.log
@@ -569,6 +598,11 @@ impl EffectReplay {
         self.pos
     }
 
+    /// Admission time the recording pinned, if any.
+    pub fn admitted_at_ms(&self) -> Option<u64> {
+        self.log.admitted_at_ms
+    }
+
     /// The invoke-effects records attached to the reply at `idx`
     /// (the reply [`next_reply`](Self::next_reply) just returned is
     /// index `position() - 1`). The replaying invoke short-circuit
@@ -712,6 +746,35 @@ mod tests {
         assert_eq!(decoded.log.invocation_id(), expected);
     }
 
+    #[test]
+    fn admission_time_pins_once_and_roundtrips_after_the_invocation() {
+        let mut session = EffectSession::new(b"grant".to_vec());
+        assert_eq!(session.admit_at(1_700_000_000_000), 1_700_000_000_000);
+        assert_eq!(
+            session.admit_at(1_700_000_000_999),
+            1_700_000_000_000,
+            "later reads in one dispatch see the pinned time"
+        );
+        let mut log = session.into_log();
+        log.set_invocation_id(crate::v2::InvocationId::derive(b"test", b"admitted"));
+        let decoded = EffectLog::from_bytes(&log.to_bytes()).expect("decode");
+        assert_eq!(decoded, log);
+        assert_eq!(
+            EffectReplay::new(decoded).admitted_at_ms(),
+            Some(1_700_000_000_000)
+        );
+
+        // Without an invocation id the time still decodes; a log that
+        // never read the clock carries no extension at all.
+        log.set_invocation_id(crate::v2::InvocationId::ZERO);
+        assert_eq!(EffectLog::from_bytes(&log.to_bytes()), Some(log));
+        let untimed = EffectLog::for_msg(b"grant".to_vec());
+        assert_eq!(
+            EffectLog::from_bytes(&untimed.to_bytes()).and_then(|l| l.admitted_at_ms()),
+            None
+        );
+    }
+
     #[test]
     fn invocation_extension_roundtrips_and_zero_stays_omitted() {
         let zero = EffectLog::for_msg(b"zero".to_vec());
@@ -51,6 +51,23 @@ pub const EFFECT_BLOB_GET: u8 = 0x03;
 /// failure (including an older host that doesn't serve this effect).
 pub const EFFECT_BLOB_PUT: u8 = 0x05;
 
+/// Like [`EFFECT_ASK_DISPATCH`], but made on behalf of a session principal
+/// that signed in to the extension over SSH (the SSH console's member).
+/// Payload `[proof_len:u16 LE][proof…][target:u32 LE][message…]`, where
+/// `proof` is a [`UserAuthProof`]; status-framed result as for
+/// [`EFFECT_ASK_DISPATCH`], except that a refusal by the target's role gate
+/// is `[RESP_OK]` + the 5-byte `STATUS_FORBIDDEN` envelope a libp2p client
+/// would see. The host verifies the proof's signature itself and relays
+/// `Caller::Peer` for the *signing key's* PeerId, with the registry's role
+/// for it bounded by the extension's `intra_caps` exactly as an actor-mode
+/// relay is, and its actor-local grant capped at the same ceiling. The
+/// extension names no principal of its own: a forged, altered or foreign
+/// proof is `[RESP_ERR]`, as is every call from an extension the operator
+/// hasn't granted `dispatch_as` in the manifest. Only the transport
+/// `ConnFulfiller` fulfils this, and it holds each connection to the first
+/// proof it verified.
+pub const EFFECT_ASK_DISPATCH_AS: u8 = 0x06;
+
 // ── Byte-stream effects ──────────────────────────────────
 //
 // Raw TCP via the host reactor (`smol::Async` in `node.rs`). The host
@@ -407,6 +424,94 @@ impl FetchResponse {
     }
 }
 
+/// Proof that an SSH client holds the key it signed in with: the
+/// `publickey` `USERAUTH_REQUEST` it signed for one session (RFC 4252 §7)
+/// and the `ssh-ed25519` signature (RFC 8709). Carried by
+/// [`EFFECT_ASK_DISPATCH_AS`] so the host, not the extension, decides who
+/// the session acts as. The session id binds the signature to one
+/// connection's key exchange, so a proof can't be minted without the
+/// client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAuthProof {
+    /// The connection's session identifier — the first exchange hash `H`.
+    pub session_id: Vec<u8>,
+    /// The user name the client sent (signed, otherwise unused).
+    pub user: Vec<u8>,
+    /// The raw ed25519 public key.
+    pub key: [u8; 32],
+    /// The signature over [`Self::signed_message`].
+    pub signature: [u8; 64],
+}
+
+impl UserAuthProof {
+    /// The bytes the client signs: `string session_id || byte 50 || string
+    /// user || string "ssh-connection" || string "publickey" || bool TRUE ||
+    /// string "ssh-ed25519" || string key_blob`, SSH `string`s being
+    /// big-endian `u32`-length-prefixed.
+    pub fn signed_message(&self) -> Vec<u8> {
+        const MSG_USERAUTH_REQUEST: u8 = 50;
+        let mut out = Vec::with_capacity(160 + self.session_id.len() + self.user.len());
+        write_ssh_string(&mut out, &self.session_id);
+        out.push(MSG_USERAUTH_REQUEST);
+        write_ssh_string(&mut out, &self.user);
+        write_ssh_string(&mut out, b"ssh-connection");
+        write_ssh_string(&mut out, b"publickey");
+        out.push(1);
+        write_ssh_string(&mut out, b"ssh-ed25519");
+        write_ssh_string(&mut out, &self.key_blob());
+        out
+    }
+
+    /// `string "ssh-ed25519" || string key` — the public key blob.
+    pub fn key_blob(&self) -> Vec<u8> {
+        let mut blob = Vec::with_capacity(51);
+        write_ssh_string(&mut blob, b"ssh-ed25519");
+        write_ssh_string(&mut blob, &self.key);
+        blob
+    }
+
+    /// The libp2p PeerId the key signs in as: the identity multihash of its
+    /// protobuf encoding (`KeyType::Ed25519`, 32 data bytes).
+    pub fn peer_id(&self) -> Vec<u8> {
+        let mut peer = Vec::with_capacity(38);
+        peer.extend_from_slice(&[0x00, 0x24, 0x08, 0x01, 0x12, 0x20]);
+        peer.extend_from_slice(&self.key);
+        peer
+    }
+
+    /// `[session_id_len:u16 LE][session_id][user_len:u16 LE][user][key:32][signature:64]`.
+    /// `None` when the session id or user name exceeds `u16::MAX` bytes.
+    pub fn encode(&self) -> Option<Vec<u8>> {
+        let session_len = u16::try_from(self.session_id.len()).ok()?;
+        let user_len = u16::try_from(self.user.len()).ok()?;
+        let mut out = Vec::with_capacity(100 + self.session_id.len() + self.user.len());
+        write_u16(&mut out, session_len);
+        out.extend_from_slice(&self.session_id);
+        write_u16(&mut out, user_len);
+        out.extend_from_slice(&self.user);
+        out.extend_from_slice(&self.key);
+        out.extend_from_slice(&self.signature);
+        Some(out)
+    }
+
+    /// Decode [`Self::encode`]'s bytes exactly; trailing bytes fail.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut c = Cursor::new(bytes);
+        let session_len = c.u16()? as usize;
+        let session_id = c.take(session_len)?.to_vec();
+        let user_len = c.u16()? as usize;
+        let user = c.take(user_len)?.to_vec();
+        let key = c.take(32)?.try_into().ok()?;
+        let signature = c.take(64)?.try_into().ok()?;
+        c.rest().is_empty().then_some(Self {
+            session_id,
+            user,
+            key,
+            signature,
+        })
+    }
+}
+
 // ── Codec helpers ───────────────────────────────────────────────────
 
 fn write_u16(out: &mut Vec<u8>, v: u16) {
@@ -422,6 +527,11 @@ fn write_str(out: &mut Vec<u8>, s: &str) {
     write_u32(out, s.len() as u32);
     out.extend_from_slice(s.as_bytes());
 }
+/// An SSH `string`: big-endian `u32` length, then the bytes.
+fn write_ssh_string(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
 
 struct Cursor<'a> {
     buf: &'a [u8],
@@ -489,6 +599,31 @@ mod tests {
         assert_eq!(decoded.body, b"{\"key\":\"value\"}");
     }
 
+    #[test]
+    fn user_auth_proof_roundtrip_and_signed_layout() {
+        let proof = UserAuthProof {
+            session_id: alloc::vec![7; 32],
+            user: b"alice".to_vec(),
+            key: [5; 32],
+            signature: [9; 64],
+        };
+        let bytes = proof.encode().unwrap();
+        assert_eq!(UserAuthProof::decode(&bytes), Some(proof.clone()));
+        assert_eq!(UserAuthProof::decode(&bytes[..bytes.len() - 1]), None);
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(UserAuthProof::decode(&trailing), None);
+
+        let message = proof.signed_message();
+        assert_eq!(&message[..4], &[0, 0, 0, 32]);
+        assert_eq!(
+            message[36], 50,
+            "SSH_MSG_USERAUTH_REQUEST after the session id"
+        );
+        assert!(message.ends_with(&proof.key_blob()));
+        assert_eq!(&proof.peer_id()[6..], &proof.key);
+    }
+
     #[test]
     fn fetch_response_roundtrip() {
         let resp = FetchResponse {
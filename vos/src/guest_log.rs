@@ -0,0 +1,255 @@
+//! Bounded per-agent ring buffers of guest diagnostics.
+//!
+//! Guest `println!` / `log::*` output reaches the host through the
+//! `DEBUG_WRITE` hostcall, which writes it to the daemon's stderr. While an
+//! agent thread runs a dispatch it also holds a [`CaptureGuard`] for that
+//! agent, and the runtime tees each complete line into [`GuestLogs`]; a guest
+//! panic message (see `actors/guest_panic.rs`) is tagged [`LogKind::Panic`].
+//! The node appends one [`LogKind::Outcome`] line per dispatch. The reserved
+//! `__logs` op reads the buffers back for `vosx space logs`.
+//!
+//! Replays and soft restarts run outside a capture, so re-executed handlers
+//! do not repeat their output here.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Lines kept per agent before the oldest are dropped.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// A line longer than this is cut into pieces, so a guest that never writes
+/// a newline cannot grow the pending buffer without bound.
+const MAX_LINE: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogKind {
+    /// A line the guest wrote through `DEBUG_WRITE`.
+    Output,
+    /// The guest's panic message.
+    Panic,
+    /// How a dispatch ended, written by the host.
+    Outcome,
+}
+
+impl LogKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogKind::Output => "output",
+            LogKind::Panic => "panic",
+            LogKind::Outcome => "outcome",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    /// Node-wide sequence number; strictly increasing across agents, so a
+    /// follower resumes from the last one it saw.
+    pub seq: u64,
+    pub unix_ms: u64,
+    /// Service id of the agent the line belongs to.
+    pub agent: u32,
+    pub kind: LogKind,
+    pub text: String,
+}
+
+/// The node's log buffers, one ring per agent.
+pub struct GuestLogs {
+    capacity: usize,
+    state: Mutex<LogState>,
+}
+
+#[derive(Default)]
+struct LogState {
+    next_seq: u64,
+    agents: BTreeMap<u32, VecDeque<LogLine>>,
+}
+
+impl Default for GuestLogs {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl GuestLogs {
+    pub fn with_capacity(capacity: usize) -> Self {
+        GuestLogs {
+            capacity: capacity.max(1),
+            state: Mutex::new(LogState::default()),
+        }
+    }
+
+    /// Append one line to `agent`'s ring, dropping its oldest line when full.
+    pub fn push(&self, agent: u32, kind: LogKind, text: &str) {
+        let unix_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.next_seq += 1;
+        let line = LogLine {
+            seq: state.next_seq,
+            unix_ms,
+            agent,
+            kind,
+            text: text.to_string(),
+        };
+        let ring = state.agents.entry(agent).or_default();
+        if ring.len() >= self.capacity {
+            ring.pop_front();
+        }
+        ring.push_back(line);
+    }
+
+    /// Buffered lines with `seq > after` and `unix_ms >= since_ms`, oldest
+    /// first and at most `limit` of them, for one agent or (`None`) all.
+    pub fn read(
+        &self,
+        agent: Option<u32>,
+        after: u64,
+        since_ms: u64,
+        limit: usize,
+    ) -> Vec<LogLine> {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut lines: Vec<LogLine> = state
+            .agents
+            .iter()
+            .filter(|(id, _)| agent.is_none_or(|agent| agent == **id))
+            .flat_map(|(_, ring)| ring.iter())
+            .filter(|line| line.seq > after && line.unix_ms >= since_ms)
+            .cloned()
+            .collect();
+        lines.sort_by_key(|line| line.seq);
+        lines.truncate(limit);
+        lines
+    }
+}
+
+thread_local! {
+    /// The agent whose dispatch this thread is running, with the bytes of
+    /// its last unterminated line. Set by [`capture`], read by [`tee`].
+    static CAPTURE: RefCell<Option<Capture>> = const { RefCell::new(None) };
+}
+
+struct Capture {
+    logs: Arc<GuestLogs>,
+    agent: u32,
+    partial: Vec<u8>,
+}
+
+impl Capture {
+    fn emit(&self, line: &[u8]) {
+        let text = String::from_utf8_lossy(line);
+        let text = text.trim_end_matches('\r');
+        let kind = if text.starts_with("panic: ") {
+            LogKind::Panic
+        } else {
+            LogKind::Output
+        };
+        self.logs.push(self.agent, kind, text);
+    }
+}
+
+/// Tee this thread's `DEBUG_WRITE` output into `logs` under `agent` until
+/// the guard drops. Any unterminated trailing line is flushed on drop.
+pub fn capture(logs: &Arc<GuestLogs>, agent: u32) -> CaptureGuard {
+    CAPTURE.with(|slot| {
+        *slot.borrow_mut() = Some(Capture {
+            logs: logs.clone(),
+            agent,
+            partial: Vec::new(),
+        });
+    });
+    CaptureGuard {
+        _thread: PhantomData,
+    }
+}
+
+/// Ends a [`capture`]; not `Send`, so it drops on the thread it captures.
+pub struct CaptureGuard {
+    _thread: PhantomData<*const ()>,
+}
+
+impl Drop for CaptureGuard {
+    fn drop(&mut self) {
+        CAPTURE.with(|slot| {
+            if let Some(capture) = slot.borrow_mut().take()
+                && !capture.partial.is_empty()
+            {
+                capture.emit(&capture.partial);
+            }
+        });
+    }
+}
+
+/// Record guest `DEBUG_WRITE` bytes for the agent this thread is
+/// capturing. A no-op outside a [`capture`].
+pub(crate) fn tee(bytes: &[u8]) {
+    CAPTURE.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        let Some(capture) = slot.as_mut() else {
+            return;
+        };
+        capture.partial.extend_from_slice(bytes);
+        while let Some(end) = capture.partial.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = capture.partial.drain(..=end).collect();
+            capture.emit(&line[..end]);
+        }
+        while capture.partial.len() > MAX_LINE {
+            let line: Vec<u8> = capture.partial.drain(..MAX_LINE).collect();
+            capture.emit(&line);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_drops_oldest_and_read_resumes_after_a_cursor() {
+        let logs = GuestLogs::with_capacity(2);
+        logs.push(1, LogKind::Output, "a");
+        logs.push(2, LogKind::Output, "b");
+        logs.push(1, LogKind::Output, "c");
+        logs.push(1, LogKind::Outcome, "d");
+
+        let texts = |lines: Vec<LogLine>| -> Vec<String> {
+            lines.into_iter().map(|line| line.text).collect()
+        };
+        // Agent 1 kept its newest two lines; agent 2's ring is separate.
+        assert_eq!(texts(logs.read(Some(1), 0, 0, usize::MAX)), ["c", "d"]);
+        assert_eq!(texts(logs.read(None, 0, 0, usize::MAX)), ["b", "c", "d"]);
+        assert_eq!(texts(logs.read(None, 0, 0, 1)), ["b"]);
+        let seq_of_c = logs.read(Some(1), 0, 0, 1)[0].seq;
+        assert_eq!(texts(logs.read(None, seq_of_c, 0, usize::MAX)), ["d"]);
+        assert!(logs.read(None, 0, u64::MAX, usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn capture_splits_lines_tags_panics_and_flushes_on_drop() {
+        let logs = Arc::new(GuestLogs::default());
+        tee(b"not captured\n");
+        {
+            let _capture = capture(&logs, 7);
+            tee(b"hel");
+            tee(b"lo\r\npanic: boom\ntrail");
+        }
+        tee(b"after\n");
+
+        let lines = logs.read(None, 0, 0, usize::MAX);
+        let seen: Vec<(u32, LogKind, &str)> = lines
+            .iter()
+            .map(|line| (line.agent, line.kind, line.text.as_str()))
+            .collect();
+        assert_eq!(
+            seen,
+            [
+                (7, LogKind::Output, "hello"),
+                (7, LogKind::Panic, "panic: boom"),
+                (7, LogKind::Output, "trail"),
+            ]
+        );
+    }
+}
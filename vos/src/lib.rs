@@ -397,6 +397,15 @@ mod host_invoker {
 #[cfg(feature = "std")]
 pub mod commit;
 
+#[cfg(feature = "std")]
+pub mod write_barrier;
+
+#[cfg(feature = "std")]
+pub mod metrics;
+
+#[cfg(feature = "std")]
+pub mod guest_log;
+
 #[cfg(feature = "storage")]
 pub mod raft;
 
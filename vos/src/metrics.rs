@@ -0,0 +1,422 @@
+//! Node-level metrics in the Prometheus text exposition format.
+//!
+//! The node's hot paths bump the counters held here: per-agent dispatches,
+//! gas and inbox depth ([`AgentMetrics`]), CRDT sync merges
+//! ([`ReplicaMetrics`]) and network frame traffic by frame kind
+//! ([`FrameTraffic`]). Point-in-time gauges (Raft progress, CRDT heads,
+//! peers, blob-store sizes) are sampled only when a scrape renders — see
+//! [`crate::node::MetricsHandle::render`] — so an idle node pays nothing for
+//! them. [`Exposition`] writes the text format and [`parse`] reads it back
+//! for consumers such as `vosx space top`.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+/// Counters shared between the node's agent threads, sync tickers and the
+/// scrape path. Entries are created on first use and live for the process.
+#[derive(Default)]
+pub struct NodeMetrics {
+    agents: RwLock<BTreeMap<u32, Arc<AgentMetrics>>>,
+    replicas: RwLock<BTreeMap<[u8; 32], Arc<ReplicaMetrics>>>,
+}
+
+impl NodeMetrics {
+    /// The counters for the agent at `service_id`, created on first use.
+    pub fn agent(&self, service_id: u32) -> Arc<AgentMetrics> {
+        if let Some(agent) = self
+            .agents
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&service_id)
+        {
+            return agent.clone();
+        }
+        self.agents
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(service_id)
+            .or_default()
+            .clone()
+    }
+
+    /// Every agent that has recorded anything, by service id.
+    pub fn agents(&self) -> Vec<(u32, Arc<AgentMetrics>)> {
+        self.agents
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(id, agent)| (*id, agent.clone()))
+            .collect()
+    }
+
+    /// The sync counters for one CRDT replication group, created on first use.
+    pub fn replica(&self, replication_id: [u8; 32]) -> Arc<ReplicaMetrics> {
+        self.replicas
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(replication_id)
+            .or_default()
+            .clone()
+    }
+
+    /// Every replication group that has recorded a sync, by id.
+    pub fn replicas(&self) -> Vec<([u8; 32], Arc<ReplicaMetrics>)> {
+        self.replicas
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(id, replica)| (*id, replica.clone()))
+            .collect()
+    }
+}
+
+/// How a dispatch reached an agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchKind {
+    /// A fire-and-forget message, tick or residual self-message.
+    Tell,
+    /// A synchronous invoke with a waiting caller.
+    Invoke,
+}
+
+impl DispatchKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DispatchKind::Tell => "tell",
+            DispatchKind::Invoke => "invoke",
+        }
+    }
+}
+
+/// One agent's counters. `queued` is a gauge kept in step with the agent's
+/// inbox channel: the sender raises it before each send and the agent thread
+/// lowers it after each receive, so it never reads below the true depth.
+#[derive(Default)]
+pub struct AgentMetrics {
+    tells: AtomicU64,
+    invokes: AtomicU64,
+    gas: AtomicU64,
+    queued: AtomicU64,
+    root: AtomicBool,
+    root_inbox: AtomicU64,
+    root_ingress: AtomicU64,
+}
+
+/// Point-in-time copy of an [`AgentMetrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AgentCounters {
+    pub tells: u64,
+    pub invokes: u64,
+    pub gas: u64,
+    pub queued: u64,
+    /// Set once a v2 root service thread has reported its backlog.
+    pub root: bool,
+    /// Admitted-but-unprocessed work in a v2 root's durable inbox.
+    pub root_inbox: u64,
+    /// Pending ingress invocations of a v2 root.
+    pub root_ingress: u64,
+}
+
+impl AgentMetrics {
+    /// Count one completed dispatch and the gas it burned.
+    pub fn record_dispatch(&self, kind: DispatchKind, gas: u64) {
+        let counter = match kind {
+            DispatchKind::Tell => &self.tells,
+            DispatchKind::Invoke => &self.invokes,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.gas.fetch_add(gas, Ordering::Relaxed);
+    }
+
+    /// An envelope is about to be sent to this agent's inbox.
+    pub fn enqueued(&self) {
+        self.queued.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// An envelope left this agent's inbox (received, or its send failed).
+    pub fn dequeued(&self) {
+        let _ = self
+            .queued
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |depth| {
+                depth.checked_sub(1)
+            });
+    }
+
+    /// Record a v2 root's durable backlog, sampled by its service thread.
+    pub fn set_root_backlog(&self, inbox: u64, ingress: u64) {
+        self.root_inbox.store(inbox, Ordering::Relaxed);
+        self.root_ingress.store(ingress, Ordering::Relaxed);
+        self.root.store(true, Ordering::Relaxed);
+    }
+
+    pub fn counters(&self) -> AgentCounters {
+        AgentCounters {
+            tells: self.tells.load(Ordering::Relaxed),
+            invokes: self.invokes.load(Ordering::Relaxed),
+            gas: self.gas.load(Ordering::Relaxed),
+            queued: self.queued.load(Ordering::Relaxed),
+            root: self.root.load(Ordering::Relaxed),
+            root_inbox: self.root_inbox.load(Ordering::Relaxed),
+            root_ingress: self.root_ingress.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Sync counters for one CRDT replication group. `unfolded` counts DAG
+/// nodes merged from peers that the local agent has not yet replayed into
+/// its state — the group's sync backlog.
+#[derive(Default)]
+pub struct ReplicaMetrics {
+    merged: AtomicU64,
+    unfolded: AtomicU64,
+}
+
+impl ReplicaMetrics {
+    /// The sync ticker inserted `nodes` peer DAG nodes.
+    pub fn record_merge(&self, nodes: u64) {
+        self.merged.fetch_add(nodes, Ordering::Relaxed);
+        self.unfolded.fetch_add(nodes, Ordering::Relaxed);
+    }
+
+    /// The agent reloaded from the store, folding every merged node in.
+    pub fn folded(&self) {
+        self.unfolded.store(0, Ordering::Relaxed);
+    }
+
+    /// `(merged_total, unfolded)`.
+    pub fn counters(&self) -> (u64, u64) {
+        (
+            self.merged.load(Ordering::Relaxed),
+            self.unfolded.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// Direction of a network frame relative to this node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FrameDirection {
+    Sent,
+    Received,
+}
+
+impl FrameDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FrameDirection::Sent => "sent",
+            FrameDirection::Received => "received",
+        }
+    }
+}
+
+/// Frame and byte counts per `(frame kind, direction)`, bumped by the wire
+/// codec and the gossip path.
+#[derive(Default)]
+pub struct FrameTraffic {
+    counts: Mutex<BTreeMap<(&'static str, FrameDirection), (u64, u64)>>,
+}
+
+impl FrameTraffic {
+    pub fn record(&self, kind: &'static str, direction: FrameDirection, bytes: usize) {
+        let mut counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let entry = counts.entry((kind, direction)).or_default();
+        entry.0 += 1;
+        entry.1 += bytes as u64;
+    }
+
+    /// `(kind, direction, frames, bytes)` in kind order.
+    pub fn snapshot(&self) -> Vec<(&'static str, FrameDirection, u64, u64)> {
+        self.counts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|((kind, direction), (frames, bytes))| (*kind, *direction, *frames, *bytes))
+            .collect()
+    }
+}
+
+/// Prometheus metric type of a family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+/// Writer for the Prometheus text exposition format (version 0.0.4).
+#[derive(Default)]
+pub struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a metric family: its `# HELP` and `# TYPE` lines.
+    pub fn family(&mut self, name: &str, kind: MetricKind, help: &str) {
+        let kind = match kind {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+        };
+        let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+        let _ = writeln!(self.out, "# HELP {name} {help}");
+        let _ = writeln!(self.out, "# TYPE {name} {kind}");
+    }
+
+    /// One sample of the most recently opened family.
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (key, value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{key}=\"{}\"", escape_label(value));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// One sample parsed back out of an exposition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub name: String,
+    pub labels: Vec<(String, String)>,
+    pub value: f64,
+}
+
+impl Sample {
+    pub fn label(&self, key: &str) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Parse the samples of a text exposition, skipping comments and any line
+/// that does not parse (timestamps are not emitted and are ignored).
+pub fn parse(text: &str) -> Vec<Sample> {
+    text.lines().filter_map(parse_line).collect()
+}
+
+fn parse_line(line: &str) -> Option<Sample> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let name_end = line.find(['{', ' '])?;
+    let name = line[..name_end].to_string();
+    let mut rest = &line[name_end..];
+    let mut labels = Vec::new();
+    if let Some(mut body) = rest.strip_prefix('{') {
+        loop {
+            body = body.trim_start_matches(',');
+            if let Some(tail) = body.strip_prefix('}') {
+                rest = tail;
+                break;
+            }
+            let (key, tail) = body.split_once("=\"")?;
+            let (value, tail) = parse_label_value(tail)?;
+            labels.push((key.to_string(), value));
+            body = tail;
+        }
+    }
+    let value = rest.split_whitespace().next()?.parse().ok()?;
+    Some(Sample {
+        name,
+        labels,
+        value,
+    })
+}
+
+/// Unescape a quoted label value up to its closing quote; returns the value
+/// and the text after the quote.
+fn parse_label_value(text: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            value.push(if c == 'n' { '\n' } else { c });
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            return Some((value, &text[i + 1..]));
+        } else {
+            value.push(c);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exposition_round_trips_through_parse() {
+        let mut out = Exposition::new();
+        out.family("vos_agent_gas_total", MetricKind::Counter, "Gas burned.");
+        out.sample(
+            "vos_agent_gas_total",
+            &[("agent", "chat \"main\""), ("id", "0x00010002")],
+            42,
+        );
+        out.family("vos_node_peers", MetricKind::Gauge, "Peers.");
+        out.sample("vos_node_peers", &[], 3);
+        let text = out.finish();
+        assert!(text.contains("# TYPE vos_agent_gas_total counter\n"));
+        assert!(text.contains("agent=\"chat \\\"main\\\"\""));
+
+        let samples = parse(&text);
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].name, "vos_agent_gas_total");
+        assert_eq!(samples[0].label("agent"), Some("chat \"main\""));
+        assert_eq!(samples[0].label("id"), Some("0x00010002"));
+        assert_eq!(samples[0].value, 42.0);
+        assert_eq!(samples[1].name, "vos_node_peers");
+        assert!(samples[1].labels.is_empty());
+        assert_eq!(samples[1].value, 3.0);
+    }
+
+    #[test]
+    fn queue_gauge_never_underflows_and_backlog_folds() {
+        let metrics = NodeMetrics::default();
+        let agent = metrics.agent(7);
+        agent.enqueued();
+        agent.dequeued();
+        agent.dequeued();
+        agent.record_dispatch(DispatchKind::Invoke, 1_000);
+        agent.record_dispatch(DispatchKind::Tell, 500);
+        let counters = metrics.agent(7).counters();
+        assert_eq!(counters.queued, 0);
+        assert_eq!(
+            (counters.tells, counters.invokes, counters.gas),
+            (1, 1, 1_500)
+        );
+
+        let replica = metrics.replica([9; 32]);
+        replica.record_merge(3);
+        replica.record_merge(2);
+        assert_eq!(replica.counters(), (5, 5));
+        replica.folded();
+        assert_eq!(metrics.replicas()[0].1.counters(), (5, 0));
+    }
+}
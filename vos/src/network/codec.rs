@@ -21,9 +21,15 @@ use libp2p::request_response::Codec;
 use std::io;
 
 use super::wire::{Frame, MAX_FRAME_BYTES};
+use crate::metrics::{FrameDirection, FrameTraffic};
 
+/// Counts every frame it moves into the owning network's
+/// [`FrameTraffic`], so per-kind byte totals cover both directions of
+/// every request/response round-trip.
 #[derive(Clone, Default)]
-pub(super) struct VosCodec;
+pub(super) struct VosCodec {
+    pub(super) traffic: std::sync::Arc<FrameTraffic>,
+}
 
 #[async_trait]
 impl Codec for VosCodec {
@@ -35,14 +41,14 @@ impl Codec for VosCodec {
     where
         T: AsyncRead + Unpin + Send,
     {
-        read_frame(io).await
+        read_frame(io, &self.traffic).await
     }
 
     async fn read_response<T>(&mut self, _: &Self::Protocol, io: &mut T) -> io::Result<Frame>
     where
         T: AsyncRead + Unpin + Send,
     {
-        read_frame(io).await
+        read_frame(io, &self.traffic).await
     }
 
     async fn write_request<T>(
@@ -54,7 +60,7 @@ impl Codec for VosCodec {
     where
         T: AsyncWrite + Unpin + Send,
     {
-        write_frame(io, &req).await
+        write_frame(io, &req, &self.traffic).await
     }
 
     async fn write_response<T>(
@@ -66,11 +72,11 @@ impl Codec for VosCodec {
     where
         T: AsyncWrite + Unpin + Send,
     {
-        write_frame(io, &resp).await
+        write_frame(io, &resp, &self.traffic).await
     }
 }
 
-async fn write_frame<W>(io: &mut W, frame: &Frame) -> io::Result<()>
+async fn write_frame<W>(io: &mut W, frame: &Frame, traffic: &FrameTraffic) -> io::Result<()>
 where
     W: AsyncWrite + Unpin + Send,
 {
@@ -84,10 +90,11 @@ where
     io.write_all(&(bytes.len() as u32).to_le_bytes()).await?;
     io.write_all(&bytes).await?;
     io.flush().await?;
+    traffic.record(frame.kind(), FrameDirection::Sent, bytes.len() + 4);
     Ok(())
 }
 
-async fn read_frame<R>(io: &mut R) -> io::Result<Frame>
+async fn read_frame<R>(io: &mut R, traffic: &FrameTraffic) -> io::Result<Frame>
 where
     R: AsyncRead + Unpin + Send,
 {
@@ -102,7 +109,10 @@ where
     }
     let mut buf = vec![0u8; len];
     io.read_exact(&mut buf).await?;
-    Frame::decode(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    let frame = Frame::decode(&buf)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    traffic.record(frame.kind(), FrameDirection::Received, len + 4);
+    Ok(frame)
 }
 
 pub(super) const PROTOCOL: StreamProtocol = StreamProtocol::new("/vos/0.1.0");
@@ -3,14 +3,22 @@
 //! [`Network`] runs the libp2p swarm on its own tokio thread,
 //! independent of [`VosNode`](crate::node::VosNode). It provides a
 //! `request_response` channel over `/vos/0.1.0` carrying [`Frame`]:
-//! `Hello` exchanges `node_prefix` on first contact, `Tell` delivers
+//! `HelloWide` exchanges the 32-bit node id and 16-bit `node_prefix`
+//! on first contact (legacy peers fall back to `Hello`), `Tell` delivers
 //! fire-and-forget envelopes, and additional frames support CRDT sync,
 //! Raft RPCs, manifest fetches, and content-addressed blob fetches.
 //! Inbound Tells are pushed into the caller-supplied
 //! [`NetworkConfig::inbox`].
+//!
+//! Peers are found by mDNS, explicit bootnodes, and a Kademlia DHT on
+//! which every node provides its [`NetworkConfig::discovery_keys`]
+//! (space and hyperspace ids). A `Tell` / `InvokeRequest` for a prefix
+//! with no direct connection is relayed through intermediate members
+//! (see `relay.rs`).
 
 mod codec;
 mod ops;
+mod relay;
 mod wire;
 
 pub(crate) use wire::raft_append_prefix_len;
@@ -18,20 +26,40 @@ pub use wire::{
     Frame, FrameError, MAX_FRAME_BYTES, ManifestBlob, RaftEntry, RaftEntryKind, RaftJoinResult,
 };
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::num::NonZeroU8;
 use std::sync::{Arc, Mutex, OnceLock, mpsc as std_mpsc};
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
 use libp2p::futures::StreamExt;
 use libp2p::gossipsub;
+use libp2p::multiaddr::Protocol;
 use libp2p::request_response::{self, ProtocolSupport};
+use libp2p::swarm::behaviour::toggle::Toggle;
+use libp2p::swarm::dial_opts::DialOpts;
 use libp2p::swarm::{NetworkBehaviour, SwarmEvent};
-use libp2p::{Multiaddr, PeerId, Swarm, identify, identity, mdns, noise, ping, tcp, yamux};
+use libp2p::{
+    Multiaddr, PeerId, StreamProtocol, Swarm, autonat, dcutr, identify, identity, kad, mdns, noise,
+    ping, tcp, yamux,
+};
 use tokio::sync::mpsc as async_mpsc;
 use tracing::{debug, error, info, warn};
 
 use codec::{PROTOCOL, VosCodec};
+use relay::{RelayFields, RelayReplyFields, RelayState, ReplySealer};
+
+/// Kademlia protocol id. Distinct from the public IPFS DHT so VOS
+/// nodes only ever route among themselves.
+const KAD_PROTOCOL: StreamProtocol = StreamProtocol::new("/vos/kad/1.0.0");
+
+/// How often a node re-announces its discovery keys on the DHT and
+/// looks up the other providers of each.
+const DISCOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often a node tells its neighbours which prefixes it reaches
+/// directly (see [`Frame::Neighbors`]).
+const NEIGHBOR_ADVERT_INTERVAL: Duration = Duration::from_secs(15);
 
 /// Combined libp2p behaviour.
 #[derive(NetworkBehaviour)]
@@ -48,6 +76,23 @@ struct VosBehaviour {
     /// fetch against the publisher rather than waiting for the
     /// next 250ms tick.
     gossip: gossipsub::Behaviour,
+    /// DHT keyed by space / hyperspace id: each node provides its
+    /// [`NetworkConfig::discovery_keys`] and dials the other
+    /// providers, so members find each other beyond the LAN.
+    kad: kad::Behaviour<kad::store::MemoryStore>,
+    /// Circuit-relay v2 client: holds reservations on the relays in
+    /// [`NetworkConfig::relays`] so a node behind NAT stays reachable
+    /// at `<relay>/p2p-circuit/p2p/<self>`.
+    relay_client: libp2p::relay::client::Behaviour,
+    /// Circuit-relay v2 server, on when [`NetworkConfig::relay_server`]
+    /// is set. Any member with a reachable address can serve.
+    relay_server: Toggle<libp2p::relay::Behaviour>,
+    /// Upgrades a relayed connection to a direct one by coordinated
+    /// hole punching once both ends have talked over the circuit.
+    dcutr: dcutr::Behaviour,
+    /// Probes our observed addresses through connected peers and
+    /// confirms the ones that are publicly dialable.
+    autonat: autonat::Behaviour,
 }
 
 /// One-way envelope received from a remote peer. Pushed to the
@@ -409,6 +454,28 @@ pub trait NetworkService: Send + Sync {
     }
 }
 
+/// Outcome of an operator-driven voter removal
+/// ([`RaftRpcHandler::handle_remove`]). Never crosses the wire: the
+/// operator's request reaches the leader's daemon as a reserved host
+/// invoke, and the daemon reports this result back in its reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RaftRemoveResult {
+    /// The leader proposed a joint-consensus entry dropping the voter.
+    /// The final configuration commits one round after `joint_index`.
+    Accepted { joint_index: u64 },
+    /// The voter is already absent from the steady-state configuration.
+    NotMember,
+    /// The receiver is not the leader of this replication group.
+    NotLeader { leader_hint: Option<u16> },
+    /// Another membership change is in flight; retry once it finalizes.
+    Busy,
+    /// The receiver isn't running the requested replication group.
+    UnknownGroup,
+    /// The removal would leave a configuration that cannot safely commit;
+    /// the reason names the violated bound.
+    Unsafe(String),
+}
+
 /// Local handler for inbound Raft RPCs. Mirrors [`SyncHandler`]'s
 /// shape: the swarm thread invokes the trait methods on its current-
 /// thread runtime, so implementations must be fast. Any redb writes
@@ -490,6 +557,15 @@ pub trait RaftRpcHandler: Send + Sync {
         RaftJoinResult::NotLeader { leader_hint: None }
     }
 
+    /// Operator-driven removal of `prefix` from this replication group.
+    /// Default impl returns `NotLeader { leader_hint: None }`; real workers
+    /// override this to check leadership, refuse a configuration that could
+    /// not commit, and call `change_membership(current \ {prefix})`.
+    /// Authorization is the caller's job — this trait never sees who asked.
+    fn handle_remove(&self, _replication_id: &[u8; 32], _prefix: u16) -> RaftRemoveResult {
+        RaftRemoveResult::NotLeader { leader_hint: None }
+    }
+
     /// Inbound `RaftStatusReq` — answer "what's your view of
     /// replication group X?" from a vosx-ps observer. Default
     /// impl returns [`RaftStatusReply::absent`]; concrete
@@ -505,6 +581,23 @@ pub trait RaftRpcHandler: Send + Sync {
 /// peer by prefix.
 type PrefixMap = Arc<Mutex<HashMap<u16, PeerId>>>;
 
+/// Prefixes this node has seen claimed by two different nodes —
+/// either two peers, or a peer and this node itself. A `ServiceId`
+/// carrying such a prefix can only ever reach the first owner.
+type PrefixCollisions = Arc<Mutex<BTreeSet<u16>>>;
+
+/// The 32-bit node ids learned from [`Frame::HelloWide`], and the ids
+/// seen claimed by two different nodes. Used to tell apart peers that
+/// share a prefix, not for routing. Only peers that negotiated the
+/// wide handshake appear here; legacy peers are known by prefix alone.
+#[derive(Default)]
+struct NodeIds {
+    owners: HashMap<u32, PeerId>,
+    collisions: BTreeSet<u32>,
+}
+
+type NodeIdMap = Arc<Mutex<NodeIds>>;
+
 /// Map: replication-group id → handler. Each Raft group running
 /// on this node registers itself via
 /// [`Network::register_raft_handler`]; the swarm thread routes
@@ -728,6 +821,8 @@ pub struct Network {
     local_prefix: u16,
     pub(in crate::network) cmd_tx: async_mpsc::UnboundedSender<NetworkCmd>,
     prefix_map: PrefixMap,
+    prefix_collisions: PrefixCollisions,
+    node_ids: NodeIdMap,
     listen_addrs: ListenAddrs,
     inbox_rx: Mutex<Option<std_mpsc::Receiver<InboundTell>>>,
     /// Inbound-frame handler for everything that isn't keyed by
@@ -747,6 +842,10 @@ pub struct Network {
     /// Frames carrying a `replication_id` with no entry surface to
     /// the peer as the default empty / current-term answer.
     raft_handlers: RaftHandlerMap,
+    /// Frame and byte counts by frame kind, shared with the swarm
+    /// thread's codec and gossip path. Read by the node's metrics
+    /// scrape via [`frame_traffic`](Self::frame_traffic).
+    frame_traffic: Arc<crate::metrics::FrameTraffic>,
     join: Option<JoinHandle<()>>,
 }
 
@@ -761,8 +860,9 @@ pub struct NetworkConfig {
     /// contact via [`Frame::Hello`] so they can resolve target
     /// prefixes back to a `PeerId`.
     pub local_prefix: u16,
-    /// Multiaddrs the node listens on. Empty = no inbound
-    /// connections (the node is dial-only).
+    /// Multiaddrs the node listens on: `/tcp/…` and/or
+    /// `/udp/…/quic-v1`, both at once for a dual-stack node. Empty =
+    /// no inbound connections (the node is dial-only).
     pub listen: Vec<Multiaddr>,
     /// Multiaddrs to dial at startup. Useful when not relying on
     /// mDNS / hyperspace discovery.
@@ -775,6 +875,20 @@ pub struct NetworkConfig {
     /// failed` WARNs from unrelated libp2p apps. Defaults to
     /// `true` for backwards compat.
     pub auto_dial_mdns: bool,
+    /// 32-byte keys (space id, hyperspace id) this node provides on
+    /// the Kademlia DHT. The swarm periodically looks up the other
+    /// providers of each key and dials them; the Hello handshake then
+    /// teaches both sides each other's `node_prefix`. Empty = the
+    /// node serves DHT queries but announces nothing.
+    pub discovery_keys: Vec<[u8; 32]>,
+    /// Circuit-relay addresses (`…/p2p/<relay>`) to hold a reservation
+    /// on. Each reservation adds a `/p2p-circuit` listen address, so
+    /// a node behind NAT can still be dialed through the relay. The
+    /// relays double as AutoNAT servers. Empty = no reservations.
+    pub relays: Vec<Multiaddr>,
+    /// Serve circuit-relay v2 reservations for other members. Only
+    /// useful on a node with a publicly reachable listen address.
+    pub relay_server: bool,
 }
 
 impl Default for NetworkConfig {
@@ -787,6 +901,9 @@ impl Default for NetworkConfig {
             listen: Vec::new(),
             bootstrap: Vec::new(),
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }
     }
 }
@@ -807,6 +924,24 @@ pub(in crate::network) enum NetworkCmd {
         msg: Vec<u8>,
         reply: std_mpsc::Sender<Vec<u8>>,
     },
+    /// Deliver a Tell to whichever peer owns `target_prefix` —
+    /// directly when connected, otherwise relayed through a neighbour.
+    SendTellToPrefix {
+        target_prefix: u16,
+        from: u32,
+        to: u32,
+        payload: Vec<u8>,
+    },
+    /// Prefix-addressed counterpart of [`NetworkCmd::SendInvoke`].
+    /// The reply is dropped when no route exists.
+    SendInvokeToPrefix {
+        target_prefix: u16,
+        from: u32,
+        to: u32,
+        chain: Vec<u32>,
+        msg: Vec<u8>,
+        reply: std_mpsc::Sender<Vec<u8>>,
+    },
     /// Security-sensitive invoke which returns a redirect to its caller
     /// instead of consulting the lossy 16-bit prefix map automatically.
     SendInvokeExact {
@@ -944,6 +1079,9 @@ pub(in crate::network) enum NetworkCmd {
 
 /// Outbound request kinds tracked while we wait for the reply.
 enum OutboundReply {
+    /// A [`Frame::HelloWide`]; on failure the dialer retries with the
+    /// legacy [`Frame::Hello`].
+    HelloWide,
     Invoke(PendingInvoke),
     InvokeExact(std_mpsc::Sender<NetworkInvokeResponse>),
     Heads(std_mpsc::Sender<Vec<[u8; 32]>>),
@@ -957,6 +1095,17 @@ enum OutboundReply {
     ProofBlob(std_mpsc::Sender<Option<Vec<u8>>>),
     ProgramBlob(std_mpsc::Sender<Option<Vec<u8>>>),
     PrivateIngress(std_mpsc::Sender<bool>),
+    /// A [`Frame::Relay`] this node forwarded on another hop's behalf.
+    /// Whatever the next hop answers is passed back unchanged; a
+    /// failure drops the channel so the previous hop fails too.
+    Relay(request_response::ResponseChannel<Frame>),
+    /// An invoke this node sealed as `relay_id` for `target_prefix`.
+    /// Only a [`Frame::RelayReply`] the target signed is accepted.
+    RelayedInvoke {
+        pending: PendingInvoke,
+        relay_id: u64,
+        target_prefix: u16,
+    },
 }
 
 struct PendingInvoke {
@@ -978,16 +1127,22 @@ impl Network {
             "NetworkConfig::local_prefix must equal the authenticated PeerId-derived prefix"
         );
         let prefix_map: PrefixMap = Arc::new(Mutex::new(HashMap::new()));
+        let prefix_collisions: PrefixCollisions = Arc::new(Mutex::new(BTreeSet::new()));
+        let node_ids: NodeIdMap = Arc::new(Mutex::new(NodeIds::default()));
         let listen_addrs: ListenAddrs = Arc::new(Mutex::new(Vec::new()));
         let service: Arc<OnceLock<Arc<dyn NetworkService>>> = Arc::new(OnceLock::new());
         let raft_handlers: RaftHandlerMap = Arc::new(Mutex::new(BTreeMap::new()));
         let (cmd_tx, cmd_rx) = async_mpsc::unbounded_channel();
         let (inbox_tx, inbox_rx) = std_mpsc::channel();
+        let frame_traffic = Arc::new(crate::metrics::FrameTraffic::default());
 
         let prefix_map_for_thread = prefix_map.clone();
+        let prefix_collisions_for_thread = prefix_collisions.clone();
+        let node_ids_for_thread = node_ids.clone();
         let listen_addrs_for_thread = listen_addrs.clone();
         let service_for_thread = service.clone();
         let raft_handlers_for_thread = raft_handlers.clone();
+        let frame_traffic_for_thread = frame_traffic.clone();
         let join = thread::spawn(move || {
             let rt = match tokio::runtime::Builder::new_current_thread()
                 .enable_all()
@@ -1003,10 +1158,13 @@ impl Network {
                 config,
                 cmd_rx,
                 prefix_map_for_thread,
+                prefix_collisions_for_thread,
+                node_ids_for_thread,
                 listen_addrs_for_thread,
                 inbox_tx,
                 service_for_thread,
                 raft_handlers_for_thread,
+                frame_traffic_for_thread,
             ));
         });
 
@@ -1015,10 +1173,13 @@ impl Network {
             local_prefix,
             cmd_tx,
             prefix_map,
+            prefix_collisions,
+            node_ids,
             listen_addrs,
             inbox_rx: Mutex::new(Some(inbox_rx)),
             service,
             raft_handlers,
+            frame_traffic,
             join: Some(join),
         }
     }
@@ -1191,6 +1352,26 @@ impl Network {
         reply.present.then_some(reply)
     }
 
+    /// Ask the locally hosted replica of one group to drop `prefix` from
+    /// its configuration. Only the leader can; see
+    /// [`RaftRpcHandler::handle_remove`]. The handler is cloned out of the
+    /// map first, so the lock is never held across the proposal.
+    pub(crate) fn local_raft_remove(
+        &self,
+        replication_id: &[u8; 32],
+        prefix: u16,
+    ) -> RaftRemoveResult {
+        let handler = self.raft_handlers.lock().ok().and_then(|handlers| {
+            handlers
+                .get(replication_id)
+                .map(|registration| registration.handler.clone())
+        });
+        match handler {
+            Some(handler) => handler.handle_remove(replication_id, prefix),
+            None => RaftRemoveResult::UnknownGroup,
+        }
+    }
+
     /// Non-blocking status snapshot for a locally hosted group. This reads a
     /// worker-published cache and never queues a request on the Raft worker.
     pub(crate) fn local_raft_status_cached(
@@ -1534,6 +1715,43 @@ impl Network {
         rx
     }
 
+    /// Send a fire-and-forget envelope to whichever peer owns
+    /// `target_prefix`. Unlike [`send_tell`](Self::send_tell) the
+    /// caller needn't be directly connected: without a direct peer the
+    /// swarm relays the Tell through intermediate members, and drops it
+    /// with a warn only when no neighbour can carry it.
+    pub fn send_tell_to_prefix(&self, target_prefix: u16, from: u32, to: u32, payload: Vec<u8>) {
+        let _ = self.cmd_tx.send(NetworkCmd::SendTellToPrefix {
+            target_prefix,
+            from,
+            to,
+            payload,
+        });
+    }
+
+    /// Prefix-addressed [`send_invoke`](Self::send_invoke): reaches the
+    /// owner of `target_prefix` directly or through relaying members.
+    /// The receiver disconnects without a reply when no route exists.
+    pub fn send_invoke_to_prefix(
+        &self,
+        target_prefix: u16,
+        from: u32,
+        to: u32,
+        chain: Vec<u32>,
+        msg: Vec<u8>,
+    ) -> std_mpsc::Receiver<Vec<u8>> {
+        let (tx, rx) = std_mpsc::channel();
+        let _ = self.cmd_tx.send(NetworkCmd::SendInvokeToPrefix {
+            target_prefix,
+            from,
+            to,
+            chain,
+            msg,
+            reply: tx,
+        });
+        rx
+    }
+
     /// Take ownership of the inbound-Tell receiver. The first call
     /// returns `Some(rx)`; subsequent calls return `None`. The
     /// caller is responsible for draining and dispatching frames
@@ -1564,6 +1782,38 @@ impl Network {
         self.local_prefix
     }
 
+    /// The local 32-bit node id, derived from the PeerId like the
+    /// prefix. See [`derive_node_id`].
+    pub fn local_node_id(&self) -> u32 {
+        derive_node_id(&self.peer_id)
+    }
+
+    /// Look up which peer owns a given 32-bit node id. Returns `None`
+    /// until the peer has completed the wide Hello handshake; legacy
+    /// peers only ever appear under [`peer_for_prefix`](Self::peer_for_prefix).
+    pub fn peer_for_node_id(&self, node_id: u32) -> Option<PeerId> {
+        self.node_ids.lock().ok()?.owners.get(&node_id).copied()
+    }
+
+    /// Snapshot of every peer that negotiated the wide handshake +
+    /// its node id, in no particular order.
+    pub fn peers_with_node_ids(&self) -> Vec<(u32, PeerId)> {
+        self.node_ids
+            .lock()
+            .map(|g| g.owners.iter().map(|(id, peer)| (*id, *peer)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Node ids found to be claimed by more than one node, either
+    /// detected here during a wide Hello or reported back by a peer
+    /// with [`Frame::PrefixCollision`].
+    pub fn node_id_collisions(&self) -> Vec<u32> {
+        self.node_ids
+            .lock()
+            .map(|g| g.collisions.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
     /// Look up which peer owns a given `node_prefix`. Returns
     /// `None` until the peer has completed the Hello handshake.
     pub fn peer_for_prefix(&self, prefix: u16) -> Option<PeerId> {
@@ -1590,6 +1840,25 @@ impl Network {
             .unwrap_or_default()
     }
 
+    /// Prefixes found here to be claimed by more than one node during
+    /// a Hello. Non-empty means some `ServiceId`s, which embed the
+    /// 16-bit prefix, are unreachable from this node; peers with
+    /// distinct node ids still complete the wide handshake.
+    pub fn prefix_collisions(&self) -> Vec<u16> {
+        self.prefix_collisions
+            .lock()
+            .map(|g| g.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Frames and bytes this node has sent and received, as
+    /// `(kind, direction, frames, bytes)` in kind order. Counts cover
+    /// request/response streams (length prefix included) and gossip
+    /// head announcements.
+    pub fn frame_traffic(&self) -> Vec<(&'static str, crate::metrics::FrameDirection, u64, u64)> {
+        self.frame_traffic.snapshot()
+    }
+
     /// Snapshot of all peers that have completed the Hello
     /// handshake. Used by the sync ticker to fan out fetches
     /// across every reachable replica, since the sync layer
@@ -1644,17 +1913,23 @@ async fn network_main(
     config: NetworkConfig,
     mut cmd_rx: async_mpsc::UnboundedReceiver<NetworkCmd>,
     prefix_map: PrefixMap,
+    prefix_collisions: PrefixCollisions,
+    node_ids: NodeIdMap,
     listen_addrs: ListenAddrs,
     inbox_tx: std_mpsc::Sender<InboundTell>,
     service: Arc<OnceLock<Arc<dyn NetworkService>>>,
     raft_handlers: RaftHandlerMap,
+    frame_traffic: Arc<crate::metrics::FrameTraffic>,
 ) {
     let local_peer_id = PeerId::from(config.keypair.public());
     let local_prefix = config.local_prefix;
     let auto_dial_mdns = config.auto_dial_mdns;
+    let discovery_keys = config.discovery_keys.clone();
+    let relay_server = config.relay_server;
+    let mut relay = RelayState::new(config.keypair.clone(), local_prefix);
     info!(peer_id = %local_peer_id, prefix = format!("{local_prefix:#06x}"), "network: starting");
 
-    let mut swarm = match build_swarm(config.keypair.clone()) {
+    let mut swarm = match build_swarm(config.keypair.clone(), relay_server, frame_traffic.clone()) {
         Ok(s) => s,
         Err(e) => {
             error!(error = %e, "network: failed to build swarm");
@@ -1669,10 +1944,41 @@ async fn network_main(
         }
     }
 
-    for addr in &config.bootstrap {
-        match swarm.dial(addr.clone()) {
-            Ok(_) => info!(%addr, "network: dialing bootstrap"),
-            Err(e) => warn!(%addr, error = %e, "network: dial failed"),
+    // A bootnode listed over both QUIC and TCP is dialed once, one
+    // address at a time, QUIC first: TCP is only tried if the QUIC
+    // handshake fails (e.g. UDP filtered).
+    for (peer, addrs) in group_bootstrap(&config.bootstrap) {
+        let opts = match peer {
+            Some(peer) => DialOpts::peer_id(peer)
+                .addresses(addrs.clone())
+                .override_dial_concurrency_factor(NonZeroU8::MIN)
+                .build(),
+            None => DialOpts::unknown_peer_id()
+                .address(addrs[0].clone())
+                .build(),
+        };
+        match swarm.dial(opts) {
+            Ok(_) => info!(?addrs, "network: dialing bootstrap"),
+            Err(e) => warn!(?addrs, error = %e, "network: dial failed"),
+        }
+    }
+
+    // Listening on `<relay>/p2p-circuit` dials the relay and asks it
+    // for a reservation; once accepted, the circuit address shows up
+    // as a `NewListenAddr` like any other.
+    for addr in &config.relays {
+        let Some(Protocol::P2p(relay_peer)) = addr.iter().last() else {
+            warn!(%addr, "network: relay address lacks a /p2p/<peer id>; skipping");
+            continue;
+        };
+        swarm
+            .behaviour_mut()
+            .autonat
+            .add_server(relay_peer, Some(addr.clone()));
+        let circuit = addr.clone().with(Protocol::P2pCircuit);
+        match swarm.listen_on(circuit.clone()) {
+            Ok(_) => info!(%circuit, "network: requesting relay reservation"),
+            Err(e) => warn!(%circuit, error = %e, "network: relay reservation failed"),
         }
     }
 
@@ -1712,22 +2018,55 @@ async fn network_main(
     // [`SyncRateLimiter`]).
     let mut sync_rate = SyncRateLimiter::default();
 
+    // DHT announce + lookup, and the neighbour advertisement that
+    // feeds relay next-hop selection. Both fire once immediately;
+    // the first rounds are cheap no-ops until a peer connects.
+    let mut discovery_tick = tokio::time::interval(DISCOVERY_INTERVAL);
+    let mut advert_tick = tokio::time::interval(NEIGHBOR_ADVERT_INTERVAL);
+
     loop {
         tokio::select! {
             event = swarm.select_next_some() => {
                 handle_swarm_event(
                     &mut swarm, event, local_prefix,
-                    &prefix_map, &listen_addrs, &inbox_tx,
+                    &prefix_map, &prefix_collisions, &node_ids, &listen_addrs, &inbox_tx,
                     &mut outbound_replies,
                     &mut warned_dial_failures,
                     &mut sync_rate,
+                    &mut relay,
                     &service,
                     &raft_handlers,
                     &response_tx,
                     &hint_senders,
+                    &frame_traffic,
                     auto_dial_mdns,
                 );
             }
+            _ = discovery_tick.tick(), if !discovery_keys.is_empty() => {
+                let dht = &mut swarm.behaviour_mut().kad;
+                // `NoKnownPeers` until identify has fed the routing
+                // table; the next tick retries.
+                let _ = dht.bootstrap();
+                for key in &discovery_keys {
+                    let key = kad::RecordKey::new(key);
+                    // Re-announce every round: a record published
+                    // before any peer connected reached nobody.
+                    if let Err(e) = dht.start_providing(key.clone()) {
+                        debug!(error = ?e, "network: DHT provide failed");
+                    }
+                    dht.get_providers(key);
+                }
+            }
+            _ = advert_tick.tick() => {
+                let direct = prefix_map.lock().map(|m| m.clone()).unwrap_or_default();
+                let advert = RelayState::advertisement(&direct);
+                for peer in direct.values() {
+                    let _ = swarm
+                        .behaviour_mut()
+                        .req_resp
+                        .send_request(peer, advert.clone());
+                }
+            }
             cmd = cmd_rx.recv() => {
                 match cmd {
                     Some(NetworkCmd::Connect(addr)) => {
@@ -1770,6 +2109,59 @@ async fn network_main(
                         );
                         debug!(%target_peer, from, to, "network: sent InvokeRequest");
                     }
+                    Some(NetworkCmd::SendTellToPrefix { target_prefix, from, to, payload }) => {
+                        let frame = Frame::Tell { from, to, payload };
+                        let sent = send_to_prefix(
+                            &mut swarm, &prefix_map, &mut relay, target_prefix, frame,
+                        );
+                        // The Ack carries nothing, so a relayed Tell
+                        // needs no signed reply.
+                        if sent.is_none() {
+                            warn!(
+                                prefix = format!("{target_prefix:#06x}"),
+                                "network: no direct or relayed route to prefix; dropping Tell",
+                            );
+                        }
+                    }
+                    Some(NetworkCmd::SendInvokeToPrefix {
+                        target_prefix, from, to, chain, msg, reply,
+                    }) => {
+                        let frame = Frame::InvokeRequest {
+                            from,
+                            to,
+                            chain: chain.clone(),
+                            msg: msg.clone(),
+                        };
+                        let sent = send_to_prefix(
+                            &mut swarm, &prefix_map, &mut relay, target_prefix, frame,
+                        );
+                        match sent {
+                            Some((req_id, relay_id)) => {
+                                let pending = PendingInvoke {
+                                    reply,
+                                    from,
+                                    to,
+                                    chain,
+                                    msg,
+                                    redirects: 0,
+                                };
+                                let pending = match relay_id {
+                                    Some(relay_id) => OutboundReply::RelayedInvoke {
+                                        pending,
+                                        relay_id,
+                                        target_prefix,
+                                    },
+                                    None => OutboundReply::Invoke(pending),
+                                };
+                                outbound_replies.insert(req_id, pending);
+                            }
+                            // Dropping `reply` disconnects the caller.
+                            None => warn!(
+                                prefix = format!("{target_prefix:#06x}"),
+                                "network: no direct or relayed route to prefix; failing invoke",
+                            ),
+                        }
+                    }
                     Some(NetworkCmd::SendInvokeExact {
                         target_peer,
                         from,
@@ -1877,8 +2269,13 @@ async fn network_main(
                         let topic = gossip_topic(&replication_id);
                         let frame = Frame::Heads { replication_id, roots };
                         let bytes = frame.encode();
+                        let len = bytes.len();
                         match swarm.behaviour_mut().gossip.publish(topic.clone(), bytes) {
-                            Ok(_) => {}
+                            Ok(_) => frame_traffic.record(
+                                frame.kind(),
+                                crate::metrics::FrameDirection::Sent,
+                                len,
+                            ),
                             // NoPeersSubscribedToTopic is the common
                             // case at startup before the topic mesh
                             // forms — not a real error, the next sync
@@ -2046,8 +2443,93 @@ async fn network_main(
     }
 }
 
+/// Send a `Tell` / `InvokeRequest` toward the owner of
+/// `target_prefix`: directly when the prefix map knows it, otherwise
+/// sealed in a [`Frame::Relay`] for the best next hop. Returns the
+/// request id, plus the `relay_id` when the frame was relayed. `None`
+/// when no neighbour can carry it.
+fn send_to_prefix(
+    swarm: &mut Swarm<VosBehaviour>,
+    prefix_map: &PrefixMap,
+    relay: &mut RelayState,
+    target_prefix: u16,
+    frame: Frame,
+) -> Option<(request_response::OutboundRequestId, Option<u64>)> {
+    let direct = prefix_map.lock().map(|m| m.clone()).unwrap_or_default();
+    if let Some(peer) = direct.get(&target_prefix) {
+        return Some((
+            swarm.behaviour_mut().req_resp.send_request(peer, frame),
+            None,
+        ));
+    }
+    let hop = relay.next_hop(&direct, target_prefix, &[])?;
+    let sealed = relay.seal(target_prefix, &frame)?;
+    let &Frame::Relay { relay_id, .. } = &sealed else {
+        return None;
+    };
+    debug!(
+        %hop,
+        prefix = format!("{target_prefix:#06x}"),
+        "network: relaying frame through neighbour",
+    );
+    let req_id = swarm.behaviour_mut().req_resp.send_request(&hop, sealed);
+    Some((req_id, Some(relay_id)))
+}
+
+/// Whether `addr` is a QUIC (`/udp/…/quic-v1`) address.
+fn is_quic(addr: &Multiaddr) -> bool {
+    addr.iter().any(|p| matches!(p, Protocol::QuicV1))
+}
+
+/// Order `addrs` QUIC-first, keeping the relative order otherwise.
+/// QUIC gives every request_response exchange its own stream with
+/// independent flow control, so a bulk snapshot chunk or proof blob
+/// can't stall the small Tells behind it the way one yamux-over-TCP
+/// connection does.
+fn prefer_quic(addrs: &mut [Multiaddr]) {
+    addrs.sort_by_key(|a| !is_quic(a));
+}
+
+/// Group bootstrap addresses by their `/p2p/<peer id>` suffix so a
+/// dual-stack bootnode gets one QUIC-first dial instead of two racing
+/// connections. Addresses without a peer id stay on their own.
+fn group_bootstrap(addrs: &[Multiaddr]) -> Vec<(Option<PeerId>, Vec<Multiaddr>)> {
+    let mut groups: Vec<(Option<PeerId>, Vec<Multiaddr>)> = Vec::new();
+    for addr in addrs {
+        let peer = match addr.iter().last() {
+            Some(Protocol::P2p(peer)) => Some(peer),
+            _ => None,
+        };
+        match groups
+            .iter_mut()
+            .find(|(p, _)| peer.is_some() && *p == peer)
+        {
+            Some((_, group)) => group.push(addr.clone()),
+            None => groups.push((peer, vec![addr.clone()])),
+        }
+    }
+    for (_, group) in &mut groups {
+        prefer_quic(group);
+    }
+    groups
+}
+
+/// Whether `addr` is worth handing to relay clients: not loopback and
+/// not itself a circuit through some other relay. `vosx space invite`
+/// uses the same test to offer a relay-serving node as a relay.
+pub fn is_relayable_listen_addr(addr: &Multiaddr) -> bool {
+    addr.iter().all(|p| match p {
+        Protocol::Ip4(ip) => !ip.is_loopback(),
+        Protocol::Ip6(ip) => !ip.is_loopback(),
+        Protocol::P2pCircuit => false,
+        _ => true,
+    })
+}
+
 fn build_swarm(
     keypair: identity::Keypair,
+    relay_server: bool,
+    frame_traffic: Arc<crate::metrics::FrameTraffic>,
 ) -> Result<Swarm<VosBehaviour>, Box<dyn std::error::Error + Send + Sync>> {
     let local_peer_id = PeerId::from(keypair.public());
     let swarm = libp2p::SwarmBuilder::with_existing_identity(keypair)
@@ -2057,7 +2539,9 @@ fn build_swarm(
             noise::Config::new,
             yamux::Config::default,
         )?
-        .with_behaviour(|key| {
+        .with_quic()
+        .with_relay_client(noise::Config::new, yamux::Config::default)?
+        .with_behaviour(|key, relay_client| {
             let mdns_cfg = mdns::Config::default();
             let mdns = mdns::tokio::Behaviour::new(mdns_cfg, local_peer_id).map_err::<Box<
                 dyn std::error::Error + Send + Sync,
@@ -2076,7 +2560,9 @@ fn build_swarm(
             // `DEFAULT_ASK_TIMEOUT` in service_host (the same
             // budget extensions' own `ask_raw` calls bound on).
             let req_resp = request_response::Behaviour::with_codec(
-                VosCodec,
+                VosCodec {
+                    traffic: frame_traffic.clone(),
+                },
                 std::iter::once((PROTOCOL, ProtocolSupport::Full)),
                 request_response::Config::default().with_request_timeout(Duration::from_secs(300)),
             );
@@ -2099,12 +2585,28 @@ fn build_swarm(
             .map_err::<Box<dyn std::error::Error + Send + Sync>, _>(|e| {
                 format!("gossipsub: {e}").into()
             })?;
+            let mut kad = kad::Behaviour::with_config(
+                local_peer_id,
+                kad::store::MemoryStore::new(local_peer_id),
+                kad::Config::new(KAD_PROTOCOL),
+            );
+            // Left to itself kad only serves queries once an external
+            // address is confirmed, which a LAN or loopback daemon
+            // never gets. Every VOS node is a full DHT participant.
+            kad.set_mode(Some(kad::Mode::Server));
+            let relay_server = relay_server
+                .then(|| libp2p::relay::Behaviour::new(local_peer_id, Default::default()));
             Ok::<_, Box<dyn std::error::Error + Send + Sync>>(VosBehaviour {
                 mdns,
                 ping,
                 identify,
                 req_resp,
                 gossip,
+                kad,
+                relay_client,
+                relay_server: relay_server.into(),
+                dcutr: dcutr::Behaviour::new(local_peer_id),
+                autonat: autonat::Behaviour::new(local_peer_id, Default::default()),
             })
         })?
         .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(60)))
@@ -2118,20 +2620,30 @@ fn handle_swarm_event(
     event: SwarmEvent<VosBehaviourEvent>,
     local_prefix: u16,
     prefix_map: &PrefixMap,
+    collisions: &PrefixCollisions,
+    node_ids: &NodeIdMap,
     listen_addrs: &ListenAddrs,
     inbox: &std_mpsc::Sender<InboundTell>,
     outbound_replies: &mut HashMap<request_response::OutboundRequestId, OutboundReply>,
     warned_dial_failures: &mut HashSet<PeerId>,
     sync_rate: &mut SyncRateLimiter,
+    relay: &mut RelayState,
     service: &Arc<OnceLock<Arc<dyn NetworkService>>>,
     raft_handlers: &RaftHandlerMap,
     response_tx: &async_mpsc::UnboundedSender<(request_response::ResponseChannel<Frame>, Frame)>,
     hint_senders: &HashMap<[u8; 32], std_mpsc::Sender<PeerId>>,
+    frame_traffic: &crate::metrics::FrameTraffic,
     auto_dial_mdns: bool,
 ) {
     match event {
         SwarmEvent::NewListenAddr { address, .. } => {
             info!(%address, "network: listening on");
+            // A relay hands its external addresses to reserving
+            // clients, so a serving node advertises what it binds
+            // rather than waiting for AutoNAT to confirm it.
+            if swarm.behaviour().relay_server.is_enabled() && is_relayable_listen_addr(&address) {
+                swarm.add_external_address(address.clone());
+            }
             if let Ok(mut v) = listen_addrs.lock()
                 && !v.contains(&address)
             {
@@ -2149,14 +2661,18 @@ fn handle_swarm_event(
             // The listener's reply rides back as the response, so
             // both sides learn each other's prefix from one round
             // trip. Doing it from both sides would be harmless but
-            // wasteful.
+            // wasteful. The wide Hello goes first; a legacy listener
+            // can't decode it, and the failure falls back to `Hello`.
             if endpoint.is_dialer() {
-                let _ = swarm.behaviour_mut().req_resp.send_request(
+                let node_id = derive_node_id(swarm.local_peer_id());
+                let req_id = swarm.behaviour_mut().req_resp.send_request(
                     &peer_id,
-                    Frame::Hello {
+                    Frame::HelloWide {
                         node_prefix: local_prefix,
+                        node_id,
                     },
                 );
+                outbound_replies.insert(req_id, OutboundReply::HelloWide);
             }
         }
         SwarmEvent::ConnectionClosed {
@@ -2168,6 +2684,10 @@ fn handle_swarm_event(
             info!(%peer_id, ?cause, "network: peer disconnected");
             if num_established == 0 {
                 forget_authenticated_prefix(prefix_map, peer_id);
+                if let Ok(mut ids) = node_ids.lock() {
+                    ids.owners.retain(|_, owner| *owner != peer_id);
+                }
+                relay.forget(&peer_id);
             }
         }
         SwarmEvent::OutgoingConnectionError { peer_id, error, .. } => {
@@ -2202,6 +2722,35 @@ fn handle_swarm_event(
             ..
         })) => {
             debug!(%peer_id, agent = %info.agent_version, "network: identify received");
+            // Only peers that speak the VOS DHT enter the routing
+            // table; unrelated libp2p apps found over mDNS stay out.
+            if info.protocols.contains(&KAD_PROTOCOL) {
+                let mut addrs = info.listen_addrs;
+                prefer_quic(&mut addrs);
+                for addr in addrs {
+                    swarm.behaviour_mut().kad.add_address(&peer_id, addr);
+                }
+            }
+        }
+        SwarmEvent::Behaviour(VosBehaviourEvent::Kad(kad::Event::OutboundQueryProgressed {
+            result:
+                kad::QueryResult::GetProviders(Ok(kad::GetProvidersOk::FoundProviders {
+                    providers,
+                    ..
+                })),
+            ..
+        })) => {
+            for provider in providers {
+                if provider == *swarm.local_peer_id() || swarm.is_connected(&provider) {
+                    continue;
+                }
+                info!(%provider, "network: DHT discovered peer; dialing");
+                // Dialling by PeerId lets kad supply the addresses it
+                // learned; the Hello handshake follows on connect.
+                if let Err(e) = swarm.dial(provider) {
+                    debug!(%provider, error = %e, "network: DHT dial failed");
+                }
+            }
         }
         SwarmEvent::Behaviour(VosBehaviourEvent::ReqResp(rr_event)) => {
             handle_req_resp(
@@ -2209,17 +2758,54 @@ fn handle_swarm_event(
                 rr_event,
                 local_prefix,
                 prefix_map,
+                collisions,
+                node_ids,
                 inbox,
                 outbound_replies,
                 warned_dial_failures,
                 sync_rate,
+                relay,
                 service,
                 raft_handlers,
                 response_tx,
             );
         }
         SwarmEvent::Behaviour(VosBehaviourEvent::Gossip(g_event)) => {
-            handle_gossipsub_event(g_event, hint_senders);
+            handle_gossipsub_event(g_event, hint_senders, frame_traffic);
+        }
+        SwarmEvent::Behaviour(VosBehaviourEvent::RelayClient(
+            libp2p::relay::client::Event::ReservationReqAccepted {
+                relay_peer_id,
+                renewal,
+                ..
+            },
+        )) => {
+            if !renewal {
+                info!(%relay_peer_id, "network: relay reservation accepted");
+            }
+        }
+        SwarmEvent::Behaviour(VosBehaviourEvent::RelayClient(ev)) => {
+            debug!(?ev, "network: relay client event");
+        }
+        SwarmEvent::Behaviour(VosBehaviourEvent::RelayServer(ev)) => {
+            debug!(?ev, "network: relay server event");
+        }
+        SwarmEvent::Behaviour(VosBehaviourEvent::Dcutr(dcutr::Event {
+            remote_peer_id,
+            result,
+        })) => match result {
+            Ok(_) => info!(%remote_peer_id, "network: hole punch succeeded; direct connection"),
+            Err(e) => {
+                // The relayed connection stays up, so traffic still
+                // flows — just through the relay.
+                info!(%remote_peer_id, error = %e, "network: hole punch failed; staying relayed")
+            }
+        },
+        SwarmEvent::Behaviour(VosBehaviourEvent::Autonat(autonat::Event::StatusChanged {
+            old,
+            new,
+        })) => {
+            info!(?old, ?new, "network: NAT status changed");
         }
         _ => {}
     }
@@ -2231,10 +2817,13 @@ fn handle_req_resp(
     event: request_response::Event<Frame, Frame>,
     local_prefix: u16,
     prefix_map: &PrefixMap,
+    collisions: &PrefixCollisions,
+    node_ids: &NodeIdMap,
     inbox: &std_mpsc::Sender<InboundTell>,
     outbound_replies: &mut HashMap<request_response::OutboundRequestId, OutboundReply>,
     warned_dial_failures: &mut HashSet<PeerId>,
     sync_rate: &mut SyncRateLimiter,
+    relay: &mut RelayState,
     service: &Arc<OnceLock<Arc<dyn NetworkService>>>,
     raft_handlers: &RaftHandlerMap,
     response_tx: &async_mpsc::UnboundedSender<(request_response::ResponseChannel<Frame>, Frame)>,
@@ -2248,7 +2837,10 @@ fn handle_req_resp(
             } => {
                 match request {
                     Frame::Hello { node_prefix } => {
-                        record_authenticated_prefix(prefix_map, node_prefix, peer);
+                        // A legacy peer can't decode `PrefixCollision`,
+                        // so a colliding prefix is only recorded here
+                        // and the reply stays a plain Hello.
+                        accept_hello(prefix_map, collisions, local_prefix, node_prefix, peer);
                         let _ = swarm.behaviour_mut().req_resp.send_response(
                             channel,
                             Frame::Hello {
@@ -2256,6 +2848,29 @@ fn handle_req_resp(
                             },
                         );
                     }
+                    Frame::HelloWide {
+                        node_prefix,
+                        node_id,
+                    } => {
+                        // The node id is what must be unique; a shared
+                        // 16-bit prefix only narrows the legacy index.
+                        // An id collision is answered instead of
+                        // silently dropped, so the newcomer learns its
+                        // services are unreachable from here.
+                        let local_node_id = derive_node_id(swarm.local_peer_id());
+                        let reply = if accept_node_id(node_ids, local_node_id, node_id, peer) {
+                            accept_hello(prefix_map, collisions, local_prefix, node_prefix, peer);
+                            Frame::HelloWide {
+                                node_prefix: local_prefix,
+                                node_id: local_node_id,
+                            }
+                        } else {
+                            Frame::PrefixCollision {
+                                node_id: derive_node_id(&peer),
+                            }
+                        };
+                        let _ = swarm.behaviour_mut().req_resp.send_response(channel, reply);
+                    }
                     Frame::Tell { from, to, payload } => {
                         let claimed_prefix = (from >> 16) as u16;
                         // Canonical v2 root transport is bound again to the
@@ -2308,34 +2923,143 @@ fn handle_req_resp(
                         // stream. Threaded into the dispatch so
                         // vos::node's auth gate can consult
                         // `auth_grants` for the caller.
-                        let svc = service.get().cloned();
-                        let response_tx = response_tx.clone();
-                        let caller = peer;
-                        tokio::task::spawn_blocking(move || {
-                            let response = match svc {
-                                Some(s) => {
-                                    s.dispatch_invoke_routed(Some(caller), from, to, chain, msg)
-                                }
-                                None => {
+                        spawn_invoke_dispatch(
+                            service,
+                            response_tx,
+                            channel,
+                            peer,
+                            from,
+                            to,
+                            chain,
+                            msg,
+                            None,
+                        );
+                    }
+                    Frame::Relay {
+                        relay_id,
+                        expires_at_ms,
+                        hops_left,
+                        mut path,
+                        target_prefix,
+                        origin_key,
+                        signature,
+                        inner,
+                    } => {
+                        let fields = RelayFields {
+                            relay_id,
+                            expires_at_ms,
+                            path: &path,
+                            target_prefix,
+                            origin_key: &origin_key,
+                            signature: &signature,
+                            inner: &inner,
+                        };
+                        // Refused frames drop `channel`, failing the
+                        // request back along every earlier hop.
+                        let direct = prefix_map.lock().map(|m| m.clone()).unwrap_or_default();
+                        let origin = match relay.open(peer, &direct, &fields) {
+                            Ok(origin) => origin,
+                            Err(reason) => {
+                                debug!(%peer, relay_id, %reason, "network: dropped relay frame");
+                                return;
+                            }
+                        };
+                        if target_prefix != local_prefix {
+                            if hops_left == 0 || path.len() >= wire::MAX_RELAY_HOPS {
+                                debug!(%origin, relay_id, "network: relay hop budget exhausted");
+                                return;
+                            }
+                            path.push(local_prefix);
+                            let Some(hop) = relay.next_hop(&direct, target_prefix, &path) else {
+                                debug!(
+                                    %origin,
+                                    prefix = format!("{target_prefix:#06x}"),
+                                    "network: no next hop for relay frame",
+                                );
+                                return;
+                            };
+                            let req_id = swarm.behaviour_mut().req_resp.send_request(
+                                &hop,
+                                Frame::Relay {
+                                    relay_id,
+                                    expires_at_ms,
+                                    hops_left: hops_left - 1,
+                                    path,
+                                    target_prefix,
+                                    origin_key,
+                                    signature,
+                                    inner,
+                                },
+                            );
+                            outbound_replies.insert(req_id, OutboundReply::Relay(channel));
+                            debug!(%origin, %hop, relay_id, "network: forwarded relay frame");
+                            return;
+                        }
+                        // Addressed to us. The origin signature stands in
+                        // for Noise: `origin` is the full authenticated
+                        // identity the inner frame is attributed to.
+                        match Frame::decode(&inner) {
+                            Ok(Frame::Tell { from, to, payload }) => {
+                                if !relayed_tell_source(relay, &direct, origin, from) {
                                     warn!(
+                                        %origin,
                                         from,
-                                        to,
-                                        "network: inbound InvokeRequest with no \
-                                         service installed; replying empty",
+                                        "network: rejected relayed Tell with a spoofed source route"
                                     );
-                                    NetworkInvokeResponse::Reply(Vec::new())
-                                }
-                            };
-                            let frame = match response {
-                                NetworkInvokeResponse::Reply(payload) => {
-                                    Frame::InvokeReply { payload }
-                                }
-                                NetworkInvokeResponse::Redirect { leader_prefix } => {
-                                    Frame::InvokeRedirect { leader_prefix }
+                                } else if inbox
+                                    .send(InboundTell {
+                                        peer: origin,
+                                        from,
+                                        to,
+                                        payload,
+                                    })
+                                    .is_err()
+                                {
+                                    warn!(%origin, "network: local inbox closed; dropping relayed Tell");
                                 }
-                            };
-                            let _ = response_tx.send((channel, frame));
-                        });
+                                let _ = swarm
+                                    .behaviour_mut()
+                                    .req_resp
+                                    .send_response(channel, Frame::Ack);
+                            }
+                            Ok(Frame::InvokeRequest {
+                                from,
+                                to,
+                                chain,
+                                msg,
+                            }) => {
+                                spawn_invoke_dispatch(
+                                    service,
+                                    response_tx,
+                                    channel,
+                                    origin,
+                                    from,
+                                    to,
+                                    chain,
+                                    msg,
+                                    Some(relay.reply_sealer(origin, relay_id)),
+                                );
+                            }
+                            other => {
+                                warn!(%origin, ?other, "network: relay frame carried an unsupported payload");
+                            }
+                        }
+                    }
+                    Frame::Neighbors { prefixes } => {
+                        // Only a Hello-authenticated neighbour may steer
+                        // our relay routing.
+                        let authenticated = prefix_map
+                            .lock()
+                            .ok()
+                            .and_then(|m| m.get(&derive_node_prefix(&peer)).copied())
+                            == Some(peer);
+                        if authenticated {
+                            relay.record_neighbors(peer, prefixes);
+                        }
+                        let _ = swarm
+                            .behaviour_mut()
+                            .req_resp
+                            .send_response(channel, Frame::Ack);
                     }
                     Frame::FetchHeads { replication_id } => {
                         // Per-peer flood cap: a peer hammering FetchHeads/
@@ -2779,9 +3503,82 @@ fn handle_req_resp(
                 ..
             } => {
                 let pending = outbound_replies.remove(&request_id);
+                // A relayed invoke's answer crossed untrusted hops: only
+                // the target's signed RelayReply is unwrapped, and the
+                // answer inside is then handled like a direct one.
+                let (response, pending) = match (response, pending) {
+                    (
+                        Frame::RelayReply {
+                            relay_id: reply_id,
+                            responder_key,
+                            signature,
+                            inner,
+                        },
+                        Some(OutboundReply::RelayedInvoke {
+                            pending,
+                            relay_id,
+                            target_prefix,
+                        }),
+                    ) => {
+                        let fields = RelayReplyFields {
+                            relay_id: reply_id,
+                            responder_key: &responder_key,
+                            signature: &signature,
+                            inner: &inner,
+                        };
+                        let direct = prefix_map.lock().map(|m| m.clone()).unwrap_or_default();
+                        match relay.open_reply(&direct, relay_id, target_prefix, &fields) {
+                            Ok(answer) => (answer, Some(OutboundReply::Invoke(pending))),
+                            // Dropping `pending` fails the caller.
+                            Err(reason) => {
+                                warn!(%peer, relay_id, %reason, "network: dropped relay reply");
+                                return;
+                            }
+                        }
+                    }
+                    (other, Some(OutboundReply::RelayedInvoke { relay_id, .. })) => {
+                        warn!(
+                            %peer,
+                            relay_id,
+                            frame = other.kind(),
+                            "network: relayed invoke answered without a signed relay reply",
+                        );
+                        return;
+                    }
+                    pair => pair,
+                };
                 match (response, pending) {
+                    (response, Some(OutboundReply::Relay(channel))) => {
+                        let _ = swarm
+                            .behaviour_mut()
+                            .req_resp
+                            .send_response(channel, response);
+                    }
                     (Frame::Hello { node_prefix }, _) => {
-                        record_authenticated_prefix(prefix_map, node_prefix, peer);
+                        accept_hello(prefix_map, collisions, local_prefix, node_prefix, peer);
+                    }
+                    (
+                        Frame::HelloWide {
+                            node_prefix,
+                            node_id,
+                        },
+                        _,
+                    ) => {
+                        let local_node_id = derive_node_id(swarm.local_peer_id());
+                        if accept_node_id(node_ids, local_node_id, node_id, peer) {
+                            accept_hello(prefix_map, collisions, local_prefix, node_prefix, peer);
+                        }
+                    }
+                    (Frame::PrefixCollision { node_id }, _) => {
+                        error!(
+                            %peer,
+                            node_id = format!("{node_id:#010x}"),
+                            "network: peer already routes this node's id to another node; \
+                             services here are unreachable through it until one side re-keys",
+                        );
+                        if let Ok(mut ids) = node_ids.lock() {
+                            ids.collisions.insert(node_id);
+                        }
                     }
                     (Frame::Ack, _) => {
                         debug!(%peer, "network: Tell ack received");
@@ -2931,6 +3728,25 @@ fn handle_req_resp(
             error,
             ..
         } => {
+            let pending = outbound_replies.remove(&request_id);
+            if let Some(OutboundReply::HelloWide) = pending {
+                // A legacy peer resets the stream on the unknown tag;
+                // the compat path is the 16-bit Hello it understands.
+                if swarm.is_connected(&peer) {
+                    debug!(
+                        %peer,
+                        error = %error,
+                        "network: wide Hello refused; falling back to Hello",
+                    );
+                    let _ = swarm.behaviour_mut().req_resp.send_request(
+                        &peer,
+                        Frame::Hello {
+                            node_prefix: local_prefix,
+                        },
+                    );
+                }
+                return;
+            }
             // Warn once per peer, then suppress: a non-listening client
             // (e.g. a connected console) or a departed peer gets re-targeted
             // every sync/mesh tick and would otherwise flood the log. The
@@ -2942,7 +3758,7 @@ fn handle_req_resp(
             }
             // Drop the reply Sender so the caller's recv yields
             // Disconnected — surfaces as None / NotFound.
-            let _ = outbound_replies.remove(&request_id);
+            drop(pending);
         }
         Event::InboundFailure { peer, error, .. } => {
             warn!(%peer, error = %error, "network: inbound request failed");
@@ -3068,6 +3884,7 @@ fn gossip_topic(rep_id: &[u8; 32]) -> gossipsub::IdentTopic {
 fn handle_gossipsub_event(
     event: gossipsub::Event,
     hint_senders: &HashMap<[u8; 32], std_mpsc::Sender<PeerId>>,
+    frame_traffic: &crate::metrics::FrameTraffic,
 ) {
     if let gossipsub::Event::Message {
         propagation_source,
@@ -3081,7 +3898,14 @@ fn handle_gossipsub_event(
         // is derivable but the frame's bytes are
         // authoritative).
         let frame = match Frame::decode(&message.data) {
-            Ok(f) => f,
+            Ok(f) => {
+                frame_traffic.record(
+                    f.kind(),
+                    crate::metrics::FrameDirection::Received,
+                    message.data.len(),
+                );
+                f
+            }
             Err(e) => {
                 warn!(error = %e, "gossipsub: bad frame, dropping");
                 return;
@@ -3097,7 +3921,64 @@ fn handle_gossipsub_event(
     }
 }
 
-fn record_authenticated_prefix(map: &PrefixMap, claimed_prefix: u16, peer: PeerId) {
+/// Learn `peer`'s prefix from a Hello exchange. Returns `false` (and
+/// records the prefix in `collisions`) when the prefix is this node's
+/// own or already belongs to another authenticated peer.
+fn accept_hello(
+    map: &PrefixMap,
+    collisions: &PrefixCollisions,
+    local_prefix: u16,
+    claimed_prefix: u16,
+    peer: PeerId,
+) -> bool {
+    let prefix = derive_node_prefix(&peer);
+    let accepted = if prefix == local_prefix {
+        warn!(
+            prefix = format!("{prefix:#06x}"),
+            %peer,
+            "network: peer derives this node's own prefix; its services are unreachable from here",
+        );
+        false
+    } else {
+        record_authenticated_prefix(map, claimed_prefix, peer)
+    };
+    if !accepted && let Ok(mut c) = collisions.lock() {
+        c.insert(prefix);
+    }
+    accepted
+}
+
+/// Learn `peer`'s 32-bit node id from a wide Hello exchange. Returns
+/// `false` (and records the id as a collision) when the id is this
+/// node's own or already belongs to another authenticated peer.
+fn accept_node_id(node_ids: &NodeIdMap, local_node_id: u32, claimed_id: u32, peer: PeerId) -> bool {
+    let node_id = derive_node_id(&peer);
+    if claimed_id != node_id {
+        warn!(
+            claimed = format!("{claimed_id:#010x}"),
+            derived = format!("{node_id:#010x}"),
+            %peer,
+            "network: ignored a peer-declared node id that does not match its authenticated PeerId"
+        );
+    }
+    let Ok(mut ids) = node_ids.lock() else {
+        return false;
+    };
+    let accepted = node_id != local_node_id && *ids.owners.entry(node_id).or_insert(peer) == peer;
+    if !accepted {
+        warn!(
+            node_id = format!("{node_id:#010x}"),
+            %peer,
+            "network: rejected a derived node-id collision; existing owner retained",
+        );
+        ids.collisions.insert(node_id);
+    }
+    accepted
+}
+
+/// Record `peer` as the owner of its derived prefix. Returns `false`
+/// when a different peer already owns it (the first owner is kept).
+fn record_authenticated_prefix(map: &PrefixMap, claimed_prefix: u16, peer: PeerId) -> bool {
     let prefix = derive_node_prefix(&peer);
     if claimed_prefix != prefix {
         warn!(
@@ -3109,16 +3990,19 @@ fn record_authenticated_prefix(map: &PrefixMap, claimed_prefix: u16, peer: PeerI
     }
     let mut m = match map.lock() {
         Ok(g) => g,
-        Err(_) => return,
+        Err(_) => return false,
     };
     match m.get(&prefix).copied() {
-        Some(prev) if prev == peer => {}
-        Some(prev) => warn!(
-            prefix = format!("{prefix:#06x}"),
-            previous = %prev,
-            current = %peer,
-            "network: rejected a derived-prefix collision; existing owner retained",
-        ),
+        Some(prev) if prev == peer => true,
+        Some(prev) => {
+            warn!(
+                prefix = format!("{prefix:#06x}"),
+                previous = %prev,
+                current = %peer,
+                "network: rejected a derived-prefix collision; existing owner retained",
+            );
+            false
+        }
         None => {
             m.insert(prefix, peer);
             info!(
@@ -3126,6 +4010,7 @@ fn record_authenticated_prefix(map: &PrefixMap, claimed_prefix: u16, peer: PeerI
                 %peer,
                 "network: learned authenticated peer prefix",
             );
+            true
         }
     }
 }
@@ -3138,6 +4023,77 @@ fn forget_authenticated_prefix(map: &PrefixMap, peer: PeerId) {
     prefixes.retain(|_, owner| *owner != peer);
 }
 
+/// Dispatch an inbound `InvokeRequest` on a blocking task and post
+/// the reply back through `response_tx`. `caller` is the authenticated
+/// identity of the invoker: the Noise peer for a direct request, the
+/// signing origin for a relayed one. A relayed request passes a
+/// `sealer`, and its reply goes back as a signed [`Frame::RelayReply`].
+#[allow(clippy::too_many_arguments)]
+fn spawn_invoke_dispatch(
+    service: &Arc<OnceLock<Arc<dyn NetworkService>>>,
+    response_tx: &async_mpsc::UnboundedSender<(request_response::ResponseChannel<Frame>, Frame)>,
+    channel: request_response::ResponseChannel<Frame>,
+    caller: PeerId,
+    from: u32,
+    to: u32,
+    chain: Vec<u32>,
+    msg: Vec<u8>,
+    sealer: Option<ReplySealer>,
+) {
+    let svc = service.get().cloned();
+    let response_tx = response_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let response = match svc {
+            Some(s) => s.dispatch_invoke_routed(Some(caller), from, to, chain, msg),
+            None => {
+                warn!(
+                    from,
+                    to,
+                    "network: inbound InvokeRequest with no \
+                     service installed; replying empty",
+                );
+                NetworkInvokeResponse::Reply(Vec::new())
+            }
+        };
+        let frame = match response {
+            NetworkInvokeResponse::Reply(payload) => Frame::InvokeReply { payload },
+            NetworkInvokeResponse::Redirect { leader_prefix } => {
+                Frame::InvokeRedirect { leader_prefix }
+            }
+        };
+        let frame = match sealer {
+            Some(sealer) => match sealer.seal(&frame) {
+                Some(sealed) => sealed,
+                None => {
+                    // Dropping `channel` fails the request at the origin.
+                    warn!(from, to, "network: failed to sign relay reply");
+                    return;
+                }
+            },
+            None => frame,
+        };
+        let _ = response_tx.send((channel, frame));
+    });
+}
+
+/// Source check for a Tell that arrived inside a [`Frame::Relay`].
+/// `origin` is signature-authenticated, so the claimed source prefix
+/// must be the one derived from it, and its authenticated owner — the
+/// directly connected peer, else the pinned relay origin — must be
+/// `origin` itself. Every payload, VRT2 included, passes the same
+/// owner check as a direct Tell.
+fn relayed_tell_source(
+    relay: &RelayState,
+    direct: &HashMap<u16, PeerId>,
+    origin: PeerId,
+    from: u32,
+) -> bool {
+    let claimed_prefix = (from >> 16) as u16;
+    claimed_prefix != 0
+        && claimed_prefix == derive_node_prefix(&origin)
+        && relay.owner(direct, claimed_prefix) == Some(origin)
+}
+
 fn authenticated_tell_source(map: &PrefixMap, peer: PeerId, from: u32) -> bool {
     let claimed_prefix = (from >> 16) as u16;
     claimed_prefix != 0
@@ -3177,6 +4133,28 @@ fn normalize_node_prefix(prefix: u16) -> u16 {
     }
 }
 
+/// Derive the 32-bit node id for a peer: the first four bytes of
+/// `blake2b(peer_id bytes)` (a 4-byte digest, so unrelated to the
+/// prefix bits) read little-endian, with zero remapped like the
+/// prefix. Exchanged by [`Frame::HelloWide`].
+///
+/// A few hundred nodes make a 16-bit prefix collision likely; the
+/// same odds in 32 bits need tens of thousands. The id only detects
+/// such collisions: routing, `ServiceId`s and stored state all keep
+/// the 16-bit prefix, so a shared prefix still reaches its first
+/// owner only.
+pub fn derive_node_id(peer_id: &PeerId) -> u32 {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(4)
+        .to_state()
+        .update(&peer_id.to_bytes())
+        .finalize();
+    match u32::from_le_bytes(hash.as_bytes().try_into().expect("4-byte digest")) {
+        0 => u32::MAX,
+        node_id => node_id,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3190,6 +4168,48 @@ mod tests {
         assert_eq!(p1, p2);
         assert_ne!(p1, 0);
         assert_eq!(normalize_node_prefix(0), u16::MAX);
+        assert_eq!(derive_node_id(&pid), derive_node_id(&pid));
+        assert_ne!(derive_node_id(&pid), 0);
+    }
+
+    #[test]
+    fn relay_server_advertises_only_routable_listen_addrs() {
+        let ok: Multiaddr = "/ip4/203.0.113.7/tcp/4001".parse().unwrap();
+        let lo: Multiaddr = "/ip4/127.0.0.1/tcp/4001".parse().unwrap();
+        let lo6: Multiaddr = "/ip6/::1/tcp/4001".parse().unwrap();
+        let circuit = ok
+            .clone()
+            .with(Protocol::P2p(PeerId::random()))
+            .with(Protocol::P2pCircuit);
+        assert!(is_relayable_listen_addr(&ok));
+        assert!(!is_relayable_listen_addr(&lo));
+        assert!(!is_relayable_listen_addr(&lo6));
+        assert!(!is_relayable_listen_addr(&circuit));
+    }
+
+    #[test]
+    fn relayed_tells_need_the_authenticated_prefix_owner() {
+        let kp = identity::Keypair::generate_ed25519();
+        let local = derive_node_prefix(&PeerId::from(kp.public()));
+        let relay = RelayState::new(kp, local);
+        let origin = PeerId::random();
+        let prefix = derive_node_prefix(&origin);
+        let from = ((prefix as u32) << 16) | 1;
+        let owned: HashMap<u16, PeerId> = [(prefix, origin)].into();
+        let squatted: HashMap<u16, PeerId> = [(prefix, PeerId::random())].into();
+
+        assert!(relayed_tell_source(&relay, &owned, origin, from));
+        // No VRT2 or any other payload bypass: a colliding owner, no
+        // owner at all, or a source prefix not derived from the origin
+        // are all refused.
+        assert!(!relayed_tell_source(&relay, &squatted, origin, from));
+        assert!(!relayed_tell_source(&relay, &HashMap::new(), origin, from));
+        assert!(!relayed_tell_source(
+            &relay,
+            &owned,
+            origin,
+            from ^ 0x0001_0000
+        ));
     }
 
     #[test]
@@ -3424,6 +4444,9 @@ mod tests {
             listen: Vec::new(),
             bootstrap: Vec::new(),
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
     }
 
@@ -3438,6 +4461,9 @@ mod tests {
             listen: Vec::new(),
             bootstrap: Vec::new(),
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let mut node = crate::node::VosNode::with_prefix(derived.wrapping_add(1));
         node.attach_network(network);
@@ -3487,6 +4513,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let inbox_a_rx = net_a.take_inbox().expect("first take");
 
@@ -3506,6 +4535,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let inbox_b_rx = net_b.take_inbox().expect("first take");
 
@@ -3559,6 +4591,77 @@ mod tests {
         net_b.join();
     }
 
+    #[test]
+    fn bootstrap_groups_dual_stack_peers_quic_first() {
+        let peer = PeerId::random();
+        let tcp: Multiaddr = format!("/ip4/10.0.0.1/tcp/4811/p2p/{peer}")
+            .parse()
+            .unwrap();
+        let quic: Multiaddr = format!("/ip4/10.0.0.1/udp/4811/quic-v1/p2p/{peer}")
+            .parse()
+            .unwrap();
+        let bare: Multiaddr = "/ip4/10.0.0.2/tcp/4811".parse().unwrap();
+        let groups = group_bootstrap(&[tcp.clone(), bare.clone(), quic.clone()]);
+        assert_eq!(
+            groups,
+            vec![(Some(peer), vec![quic, tcp]), (None, vec![bare])]
+        );
+    }
+
+    #[test]
+    fn tell_crosses_a_quic_only_connection() {
+        let kp_a = identity::Keypair::generate_ed25519();
+        let kp_b = identity::Keypair::generate_ed25519();
+        let prefix_a = derive_node_prefix(&PeerId::from(kp_a.public()));
+        let prefix_b = derive_node_prefix(&PeerId::from(kp_b.public()));
+
+        let net_a = Network::start(NetworkConfig {
+            keypair: kp_a,
+            local_prefix: prefix_a,
+            listen: vec!["/ip4/127.0.0.1/udp/0/quic-v1".parse().unwrap()],
+            bootstrap: vec![],
+            auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
+        });
+        let inbox_a_rx = net_a.take_inbox().expect("first take");
+        let a_addr = wait_for(
+            || net_a.listen_addrs().into_iter().next(),
+            Duration::from_secs(5),
+        )
+        .expect("net_a should have bound a QUIC address");
+        assert!(is_quic(&a_addr), "bound {a_addr}");
+
+        // B is dial-only: the only path between them is the QUIC one.
+        let net_b = Network::start(NetworkConfig {
+            keypair: kp_b,
+            local_prefix: prefix_b,
+            listen: vec![],
+            bootstrap: vec![a_addr.with(Protocol::P2p(net_a.peer_id()))],
+            auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
+        });
+        let target_a = wait_for(|| net_b.peer_for_prefix(prefix_a), Duration::from_secs(10))
+            .expect("Hello handshake over QUIC");
+        net_b.send_tell(
+            target_a,
+            (u32::from(prefix_b) << 16) | 1,
+            2,
+            b"over quic".to_vec(),
+        );
+        let inbound = inbox_a_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("Tell to A");
+        assert_eq!(inbound.payload, b"over quic");
+        assert_eq!(inbound.peer, net_b.peer_id());
+
+        net_a.join();
+        net_b.join();
+    }
+
     #[test]
     fn tell_source_route_is_bound_to_the_authenticated_peer_prefix() {
         let owner = identity::Keypair::generate_ed25519().public().to_peer_id();
@@ -3605,6 +4708,132 @@ mod tests {
         assert!(prefixes.lock().unwrap().is_empty());
     }
 
+    /// Two fresh keypairs whose PeerIds derive the same 16-bit prefix.
+    /// A birthday search — a few hundred keys on average.
+    fn colliding_keypairs() -> (identity::Keypair, identity::Keypair) {
+        let mut seen: HashMap<u16, identity::Keypair> = HashMap::new();
+        loop {
+            let kp = identity::Keypair::generate_ed25519();
+            let prefix = derive_node_prefix(&PeerId::from(kp.public()));
+            if let Some(first) = seen.remove(&prefix) {
+                return (first, kp);
+            }
+            seen.insert(prefix, kp);
+        }
+    }
+
+    #[test]
+    fn hello_from_a_peer_deriving_our_own_prefix_is_a_collision() {
+        let (mine, theirs) = colliding_keypairs();
+        let local_prefix = derive_node_prefix(&PeerId::from(mine.public()));
+        let peer = PeerId::from(theirs.public());
+        let prefixes = Arc::new(Mutex::new(HashMap::new()));
+        let collisions = Arc::new(Mutex::new(BTreeSet::new()));
+
+        assert!(!accept_hello(
+            &prefixes,
+            &collisions,
+            local_prefix,
+            local_prefix,
+            peer
+        ));
+        assert!(prefixes.lock().unwrap().is_empty());
+        assert!(collisions.lock().unwrap().contains(&local_prefix));
+    }
+
+    #[test]
+    fn node_id_collisions_are_refused_and_recorded() {
+        let kp = identity::Keypair::generate_ed25519();
+        let local_node_id = derive_node_id(&PeerId::from(kp.public()));
+        let peer = PeerId::random();
+        let node_id = derive_node_id(&peer);
+        let node_ids: NodeIdMap = Arc::new(Mutex::new(NodeIds::default()));
+
+        assert!(accept_node_id(&node_ids, local_node_id, node_id, peer));
+        assert!(accept_node_id(&node_ids, local_node_id, node_id, peer));
+        // Model a second key deriving the same id: the first owner stays.
+        let squatter = PeerId::random();
+        let squatted = derive_node_id(&squatter);
+        node_ids.lock().unwrap().owners.insert(squatted, peer);
+        assert!(!accept_node_id(
+            &node_ids,
+            local_node_id,
+            squatted,
+            squatter
+        ));
+        // A peer deriving this node's own id is never recorded.
+        let twin = PeerId::random();
+        assert!(!accept_node_id(&node_ids, derive_node_id(&twin), 0, twin));
+
+        let ids = node_ids.lock().unwrap();
+        assert_eq!(ids.owners.get(&node_id), Some(&peer));
+        assert_eq!(ids.owners.get(&squatted), Some(&peer));
+        assert!(!ids.owners.contains_key(&derive_node_id(&twin)));
+        assert_eq!(
+            ids.collisions,
+            BTreeSet::from([squatted, derive_node_id(&twin)])
+        );
+    }
+
+    #[test]
+    fn colliding_legacy_prefixes_still_complete_the_wide_handshake() {
+        let (kp_x, kp_y) = colliding_keypairs();
+        let shared = derive_node_prefix(&PeerId::from(kp_x.public()));
+        let kp_hub = identity::Keypair::generate_ed25519();
+        let hub_prefix = derive_node_prefix(&PeerId::from(kp_hub.public()));
+        let start =
+            |keypair: identity::Keypair, listen: Vec<Multiaddr>, bootstrap: Vec<Multiaddr>| {
+                let local_prefix = derive_node_prefix(&PeerId::from(keypair.public()));
+                Network::start(NetworkConfig {
+                    keypair,
+                    local_prefix,
+                    listen,
+                    bootstrap,
+                    auto_dial_mdns: false,
+                    discovery_keys: Vec::new(),
+                    relays: Vec::new(),
+                    relay_server: false,
+                })
+            };
+
+        let hub = start(
+            kp_hub,
+            vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+            vec![],
+        );
+        let hub_addr = wait_for(
+            || hub.listen_addrs().into_iter().next(),
+            Duration::from_secs(5),
+        )
+        .expect("hub should bind")
+        .with(Protocol::P2p(hub.peer_id()));
+
+        let x = start(kp_x, vec![], vec![hub_addr.clone()]);
+        wait_for(|| hub.peer_for_prefix(shared), Duration::from_secs(10))
+            .expect("hub learns the first owner");
+        let y = start(kp_y, vec![], vec![hub_addr]);
+
+        wait_for(
+            || hub.peer_for_node_id(y.local_node_id()),
+            Duration::from_secs(10),
+        )
+        .expect("distinct node ids are both learned despite the shared prefix");
+        assert_eq!(hub.prefix_collisions(), vec![shared]);
+        assert_eq!(
+            hub.peer_for_prefix(shared),
+            Some(x.peer_id()),
+            "the legacy prefix index keeps its first owner"
+        );
+        assert_eq!(hub.peer_for_node_id(x.local_node_id()), Some(x.peer_id()));
+        assert_eq!(y.peer_for_prefix(hub_prefix), Some(hub.peer_id()));
+        assert!(hub.node_id_collisions().is_empty());
+        assert!(y.node_id_collisions().is_empty());
+
+        hub.join();
+        x.join();
+        y.join();
+    }
+
     fn wait_for<T>(mut probe: impl FnMut() -> Option<T>, deadline: Duration) -> Option<T> {
         let until = std::time::Instant::now() + deadline;
         loop {
@@ -3641,6 +4870,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_listen = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -3655,6 +4887,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         let rep_id = [0xCDu8; 32];
@@ -3770,6 +5005,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_listen = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -3784,6 +5022,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         // Build node B with a pre-populated replica, attach net_b.
@@ -3887,6 +5128,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_listen = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -3901,6 +5145,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         let mut node_b = VosNode::with_prefix(prefix_b);
@@ -3993,6 +5240,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_listen = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -4007,6 +5257,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         let rep_id = [0x77u8; 32];
@@ -4085,6 +5338,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_listen = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -4099,6 +5355,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         let rep_id = [0x42u8; 32];
@@ -4206,6 +5465,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_listen = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -4219,6 +5481,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let seen = Arc::new(Mutex::new(None));
         net_b.set_service(Arc::new(PrivateSink {
@@ -4318,6 +5583,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_listen = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -4332,6 +5600,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         // Install dispatcher on B before any invoke can race in.
@@ -4431,6 +5702,9 @@ mod tests {
             listen: vec![listen.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let address_a = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -4444,6 +5718,9 @@ mod tests {
             listen: vec![listen.clone()],
             bootstrap: vec![address_a.clone()],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let net_c = Network::start(NetworkConfig {
             keypair: key_c,
@@ -4451,6 +5728,9 @@ mod tests {
             listen: vec![listen],
             bootstrap: vec![address_a],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         net_b.set_service(Arc::new(Redirector(prefix_c)));
         let seen_caller = Arc::new(Mutex::new(None));
@@ -4497,6 +5777,134 @@ mod tests {
         net_c.join();
     }
 
+    /// A and C each dial only B, and neither listens, so A can never
+    /// reach C directly: prefix-addressed sends must ride a
+    /// [`Frame::Relay`] through B and still attribute the frame to A.
+    #[test]
+    fn prefix_sends_relay_through_an_intermediate_member() {
+        struct CallerRecorder(Arc<Mutex<Option<PeerId>>>);
+        impl NetworkService for CallerRecorder {
+            fn dispatch_invoke(
+                &self,
+                caller_peer_id: Option<PeerId>,
+                _from: u32,
+                _to: u32,
+                _chain: Vec<u32>,
+                _msg: Vec<u8>,
+            ) -> Vec<u8> {
+                *self.0.lock().unwrap() = caller_peer_id;
+                b"relayed reply".to_vec()
+            }
+        }
+
+        let mut keys = Vec::new();
+        while keys.len() < 3 {
+            let key = identity::Keypair::generate_ed25519();
+            let prefix = derive_node_prefix(&PeerId::from(key.public()));
+            if keys.iter().all(|(_, p)| *p != prefix) {
+                keys.push((key, prefix));
+            }
+        }
+        let (key_c, prefix_c) = keys.pop().unwrap();
+        let (key_b, prefix_b) = keys.pop().unwrap();
+        let (key_a, prefix_a) = keys.pop().unwrap();
+
+        let net_b = Network::start(NetworkConfig {
+            keypair: key_b,
+            local_prefix: prefix_b,
+            listen: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
+            bootstrap: vec![],
+            auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
+        });
+        let address_b = wait_for(
+            || net_b.listen_addrs().into_iter().next(),
+            Duration::from_secs(5),
+        )
+        .expect("relay binds")
+        .with(libp2p::multiaddr::Protocol::P2p(net_b.peer_id()));
+        let net_a = Network::start(NetworkConfig {
+            keypair: key_a,
+            local_prefix: prefix_a,
+            listen: vec![],
+            bootstrap: vec![address_b.clone()],
+            auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
+        });
+        let net_c = Network::start(NetworkConfig {
+            keypair: key_c,
+            local_prefix: prefix_c,
+            listen: vec![],
+            bootstrap: vec![address_b],
+            auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
+        });
+        let seen_caller = Arc::new(Mutex::new(None));
+        net_c.set_service(Arc::new(CallerRecorder(seen_caller.clone())));
+        let inbox_c = net_c.take_inbox().unwrap();
+
+        wait_for(
+            || {
+                (net_a.peer_for_prefix(prefix_b).is_some()
+                    && net_b.peer_for_prefix(prefix_a).is_some()
+                    && net_b.peer_for_prefix(prefix_c).is_some())
+                .then_some(())
+            },
+            Duration::from_secs(10),
+        )
+        .expect("both edges authenticate through the relay");
+        assert_eq!(net_a.peer_for_prefix(prefix_c), None);
+
+        let reply = net_a
+            .send_invoke_to_prefix(
+                prefix_c,
+                ((prefix_a as u32) << 16) | 1,
+                ((prefix_c as u32) << 16) | 2,
+                vec![],
+                vec![7],
+            )
+            .recv_timeout(Duration::from_secs(5))
+            .expect("relayed invoke completes");
+        assert_eq!(reply, b"relayed reply");
+        assert_eq!(*seen_caller.lock().unwrap(), Some(net_a.peer_id()));
+
+        net_a.send_tell_to_prefix(
+            prefix_c,
+            ((prefix_a as u32) << 16) | 1,
+            ((prefix_c as u32) << 16) | 2,
+            b"over the hop".to_vec(),
+        );
+        let tell = inbox_c
+            .recv_timeout(Duration::from_secs(5))
+            .expect("relayed Tell delivered");
+        assert_eq!(tell.peer, net_a.peer_id());
+        assert_eq!(tell.payload, b"over the hop");
+
+        // No neighbour reaches an unknown prefix's owner: the invoke
+        // fails fast rather than waiting out its timeout.
+        let unknown = (1..=u16::MAX)
+            .find(|p| ![prefix_a, prefix_b, prefix_c].contains(p))
+            .unwrap();
+        let dead_end = Network::start(NetworkConfig::default());
+        assert!(
+            dead_end
+                .send_invoke_to_prefix(unknown, 0, (unknown as u32) << 16, vec![], vec![])
+                .recv_timeout(Duration::from_secs(5))
+                .is_err()
+        );
+
+        dead_end.join();
+        net_a.join();
+        net_b.join();
+        net_c.join();
+    }
+
     /// Full path: `VosNode::invoke` on A finds no local route,
     /// falls through to the network, hits B's `LocalInvokeHandler`
     /// (installed by `attach_network`), which dispatches against
@@ -4518,6 +5926,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_listen = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -4532,6 +5943,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         wait_for(
@@ -4609,6 +6023,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_listen = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -4623,6 +6040,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         // Wait for the Hello round trip before attaching, so we can
@@ -4816,6 +6236,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let a_addr = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -4830,6 +6253,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         // Install the stub on B — A is the leader-side caller.
@@ -5067,6 +6493,9 @@ mod tests {
             listen: vec![listen],
             bootstrap: vec![],
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let receiver_addr = wait_for(
             || receiver.listen_addrs().into_iter().next(),
@@ -5080,6 +6509,9 @@ mod tests {
             listen: Vec::new(),
             bootstrap: vec![receiver_addr],
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         wait_for(
             || receiver.peer_for_prefix(attacker_prefix).map(|_| ()),
@@ -5231,6 +6663,9 @@ mod tests {
             listen: vec![listen],
             bootstrap: vec![],
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }));
         let local_addr = wait_for(
             || local.listen_addrs().into_iter().next(),
@@ -5244,6 +6679,9 @@ mod tests {
             listen: Vec::new(),
             bootstrap: vec![local_addr],
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }));
         wait_for(
             || local.peer_for_prefix(attacker_prefix).map(|_| ()),
@@ -5277,6 +6715,7 @@ mod tests {
                 replication_id,
                 election_timeout_ms: (40, 80),
                 heartbeat_interval_ms: 20,
+                witnesses: Vec::new(),
             },
             Some(local.clone()),
             None,
@@ -5335,6 +6774,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }));
         let a_addr = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -5349,6 +6791,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![a_dial.clone()],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }));
         // Wait for B's listen addr so C can bootstrap to both A
         // and B — without an explicit dial between B and C the
@@ -5366,6 +6811,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial, b_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }));
 
         // Wait for the Hello triangle to close.
@@ -5410,6 +6858,7 @@ mod tests {
                     // election window so followers' timers are
                     // reset before they can challenge.
                     heartbeat_interval_ms: 20,
+                    witnesses: Vec::new(),
                 },
                 Some(network),
                 None,
@@ -5564,6 +7013,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }));
         let a_addr = wait_for(
             || net_a.listen_addrs().into_iter().next(),
@@ -5578,6 +7030,9 @@ mod tests {
             listen: vec![listen_addr.clone()],
             bootstrap: vec![a_dial.clone()],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }));
         let b_addr = wait_for(
             || net_b.listen_addrs().into_iter().next(),
@@ -5592,6 +7047,9 @@ mod tests {
             listen: vec![listen_addr],
             bootstrap: vec![a_dial, b_dial],
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }));
 
         wait_for(
@@ -5630,6 +7088,7 @@ mod tests {
                         replication_id: [0xC2; 32],
                         election_timeout_ms: (50, 150),
                         heartbeat_interval_ms: 20,
+                        witnesses: Vec::new(),
                     },
                     Some(network),
                     None,
@@ -0,0 +1,609 @@
+//! Multi-hop prefix routing for [`Frame::Relay`].
+//!
+//! The prefix map only resolves peers this node holds a direct,
+//! Hello-authenticated connection to. When a `Tell` /
+//! `InvokeRequest` targets a prefix outside that set, the swarm
+//! thread wraps it in an origin-signed [`Frame::Relay`] and hands it
+//! to the best next hop:
+//!
+//! 1. the target itself, if it is directly connected;
+//! 2. a neighbour whose last [`Frame::Neighbors`] advertisement
+//!    listed the target;
+//! 3. otherwise the connected neighbour whose prefix is XOR-closest
+//!    to the target.
+//!
+//! Loops are cut three ways: a hop never forwards to a prefix already
+//! in `path`, `hops_left` bounds the walk at [`MAX_RELAY_HOPS`], and
+//! every hop remembers `(origin, relay_id)` until the frame expires so
+//! a duplicate is dropped rather than forwarded again.
+//!
+//! The origin signature lets the target authenticate the origin's
+//! full `PeerId` exactly as Noise does for a direct connection;
+//! intermediate hops are trusted only to carry bytes. The target in
+//! turn signs its answer to a relayed `InvokeRequest` as a
+//! [`Frame::RelayReply`], bound to the origin and `relay_id`, so no hop
+//! can forge or swap an `InvokeReply` / `InvokeRedirect` on the way
+//! back.
+//!
+//! A relayed peer has no Hello to pin its prefix, so the first
+//! authenticated relay origin (or reply signer) for a prefix owns it,
+//! just as the first Hello owns a direct prefix: a later key that
+//! derives the same prefix is refused, and a directly connected owner
+//! always wins. Unlike a Hello pin, which ends with its connection, a
+//! relay pin lapses after [`RELAY_PIN_IDLE`] without authenticated
+//! traffic from its owner, so a colliding key that got there first
+//! holds the prefix only while it keeps talking.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use libp2p::{PeerId, identity};
+
+use super::wire::{MAX_NEIGHBORS, MAX_RELAY_HOPS, relay_reply_signing_bytes, relay_signing_bytes};
+use super::{Frame, derive_node_prefix};
+
+/// How long a sealed relay frame stays valid.
+const RELAY_TTL: Duration = Duration::from_secs(30);
+
+/// Tolerated clock skew between the origin and a hop when checking
+/// `expires_at_ms` for frames dated too far in the future.
+const RELAY_CLOCK_SKEW: Duration = Duration::from_secs(30);
+
+/// Cap on remembered `(origin, relay_id)` pairs. Entries age out with
+/// their frame's expiry; a node that still hits the cap refuses new
+/// relays rather than forgetting ones that could then be replayed.
+const MAX_SEEN_RELAYS: usize = 65_536;
+
+/// How long a relay owner pin outlives the last frame its owner
+/// authenticated. Expired pins are swept whenever a neighbour drops.
+const RELAY_PIN_IDLE: Duration = Duration::from_secs(600);
+
+/// Why an inbound [`Frame::Relay`] or [`Frame::RelayReply`] was
+/// refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RelayReject {
+    /// `path` is empty, doesn't end at the sending peer, or doesn't
+    /// start at the signing origin.
+    BadPath,
+    /// This node already appears in `path`.
+    Loop,
+    /// The origin key doesn't decode.
+    BadOrigin,
+    BadSignature,
+    Expired,
+    Duplicate,
+    Saturated,
+    /// The signer's prefix is already owned by a different `PeerId`.
+    OwnerMismatch,
+    /// A reply signed by a key that doesn't own the target prefix, or
+    /// answering a different relay.
+    WrongResponder,
+    /// A reply whose inner frame isn't an invoke answer.
+    BadReply,
+}
+
+impl core::fmt::Display for RelayReject {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let reason = match self {
+            RelayReject::BadPath => "relay path does not match its sender and origin",
+            RelayReject::Loop => "relay path already visited this node",
+            RelayReject::BadOrigin => "relay origin key failed to decode",
+            RelayReject::BadSignature => "relay origin signature is invalid",
+            RelayReject::Expired => "relay frame expired or dated in the future",
+            RelayReject::Duplicate => "relay frame already seen",
+            RelayReject::Saturated => "relay dedupe cache is full",
+            RelayReject::OwnerMismatch => "relay signer's prefix belongs to another peer",
+            RelayReject::WrongResponder => "relay reply is not from the target of this relay",
+            RelayReject::BadReply => "relay reply does not carry an invoke answer",
+        };
+        f.write_str(reason)
+    }
+}
+
+/// Borrowed view of an inbound [`Frame::Relay`]'s signed fields plus
+/// its routing state.
+pub(super) struct RelayFields<'a> {
+    pub relay_id: u64,
+    pub expires_at_ms: u64,
+    pub path: &'a [u16],
+    pub target_prefix: u16,
+    pub origin_key: &'a [u8],
+    pub signature: &'a [u8],
+    pub inner: &'a [u8],
+}
+
+/// Borrowed view of an inbound [`Frame::RelayReply`].
+pub(super) struct RelayReplyFields<'a> {
+    pub relay_id: u64,
+    pub responder_key: &'a [u8],
+    pub signature: &'a [u8],
+    pub inner: &'a [u8],
+}
+
+/// Signs the target's answer to one relayed `InvokeRequest`. Handed to
+/// the blocking dispatch task so the reply is sealed where it's built.
+pub(super) struct ReplySealer {
+    keypair: identity::Keypair,
+    origin: PeerId,
+    relay_id: u64,
+}
+
+impl ReplySealer {
+    /// Wrap `reply` in a [`Frame::RelayReply`] for the origin. `None`
+    /// only if signing fails.
+    pub(super) fn seal(&self, reply: &Frame) -> Option<Frame> {
+        let inner = reply.encode();
+        let signed = relay_reply_signing_bytes(&self.origin.to_bytes(), self.relay_id, &inner);
+        let signature = self.keypair.sign(&signed).ok()?;
+        Some(Frame::RelayReply {
+            relay_id: self.relay_id,
+            responder_key: self.keypair.public().encode_protobuf(),
+            signature,
+            inner,
+        })
+    }
+}
+
+/// Swarm-thread routing state: the local signing key, the prefixes
+/// each neighbour last advertised, the replay / loop dedupe set, and
+/// the owners pinned for prefixes only reached through relays, each
+/// with the unix-ms time its pin expires.
+pub(super) struct RelayState {
+    keypair: identity::Keypair,
+    local_prefix: u16,
+    next_id: u64,
+    neighbor_routes: HashMap<PeerId, Vec<u16>>,
+    seen: HashMap<(PeerId, u64), u64>,
+    owners: HashMap<u16, (PeerId, u64)>,
+}
+
+impl RelayState {
+    pub(super) fn new(keypair: identity::Keypair, local_prefix: u16) -> Self {
+        // Seed from the clock so a restarted origin doesn't reuse ids
+        // a hop may still remember from its previous run.
+        let next_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self {
+            keypair,
+            local_prefix,
+            next_id,
+            neighbor_routes: HashMap::new(),
+            seen: HashMap::new(),
+            owners: HashMap::new(),
+        }
+    }
+
+    /// Wrap `inner` for `target_prefix`, signed with the local key.
+    /// `None` only if signing fails. The frame's `relay_id` is what
+    /// [`RelayState::open_reply`] later expects the answer to carry.
+    pub(super) fn seal(&mut self, target_prefix: u16, inner: &Frame) -> Option<Frame> {
+        let relay_id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        let expires_at_ms = now_ms().saturating_add(RELAY_TTL.as_millis() as u64);
+        let inner = inner.encode();
+        let signed = relay_signing_bytes(relay_id, expires_at_ms, target_prefix, &inner);
+        let signature = self.keypair.sign(&signed).ok()?;
+        Some(Frame::Relay {
+            relay_id,
+            expires_at_ms,
+            hops_left: (MAX_RELAY_HOPS - 1) as u8,
+            path: vec![self.local_prefix],
+            target_prefix,
+            origin_key: self.keypair.public().encode_protobuf(),
+            signature,
+            inner,
+        })
+    }
+
+    /// Authenticate an inbound relay frame received from `sender` and
+    /// record it in the dedupe set. `direct` is the Hello-authenticated
+    /// prefix map; the origin must own its prefix there or, failing
+    /// that, in the relay pins. Returns the origin's full `PeerId` on
+    /// success.
+    pub(super) fn open(
+        &mut self,
+        sender: PeerId,
+        direct: &HashMap<u16, PeerId>,
+        relay: &RelayFields<'_>,
+    ) -> Result<PeerId, RelayReject> {
+        if relay.path.last().copied() != Some(derive_node_prefix(&sender)) {
+            return Err(RelayReject::BadPath);
+        }
+        if relay.path.contains(&self.local_prefix) {
+            return Err(RelayReject::Loop);
+        }
+        let origin_key = identity::PublicKey::try_decode_protobuf(relay.origin_key)
+            .map_err(|_| RelayReject::BadOrigin)?;
+        let origin = origin_key.to_peer_id();
+        if relay.path.first().copied() != Some(derive_node_prefix(&origin)) {
+            return Err(RelayReject::BadPath);
+        }
+        let signed = relay_signing_bytes(
+            relay.relay_id,
+            relay.expires_at_ms,
+            relay.target_prefix,
+            relay.inner,
+        );
+        if !origin_key.verify(&signed, relay.signature) {
+            return Err(RelayReject::BadSignature);
+        }
+        self.check_owner(direct, origin)?;
+        let now = now_ms();
+        let horizon = now.saturating_add((RELAY_TTL + RELAY_CLOCK_SKEW).as_millis() as u64);
+        if relay.expires_at_ms <= now || relay.expires_at_ms > horizon {
+            return Err(RelayReject::Expired);
+        }
+        if self.seen.contains_key(&(origin, relay.relay_id)) {
+            return Err(RelayReject::Duplicate);
+        }
+        if self.seen.len() >= MAX_SEEN_RELAYS {
+            self.seen.retain(|_, expires| *expires > now);
+            if self.seen.len() >= MAX_SEEN_RELAYS {
+                return Err(RelayReject::Saturated);
+            }
+        }
+        self.seen
+            .insert((origin, relay.relay_id), relay.expires_at_ms);
+        self.pin(derive_node_prefix(&origin), origin, now);
+        Ok(origin)
+    }
+
+    /// The authenticated owner of `prefix`: the direct peer if one is
+    /// connected, else the relay peer pinned to it, if its pin is live.
+    pub(super) fn owner(&self, direct: &HashMap<u16, PeerId>, prefix: u16) -> Option<PeerId> {
+        if let Some(peer) = direct.get(&prefix) {
+            return Some(*peer);
+        }
+        let now = now_ms();
+        self.owners
+            .get(&prefix)
+            .filter(|(_, expires)| *expires > now)
+            .map(|(peer, _)| *peer)
+    }
+
+    /// Pin `peer` as the relay owner of `prefix`, or extend its pin.
+    /// Callers have already passed [`Self::check_owner`], so a live pin
+    /// here is `peer`'s own.
+    fn pin(&mut self, prefix: u16, peer: PeerId, now: u64) {
+        let expires = now.saturating_add(RELAY_PIN_IDLE.as_millis() as u64);
+        self.owners.insert(prefix, (peer, expires));
+    }
+
+    /// A sealer for the answer to the relay `relay_id` from `origin`.
+    pub(super) fn reply_sealer(&self, origin: PeerId, relay_id: u64) -> ReplySealer {
+        ReplySealer {
+            keypair: self.keypair.clone(),
+            origin,
+            relay_id,
+        }
+    }
+
+    /// Authenticate the answer to a relay this node sealed for
+    /// `target_prefix` as `relay_id`. The signer must derive the target
+    /// prefix, own it, and have signed for this node and this relay.
+    /// Returns the inner `InvokeReply` / `InvokeRedirect`.
+    pub(super) fn open_reply(
+        &mut self,
+        direct: &HashMap<u16, PeerId>,
+        relay_id: u64,
+        target_prefix: u16,
+        reply: &RelayReplyFields<'_>,
+    ) -> Result<Frame, RelayReject> {
+        if reply.relay_id != relay_id {
+            return Err(RelayReject::WrongResponder);
+        }
+        let responder_key = identity::PublicKey::try_decode_protobuf(reply.responder_key)
+            .map_err(|_| RelayReject::BadOrigin)?;
+        let responder = responder_key.to_peer_id();
+        if derive_node_prefix(&responder) != target_prefix {
+            return Err(RelayReject::WrongResponder);
+        }
+        let local = self.keypair.public().to_peer_id();
+        let signed = relay_reply_signing_bytes(&local.to_bytes(), relay_id, reply.inner);
+        if !responder_key.verify(&signed, reply.signature) {
+            return Err(RelayReject::BadSignature);
+        }
+        self.check_owner(direct, responder)?;
+        let inner = match Frame::decode(reply.inner) {
+            Ok(frame @ (Frame::InvokeReply { .. } | Frame::InvokeRedirect { .. })) => frame,
+            _ => return Err(RelayReject::BadReply),
+        };
+        self.pin(target_prefix, responder, now_ms());
+        Ok(inner)
+    }
+
+    fn check_owner(&self, direct: &HashMap<u16, PeerId>, peer: PeerId) -> Result<(), RelayReject> {
+        match self.owner(direct, derive_node_prefix(&peer)) {
+            Some(owner) if owner != peer => Err(RelayReject::OwnerMismatch),
+            _ => Ok(()),
+        }
+    }
+
+    /// Pick the peer to hand a frame for `target_prefix` to, given the
+    /// authenticated direct connections and the prefixes already on
+    /// the frame's `path`. `None` when every neighbour is excluded.
+    pub(super) fn next_hop(
+        &self,
+        direct: &HashMap<u16, PeerId>,
+        target_prefix: u16,
+        path: &[u16],
+    ) -> Option<PeerId> {
+        if let Some(peer) = direct.get(&target_prefix) {
+            return Some(*peer);
+        }
+        let candidates = || {
+            direct
+                .iter()
+                .filter(|(prefix, _)| **prefix != self.local_prefix && !path.contains(prefix))
+        };
+        candidates()
+            .filter(|(_, peer)| {
+                self.neighbor_routes
+                    .get(peer)
+                    .is_some_and(|reach| reach.contains(&target_prefix))
+            })
+            .min_by_key(|(prefix, _)| **prefix)
+            .or_else(|| candidates().min_by_key(|(prefix, _)| **prefix ^ target_prefix))
+            .map(|(_, peer)| *peer)
+    }
+
+    /// Replace the prefixes `peer` reaches directly.
+    pub(super) fn record_neighbors(&mut self, peer: PeerId, mut prefixes: Vec<u16>) {
+        prefixes.retain(|prefix| *prefix != self.local_prefix);
+        self.neighbor_routes.insert(peer, prefixes);
+    }
+
+    /// Drop everything learned from `peer` once it disconnects, and
+    /// every relay pin that has lapsed.
+    pub(super) fn forget(&mut self, peer: &PeerId) {
+        self.neighbor_routes.remove(peer);
+        let now = now_ms();
+        self.owners.retain(|_, (_, expires)| *expires > now);
+    }
+
+    /// The advertisement to send our neighbours: every prefix we hold
+    /// an authenticated direct connection to.
+    pub(super) fn advertisement(direct: &HashMap<u16, PeerId>) -> Frame {
+        Frame::Neighbors {
+            prefixes: direct.keys().copied().take(MAX_NEIGHBORS).collect(),
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node() -> (identity::Keypair, PeerId, u16) {
+        let kp = identity::Keypair::generate_ed25519();
+        let peer = PeerId::from(kp.public());
+        let prefix = derive_node_prefix(&peer);
+        (kp, peer, prefix)
+    }
+
+    fn fields(frame: &Frame) -> RelayFields<'_> {
+        match frame {
+            Frame::Relay {
+                relay_id,
+                expires_at_ms,
+                path,
+                target_prefix,
+                origin_key,
+                signature,
+                inner,
+                ..
+            } => RelayFields {
+                relay_id: *relay_id,
+                expires_at_ms: *expires_at_ms,
+                path,
+                target_prefix: *target_prefix,
+                origin_key,
+                signature,
+                inner,
+            },
+            other => panic!("expected Relay, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn sealed_relay_opens_once_with_the_origin_identity() {
+        let (origin_kp, origin, origin_prefix) = node();
+        let (hop_kp, _, hop_prefix) = node();
+        let inner = Frame::Tell {
+            from: (origin_prefix as u32) << 16,
+            to: 0x4242_0001,
+            payload: b"hello".to_vec(),
+        };
+        let sealed = RelayState::new(origin_kp, origin_prefix)
+            .seal(0x4242, &inner)
+            .unwrap();
+        let mut hop = RelayState::new(hop_kp, hop_prefix);
+        let direct = HashMap::new();
+        assert_eq!(hop.open(origin, &direct, &fields(&sealed)), Ok(origin));
+        assert_eq!(
+            hop.open(origin, &direct, &fields(&sealed)),
+            Err(RelayReject::Duplicate)
+        );
+        assert_eq!(hop.owner(&direct, origin_prefix), Some(origin));
+    }
+
+    #[test]
+    fn relay_pins_lapse_and_free_the_prefix() {
+        let (origin_kp, origin, origin_prefix) = node();
+        let (hop_kp, _, hop_prefix) = node();
+        let mut hop = RelayState::new(hop_kp, hop_prefix);
+        let direct = HashMap::new();
+        let squatter = PeerId::random();
+        hop.owners
+            .insert(origin_prefix, (squatter, now_ms() + 60_000));
+        let sealed = RelayState::new(origin_kp.clone(), origin_prefix)
+            .seal(0x4242, &Frame::Ack)
+            .unwrap();
+        assert_eq!(
+            hop.open(origin, &direct, &fields(&sealed)),
+            Err(RelayReject::OwnerMismatch),
+            "a live pin refuses a colliding origin"
+        );
+
+        hop.owners.insert(origin_prefix, (squatter, now_ms() - 1));
+        assert_eq!(hop.owner(&direct, origin_prefix), None);
+        let sealed = RelayState::new(origin_kp, origin_prefix)
+            .seal(0x4242, &Frame::Ack)
+            .unwrap();
+        assert_eq!(hop.open(origin, &direct, &fields(&sealed)), Ok(origin));
+        assert_eq!(hop.owner(&direct, origin_prefix), Some(origin));
+
+        hop.owners.insert(0x4242, (squatter, now_ms() - 1));
+        hop.forget(&PeerId::random());
+        assert!(!hop.owners.contains_key(&0x4242), "lapsed pins are swept");
+        assert!(hop.owners.contains_key(&origin_prefix));
+    }
+
+    #[test]
+    fn relay_rejects_tampering_and_loops() {
+        let (origin_kp, origin, origin_prefix) = node();
+        let (hop_kp, hop, hop_prefix) = node();
+        let mut sealed = RelayState::new(origin_kp, origin_prefix)
+            .seal(0x4242, &Frame::Ack)
+            .unwrap();
+        let mut state = RelayState::new(hop_kp, hop_prefix);
+        let direct = HashMap::new();
+
+        // A hop that claims to forward on the origin's behalf must be
+        // the last prefix on the path.
+        assert_eq!(
+            state.open(hop, &direct, &fields(&sealed)),
+            Err(RelayReject::BadPath)
+        );
+
+        // A directly connected owner of the origin's prefix wins.
+        let squatter: HashMap<u16, PeerId> = [(origin_prefix, PeerId::random())].into();
+        assert_eq!(
+            state.open(origin, &squatter, &fields(&sealed)),
+            Err(RelayReject::OwnerMismatch)
+        );
+
+        if let Frame::Relay { inner, .. } = &mut sealed {
+            inner.push(0);
+        }
+        assert_eq!(
+            state.open(origin, &direct, &fields(&sealed)),
+            Err(RelayReject::BadSignature)
+        );
+
+        if let Frame::Relay { path, .. } = &mut sealed {
+            path.insert(0, hop_prefix);
+        }
+        assert_eq!(
+            state.open(origin, &direct, &fields(&sealed)),
+            Err(RelayReject::Loop)
+        );
+    }
+
+    fn reply_fields(frame: &Frame) -> RelayReplyFields<'_> {
+        match frame {
+            Frame::RelayReply {
+                relay_id,
+                responder_key,
+                signature,
+                inner,
+            } => RelayReplyFields {
+                relay_id: *relay_id,
+                responder_key,
+                signature,
+                inner,
+            },
+            other => panic!("expected RelayReply, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn relay_replies_verify_end_to_end_and_reject_forgeries() {
+        let (origin_kp, origin, origin_prefix) = node();
+        let (target_kp, target, target_prefix) = node();
+        let (hop_kp, _, hop_prefix) = node();
+        let mut origin_state = RelayState::new(origin_kp, origin_prefix);
+        let sealed = origin_state.seal(target_prefix, &Frame::Ack).unwrap();
+        let Frame::Relay { relay_id, .. } = sealed else {
+            unreachable!()
+        };
+        let mut target_state = RelayState::new(target_kp, target_prefix);
+        let direct = HashMap::new();
+        assert_eq!(
+            target_state.open(origin, &direct, &fields(&sealed)),
+            Ok(origin)
+        );
+        let answer = Frame::InvokeReply {
+            payload: b"ok".to_vec(),
+        };
+        let reply = target_state
+            .reply_sealer(origin, relay_id)
+            .seal(&answer)
+            .unwrap();
+
+        // A hop re-signing a forged answer with its own key.
+        let forged = RelayState::new(hop_kp, hop_prefix)
+            .reply_sealer(origin, relay_id)
+            .seal(&Frame::InvokeRedirect { leader_prefix: 7 })
+            .unwrap();
+        assert_eq!(
+            origin_state.open_reply(&direct, relay_id, target_prefix, &reply_fields(&forged)),
+            Err(RelayReject::WrongResponder)
+        );
+        // The target's signature over a swapped payload.
+        let mut swapped = reply.clone();
+        if let Frame::RelayReply { inner, .. } = &mut swapped {
+            *inner = Frame::InvokeReply {
+                payload: b"no".to_vec(),
+            }
+            .encode();
+        }
+        assert_eq!(
+            origin_state.open_reply(&direct, relay_id, target_prefix, &reply_fields(&swapped)),
+            Err(RelayReject::BadSignature)
+        );
+        // The right answer replayed into another relay.
+        assert_eq!(
+            origin_state.open_reply(&direct, relay_id + 1, target_prefix, &reply_fields(&reply)),
+            Err(RelayReject::WrongResponder)
+        );
+        assert_eq!(
+            origin_state.open_reply(&direct, relay_id, target_prefix, &reply_fields(&reply)),
+            Ok(answer)
+        );
+        assert_eq!(origin_state.owner(&direct, target_prefix), Some(target));
+    }
+
+    #[test]
+    fn next_hop_prefers_direct_then_advertised_then_xor_closest() {
+        let (kp, _, local) = node();
+        let mut state = RelayState::new(kp, local);
+        let a = PeerId::random();
+        let b = PeerId::random();
+        let direct: HashMap<u16, PeerId> = [(0x0100, a), (0x0F00, b)].into_iter().collect();
+
+        assert_eq!(state.next_hop(&direct, 0x0100, &[local]), Some(a));
+        // Nothing advertised: 0x0F00 is XOR-closer to 0x0F01.
+        assert_eq!(state.next_hop(&direct, 0x0F01, &[local]), Some(b));
+        // `a` advertises a direct link to the target.
+        state.record_neighbors(a, vec![0x0F01]);
+        assert_eq!(state.next_hop(&direct, 0x0F01, &[local]), Some(a));
+        // A prefix already on the path is never revisited.
+        assert_eq!(state.next_hop(&direct, 0x0F01, &[local, 0x0100]), Some(b));
+        assert_eq!(
+            state.next_hop(&direct, 0x0F01, &[local, 0x0100, 0x0F00]),
+            None
+        );
+        state.forget(&a);
+        assert_eq!(state.next_hop(&direct, 0x0F01, &[local]), Some(b));
+    }
+}
@@ -5,11 +5,12 @@
 //! envelope around frames — the libp2p `request_response` codec
 //! length-prefixes the bytes for us.
 //!
-//! Frames carry control messages (`Hello`, `Tell`, `InvokeRequest` /
-//! `InvokeReply`), CRDT sync reads (`FetchHeads` / `Heads`,
-//! `FetchNode` / `NodeReply`), Raft RPCs, manifest fetches, and
-//! content-addressed blob fetches. Each frame is tagged so the
-//! decoder can dispatch without a schema.
+//! Frames carry control messages (`Hello` / `HelloWide`, `Tell`,
+//! `InvokeRequest` / `InvokeReply`), CRDT sync reads (`FetchHeads` /
+//! `Heads`, `FetchNode` / `NodeReply`), Raft RPCs, manifest fetches,
+//! content-addressed blob fetches, and the multi-hop `Relay` /
+//! `RelayReply` / `Neighbors` routing frames. Each frame is tagged so
+//! the decoder can dispatch without a schema.
 //!
 //! The encoding is deliberately hand-rolled (no serde / rkyv): the
 //! schema is small, framing the wire format ourselves makes
@@ -17,6 +18,8 @@
 //! into the network feature's dep tree.
 
 const TAG_HELLO: u8 = 0x10;
+const TAG_PREFIX_COLLISION: u8 = 0x11;
+const TAG_HELLO_WIDE: u8 = 0x12;
 const TAG_TELL: u8 = 0x01;
 const TAG_INVOKE_REQ: u8 = 0x02;
 const TAG_INVOKE_REPLY: u8 = 0x03;
@@ -61,6 +64,13 @@ const TAG_FETCH_PROGRAM_BLOB: u8 = 0x52;
 const TAG_PROGRAM_BLOB_REPLY: u8 = 0x53;
 const TAG_STORE_PRIVATE_INGRESS: u8 = 0x54;
 const TAG_PRIVATE_INGRESS_STORED: u8 = 0x55;
+// Multi-hop prefix routing. `RELAY` wraps an origin-signed `Tell` /
+// `InvokeRequest` for a prefix the sender has no direct connection
+// to; `NEIGHBORS` advertises the prefixes a peer reaches directly so
+// its neighbours can pick a next hop.
+const TAG_RELAY: u8 = 0x60;
+const TAG_NEIGHBORS: u8 = 0x61;
+const TAG_RELAY_REPLY: u8 = 0x62;
 
 /// CIDs are 32-byte blake2b hashes (matches `commit::Blake2b` in
 /// `vos`). A wider hasher would require a wire-format bump.
@@ -127,12 +137,52 @@ const MAX_CHAIN_LEN: usize = 32;
 /// consume.
 const MAX_PRIVATE_INGRESS_BYTES: usize = 64 * 1024;
 
+/// Maximum number of hops a [`Frame::Relay`] may take, origin
+/// included. Bounds both the `hops_left` budget and the visited
+/// `path`, so a relay loop dies after at most this many forwards.
+pub const MAX_RELAY_HOPS: usize = 8;
+
+/// Cap on the protobuf-encoded public key and on the signature
+/// carried by a [`Frame::Relay`] or [`Frame::RelayReply`]. Ed25519 needs 36 and 64
+/// bytes respectively; the slack admits the other libp2p key types.
+const MAX_RELAY_KEY_BYTES: usize = 512;
+
+/// Cap on the prefixes listed in one [`Frame::Neighbors`]
+/// advertisement. A node's direct connection count stays far below
+/// this; the cap bounds the alloc a malicious peer can force.
+pub(crate) const MAX_NEIGHBORS: usize = 1024;
+
+/// Domain tag for the bytes an origin signs over a relayed frame.
+const RELAY_SIGNING_DOMAIN: &[u8] = b"vos-relay/v1";
+
+/// Domain tag for the bytes a relay target signs over its reply.
+const RELAY_REPLY_SIGNING_DOMAIN: &[u8] = b"vos-relay-reply/v1";
+
 /// One frame on the wire. See module docs for tag layout.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Frame {
+    /// Legacy (version 1) handshake: the 16-bit prefix only. Still
+    /// answered, and still sent to a peer that can't decode
+    /// [`Frame::HelloWide`].
     Hello {
         node_prefix: u16,
     },
+    /// Reply to [`Frame::HelloWide`] when the sender's 32-bit node id
+    /// is already held by a different authenticated peer (or by the
+    /// responder itself). Services on the sender stay unreachable
+    /// from the responder until one side re-keys. Never sent in reply
+    /// to a legacy [`Frame::Hello`]: such a peer predates this tag.
+    PrefixCollision {
+        node_id: u32,
+    },
+    /// Version 2 handshake: the legacy 16-bit prefix plus the 32-bit
+    /// node id (see `derive_node_id`). Answered in kind, or with
+    /// [`Frame::PrefixCollision`]. A legacy peer fails to decode it,
+    /// and the dialer falls back to [`Frame::Hello`].
+    HelloWide {
+        node_prefix: u16,
+        node_id: u32,
+    },
     Tell {
         from: u32,
         to: u32,
@@ -349,6 +399,72 @@ pub enum Frame {
     PrivateIngressStored {
         accepted: bool,
     },
+    /// An encoded `Tell` / `InvokeRequest` (`inner`) travelling to
+    /// `target_prefix` through intermediate members. The origin signs
+    /// [`relay_signing_bytes`] with its libp2p key, so every hop can
+    /// authenticate the origin without a direct connection to it.
+    /// `path` lists the prefixes already visited (origin first) and
+    /// `hops_left` the remaining forward budget; both are per-hop
+    /// routing state and deliberately unsigned. A relayed
+    /// `InvokeRequest` is answered with a [`Frame::RelayReply`] that
+    /// rides back unchanged along the same hops.
+    Relay {
+        relay_id: u64,
+        expires_at_ms: u64,
+        hops_left: u8,
+        path: Vec<u16>,
+        target_prefix: u16,
+        origin_key: Vec<u8>,
+        signature: Vec<u8>,
+        inner: Vec<u8>,
+    },
+    /// The target's answer to a relayed `InvokeRequest`: an encoded
+    /// `InvokeReply` / `InvokeRedirect` (`inner`) signed by the
+    /// target's libp2p key over [`relay_reply_signing_bytes`], so the
+    /// origin can reject a reply forged by any hop on the way back.
+    RelayReply {
+        relay_id: u64,
+        responder_key: Vec<u8>,
+        signature: Vec<u8>,
+        inner: Vec<u8>,
+    },
+    /// Prefixes the sender currently has an authenticated direct
+    /// connection to. Acked with [`Frame::Ack`]; receivers use it to
+    /// pick a relay next hop for prefixes they can't reach directly.
+    Neighbors {
+        prefixes: Vec<u16>,
+    },
+}
+
+/// Bytes an origin signs for a [`Frame::Relay`]. Covers everything
+/// but the per-hop `path` / `hops_left` routing state.
+pub(crate) fn relay_signing_bytes(
+    relay_id: u64,
+    expires_at_ms: u64,
+    target_prefix: u16,
+    inner: &[u8],
+) -> Vec<u8> {
+    let mut out = Vec::with_capacity(RELAY_SIGNING_DOMAIN.len() + 18 + inner.len());
+    out.extend_from_slice(RELAY_SIGNING_DOMAIN);
+    out.extend_from_slice(&relay_id.to_le_bytes());
+    out.extend_from_slice(&expires_at_ms.to_le_bytes());
+    out.extend_from_slice(&target_prefix.to_le_bytes());
+    out.extend_from_slice(inner);
+    out
+}
+
+/// Bytes a relay target signs for a [`Frame::RelayReply`]. Binds the
+/// reply to the requesting origin's `PeerId` and its `relay_id`, so a
+/// hop can't replay one origin's reply into another request.
+pub(crate) fn relay_reply_signing_bytes(origin: &[u8], relay_id: u64, inner: &[u8]) -> Vec<u8> {
+    let mut out =
+        Vec::with_capacity(RELAY_REPLY_SIGNING_DOMAIN.len() + 12 + origin.len() + inner.len());
+    out.extend_from_slice(RELAY_REPLY_SIGNING_DOMAIN);
+    out.extend_from_slice(&(origin.len() as u32).to_le_bytes());
+    out.extend_from_slice(origin);
+    out.extend_from_slice(&relay_id.to_le_bytes());
+    out.extend_from_slice(inner);
+    out
 }
 
 /// One actor's name + compiled PVM blob, as ferried by
@@ -487,6 +603,46 @@ pub(crate) fn raft_append_prefix_len(entries: &[RaftEntry]) -> Option<usize> {
 }
 
 impl Frame {
+    /// Stable snake_case name of the variant — the `kind` label on the
+    /// node's per-frame traffic metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Frame::Hello { .. } => "hello",
+            Frame::PrefixCollision { .. } => "prefix_collision",
+            Frame::HelloWide { .. } => "hello_wide",
+            Frame::Tell { .. } => "tell",
+            Frame::InvokeRequest { .. } => "invoke_request",
+            Frame::InvokeReply { .. } => "invoke_reply",
+            Frame::InvokeRedirect { .. } => "invoke_redirect",
+            Frame::FetchHeads { .. } => "fetch_heads",
+            Frame::Heads { .. } => "heads",
+            Frame::FetchNode { .. } => "fetch_node",
+            Frame::NodeReply { .. } => "node_reply",
+            Frame::Ack => "ack",
+            Frame::RaftAppendReq { .. } => "raft_append_req",
+            Frame::RaftAppendResp { .. } => "raft_append_resp",
+            Frame::RaftVoteReq { .. } => "raft_vote_req",
+            Frame::RaftVoteResp { .. } => "raft_vote_resp",
+            Frame::RaftInstallSnapshotReq { .. } => "raft_install_snapshot_req",
+            Frame::RaftInstallSnapshotResp { .. } => "raft_install_snapshot_resp",
+            Frame::RaftJoinReq { .. } => "raft_join_req",
+            Frame::RaftJoinResp { .. } => "raft_join_resp",
+            Frame::ManifestReq => "manifest_req",
+            Frame::ManifestResp { .. } => "manifest_resp",
+            Frame::RaftStatusReq { .. } => "raft_status_req",
+            Frame::RaftStatusResp { .. } => "raft_status_resp",
+            Frame::FetchProofBlob { .. } => "fetch_proof_blob",
+            Frame::ProofBlobReply { .. } => "proof_blob_reply",
+            Frame::FetchProgramBlob { .. } => "fetch_program_blob",
+            Frame::ProgramBlobReply { .. } => "program_blob_reply",
+            Frame::StorePrivateIngress { .. } => "store_private_ingress",
+            Frame::PrivateIngressStored { .. } => "private_ingress_stored",
+            Frame::Relay { .. } => "relay",
+            Frame::RelayReply { .. } => "relay_reply",
+            Frame::Neighbors { .. } => "neighbors",
+        }
+    }
+
     pub fn encode(&self) -> Vec<u8> {
         let mut out = Vec::new();
         match self {
@@ -494,6 +650,18 @@ impl Frame {
                 out.push(TAG_HELLO);
                 out.extend_from_slice(&node_prefix.to_le_bytes());
             }
+            Frame::PrefixCollision { node_id } => {
+                out.push(TAG_PREFIX_COLLISION);
+                out.extend_from_slice(&node_id.to_le_bytes());
+            }
+            Frame::HelloWide {
+                node_prefix,
+                node_id,
+            } => {
+                out.push(TAG_HELLO_WIDE);
+                out.extend_from_slice(&node_prefix.to_le_bytes());
+                out.extend_from_slice(&node_id.to_le_bytes());
+            }
             Frame::Tell { from, to, payload } => {
                 out.push(TAG_TELL);
                 out.extend_from_slice(&from.to_le_bytes());
@@ -852,6 +1020,54 @@ impl Frame {
                 out.push(TAG_PRIVATE_INGRESS_STORED);
                 out.push(u8::from(*accepted));
             }
+            Frame::Relay {
+                relay_id,
+                expires_at_ms,
+                hops_left,
+                path,
+                target_prefix,
+                origin_key,
+                signature,
+                inner,
+            } => {
+                out.push(TAG_RELAY);
+                out.extend_from_slice(&relay_id.to_le_bytes());
+                out.extend_from_slice(&expires_at_ms.to_le_bytes());
+                out.push(*hops_left);
+                out.extend_from_slice(&(path.len() as u32).to_le_bytes());
+                for prefix in path {
+                    out.extend_from_slice(&prefix.to_le_bytes());
+                }
+                out.extend_from_slice(&target_prefix.to_le_bytes());
+                out.extend_from_slice(&(origin_key.len() as u32).to_le_bytes());
+                out.extend_from_slice(origin_key);
+                out.extend_from_slice(&(signature.len() as u32).to_le_bytes());
+                out.extend_from_slice(signature);
+                out.extend_from_slice(&(inner.len() as u32).to_le_bytes());
+                out.extend_from_slice(inner);
+            }
+            Frame::RelayReply {
+                relay_id,
+                responder_key,
+                signature,
+                inner,
+            } => {
+                out.push(TAG_RELAY_REPLY);
+                out.extend_from_slice(&relay_id.to_le_bytes());
+                out.extend_from_slice(&(responder_key.len() as u32).to_le_bytes());
+                out.extend_from_slice(responder_key);
+                out.extend_from_slice(&(signature.len() as u32).to_le_bytes());
+                out.extend_from_slice(signature);
+                out.extend_from_slice(&(inner.len() as u32).to_le_bytes());
+                out.extend_from_slice(inner);
+            }
+            Frame::Neighbors { prefixes } => {
+                out.push(TAG_NEIGHBORS);
+                out.extend_from_slice(&(prefixes.len() as u32).to_le_bytes());
+                for prefix in prefixes {
+                    out.extend_from_slice(&prefix.to_le_bytes());
+                }
+            }
         }
         out
     }
@@ -863,6 +1079,11 @@ impl Frame {
             TAG_HELLO => Frame::Hello {
                 node_prefix: r.u16()?,
             },
+            TAG_PREFIX_COLLISION => Frame::PrefixCollision { node_id: r.u32()? },
+            TAG_HELLO_WIDE => Frame::HelloWide {
+                node_prefix: r.u16()?,
+                node_id: r.u32()?,
+            },
             TAG_TELL => Frame::Tell {
                 from: r.u32()?,
                 to: r.u32()?,
@@ -1237,6 +1458,71 @@ impl Frame {
                     other => return Err(FrameError::BadOption(other)),
                 },
             },
+            TAG_RELAY => {
+                let relay_id = r.u64()?;
+                let expires_at_ms = r.u64()?;
+                let hops_left = r.u8()?;
+                if hops_left as usize > MAX_RELAY_HOPS {
+                    return Err(FrameError::RelayPathTooLong(hops_left as usize));
+                }
+                let path_len = r.u32()? as usize;
+                if path_len > MAX_RELAY_HOPS {
+                    return Err(FrameError::RelayPathTooLong(path_len));
+                }
+                let mut path = Vec::with_capacity(path_len);
+                for _ in 0..path_len {
+                    path.push(r.u16()?);
+                }
+                let target_prefix = r.u16()?;
+                let origin_key = r.bytes_with_len_prefix()?;
+                if origin_key.len() > MAX_RELAY_KEY_BYTES {
+                    return Err(FrameError::RelayKeyTooLarge(origin_key.len()));
+                }
+                let signature = r.bytes_with_len_prefix()?;
+                if signature.len() > MAX_RELAY_KEY_BYTES {
+                    return Err(FrameError::RelayKeyTooLarge(signature.len()));
+                }
+                let inner = r.bytes_with_len_prefix()?;
+                Frame::Relay {
+                    relay_id,
+                    expires_at_ms,
+                    hops_left,
+                    path,
+                    target_prefix,
+                    origin_key,
+                    signature,
+                    inner,
+                }
+            }
+            TAG_RELAY_REPLY => {
+                let relay_id = r.u64()?;
+                let responder_key = r.bytes_with_len_prefix()?;
+                if responder_key.len() > MAX_RELAY_KEY_BYTES {
+                    return Err(FrameError::RelayKeyTooLarge(responder_key.len()));
+                }
+                let signature = r.bytes_with_len_prefix()?;
+                if signature.len() > MAX_RELAY_KEY_BYTES {
+                    return Err(FrameError::RelayKeyTooLarge(signature.len()));
+                }
+                let inner = r.bytes_with_len_prefix()?;
+                Frame::RelayReply {
+                    relay_id,
+                    responder_key,
+                    signature,
+                    inner,
+                }
+            }
+            TAG_NEIGHBORS => {
+                let n = r.u32()? as usize;
+                if n > MAX_NEIGHBORS {
+                    return Err(FrameError::NeighborsTooMany(n));
+                }
+                let mut prefixes = Vec::with_capacity(n);
+                for _ in 0..n {
+                    prefixes.push(r.u16()?);
+                }
+                Frame::Neighbors { prefixes }
+            }
             other => return Err(FrameError::UnknownTag(other)),
         };
         if !r.is_empty() {
@@ -1261,6 +1547,9 @@ pub enum FrameError {
     BadRaftEntryKind(u8),
     ManifestTooManyBlobs(usize),
     ManifestBadName,
+    RelayPathTooLong(usize),
+    RelayKeyTooLarge(usize),
+    NeighborsTooMany(usize),
 }
 
 impl core::fmt::Display for FrameError {
@@ -1306,6 +1595,18 @@ impl core::fmt::Display for FrameError {
             FrameError::ManifestBadName => {
                 write!(f, "manifest blob name was not valid UTF-8")
             }
+            FrameError::RelayPathTooLong(n) => {
+                write!(f, "relay hop count {n} exceeds cap {MAX_RELAY_HOPS}")
+            }
+            FrameError::RelayKeyTooLarge(n) => {
+                write!(
+                    f,
+                    "relay key or signature length {n} exceeds cap {MAX_RELAY_KEY_BYTES}"
+                )
+            }
+            FrameError::NeighborsTooMany(n) => {
+                write!(f, "neighbor count {n} exceeds cap {MAX_NEIGHBORS}")
+            }
         }
     }
 }
@@ -1386,6 +1687,13 @@ mod tests {
         roundtrip(Frame::Hello {
             node_prefix: u16::MAX,
         });
+        roundtrip(Frame::PrefixCollision {
+            node_id: 0x42AB_0001,
+        });
+        roundtrip(Frame::HelloWide {
+            node_prefix: 0x42AB,
+            node_id: u32::MAX,
+        });
     }
 
     #[test]
@@ -1538,6 +1846,79 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn relay_and_neighbors_roundtrip() {
+        let inner = Frame::Tell {
+            from: 0x1234_0001,
+            to: 0x5678_0002,
+            payload: b"hi".to_vec(),
+        }
+        .encode();
+        roundtrip(Frame::Relay {
+            relay_id: 7,
+            expires_at_ms: 1_700_000_000_000,
+            hops_left: 6,
+            path: vec![0x1234, 0x9abc],
+            target_prefix: 0x5678,
+            origin_key: vec![0x08; 36],
+            signature: vec![0x5A; 64],
+            inner,
+        });
+        roundtrip(Frame::RelayReply {
+            relay_id: 7,
+            responder_key: vec![0x08; 36],
+            signature: vec![0x5A; 64],
+            inner: Frame::InvokeReply {
+                payload: b"ok".to_vec(),
+            }
+            .encode(),
+        });
+        roundtrip(Frame::Neighbors { prefixes: vec![] });
+        roundtrip(Frame::Neighbors {
+            prefixes: vec![0x0001, 0xFFFF],
+        });
+    }
+
+    #[test]
+    fn relay_hops_and_neighbors_capped() {
+        let long_path = Frame::Relay {
+            relay_id: 1,
+            expires_at_ms: 0,
+            hops_left: 0,
+            path: vec![1; MAX_RELAY_HOPS + 1],
+            target_prefix: 2,
+            origin_key: Vec::new(),
+            signature: Vec::new(),
+            inner: Vec::new(),
+        }
+        .encode();
+        assert!(matches!(
+            Frame::decode(&long_path),
+            Err(FrameError::RelayPathTooLong(_))
+        ));
+        let big_budget = Frame::Relay {
+            relay_id: 1,
+            expires_at_ms: 0,
+            hops_left: u8::MAX,
+            path: Vec::new(),
+            target_prefix: 2,
+            origin_key: Vec::new(),
+            signature: Vec::new(),
+            inner: Vec::new(),
+        }
+        .encode();
+        assert!(matches!(
+            Frame::decode(&big_budget),
+            Err(FrameError::RelayPathTooLong(255))
+        ));
+        let mut bad = vec![TAG_NEIGHBORS];
+        bad.extend_from_slice(&((MAX_NEIGHBORS + 1) as u32).to_le_bytes());
+        assert!(matches!(
+            Frame::decode(&bad),
+            Err(FrameError::NeighborsTooMany(_))
+        ));
+    }
+
     #[test]
     fn proof_blob_reply_bad_option_rejected() {
         let mut bad = Vec::new();
@@ -277,6 +277,23 @@ where
 #[cfg(feature = "network")]
 const V2_RAFT_VOTER_AUTH_TIMEOUT_MS: u64 = 5_000;
 
+/// How long an operator `__remove_voter` waits, after the leader accepts the
+/// joint entry, for every remaining voter to activate the final configuration.
+#[cfg(all(feature = "network", feature = "storage"))]
+const RAFT_REMOVE_CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long an operator `__upgrade_v2` waits, after its entry commits, for
+/// every steady voter to report having applied through that commit index.
+#[cfg(all(feature = "network", feature = "storage"))]
+const V2_UPGRADE_APPLY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Answers an operator `__snapshot`: copies the daemon's persisted state into
+/// the named staging directory (holding [`crate::write_barrier::quiesce`]
+/// while it copies) and returns a JSON report. Installed by the host, which
+/// owns the on-disk layout, via [`VosNode::set_snapshot_handler`].
+#[cfg(feature = "network")]
+pub(crate) type SnapshotHandler = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
 /// Remote transport discovery is deliberately much shorter than an ingress
 /// operation. It runs off-router, is coalesced per root, and is retried from
 /// durable publication state, so a slow/dead bootstrap never needs to hold a
@@ -511,6 +528,10 @@ pub struct AgentConfig {
     /// All cluster members must list the same set in the same
     /// order.
     pub members: Vec<u16>,
+    /// Subset of `members` that run as log-less Raft witnesses.
+    /// They vote but never lead or hold payloads; all replicas must
+    /// list the same set.
+    pub witnesses: Vec<u16>,
     /// Periodic `tick()` interval in milliseconds. When `Some(>0)`, the
     /// agent thread dispatches a synthetic `tick` message to the actor's
     /// `tick` handler about every interval, *between* inbound work — the
@@ -570,6 +591,7 @@ impl AgentConfig {
             #[cfg(feature = "storage")]
             pre_opened_lock: None,
             members: Vec::new(),
+            witnesses: Vec::new(),
             tick_ms: None,
             intra_caps: Vec::new(),
             #[cfg(all(feature = "storage", feature = "network"))]
@@ -588,6 +610,13 @@ impl AgentConfig {
         self
     }
 
+    /// Mark members of a `Consistency::Raft` group as log-less
+    /// witnesses. Same list on every replica.
+    pub fn with_witnesses(mut self, witnesses: Vec<u16>) -> Self {
+        self.witnesses = witnesses;
+        self
+    }
+
     /// Dispatch a synthetic `tick` to this agent's `tick` handler about
     /// every `ms` milliseconds (0 disables). Only meaningful for agents
     /// that define a `tick` handler.
@@ -800,6 +829,11 @@ pub struct ExtensionConfig {
     /// role-gated handlers refuse it. See [`IntraCap`] for the
     /// intersection model and wildcard semantics.
     pub intra_caps: Vec<crate::actors::IntraCap>,
+    /// Operator grant for a transport extension's `ctx.ask_dispatch_as`:
+    /// relaying calls as the SSH members who sign in to it. `false` (the
+    /// default) refuses every such call, whatever the extension declares —
+    /// set from the manifest's `dispatch_as = true`.
+    pub dispatch_as: bool,
     /// PEM-encoded server certificate chain for host-terminated
     /// TLS on this extension's byte-stream listeners. When both this and
     /// [`Self::tls_key_pem`] are set, the host builds a TLS acceptor and a
@@ -852,6 +886,7 @@ impl ExtensionConfig {
             cap_policy: crate::extension::CapPolicy::default(),
             relay_unauthenticated: false,
             intra_caps: Vec::new(),
+            dispatch_as: false,
             tls_cert_pem: None,
             tls_key_pem: None,
             serves_addr: None,
@@ -874,6 +909,7 @@ impl ExtensionConfig {
             cap_policy: crate::extension::CapPolicy::default(),
             relay_unauthenticated: false,
             intra_caps: Vec::new(),
+            dispatch_as: false,
             tls_cert_pem: None,
             tls_key_pem: None,
             serves_addr: None,
@@ -960,6 +996,14 @@ impl ExtensionConfig {
         self
     }
 
+    /// Grant a transport extension `ctx.ask_dispatch_as`: it may relay calls
+    /// as a member who proved their SSH key to it, bounded by
+    /// [`Self::intra_caps`]. Without this grant the host refuses them.
+    pub fn allow_dispatch_as(mut self) -> Self {
+        self.dispatch_as = true;
+        self
+    }
+
     /// Enable state persistence under the given data directory.
     /// The extension's state is stored in `{data_dir}/extensions/{name}.redb`
     /// where `name` is derived from the `.so` filename.
@@ -1139,7 +1183,7 @@ pub struct VosNode {
     next_local: AtomicU16,
     /// Map from ServiceId → agent channel. Multiple services can map
     /// to the same agent (an agent with child actors).
-    routes: HashMap<u32, mpsc::Sender<Envelope>>,
+    routes: HashMap<u32, InboxTx>,
     agents: Vec<AgentHandle>,
     /// Outbound channel — agent threads send cross-service transfers here.
     outbox_tx: mpsc::Sender<Envelope>,
@@ -1206,6 +1250,13 @@ pub struct VosNode {
     ///
     /// [`run_until_idle`]: VosNode::run_until_idle
     last_activity: ActivityClock,
+    /// Per-agent dispatch / gas / inbox-depth counters and CRDT sync
+    /// counters, bumped by the agent threads and sync tickers and read
+    /// by [`MetricsHandle::render`].
+    metrics: Arc<crate::metrics::NodeMetrics>,
+    /// Per-agent ring buffers of guest output, panics and dispatch
+    /// outcomes, filled by the agent threads and read by `__logs`.
+    guest_logs: Arc<crate::guest_log::GuestLogs>,
     /// Optional libp2p network handle. Shared with all agent
     /// threads so cross-node `external_invoke` works regardless of
     /// whether the network was attached before or after agent
@@ -1237,6 +1288,10 @@ pub struct VosNode {
     /// catalog ops unsigned, so the registry refuses them — fail closed.
     /// Set by [`set_operator_signer`](Self::set_operator_signer).
     operator_signer: Option<crate::registry::CatalogOpSigner>,
+    /// Backs the operator-only `__snapshot` op (online backup). `None`
+    /// refuses it. Set by [`set_snapshot_handler`](Self::set_snapshot_handler).
+    #[cfg(feature = "network")]
+    snapshot_handler: Option<SnapshotHandler>,
     /// Map: replication group → local replica handle.
     /// Populated by `register` whenever a CRDT actor with a
     /// `replication_id` is added. Read by [`NodeService`] (db
@@ -1252,6 +1307,11 @@ pub struct VosNode {
     /// current leader.
     #[cfg(all(feature = "network", feature = "storage"))]
     raft_hosts: RaftHosts,
+    /// Log-less witness workers this node hosts, keyed by replication
+    /// group. A witness votes in its group but runs no agent, so it has
+    /// no entry in `agent_info` (see [`Self::register_raft_witness`]).
+    #[cfg(all(feature = "network", feature = "storage"))]
+    raft_witnesses: Arc<Mutex<HashMap<[u8; 32], crate::raft::RaftWorker>>>,
     /// Exact Raft group → root-owned private-ingress sidecar sink.
     #[cfg(all(feature = "network", feature = "storage"))]
     v2_private_ingress_routes: V2PrivateIngressRoutes,
@@ -1320,6 +1380,10 @@ pub struct VosNode {
     /// it at its `blob_store` cache) so `vos` stays cache-agnostic — it only
     /// reads files by hash, never owns the cache. `None` serves nothing.
     pub(crate) program_blobs_dir: Option<std::path::PathBuf>,
+    /// Durable coordinator journal for multi-root batches driven by
+    /// [`drive_root_batch`](Self::drive_root_batch). `None` refuses to drive
+    /// any batch: without a journal a crash could strand reservations.
+    root_batch_journal: Option<Mutex<crate::v2::FileRootBatchJournalV2>>,
 }
 
 /// Shared content-addressed proof-blob store. Cheap to clone; both
@@ -1367,6 +1431,44 @@ pub(crate) struct ReplicaSlot {
     pub node_validator: Option<crate::commit::NodeValidator>,
 }
 
+/// Sending half of one agent's inbox. Actor agents and v2 roots carry
+/// their [`crate::metrics::AgentMetrics`], whose queue-depth gauge is
+/// raised before every send (and lowered again if the send fails), so the
+/// receiving thread's decrement can never run ahead of it.
+#[derive(Clone)]
+struct InboxTx {
+    tx: mpsc::Sender<Envelope>,
+    metrics: Option<Arc<crate::metrics::AgentMetrics>>,
+}
+
+impl InboxTx {
+    fn metered(tx: mpsc::Sender<Envelope>, metrics: Arc<crate::metrics::AgentMetrics>) -> Self {
+        Self {
+            tx,
+            metrics: Some(metrics),
+        }
+    }
+
+    fn send(&self, envelope: Envelope) -> Result<(), mpsc::SendError<Envelope>> {
+        if let Some(metrics) = &self.metrics {
+            metrics.enqueued();
+        }
+        let sent = self.tx.send(envelope);
+        if sent.is_err()
+            && let Some(metrics) = &self.metrics
+        {
+            metrics.dequeued();
+        }
+        sent
+    }
+}
+
+impl From<mpsc::Sender<Envelope>> for InboxTx {
+    fn from(tx: mpsc::Sender<Envelope>) -> Self {
+        Self { tx, metrics: None }
+    }
+}
+
 /// Shared invoke-route table. Cheap to clone and pass to threads.
 type InvokeRoutes = Arc<Mutex<HashMap<u32, mpsc::Sender<InvokeRequest>>>>;
 
@@ -1960,12 +2062,81 @@ fn describe_agent_json(id: u32, info: &AgentInfo, running: bool) -> String {
     )
 }
 
-/// Minimal JSON string escaper for the handful of fields `__describe`
-/// emits (agent names + listen addresses). Escapes the two characters
-/// that would break a JSON string literal; other control characters are
-/// not expected in manifest-derived identifiers.
+/// Minimal JSON string escaper for the fields `__describe` and `__logs`
+/// emit. Besides the quote and backslash, control characters are escaped:
+/// guest log lines are arbitrary text.
 fn json_escape(s: &str) -> String {
-    s.replace('\\', "\\\\").replace('"', "\\\"")
+    use std::fmt::Write;
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Most lines one `__logs` reply carries; a follower pages with `after`.
+#[cfg(feature = "network")]
+const MAX_LOG_LINES_PER_REPLY: usize = 500;
+
+/// One voter's view of a Raft group: the local worker when `prefix` is this
+/// node, otherwise a `RaftStatusReq` round-trip bounded to one second.
+#[cfg(all(feature = "network", feature = "storage"))]
+fn raft_status_of(
+    network: &crate::network::Network,
+    replication_id: &[u8; 32],
+    prefix: u16,
+) -> Option<crate::network::RaftStatusReply> {
+    let status = if prefix == network.local_prefix() {
+        network.local_raft_status(replication_id)
+    } else {
+        network.peer_for_prefix(prefix).and_then(|peer| {
+            network
+                .send_raft_status_req(peer, *replication_id)
+                .recv_timeout(Duration::from_secs(1))
+                .ok()
+        })
+    };
+    status.filter(|status| status.present)
+}
+
+#[cfg(all(feature = "network", feature = "storage"))]
+fn not_leader_reason(leader_hint: Option<u16>) -> String {
+    match leader_hint {
+        Some(leader) => format!(
+            "this daemon is not the group's leader; run against the daemon of node {leader:#06x}"
+        ),
+        None => "this daemon is not the group's leader and knows of none; retry".into(),
+    }
+}
+
+#[cfg(all(feature = "network", feature = "storage"))]
+fn join_prefixes(prefixes: &[u16]) -> String {
+    prefixes
+        .iter()
+        .map(u16::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Lowercase hex for the 32-byte v2 identifiers in host-built JSON replies.
+fn lower_hex(bytes: &[u8]) -> String {
+    use core::fmt::Write;
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
 }
 
 /// Mask a (possibly prefix-scoped) `ServiceId` value down to its
@@ -1974,6 +2145,364 @@ fn local_id_of(svc_id: u32) -> u16 {
     (svc_id & 0xFFFF) as u16
 }
 
+/// Thread-safe view of a node's metrics: the counters in
+/// [`crate::metrics::NodeMetrics`] plus the node state sampled when a scrape
+/// renders. Returned by [`VosNode::metrics_handle`] so a host's exporter
+/// thread can scrape while [`VosNode::run_forever`] holds the node; the
+/// member-gated `__metrics` op answers with the same text.
+#[derive(Clone)]
+pub struct MetricsHandle {
+    node_prefix: u16,
+    metrics: Arc<crate::metrics::NodeMetrics>,
+    agent_info: AgentInfos,
+    #[cfg(feature = "network")]
+    shared_network: SharedNetwork,
+    #[cfg(all(feature = "network", feature = "storage"))]
+    crdt_replicas: Arc<Mutex<HashMap<[u8; 32], ReplicaSlot>>>,
+    #[cfg(all(feature = "network", feature = "storage"))]
+    raft_hosts: RaftHosts,
+    blob_dirs: Vec<(&'static str, std::path::PathBuf)>,
+}
+
+impl MetricsHandle {
+    /// Render every node metric in the Prometheus text format. Raft
+    /// status is read from each local worker (one inbox round-trip per
+    /// group) and CRDT heads from each replica's store, so a scrape costs
+    /// a few milliseconds per hosted group.
+    pub fn render(&self) -> String {
+        use crate::metrics::{Exposition, MetricKind};
+
+        let mut out = Exposition::new();
+        let prefix = format!("{:#06x}", self.node_prefix);
+        out.family(
+            "vos_node_info",
+            MetricKind::Gauge,
+            "Always 1; the prefix label identifies this node.",
+        );
+        out.sample("vos_node_info", &[("prefix", prefix.as_str())], 1);
+
+        let agents: Vec<(String, String, crate::metrics::AgentCounters)> = self
+            .metrics
+            .agents()
+            .into_iter()
+            .map(|(id, agent)| (self.agent_name(id), format!("{id:#010x}"), agent.counters()))
+            .collect();
+        out.family(
+            "vos_agent_dispatches_total",
+            MetricKind::Counter,
+            "Dispatches run by each agent since boot, by how they arrived.",
+        );
+        for (name, id, counters) in &agents {
+            for (kind, value) in [("tell", counters.tells), ("invoke", counters.invokes)] {
+                out.sample(
+                    "vos_agent_dispatches_total",
+                    &[
+                        ("agent", name.as_str()),
+                        ("id", id.as_str()),
+                        ("kind", kind),
+                    ],
+                    value,
+                );
+            }
+        }
+        out.family(
+            "vos_agent_gas_total",
+            MetricKind::Counter,
+            "Gas burned by each agent's refine slices since boot, replays included.",
+        );
+        for (name, id, counters) in &agents {
+            out.sample(
+                "vos_agent_gas_total",
+                &[("agent", name.as_str()), ("id", id.as_str())],
+                counters.gas,
+            );
+        }
+        out.family(
+            "vos_agent_queue_depth",
+            MetricKind::Gauge,
+            "Envelopes waiting in each agent's inbox.",
+        );
+        for (name, id, counters) in &agents {
+            out.sample(
+                "vos_agent_queue_depth",
+                &[("agent", name.as_str()), ("id", id.as_str())],
+                counters.queued,
+            );
+        }
+        out.family(
+            "vos_root_backlog",
+            MetricKind::Gauge,
+            "Durable work pending in each v2 root, by queue.",
+        );
+        for (name, id, counters) in agents.iter().filter(|(_, _, c)| c.root) {
+            for (queue, value) in [
+                ("inbox", counters.root_inbox),
+                ("ingress", counters.root_ingress),
+            ] {
+                out.sample(
+                    "vos_root_backlog",
+                    &[
+                        ("agent", name.as_str()),
+                        ("id", id.as_str()),
+                        ("queue", queue),
+                    ],
+                    value,
+                );
+            }
+        }
+
+        #[cfg(feature = "network")]
+        self.render_network(&mut out);
+        #[cfg(all(feature = "network", feature = "storage"))]
+        self.render_replication(&mut out);
+
+        out.family(
+            "vos_blob_store_bytes",
+            MetricKind::Gauge,
+            "Bytes held in each on-disk blob store.",
+        );
+        let usage: Vec<_> = self
+            .blob_dirs
+            .iter()
+            .map(|(store, dir)| (*store, dir_usage(dir)))
+            .collect();
+        for (store, (_, bytes)) in &usage {
+            out.sample("vos_blob_store_bytes", &[("store", *store)], bytes);
+        }
+        out.family(
+            "vos_blob_store_blobs",
+            MetricKind::Gauge,
+            "Blobs held in each on-disk blob store.",
+        );
+        for (store, (blobs, _)) in &usage {
+            out.sample("vos_blob_store_blobs", &[("store", *store)], blobs);
+        }
+        out.finish()
+    }
+
+    fn agent_name(&self, id: u32) -> String {
+        self.agent_info
+            .read()
+            .ok()
+            .and_then(|infos| infos.get(&id).and_then(|info| info.name.clone()))
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "network")]
+    fn render_network(&self, out: &mut crate::metrics::Exposition) {
+        use crate::metrics::MetricKind;
+
+        let Some(network) = self.shared_network.lock().ok().and_then(|g| g.clone()) else {
+            return;
+        };
+        out.family(
+            "vos_network_peers",
+            MetricKind::Gauge,
+            "Peers that completed the Hello handshake.",
+        );
+        out.sample("vos_network_peers", &[], network.connected_peers().len());
+        out.family(
+            "vos_network_prefix_collisions",
+            MetricKind::Gauge,
+            "Node prefixes claimed by more than one peer.",
+        );
+        out.sample(
+            "vos_network_prefix_collisions",
+            &[],
+            network.prefix_collisions().len(),
+        );
+        out.family(
+            "vos_network_node_id_collisions",
+            MetricKind::Gauge,
+            "32-bit node ids claimed by more than one peer.",
+        );
+        out.sample(
+            "vos_network_node_id_collisions",
+            &[],
+            network.node_id_collisions().len(),
+        );
+        let traffic = network.frame_traffic();
+        out.family(
+            "vos_network_frames_total",
+            MetricKind::Counter,
+            "Frames sent and received since boot, by frame kind.",
+        );
+        for (kind, direction, frames, _) in &traffic {
+            out.sample(
+                "vos_network_frames_total",
+                &[("kind", *kind), ("direction", direction.as_str())],
+                frames,
+            );
+        }
+        out.family(
+            "vos_network_frame_bytes_total",
+            MetricKind::Counter,
+            "Bytes sent and received since boot, by frame kind.",
+        );
+        for (kind, direction, _, bytes) in &traffic {
+            out.sample(
+                "vos_network_frame_bytes_total",
+                &[("kind", *kind), ("direction", direction.as_str())],
+                bytes,
+            );
+        }
+    }
+
+    #[cfg(all(feature = "network", feature = "storage"))]
+    fn render_replication(&self, out: &mut crate::metrics::Exposition) {
+        use crate::metrics::MetricKind;
+
+        let network = self.shared_network.lock().ok().and_then(|g| g.clone());
+        let hosts: HashMap<[u8; 32], u32> = self
+            .raft_hosts
+            .lock()
+            .map(|hosts| hosts.iter().map(|(id, group)| (*group, *id)).collect())
+            .unwrap_or_default();
+        let groups: Vec<(String, String, crate::network::RaftStatusReply)> = network
+            .as_ref()
+            .map(|network| {
+                network
+                    .registered_raft_groups()
+                    .into_iter()
+                    .filter_map(|group| {
+                        let status = network.local_raft_status(&group)?;
+                        let name = hosts
+                            .get(&group)
+                            .map(|id| self.agent_name(*id))
+                            .unwrap_or_default();
+                        Some((lower_hex(&group), name, status))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let raft_gauges: [(&str, &str, fn(&crate::network::RaftStatusReply) -> u64); 6] = [
+            (
+                "vos_raft_term",
+                "Current Raft term of each local replica.",
+                |s| s.current_term,
+            ),
+            (
+                "vos_raft_commit_index",
+                "Highest log index known committed by each local replica.",
+                |s| s.commit_index,
+            ),
+            (
+                "vos_raft_last_applied",
+                "Highest log index applied to each local replica's state.",
+                |s| s.last_applied,
+            ),
+            (
+                "vos_raft_apply_lag",
+                "Committed entries each local replica has not applied yet.",
+                |s| s.commit_index.saturating_sub(s.last_applied),
+            ),
+            (
+                "vos_raft_is_leader",
+                "1 when the local replica leads its group.",
+                |s| u64::from(s.role == crate::network::RaftRole::Leader),
+            ),
+            (
+                "vos_raft_members",
+                "Voters in each group's active configuration.",
+                |s| s.members.len() as u64,
+            ),
+        ];
+        for (metric, help, value) in raft_gauges {
+            out.family(metric, MetricKind::Gauge, help);
+            for (group, name, status) in &groups {
+                out.sample(
+                    metric,
+                    &[("group", group.as_str()), ("agent", name.as_str())],
+                    value(status),
+                );
+            }
+        }
+
+        let slots: Vec<([u8; 32], ReplicaSlot)> = self
+            .crdt_replicas
+            .lock()
+            .map(|slots| slots.iter().map(|(id, slot)| (*id, slot.clone())).collect())
+            .unwrap_or_default();
+        out.family(
+            "vos_crdt_heads",
+            MetricKind::Gauge,
+            "Merkle-clock heads of each local CRDT replica.",
+        );
+        for (group, slot) in &slots {
+            let heads = crate::commit::read_roots(&slot.db).map_or(0, |roots| roots.len());
+            out.sample(
+                "vos_crdt_heads",
+                &[
+                    ("group", lower_hex(group).as_str()),
+                    ("agent", slot.name.as_str()),
+                ],
+                heads,
+            );
+        }
+        let names: HashMap<[u8; 32], String> = slots
+            .into_iter()
+            .map(|(group, slot)| (group, slot.name))
+            .collect();
+        let replicas: Vec<(String, String, (u64, u64))> = self
+            .metrics
+            .replicas()
+            .into_iter()
+            .map(|(group, replica)| {
+                (
+                    lower_hex(&group),
+                    names.get(&group).cloned().unwrap_or_default(),
+                    replica.counters(),
+                )
+            })
+            .collect();
+        out.family(
+            "vos_crdt_merged_nodes_total",
+            MetricKind::Counter,
+            "DAG nodes pulled from peers by each replica's sync ticker since boot.",
+        );
+        for (group, name, (merged, _)) in &replicas {
+            out.sample(
+                "vos_crdt_merged_nodes_total",
+                &[("group", group.as_str()), ("agent", name.as_str())],
+                merged,
+            );
+        }
+        out.family(
+            "vos_crdt_sync_backlog",
+            MetricKind::Gauge,
+            "Merged DAG nodes the local agent has not folded into its state yet.",
+        );
+        for (group, name, (_, unfolded)) in &replicas {
+            out.sample(
+                "vos_crdt_sync_backlog",
+                &[("group", group.as_str()), ("agent", name.as_str())],
+                unfolded,
+            );
+        }
+    }
+}
+
+/// `(files, bytes)` under `dir`, recursively. Unreadable entries count as
+/// empty; a missing directory is an empty store.
+fn dir_usage(dir: &std::path::Path) -> (u64, u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (0, 0);
+    };
+    entries
+        .flatten()
+        .fold((0, 0), |(files, bytes), entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => {
+                let (more_files, more_bytes) = dir_usage(&entry.path());
+                (files + more_files, bytes + more_bytes)
+            }
+            Ok(kind) if kind.is_file() => (
+                files + 1,
+                bytes + entry.metadata().map_or(0, |meta| meta.len()),
+            ),
+            _ => (files, bytes),
+        })
+}
+
 /// Thread-safe handle for invoking local services. Returned by
 /// [`VosNode::invoke_handle`] so background tasks can keep
 /// calling into the node while [`VosNode::run_forever`] holds
@@ -2097,6 +2626,13 @@ struct NodeService {
     /// it enters the registry CRDT. Non-root operators produce a signature the
     /// registry rejects under its immutable-root check.
     operator_signer: Option<crate::registry::CatalogOpSigner>,
+    /// Host copier for `__snapshot` — a clone of
+    /// [`VosNode::snapshot_handler`]. `None` refuses online snapshots.
+    snapshot_handler: Option<SnapshotHandler>,
+    /// Renders the `__metrics` reply. `None` answers it with Unit.
+    metrics: Option<MetricsHandle>,
+    /// The node's guest log buffers, read by `__logs`.
+    guest_logs: Arc<crate::guest_log::GuestLogs>,
     /// Per-instance-name `SyncFloor` cache for the sync-serve gate. The
     /// floor is a static install-time property, but resolving it hits the
     /// registry with a blocking probe (up to ~5 s); caching keeps
@@ -2568,10 +3104,11 @@ impl NodeService {
             .cloned()
     }
 
-    /// Host-side handler for the reserved `__stop` / `__describe` wire
-    /// Methods. Returns `Some(reply_bytes)` when the
+    /// Host-side handler for the reserved `__stop` / `__describe` /
+    /// `__metrics` / `__logs` / `__doctor` wire Methods. Returns `Some(reply_bytes)` when the
     /// invoke's `Msg.name` is one of them (already answered), `None` to let
-    /// `dispatch_invoke` forward the invoke normally. The reply matches the
+    /// `dispatch_invoke` forward the invoke normally. `__upgrade_v2` is only
+    /// gated here; an authorized one is forwarded to the v2 root thread. The reply matches the
     /// raw-`Value` shape `dispatch_invoke` otherwise returns: empty (→
     /// `Value::Unit` → client renders `null`) for `__stop`, an rkyv
     /// `Value::Str(json)` for `__describe`. Tries the scoped id first, then the
@@ -2582,7 +3119,10 @@ impl NodeService {
     /// target actor's own `#[msg(role=…)]` gate, so they carry their own
     /// space-role check against the caller's grant (`lookup_caller_role`):
     /// `__stop` (privileged — stops an agent) requires **ADMIN**; `__describe`
-    /// (reads name/kind/listen-addr) requires any space member (≥ read-only).
+    /// (reads name/kind/listen-addr), `__metrics` (node counters) and
+    /// `__doctor` (mesh health probes) require any space member
+    /// (≥ read-only); `__logs` (guest output) requires
+    /// **DEVELOPER**.
     /// An unauthorized caller (incl. an anonymous / non-member peer) gets a
     /// `STATUS_FORBIDDEN` envelope, NOT a silent stop/enumerate. The role
     /// lookup runs only after a reserved name matches, so normal dispatch
@@ -2628,6 +3168,110 @@ impl NodeService {
                     None => Some(Vec::new()),
                 }
             }
+            // Node-wide counters for `vosx space top`: read-only, so any
+            // space member may scrape them, like `__describe`.
+            "__metrics" => {
+                if self.lookup_caller_role(caller_peer_id) == AUTH_ROLE_NONE {
+                    warn!(
+                        target = to,
+                        "__metrics refused: caller is not a space member"
+                    );
+                    return Some(forbidden_envelope());
+                }
+                match &self.metrics {
+                    Some(metrics) => Some(crate::Encode::encode(&crate::value::Value::Str(
+                        metrics.render(),
+                    ))),
+                    None => Some(Vec::new()),
+                }
+            }
+            // Guest output can carry anything an actor chose to print, so
+            // reading it takes DEVELOPER or above.
+            "__logs" => {
+                if self.lookup_caller_role(caller_peer_id) < AUTH_ROLE_DEVELOPER {
+                    warn!(target = to, "__logs refused: caller lacks DEVELOPER role");
+                    return Some(forbidden_envelope());
+                }
+                let args = intercepted_msg(msg).map(|decoded| decoded.args);
+                let report = self.guest_logs_json(args.as_ref());
+                Some(crate::Encode::encode(&crate::value::Value::Str(report)))
+            }
+            // Mesh health for `vosx space doctor`: read-only, so any space
+            // member may ask, like `__metrics`.
+            "__doctor" => {
+                if self.lookup_caller_role(caller_peer_id) == AUTH_ROLE_NONE {
+                    warn!(
+                        target = to,
+                        "__doctor refused: caller is not a space member"
+                    );
+                    return Some(forbidden_envelope());
+                }
+                let report = self.doctor_json();
+                Some(crate::Encode::encode(&crate::value::Value::Str(report)))
+            }
+            // Guest-owned v2 upgrades replace the code behind every voter's
+            // replica, so only this daemon's own operator holding ADMIN may
+            // drive one. The v2 root thread owns the service and answers.
+            "__upgrade_v2" => {
+                if !self.caller_is_operator(caller_peer_id)
+                    || self.lookup_caller_role(caller_peer_id) < AUTH_ROLE_ADMIN
+                {
+                    warn!(
+                        target = to,
+                        "__upgrade_v2 refused: caller is not this daemon's ADMIN operator"
+                    );
+                    return Some(forbidden_envelope());
+                }
+                None
+            }
+            // Dropping a voter rewrites the group's quorum, so it takes the
+            // same operator + ADMIN gate as an upgrade. Answered host-side on
+            // the leader; the reply is a JSON report for `space voters`.
+            "__remove_voter" => {
+                if !self.caller_is_operator(caller_peer_id)
+                    || self.lookup_caller_role(caller_peer_id) < AUTH_ROLE_ADMIN
+                {
+                    warn!(
+                        target = to,
+                        "__remove_voter refused: caller is not this daemon's ADMIN operator"
+                    );
+                    return Some(forbidden_envelope());
+                }
+                let prefix = intercepted_msg(msg)
+                    .and_then(|decoded| decoded.args.get_u32("prefix"))
+                    .and_then(|prefix| u16::try_from(prefix).ok());
+                let report = match prefix {
+                    #[cfg(feature = "storage")]
+                    Some(prefix) => self.remove_raft_voter(to, to_unscoped, prefix),
+                    #[cfg(not(feature = "storage"))]
+                    Some(_) => "{\"error\":\"this daemon hosts no Raft groups\"}".to_string(),
+                    None => "{\"error\":\"missing or out-of-range voter prefix\"}".to_string(),
+                };
+                Some(crate::Encode::encode(&crate::value::Value::Str(report)))
+            }
+            // An online snapshot copies every database this daemon owns and
+            // briefly stalls its writers, so it is the operator's alone.
+            "__snapshot" => {
+                if !self.caller_is_operator(caller_peer_id)
+                    || self.lookup_caller_role(caller_peer_id) < AUTH_ROLE_ADMIN
+                {
+                    warn!(
+                        target = to,
+                        "__snapshot refused: caller is not this daemon's ADMIN operator"
+                    );
+                    return Some(forbidden_envelope());
+                }
+                let output =
+                    intercepted_msg(msg).and_then(|decoded| decoded.args.get_str("output"));
+                let report = match (output, self.snapshot_handler.as_ref()) {
+                    (Some(output), Some(handler)) => handler(&output),
+                    (None, _) => "{\"error\":\"missing snapshot output directory\"}".to_string(),
+                    (_, None) => {
+                        "{\"error\":\"this daemon does not serve online snapshots\"}".to_string()
+                    }
+                };
+                Some(crate::Encode::encode(&crate::value::Value::Str(report)))
+            }
             _ => None,
         }
     }
@@ -2723,11 +3367,212 @@ impl NodeService {
         let Some(status) = status else {
             return false;
         };
-        let prefix = crate::network::derive_node_prefix(caller);
-        status.is_active_voter(prefix)
-            && self
-                .lookup_node_member(prefix)
-                .is_some_and(|member| node_member_authenticates_voter(&member, prefix, caller))
+        let prefix = crate::network::derive_node_prefix(caller);
+        status.is_active_voter(prefix)
+            && self
+                .lookup_node_member(prefix)
+                .is_some_and(|member| node_member_authenticates_voter(&member, prefix, caller))
+    }
+
+    /// The `__logs` reply: buffered guest log lines as JSON
+    /// `{"next":seq,"lines":[…]}`, filtered by the optional `agent`
+    /// instance name, `after` cursor (the previous reply's `next`),
+    /// `since_ms` wall-clock floor and `limit` (capped at
+    /// [`MAX_LOG_LINES_PER_REPLY`]).
+    fn guest_logs_json(&self, args: Option<&crate::value::Args>) -> String {
+        let names: HashMap<u32, String> = self
+            .agent_info
+            .read()
+            .map(|info| {
+                info.iter()
+                    .filter_map(|(id, info)| Some((*id, info.name.clone()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let agent = args
+            .and_then(|args| args.get_str("agent"))
+            .filter(|a| !a.is_empty());
+        let agent_id = match &agent {
+            Some(agent) => match names.iter().find(|(_, name)| *name == agent) {
+                Some((id, _)) => Some(*id),
+                None => {
+                    return format!(
+                        "{{\"error\":\"no agent '{}' on this node\"}}",
+                        json_escape(agent)
+                    );
+                }
+            },
+            None => None,
+        };
+        let after = args.and_then(|args| args.get_u64("after")).unwrap_or(0);
+        let since_ms = args.and_then(|args| args.get_u64("since_ms")).unwrap_or(0);
+        let limit = args
+            .and_then(|args| args.get_u32("limit"))
+            .map_or(MAX_LOG_LINES_PER_REPLY, |limit| {
+                (limit as usize).min(MAX_LOG_LINES_PER_REPLY)
+            });
+        let lines = self.guest_logs.read(agent_id, after, since_ms, limit);
+        let next = lines.last().map_or(after, |line| line.seq);
+        let rendered: Vec<String> = lines
+            .iter()
+            .map(|line| {
+                format!(
+                    "{{\"seq\":{},\"unix_ms\":{},\"id\":{},\"agent\":\"{}\",\"kind\":\"{}\",\"text\":\"{}\"}}",
+                    line.seq,
+                    line.unix_ms,
+                    line.agent,
+                    json_escape(names.get(&line.agent).map_or("", String::as_str)),
+                    line.kind.as_str(),
+                    json_escape(&line.text),
+                )
+            })
+            .collect();
+        format!("{{\"next\":{next},\"lines\":[{}]}}", rendered.join(","))
+    }
+
+    /// The `__doctor` reply: this node's view of the mesh as JSON — its
+    /// connected peers and prefix / node-id collisions, each hosted Raft group as
+    /// every voter reports it, and each CRDT replica's heads beside the
+    /// heads its connected peers serve. `unix_ms` is read last, so the
+    /// caller can bound clock skew by its own round trip.
+    fn doctor_json(&self) -> String {
+        let Some(network) = self.shared_network.lock().ok().and_then(|g| g.clone()) else {
+            return "{\"error\":\"this daemon has no network attached\"}".to_string();
+        };
+        let peers: Vec<String> = network
+            .peers_with_prefixes()
+            .iter()
+            .map(|(prefix, peer)| format!("{{\"prefix\":{prefix},\"peer_id\":\"{peer}\"}}"))
+            .collect();
+        let collisions: Vec<String> = network
+            .prefix_collisions()
+            .iter()
+            .map(u16::to_string)
+            .collect();
+        let node_id_collisions: Vec<String> = network
+            .node_id_collisions()
+            .iter()
+            .map(u32::to_string)
+            .collect();
+        #[cfg(feature = "storage")]
+        let (raft, crdt) = (
+            self.doctor_raft_json(&network),
+            self.doctor_crdt_json(&network),
+        );
+        #[cfg(not(feature = "storage"))]
+        let (raft, crdt) = (String::new(), String::new());
+        let unix_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        format!(
+            "{{\"prefix\":{},\"unix_ms\":{unix_ms},\"peers\":[{}],\"collisions\":[{}],\"node_id_collisions\":[{}],\"raft\":[{raft}],\"crdt\":[{crdt}]}}",
+            network.local_prefix(),
+            peers.join(","),
+            collisions.join(","),
+            node_id_collisions.join(","),
+        )
+    }
+
+    /// One JSON object per locally hosted Raft group: its active members
+    /// and every voter's status (`reachable: false` when a voter did not
+    /// answer within [`raft_status_of`]'s bound).
+    #[cfg(feature = "storage")]
+    fn doctor_raft_json(&self, network: &crate::network::Network) -> String {
+        let hosts: HashMap<[u8; 32], u32> = self
+            .raft_hosts
+            .lock()
+            .map(|hosts| hosts.iter().map(|(id, group)| (*group, *id)).collect())
+            .unwrap_or_default();
+        let groups: Vec<String> = network
+            .registered_raft_groups()
+            .into_iter()
+            .filter_map(|group| {
+                let local = network.local_raft_status(&group)?;
+                let name = hosts
+                    .get(&group)
+                    .and_then(|id| self.agent_info.read().ok()?.get(id)?.name.clone())
+                    .unwrap_or_default();
+                let mut voters = local.members.clone();
+                voters.extend(local.joint_old.iter().flatten().copied());
+                voters.sort_unstable();
+                voters.dedup();
+                let voters: Vec<String> = voters
+                    .iter()
+                    .map(|&prefix| match raft_status_of(network, &group, prefix) {
+                        Some(status) => format!(
+                            "{{\"prefix\":{prefix},\"reachable\":true,\"role\":{},\"term\":{},\"commit\":{},\"applied\":{},\"leader\":{}}}",
+                            status.role.to_wire(),
+                            status.current_term,
+                            status.commit_index,
+                            status.last_applied,
+                            status
+                                .leader_hint
+                                .map_or_else(|| "null".to_string(), |leader| leader.to_string()),
+                        ),
+                        None => format!("{{\"prefix\":{prefix},\"reachable\":false}}"),
+                    })
+                    .collect();
+                Some(format!(
+                    "{{\"group\":\"{}\",\"agent\":\"{}\",\"members\":[{}],\"voters\":[{}]}}",
+                    lower_hex(&group),
+                    json_escape(&name),
+                    join_prefixes(&local.members),
+                    voters.join(","),
+                ))
+            })
+            .collect();
+        groups.join(",")
+    }
+
+    /// One JSON object per local CRDT replica: its heads and those of each
+    /// connected peer that serves the group. Every peer is asked at once
+    /// and the answers share a one-second deadline; a peer that answers
+    /// with no heads does not host the group and is left out.
+    #[cfg(feature = "storage")]
+    fn doctor_crdt_json(&self, network: &crate::network::Network) -> String {
+        let heads_json = |heads: &[[u8; 32]]| -> String {
+            heads
+                .iter()
+                .map(|head| format!("\"{}\"", lower_hex(head)))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let slots: Vec<([u8; 32], ReplicaSlot)> = self
+            .replicas
+            .lock()
+            .map(|slots| slots.iter().map(|(id, slot)| (*id, slot.clone())).collect())
+            .unwrap_or_default();
+        let peers = network.peers_with_prefixes();
+        let replicas: Vec<String> = slots
+            .iter()
+            .map(|(group, slot)| {
+                let heads = crate::commit::read_roots(&slot.db).unwrap_or_default();
+                let pending: Vec<(u16, _)> = peers
+                    .iter()
+                    .map(|(prefix, peer)| (*prefix, network.send_fetch_heads(*peer, *group)))
+                    .collect();
+                let deadline = Instant::now() + Duration::from_secs(1);
+                let remote: Vec<String> = pending
+                    .into_iter()
+                    .filter_map(|(prefix, reply)| {
+                        let roots = reply
+                            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                            .ok()?;
+                        (!roots.is_empty()).then(|| {
+                            format!("{{\"prefix\":{prefix},\"heads\":[{}]}}", heads_json(&roots))
+                        })
+                    })
+                    .collect();
+                format!(
+                    "{{\"group\":\"{}\",\"agent\":\"{}\",\"heads\":[{}],\"peers\":[{}]}}",
+                    lower_hex(group),
+                    json_escape(&slot.name),
+                    heads_json(&heads),
+                    remote.join(","),
+                )
+            })
+            .collect();
+        replicas.join(",")
     }
 
     /// Render an agent's describe JSON by id (the `__describe` primitive's
@@ -2879,9 +3724,48 @@ fn node_member_authenticates_voter(
     member: &crate::registry::MemberRow,
     expected_prefix: u16,
     peer: &libp2p::PeerId,
+) -> bool {
+    node_member_authenticates_as(
+        member,
+        &[crate::registry::NODE_ROLE_VOTER],
+        expected_prefix,
+        peer,
+    )
+}
+
+/// [`node_member_authenticates_voter`] widened to enrolled witnesses. Only
+/// Raft membership (join admission) accepts a witness; delegation authority
+/// and leader binding stay voter-only because a witness never leads and
+/// holds no service state.
+#[cfg(all(feature = "network", feature = "storage"))]
+fn node_member_authenticates_raft_member(
+    member: &crate::registry::MemberRow,
+    expected_prefix: u16,
+    peer: &libp2p::PeerId,
+) -> bool {
+    node_member_authenticates_as(
+        member,
+        &[
+            crate::registry::NODE_ROLE_VOTER,
+            crate::registry::NODE_ROLE_WITNESS,
+        ],
+        expected_prefix,
+        peer,
+    )
+}
+
+/// The one registry-row identity check behind both voter and Raft-member
+/// authentication: an enrolled node in one of `roles` whose row prefix,
+/// derived PeerId prefix and full key all name `peer`.
+#[cfg(all(feature = "network", feature = "storage"))]
+fn node_member_authenticates_as(
+    member: &crate::registry::MemberRow,
+    roles: &[u8],
+    expected_prefix: u16,
+    peer: &libp2p::PeerId,
 ) -> bool {
     member.kind == crate::registry::MEMBER_KIND_NODE
-        && member.role == crate::registry::NODE_ROLE_VOTER
+        && roles.contains(&member.role)
         && member.prefix == expected_prefix
         && crate::network::derive_node_prefix(peer) == expected_prefix
         && member.key == peer.to_bytes()
@@ -3547,12 +4431,15 @@ impl crate::network::NetworkService for NodeService {
     }
 
     /// Admit a Raft joiner only if it is enrolled as a `NODE_ROLE_VOTER`
-    /// in the local space-registry. Fails **closed** — an unreachable or
-    /// empty registry reply (`lookup_node_role` → `0` = "not enrolled")
-    /// denies the join — so a peer an admin never enrolled cannot make
-    /// itself a voter.
+    /// or `NODE_ROLE_WITNESS` in the local space-registry. Fails
+    /// **closed** — an unreachable or empty registry reply
+    /// (`lookup_node_role` → `0` = "not enrolled") denies the join — so a
+    /// peer an admin never enrolled cannot make itself a voter.
     fn raft_join_authorized(&self, prefix: u16) -> bool {
-        self.lookup_node_role(prefix) == NODE_ROLE_REPLY_VOTER
+        matches!(
+            self.lookup_node_role(prefix),
+            NODE_ROLE_REPLY_VOTER | NODE_ROLE_REPLY_WITNESS
+        )
     }
 
     #[cfg(all(feature = "network", feature = "storage"))]
@@ -3567,7 +4454,7 @@ impl crate::network::NetworkService for NodeService {
         let Some(member) = self.lookup_node_member(joiner_prefix) else {
             return crate::network::RaftJoinResult::NotAuthorized;
         };
-        if !node_member_authenticates_voter(&member, joiner_prefix, &peer) {
+        if !node_member_authenticates_raft_member(&member, joiner_prefix, &peer) {
             return crate::network::RaftJoinResult::NotAuthorized;
         }
         let bound = self
@@ -3594,6 +4481,9 @@ impl crate::network::NetworkService for NodeService {
                 crate::network::RaftJoinResult::PolicyMismatch
             };
         };
+        // Witnesses commit to the group's policy exactly like voters: a
+        // witness decides elections, so it must not join a group whose
+        // trust root it never agreed to.
         if route.production_trust_policy != production_trust_policy {
             return crate::network::RaftJoinResult::PolicyMismatch;
         }
@@ -3611,6 +4501,136 @@ impl crate::network::NetworkService for NodeService {
         // joint-configuration proposal.
         handler.handle_join(replication_id, joiner_prefix)
     }
+
+    /// Drive one operator-requested voter removal on the Raft group hosting
+    /// `to`, answering a JSON report. This daemon must lead the group. The
+    /// proposal is refused unless a majority of the *remaining* voters
+    /// answer status in the leader's term — otherwise the joint entry could
+    /// never commit and the group would wedge half-changed. On acceptance
+    /// the report names every remaining voter that confirmed a steady
+    /// configuration without `prefix`.
+    #[cfg(all(feature = "network", feature = "storage"))]
+    fn remove_raft_voter(&self, to: u32, to_unscoped: u32, prefix: u16) -> String {
+        let failure = |reason: String| format!("{{\"error\":\"{}\"}}", json_escape(&reason));
+        let replication_id = self
+            .raft_hosts
+            .lock()
+            .ok()
+            .and_then(|hosts| hosts.get(&to).or_else(|| hosts.get(&to_unscoped)).copied());
+        let Some(replication_id) = replication_id else {
+            return failure("this daemon hosts no Raft group for that agent".into());
+        };
+        let Some(network) = self
+            .shared_network
+            .lock()
+            .ok()
+            .and_then(|network| network.clone())
+        else {
+            return failure("no network is attached".into());
+        };
+        let Some(status) = network.local_raft_status(&replication_id) else {
+            return failure("the local Raft status is unavailable".into());
+        };
+        if status.role != crate::network::RaftRole::Leader {
+            return failure(not_leader_reason(status.leader_hint));
+        }
+        if status.joint_old.is_none() && !status.members.contains(&prefix) {
+            return format!(
+                "{{\"removed\":{prefix},\"joint_index\":0,\"members\":[{}],\"confirmed\":[]}}",
+                join_prefixes(&status.members),
+            );
+        }
+        let remaining: Vec<u16> = status
+            .members
+            .iter()
+            .copied()
+            .filter(|member| *member != prefix)
+            .collect();
+        let live: Vec<u16> = remaining
+            .iter()
+            .copied()
+            .filter(|&member| {
+                raft_status_of(&network, &replication_id, member)
+                    .is_some_and(|peer| peer.current_term == status.current_term)
+            })
+            .collect();
+        if live.len() * 2 <= remaining.len() {
+            return failure(format!(
+                "only {} of the {} remaining voters answer in term {}; removing {prefix:#06x} \
+                 now could leave the group unable to commit",
+                live.len(),
+                remaining.len(),
+                status.current_term,
+            ));
+        }
+
+        // A v2 root serializes membership changes with private-ingress
+        // admission exactly as a join does.
+        let route = self
+            .v2_private_ingress_routes
+            .read()
+            .ok()
+            .and_then(|routes| routes.get(&replication_id).cloned());
+        let barrier = match &route {
+            Some(route) => {
+                let Some(barrier) = route.barrier.try_acquire() else {
+                    return failure("another membership change is in flight; retry".into());
+                };
+                let (reply_tx, reply_rx) = mpsc::channel();
+                if route.quiescence_tx.try_send(reply_tx).is_err()
+                    || reply_rx.recv_timeout(V2_PRIVATE_INGRESS_STAGE_TIMEOUT).ok() != Some(true)
+                {
+                    return failure("private ingress did not quiesce; retry".into());
+                }
+                Some(barrier)
+            }
+            None => None,
+        };
+        let joint_index = match network.local_raft_remove(&replication_id, prefix) {
+            crate::network::RaftRemoveResult::Accepted { joint_index } => joint_index,
+            crate::network::RaftRemoveResult::NotMember => 0,
+            crate::network::RaftRemoveResult::NotLeader { leader_hint } => {
+                return failure(not_leader_reason(leader_hint));
+            }
+            crate::network::RaftRemoveResult::Busy => {
+                return failure("another membership change is in flight; retry".into());
+            }
+            crate::network::RaftRemoveResult::UnknownGroup => {
+                return failure("this daemon hosts no Raft group for that agent".into());
+            }
+            crate::network::RaftRemoveResult::Unsafe(reason) => return failure(reason),
+        };
+        drop(barrier);
+
+        // Confirm through each remaining voter's own status: the final
+        // configuration must be active there, not merely proposed here.
+        let deadline = Instant::now() + RAFT_REMOVE_CONFIRM_TIMEOUT;
+        let mut pending = remaining.clone();
+        loop {
+            pending.retain(|&member| {
+                !raft_status_of(&network, &replication_id, member).is_some_and(|peer| {
+                    peer.joint_old.is_none()
+                        && !peer.members.contains(&prefix)
+                        && peer.commit_index > joint_index
+                })
+            });
+            if pending.is_empty() {
+                break;
+            }
+            if Instant::now() >= deadline {
+                return failure(format!(
+                    "removal proposed at index {joint_index}, but voters [{}] have not \
+                     activated the final configuration within {RAFT_REMOVE_CONFIRM_TIMEOUT:?}",
+                    join_prefixes(&pending),
+                ));
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        format!(
+            "{{\"removed\":{prefix},\"joint_index\":{joint_index},\"members\":[{members}],\"confirmed\":[{members}]}}",
+            members = join_prefixes(&remaining),
+        )
+    }
 }
 
 /// Replay every log in the strategy's DAG against `runtime`'s
@@ -3836,9 +4856,9 @@ const SYNC_REPROBE_EVERY: u64 = 8;
 #[cfg(all(feature = "network", feature = "storage"))]
 #[derive(Debug)]
 enum SyncOutcome {
-    /// Peer has the replication group. `inserted` is true iff
-    /// at least one DAG node was new locally.
-    PeerHasGroup { inserted: bool },
+    /// Peer has the replication group. `inserted` counts the DAG
+    /// nodes that were new locally.
+    PeerHasGroup { inserted: u64 },
     /// Peer answered with empty heads — they don't (currently)
     /// host this group. Treated as a soft signal: the membership
     /// cache demotes them, but a full re-probe sweep
@@ -3868,6 +4888,7 @@ fn sync_loop(
     slot: ReplicaSlot,
     shutdown: Arc<AtomicBool>,
     notifier: Option<mpsc::Sender<()>>,
+    metrics: Arc<crate::metrics::ReplicaMetrics>,
 ) {
     let mut confirmed: HashSet<libp2p::PeerId> = HashSet::new();
     let mut tick: u64 = 0;
@@ -3930,8 +4951,9 @@ fn sync_loop(
             match sync_with_peer(&net, peer, &rep_id, &slot) {
                 Ok(SyncOutcome::PeerHasGroup { inserted }) => {
                     confirmed.insert(peer);
-                    if inserted {
+                    if inserted > 0 {
                         any_inserted = true;
+                        metrics.record_merge(inserted);
                     }
                 }
                 Ok(SyncOutcome::PeerEmpty) => {
@@ -3995,7 +5017,7 @@ fn sync_with_peer(
     cc.set_node_validator(slot.node_validator.clone());
     let mut frontier: Vec<[u8; 32]> = heads.clone();
     let mut seen: HashSet<[u8; 32]> = HashSet::new();
-    let mut inserted_any = false;
+    let mut inserted: u64 = 0;
 
     while let Some(cid) = frontier.pop() {
         if !seen.insert(cid) {
@@ -4009,7 +5031,7 @@ fn sync_with_peer(
             continue;
         };
         match cc.insert_node(&cid, &node_bytes) {
-            Ok(true) => inserted_any = true,
+            Ok(true) => inserted += 1,
             Ok(false) => {}
             Err(e) => {
                 warn!(error = %e, "sync: node from peer rejected");
@@ -4024,12 +5046,10 @@ fn sync_with_peer(
         }
     }
 
-    if inserted_any {
+    if inserted > 0 {
         cc.compact_roots()?;
     }
-    Ok(SyncOutcome::PeerHasGroup {
-        inserted: inserted_any,
-    })
+    Ok(SyncOutcome::PeerHasGroup { inserted })
 }
 
 /// Shared "last activity" instant, bumped on every dispatch. The
@@ -4069,16 +5089,22 @@ impl VosNode {
             agent_shutdown: Arc::new(Mutex::new(HashMap::new())),
             agent_info: Arc::new(std::sync::RwLock::new(HashMap::new())),
             last_activity: Arc::new(Mutex::new(Instant::now())),
+            metrics: Arc::new(crate::metrics::NodeMetrics::default()),
+            guest_logs: Arc::new(crate::guest_log::GuestLogs::default()),
             #[cfg(feature = "network")]
             shared_network: Arc::new(Mutex::new(None)),
             #[cfg(feature = "network")]
             operator_peer: None,
             operator_signer: None,
+            #[cfg(feature = "network")]
+            snapshot_handler: None,
             #[cfg(all(feature = "network", feature = "storage"))]
             crdt_replicas: Arc::new(Mutex::new(HashMap::new())),
             #[cfg(all(feature = "network", feature = "storage"))]
             raft_hosts: Arc::new(Mutex::new(HashMap::new())),
             #[cfg(all(feature = "network", feature = "storage"))]
+            raft_witnesses: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(all(feature = "network", feature = "storage"))]
             v2_private_ingress_routes: Arc::new(RwLock::new(HashMap::new())),
             #[cfg(all(feature = "network", feature = "storage"))]
             v2_raft_transport_resolutions: Arc::new(Mutex::new(HashMap::new())),
@@ -4101,6 +5127,7 @@ impl VosNode {
             proof_blobs: Arc::new(RwLock::new(HashMap::new())),
             proof_blobs_dir: None,
             program_blobs_dir: None,
+            root_batch_journal: None,
         }
     }
 
@@ -4230,6 +5257,51 @@ impl VosNode {
         self.operator_signer = Some(Arc::new(signer));
     }
 
+    /// Install the host's online-snapshot copier behind the reserved
+    /// `__snapshot` op. `handler` receives the staging directory the
+    /// operator named and returns the JSON report sent back; it must take
+    /// [`crate::write_barrier::quiesce`] around the copy so every database
+    /// and image it clones is one committed point in time. Call before
+    /// [`attach_network`](Self::attach_network). Unset refuses the op.
+    #[cfg(feature = "network")]
+    pub fn set_snapshot_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.snapshot_handler = Some(Arc::new(handler));
+    }
+
+    /// The node's guest log buffers: each agent's recent `DEBUG_WRITE`
+    /// lines, panics and dispatch outcomes, as served by `__logs`.
+    pub fn guest_logs(&self) -> Arc<crate::guest_log::GuestLogs> {
+        self.guest_logs.clone()
+    }
+
+    /// A cloneable handle that renders this node's metrics in the
+    /// Prometheus text format — see [`MetricsHandle::render`]. Take it
+    /// after the blob-store directories are configured; it samples
+    /// whatever agents, groups and network the node has at scrape time.
+    pub fn metrics_handle(&self) -> MetricsHandle {
+        MetricsHandle {
+            node_prefix: self.node_prefix,
+            metrics: self.metrics.clone(),
+            agent_info: self.agent_info.clone(),
+            #[cfg(feature = "network")]
+            shared_network: self.shared_network.clone(),
+            #[cfg(all(feature = "network", feature = "storage"))]
+            crdt_replicas: self.crdt_replicas.clone(),
+            #[cfg(all(feature = "network", feature = "storage"))]
+            raft_hosts: self.raft_hosts.clone(),
+            blob_dirs: [
+                ("program", self.program_blobs_dir.clone()),
+                ("proof", self.proof_blobs_dir.clone()),
+            ]
+            .into_iter()
+            .filter_map(|(store, dir)| Some((store, dir?)))
+            .collect(),
+        }
+    }
+
     /// Attach a libp2p [`Network`](crate::network::Network) so the
     /// node can route to and from peers.
     ///
@@ -4276,6 +5348,9 @@ impl VosNode {
             program_blobs_dir: self.program_blobs_dir.clone(),
             operator_peer: self.operator_peer.clone(),
             operator_signer: self.operator_signer.clone(),
+            snapshot_handler: self.snapshot_handler.clone(),
+            metrics: Some(self.metrics_handle()),
+            guest_logs: self.guest_logs.clone(),
             #[cfg(feature = "storage")]
             sync_floor_cache: Arc::new(RwLock::new(HashMap::new())),
         });
@@ -4600,7 +5675,9 @@ impl VosNode {
         }
         let (inbox_tx, inbox_rx) = mpsc::channel();
         let (invoke_tx, invoke_rx) = mpsc::channel();
-        self.routes.insert(id.0, inbox_tx);
+        let metrics = self.metrics.agent(id.0);
+        self.routes
+            .insert(id.0, InboxTx::metered(inbox_tx, metrics.clone()));
         self.invoke_routes.lock().unwrap().insert(id.0, invoke_tx);
         self.record_agent_name(id, Some(name.clone()));
         self.agent_info.write().unwrap().insert(
@@ -4663,6 +5740,7 @@ impl VosNode {
                     logical_timeslot,
                     shutdown,
                     activity,
+                    metrics,
                 )
             })),
         });
@@ -5642,6 +6720,7 @@ impl VosNode {
                 replication_id,
                 election_timeout_ms: raft_config.election_timeout_ms,
                 heartbeat_interval_ms: raft_config.heartbeat_interval_ms,
+                witnesses: raft_config.witnesses.clone(),
             },
             network.clone(),
             Some(apply_tx),
@@ -5811,7 +6890,8 @@ impl VosNode {
         let (invoke_tx, invoke_rx) = mpsc::channel();
         let outbox = self.outbox_tx.clone();
 
-        self.routes.insert(id.0, tx);
+        self.routes
+            .insert(id.0, InboxTx::metered(tx, self.metrics.agent(id.0)));
         self.invoke_routes.lock().unwrap().insert(id.0, invoke_tx);
         self.record_agent_name(id, config.name.clone());
         // Monotone locality seal (immutable-local): a named agent's
@@ -5907,6 +6987,7 @@ impl VosNode {
                         replication_id: rep_id,
                         election_timeout_ms: (150, 300),
                         heartbeat_interval_ms: 50,
+                        witnesses: config.witnesses.clone(),
                     };
                     let (worker_tx, worker_rx) = mpsc::channel::<u64>();
                     let worker = crate::raft::RaftWorker::spawn(
@@ -5982,6 +7063,8 @@ impl VosNode {
         } else {
             None
         };
+        let node_metrics = self.metrics.clone();
+        let guest_logs = self.guest_logs.clone();
 
         let join = thread::spawn(move || {
             agent_thread(
@@ -5996,6 +7079,8 @@ impl VosNode {
                 shutdown,
                 activity,
                 operator_signer,
+                node_metrics,
+                guest_logs,
                 #[cfg(feature = "network")]
                 shared_network,
                 #[cfg(all(feature = "network", feature = "storage"))]
@@ -6034,7 +7119,7 @@ impl VosNode {
         let (invoke_tx, invoke_rx) = mpsc::channel();
         let outbox = self.outbox_tx.clone();
 
-        self.routes.insert(id.0, tx);
+        self.routes.insert(id.0, tx.into());
         self.invoke_routes.lock().unwrap().insert(id.0, invoke_tx);
         self.record_agent_name(id, config.name.clone());
         // A serving (transport) extension carries a host-bound listen
@@ -6320,6 +7405,48 @@ impl VosNode {
         registered
     }
 
+    /// Host a log-less witness for one Raft group: spawn its worker on
+    /// `db_path` with no apply notifier and install it as the group's
+    /// RPC handler. The witness votes and stores entry ids only, so no
+    /// agent runs here. Idempotent per `cfg.replication_id`.
+    #[cfg(all(feature = "network", feature = "storage"))]
+    pub fn register_raft_witness(
+        &self,
+        db_path: &std::path::Path,
+        cfg: crate::raft::WorkerConfig,
+    ) -> Result<(), String> {
+        let rep_id = cfg.replication_id;
+        if !cfg.witnesses.contains(&cfg.me) {
+            return Err(format!(
+                "node {:#06x} is not a witness of this group",
+                cfg.me
+            ));
+        }
+        let mut witnesses = self.raft_witnesses.lock().unwrap();
+        if witnesses.contains_key(&rep_id) {
+            return Ok(());
+        }
+        let db = redb::Database::create(db_path)
+            .map_err(|e| format!("open witness db {}: {e}", db_path.display()))?;
+        let network = self.shared_network.lock().ok().and_then(|g| g.clone());
+        let worker = crate::raft::RaftWorker::spawn(Arc::new(db), cfg, network.clone(), None);
+        if let Some(net) = network.as_ref() {
+            net.register_raft_handler(rep_id, Arc::new(worker.handler()));
+        }
+        witnesses.insert(rep_id, worker);
+        Ok(())
+    }
+
+    /// Whether this node already hosts a witness for `replication_id`;
+    /// the spawn-reconcile counterpart of [`Self::has_agent`].
+    #[cfg(all(feature = "network", feature = "storage"))]
+    pub fn has_raft_witness(&self, replication_id: &[u8; 32]) -> bool {
+        self.raft_witnesses
+            .lock()
+            .map(|w| w.contains_key(replication_id))
+            .unwrap_or(false)
+    }
+
     /// Describe a SINGLE agent by id: a JSON snapshot of its
     /// registered metadata + live running flag, backing `vosx <agent> describe`.
     /// Generic across actor / service / transport agents (the in-process twin of
@@ -6391,7 +7518,7 @@ impl VosNode {
     pub(crate) fn install_inspector(&mut self) -> (ServiceId, mpsc::Receiver<Envelope>) {
         let id = self.alloc_id();
         let (tx, rx) = mpsc::channel();
-        self.routes.insert(id.0, tx);
+        self.routes.insert(id.0, tx.into());
         (id, rx)
     }
 
@@ -6448,8 +7575,10 @@ impl VosNode {
     ) {
         let shared_network = self.shared_network.clone();
         let shutdown = self.shutdown.clone();
-        let join =
-            thread::spawn(move || sync_loop(rep_id, shared_network, slot, shutdown, notifier));
+        let metrics = self.metrics.replica(rep_id);
+        let join = thread::spawn(move || {
+            sync_loop(rep_id, shared_network, slot, shutdown, notifier, metrics)
+        });
         self.sync_threads.push(join);
     }
 
@@ -6530,11 +7659,124 @@ impl VosNode {
         })
     }
 
+    /// Journal multi-root batches in `journal`. Attach it before
+    /// [`recover_root_batches`](Self::recover_root_batches) runs at boot.
+    pub fn attach_root_batch_journal(&mut self, journal: crate::v2::FileRootBatchJournalV2) {
+        self.root_batch_journal = Some(Mutex::new(journal));
+    }
+
+    /// Drive a multi-root batch over this node's v2 roots: each phase call
+    /// goes to its step's target actor as `Origin::System` under the phase's
+    /// stable invocation id, and the node's batch journal records every vote,
+    /// the decision and each settle call. Re-driving a batch whose earlier
+    /// drive failed resumes it. Batches are driven one at a time.
+    pub fn drive_root_batch(
+        &self,
+        batch: &crate::v2::RootBatchV2,
+    ) -> Result<crate::v2::RootBatchRecordV2, NodeRootBatchError> {
+        self.with_root_batch_routes(batch, |journal, participants, logical_timeslot| {
+            batch.drive(journal, participants, logical_timeslot)
+        })
+    }
+
+    /// Settle every unsettled journaled batch without issuing a prepare.
+    /// [`Restart`](crate::v2::RootBatchRecoveryV2::Restart) compensates every
+    /// undecided batch and runs once at boot after the roots registered;
+    /// [`Deadline`](crate::v2::RootBatchRecoveryV2::Deadline) is the periodic
+    /// sweep. A batch whose root is still unavailable stays journaled and is
+    /// retried by the next sweep.
+    pub fn recover_root_batches(
+        &self,
+        recovery: crate::v2::RootBatchRecoveryV2,
+    ) -> Vec<Result<crate::v2::RootBatchRecordV2, NodeRootBatchError>> {
+        use crate::v2::RootBatchJournalV2;
+
+        let unsettled = match self.root_batch_journal.as_ref() {
+            Some(journal) => journal.lock().unwrap().unsettled(),
+            None => return Vec::new(),
+        };
+        match unsettled {
+            Ok(records) => records
+                .iter()
+                .map(|record| {
+                    self.with_root_batch_routes(
+                        &record.batch,
+                        |journal, participants, logical_timeslot| {
+                            record
+                                .batch
+                                .recover(journal, participants, logical_timeslot, recovery)
+                        },
+                    )
+                })
+                .collect(),
+            Err(error) => vec![Err(crate::v2::RootBatchErrorV2::Journal(error))],
+        }
+    }
+
+    fn with_root_batch_routes(
+        &self,
+        batch: &crate::v2::RootBatchV2,
+        run: impl FnOnce(
+            &mut crate::v2::FileRootBatchJournalV2,
+            &mut [&mut dyn crate::v2::RootBatchParticipantV2<
+                Error = crate::actors::client::ClientError,
+            >],
+            u64,
+        ) -> Result<crate::v2::RootBatchRecordV2, NodeRootBatchError>,
+    ) -> Result<crate::v2::RootBatchRecordV2, NodeRootBatchError> {
+        let journal = self.root_batch_journal.as_ref().ok_or_else(|| {
+            crate::v2::RootBatchErrorV2::Journal(std::io::Error::other(
+                "no root batch journal attached",
+            ))
+        })?;
+        let mut journal = journal.lock().unwrap();
+        // Steps are routed by their target actor, so `root` only has to
+        // index a handle; an index past the step bound stays unknown.
+        let roots = batch
+            .steps
+            .iter()
+            .map(|step| step.root.saturating_add(1))
+            .max()
+            .unwrap_or(0)
+            .min(crate::v2::MAX_ROOT_BATCH_STEPS);
+        let mut routes: Vec<NodeRootBatchRoute<'_>> =
+            (0..roots).map(|_| NodeRootBatchRoute(self)).collect();
+        let mut participants: Vec<
+            &mut dyn crate::v2::RootBatchParticipantV2<Error = crate::actors::client::ClientError>,
+        > = routes
+            .iter_mut()
+            .map(|route| route as &mut dyn crate::v2::RootBatchParticipantV2<Error = _>)
+            .collect();
+        let logical_timeslot = self
+            .v2_logical_timeslot
+            .load(Ordering::Relaxed)
+            .max(v2_wall_timeslot());
+        run(&mut journal, &mut participants, logical_timeslot)
+    }
+
     fn invoke_actor_wire(
         &self,
         target: crate::v2::ActorId,
         arguments: Vec<u8>,
         proof_requested: bool,
+    ) -> Result<Vec<u8>, crate::actors::client::ClientError> {
+        let ordinal = self.v2_invocation_ordinal.fetch_add(1, Ordering::Relaxed);
+        let mut nonce = Vec::with_capacity(72);
+        nonce.extend_from_slice(&self.v2_invocation_seed);
+        nonce.extend_from_slice(&ordinal.to_le_bytes());
+        nonce.extend_from_slice(&target.0);
+        let invocation = crate::v2::InvocationId::derive(b"vos/node-root-invocation/v2", &nonce);
+        self.invoke_actor_exact_wire(invocation, target, arguments, proof_requested)
+    }
+
+    /// Invoke under a caller-chosen invocation id. Re-sending the same id and
+    /// arguments reattaches the committed result instead of executing again.
+    fn invoke_actor_exact_wire(
+        &self,
+        invocation: crate::v2::InvocationId,
+        target: crate::v2::ActorId,
+        arguments: Vec<u8>,
+        proof_requested: bool,
     ) -> Result<Vec<u8>, crate::actors::client::ClientError> {
         use crate::Decode;
 
@@ -6561,13 +7803,8 @@ impl VosNode {
             .get(&route)
             .cloned()
             .ok_or(crate::actors::client::ClientError::Unreachable)?;
-        let ordinal = self.v2_invocation_ordinal.fetch_add(1, Ordering::Relaxed);
-        let mut nonce = Vec::with_capacity(72);
-        nonce.extend_from_slice(&self.v2_invocation_seed);
-        nonce.extend_from_slice(&ordinal.to_le_bytes());
-        nonce.extend_from_slice(&target.0);
         let ingress = crate::v2::RootTreeInvocationV2 {
-            invocation: crate::v2::InvocationId::derive(b"vos/node-root-invocation/v2", &nonce),
+            invocation,
             target,
             method: message.name,
             arguments,
@@ -6767,13 +8004,17 @@ impl VosNode {
         {
             if !target.is_on_node(self.node_prefix) && !target.is_local() {
                 let net = self.shared_network.lock().ok().and_then(|g| g.clone());
-                if let Some(net) = net
-                    && let Some(peer) = net.peer_for_prefix(target.node_prefix())
-                {
+                if let Some(net) = net {
                     // `from = 0` because this is host-side; it
-                    // never participates in chain detection.
-                    let reply_rx =
-                        net.send_invoke(peer, ServiceId::REGISTRY.0, target.0, Vec::new(), msg);
+                    // never participates in chain detection. The
+                    // target may sit behind a relaying member.
+                    let reply_rx = net.send_invoke_to_prefix(
+                        target.node_prefix(),
+                        ServiceId::REGISTRY.0,
+                        target.0,
+                        Vec::new(),
+                        msg,
+                    );
                     // Daemon's `dispatch_invoke` already strips
                     // the envelope back to raw reply bytes, so
                     // we just forward them.
@@ -6907,9 +8148,10 @@ impl VosNode {
         }
 
         // Remote delivery via the network (if attached). The
-        // target's high 16 bits select the peer; if we haven't
-        // seen that prefix yet (no Hello received), the envelope
-        // is dropped with a warn — VOS has no store-and-forward
+        // target's high 16 bits select the peer; a prefix we hold no
+        // direct connection to is relayed through a neighbouring
+        // member, and the network drops the envelope with a warn only
+        // when no route exists — VOS has no store-and-forward
         // semantics today.
         #[cfg(feature = "network")]
         {
@@ -6919,15 +8161,11 @@ impl VosNode {
                     warn!(%target, "node: remote v2 envelope omitted its exact destination peer");
                     return;
                 }
-                let prefix = target.node_prefix();
-                if let Some(peer) = net.peer_for_prefix(prefix) {
-                    net.send_tell(peer, envelope.from.0, envelope.to.0, envelope.payload);
-                    return;
-                }
-                warn!(
-                    %target,
-                    prefix = format!("{prefix:#06x}"),
-                    "node: no peer known for prefix; dropping remote envelope",
+                net.send_tell_to_prefix(
+                    target.node_prefix(),
+                    envelope.from.0,
+                    envelope.to.0,
+                    envelope.payload,
                 );
                 return;
             }
@@ -6946,7 +8184,7 @@ impl VosNode {
         envelope: Envelope,
         binding: V2ActorRoute,
         network: Arc<crate::network::Network>,
-        local_tx: Option<mpsc::Sender<Envelope>>,
+        local_tx: Option<InboxTx>,
     ) {
         let Some(replication_id) = binding.replication_id else {
             warn!(target = %envelope.to, "node: Raft transport route omitted its replication identity");
@@ -7135,6 +8373,40 @@ impl Default for VosNode {
     }
 }
 
+/// Failure of [`VosNode::drive_root_batch`] or one recovered batch.
+pub type NodeRootBatchError =
+    crate::v2::RootBatchErrorV2<crate::actors::client::ClientError, std::io::Error>;
+
+/// Issues batch phase calls through the node's v2 root routes. The host
+/// ingress path always admits as `Origin::System`, so a step claiming any
+/// other origin or carrying credentials is refused instead of misattributed.
+struct NodeRootBatchRoute<'a>(&'a VosNode);
+
+impl crate::v2::RootBatchParticipantV2 for NodeRootBatchRoute<'_> {
+    type Error = crate::actors::client::ClientError;
+
+    fn invoke_phase(
+        &mut self,
+        request: crate::v2::LocalWorkRequestV2,
+    ) -> Result<bool, Self::Error> {
+        use crate::Decode;
+
+        if request.origin != crate::v2::Origin::System
+            || request.authorization != crate::v2::AuthorizationEvidenceV2::Public
+        {
+            return Err(crate::actors::client::ClientError::Forbidden);
+        }
+        let reply = self.0.invoke_actor_exact_wire(
+            request.invocation,
+            request.target,
+            request.arguments,
+            false,
+        )?;
+        Ok(<crate::value::Value as Decode>::try_decode(&reply)
+            == Some(crate::value::Value::Bool(true)))
+    }
+}
+
 fn v2_root_origin(
     caller: &crate::actors::Caller,
     actor_routes: &RwLock<HashMap<crate::v2::ActorId, V2ActorRoute>>,
@@ -8445,7 +9717,7 @@ fn collect_v2_raft_status_probes(
 #[cfg(all(feature = "network", feature = "storage"))]
 fn dispatch_resolved_v2_raft_transport(
     network: &crate::network::Network,
-    local_tx: Option<mpsc::Sender<Envelope>>,
+    local_tx: Option<InboxTx>,
     peer: libp2p::PeerId,
     route: ServiceId,
     mut envelope: Envelope,
@@ -8707,6 +9979,35 @@ where
     Some(assertion.ok_or(crate::STATUS_FORBIDDEN))
 }
 
+/// An exact retry of an invocation whose reply a direct caller already
+/// accepted carries no reply bytes any more. A bool reply is re-derived from
+/// the receipt's reply commitment, so a batch coordinator retrying a phase
+/// call after a crash still learns its committed outcome.
+fn retained_bool_reply(
+    ingress: &crate::v2::RootTreeInvocationV2,
+    committed: &crate::v2::CommittedRootTreeSliceV2,
+) -> Option<Result<Vec<u8>, u8>> {
+    use crate::Encode;
+
+    if !committed.duplicate || committed.published.reply.is_some() || ingress.proof_requested {
+        return None;
+    }
+    let commitment = committed.receipt.reply_commitment?;
+    [true, false]
+        .into_iter()
+        .map(|vote| crate::value::Value::Bool(vote).encode())
+        .find(|result| {
+            crate::v2::ReplyRecordV2 {
+                call_id: ingress.invocation.root_reply_id(),
+                producer: ingress.target,
+                result: result.clone(),
+            }
+            .commitment()
+                == commitment
+        })
+        .map(Ok)
+}
+
 fn v2_root_service_thread<B>(
     id: ServiceId,
     root_name: String,
@@ -8725,6 +10026,7 @@ fn v2_root_service_thread<B>(
     logical_timeslot: Arc<AtomicU64>,
     shutdown: Arc<AtomicBool>,
     activity: ActivityClock,
+    metrics: Arc<crate::metrics::AgentMetrics>,
 ) -> AgentResult
 where
     B: crate::v2::CommittedImageStoreV2
@@ -8771,6 +10073,7 @@ where
             }
         }
         while let Ok(envelope) = inbox_rx.try_recv() {
+            metrics.dequeued();
             *activity.lock().unwrap() = Instant::now();
             handle_v2_root_transport(
                 id,
@@ -8805,6 +10108,9 @@ where
                 &logical_timeslot,
                 &mut state,
             );
+            if let Ok(backlog) = service.backlog() {
+                metrics.set_root_backlog(backlog.inbox, backlog.ingress);
+            }
             last_transport_retry = Instant::now();
         }
         let req = match invoke_rx.recv_timeout(Duration::from_millis(10)) {
@@ -8813,6 +10119,37 @@ where
             Err(mpsc::RecvTimeoutError::Disconnected) => break,
         };
         *activity.lock().unwrap() = Instant::now();
+        metrics.record_dispatch(crate::metrics::DispatchKind::Invoke, 0);
+        if let Some(message) = v2_root_upgrade_request(&req) {
+            // The network gate admitted only this daemon's ADMIN operator;
+            // re-check here so an in-process actor cannot reach the verb.
+            #[cfg(feature = "network")]
+            let admin = matches!(req.caller, crate::actors::Caller::Peer(_))
+                && req.space_role.is_some_and(|role| role >= AUTH_ROLE_ADMIN);
+            #[cfg(not(feature = "network"))]
+            let admin = false;
+            if !admin {
+                send_v2_status(req.reply, crate::STATUS_FORBIDDEN, id);
+                continue;
+            }
+            let report = handle_v2_root_upgrade(
+                id,
+                &mut service,
+                &message,
+                #[cfg(feature = "network")]
+                &shared_network,
+            );
+            let _ = send_reply_capped(
+                req.reply,
+                encode_invoke_envelope(
+                    crate::STATUS_DONE,
+                    &[],
+                    &crate::Encode::encode(&crate::value::Value::Str(report)),
+                ),
+                id,
+            );
+            continue;
+        }
         let ingress = match crate::v2::RootTreeInvocationV2::decode(&req.msg) {
             Ok(ingress) => ingress,
             Err(_) => {
@@ -9162,7 +10499,8 @@ where
             is_role_authority && req.role_authority_request,
             &ingress,
             &committed,
-        );
+        )
+        .or_else(|| retained_bool_reply(&ingress, &committed));
         publish_v2_root_slice(
             id,
             &mut service,
@@ -9191,10 +10529,212 @@ where
             routes.remove(&private_ingress.replication_id);
         }
     }
-    AgentResult {
-        id,
-        panics: 0,
-        error,
+    AgentResult {
+        id,
+        panics: 0,
+        error,
+    }
+}
+
+/// The reserved `__upgrade_v2` dynamic message, if `req` carries one. It is
+/// sent raw rather than as a `RootTreeInvocationV2`: the upgrade targets the
+/// service hosting the root actor, not a method of the actor itself.
+fn v2_root_upgrade_request(req: &InvokeRequest) -> Option<crate::value::Msg> {
+    let bytes = req.msg.strip_prefix(&[crate::value::TAG_DYNAMIC])?;
+    let message = <crate::value::Msg as crate::Decode>::try_decode(bytes)?;
+    (message.name == "__upgrade_v2").then_some(message)
+}
+
+/// Capability presented with a replacement package: bound to the exact root
+/// service and replacement deployment and authenticated by the package's own
+/// deployment signature, mirroring the install capability `space up` binds.
+fn v2_upgrade_authorization(
+    service: &crate::v2::ServiceIdentityV2,
+    package: &crate::v2::VosPackageV2,
+) -> crate::v2::AuthorizationEvidenceV2 {
+    crate::v2::AuthorizationEvidenceV2::SystemCapability {
+        capability: crate::v2::SystemCapabilityId(
+            crate::v2::Hash::digest(
+                b"vos/space-upgrade-capability/v2",
+                &[
+                    &service.space.0,
+                    &service.root_service.0,
+                    &package.deployment_id().0,
+                ],
+            )
+            .0,
+        ),
+        authenticator: package.deployment_signature.signature.clone(),
+    }
+}
+
+/// Drive one operator-requested `UpgradeActor` for this root and render the
+/// result as the JSON object `vosx space upgrade-v2` prints. Every reported
+/// field is read back from guest-owned state after Accumulate. A failure
+/// renders as `{"error":"…"}`; guest Accumulate applies the transition
+/// atomically, so an error before the commit leaves the actor unchanged.
+fn handle_v2_root_upgrade<B>(
+    id: ServiceId,
+    service: &mut crate::v2::LocalRootTreeServiceV2<B>,
+    message: &crate::value::Msg,
+    #[cfg(feature = "network")] shared_network: &SharedNetwork,
+) -> String
+where
+    B: crate::v2::CommittedImageStoreV2
+        + crate::v2::ProofArtifactStoreV2<Error = <B as crate::v2::CommittedImageStoreV2>::Error>,
+{
+    use crate::v2::V2Wire;
+
+    let failure = |reason: String| serde_json::json!({ "error": reason }).to_string();
+    let Some(bytes) = message.args.get_bytes("package") else {
+        return failure("missing `package` argument".into());
+    };
+    let package = match crate::v2::VosPackageV2::decode(&bytes) {
+        Ok(package) if package.encode() == bytes => package,
+        Ok(_) => return failure("replacement package wire is not canonical".into()),
+        Err(error) => return failure(format!("decode replacement package: {error:?}")),
+    };
+    // The expected deployment/program pair and the linear base are read
+    // after this barrier, so the proposal is ordered after exactly the state
+    // the guest will compare against.
+    if let Err(barrier) = service.prepare_admission_barrier() {
+        #[cfg(all(feature = "storage", feature = "network"))]
+        if let Some(leader) = service.admission_leader_hint() {
+            return failure(format!(
+                "this node is not the Raft leader; run the upgrade against node {leader:#06x}"
+            ));
+        }
+        return failure(format!("admission barrier failed: {barrier}"));
+    }
+    let authorization = v2_upgrade_authorization(service.identity(), &package);
+    let upgraded = match service.upgrade_root_actor_after_barrier(&package, authorization) {
+        Ok(upgraded) => upgraded,
+        Err(error) => {
+            warn!(%id, ?error, "v2 root actor upgrade was not applied");
+            return failure(error.to_string());
+        }
+    };
+    info!(
+        %id,
+        revision = upgraded.revision,
+        duplicate = upgraded.duplicate,
+        "v2 root actor upgraded",
+    );
+    #[cfg(all(feature = "network", feature = "storage"))]
+    let voters = match await_v2_upgrade_applied(service, shared_network) {
+        Ok(voters) => voters,
+        Err(reason) => return failure(reason),
+    };
+    #[cfg(all(feature = "network", not(feature = "storage")))]
+    let _ = shared_network;
+    #[cfg(not(all(feature = "network", feature = "storage")))]
+    let voters: Vec<u16> = Vec::new();
+    let descriptor = &upgraded.descriptor;
+    let methods = crate::v2::PackageRolePoliciesV2::decode(&descriptor.role_policies)
+        .map(|policies| {
+            policies
+                .methods
+                .into_iter()
+                .map(|policy| policy.method)
+                .collect()
+        })
+        .unwrap_or_default();
+    serde_json::to_string(&V2UpgradeReply {
+        actor: lower_hex(&descriptor.actor.0),
+        previous_deployment: lower_hex(&upgraded.upgrade.expected_deployment.0),
+        previous_program: lower_hex(&upgraded.upgrade.expected_program.0),
+        deployment: lower_hex(&descriptor.deployment.0),
+        program: lower_hex(&descriptor.program.0),
+        producer: lower_hex(&descriptor.producer.0),
+        methods,
+        revision: upgraded.revision,
+        duplicate: upgraded.duplicate,
+        voters,
+    })
+    .unwrap_or_else(|error| failure(format!("encode upgrade reply: {error}")))
+}
+
+/// The JSON object [`handle_v2_root_upgrade`] answers on success.
+#[derive(serde::Serialize)]
+struct V2UpgradeReply {
+    actor: String,
+    previous_deployment: String,
+    previous_program: String,
+    deployment: String,
+    program: String,
+    producer: String,
+    methods: Vec<String>,
+    revision: u64,
+    duplicate: bool,
+    voters: Vec<u16>,
+}
+
+/// Wait until every steady data voter of this Raft root reports
+/// `last_applied` at or past the leader's commit index observed right after
+/// the upgrade committed. Witnesses are not awaited: they vote but never
+/// apply, so their `last_applied` stays put. Returns the data-voter
+/// prefixes; empty for a Local root, whose committed image is its only
+/// replica.
+#[cfg(all(feature = "network", feature = "storage"))]
+fn await_v2_upgrade_applied<B>(
+    service: &crate::v2::LocalRootTreeServiceV2<B>,
+    shared_network: &SharedNetwork,
+) -> Result<Vec<u16>, String>
+where
+    B: crate::v2::CommittedImageStoreV2
+        + crate::v2::ProofArtifactStoreV2<Error = <B as crate::v2::CommittedImageStoreV2>::Error>,
+{
+    let Some(replication_id) = service.replication_id() else {
+        return Ok(Vec::new());
+    };
+    let (leader, voters) = service.steady_raft_voters().ok_or_else(|| {
+        "upgrade committed, but leadership changed before voters could be checked; \
+         confirm with `space raft-status` on every voter"
+            .to_string()
+    })?;
+    if voters == [leader] {
+        return Ok(voters);
+    }
+    let network = shared_network
+        .lock()
+        .ok()
+        .and_then(|network| network.clone())
+        .ok_or_else(|| "upgrade committed, but no network is attached".to_string())?;
+    let target = network
+        .local_raft_status(&replication_id)
+        .map(|status| status.commit_index)
+        .ok_or_else(|| "upgrade committed, but the local Raft status is unavailable".to_string())?;
+    let deadline = Instant::now() + V2_UPGRADE_APPLY_TIMEOUT;
+    let mut pending = voters.clone();
+    loop {
+        pending.retain(|&prefix| {
+            let status = if prefix == leader {
+                network.local_raft_status(&replication_id)
+            } else {
+                network.peer_for_prefix(prefix).and_then(|peer| {
+                    network
+                        .send_raft_status_req(peer, replication_id)
+                        .recv_timeout(Duration::from_secs(1))
+                        .ok()
+                })
+            };
+            !status.is_some_and(|status| status.present && status.last_applied >= target)
+        });
+        if pending.is_empty() {
+            return Ok(voters);
+        }
+        if Instant::now() >= deadline {
+            let lagging = pending
+                .iter()
+                .map(|prefix| format!("{prefix:#06x}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(format!(
+                "upgrade committed at index {target}, but voters [{lagging}] have not applied it \
+                 within {V2_UPGRADE_APPLY_TIMEOUT:?}"
+            ));
+        }
+        thread::sleep(Duration::from_millis(100));
     }
 }
 
@@ -10469,53 +12009,119 @@ fn retry_v2_root_transport<B>(
             queue_v2_root_publication(id, service, &publication, outbox, actor_routes, state);
         }
     }
-    if matches!(
+    if let Ok(backlog) = service.backlog()
+        && backlog.oldest_admitted_at.is_some()
+    {
+        debug!(
+            %id,
+            inbox = backlog.inbox,
+            ingress = backlog.ingress,
+            actors = backlog.by_actor.len(),
+            oldest_admitted_at = ?backlog.oldest_admitted_at,
+            "v2 root backlog"
+        );
+    }
+    let inbox_runs = matches!(
         service.consistency(),
         crate::v2::ConsistencyModeV2::Local
             | crate::v2::ConsistencyModeV2::Raft
             | crate::v2::ConsistencyModeV2::Crdt
-    ) && let Ok(pending) = service.pending_inbox_calls()
-    {
-        for (call, _) in pending {
-            run_v2_root_inbox(
-                id,
-                service,
-                call,
-                outbox,
-                actor_routes,
-                proof_producer,
-                logical_timeslot,
-                state,
-            );
+    );
+    // Inbox rows and direct ingresses share one weighted fair order, so a
+    // flooded actor or caller cannot starve the rest of the root. Runs of
+    // unattested ingresses are handed over together so independent actors can
+    // refine concurrently; they still commit in this order.
+    let Ok(pending) = service.pending_work() else {
+        return;
+    };
+    let mut ingresses = Vec::new();
+    for work in pending {
+        match work.source {
+            crate::v2::PendingWorkSourceV2::Ingress(invocation) if !work.proof_requested => {
+                ingresses.push(invocation);
+                continue;
+            }
+            _ => run_v2_root_ingresses(id, service, &mut ingresses, outbox, actor_routes, state),
         }
-    }
-    if let Ok(pending) = service.pending_ingresses() {
-        for ingress in pending {
-            let result = if ingress.proof_requested {
-                let Some(producer) = proof_producer.as_mut() else {
-                    continue;
-                };
-                match service.invoke_admitted_attested(ingress.invocation, producer) {
-                    Ok(committed) => Ok(committed),
-                    Err(crate::v2::AttestedRootTreeInvokeErrorV2::Root(error)) => Err(error),
-                    Err(failure) => {
-                        warn!(%id, ?failure, "v2 queued attested ingress retry failed");
-                        continue;
-                    }
-                }
-            } else {
-                service.invoke_admitted(ingress.invocation)
-            };
-            match result {
-                Ok(committed) => {
-                    publish_v2_root_slice(id, service, committed, None, outbox, actor_routes, state)
+        let invocation = match work.source {
+            crate::v2::PendingWorkSourceV2::Inbox(call) => {
+                if inbox_runs {
+                    run_v2_root_inbox(
+                        id,
+                        service,
+                        call,
+                        outbox,
+                        actor_routes,
+                        proof_producer,
+                        logical_timeslot,
+                        state,
+                    );
                 }
-                Err(crate::v2::LocalRootTreeInvokeErrorV2::Schedule(
-                    crate::v2::ScheduleErrorV2::ActorBusy(_),
-                )) => {}
-                Err(failure) => warn!(%id, ?failure, "v2 queued ingress retry failed"),
+                continue;
+            }
+            crate::v2::PendingWorkSourceV2::Ingress(invocation) => invocation,
+        };
+        let Some(producer) = proof_producer.as_mut() else {
+            continue;
+        };
+        let result = match service.invoke_admitted_attested(invocation, producer) {
+            Ok(committed) => Ok(committed),
+            Err(crate::v2::AttestedRootTreeInvokeErrorV2::Root(error)) => Err(error),
+            Err(failure) => {
+                warn!(%id, ?failure, "v2 queued attested ingress retry failed");
+                continue;
+            }
+        };
+        finish_v2_root_ingress(id, service, result, outbox, actor_routes, state);
+    }
+    run_v2_root_ingresses(id, service, &mut ingresses, outbox, actor_routes, state);
+}
+
+/// Execute a run of queued unattested ingresses in order, refining independent
+/// actors concurrently where the root allows it.
+fn run_v2_root_ingresses<B>(
+    id: ServiceId,
+    service: &mut crate::v2::LocalRootTreeServiceV2<B>,
+    ingresses: &mut Vec<crate::v2::InvocationId>,
+    outbox: &mpsc::Sender<Envelope>,
+    actor_routes: &RwLock<HashMap<crate::v2::ActorId, V2ActorRoute>>,
+    state: &mut V2RootThreadState,
+) where
+    B: crate::v2::CommittedImageStoreV2
+        + crate::v2::ProofArtifactStoreV2<Error = <B as crate::v2::CommittedImageStoreV2>::Error>,
+{
+    if ingresses.is_empty() {
+        return;
+    }
+    match service.invoke_admitted_batch(&core::mem::take(ingresses)) {
+        Ok(results) => {
+            for result in results {
+                finish_v2_root_ingress(id, service, result, outbox, actor_routes, state);
             }
         }
+        Err(failure) => warn!(%id, ?failure, "v2 queued ingress retry failed"),
+    }
+}
+
+fn finish_v2_root_ingress<B>(
+    id: ServiceId,
+    service: &mut crate::v2::LocalRootTreeServiceV2<B>,
+    result: Result<crate::v2::CommittedRootTreeSliceV2, crate::v2::LocalRootTreeInvokeErrorV2>,
+    outbox: &mpsc::Sender<Envelope>,
+    actor_routes: &RwLock<HashMap<crate::v2::ActorId, V2ActorRoute>>,
+    state: &mut V2RootThreadState,
+) where
+    B: crate::v2::CommittedImageStoreV2
+        + crate::v2::ProofArtifactStoreV2<Error = <B as crate::v2::CommittedImageStoreV2>::Error>,
+{
+    match result {
+        Ok(committed) => {
+            publish_v2_root_slice(id, service, committed, None, outbox, actor_routes, state)
+        }
+        Err(crate::v2::LocalRootTreeInvokeErrorV2::Schedule(
+            crate::v2::ScheduleErrorV2::ActorBusy(_),
+        )) => {}
+        Err(failure) => warn!(%id, ?failure, "v2 queued ingress retry failed"),
     }
 }
 
@@ -10537,6 +12143,8 @@ fn agent_thread(
     shutdown: Arc<AtomicBool>,
     activity: ActivityClock,
     operator_signer: Option<crate::registry::CatalogOpSigner>,
+    node_metrics: Arc<crate::metrics::NodeMetrics>,
+    guest_logs: Arc<crate::guest_log::GuestLogs>,
     #[cfg(feature = "network")] shared_network: SharedNetwork,
     #[cfg(all(feature = "network", feature = "storage"))] sync_rx: Option<mpsc::Receiver<()>>,
 ) -> AgentResult {
@@ -10723,15 +12331,17 @@ fn agent_thread(
             if !target.is_on_node(local_prefix) && !target.is_local() {
                 let net = shared_network_for_ext.lock().ok().and_then(|g| g.clone());
                 if let Some(net) = net {
-                    let prefix = target.node_prefix();
-                    if let Some(peer) = net.peer_for_prefix(prefix) {
-                        let reply_rx =
-                            net.send_invoke(peer, id.0, target.0, chain_snapshot, msg.to_vec());
-                        return reply_rx
-                            .recv_timeout(std::time::Duration::from_secs(10))
-                            .ok()
-                            .map(crate::runtime::ExternalInvokeReply::Done);
-                    }
+                    let reply_rx = net.send_invoke_to_prefix(
+                        target.node_prefix(),
+                        id.0,
+                        target.0,
+                        chain_snapshot,
+                        msg.to_vec(),
+                    );
+                    return reply_rx
+                        .recv_timeout(std::time::Duration::from_secs(10))
+                        .ok()
+                        .map(crate::runtime::ExternalInvokeReply::Done);
                 }
             }
         }
@@ -10748,6 +12358,11 @@ fn agent_thread(
     // Capture rep_id up front — config is consumed below.
     #[cfg(all(feature = "network", feature = "storage"))]
     let agent_rep_id: Option<[u8; 32]> = config.replication_id;
+    let metrics = node_metrics.agent(id.0);
+    #[cfg(all(feature = "network", feature = "storage"))]
+    let replica_metrics = agent_rep_id
+        .filter(|_| consistency == Consistency::Crdt)
+        .map(|rep_id| node_metrics.replica(rep_id));
     // Multi-mode Raft: register() pre-spawned the worker and
     // handed it to us through the config; build the Multi-flavour
     // strategy here while we still own `config` mutably.
@@ -10763,6 +12378,7 @@ fn agent_thread(
                         me: id.node_prefix(),
                         members: config.members.clone(),
                         replication_id: agent_rep_id.unwrap_or([0u8; 32]),
+                        witnesses: config.witnesses.clone(),
                         ..crate::raft::RaftConfig::default()
                     };
                     match crate::raft::RaftCommit::from_worker(db, cfg, worker, apply_rx) {
@@ -10940,6 +12556,13 @@ fn agent_thread(
                             space_role: req.space_role,
                         })
                     });
+                    let scope = DispatchScope::open(
+                        &runtime,
+                        &metrics,
+                        &guest_logs,
+                        id,
+                        crate::metrics::DispatchKind::Invoke,
+                    );
                     let outcome = handle_invoke_request(
                         &mut runtime,
                         svc_id,
@@ -10950,6 +12573,7 @@ fn agent_thread(
                         recording_enabled,
                         operator_signer.as_ref(),
                     );
+                    scope.finish(&mut runtime, &outcome);
                     if let Err(e) = outcome {
                         fatal_error = Some(format!("commit failed during invoke: {e}"));
                         break;
@@ -10991,6 +12615,9 @@ fn agent_thread(
                     fatal_error = Some(format!("soft restart failed: {err}"));
                     break;
                 }
+                if let Some(replica) = &replica_metrics {
+                    replica.folded();
+                }
                 // Restart the loop so we re-check invokes that
                 // may have arrived during the soft restart.
                 continue;
@@ -11012,7 +12639,14 @@ fn agent_thread(
             // again. Including `is_suspended` here would busy-spin
             // on yielded children.
             // Keep the chain set by the dispatch that produced it.
-            if let Err(e) = dispatch_once(
+            let scope = DispatchScope::open(
+                &runtime,
+                &metrics,
+                &guest_logs,
+                id,
+                crate::metrics::DispatchKind::Tell,
+            );
+            let outcome = dispatch_once(
                 &mut runtime,
                 svc_id,
                 &outbox,
@@ -11020,7 +12654,9 @@ fn agent_thread(
                 None,
                 strategy.as_mut(),
                 recording_enabled,
-            ) {
+            );
+            scope.finish(&mut runtime, &outcome);
+            if let Err(e) = outcome {
                 // On a Raft follower the commit can return
                 // NotLeader. Log, soft-restart to bring the runtime
                 // back in sync, continue. CRDT failures are still
@@ -11062,6 +12698,7 @@ fn agent_thread(
                 };
                 match inbox.recv_timeout(wait) {
                     Ok(env) => {
+                        metrics.dequeued();
                         bump();
                         *current_chain.lock().unwrap() = vec![id.0];
                         env.payload
@@ -11071,7 +12708,14 @@ fn agent_thread(
                 }
             }
         };
-        if let Err(e) = dispatch_once(
+        let scope = DispatchScope::open(
+            &runtime,
+            &metrics,
+            &guest_logs,
+            id,
+            crate::metrics::DispatchKind::Tell,
+        );
+        let outcome = dispatch_once(
             &mut runtime,
             svc_id,
             &outbox,
@@ -11079,7 +12723,9 @@ fn agent_thread(
             Some(msg),
             strategy.as_mut(),
             recording_enabled,
-        ) {
+        );
+        scope.finish(&mut runtime, &outcome);
+        if let Err(e) = outcome {
             // Tell-style dispatch on a follower will return
             // NotLeader. Soft-restart and continue rather than
             // killing the agent; the message is effectively
@@ -11107,6 +12753,57 @@ fn agent_thread(
     }
 }
 
+/// Held across one dispatch on an agent thread: tees the guest's
+/// `DEBUG_WRITE` lines into the node's log buffer, and
+/// [`finish`](Self::finish) records the dispatch's metrics and its
+/// outcome line. Replays and soft restarts run outside a scope.
+struct DispatchScope<'a> {
+    metrics: &'a crate::metrics::AgentMetrics,
+    logs: &'a crate::guest_log::GuestLogs,
+    id: ServiceId,
+    kind: crate::metrics::DispatchKind,
+    panics_before: u32,
+    capture: crate::guest_log::CaptureGuard,
+}
+
+impl<'a> DispatchScope<'a> {
+    fn open(
+        runtime: &VosRuntime,
+        metrics: &'a crate::metrics::AgentMetrics,
+        logs: &'a Arc<crate::guest_log::GuestLogs>,
+        id: ServiceId,
+        kind: crate::metrics::DispatchKind,
+    ) -> Self {
+        DispatchScope {
+            metrics,
+            logs: logs.as_ref(),
+            id,
+            kind,
+            panics_before: runtime.panics,
+            capture: crate::guest_log::capture(logs, id.0),
+        }
+    }
+
+    fn finish(self, runtime: &mut VosRuntime, outcome: &Result<(), crate::commit::CommitError>) {
+        // Flush the guest's unterminated last line ahead of the outcome.
+        drop(self.capture);
+        let gas = runtime.take_gas_burned();
+        self.metrics.record_dispatch(self.kind, gas);
+        let result = match outcome {
+            Err(e) => format!("commit failed: {e}"),
+            Ok(()) if runtime.panics > self.panics_before => {
+                "trapped (panic, out of gas or fault)".to_string()
+            }
+            Ok(()) => "ok".to_string(),
+        };
+        self.logs.push(
+            self.id.0,
+            crate::guest_log::LogKind::Outcome,
+            &format!("{} {result}, {gas} gas", self.kind.as_str()),
+        );
+    }
+}
+
 /// Publish the strategy's current roots on the gossipsub topic
 /// for `rep_id` if the agent is replicated and a network is
 /// attached. Cheap when not replicated (early return); the
@@ -11320,6 +13017,12 @@ pub(crate) const AUTH_ROLE_NONE: u8 = 0;
 #[cfg(feature = "network")]
 pub(crate) const AUTH_ROLE_ADMIN: u8 = 3;
 
+/// Wire-byte for the DEVELOPER grant. Mirrors
+/// `space_registry::AUTH_ROLE_DEVELOPER`; the floor for reading actor logs
+/// through `__logs`.
+#[cfg(feature = "network")]
+pub(crate) const AUTH_ROLE_DEVELOPER: u8 = 2;
+
 /// Wire-byte for the lowest grant tier (read / Member). Mirrors
 /// `space_registry::AUTH_ROLE_READONLY`; the floor that authorizes a
 /// peer to be served a private replica's sync data.
@@ -11334,6 +13037,12 @@ pub(crate) const AUTH_ROLE_READONLY: u8 = 1;
 #[cfg(feature = "network")]
 pub(crate) const NODE_ROLE_REPLY_VOTER: u8 = 1;
 
+/// `node_role` reply byte for an enrolled Raft WITNESS
+/// (`NODE_ROLE_WITNESS` = 2, encoded `role + 1`). Same local-literal
+/// rationale as [`NODE_ROLE_REPLY_VOTER`].
+#[cfg(feature = "network")]
+pub(crate) const NODE_ROLE_REPLY_WITNESS: u8 = 3;
+
 /// The bare (un-role-gated) READ handlers on the private `msg-*` channel
 /// replicas — `msg-log` (`history` / `stats`), `msg-ctl` (`commits` /
 /// `commit_at` / `head`), `msg-directory` (`kp_count` / `channels`).
@@ -11960,6 +13669,7 @@ fn extension_thread(
             shutdown,
             activity,
             invoke_routes,
+            agent_names,
             raft_fwd,
         );
     }
@@ -12385,6 +14095,72 @@ fn resolve_relay_caller(
     (carrier, Some(effective.as_u8()))
 }
 
+/// The `(caller, space_role, actor_local_role)` an extension relays on an
+/// outbound ask to `target`: [`resolve_relay_caller`]'s capped caller, plus
+/// the propagated peer's actor-local grant on the target, passed through
+/// faithfully ([`relay_actor_local_role`]) — it overrides `space_role`, so
+/// the carrier then stays the Peer. Shared by the actor-mode
+/// `EFFECT_ASK_DISPATCH` (the caller of the invoke in flight) and the
+/// transport `EFFECT_ASK_DISPATCH_AS` (a signed-in SSH member, whose
+/// actor-local grant the caller then caps with [`cap_actor_local_role`]).
+fn bounded_relay(
+    invoke_routes: &InvokeRoutes,
+    agent_names: &AgentNames,
+    intra_caps: &[crate::actors::IntraCap],
+    propagated: Option<&PropagatedCaller>,
+    target: u32,
+) -> (crate::actors::Caller, Option<u8>, Option<u8>) {
+    let target_name = agent_names
+        .read()
+        .ok()
+        .and_then(|m| m.get(&local_id_of(target)).cloned());
+    #[allow(unused_mut)]
+    let (mut caller, space_role) =
+        resolve_relay_caller(propagated, intra_caps, target_name.as_deref());
+    // Only Peer callers (libp2p gate) carry an actor-local grant, so this is
+    // `network`-only.
+    #[cfg(feature = "network")]
+    let actor_local_role = match relay_actor_local_role(
+        invoke_routes,
+        propagated,
+        intra_caps,
+        target_name.as_deref(),
+    ) {
+        Some((peer_bytes, role)) => {
+            caller = crate::actors::Caller::Peer(peer_bytes);
+            Some(role)
+        }
+        None => None,
+    };
+    #[cfg(not(feature = "network"))]
+    let actor_local_role: Option<u8> = {
+        let _ = invoke_routes;
+        None
+    };
+    (caller, space_role, actor_local_role)
+}
+
+/// Cap an actor-local grant [`bounded_relay`] relays to `target` at the
+/// extension's `intra_caps` ceiling for it: the grant's byte, but no higher
+/// than the ceiling's [`SpaceRole`](crate::actors::SpaceRole) discriminant.
+/// Role bytes rank authority within an actor's role space, so the cap can
+/// lower a grant or keep it, never raise it. Used where the extension itself
+/// vouches for the principal (the transport `EFFECT_ASK_DISPATCH_AS`), so
+/// the grant can't outrun what the operator granted the relay.
+fn cap_actor_local_role(
+    agent_names: &AgentNames,
+    intra_caps: &[crate::actors::IntraCap],
+    target: u32,
+    actor_local_role: Option<u8>,
+) -> Option<u8> {
+    let target_name = agent_names
+        .read()
+        .ok()
+        .and_then(|m| m.get(&local_id_of(target)).cloned());
+    let ceiling = crate::actors::cap_for(intra_caps, target_name.as_deref())?;
+    actor_local_role.map(|role| role.min(ceiling.as_u8()))
+}
+
 /// Outcome of a single extension dispatch. `Ok(bytes)` means the
 /// handler completed with the given reply (`bytes` may be empty
 /// for a `()` return). `Err` covers the cases that can't be
@@ -12652,39 +14428,14 @@ impl Fulfiller<'_> {
                     return bs::resp_err("");
                 }
                 let target = u32::from_le_bytes(rest[..4].try_into().unwrap());
-                let target_name = self
-                    .agent_names
-                    .read()
-                    .ok()
-                    .and_then(|m| m.get(&local_id_of(target)).cloned());
                 let propagated = current_relay_caller();
-                #[allow(unused_mut)]
-                let (mut caller, space_role) = resolve_relay_caller(
-                    propagated.as_ref(),
-                    self.intra_caps,
-                    target_name.as_deref(),
-                );
-                // Faithfully propagate the propagated peer's actor-local grant
-                // on the final target (in the target's own role space, relayed
-                // uncapped — the cap only gates *whether* the relay may reach
-                // the target). Overrides `space_role`, so the carrier must stay
-                // the Peer for the override to bind. Only Peer callers (libp2p
-                // gate) carry one, so this is `network`-only.
-                #[cfg(feature = "network")]
-                let actor_local_role = match relay_actor_local_role(
+                let (caller, space_role, actor_local_role) = bounded_relay(
                     self.invoke_routes,
-                    propagated.as_ref(),
+                    self.agent_names,
                     self.intra_caps,
-                    target_name.as_deref(),
-                ) {
-                    Some((peer_bytes, role)) => {
-                        caller = crate::actors::Caller::Peer(peer_bytes);
-                        Some(role)
-                    }
-                    None => None,
-                };
-                #[cfg(not(feature = "network"))]
-                let actor_local_role: Option<u8> = None;
+                    propagated.as_ref(),
+                    target,
+                );
                 match route_invoke(
                     self.invoke_routes,
                     self.raft_fwd,
@@ -12692,6 +14443,7 @@ impl Fulfiller<'_> {
                     caller,
                     space_role,
                     actor_local_role,
+                    false,
                     rest,
                 )
                 .await
@@ -12846,7 +14598,10 @@ impl Fulfiller<'_> {
 /// ambiguity) and awaited on the executor (no blocking-pool thread), so other
 /// connection tasks keep serving. The relayed caller is
 /// [`Caller::Unauthenticated`] (a conn task has no inbound authenticated
-/// caller). The remaining effects are still rejected in v1:
+/// caller), except on [`EFFECT_ASK_DISPATCH_AS`], where the extension passes
+/// an SSH member's sign-in proof and the host, having verified it, relays
+/// that member bounded by the extension's `intra_caps`. The remaining
+/// effects are still rejected in v1:
 ///   - `EFFECT_LISTEN`/`ACCEPT` — the host owns the accept loop; a connection
 ///     task cannot bind or accept.
 ///   - `EFFECT_FETCH`/`BLOB_GET`/`BLOB_PUT` — synchronous/blocking on the
@@ -12869,14 +14624,27 @@ struct ConnFulfiller {
     /// Leader-forwarding context for asks that target a raft-hosted
     /// agent whose local replica is a follower.
     raft_fwd: RaftFwd,
+    /// The extension's declared intra-system caps — the ceiling on what an
+    /// `EFFECT_ASK_DISPATCH_AS` relays for the signed-in member.
+    intra_caps: Arc<[crate::actors::IntraCap]>,
+    /// Host reverse map `local_id → instance_name`, so `intra_caps`
+    /// (declared by name) bind to the ask target.
+    agent_names: AgentNames,
+    /// The operator granted `dispatch_as` ([`ExtensionConfig::dispatch_as`]);
+    /// without it every `EFFECT_ASK_DISPATCH_AS` is refused.
+    dispatch_as: bool,
+    /// The encoded [`UserAuthProof`](crate::effects::UserAuthProof) this
+    /// connection first relayed with, and its verified PeerId. A connection
+    /// acts as one principal: a later call under any other proof is refused.
+    principal: Option<(Vec<u8>, Vec<u8>)>,
 }
 
 impl ConnFulfiller {
     async fn fulfill(&mut self, effect: &[u8]) -> Vec<u8> {
         use crate::effects::bytestream as bs;
         use crate::effects::{
-            EFFECT_ACCEPT, EFFECT_ASK, EFFECT_ASK_DISPATCH, EFFECT_CLOSE, EFFECT_LISTEN,
-            EFFECT_READ, EFFECT_WRITE,
+            EFFECT_ACCEPT, EFFECT_ASK, EFFECT_ASK_DISPATCH, EFFECT_ASK_DISPATCH_AS, EFFECT_CLOSE,
+            EFFECT_LISTEN, EFFECT_READ, EFFECT_WRITE,
         };
         let extension_id = self.extension_id;
         match effect.first().copied() {
@@ -12886,6 +14654,7 @@ impl ConnFulfiller {
             // caller (the gateway) can tell a real reply from a failure.
             Some(EFFECT_ASK) => self.fulfill_ask(&effect[1..]).await,
             Some(EFFECT_ASK_DISPATCH) => self.fulfill_ask_dispatch(&effect[1..]).await,
+            Some(EFFECT_ASK_DISPATCH_AS) => self.fulfill_ask_dispatch_as(&effect[1..]).await,
             Some(EFFECT_READ) => {
                 let Some((cid, max)) = bs::decode_read(&effect[1..]) else {
                     return bs::resp_err("read: bad request");
@@ -12968,26 +14737,140 @@ impl ConnFulfiller {
         }
     }
 
-    /// Fulfil a plain `EFFECT_ASK` from a transport connection task:
-    /// the raw reply bytes the `Ask` future's `decode_reply` expects.
-    /// Any failure (no route / send error / timeout / non-DONE status) degrades
-    /// to empty bytes → `Value::Unit`, which the handler already tolerates —
-    /// callers that must distinguish a failure use `EFFECT_ASK_DISPATCH`.
-    async fn fulfill_ask(&self, rest: &[u8]) -> Vec<u8> {
-        self.invoke_route(rest).await.unwrap_or_default()
+    /// Fulfil a plain `EFFECT_ASK` from a transport connection task:
+    /// the raw reply bytes the `Ask` future's `decode_reply` expects.
+    /// Any failure (no route / send error / timeout / non-DONE status) degrades
+    /// to empty bytes → `Value::Unit`, which the handler already tolerates —
+    /// callers that must distinguish a failure use `EFFECT_ASK_DISPATCH`.
+    async fn fulfill_ask(&self, rest: &[u8]) -> Vec<u8> {
+        self.invoke_route(rest).await.unwrap_or_default()
+    }
+
+    /// Fulfil an `EFFECT_ASK_DISPATCH`: same routing as
+    /// [`fulfill_ask`], but **status-framed** so the caller (the http-gateway)
+    /// can tell a real reply (`Some`) from a dispatch failure (`None`) —
+    /// `[RESP_OK][reply…]` vs `[RESP_ERR]`, the byte-stream convention. Lets a
+    /// gateway render a handler panic as `502` instead of `200 null`.
+    async fn fulfill_ask_dispatch(&self, rest: &[u8]) -> Vec<u8> {
+        use crate::effects::bytestream as bs;
+        match self.invoke_route(rest).await {
+            Some(reply) => bs::resp_ok_bytes(&reply),
+            None => bs::resp_err(""),
+        }
+    }
+
+    /// Fulfil an `EFFECT_ASK_DISPATCH_AS`: the status-framed ask of
+    /// [`Self::fulfill_ask_dispatch`], made on behalf of the SSH member whose
+    /// sign-in proof the extension passes along. Refused outright unless the
+    /// operator granted `dispatch_as`; otherwise the host verifies the proof
+    /// ([`Self::verified_principal`]) and the key's PeerId enters the relay as
+    /// `Caller::Peer` with its registry role, leaving it bounded by the
+    /// extension's `intra_caps` ([`bounded_relay`]) — the same intersection
+    /// an actor-mode extension's relay gets, so a transport without caps
+    /// still reaches role-gated targets only as `Unauthenticated`. Unlike
+    /// the actor-mode relay, the peer's actor-local grant is capped at the
+    /// same ceiling ([`cap_actor_local_role`]): here the extension, not a
+    /// libp2p connection, vouches for the session. A refusal by the target
+    /// is relayed as the forbidden envelope, not a failure.
+    async fn fulfill_ask_dispatch_as(&mut self, rest: &[u8]) -> Vec<u8> {
+        use crate::effects::bytestream as bs;
+        if !self.dispatch_as {
+            warn!(
+                extension_id = %self.extension_id,
+                "transport ask_dispatch_as refused: the manifest doesn't grant dispatch_as"
+            );
+            return bs::resp_err("");
+        }
+        let Some((proof, rest)) = rest.split_first_chunk::<2>().and_then(|(len, rest)| {
+            let len = usize::from(u16::from_le_bytes(*len));
+            (rest.len() >= len).then(|| rest.split_at(len))
+        }) else {
+            return bs::resp_err("");
+        };
+        if rest.len() < 4 {
+            return bs::resp_err("");
+        }
+        let Some(peer) = self.verified_principal(proof) else {
+            warn!(
+                extension_id = %self.extension_id,
+                cid = self.cid,
+                "transport ask_dispatch_as refused: bad or foreign sign-in proof"
+            );
+            return bs::resp_err("");
+        };
+        let target = u32::from_le_bytes(rest[..4].try_into().unwrap());
+        let propagated = PropagatedCaller {
+            space_role: self.peer_space_role(&peer),
+            caller: crate::actors::Caller::Peer(peer),
+        };
+        let (caller, space_role, actor_local_role) = bounded_relay(
+            &self.invoke_routes,
+            &self.agent_names,
+            &self.intra_caps,
+            Some(&propagated),
+            target,
+        );
+        let actor_local_role = cap_actor_local_role(
+            &self.agent_names,
+            &self.intra_caps,
+            target,
+            actor_local_role,
+        );
+        match route_invoke(
+            &self.invoke_routes,
+            &self.raft_fwd,
+            self.extension_id,
+            caller,
+            space_role,
+            actor_local_role,
+            true,
+            rest,
+        )
+        .await
+        {
+            Some(reply) => bs::resp_ok_bytes(&reply),
+            None => bs::resp_err(""),
+        }
+    }
+
+    /// The PeerId an encoded [`UserAuthProof`](crate::effects::UserAuthProof)
+    /// signs in as, once its ed25519 signature verifies over the RFC 4252
+    /// `publickey` request. The first proof a connection verifies pins it;
+    /// any other proof afterwards is `None`.
+    fn verified_principal(&mut self, encoded: &[u8]) -> Option<Vec<u8>> {
+        if let Some((pinned, peer)) = &self.principal {
+            return (pinned.as_slice() == encoded).then(|| peer.clone());
+        }
+        let proof = crate::effects::UserAuthProof::decode(encoded)?;
+        if proof.session_id.is_empty() {
+            return None;
+        }
+        let peer = proof.peer_id();
+        // The PeerId is the identity multihash of the protobuf public key.
+        crate::v2::verify_ed25519_signature(&peer[2..], &proof.signed_message(), &proof.signature)
+            .then_some(())?;
+        self.principal = Some((encoded.to_vec(), peer.clone()));
+        Some(peer)
     }
 
-    /// Fulfil an `EFFECT_ASK_DISPATCH`: same routing as
-    /// [`fulfill_ask`], but **status-framed** so the caller (the http-gateway)
-    /// can tell a real reply (`Some`) from a dispatch failure (`None`) —
-    /// `[RESP_OK][reply…]` vs `[RESP_ERR]`, the byte-stream convention. Lets a
-    /// gateway render a handler panic as `502` instead of `200 null`.
-    async fn fulfill_ask_dispatch(&self, rest: &[u8]) -> Vec<u8> {
-        use crate::effects::bytestream as bs;
-        match self.invoke_route(rest).await {
-            Some(reply) => bs::resp_ok_bytes(&reply),
-            None => bs::resp_err(""),
-        }
+    /// The registry's space role for `peer`, `None` for no grant. A
+    /// synchronous probe of the registry's cached grant table, so it holds
+    /// the executor only for a local round trip.
+    #[cfg(feature = "network")]
+    fn peer_space_role(&self, peer: &[u8]) -> Option<u8> {
+        use crate::actors::codec::Encode;
+        use crate::value::{Msg, TAG_DYNAMIC};
+        let msg = Msg::new("peer_role").with("peer_id", peer.to_vec());
+        let mut payload = Vec::with_capacity(1 + 64);
+        payload.push(TAG_DYNAMIC);
+        payload.extend_from_slice(&msg.encode());
+        registry_probe_u8(&self.invoke_routes, payload).filter(|&role| role != AUTH_ROLE_NONE)
+    }
+
+    /// Without the libp2p gate there are no peer grants to consult.
+    #[cfg(not(feature = "network"))]
+    fn peer_space_role(&self, _peer: &[u8]) -> Option<u8> {
+        None
     }
 
     /// Transport conn tasks have no inbound caller — they serve raw external
@@ -13000,7 +14883,8 @@ impl ConnFulfiller {
     /// relay is always the `Unauthenticated` floor regardless of `intra_caps`.
     /// Confinement of what a transport-fronted request can reach is the *target*
     /// actor's own role gate, not the gateway's caps. (`intra_caps` bound only
-    /// the actor-mode `ctx.ask_dispatch` relay, which carries a real caller.)
+    /// the relays that carry a real caller: the actor-mode `ctx.ask_dispatch`
+    /// and the transport `ctx.ask_dispatch_as`.)
     async fn invoke_route(&self, rest: &[u8]) -> Option<Vec<u8>> {
         route_invoke(
             &self.invoke_routes,
@@ -13009,6 +14893,7 @@ impl ConnFulfiller {
             crate::actors::Caller::Unauthenticated,
             None,
             None,
+            false,
             rest,
         )
         .await
@@ -13025,7 +14910,10 @@ impl ConnFulfiller {
 /// role-gated target consults the caller's capped role. Returns the unwrapped reply
 /// bytes on a `STATUS_DONE`/`STATUS_YIELDED` envelope (`Some`, possibly empty
 /// for a `()` return) or `None` on any failure (no route / send error / timeout
-/// / non-DONE status). Shared by [`ConnFulfiller`] (transport) + [`Fulfiller`]
+/// / non-DONE status). With `keep_forbidden`, a refusal by the target's role
+/// gate instead comes back as the 5-byte `STATUS_FORBIDDEN` envelope — the
+/// shape the libp2p invoke path hands a client — so a relay acting for a
+/// principal can tell "permission denied" from a failure. Shared by [`ConnFulfiller`] (transport) + [`Fulfiller`]
 /// (actor-mode `EFFECT_ASK_DISPATCH`).
 ///
 /// Raft targets get one extra move: when the target maps to a
@@ -13042,6 +14930,7 @@ async fn route_invoke(
     caller: crate::actors::Caller,
     space_role: Option<u8>,
     actor_local_role: Option<u8>,
+    keep_forbidden: bool,
     rest: &[u8],
 ) -> Option<Vec<u8>> {
     if rest.len() < 4 {
@@ -13140,6 +15029,14 @@ async fn route_invoke(
                     None => None,
                 };
             }
+            if keep_forbidden && env.first().copied() == Some(crate::actors::run::STATUS_FORBIDDEN)
+            {
+                return Some(encode_invoke_envelope(
+                    crate::actors::run::STATUS_FORBIDDEN,
+                    &[],
+                    &[],
+                ));
+            }
             unwrap_invoke_envelope(&env)
         }
         AskOutcome::Timeout => None,
@@ -13391,6 +15288,9 @@ async fn run_conn_task(
     extension_id: ServiceId,
     invoke_routes: InvokeRoutes,
     raft_fwd: RaftFwd,
+    intra_caps: Arc<[crate::actors::IntraCap]>,
+    dispatch_as: bool,
+    agent_names: AgentNames,
 ) {
     use crate::extension::TaskOutcome;
 
@@ -13408,6 +15308,10 @@ async fn run_conn_task(
         extension_id,
         invoke_routes,
         raft_fwd,
+        intra_caps,
+        agent_names,
+        dispatch_as,
+        principal: None,
     };
     let mut result: Vec<u8> = Vec::new();
     loop {
@@ -13473,6 +15377,7 @@ fn run_transport_extension(
     shutdown: Arc<AtomicBool>,
     activity: ActivityClock,
     invoke_routes: InvokeRoutes,
+    agent_names: AgentNames,
     raft_fwd: RaftFwd,
 ) -> AgentResult {
     use crate::extension::{ExtensionKind, SharedInstance};
@@ -13547,6 +15452,10 @@ fn run_transport_extension(
     // field is public and a caller can set it to 0 directly, which would make
     // `live.get() >= 0` always true and refuse *every* connection (self-DoS).
     let max_conns = config.serves_max_conns.max(1);
+    // Gate and bound what a conn task's `ctx.ask_dispatch_as` may relay for
+    // the member who signed in; shared by every connection.
+    let intra_caps: Arc<[crate::actors::IntraCap]> = Arc::from(config.intra_caps.clone());
+    let dispatch_as = config.dispatch_as;
 
     // SAFETY: create_state pairs with drop_state (via `StateGuard` below); both
     // go through the plugin's symbol pair so the allocator matches.
@@ -13656,6 +15565,9 @@ fn run_transport_extension(
                 id,
                 invoke_routes.clone(),
                 raft_fwd.clone(),
+                intra_caps.clone(),
+                dispatch_as,
+                agent_names.clone(),
             ))
             .detach();
         }
@@ -15537,7 +17449,7 @@ mod tests {
             },
         );
         let (tx, rx) = mpsc::channel();
-        node.routes.insert(route.0, tx);
+        node.routes.insert(route.0, tx.into());
 
         node.route(Envelope {
             from: ServiceId::new(0, 8),
@@ -15829,6 +17741,9 @@ mod tests {
                 listen: Vec::new(),
                 bootstrap: Vec::new(),
                 auto_dial_mdns: false,
+                discovery_keys: Vec::new(),
+                relays: Vec::new(),
+                relay_server: false,
             },
         ));
         let remote_peer = libp2p::PeerId::random();
@@ -15874,7 +17789,7 @@ mod tests {
             .insert(ServiceId::REGISTRY.0, registry_tx);
         let unrelated = ServiceId::new(prefix, 10);
         let (unrelated_tx, unrelated_rx) = mpsc::channel();
-        node.routes.insert(unrelated.0, unrelated_tx);
+        node.routes.insert(unrelated.0, unrelated_tx.into());
 
         let envelope = Envelope {
             from: ServiceId::new(prefix, 11),
@@ -16026,6 +17941,9 @@ mod tests {
             listen: Vec::new(),
             bootstrap: Vec::new(),
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let network_b = Network::start(NetworkConfig {
             keypair: key_b,
@@ -16033,6 +17951,9 @@ mod tests {
             listen: Vec::new(),
             bootstrap: Vec::new(),
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         network_a.register_raft_handler(
             destination_replication,
@@ -16246,7 +18167,7 @@ mod tests {
         )
         .unwrap();
         let (local_tx, local_rx) = mpsc::channel();
-        node.routes.insert(route.0, local_tx);
+        node.routes.insert(route.0, local_tx.into());
 
         node.route(Envelope {
             from: ServiceId::new(colliding_prefix, 8),
@@ -17249,6 +19170,10 @@ mod tests {
                 extension_id: ServiceId(7),
                 invoke_routes: routes.clone(),
                 raft_fwd: RaftFwd::default(),
+                intra_caps: Arc::default(),
+                agent_names: AgentNames::default(),
+                dispatch_as: false,
+                principal: None,
             };
             f.fulfill(&dispatch_effect(target, b"payload-x")).await
         });
@@ -17270,6 +19195,10 @@ mod tests {
                 extension_id: ServiceId(7),
                 invoke_routes: routes.clone(),
                 raft_fwd: RaftFwd::default(),
+                intra_caps: Arc::default(),
+                agent_names: AgentNames::default(),
+                dispatch_as: false,
+                principal: None,
             };
             f.fulfill(&dispatch_effect(target, b"x")).await
         });
@@ -17282,6 +19211,179 @@ mod tests {
         stub.join().unwrap();
     }
 
+    /// A signed [`UserAuthProof`](crate::effects::UserAuthProof) for `seed`'s
+    /// key over `session`, encoded as the `EFFECT_ASK_DISPATCH_AS` effect
+    /// to `target`.
+    #[cfg(feature = "network")]
+    fn ask_as_effect(seed: u8, session: u8, target: u32) -> (Vec<u8>, Vec<u8>) {
+        use ed25519_dalek::{Signer, SigningKey};
+        let member = SigningKey::from_bytes(&[seed; 32]);
+        let mut proof = crate::effects::UserAuthProof {
+            session_id: alloc::vec![session; 32],
+            user: b"alice".to_vec(),
+            key: member.verifying_key().to_bytes(),
+            signature: [0; 64],
+        };
+        proof.signature = member.sign(&proof.signed_message()).to_bytes();
+        let encoded = proof.encode().unwrap();
+        let mut effect = alloc::vec![crate::effects::EFFECT_ASK_DISPATCH_AS];
+        effect.extend_from_slice(&(encoded.len() as u16).to_le_bytes());
+        effect.extend_from_slice(&encoded);
+        effect.extend_from_slice(&target.to_le_bytes());
+        effect.extend_from_slice(b"msg");
+        (effect, proof.peer_id())
+    }
+
+    /// `EFFECT_ASK_DISPATCH_AS` relays the signing key's PeerId as
+    /// `Caller::Peer`, its registry role capped by the extension's
+    /// `intra_caps` — and, with no caps declared, only as
+    /// `Unauthenticated`, so an admin's proof buys a cap-less extension
+    /// nothing.
+    #[cfg(feature = "network")]
+    #[test]
+    fn conn_task_ask_as_relays_the_principal_bounded_by_intra_caps() {
+        use crate::actors::{Caller, IntraCap, SpaceRole};
+
+        let target = 54u32;
+        let (routes, registry) = spawn_stub_peer_role_registry(SpaceRole::Admin.as_u8());
+        let (tx, rx) = mpsc::channel::<InvokeRequest>();
+        routes.lock().unwrap().insert(target, tx);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let stub = {
+            let seen = seen.clone();
+            thread::spawn(move || {
+                while let Ok(req) = rx.recv() {
+                    seen.lock()
+                        .unwrap()
+                        .push((req.caller.clone(), req.space_role));
+                    // Stand in for the target's role gate: members only.
+                    let status = if req.space_role.is_some() {
+                        crate::actors::run::STATUS_DONE
+                    } else {
+                        crate::actors::run::STATUS_FORBIDDEN
+                    };
+                    req.reply
+                        .send(encode_invoke_envelope(status, &[7, 7], b"ok"));
+                }
+            })
+        };
+        let (effect, peer) = ask_as_effect(5, 7, target);
+
+        let ask = |caps: Vec<IntraCap>| {
+            async_io::block_on(async {
+                let mut f = ConnFulfiller {
+                    conn: None,
+                    cid: 0,
+                    extension_id: ServiceId(7),
+                    invoke_routes: routes.clone(),
+                    raft_fwd: RaftFwd::default(),
+                    intra_caps: Arc::from(caps),
+                    agent_names: AgentNames::default(),
+                    dispatch_as: true,
+                    principal: None,
+                };
+                f.fulfill(&effect).await
+            })
+        };
+        let capped = ask(vec![IntraCap::parse("*:member").unwrap()]);
+        assert_eq!(
+            crate::effects::bytestream::decode_resp_bytes(&capped).as_deref(),
+            Some(&b"ok"[..])
+        );
+        // The refusal surfaces as the forbidden envelope, not a failure.
+        let uncapped = ask(Vec::new());
+        assert_eq!(
+            crate::effects::bytestream::decode_resp_bytes(&uncapped),
+            Some(alloc::vec![
+                crate::actors::run::STATUS_FORBIDDEN,
+                0,
+                0,
+                0,
+                0
+            ])
+        );
+
+        assert_eq!(
+            seen.lock().unwrap().as_slice(),
+            &[
+                (Caller::Peer(peer), Some(SpaceRole::Member.as_u8())),
+                (Caller::Unauthenticated, None),
+            ],
+            "an admin principal relays at the member ceiling, and not at all without caps",
+        );
+        drop(routes);
+        stub.join().unwrap();
+        registry.join().unwrap();
+    }
+
+    /// The host, not the extension, decides who an `EFFECT_ASK_DISPATCH_AS`
+    /// acts as: nothing relays without the operator's `dispatch_as` grant,
+    /// a proof whose signature doesn't verify is refused, and a connection
+    /// stays pinned to the first member it proved.
+    #[cfg(feature = "network")]
+    #[test]
+    fn conn_task_ask_as_requires_the_grant_and_a_verified_proof() {
+        use crate::actors::IntraCap;
+
+        let target = 55u32;
+        let (routes, stub) =
+            spawn_stub_invoke_target_status(target, crate::actors::run::STATUS_DONE);
+        let fulfiller = |dispatch_as: bool| ConnFulfiller {
+            conn: None,
+            cid: 0,
+            extension_id: ServiceId(7),
+            invoke_routes: routes.clone(),
+            raft_fwd: RaftFwd::default(),
+            intra_caps: Arc::from(vec![IntraCap::parse("*:member").unwrap()]),
+            agent_names: AgentNames::default(),
+            dispatch_as,
+            principal: None,
+        };
+        let refused = alloc::vec![crate::effects::RESP_ERR];
+        let (alice, _) = ask_as_effect(5, 7, target);
+        let (bob, _) = ask_as_effect(6, 8, target);
+        let mut forged = alice.clone();
+        // Flip a signature bit: [tag][len:2][session_len:2][session:32]
+        // [user_len:2][user:5][key:32] precede it.
+        forged[1 + 2 + 2 + 32 + 2 + 5 + 32] ^= 1;
+
+        async_io::block_on(async {
+            assert_eq!(fulfiller(false).fulfill(&alice).await, refused, "no grant");
+            assert_eq!(fulfiller(true).fulfill(&forged).await, refused, "forged");
+
+            let mut f = fulfiller(true);
+            assert_eq!(
+                f.fulfill(&alice).await.first(),
+                Some(&crate::effects::RESP_OK)
+            );
+            assert_eq!(
+                f.fulfill(&alice).await.first(),
+                Some(&crate::effects::RESP_OK)
+            );
+            assert_eq!(f.fulfill(&bob).await, refused, "a second principal");
+        });
+        drop(routes);
+        stub.join().unwrap();
+    }
+
+    /// A relayed actor-local grant is capped at the `intra_caps` ceiling
+    /// for the target, and dropped where no cap reaches it.
+    #[test]
+    fn actor_local_role_is_capped_at_the_relay_ceiling() {
+        use crate::actors::IntraCap;
+        let names = AgentNames::default();
+        names
+            .write()
+            .unwrap()
+            .insert(local_id_of(60), "notes".into());
+        let member = [IntraCap::parse("notes:member").unwrap()];
+        assert_eq!(cap_actor_local_role(&names, &member, 60, Some(3)), Some(1));
+        assert_eq!(cap_actor_local_role(&names, &member, 60, Some(0)), Some(0));
+        assert_eq!(cap_actor_local_role(&names, &member, 60, None), None);
+        let other = [IntraCap::parse("ledger:*").unwrap()];
+        assert_eq!(cap_actor_local_role(&names, &other, 60, Some(3)), None);
+    }
+
     /// A target that DROPS its reply sender (the signature of a raft
     /// follower refusing a write, or a panicking PVM handler). With no
     /// raft-forward context the ask must fail FAST — the canceled
@@ -17306,6 +19408,10 @@ mod tests {
                 extension_id: ServiceId(7),
                 invoke_routes: routes.clone(),
                 raft_fwd: RaftFwd::default(),
+                intra_caps: Arc::default(),
+                agent_names: AgentNames::default(),
+                dispatch_as: false,
+                principal: None,
             };
             f.fulfill(&dispatch_effect(target, b"x")).await
         });
@@ -17342,6 +19448,10 @@ mod tests {
                 extension_id: ServiceId(7),
                 invoke_routes: routes.clone(),
                 raft_fwd: fwd,
+                intra_caps: Arc::default(),
+                agent_names: AgentNames::default(),
+                dispatch_as: false,
+                principal: None,
             };
             f.fulfill(&dispatch_effect(target, b"x")).await
         });
@@ -17360,6 +19470,10 @@ mod tests {
                 extension_id: ServiceId(7),
                 invoke_routes: routes,
                 raft_fwd: RaftFwd::default(),
+                intra_caps: Arc::default(),
+                agent_names: AgentNames::default(),
+                dispatch_as: false,
+                principal: None,
             };
             f.fulfill(&dispatch_effect(999, b"x")).await
         });
@@ -17387,6 +19501,10 @@ mod tests {
                 extension_id: ServiceId(7),
                 invoke_routes: routes.clone(),
                 raft_fwd: RaftFwd::default(),
+                intra_caps: Arc::default(),
+                agent_names: AgentNames::default(),
+                dispatch_as: false,
+                principal: None,
             };
             f.fulfill(&ask_effect(target, &payload)).await
         });
@@ -17417,6 +19535,10 @@ mod tests {
                     extension_id: ServiceId(7),
                     invoke_routes: routes,
                     raft_fwd: RaftFwd::default(),
+                    intra_caps: Arc::default(),
+                    agent_names: AgentNames::default(),
+                    dispatch_as: false,
+                    principal: None,
                 };
                 f.fulfill(&ask_effect(target, &payload)).await
             }
@@ -17446,6 +19568,10 @@ mod tests {
                 extension_id: ServiceId(7),
                 invoke_routes: routes,
                 raft_fwd: RaftFwd::default(),
+                intra_caps: Arc::default(),
+                agent_names: AgentNames::default(),
+                dispatch_as: false,
+                principal: None,
             };
             f.fulfill(&ask_effect(12345, b"x")).await
         });
@@ -17820,6 +19946,9 @@ mod tests {
             program_blobs_dir: None,
             operator_peer: None,
             operator_signer: None,
+            snapshot_handler: None,
+            metrics: None,
+            guest_logs: Arc::default(),
             #[cfg(feature = "storage")]
             sync_floor_cache: Arc::new(RwLock::new(HashMap::new())),
         };
@@ -18154,6 +20283,17 @@ mod tests {
             ..row
         };
         assert!(!node_member_authenticates_voter(&observer, prefix, &voter));
+        assert!(!node_member_authenticates_raft_member(
+            &observer, prefix, &voter
+        ));
+        let witness = crate::registry::MemberRow {
+            role: crate::registry::NODE_ROLE_WITNESS,
+            ..observer.clone()
+        };
+        assert!(!node_member_authenticates_voter(&witness, prefix, &voter));
+        assert!(node_member_authenticates_raft_member(
+            &witness, prefix, &voter
+        ));
 
         let status = crate::network::RaftStatusReply {
             present: true,
@@ -18281,6 +20421,9 @@ mod tests {
             listen: vec![listen.clone()],
             bootstrap: vec![],
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let listen_deadline = Instant::now() + Duration::from_secs(5);
         let caller_address = loop {
@@ -18296,6 +20439,9 @@ mod tests {
             listen: vec![listen],
             bootstrap: vec![caller_address],
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         voter_network.set_service(Arc::new(Leader));
         let peer_deadline = Instant::now() + Duration::from_secs(10);
@@ -18775,6 +20921,121 @@ mod tests {
         Arc::try_unwrap(network).ok().unwrap().join();
     }
 
+    #[test]
+    #[cfg(all(feature = "network", feature = "storage"))]
+    fn witness_join_must_match_the_group_production_trust_policy() {
+        use crate::actors::codec::Encode;
+        use crate::network::{NetworkService, RaftAppendResult, RaftJoinResult};
+        use crate::value::Value;
+
+        struct RefusingHandler;
+        impl crate::network::RaftRpcHandler for RefusingHandler {
+            fn append_entries(
+                &self,
+                _replication_id: &[u8; 32],
+                _from_prefix: u16,
+                term: u64,
+                prev_log_index: u64,
+                _prev_log_term: u64,
+                _leader_commit: u64,
+                _entries: Vec<crate::network::RaftEntry>,
+            ) -> RaftAppendResult {
+                RaftAppendResult {
+                    term,
+                    success: false,
+                    match_index: prev_log_index,
+                }
+            }
+
+            fn request_vote(
+                &self,
+                _replication_id: &[u8; 32],
+                _from_prefix: u16,
+                term: u64,
+                _last_log_index: u64,
+                _last_log_term: u64,
+            ) -> crate::network::RaftVoteResult {
+                crate::network::RaftVoteResult {
+                    term,
+                    vote_granted: false,
+                }
+            }
+
+            fn handle_join(
+                &self,
+                _replication_id: &[u8; 32],
+                _joiner_prefix: u16,
+            ) -> RaftJoinResult {
+                panic!("a policy-mismatched witness must never reach membership change");
+            }
+        }
+
+        let witness = libp2p::PeerId::random();
+        let prefix = crate::network::derive_node_prefix(&witness);
+        let member = crate::registry::MemberRow {
+            kind: crate::registry::MEMBER_KIND_NODE,
+            key: witness.to_bytes(),
+            prefix,
+            role: crate::registry::NODE_ROLE_WITNESS,
+            proof_kind: 0,
+            proof_data: Vec::new(),
+        };
+        let routes: InvokeRoutes = Arc::new(Mutex::new(HashMap::new()));
+        let (registry_tx, registry_rx) = mpsc::channel::<InvokeRequest>();
+        routes
+            .lock()
+            .unwrap()
+            .insert(ServiceId::REGISTRY.0, registry_tx);
+        let registry = thread::spawn(move || {
+            let request = registry_rx.recv().unwrap();
+            let page = crate::registry::MemberPage {
+                members: vec![member],
+                next_kind: crate::registry::MEMBER_KIND_IDENTITY,
+                next_key: Vec::new(),
+                more: false,
+            };
+            let reply = Value::Bytes(page.encode()).encode();
+            assert!(request.reply.send(encode_invoke_envelope(
+                crate::actors::run::STATUS_DONE,
+                &[],
+                &reply,
+            )));
+        });
+        let service = lifecycle_service(
+            routes,
+            Arc::new(Mutex::new(HashMap::new())),
+            Arc::new(std::sync::RwLock::new(HashMap::new())),
+        );
+        let replication_id = [0x73; 32];
+        let (sidecar_tx, _sidecar_rx) = mpsc::sync_channel(1);
+        let (quiescence_tx, _quiescence_rx) = mpsc::sync_channel(1);
+        service.v2_private_ingress_routes.write().unwrap().insert(
+            replication_id,
+            Arc::new(V2PrivateIngressRoute {
+                tx: sidecar_tx,
+                quiescence_tx,
+                barrier: V2PrivateIngressBarrier::new(),
+                production_trust_policy: Some([0x5a; 32]),
+            }),
+        );
+        let handler = Arc::new(RefusingHandler);
+        let network = Arc::new(crate::network::Network::start(
+            crate::network::NetworkConfig::default(),
+        ));
+        network.register_raft_handler(replication_id, handler.clone());
+        *service.shared_network.lock().unwrap() = Some(network.clone());
+
+        assert_eq!(
+            service.handle_raft_join(witness, &replication_id, prefix, None, handler.as_ref()),
+            RaftJoinResult::PolicyMismatch,
+            "a witness decides elections, so it answers to the group's trust policy",
+        );
+        registry.join().unwrap();
+        *service.shared_network.lock().unwrap() = None;
+        drop(service);
+        Arc::try_unwrap(network).ok().unwrap().join();
+    }
+
     #[test]
     #[cfg(all(feature = "network", feature = "storage"))]
     fn crdt_replica_authority_binds_the_full_enrolled_peer_id() {
@@ -18901,6 +21162,9 @@ mod tests {
             listen: Vec::new(),
             bootstrap: Vec::new(),
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         }));
         network.register_raft_handler(
             replication_id,
@@ -19365,6 +21629,9 @@ mod tests {
             program_blobs_dir: None,
             operator_peer: None,
             operator_signer: None,
+            snapshot_handler: None,
+            metrics: None,
+            guest_logs: Arc::default(),
             #[cfg(feature = "storage")]
             sync_floor_cache: Arc::new(RwLock::new(HashMap::new())),
         }
@@ -19469,7 +21736,7 @@ mod tests {
         let service = lifecycle_service(routes, shutdown, info);
         let peer = libp2p::PeerId::random();
 
-        for method in ["__stop", "__describe"] {
+        for method in ["__stop", "__describe", "__metrics", "__doctor"] {
             let mut payload = vec![TAG_DYNAMIC];
             payload.extend_from_slice(&Msg::new(method).encode());
             let reply = service.dispatch_invoke(Some(peer), 0, target.0, vec![], payload);
@@ -19488,6 +21755,90 @@ mod tests {
         reg.join().unwrap();
     }
 
+    /// `__logs` is DEVELOPER-gated and answers with the buffered guest
+    /// lines as JSON, filtered by agent name and resumable by cursor.
+    #[cfg(feature = "network")]
+    #[test]
+    fn logs_interceptor_gates_on_developer_and_filters() {
+        use crate::actors::codec::{Decode, Encode};
+        use crate::guest_log::LogKind;
+        use crate::network::NetworkService;
+        use crate::value::{Msg, TAG_DYNAMIC, Value};
+
+        let agent = |name: &str| AgentInfo {
+            name: Some(name.into()),
+            kind: 0,
+            serves_addr: None,
+            consistency: None,
+            network_reachable: true,
+        };
+        let info: AgentInfos = Arc::new(std::sync::RwLock::new(HashMap::from([
+            (7, agent("chat")),
+            (8, agent("ledger")),
+        ])));
+        let shutdown: AgentShutdown = Arc::new(Mutex::new(HashMap::new()));
+        let logs_request = |msg: Msg| {
+            let mut payload = vec![TAG_DYNAMIC];
+            payload.extend_from_slice(&msg.encode());
+            payload
+        };
+
+        let (routes, reg) = spawn_stub_peer_role_registry(AUTH_ROLE_READONLY);
+        let service = lifecycle_service(routes, shutdown.clone(), info.clone());
+        let reply = service.dispatch_invoke(
+            Some(libp2p::PeerId::random()),
+            0,
+            0,
+            vec![],
+            logs_request(Msg::new("__logs")),
+        );
+        assert_eq!(
+            reply.first().copied(),
+            Some(crate::actors::run::STATUS_FORBIDDEN),
+            "a read-only member must not read actor logs",
+        );
+        drop(service);
+        reg.join().unwrap();
+
+        let (routes, reg) = spawn_stub_peer_role_registry(AUTH_ROLE_DEVELOPER);
+        let service = lifecycle_service(routes, shutdown, info);
+        service
+            .guest_logs
+            .push(7, LogKind::Output, "said \"hi\"\tthere");
+        service
+            .guest_logs
+            .push(8, LogKind::Outcome, "invoke ok, 10 gas");
+        service.guest_logs.push(7, LogKind::Panic, "panic: boom");
+        let peer = libp2p::PeerId::random();
+        let read = |msg: Msg| {
+            let reply = service.dispatch_invoke(Some(peer), 0, 0, vec![], logs_request(msg));
+            match <Value as Decode>::decode(&reply) {
+                Value::Str(json) => json,
+                other => panic!("__logs should reply Value::Str, got {other:?}"),
+            }
+        };
+
+        let json = read(Msg::new("__logs").with("agent", "chat"));
+        assert!(json.starts_with("{\"next\":3,"), "logs json: {json}");
+        assert!(
+            json.contains(
+                "\"agent\":\"chat\",\"kind\":\"output\",\"text\":\"said \\\"hi\\\"\\tthere\""
+            ) && json.contains("\"kind\":\"panic\"")
+                && !json.contains("ledger"),
+            "logs json: {json}",
+        );
+        let json = read(Msg::new("__logs").with("after", 1u64).with("limit", 1u32));
+        assert!(
+            json.starts_with("{\"next\":2,") && json.contains("\"agent\":\"ledger\""),
+            "logs json: {json}",
+        );
+        let json = read(Msg::new("__logs").with("agent", "nope"));
+        assert!(json.contains("\"error\""), "logs json: {json}");
+
+        drop(service);
+        reg.join().unwrap();
+    }
+
     // ── R4: actor-local grant propagated through extension relays ──
 
     /// Spawn a mock registry on route 0 that replies `role` to every
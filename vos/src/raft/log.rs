@@ -688,9 +688,11 @@ pub fn seed_active_config(db: &Database, members: &[u16]) -> Result<bool, Commit
     if load_active_config(db)?.is_some() {
         return Ok(false);
     }
+    let barrier = crate::write_barrier::enter();
     let txn = db.begin_write()?;
     write_active_config_in_txn(&txn, Some(0), members, None)?;
     txn.commit()?;
+    drop(barrier);
     Ok(true)
 }
 
@@ -301,6 +301,7 @@ impl Storage<u16> for RedbStorage {
         let new_state = batch.state.clone();
         let new_config = batch.active_config.clone();
         let mut do_txn = || -> Result<(), CommitError> {
+            let _barrier = crate::write_barrier::enter();
             let txn = self.db.begin_write()?;
 
             // Order matches the WriteBatch contract:
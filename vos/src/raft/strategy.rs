@@ -94,6 +94,11 @@ pub struct RaftConfig {
     /// in-flight propose to commit before failing with
     /// `CommitError::Config("propose timed out")`. Defaults to 5 s.
     pub propose_timeout_ms: u64,
+    /// Members of `members` that run as log-less witnesses (registry
+    /// role `NODE_ROLE_WITNESS`). They vote and count toward quorum but
+    /// never lead and never receive payloads. Every replica of the group
+    /// must agree on this list.
+    pub witnesses: Vec<u16>,
 }
 
 impl Default for RaftConfig {
@@ -106,6 +111,7 @@ impl Default for RaftConfig {
             heartbeat_interval_ms: 50,
             replication_id: [0u8; 32],
             propose_timeout_ms: 5_000,
+            witnesses: Vec::new(),
         }
     }
 }
@@ -228,6 +234,7 @@ impl RaftCommit {
         payload: &[u8],
     ) -> Result<(), CommitError> {
         let term = self.meta.current_term;
+        let barrier = crate::write_barrier::enter();
         let txn = self.db.begin_write()?;
         // Wrap the application payload as `EntryKind::Data` so the
         // single-node and multi-node on-disk formats agree —
@@ -261,6 +268,7 @@ impl RaftCommit {
             }
         }
         txn.commit()?;
+        drop(barrier);
         Ok(())
     }
 }
@@ -297,6 +305,7 @@ impl CommitStrategy for RaftCommit {
             // is the exact point our `last_applied` should reach.
             self.meta = RaftMeta::load(&self.db)?;
             let new_last_applied = self.meta.commit_index;
+            let barrier = crate::write_barrier::enter();
             let txn = self.db.begin_write()?;
             {
                 if state_changed && let Some(state) = state {
@@ -328,6 +337,7 @@ impl CommitStrategy for RaftCommit {
                 self.meta.write_host_fields_in_txn(&txn)?;
             }
             txn.commit()?;
+            drop(barrier);
             if state_changed && let Some(state) = state {
                 self.last_state = state.to_vec();
             }
@@ -405,6 +415,7 @@ impl CommitStrategy for RaftCommit {
                 // worker-owned scalars.
                 self.meta = RaftMeta::load(&self.db)?;
                 self.meta.last_applied = self.meta.last_applied.max(idx);
+                let barrier = crate::write_barrier::enter();
                 let txn = self.db.begin_write()?;
                 {
                     if let Some(state) = state_write {
@@ -427,6 +438,7 @@ impl CommitStrategy for RaftCommit {
                 }
                 self.meta.write_host_fields_in_txn(&txn)?;
                 txn.commit()?;
+                drop(barrier);
             }
         }
         if let Some(state) = state_write {
@@ -449,6 +461,7 @@ impl CommitStrategy for RaftCommit {
         // rows the replayed history no longer produces must not linger.
         self.meta = RaftMeta::load(&self.db)?;
         let new_last_applied = self.meta.commit_index;
+        let barrier = crate::write_barrier::enter();
         let txn = self.db.begin_write()?;
         {
             let mut state_table = txn.open_table(STATE_TABLE)?;
@@ -460,6 +473,7 @@ impl CommitStrategy for RaftCommit {
             self.meta.write_host_fields_in_txn(&txn)?;
         }
         txn.commit()?;
+        drop(barrier);
         self.last_state = state.to_vec();
         Ok(CommitReceipt {
             node_appended: false,
@@ -625,6 +639,7 @@ mod tests {
             heartbeat_interval_ms: 500,
             replication_id: [0xC0; 32],
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         }
     }
 
@@ -699,6 +714,7 @@ mod tests {
                 replication_id: cfg.replication_id,
                 election_timeout_ms: cfg.election_timeout_ms,
                 heartbeat_interval_ms: cfg.heartbeat_interval_ms,
+                witnesses: cfg.witnesses.clone(),
             },
             None, // no network — single-node self-quorum doesn't need one
             Some(apply_tx),
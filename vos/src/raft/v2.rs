@@ -230,10 +230,12 @@ impl RaftAccumulateLogV2 {
         self.cfg.propose_timeout_ms
     }
 
-    /// Return the complete steady-state voter set only while this replica is
-    /// the current leader. Private ingress is staged outside the Raft log, so
-    /// callers must refuse a joint configuration rather than accidentally
-    /// omitting either half of its quorum.
+    /// Return the complete steady-state data-voter set only while this
+    /// replica is the current leader. Private ingress is staged outside the
+    /// Raft log, so callers must refuse a joint configuration rather than
+    /// accidentally omitting either half of its quorum. Log-less witnesses
+    /// are left out: they never hold payloads or advance `last_applied`, so
+    /// nothing staged on or awaited from a replica can involve them.
     pub(crate) fn steady_leader_voters(&self) -> Option<(u16, Vec<u16>)> {
         match &self.role {
             // The single-node strategy deliberately stores an empty static
@@ -249,6 +251,7 @@ impl RaftAccumulateLogV2 {
                     return None;
                 }
                 let mut members = snapshot.members;
+                members.retain(|member| !snapshot.witnesses.contains(member));
                 members.sort_unstable();
                 members.dedup();
                 members
@@ -392,6 +395,7 @@ impl RaftAccumulateLogV2 {
         let decoded = Self::decode_payload(payload)?;
         let cache = self.log.cache_snapshot();
         let result: Result<CommittedAccumulateEntryV2, CommitError> = (|| {
+            let barrier = crate::write_barrier::enter();
             let transaction = self.db.begin_write()?;
             let kind = vos_raft::EntryKind::Data {
                 payload: payload.to_vec(),
@@ -403,6 +407,7 @@ impl RaftAccumulateLogV2 {
             self.meta.commit_index = index;
             self.meta.write_in_txn(&transaction)?;
             transaction.commit()?;
+            drop(barrier);
             Ok(CommittedAccumulateEntryV2 {
                 index,
                 request: decoded.request,
@@ -629,11 +634,13 @@ impl CommittedAccumulateLogV2 for RaftAccumulateLogV2 {
                 "raft v2 applied service image exceeds the committed snapshot wire limits".into(),
             )
         })?;
+        let barrier = crate::write_barrier::enter();
         let transaction = self.db.begin_write()?;
         super::redb_storage::write_applied_state_v2_in_txn(&transaction, index, &snapshot)?;
         self.meta.last_applied = index;
         self.meta.write_host_fields_in_txn(&transaction)?;
         transaction.commit()?;
+        drop(barrier);
         Ok(())
     }
 }
@@ -975,6 +982,7 @@ mod tests {
             heartbeat_interval_ms: 5,
             replication_id: [0xA1; 32],
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         };
         let (apply_tx, apply_rx) = std_mpsc::channel::<u64>();
         let worker = RaftWorker::spawn(
@@ -985,6 +993,7 @@ mod tests {
                 replication_id: cfg.replication_id,
                 election_timeout_ms: cfg.election_timeout_ms,
                 heartbeat_interval_ms: cfg.heartbeat_interval_ms,
+                witnesses: cfg.witnesses.clone(),
             },
             None,
             Some(apply_tx),
@@ -1026,4 +1035,125 @@ mod tests {
         drop(log);
         std::fs::remove_dir_all(directory).unwrap();
     }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn steady_leader_voters_leave_out_log_less_witnesses() {
+        use super::super::worker::{RaftWorker, Role, WorkerConfig};
+        use crate::network::{Network, NetworkConfig, derive_node_prefix};
+
+        let key_data = libp2p::identity::Keypair::generate_ed25519();
+        let data = derive_node_prefix(&libp2p::PeerId::from(key_data.public()));
+        let (key_witness, witness) = loop {
+            let key = libp2p::identity::Keypair::generate_ed25519();
+            let prefix = derive_node_prefix(&libp2p::PeerId::from(key.public()));
+            if prefix != data {
+                break (key, prefix);
+            }
+        };
+        let listen: libp2p::Multiaddr = "/ip4/127.0.0.1/tcp/0".parse().unwrap();
+        let net_data = Arc::new(Network::start(NetworkConfig {
+            keypair: key_data,
+            local_prefix: data,
+            listen: vec![listen.clone()],
+            bootstrap: vec![],
+            auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
+        }));
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let address = loop {
+            if let Some(address) = net_data.listen_addrs().into_iter().next() {
+                break address.with(libp2p::multiaddr::Protocol::P2p(net_data.peer_id()));
+            }
+            assert!(Instant::now() < deadline, "data voter did not bind");
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        let net_witness = Arc::new(Network::start(NetworkConfig {
+            keypair: key_witness,
+            local_prefix: witness,
+            listen: vec![listen],
+            bootstrap: vec![address],
+            auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
+        }));
+        while net_data.peer_for_prefix(witness).is_none()
+            || net_witness.peer_for_prefix(data).is_none()
+        {
+            assert!(
+                Instant::now() < deadline,
+                "Hello handshake did not complete"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let (path, directory) = temp_path();
+        let replication_id = [0xA7; 32];
+        let cfg = RaftConfig {
+            me: data,
+            members: vec![data, witness],
+            voter_peer_ids: Vec::new(),
+            election_timeout_ms: (30, 60),
+            heartbeat_interval_ms: 10,
+            replication_id,
+            propose_timeout_ms: 2_000,
+            witnesses: vec![witness],
+        };
+        let worker_config = |me| WorkerConfig {
+            me,
+            members: cfg.members.clone(),
+            replication_id,
+            election_timeout_ms: cfg.election_timeout_ms,
+            heartbeat_interval_ms: cfg.heartbeat_interval_ms,
+            witnesses: cfg.witnesses.clone(),
+        };
+        let db = Arc::new(Database::create(&path).unwrap());
+        let (apply_tx, apply_rx) = std_mpsc::channel::<u64>();
+        let data_worker = RaftWorker::spawn(
+            db.clone(),
+            worker_config(data),
+            Some(net_data.clone()),
+            Some(apply_tx),
+        );
+        let witness_worker = RaftWorker::spawn(
+            Arc::new(Database::create(directory.join("witness.redb")).unwrap()),
+            worker_config(witness),
+            Some(net_witness.clone()),
+            None,
+        );
+        net_data.register_raft_handler(replication_id, Arc::new(data_worker.handler()));
+        net_witness.register_raft_handler(replication_id, Arc::new(witness_worker.handler()));
+        for network in [&net_data, &net_witness] {
+            assert!(network.bind_raft_voter_peer(replication_id, data, net_data.peer_id()));
+            assert!(network.bind_raft_voter_peer(replication_id, witness, net_witness.peer_id()));
+        }
+
+        let handle = data_worker.handler();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while handle
+            .snapshot()
+            .is_none_or(|snapshot| snapshot.role != Role::Leader)
+        {
+            assert!(
+                Instant::now() < deadline,
+                "the data voter did not win the witness's vote"
+            );
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let log = RaftAccumulateLogV2::from_worker(db, cfg, data_worker, apply_rx).unwrap();
+        // The witness voted the leader in, yet it never applies anything:
+        // waiting on it (upgrade confirmation, private-ingress staging)
+        // would stall until timeout.
+        assert_eq!(log.steady_leader_voters(), Some((data, vec![data])));
+
+        drop(log);
+        drop(witness_worker);
+        for network in [net_data, net_witness] {
+            Arc::try_unwrap(network).ok().unwrap().join();
+        }
+        std::fs::remove_dir_all(directory).unwrap();
+    }
 }
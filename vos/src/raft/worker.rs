@@ -32,7 +32,7 @@ use redb::Database;
 use crate::commit::CommitError;
 use crate::network::{
     Network, RaftAppendResult, RaftEntry, RaftEntryKind, RaftInstallSnapshotResult, RaftJoinResult,
-    RaftRole, RaftRpcHandler, RaftStatusReply, RaftVoteResult,
+    RaftRemoveResult, RaftRole, RaftRpcHandler, RaftStatusReply, RaftVoteResult,
 };
 
 use super::RaftMeta;
@@ -76,6 +76,9 @@ pub struct WorkerConfig {
     pub election_timeout_ms: (u64, u64),
     /// Leader heartbeat interval in milliseconds.
     pub heartbeat_interval_ms: u64,
+    /// Members that run as log-less witnesses — see
+    /// [`vos_raft::Config::witnesses`]. Empty for an all-data group.
+    pub witnesses: Vec<u16>,
 }
 
 impl WorkerConfig {
@@ -83,6 +86,7 @@ impl WorkerConfig {
         let mut c = RaftCfg::new(self.me, self.members, self.replication_id);
         c.election_timeout_ms = self.election_timeout_ms;
         c.heartbeat_interval_ms = self.heartbeat_interval_ms;
+        c.witnesses = self.witnesses;
         // Pre-vote disabled until vos's libp2p frame layer
         // routes `PreVoteReq` / `PreVoteResp`. Without that
         // wire support, the worker would stay in PreCandidate
@@ -214,6 +218,9 @@ pub struct WorkerSnapshot {
     /// `None` between elections. Followers use it to redirect
     /// misaddressed client / join requests.
     pub leader_hint: Option<u16>,
+    /// Configured log-less witnesses. They appear in `members` like
+    /// any voter.
+    pub witnesses: Vec<u16>,
 }
 
 impl From<vos_raft::WorkerSnapshot<u16>> for WorkerSnapshot {
@@ -229,6 +236,7 @@ impl From<vos_raft::WorkerSnapshot<u16>> for WorkerSnapshot {
             joint_old: s.joint_old,
             active_config_index: s.active_config_index,
             leader_hint: s.leader_hint,
+            witnesses: s.witnesses,
         }
     }
 }
@@ -553,6 +561,50 @@ impl RaftRpcHandler for WorkerHandle {
         }
     }
 
+    fn handle_remove(&self, _replication_id: &[u8; 32], prefix: u16) -> RaftRemoveResult {
+        let snap = match block_on(self.inner.snapshot()) {
+            Some(s) => s,
+            None => return RaftRemoveResult::NotLeader { leader_hint: None },
+        };
+        if snap.role != vos_raft::Role::Leader {
+            return RaftRemoveResult::NotLeader {
+                leader_hint: snap.leader_hint,
+            };
+        }
+        // Chaining a second change onto an unfinished joint phase would
+        // compute the new set from a configuration that may still roll
+        // back; wait for the in-flight change to finalize.
+        if snap.joint_old.is_some() {
+            return RaftRemoveResult::Busy;
+        }
+        if !snap.members.contains(&prefix) {
+            return RaftRemoveResult::NotMember;
+        }
+        let new_members: Vec<u16> = snap
+            .members
+            .iter()
+            .copied()
+            .filter(|member| *member != prefix)
+            .collect();
+        // Witnesses vote but hold no log, so a witness-only group could
+        // never serve or rebuild state again.
+        if !new_members
+            .iter()
+            .any(|member| !snap.witnesses.contains(member))
+        {
+            return RaftRemoveResult::Unsafe(
+                "removal would leave no log-bearing voter in the group".into(),
+            );
+        }
+        match block_on(self.inner.change_membership(new_members)) {
+            Ok(joint_index) => RaftRemoveResult::Accepted { joint_index },
+            Err(vos_raft::ChangeMembershipError::NotLeader) => RaftRemoveResult::NotLeader {
+                leader_hint: snap.leader_hint,
+            },
+            Err(_) => RaftRemoveResult::Busy,
+        }
+    }
+
     fn install_snapshot(
         &self,
         _replication_id: &[u8; 32],
@@ -662,6 +714,7 @@ mod tests {
             // RPCs aren't racing the election timer.
             election_timeout_ms: (5_000, 10_000),
             heartbeat_interval_ms: 500,
+            witnesses: Vec::new(),
         }
     }
 
@@ -936,6 +989,7 @@ mod tests {
             replication_id: [0xC0; 32],
             election_timeout_ms: (10, 30),
             heartbeat_interval_ms: 500,
+            witnesses: Vec::new(),
         };
         let worker = RaftWorker::spawn(db, cfg, None, None);
         let h = worker.handler();
@@ -958,6 +1012,7 @@ mod tests {
             replication_id: [0xC0; 32],
             election_timeout_ms: (50, 100),
             heartbeat_interval_ms: 25,
+            witnesses: Vec::new(),
         };
         let worker = RaftWorker::spawn(db, cfg, None, None);
         let h = worker.handler();
@@ -1100,6 +1155,7 @@ mod tests {
             replication_id: [0xC0; 32],
             election_timeout_ms: (10, 30),
             heartbeat_interval_ms: 500,
+            witnesses: Vec::new(),
         };
         let worker = RaftWorker::spawn(db.clone(), cfg, None, None);
         let h = worker.handler();
@@ -103,6 +103,25 @@ pub struct AgentPage {
     pub more: bool,
 }
 
+/// Where an imported agent's state came from. Written once, by
+/// [`RegistryRef::record_provenance`], after `space agent-import` installs
+/// an exported archive under a fresh `replication_id`; keyed by that id so
+/// the record outlives an uninstall the same way the id's tombstone does.
+#[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Clone, Debug, PartialEq, Eq)]
+#[rkyv(crate = rkyv)]
+pub struct ProvenanceRow {
+    pub instance_name: String,
+    /// The fresh id the import installed under.
+    pub replication_id: [u8; 32],
+    pub source_space: [u8; 32],
+    pub source_instance: String,
+    pub source_replication_id: [u8; 32],
+    /// BLAKE2b-256 of the archive's signed manifest.
+    pub archive_digest: [u8; 32],
+    /// PeerId bytes of the node key that signed the archive.
+    pub exporter: Vec<u8>,
+}
+
 /// One page of [`RegistryRef::agent_names`] — the names-only projection of
 /// [`AgentPage`], for callers (e.g. the gateway rendering `/__schema`) that
 /// want the instance-name list without the `AgentRow` rkyv decode. Same
@@ -207,6 +226,10 @@ pub const MEMBER_KIND_IDENTITY: u8 = 1;
 /// Node role discriminant (only meaningful when `kind = Node`).
 pub const NODE_ROLE_VOTER: u8 = 0;
 pub const NODE_ROLE_OBSERVER: u8 = 1;
+/// Raft voter that stores only term, vote and entry ids — never
+/// payloads or snapshots — and never leads. Breaks ties in
+/// two-node groups (see `vos_raft::Config::witnesses`).
+pub const NODE_ROLE_WITNESS: u8 = 2;
 
 /// Identity proof-kind discriminant (only meaningful when
 /// `kind = Identity`).
@@ -245,6 +268,18 @@ pub const AUTH_ROLE_READONLY: u8 = 1;
 pub const AUTH_ROLE_DEVELOPER: u8 = 2;
 pub const AUTH_ROLE_ADMIN: u8 = 3;
 
+/// Display name of an `AUTH_ROLE_*` byte, as `vosx space role` spells it
+/// — used for audit-row details.
+pub fn auth_role_name(role: u8) -> &'static str {
+    match role {
+        AUTH_ROLE_NONE => "none",
+        AUTH_ROLE_READONLY => "read",
+        AUTH_ROLE_DEVELOPER => "developer",
+        AUTH_ROLE_ADMIN => "admin",
+        _ => "?",
+    }
+}
+
 /// Canonical invite evidence signed by the granting administrator.
 ///
 /// The optional input exists only to reconstruct and reject historical
@@ -430,6 +465,176 @@ pub struct InvitePage {
     pub next: Vec<u8>,
 }
 
+// ── Audit log ─────────────────────────────────────────────────────
+//
+// Every privileged mutation the registry (or the space-authority)
+// accepts appends one row to an append-only, hash-chained log: who
+// signed it, which handler, what it targeted, the exact bytes the
+// signature covers and the signature itself. Each row commits to its
+// predecessor's hash, so editing or dropping a row anywhere breaks every
+// later link — a reader that re-walks the chain from seq 0 with
+// [`verify_audit_chain`] detects it, and [`audit_op_matches`] plus an
+// ed25519 check over `op` proves each row was really authorized.
+//
+// `seq` is acceptance order on the replica that served the log: the
+// registry is a CRDT, so two replicas may chain the same rows in a
+// different order and their heads differ. A row's `at_ms`, `op` and
+// `signature` are replica-independent; `at_ms` is the consensus
+// admission time (see `Context::admitted_at_ms`), never a local clock.
+
+/// Domain tag for [`audit_row_hash`].
+pub const AUDIT_DOMAIN: &[u8] = b"vos-registry-audit/v2";
+
+/// One accepted privileged mutation.
+#[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Clone, Debug, PartialEq, Eq)]
+#[rkyv(crate = rkyv)]
+pub struct AuditRow {
+    /// Position in this replica's chain, from 0.
+    pub seq: u64,
+    /// Consensus admission time of the mutation (Unix-epoch ms); `None`
+    /// when the host recorded none.
+    pub at_ms: Option<u64>,
+    /// PeerId bytes of the key that signed the mutation.
+    pub actor: Vec<u8>,
+    /// Handler name, e.g. `grant_role`.
+    pub action: String,
+    /// What the mutation acted on: a PeerId for role, member and invite
+    /// redemption ops, a token key for `revoke_invite`, a UTF-8 name for
+    /// catalog ops.
+    pub target: Vec<u8>,
+    /// Handler-specific summary, e.g. `role=2 epoch=4`.
+    pub detail: String,
+    /// The exact message `signature` covers: [`canonical_op_bytes`] for
+    /// registry ops, the v2 wire encoding for space-authority mutations.
+    pub op: Vec<u8>,
+    /// The signature that authorized the mutation.
+    pub signature: Vec<u8>,
+    /// `hash` of the previous row; zero for seq 0.
+    pub prev: [u8; 32],
+    /// [`audit_row_hash`] over every field above.
+    pub hash: [u8; 32],
+}
+
+/// One page of [`RegistryRef::audit`], in seq order. Continue from the
+/// last row's `seq + 1` while `more` is set.
+#[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Clone, Debug, PartialEq, Eq)]
+#[rkyv(crate = rkyv)]
+pub struct AuditPage {
+    pub rows: Vec<AuditRow>,
+    pub more: bool,
+}
+
+/// The chained hash of an audit row:
+/// `blake2b(domain, prev || u64(seq) || u8(has_at) || u64(at_ms) ||
+/// (u32(len) || field)*)` over `actor, action, target, detail, op,
+/// signature`. `row.hash` itself is not an input.
+pub fn audit_row_hash(row: &AuditRow) -> [u8; 32] {
+    let mut framed = Vec::new();
+    framed.extend_from_slice(&row.prev);
+    framed.extend_from_slice(&row.seq.to_le_bytes());
+    framed.push(row.at_ms.is_some() as u8);
+    framed.extend_from_slice(&row.at_ms.unwrap_or(0).to_le_bytes());
+    for field in [
+        &row.actor[..],
+        row.action.as_bytes(),
+        &row.target,
+        row.detail.as_bytes(),
+        &row.op,
+        &row.signature,
+    ] {
+        framed.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        framed.extend_from_slice(field);
+    }
+    crate::crypto::blake2b_hash(AUDIT_DOMAIN, &[&framed])
+}
+
+impl AuditRow {
+    /// An unchained row for one accepted mutation; [`Self::chained_after`]
+    /// fills in `seq`, `prev` and `hash`.
+    pub fn new(
+        at_ms: Option<u64>,
+        actor: Vec<u8>,
+        action: String,
+        target: Vec<u8>,
+        detail: String,
+        op: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Self {
+        Self {
+            seq: 0,
+            at_ms,
+            actor,
+            action,
+            target,
+            detail,
+            op,
+            signature,
+            prev: [0; 32],
+            hash: [0; 32],
+        }
+    }
+
+    /// Link this row after `tail` (the chain's current last row, `None`
+    /// for an empty chain).
+    pub fn chained_after(mut self, tail: Option<&AuditRow>) -> Self {
+        self.seq = tail.map_or(0, |tail| tail.seq + 1);
+        self.prev = tail.map(|tail| tail.hash).unwrap_or_default();
+        self.hash = audit_row_hash(&self);
+        self
+    }
+}
+
+/// Re-walk a whole chain from seq 0. Returns the first seq whose
+/// position, `prev` link or hash doesn't hold.
+pub fn verify_audit_chain(rows: &[AuditRow]) -> Result<(), u64> {
+    let mut prev = [0u8; 32];
+    for (seq, row) in rows.iter().enumerate() {
+        let seq = seq as u64;
+        if row.seq != seq || row.prev != prev || row.hash != audit_row_hash(row) {
+            return Err(seq);
+        }
+        prev = row.hash;
+    }
+    Ok(())
+}
+
+/// The op name a [`canonical_op_bytes`] encoding was built for, or `None`
+/// when `op` isn't one.
+pub fn canonical_op_name(op: &[u8]) -> Option<&str> {
+    let rest = op.strip_prefix(REGISTRY_OP_DOMAIN)?;
+    let len = u16::from_le_bytes(rest.get(..2)?.try_into().ok()?) as usize;
+    core::str::from_utf8(rest.get(2..2 + len)?).ok()
+}
+
+/// Does a row's signed `op` authorize its `action` on its `target`?
+///
+/// A registry row must carry the canonical op of the same name — except
+/// invite redemption, which is authorized by the admin's `invite`
+/// evidence. A space-authority row carries the v2 wire mutation, which
+/// must decode to the logged kind. The signature over `op` is checked
+/// separately (ed25519 lives outside `vos`).
+pub fn audit_op_matches(row: &AuditRow) -> bool {
+    use crate::v2::{RoleAuthorityInviteRevocationV2, RoleAuthorityMutationV2, V2Wire};
+
+    if let Some(name) = canonical_op_name(&row.op) {
+        return match row.action.as_str() {
+            "redeem_invite" => name == "invite",
+            action => name == action,
+        };
+    }
+    match (
+        row.action.as_str(),
+        RoleAuthorityMutationV2::decode(&row.op),
+    ) {
+        ("grant_role", Ok(RoleAuthorityMutationV2::Grant { .. }))
+        | ("revoke_role", Ok(RoleAuthorityMutationV2::Revoke { .. })) => return true,
+        _ => {}
+    }
+    row.action == "revoke_invite"
+        && RoleAuthorityInviteRevocationV2::decode(&row.op)
+            .is_ok_and(|revocation| revocation.token_pub[..] == row.target[..])
+}
+
 // ── Result codes ─────────────────────────────────────────────────
 
 /// Status returned by a mutation handler. `Ok` is always `0`.
@@ -455,8 +660,8 @@ pub enum Status {
     /// handler. Distinct from `NotFound` so clients can surface
     /// "permission denied" specifically.
     Forbidden = 7,
-    /// Hyperspace `register_remote`: the supplied `host_prefix` doesn't
-    /// match any registered node prefix.
+    /// Hyperspace `register_remote` / `add_node`: the supplied prefix
+    /// doesn't fit the 16-bit node-prefix space.
     BadPrefix = 8,
     /// Monotone-locality guard: `install` refused to (re)create an
     /// instance at a *wider* consistency tier than the narrowest one the
@@ -475,6 +680,10 @@ pub enum Status {
     /// Canonical role-authority cutover requires one fully synchronized
     /// registry replica and no legacy role evidence that could remain live.
     AuthorityCutoverNotQuiescent = 13,
+    /// `add_node` refused because the node prefix is already enrolled
+    /// for a different peer. Two nodes sharing a prefix would make
+    /// every `ServiceId` on it ambiguous to route.
+    PrefixCollision = 14,
 }
 
 impl Status {
@@ -496,6 +705,7 @@ impl Status {
             11 => Some(Self::StaleUpgrade),
             12 => Some(Self::CrdtOptInRequired),
             13 => Some(Self::AuthorityCutoverNotQuiescent),
+            14 => Some(Self::PrefixCollision),
             _ => None,
         }
     }
@@ -518,6 +728,7 @@ impl core::fmt::Display for Status {
             Status::StaleUpgrade => "stale upgrade",
             Status::CrdtOptInRequired => "CRDT consistency requires #[actor(crdt)]",
             Status::AuthorityCutoverNotQuiescent => "role-authority cutover is not quiescent",
+            Status::PrefixCollision => "node prefix already enrolled for another peer",
         })
     }
 }
@@ -1387,6 +1598,53 @@ impl RegistryRef {
         )
     }
 
+    /// Record where `instance_name`'s state was imported from. Write-once
+    /// per installed `replication_id`: a second record for the same
+    /// installation is `Status::ReplicationIdReused`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_provenance<I: Invoker>(
+        &self,
+        inv: &mut I,
+        instance_name: String,
+        source_space: Vec<u8>,
+        source_instance: String,
+        source_replication_id: Vec<u8>,
+        archive_digest: Vec<u8>,
+        exporter: Vec<u8>,
+        auth: Vec<u8>,
+    ) -> Result<Status, ClientError> {
+        decode_rkyv(
+            self.call(
+                inv,
+                Msg::new("record_provenance")
+                    .with("instance_name", instance_name)
+                    .with("source_space", source_space)
+                    .with("source_instance", source_instance)
+                    .with("source_replication_id", source_replication_id)
+                    .with("archive_digest", archive_digest)
+                    .with("exporter", exporter)
+                    .with("auth", auth),
+            )
+            .await?,
+        )
+    }
+
+    /// The import provenance of the installed `instance_name`, or `None`
+    /// when it was installed directly rather than imported.
+    pub async fn provenance<I: Invoker>(
+        &self,
+        inv: &mut I,
+        instance_name: String,
+    ) -> Result<Option<ProvenanceRow>, ClientError> {
+        decode_opt(
+            self.call(
+                inv,
+                Msg::new("provenance").with("instance_name", instance_name),
+            )
+            .await?,
+        )
+    }
+
     pub async fn unpublish<I: Invoker>(
         &self,
         inv: &mut I,
@@ -1645,6 +1903,30 @@ impl RegistryRef {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub async fn rotate_identity<I: Invoker>(
+        &self,
+        inv: &mut I,
+        old_peer: Vec<u8>,
+        new_peer: Vec<u8>,
+        epoch: u64,
+        new_sig: Vec<u8>,
+        auth: Vec<u8>,
+    ) -> Result<Status, ClientError> {
+        decode_rkyv(
+            self.call(
+                inv,
+                Msg::new("rotate_identity")
+                    .with("old_peer", old_peer)
+                    .with("new_peer", new_peer)
+                    .with("epoch", epoch)
+                    .with("new_sig", new_sig)
+                    .with("auth", auth),
+            )
+            .await?,
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn grant_actor_role<I: Invoker>(
         &self,
@@ -1792,6 +2074,41 @@ impl RegistryRef {
             .await?,
         )
     }
+
+    // ── Audit log ─────────────────────────────────────────────────
+
+    /// One page of the admin audit log, starting at seq `from`.
+    /// `budget` caps the page (0 = the registry's max). Prefer
+    /// [`audit_all`](Self::audit_all) unless paging by hand.
+    pub async fn audit<I: Invoker>(
+        &self,
+        inv: &mut I,
+        from: u64,
+        budget: u32,
+    ) -> Result<AuditPage, ClientError> {
+        decode_rkyv(
+            self.call(
+                inv,
+                Msg::new("audit").with("from", from).with("budget", budget),
+            )
+            .await?,
+        )
+    }
+
+    /// Drain the whole audit log from seq 0 — what a reader needs to
+    /// [`verify_audit_chain`].
+    pub async fn audit_all<I: Invoker>(&self, inv: &mut I) -> Result<Vec<AuditRow>, ClientError> {
+        let mut out: Vec<AuditRow> = Vec::new();
+        loop {
+            let page = self.audit(inv, out.len() as u64, 0).await?;
+            let more = page.more;
+            out.extend(page.rows);
+            if !more {
+                break;
+            }
+        }
+        Ok(out)
+    }
 }
 
 #[cfg(test)]
@@ -1945,4 +2262,59 @@ mod tests {
         assert_eq!(ed25519_pubkey_from_peer_id(&pid), Some([42u8; 32]));
         assert!(ed25519_pubkey_from_peer_id(&[0u8; 10]).is_none());
     }
+
+    #[test]
+    fn audit_chain_detects_edits_drops_and_reorders() {
+        let mut rows: Vec<AuditRow> = Vec::new();
+        for (action, target) in [
+            ("grant_role", b"a"),
+            ("publish", b"b"),
+            ("revoke_role", b"c"),
+        ] {
+            let row = AuditRow::new(
+                Some(1_700_000_000_000 + rows.len() as u64),
+                alloc::vec![7u8; 38],
+                action.into(),
+                target.to_vec(),
+                String::new(),
+                canonical_op_bytes(action, &[target]),
+                alloc::vec![9u8; 64],
+            );
+            rows.push(row.chained_after(rows.last()));
+        }
+        assert_eq!(verify_audit_chain(&rows), Ok(()));
+        assert_eq!(verify_audit_chain(&[]), Ok(()));
+        assert!(rows.iter().all(audit_op_matches));
+
+        let mut edited = rows.clone();
+        edited[1].target = b"z".to_vec();
+        assert_eq!(verify_audit_chain(&edited), Err(1));
+
+        // The admission time is covered too.
+        let mut retimed = rows.clone();
+        retimed[2].at_ms = None;
+        assert_eq!(verify_audit_chain(&retimed), Err(2));
+
+        // Re-hashing the edited row doesn't help: the next link breaks.
+        let tail = edited[0].clone();
+        edited[1] = edited[1].clone().chained_after(Some(&tail));
+        assert_eq!(verify_audit_chain(&edited), Err(2));
+
+        let mut dropped = rows.clone();
+        dropped.remove(0);
+        assert_eq!(verify_audit_chain(&dropped), Err(0));
+
+        let mut swapped = rows.clone();
+        swapped.swap(1, 2);
+        assert_eq!(verify_audit_chain(&swapped), Err(1));
+
+        // A row whose signed op is for another handler doesn't match.
+        let mut relabeled = rows[0].clone();
+        relabeled.action = "revoke_role".into();
+        assert!(!audit_op_matches(&relabeled));
+        let mut redeemed = rows[0].clone();
+        redeemed.action = "redeem_invite".into();
+        redeemed.op = invite_signed_bytes(&[1; 32], AUTH_ROLE_READONLY, 9, &[2; 32], None);
+        assert!(audit_op_matches(&redeemed));
+    }
 }
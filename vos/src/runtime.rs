@@ -783,6 +783,12 @@ pub struct VosRuntime<D: DataLayer = MemoryDataLayer> {
     ///
     /// [`take_dispatch_delta`]: VosRuntime::take_dispatch_delta
     dispatch_effect_bearing: HashMap<u32, bool>,
+    /// Gas burned by every refine slice (any service, children
+    /// included) since the last [`take_gas_burned`]. Read by the node
+    /// after each dispatch for its per-agent gas metric.
+    ///
+    /// [`take_gas_burned`]: VosRuntime::take_gas_burned
+    gas_burned: u64,
 }
 
 impl VosRuntime<MemoryDataLayer> {
@@ -822,9 +828,17 @@ impl<D: DataLayer> VosRuntime<D> {
             dispatch_anchor: HashMap::new(),
             dispatch_writes: HashMap::new(),
             dispatch_effect_bearing: HashMap::new(),
+            gas_burned: 0,
         }
     }
 
+    /// Take the gas burned since the previous take, summed over every
+    /// refine slice this runtime ran. An out-of-gas slice counts its
+    /// whole budget.
+    pub fn take_gas_burned(&mut self) -> u64 {
+        std::mem::take(&mut self.gas_burned)
+    }
+
     /// Take the `(kind, anchor)` of the first work-result applied for
     /// `svc_id` since the previous take — the anchor of the state the
     /// dispatch ran against. `None` when no anchored work-result was
@@ -1216,6 +1230,7 @@ impl<D: DataLayer> VosRuntime<D> {
                 // Delimit this dispatch's journal contributions so a trap
                 // can drop them whole (A2 discard-on-panic).
                 let dispatch_mark = journal.mark();
+                let gas_before = kernel.active_gas();
 
                 let (halted, continuation) = match run_refine_kernel(
                     &mut kernel,
@@ -1240,6 +1255,9 @@ impl<D: DataLayer> VosRuntime<D> {
                     } => (Some(output), continuation),
                     RefineKernelExit::Failed => (None, None),
                 };
+                self.gas_burned = self
+                    .gas_burned
+                    .saturating_add(gas_before.saturating_sub(kernel.active_gas()));
 
                 // A provable Task witness is producer-private. Returning a
                 // child PANICKED marker is not enough under CRDT/Raft: the
@@ -1671,6 +1689,7 @@ fn handle_refine_hostcall(
             let buf = kread(kernel, a0 as u32, a1 as usize);
             let _ = std::io::stderr().write_all(&buf);
             let _ = std::io::stderr().flush();
+            crate::guest_log::tee(&buf);
             (buf.len() as u64, 0)
         }
         hostcall::FETCH => {
@@ -1792,14 +1811,24 @@ fn handle_refine_hostcall(
         }
         hostcall::NOW_MS => {
             // Host wall-clock in Unix-epoch milliseconds. No args; the value is
-            // returned directly. Intentionally non-deterministic — sound only
-            // for non-replicated (`Local`) actors (the messenger reads it for
-            // MLS Lifetime validity); replicated state takes time from chronos.
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_millis() as u64)
-                .unwrap_or(0);
-            (now, 0)
+            // returned directly. Unrecorded for non-replicated (`Local`) actors
+            // (the messenger reads it for MLS Lifetime validity). A replicated
+            // top-level dispatch pins its first reading in the effect log as
+            // the admission time and every replica replays that value, so
+            // the handler never sees its own replica's clock.
+            let now = || {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0)
+            };
+            match mode {
+                crate::effect_log::EffectMode::Recording(s) if depth == 0 => (s.admit_at(now()), 0),
+                crate::effect_log::EffectMode::Replaying(replay) if depth == 0 => {
+                    (replay.admitted_at_ms().unwrap_or(error::HOST_NONE), 0)
+                }
+                _ => (now(), 0),
+            }
         }
         hostcall::PROVABLE_RECORD_INTENT => {
             if mode.has_replication_context() {
@@ -2104,6 +2133,7 @@ pub(crate) fn handle_task_hostcall(
             let buf = kread(kernel, echo7 as u32, echo8 as usize);
             let _ = std::io::stderr().write_all(&buf);
             let _ = std::io::stderr().flush();
+            crate::guest_log::tee(&buf);
         }
         hostcall::DEBUG_WRITE => {
             error!("recorded task: DEBUG_WRITE would disclose private witness bytes");
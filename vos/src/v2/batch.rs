@@ -0,0 +1,905 @@
+//! Multi-root batch transactions over independent v2 root services.
+//!
+//! Every root tree is its own JAM service, so no single Accumulate can update
+//! two of them. A [`RootBatchV2`] is a presumed-abort saga instead: each step
+//! names a prepare, commit and compensate method on one root's actor, and the
+//! coordinator drives them as ordinary direct invocations. Every phase call has
+//! an invocation id derived from the batch, step and phase, so an exact retry
+//! reattaches the guest's committed result instead of executing again.
+//!
+//! The coordinator's own progress lives in a durable [`RootBatchJournalV2`].
+//! A batch is journaled before its first prepare, each vote as its prepare
+//! returns, and the decision before the first commit or compensate call:
+//!
+//! - a step votes yes only when its prepare committed and replied exactly
+//!   `Value::Bool(true)`;
+//! - no prepare is issued at or after `deadline_timeslot`;
+//! - the decision is commit iff every step voted yes, and once journaled it is
+//!   never re-derived.
+//!
+//! [`RootBatchV2::recover`] settles a journaled batch without its caller.
+//! After a coordinator restart it compensates every undecided batch; from a
+//! periodic sweep it compensates an undecided batch once its deadline passed.
+//! Both finish the remaining settle calls of a decided batch. Abort
+//! compensates every step, including ones whose prepare never ran or whose vote
+//! was lost in the crash, so actors must treat compensate as idempotent,
+//! tolerate a missing reservation, and refuse a later prepare of the same
+//! batch (a tombstone row); commit must be idempotent. Those rows are the
+//! actor's own guest state.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::path::{Path, PathBuf};
+
+use super::wire::{DecodeError, Decoder, Encoder, V2Wire};
+use super::{
+    ActorId, AuthorizationEvidenceV2, CommittedImageStoreV2, Hash, InvocationId,
+    LocalRootTreeInvokeErrorV2, LocalRootTreeServiceV2, LocalWorkRequestV2, Origin,
+    ProofArtifactStoreV2, ReplyRecordV2,
+};
+
+/// Upper bound on steps in one batch.
+pub const MAX_ROOT_BATCH_STEPS: usize = 64;
+
+const BATCH_INVOCATION_NAMESPACE: &[u8] = b"vos/root-batch/v2";
+
+/// One method call of a batch phase. `arguments` is the canonical actor
+/// message wire (`TAG_DYNAMIC ++ rkyv(Msg)`) and should carry the batch id
+/// the actor keys its reservation and tombstone rows by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootBatchCallV2 {
+    pub method: String,
+    pub arguments: Vec<u8>,
+}
+
+/// One participant of a batch: an actor on the root at `root` (an index into
+/// the participants passed to [`RootBatchV2::drive`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootBatchStepV2 {
+    pub root: usize,
+    pub target: ActorId,
+    pub origin: Origin,
+    pub authorization: AuthorizationEvidenceV2,
+    pub prepare: RootBatchCallV2,
+    pub commit: RootBatchCallV2,
+    pub compensate: RootBatchCallV2,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootBatchV2 {
+    /// Caller-chosen unique id. Reusing it with different steps is rejected
+    /// by the journal, and by the guests as a divergent invocation.
+    pub id: Hash,
+    /// First logical timeslot at which no prepare may be issued.
+    pub deadline_timeslot: u64,
+    pub steps: Vec<RootBatchStepV2>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RootBatchPhaseV2 {
+    Prepare,
+    Commit,
+    Compensate,
+}
+
+impl RootBatchPhaseV2 {
+    const fn tag(self) -> u8 {
+        match self {
+            Self::Prepare => 0,
+            Self::Commit => 1,
+            Self::Compensate => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RootBatchDecisionV2 {
+    Committed,
+    Compensated,
+}
+
+/// Why [`RootBatchV2::recover`] runs. Neither mode issues a prepare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RootBatchRecoveryV2 {
+    /// The coordinator restarted: compensate every undecided batch.
+    Restart,
+    /// A periodic sweep: compensate an undecided batch at or after its
+    /// deadline and leave earlier ones to their caller.
+    Deadline,
+}
+
+/// The journaled progress of one batch. The record carries the whole batch
+/// so recovery can re-issue its calls without the original caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootBatchRecordV2 {
+    pub batch: RootBatchV2,
+    /// Each step's prepare vote; `None` until its prepare replied.
+    pub votes: Vec<Option<bool>>,
+    /// Journaled before any commit or compensate call is issued.
+    pub decision: Option<RootBatchDecisionV2>,
+    /// Whether each step's commit or compensate call has committed.
+    pub settled: Vec<bool>,
+}
+
+impl RootBatchRecordV2 {
+    fn new(batch: RootBatchV2) -> Self {
+        let steps = batch.steps.len();
+        Self {
+            batch,
+            votes: alloc::vec![None; steps],
+            decision: None,
+            settled: alloc::vec![false; steps],
+        }
+    }
+
+    /// Decided, and every step's settle call committed.
+    pub fn is_settled(&self) -> bool {
+        self.decision.is_some() && self.settled.iter().all(|settled| *settled)
+    }
+}
+
+impl V2Wire for RootBatchRecordV2 {
+    const MAGIC: [u8; 4] = *b"VRB2";
+
+    fn encode_body(&self, out: &mut Vec<u8>) {
+        let mut encoder = Encoder(out);
+        encoder.fixed(&self.batch.id.0);
+        encoder.u64(self.batch.deadline_timeslot);
+        encoder.list(&self.batch.steps, |encoder, step| {
+            encoder.u32(step.root as u32);
+            encoder.fixed(&step.target.0);
+            super::contracts::encode_origin(encoder, step.origin);
+            super::contracts::encode_auth(encoder, &step.authorization);
+            for call in [&step.prepare, &step.commit, &step.compensate] {
+                encoder.string(&call.method);
+                encoder.bytes(&call.arguments);
+            }
+        });
+        encoder.list(&self.votes, |encoder, vote| {
+            encoder.option(vote, |encoder, vote| encoder.bool(*vote));
+        });
+        encoder.option(&self.decision, |encoder, decision| {
+            encoder.u8(match decision {
+                RootBatchDecisionV2::Committed => 0,
+                RootBatchDecisionV2::Compensated => 1,
+            });
+        });
+        encoder.list(&self.settled, |encoder, settled| encoder.bool(*settled));
+    }
+
+    fn decode_body(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        fn call(decoder: &mut Decoder<'_>) -> Result<RootBatchCallV2, DecodeError> {
+            Ok(RootBatchCallV2 {
+                method: decoder.string()?,
+                arguments: decoder.bytes()?,
+            })
+        }
+        let id = Hash(decoder.fixed()?);
+        let deadline_timeslot = decoder.u64()?;
+        let steps = decoder.list(|decoder| {
+            Ok(RootBatchStepV2 {
+                root: decoder.u32()? as usize,
+                target: ActorId(decoder.fixed()?),
+                origin: super::contracts::decode_origin(decoder)?,
+                authorization: super::contracts::decode_auth(decoder)?,
+                prepare: call(decoder)?,
+                commit: call(decoder)?,
+                compensate: call(decoder)?,
+            })
+        })?;
+        let votes = decoder.list(|decoder| decoder.option(Decoder::bool))?;
+        let decision = decoder.option(|decoder| match decoder.u8()? {
+            0 => Ok(RootBatchDecisionV2::Committed),
+            1 => Ok(RootBatchDecisionV2::Compensated),
+            _ => Err(DecodeError::InvalidTag),
+        })?;
+        let settled = decoder.list(Decoder::bool)?;
+        if steps.is_empty()
+            || steps.len() > MAX_ROOT_BATCH_STEPS
+            || votes.len() != steps.len()
+            || settled.len() != steps.len()
+            || (decision.is_none() && settled.contains(&true))
+        {
+            return Err(DecodeError::NonCanonical);
+        }
+        Ok(Self {
+            batch: RootBatchV2 {
+                id,
+                deadline_timeslot,
+                steps,
+            },
+            votes,
+            decision,
+            settled,
+        })
+    }
+}
+
+/// Durable coordinator journal, keyed by batch id.
+///
+/// `commit` must return success only after the record is recoverable
+/// following a process restart, like [`CommittedImageStoreV2::commit`]. The
+/// coordinator never issues a phase call that depends on a record before
+/// that record is committed.
+pub trait RootBatchJournalV2 {
+    type Error;
+
+    fn load(&self, batch: &Hash) -> Result<Option<RootBatchRecordV2>, Self::Error>;
+
+    fn commit(&mut self, record: &RootBatchRecordV2) -> Result<(), Self::Error>;
+
+    /// Every journaled batch that is not yet settled, in batch-id order.
+    fn unsettled(&self) -> Result<Vec<RootBatchRecordV2>, Self::Error>;
+}
+
+/// Process-local journal. It survives a dropped coordinator but not a
+/// process restart; use [`FileRootBatchJournalV2`] for durable batches.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryRootBatchJournalV2 {
+    records: BTreeMap<Hash, Vec<u8>>,
+}
+
+impl RootBatchJournalV2 for MemoryRootBatchJournalV2 {
+    type Error = DecodeError;
+
+    fn load(&self, batch: &Hash) -> Result<Option<RootBatchRecordV2>, Self::Error> {
+        self.records
+            .get(batch)
+            .map(|bytes| RootBatchRecordV2::decode(bytes))
+            .transpose()
+    }
+
+    fn commit(&mut self, record: &RootBatchRecordV2) -> Result<(), Self::Error> {
+        self.records.insert(record.batch.id, record.encode());
+        Ok(())
+    }
+
+    fn unsettled(&self) -> Result<Vec<RootBatchRecordV2>, Self::Error> {
+        let mut records = Vec::new();
+        for bytes in self.records.values() {
+            let record = RootBatchRecordV2::decode(bytes)?;
+            if !record.is_settled() {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// Atomic filesystem journal: one `<batch id hex>.vrb2` file per batch in
+/// `directory`, replaced by flush, rename and directory sync like
+/// [`super::FileCommittedImageStoreV2`]. One directory is owned by one
+/// coordinator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRootBatchJournalV2 {
+    directory: PathBuf,
+}
+
+impl FileRootBatchJournalV2 {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn record_path(&self, batch: &Hash) -> PathBuf {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        let mut name = [0_u8; 64];
+        for (index, byte) in batch.0.iter().copied().enumerate() {
+            name[index * 2] = HEX[usize::from(byte >> 4)];
+            name[index * 2 + 1] = HEX[usize::from(byte & 0x0f)];
+        }
+        self.directory
+            .join(std::str::from_utf8(&name).expect("lowercase hexadecimal is valid UTF-8"))
+            .with_extension("vrb2")
+    }
+
+    fn read(path: &Path) -> std::io::Result<Option<RootBatchRecordV2>> {
+        match std::fs::read(path) {
+            Ok(bytes) => RootBatchRecordV2::decode(&bytes)
+                .map(Some)
+                .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl RootBatchJournalV2 for FileRootBatchJournalV2 {
+    type Error = std::io::Error;
+
+    fn load(&self, batch: &Hash) -> Result<Option<RootBatchRecordV2>, Self::Error> {
+        let record = Self::read(&self.record_path(batch))?;
+        if record
+            .as_ref()
+            .is_some_and(|record| record.batch.id != *batch)
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "root batch record is stored under another batch id",
+            ));
+        }
+        Ok(record)
+    }
+
+    fn commit(&mut self, record: &RootBatchRecordV2) -> Result<(), Self::Error> {
+        use std::io::Write;
+
+        std::fs::create_dir_all(&self.directory)?;
+        let path = self.record_path(&record.batch.id);
+        let temporary = path.with_extension("vrb2-next");
+        let mut file = std::fs::File::create(&temporary)?;
+        file.write_all(&record.encode())?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&temporary, &path)?;
+        std::fs::File::open(&self.directory)?.sync_all()
+    }
+
+    fn unsettled(&self) -> Result<Vec<RootBatchRecordV2>, Self::Error> {
+        let entries = match std::fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let mut records = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != "vrb2") {
+                continue;
+            }
+            if let Some(record) = Self::read(&path)?
+                && !record.is_settled()
+            {
+                records.push(record);
+            }
+        }
+        records.sort_by_key(|record| record.batch.id);
+        Ok(records)
+    }
+}
+
+#[derive(Debug)]
+pub enum RootBatchErrorV2<R, J> {
+    EmptyBatch,
+    TooManySteps,
+    UnknownRoot(usize),
+    EmptyMethod(usize),
+    /// The journal holds different steps or deadline under this batch id.
+    DivergentBatch,
+    Journal(J),
+    /// A root failed a phase call. The journal still holds every earlier
+    /// decision; drive or recover the batch again once the root is available.
+    Root {
+        step: usize,
+        phase: RootBatchPhaseV2,
+        error: R,
+    },
+}
+
+impl<R: core::fmt::Debug, J: core::fmt::Debug> core::fmt::Display for RootBatchErrorV2<R, J> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "cannot drive VOS v2 root batch: {self:?}")
+    }
+}
+
+impl<R: core::fmt::Debug, J: core::fmt::Debug> core::error::Error for RootBatchErrorV2<R, J> {}
+
+/// A root service a batch can drive. Implemented for every durable
+/// [`LocalRootTreeServiceV2`] and by the node for its registered roots.
+pub trait RootBatchParticipantV2 {
+    type Error;
+
+    /// Admit and execute one phase call, or reattach its committed result on
+    /// an exact retry. Returns whether the committed reply is exactly
+    /// `Value::Bool(true)`.
+    fn invoke_phase(&mut self, request: LocalWorkRequestV2) -> Result<bool, Self::Error>;
+}
+
+impl<B> RootBatchParticipantV2 for LocalRootTreeServiceV2<B>
+where
+    B: CommittedImageStoreV2 + ProofArtifactStoreV2<Error = <B as CommittedImageStoreV2>::Error>,
+{
+    type Error = LocalRootTreeInvokeErrorV2;
+
+    fn invoke_phase(&mut self, request: LocalWorkRequestV2) -> Result<bool, Self::Error> {
+        // Checked against the receipt's reply commitment rather than the
+        // published reply, which an acknowledged duplicate no longer carries.
+        let yes = ReplyRecordV2 {
+            call_id: request.invocation.root_reply_id(),
+            producer: request.target,
+            result: crate::Encode::encode(&crate::value::Value::Bool(true)),
+        };
+        let receipt = self.invoke(request)?.receipt;
+        Ok(receipt.reply_commitment == Some(yes.commitment()))
+    }
+}
+
+type BatchError<R, J> = RootBatchErrorV2<R, <J as RootBatchJournalV2>::Error>;
+
+impl RootBatchV2 {
+    /// Stable invocation id of one phase call.
+    pub fn invocation(&self, step: usize, phase: RootBatchPhaseV2) -> InvocationId {
+        let mut nonce = Vec::with_capacity(32 + 8 + 1);
+        nonce.extend_from_slice(&self.id.0);
+        nonce.extend_from_slice(&(step as u64).to_le_bytes());
+        nonce.push(phase.tag());
+        InvocationId::derive(BATCH_INVOCATION_NAMESPACE, &nonce)
+    }
+
+    /// Drive the batch to a decision and settle every step, or resume one
+    /// whose earlier drive failed on an unavailable root. `logical_timeslot`
+    /// is the admission slot for any call issued now; it only gates new
+    /// prepares against the deadline. Returns the settled record.
+    pub fn drive<R, J: RootBatchJournalV2>(
+        &self,
+        journal: &mut J,
+        participants: &mut [&mut dyn RootBatchParticipantV2<Error = R>],
+        logical_timeslot: u64,
+    ) -> Result<RootBatchRecordV2, BatchError<R, J>> {
+        let mut record = self.begin::<R, J>(journal, participants.len())?;
+        if record.decision.is_none() {
+            let decision = self.prepare(&mut record, journal, participants, logical_timeslot)?;
+            record.decision = Some(decision);
+            journal
+                .commit(&record)
+                .map_err(BatchError::<R, J>::Journal)?;
+        }
+        self.settle(record, journal, participants, logical_timeslot)
+    }
+
+    /// Settle a journaled batch without issuing any prepare: compensate it if
+    /// it is undecided and `recovery` applies, and finish the settle calls of
+    /// a decided batch. An undecided batch before its deadline is returned
+    /// unchanged from a [`RootBatchRecoveryV2::Deadline`] sweep.
+    pub fn recover<R, J: RootBatchJournalV2>(
+        &self,
+        journal: &mut J,
+        participants: &mut [&mut dyn RootBatchParticipantV2<Error = R>],
+        logical_timeslot: u64,
+        recovery: RootBatchRecoveryV2,
+    ) -> Result<RootBatchRecordV2, BatchError<R, J>> {
+        let mut record = self.begin::<R, J>(journal, participants.len())?;
+        if record.decision.is_none() {
+            if recovery == RootBatchRecoveryV2::Deadline
+                && logical_timeslot < self.deadline_timeslot
+            {
+                return Ok(record);
+            }
+            record.decision = Some(RootBatchDecisionV2::Compensated);
+            journal
+                .commit(&record)
+                .map_err(BatchError::<R, J>::Journal)?;
+        }
+        self.settle(record, journal, participants, logical_timeslot)
+    }
+
+    /// The journaled record of this batch, journaling a fresh one first.
+    fn begin<R, J: RootBatchJournalV2>(
+        &self,
+        journal: &mut J,
+        participants: usize,
+    ) -> Result<RootBatchRecordV2, BatchError<R, J>> {
+        self.validate::<R, J>(participants)?;
+        match journal
+            .load(&self.id)
+            .map_err(BatchError::<R, J>::Journal)?
+        {
+            Some(record) if record.batch != *self => Err(BatchError::<R, J>::DivergentBatch),
+            Some(record) => Ok(record),
+            None => {
+                let record = RootBatchRecordV2::new(self.clone());
+                journal
+                    .commit(&record)
+                    .map_err(BatchError::<R, J>::Journal)?;
+                Ok(record)
+            }
+        }
+    }
+
+    /// Collect votes in step order, journaling each one, and return the
+    /// decision. The first missing or no vote decides compensation.
+    fn prepare<R, J: RootBatchJournalV2>(
+        &self,
+        record: &mut RootBatchRecordV2,
+        journal: &mut J,
+        participants: &mut [&mut dyn RootBatchParticipantV2<Error = R>],
+        logical_timeslot: u64,
+    ) -> Result<RootBatchDecisionV2, BatchError<R, J>> {
+        for index in 0..self.steps.len() {
+            let vote = match record.votes[index] {
+                Some(vote) => vote,
+                None if logical_timeslot < self.deadline_timeslot => {
+                    let request = self.request(index, RootBatchPhaseV2::Prepare, logical_timeslot);
+                    let vote = participants[self.steps[index].root]
+                        .invoke_phase(request)
+                        .map_err(|error| BatchError::<R, J>::Root {
+                            step: index,
+                            phase: RootBatchPhaseV2::Prepare,
+                            error,
+                        })?;
+                    record.votes[index] = Some(vote);
+                    journal
+                        .commit(record)
+                        .map_err(BatchError::<R, J>::Journal)?;
+                    vote
+                }
+                // Past the deadline a missing prepare is never issued.
+                None => false,
+            };
+            if !vote {
+                return Ok(RootBatchDecisionV2::Compensated);
+            }
+        }
+        Ok(RootBatchDecisionV2::Committed)
+    }
+
+    /// Issue every unsettled commit or compensate call of a decided record.
+    fn settle<R, J: RootBatchJournalV2>(
+        &self,
+        mut record: RootBatchRecordV2,
+        journal: &mut J,
+        participants: &mut [&mut dyn RootBatchParticipantV2<Error = R>],
+        logical_timeslot: u64,
+    ) -> Result<RootBatchRecordV2, BatchError<R, J>> {
+        let phase = match record.decision {
+            Some(RootBatchDecisionV2::Committed) => RootBatchPhaseV2::Commit,
+            Some(RootBatchDecisionV2::Compensated) => RootBatchPhaseV2::Compensate,
+            None => unreachable!("settle runs only after a journaled decision"),
+        };
+        for index in 0..self.steps.len() {
+            if record.settled[index] {
+                continue;
+            }
+            let request = self.request(index, phase, logical_timeslot);
+            participants[self.steps[index].root]
+                .invoke_phase(request)
+                .map_err(|error| BatchError::<R, J>::Root {
+                    step: index,
+                    phase,
+                    error,
+                })?;
+            record.settled[index] = true;
+            journal
+                .commit(&record)
+                .map_err(BatchError::<R, J>::Journal)?;
+        }
+        Ok(record)
+    }
+
+    fn validate<R, J: RootBatchJournalV2>(
+        &self,
+        participants: usize,
+    ) -> Result<(), BatchError<R, J>> {
+        if self.steps.is_empty() {
+            return Err(RootBatchErrorV2::EmptyBatch);
+        }
+        if self.steps.len() > MAX_ROOT_BATCH_STEPS {
+            return Err(RootBatchErrorV2::TooManySteps);
+        }
+        for (index, step) in self.steps.iter().enumerate() {
+            if step.root >= participants {
+                return Err(RootBatchErrorV2::UnknownRoot(step.root));
+            }
+            if [&step.prepare, &step.commit, &step.compensate]
+                .iter()
+                .any(|call| call.method.is_empty())
+            {
+                return Err(RootBatchErrorV2::EmptyMethod(index));
+            }
+        }
+        Ok(())
+    }
+
+    /// The direct invocation of one phase call of step `index`.
+    pub fn request(
+        &self,
+        index: usize,
+        phase: RootBatchPhaseV2,
+        logical_timeslot: u64,
+    ) -> LocalWorkRequestV2 {
+        let step = &self.steps[index];
+        let call = match phase {
+            RootBatchPhaseV2::Prepare => &step.prepare,
+            RootBatchPhaseV2::Commit => &step.commit,
+            RootBatchPhaseV2::Compensate => &step.compensate,
+        };
+        LocalWorkRequestV2 {
+            invocation: self.invocation(index, phase),
+            workflow_step: 0,
+            logical_timeslot,
+            target: step.target,
+            method: call.method.clone(),
+            arguments: call.arguments.clone(),
+            origin: step.origin,
+            authorization: step.authorization.clone(),
+            causal_parent: None,
+            parent_call: None,
+            causal_context: None,
+            awaited_reply: None,
+            awaited_timeout: None,
+            imported_blobs: Vec::new(),
+            proof_requested: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Records committed invocations like a guest's dedup rows and answers
+    /// prepares with a scripted vote.
+    struct ScriptedRoot {
+        vote: bool,
+        available: bool,
+        committed: BTreeMap<InvocationId, (String, bool)>,
+    }
+
+    impl ScriptedRoot {
+        fn new(vote: bool) -> Self {
+            Self {
+                vote,
+                available: true,
+                committed: BTreeMap::new(),
+            }
+        }
+
+        fn methods(&self) -> Vec<&str> {
+            self.committed
+                .values()
+                .map(|(method, _)| method.as_str())
+                .collect()
+        }
+    }
+
+    impl RootBatchParticipantV2 for ScriptedRoot {
+        type Error = LocalRootTreeInvokeErrorV2;
+
+        fn invoke_phase(&mut self, request: LocalWorkRequestV2) -> Result<bool, Self::Error> {
+            if let Some((_, reply)) = self.committed.get(&request.invocation) {
+                return Ok(*reply);
+            }
+            if !self.available {
+                return Err(LocalRootTreeInvokeErrorV2::ProofUnavailable);
+            }
+            let reply = request.method != "reserve" || self.vote;
+            self.committed
+                .insert(request.invocation, (request.method, reply));
+            Ok(reply)
+        }
+    }
+
+    fn call(method: &str) -> RootBatchCallV2 {
+        RootBatchCallV2 {
+            method: method.into(),
+            arguments: vec![crate::value::TAG_DYNAMIC],
+        }
+    }
+
+    fn transfer() -> RootBatchV2 {
+        let step = |root: usize, actor: u8| RootBatchStepV2 {
+            root,
+            target: ActorId([actor; 32]),
+            origin: Origin::System,
+            authorization: AuthorizationEvidenceV2::Public,
+            prepare: call("reserve"),
+            commit: call("apply"),
+            compensate: call("release"),
+        };
+        RootBatchV2 {
+            id: Hash([7; 32]),
+            deadline_timeslot: 100,
+            steps: vec![step(0, 1), step(1, 2)],
+        }
+    }
+
+    #[test]
+    fn unanimous_prepare_commits_every_root() {
+        let mut journal = MemoryRootBatchJournalV2::default();
+        let (mut debit, mut credit) = (ScriptedRoot::new(true), ScriptedRoot::new(true));
+        let record = transfer()
+            .drive(&mut journal, &mut [&mut debit, &mut credit], 10)
+            .unwrap();
+        assert_eq!(record.decision, Some(RootBatchDecisionV2::Committed));
+        assert_eq!(record.votes, vec![Some(true); 2]);
+        assert!(record.is_settled());
+        assert_eq!(debit.methods().len(), 2);
+        assert!(debit.methods().contains(&"apply"));
+        assert!(credit.methods().contains(&"apply"));
+        assert!(journal.unsettled().unwrap().is_empty());
+        assert_eq!(journal.load(&record.batch.id).unwrap(), Some(record));
+    }
+
+    #[test]
+    fn a_no_vote_compensates_every_step_and_skips_later_prepares() {
+        let mut journal = MemoryRootBatchJournalV2::default();
+        let (mut debit, mut credit) = (ScriptedRoot::new(false), ScriptedRoot::new(true));
+        let record = transfer()
+            .drive(&mut journal, &mut [&mut debit, &mut credit], 10)
+            .unwrap();
+        assert_eq!(record.decision, Some(RootBatchDecisionV2::Compensated));
+        assert_eq!(record.votes, vec![Some(false), None]);
+        assert_eq!(credit.methods(), vec!["release"]);
+        assert!(debit.methods().contains(&"release"));
+    }
+
+    #[test]
+    fn the_deadline_sweep_compensates_a_batch_stuck_on_an_unavailable_root() {
+        let batch = transfer();
+        let mut journal = MemoryRootBatchJournalV2::default();
+        let (mut debit, mut credit) = (ScriptedRoot::new(true), ScriptedRoot::new(true));
+        // The credit root is down: the debit is prepared, nothing is decided.
+        credit.available = false;
+        assert!(matches!(
+            batch.drive(&mut journal, &mut [&mut debit, &mut credit], 10),
+            Err(RootBatchErrorV2::Root {
+                step: 1,
+                phase: RootBatchPhaseV2::Prepare,
+                ..
+            })
+        ));
+        let stuck = journal.unsettled().unwrap();
+        assert_eq!(stuck.len(), 1);
+        assert_eq!(stuck[0].votes, vec![Some(true), None]);
+
+        // Before the deadline the sweep leaves the batch to its caller.
+        credit.available = true;
+        let early = batch
+            .recover(
+                &mut journal,
+                &mut [&mut debit, &mut credit],
+                50,
+                RootBatchRecoveryV2::Deadline,
+            )
+            .unwrap();
+        assert_eq!(early.decision, None);
+        assert!(credit.methods().is_empty());
+
+        // At the deadline it compensates, and the missing prepare is never
+        // issued afterwards.
+        let aborted = batch
+            .recover(
+                &mut journal,
+                &mut [&mut debit, &mut credit],
+                100,
+                RootBatchRecoveryV2::Deadline,
+            )
+            .unwrap();
+        assert_eq!(aborted.decision, Some(RootBatchDecisionV2::Compensated));
+        assert!(aborted.is_settled());
+        assert_eq!(credit.methods(), vec!["release"]);
+        let again = batch
+            .drive(&mut journal, &mut [&mut debit, &mut credit], 101)
+            .unwrap();
+        assert_eq!(again, aborted);
+        assert!(!credit.methods().contains(&"reserve"));
+    }
+
+    #[test]
+    fn restart_recovery_compensates_an_undecided_batch_even_after_every_vote() {
+        let batch = transfer();
+        let mut journal = MemoryRootBatchJournalV2::default();
+        let (mut debit, mut credit) = (ScriptedRoot::new(true), ScriptedRoot::new(true));
+        // Both prepares voted yes, then the coordinator died before it
+        // journaled a decision.
+        let mut record = batch.begin::<(), _>(&mut journal, 2).unwrap();
+        for (index, root) in [&mut debit, &mut credit].into_iter().enumerate() {
+            let request = batch.request(index, RootBatchPhaseV2::Prepare, 10);
+            record.votes[index] = Some(root.invoke_phase(request).unwrap());
+        }
+        journal.commit(&record).unwrap();
+
+        let recovered = batch
+            .recover(
+                &mut journal,
+                &mut [&mut debit, &mut credit],
+                11,
+                RootBatchRecoveryV2::Restart,
+            )
+            .unwrap();
+        assert_eq!(recovered.decision, Some(RootBatchDecisionV2::Compensated));
+        assert!(debit.methods().contains(&"release"));
+        assert!(!debit.methods().contains(&"apply"));
+        assert!(journal.unsettled().unwrap().is_empty());
+    }
+
+    #[test]
+    fn a_journaled_decision_is_finished_not_re_derived() {
+        let batch = transfer();
+        let mut journal = MemoryRootBatchJournalV2::default();
+        let (mut debit, mut credit) = (ScriptedRoot::new(true), ScriptedRoot::new(true));
+        // The commit was decided and reached the debit root only.
+        let mut record = batch.begin::<(), _>(&mut journal, 2).unwrap();
+        record.votes = vec![Some(true); 2];
+        record.decision = Some(RootBatchDecisionV2::Committed);
+        journal.commit(&record).unwrap();
+        debit
+            .invoke_phase(batch.request(0, RootBatchPhaseV2::Commit, 10))
+            .unwrap();
+
+        // Restart recovery past the deadline still finishes the commit.
+        let finished = batch
+            .recover(
+                &mut journal,
+                &mut [&mut debit, &mut credit],
+                500,
+                RootBatchRecoveryV2::Restart,
+            )
+            .unwrap();
+        assert_eq!(finished.decision, Some(RootBatchDecisionV2::Committed));
+        assert_eq!(credit.methods(), vec!["apply"]);
+        assert!(!debit.methods().contains(&"release"));
+    }
+
+    #[test]
+    fn a_reused_batch_id_with_other_steps_is_divergent() {
+        let mut journal = MemoryRootBatchJournalV2::default();
+        let (mut debit, mut credit) = (ScriptedRoot::new(true), ScriptedRoot::new(true));
+        transfer()
+            .drive(&mut journal, &mut [&mut debit, &mut credit], 10)
+            .unwrap();
+        let mut other = transfer();
+        other.deadline_timeslot += 1;
+        assert!(matches!(
+            other.drive(&mut journal, &mut [&mut debit, &mut credit], 10),
+            Err(RootBatchErrorV2::DivergentBatch)
+        ));
+    }
+
+    #[test]
+    fn file_journal_records_roundtrip_and_list_only_unsettled_batches() {
+        let directory = std::env::temp_dir().join(format!(
+            "vos-root-batch-journal-{}-{}",
+            std::process::id(),
+            line!()
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        let mut journal = FileRootBatchJournalV2::new(&directory);
+        assert!(journal.unsettled().unwrap().is_empty());
+
+        let mut record = RootBatchRecordV2::new(transfer());
+        record.batch.steps[1].origin = Origin::Member(crate::v2::SubjectId([9; 32]));
+        record.votes[0] = Some(true);
+        journal.commit(&record).unwrap();
+        let mut settled = RootBatchRecordV2::new(RootBatchV2 {
+            id: Hash([8; 32]),
+            ..transfer()
+        });
+        settled.decision = Some(RootBatchDecisionV2::Compensated);
+        settled.settled = vec![true; 2];
+        journal.commit(&settled).unwrap();
+
+        let reopened = FileRootBatchJournalV2::new(&directory);
+        assert_eq!(
+            reopened.load(&record.batch.id).unwrap(),
+            Some(record.clone())
+        );
+        assert_eq!(reopened.load(&Hash([1; 32])).unwrap(), None);
+        assert_eq!(reopened.unsettled().unwrap(), vec![record]);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn record_wire_rejects_inconsistent_progress() {
+        let mut record = RootBatchRecordV2::new(transfer());
+        record.settled[0] = true;
+        assert_eq!(
+            RootBatchRecordV2::decode(&record.encode()),
+            Err(DecodeError::NonCanonical),
+            "a settle call cannot precede the decision"
+        );
+        record.settled[0] = false;
+        record.votes.pop();
+        assert_eq!(
+            RootBatchRecordV2::decode(&record.encode()),
+            Err(DecodeError::NonCanonical)
+        );
+    }
+}
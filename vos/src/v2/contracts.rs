@@ -454,6 +454,8 @@ pub struct ActorPrivateInputV2 {
     pub external_actors: Vec<ExternalActorBindingV2>,
     /// Canonical identity of the workflow slice executing this actor.
     pub input: WorkInputIdV2,
+    /// Consensus admission timeslot of the carrying work envelope.
+    pub logical_timeslot: u64,
     /// Batch identity and scheduler dispatch ordinal allocated by the generic
     /// service. Present only for an explicitly CRDT service.
     pub change: Option<CrdtDispatchV2>,
@@ -2411,6 +2413,12 @@ impl AccumulationRejectionV2 {
                 | Self::ActorBusy(_)
         )
     }
+
+    /// The transition was refined against a base that is no longer current;
+    /// refining the same work again at the current base can succeed.
+    pub const fn is_stale_anchor(&self) -> bool {
+        matches!(self, Self::StaleLinearWork { .. } | Self::StaleStateRoot)
+    }
 }
 
 /// Guest output. New installs, ingress admissions, accepted transitions,
@@ -2947,6 +2955,7 @@ impl V2Wire for ActorPrivateInputV2 {
         e.list(&self.external_actors, encode_external_actor);
         e.fixed(&self.input.invocation.0);
         e.u64(self.input.workflow_step);
+        e.u64(self.logical_timeslot);
         e.option(&self.change, |e, dispatch| {
             e.fixed(&dispatch.change.0);
             e.u32(dispatch.ordinal);
@@ -2968,6 +2977,7 @@ impl V2Wire for ActorPrivateInputV2 {
                 invocation: InvocationId(d.fixed()?),
                 workflow_step: d.u64()?,
             },
+            logical_timeslot: d.u64()?,
             change: d.option(|d| {
                 Ok(CrdtDispatchV2 {
                     change: ChangeId(d.fixed()?),
@@ -5273,7 +5283,7 @@ fn decode_base(d: &mut Decoder<'_>) -> Result<ConsistencyBaseV2, DecodeError> {
     }
 }
 
-fn encode_origin(e: &mut Encoder<'_>, value: Origin) {
+pub(super) fn encode_origin(e: &mut Encoder<'_>, value: Origin) {
     match value {
         Origin::Anonymous => e.u8(0),
         Origin::Member(id) => {
@@ -5288,7 +5298,7 @@ fn encode_origin(e: &mut Encoder<'_>, value: Origin) {
     }
 }
 
-fn decode_origin(d: &mut Decoder<'_>) -> Result<Origin, DecodeError> {
+pub(super) fn decode_origin(d: &mut Decoder<'_>) -> Result<Origin, DecodeError> {
     match d.u8()? {
         0 => Ok(Origin::Anonymous),
         1 => Ok(Origin::Member(SubjectId(d.fixed()?))),
@@ -6536,6 +6546,7 @@ mod tests {
                 invocation: InvocationId([23; 32]),
                 workflow_step: 7,
             },
+            logical_timeslot: 1_700_000_000_000,
             change: Some(CrdtDispatchV2 {
                 change: ChangeId([23; 32]),
                 ordinal: 4,
@@ -691,6 +691,7 @@ impl CommittedImageStoreV2 for FileCommittedImageStoreV2 {
     fn commit(&mut self, image: &[u8]) -> Result<(), Self::Error> {
         use std::io::Write;
 
+        let _barrier = crate::write_barrier::enter();
         let parent = self.path.parent().unwrap_or_else(|| Path::new("."));
         std::fs::create_dir_all(parent)?;
         let temporary = self.temporary_path();
@@ -726,6 +727,7 @@ impl ProofArtifactStoreV2 for FileCommittedImageStoreV2 {
     fn commit_proof(&mut self, reference: &BlobRefV2, proof: &[u8]) -> Result<(), Self::Error> {
         use std::io::Write;
 
+        let _barrier = crate::write_barrier::enter();
         if !reference.matches(proof) {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
@@ -833,6 +835,7 @@ impl ProofArtifactStoreV2 for FileCommittedImageStoreV2 {
         arguments: &[u8],
         staging: PrivateIngressStagingV2,
     ) -> Result<bool, Self::Error> {
+        let _barrier = crate::write_barrier::enter();
         if !reference.matches(arguments) {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
@@ -876,6 +879,7 @@ impl ProofArtifactStoreV2 for FileCommittedImageStoreV2 {
         &mut self,
         invocation: super::InvocationId,
     ) -> Result<bool, Self::Error> {
+        let _barrier = crate::write_barrier::enter();
         let directory = self.private_ingress_directory();
         match std::fs::remove_file(self.private_ingress_path(invocation)) {
             Ok(()) => {
@@ -892,6 +896,7 @@ impl ProofArtifactStoreV2 for FileCommittedImageStoreV2 {
         retained: &[(super::InvocationId, BlobRefV2)],
         terminal: &[super::InvocationId],
     ) -> Result<(), Self::Error> {
+        let _barrier = crate::write_barrier::enter();
         let directory = self.private_ingress_directory();
         let entries = match std::fs::read_dir(&directory) {
             Ok(entries) => entries,
@@ -1055,6 +1060,7 @@ impl ProofArtifactStoreV2 for FileCommittedImageStoreV2 {
     ) -> Result<bool, Self::Error> {
         use std::io::Write;
 
+        let _barrier = crate::write_barrier::enter();
         if !crate::provable::ProofRecordEntry::decode(record)
             .is_some_and(|entry| entry.encode() == record)
         {
@@ -1102,6 +1108,7 @@ impl ProofArtifactStoreV2 for FileCommittedImageStoreV2 {
         actor: ActorId,
         tag: &[u8; 32],
     ) -> Result<bool, Self::Error> {
+        let _barrier = crate::write_barrier::enter();
         let directory = self.producer_record_directory();
         let path = self.producer_record_path(actor, tag);
         match std::fs::remove_file(path) {
@@ -10,6 +10,8 @@
 //! This is a clean boundary. None of the types in this module accept legacy
 //! `RefinePayload`, `EffectLog`, or continuation encodings.
 
+#[cfg(feature = "std")]
+mod batch;
 mod causal;
 mod continuation;
 mod contracts;
@@ -30,10 +32,17 @@ mod state_tree;
 mod storage;
 #[cfg(feature = "std")]
 mod transport;
+pub mod trust_socket;
 pub(crate) mod wire;
 
 pub use crate::attestation::AttestationPreparationV2;
 
+#[cfg(feature = "std")]
+pub use batch::{
+    FileRootBatchJournalV2, MAX_ROOT_BATCH_STEPS, MemoryRootBatchJournalV2, RootBatchCallV2,
+    RootBatchDecisionV2, RootBatchErrorV2, RootBatchJournalV2, RootBatchParticipantV2,
+    RootBatchPhaseV2, RootBatchRecordV2, RootBatchRecoveryV2, RootBatchStepV2, RootBatchV2,
+};
 pub use continuation::{ContinuationProgramV2, ContinuationSnapshotV2};
 #[cfg(all(feature = "std", feature = "network", feature = "storage"))]
 pub(crate) use contracts::crdt_change_blob_references;
@@ -91,14 +100,19 @@ pub use pvm::{
     transpile_service_elf, validate_actor_program_layout,
 };
 #[cfg(feature = "std")]
+pub(crate) use root_service::verify_ed25519_signature;
+#[cfg(feature = "std")]
 pub use root_service::{
     AttestedRootTreeInvokeErrorV2, CommittedCrdtSyncV2, CommittedRootTreeSliceV2,
     LocalRootTreeConfigErrorV2, LocalRootTreeConfigV2, LocalRootTreeInvokeErrorV2,
-    LocalRootTreeOpenErrorV2, LocalRootTreeServiceV2, RootTreeAttestedResultV2,
-    RootTreeIngressRecoveryV2, RootTreeInvocationV2, RootTreeTransportV2,
+    LocalRootTreeOpenErrorV2, LocalRootTreeServiceV2, RootActorUpgradeErrorV2, RootActorUpgradeV2,
+    RootTreeAttestedResultV2, RootTreeIngressRecoveryV2, RootTreeInvocationV2, RootTreeTransportV2,
 };
 #[cfg(feature = "std")]
-pub use scheduler::{LocalWorkRequestV2, LocalWorkSchedulerV2, PreparedWorkV2, ScheduleErrorV2};
+pub use scheduler::{
+    LocalWorkRequestV2, LocalWorkSchedulerV2, PendingWorkSourceV2, PendingWorkV2, PreparedWorkV2,
+    RootBacklogV2, ScheduleErrorV2, WorkFairnessPolicyV2, WorkPriorityV2,
+};
 #[cfg(feature = "std")]
 pub use service::{
     AccumulatedServiceOutputV2, AttestedServiceErrorV2, CommittedAccumulateBatchV2,
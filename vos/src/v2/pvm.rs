@@ -557,6 +557,7 @@ impl ActorRefineRuntimeV2 {
                 actor_tree: actor_tree.clone(),
                 external_actors: work.external_actors.clone(),
                 input: work.input_id(),
+                logical_timeslot: work.logical_timeslot,
                 change: crdt_dispatch(work, 0),
                 state,
                 causal_states,
@@ -7,6 +7,7 @@
 //! effects visible only after the configured image store accepts the complete
 //! post-Accumulate image.
 
+use alloc::collections::BTreeSet;
 use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
@@ -18,20 +19,20 @@ use super::wire::{DecodeError, Decoder, Encoder};
 use super::{
     AccumulateRequestV2, AccumulatedRoleAssertionV2, AccumulatedServiceOutputV2,
     AccumulationEnvelopeV2, AccumulationReceiptV2, AccumulationRejectionV2, AccumulationResultV2,
-    ActorDirectoryV2, ActorGenesisV2, ActorId, AttestedServiceErrorV2, AuthorizationEvidenceV2,
-    BlobRefV2, CausalCallContextV2, CommittedImageStoreV2, ConsistencyBaseV2, ConsistencyModeV2,
-    ContinuationSnapshotV2, CrdtChangeV2, CrdtSyncEnvelopeV2, DedupRecordV2, DeliveryRecordV2,
-    DirectIngressV2, DurableJamStoreV2, DurableStoreOpenErrorV2, ExternalActorBindingV2,
-    ExternalActorDirectoryV2, ImportedBlobV2, ImportedProgramV2, JamServiceV2, LocalJamStoreHostV2,
-    LocalJamStoreV2, LocalStoreReadErrorV2, LocalWorkRequestV2, LocalWorkSchedulerV2,
-    MessageRecordV2, MethodPolicyV2, NoRefineProtocolHostV2, Origin, PackageError,
-    PackageRolePoliciesV2, PreparedWorkV2, ProductionTrustErrorV2, ProductionTrustV2, ProgramId,
-    ProofArtifactStoreV2, PublicationAckV2, PublicationRecordV2, PublishedEffectsV2,
-    RefinedServiceOutputV2, RoleAssertionEligibilityV2, RoleAuthorityBindingV2,
-    RoleAuthorizationClaimV2, RoleCredentialV2, ScheduleErrorV2, ServiceDispatchError,
-    ServiceGenesisV2, ServiceIdentityV2, ServicePvmErrorV2, StateKeyV2, V2Wire, VosPackageV2,
-    WorkInputIdV2, WorkflowCheckpointV2, crdt_node_storage_key, dedup_storage_key,
-    delivery_storage_key,
+    ActorDirectoryV2, ActorGenesisV2, ActorId, ActorUpgradeV2, AttestedServiceErrorV2,
+    AuthorizationEvidenceV2, BlobRefV2, CausalCallContextV2, CommittedImageStoreV2,
+    ConsistencyBaseV2, ConsistencyModeV2, ContinuationSnapshotV2, CrdtChangeV2, CrdtSyncEnvelopeV2,
+    DedupRecordV2, DeliveryRecordV2, DirectIngressV2, DurableJamStoreV2, DurableStoreOpenErrorV2,
+    ExternalActorBindingV2, ExternalActorDirectoryV2, ImportedBlobV2, ImportedProgramV2,
+    JamServiceV2, LocalJamStoreHostV2, LocalJamStoreV2, LocalStoreReadErrorV2, LocalWorkRequestV2,
+    LocalWorkSchedulerV2, MessageRecordV2, MethodPolicyV2, NoRefineProtocolHostV2, Origin,
+    PackageError, PackageRolePoliciesV2, PendingWorkV2, PreparedWorkV2, ProductionTrustErrorV2,
+    ProductionTrustV2, ProgramId, ProofArtifactStoreV2, PublicationAckV2, PublicationRecordV2,
+    PublishedEffectsV2, RefinedServiceOutputV2, RoleAssertionEligibilityV2, RoleAuthorityBindingV2,
+    RoleAuthorizationClaimV2, RoleCredentialV2, RootBacklogV2, ScheduleErrorV2,
+    ServiceDispatchError, ServiceGenesisV2, ServiceIdentityV2, ServicePvmErrorV2, StateKeyV2,
+    V2Wire, VosPackageV2, WorkFairnessPolicyV2, WorkInputIdV2, WorkflowCheckpointV2,
+    crdt_node_storage_key, dedup_storage_key, delivery_storage_key,
 };
 
 #[cfg(feature = "storage")]
@@ -41,6 +42,10 @@ use crate::commit::CommitError;
 #[cfg(feature = "storage")]
 use crate::raft::RaftAccumulateLogV2;
 
+/// Upper bound on ingresses refined together by
+/// [`LocalRootTreeServiceV2::invoke_admitted_batch`].
+const MAX_PARALLEL_REFINE_WAVE: usize = 64;
+
 /// Strict host ingress for one direct invocation of a registered v2 root.
 ///
 /// The payload remains the canonical actor message wire
@@ -528,6 +533,9 @@ pub struct LocalRootTreeConfigV2 {
     pub install_authorization: AuthorizationEvidenceV2,
     pub refine_gas: u64,
     pub accumulate_gas: u64,
+    /// Dispatch priorities and actor weights for queued work. Not part of the
+    /// installed genesis, but every replica must declare the same policy.
+    pub work_fairness: WorkFairnessPolicyV2,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -635,6 +643,42 @@ impl<P: core::fmt::Debug> core::fmt::Display for AttestedRootTreeInvokeErrorV2<P
 
 impl<P: core::fmt::Debug> core::error::Error for AttestedRootTreeInvokeErrorV2<P> {}
 
+/// Failure while replacing a hosted root actor through guest `UpgradeActor`.
+/// Package and trust failures are detected before anything is proposed.
+#[derive(Debug)]
+pub enum RootActorUpgradeErrorV2 {
+    InvalidPackage(LocalRootTreeConfigErrorV2),
+    /// A replicated root cannot rely on the process-local conformance
+    /// allowlist: every voter must reach the same authorization decision.
+    ProductionTrustRequired,
+    EntryTooLarge,
+    Root(LocalRootTreeInvokeErrorV2),
+}
+
+impl From<LocalRootTreeInvokeErrorV2> for RootActorUpgradeErrorV2 {
+    fn from(error: LocalRootTreeInvokeErrorV2) -> Self {
+        Self::Root(error)
+    }
+}
+
+impl core::fmt::Display for RootActorUpgradeErrorV2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "cannot upgrade VOS v2 root actor: {self:?}")
+    }
+}
+
+impl core::error::Error for RootActorUpgradeErrorV2 {}
+
+/// Committed root actor upgrade. `descriptor` is read back from guest-owned
+/// state after Accumulate, never echoed from the replacement package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootActorUpgradeV2 {
+    pub upgrade: ActorUpgradeV2,
+    pub descriptor: ActorGenesisV2,
+    pub revision: u64,
+    pub duplicate: bool,
+}
+
 /// Result made visible only after physical Accumulate committed the durable
 /// service image. Non-empty effects remain in a recoverable publication row
 /// until the consumer acknowledges its exact commitment through IC-5.
@@ -893,6 +937,25 @@ where
         }
     }
 
+    fn refine_actor_trees_after_barrier(
+        &self,
+        items: &[(&super::WorkEnvelopeV2, &super::RefineImportsV2)],
+    ) -> Vec<Result<RefinedServiceOutputV2, RootTreeDriverErrorV2>> {
+        match self {
+            Self::Direct(service) => service
+                .refine_actor_trees(items)
+                .into_iter()
+                .map(|refined| refined.map_err(RootTreeDriverErrorV2::Direct))
+                .collect(),
+            #[cfg(feature = "storage")]
+            Self::Raft(service) => service
+                .refine_actor_trees_after_barrier(items)
+                .into_iter()
+                .map(|refined| refined.map_err(RootTreeDriverErrorV2::Raft))
+                .collect(),
+        }
+    }
+
     fn accumulate(
         &mut self,
         request: &AccumulateRequestV2,
@@ -1039,9 +1102,14 @@ pub struct LocalRootTreeServiceV2<B> {
     expected_root: ActorGenesisV2,
     expected_external_actors: Vec<ExternalActorBindingV2>,
     expected_role_authority: Option<RoleAuthorityBindingV2>,
+    work_fairness: WorkFairnessPolicyV2,
 }
 
-fn verify_ed25519_signature(public_key_wire: &[u8], message: &[u8], signature: &[u8]) -> bool {
+pub(crate) fn verify_ed25519_signature(
+    public_key_wire: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
     // `.vos` v2 deployment keys are the canonical libp2p Ed25519 public-key
     // protobuf: field 1 = key type 1, field 2 = exactly 32 key bytes. Decode
     // this tiny frozen wire locally so a bare std host does not need the
@@ -1081,13 +1149,23 @@ fn installation_availability(
     config: &LocalRootTreeConfigV2,
     descriptor: &ActorGenesisV2,
 ) -> (Vec<ImportedProgramV2>, Vec<ImportedBlobV2>) {
+    let programs = package_programs(&config.package, descriptor.program);
+    let blobs = vec![ImportedBlobV2 {
+        reference: descriptor.initial_state.clone(),
+        bytes: config.initial_state.clone(),
+    }];
+    (programs, blobs)
+}
+
+/// Actor and private task programs a signed package makes available to one
+/// Accumulate request, sorted and deduplicated by content address.
+fn package_programs(package: &VosPackageV2, program: ProgramId) -> Vec<ImportedProgramV2> {
     let mut programs = vec![ImportedProgramV2 {
-        program: descriptor.program,
-        pvm: config.package.actor_pvm.clone(),
+        program,
+        pvm: package.actor_pvm.clone(),
     }];
     programs.extend(
-        config
-            .package
+        package
             .task_dependencies
             .iter()
             .map(|dependency| ImportedProgramV2 {
@@ -1097,11 +1175,17 @@ fn installation_availability(
     );
     programs.sort_by_key(|program| program.program);
     programs.dedup_by_key(|program| program.program);
-    let blobs = vec![ImportedBlobV2 {
-        reference: descriptor.initial_state.clone(),
-        bytes: config.initial_state.clone(),
-    }];
-    (programs, blobs)
+    programs
+}
+
+/// Fields `UpgradeActor` never changes. Deployment, program, producer, and
+/// policies may legitimately differ from genesis after a committed upgrade.
+fn same_root_actor_identity(current: &ActorGenesisV2, genesis: &ActorGenesisV2) -> bool {
+    current.actor == genesis.actor
+        && current.name == genesis.name
+        && current.parent == genesis.parent
+        && current.initial_state == genesis.initial_state
+        && current.crdt == genesis.crdt
 }
 
 impl LocalRootTreeConfigV2 {
@@ -1685,6 +1769,7 @@ where
             expected_root,
             expected_external_actors: config.external_actors,
             expected_role_authority: config.role_authority,
+            work_fairness: config.work_fairness,
         };
         root.ensure_installed().map_err(|error| match error {
             LocalRootTreeInvokeErrorV2::Service(error) => LocalRootTreeOpenErrorV2::Service(error),
@@ -1750,6 +1835,11 @@ where
         self.service.accumulate_host().production_logical_timeslot()
     }
 
+    /// Dispatch priorities and actor weights this root was opened with.
+    pub fn work_fairness(&self) -> &WorkFairnessPolicyV2 {
+        &self.work_fairness
+    }
+
     pub fn role_authority(&self) -> Option<&RoleAuthorityBindingV2> {
         self.expected_role_authority.as_ref()
     }
@@ -2191,16 +2281,6 @@ where
         if header.service != self.identity || header.consistency != self.consistency {
             return Err(LocalRootTreeInvokeErrorV2::ExistingServiceMismatch);
         }
-        if self
-            .service
-            .accumulate_host()
-            .program(self.expected_root.program)
-            .is_none()
-        {
-            return Err(LocalRootTreeInvokeErrorV2::MissingInstalledProgram(
-                self.expected_root.program,
-            ));
-        }
         let directory = self
             .service
             .accumulate_host()
@@ -2237,7 +2317,10 @@ where
             .transpose()
             .map_err(|_| LocalRootTreeInvokeErrorV2::ExistingActorMismatch)?;
         let descriptor = descriptor.ok_or(LocalRootTreeInvokeErrorV2::ExistingActorMismatch)?;
-        if descriptor != self.expected_root
+        // A committed `UpgradeActor` may have replaced the root actor's code
+        // and policy surface since genesis. Guest Accumulate authorized that
+        // transition, so only the upgrade-invariant identity is pinned here.
+        if !same_root_actor_identity(&descriptor, &self.expected_root)
             || external.as_ref().is_none_or(|directory| {
                 directory.actors.as_slice() != self.expected_external_actors.as_slice()
             })
@@ -2245,6 +2328,16 @@ where
         {
             return Err(LocalRootTreeInvokeErrorV2::ExistingActorMismatch);
         }
+        if self
+            .service
+            .accumulate_host()
+            .program(descriptor.program)
+            .is_none()
+        {
+            return Err(LocalRootTreeInvokeErrorV2::MissingInstalledProgram(
+                descriptor.program,
+            ));
+        }
         let policies = PackageRolePoliciesV2::decode(&descriptor.role_policies)
             .map_err(|_| LocalRootTreeInvokeErrorV2::ExistingActorMismatch)?;
         for dependency in policies.task_dependencies {
@@ -2293,6 +2386,154 @@ where
             .map(|index| policies.methods[index].clone()))
     }
 
+    /// Current guest-owned descriptor of the hosted root actor.
+    pub fn root_actor_descriptor(&self) -> Result<ActorGenesisV2, LocalRootTreeInvokeErrorV2> {
+        let header = self
+            .service
+            .accumulate_host()
+            .header()
+            .map_err(LocalRootTreeInvokeErrorV2::CorruptStore)?
+            .ok_or(LocalRootTreeInvokeErrorV2::ServiceNotInstalled)?;
+        self.service
+            .accumulate_host()
+            .state_row(
+                header.service_root,
+                &StateKeyV2::ActorDescriptor(self.root_actor),
+            )
+            .map_err(LocalRootTreeInvokeErrorV2::CorruptStore)?
+            .and_then(|bytes| ActorGenesisV2::decode(&bytes).ok())
+            .ok_or(LocalRootTreeInvokeErrorV2::CorruptWorkflow)
+    }
+
+    /// Check that `package` is a signed replacement this root can activate.
+    /// Guest Accumulate repeats every binding that affects committed state.
+    pub fn validate_upgrade_package(
+        &self,
+        package: &VosPackageV2,
+    ) -> Result<(), LocalRootTreeConfigErrorV2> {
+        package
+            .validate()
+            .map_err(LocalRootTreeConfigErrorV2::InvalidPackage)?;
+        verify_package_signature(package)?;
+        super::validate_actor_program_layout(&package.actor_pvm)
+            .map_err(|_| LocalRootTreeConfigErrorV2::InvalidActorProgramLayout)?;
+        if package.manifest.service_program != self.identity.service_program {
+            return Err(LocalRootTreeConfigErrorV2::WrongServiceProgram);
+        }
+        if package.manifest.service_abi != super::ABI_VERSION {
+            return Err(LocalRootTreeConfigErrorV2::WrongServiceAbi);
+        }
+        if package.manifest.execution_semantics != super::EXECUTION_SEMANTICS_ID {
+            return Err(LocalRootTreeConfigErrorV2::WrongExecutionSemantics);
+        }
+        if package.manifest.crdt || self.consistency == ConsistencyModeV2::Crdt {
+            // CRDT peers need a causal program-metadata operation first.
+            return Err(LocalRootTreeConfigErrorV2::InvalidConsistency);
+        }
+        Ok(())
+    }
+
+    /// Replace the root actor's program and policy surface with `package`
+    /// through one canonical `UpgradeActor`.
+    ///
+    /// The caller must already hold the current-term admission barrier, so
+    /// the expected deployment/program pair and the linear base read here are
+    /// exactly the state the proposal is ordered after. The replacement PVM
+    /// travels as request availability, which a Raft entry replicates to
+    /// every voter. A retry of the same transition reports `duplicate`.
+    pub(crate) fn upgrade_root_actor_after_barrier(
+        &mut self,
+        package: &VosPackageV2,
+        authorization: AuthorizationEvidenceV2,
+    ) -> Result<RootActorUpgradeV2, RootActorUpgradeErrorV2> {
+        self.validate_upgrade_package(package)
+            .map_err(RootActorUpgradeErrorV2::InvalidPackage)?;
+        if self.consistency == ConsistencyModeV2::Raft
+            && self.production_trust_policy_id().is_none()
+        {
+            return Err(RootActorUpgradeErrorV2::ProductionTrustRequired);
+        }
+        let header = self
+            .service
+            .accumulate_host()
+            .header()
+            .map_err(LocalRootTreeInvokeErrorV2::CorruptStore)?
+            .ok_or(LocalRootTreeInvokeErrorV2::ServiceNotInstalled)?;
+        let state_root = header
+            .state_root
+            .ok_or(LocalRootTreeInvokeErrorV2::CorruptWorkflow)?;
+        let current = self.root_actor_descriptor()?;
+        let replacement = package
+            .actor_genesis(
+                self.root_actor,
+                current.name.clone(),
+                current.parent,
+                current.initial_state.clone(),
+            )
+            .map_err(|error| {
+                RootActorUpgradeErrorV2::InvalidPackage(LocalRootTreeConfigErrorV2::InvalidPackage(
+                    error,
+                ))
+            })?;
+        let upgrade = ActorUpgradeV2 {
+            service: self.identity.clone(),
+            actor: self.root_actor,
+            expected_deployment: current.deployment,
+            expected_program: current.program,
+            replacement_deployment: replacement.deployment,
+            replacement_program: replacement.program,
+            producer: replacement.producer,
+            role_policies: replacement.role_policies,
+            base: ConsistencyBaseV2::Linear {
+                revision: header.revision,
+                state_root,
+            },
+            authorization,
+        };
+        let request = AccumulateRequestV2::UpgradeActor(upgrade.clone());
+        let programs = package_programs(package, upgrade.replacement_program);
+        #[cfg(feature = "storage")]
+        if self.consistency == ConsistencyModeV2::Raft
+            && !crate::raft::v2::accumulate_entry_fits_network_frame(
+                &request,
+                None,
+                &programs,
+                &[],
+                &[],
+            )
+            .map_err(|_| LocalRootTreeInvokeErrorV2::UnexpectedResult)?
+        {
+            return Err(RootActorUpgradeErrorV2::EntryTooLarge);
+        }
+        // Conformance hosts authorize these exact bytes process-locally;
+        // production stores ignore the allowlist and ask their trust policy.
+        self.service.accumulate_host_mut().allow_upgrade(&upgrade);
+        let result = self
+            .service
+            .accumulate_with_availability_after_barrier(&request, &programs, &[])
+            .map_err(RootTreeDriverErrorV2::into_invoke)?;
+        let duplicate = match result.result {
+            AccumulationResultV2::ActorUpgraded { duplicate, .. } => duplicate,
+            AccumulationResultV2::Rejected(rejection) => {
+                return Err(LocalRootTreeInvokeErrorV2::Rejected(rejection).into());
+            }
+            _ => return Err(LocalRootTreeInvokeErrorV2::UnexpectedResult.into()),
+        };
+        let revision = self
+            .service
+            .accumulate_host()
+            .header()
+            .map_err(LocalRootTreeInvokeErrorV2::CorruptStore)?
+            .ok_or(LocalRootTreeInvokeErrorV2::ServiceNotInstalled)?
+            .revision;
+        Ok(RootActorUpgradeV2 {
+            upgrade,
+            descriptor: self.root_actor_descriptor()?,
+            revision,
+            duplicate,
+        })
+    }
+
     /// Recover the exact authorization evidence already admitted for a
     /// direct invocation. This lets a host reattach a lost-result retry
     /// without reinterpreting the actor's *current* package policy after an
@@ -2941,6 +3182,147 @@ where
         self.execute_admitted_after_barrier(request, private_arguments)
     }
 
+    /// Execute admitted direct ingresses in the given order, refining
+    /// independent actors concurrently.
+    ///
+    /// Consecutive ingresses whose imported actor sets are disjoint form a
+    /// wave that is prepared against one committed base, refined in
+    /// parallel, and accumulated in input order. A transition whose anchor
+    /// went stale meanwhile is re-prepared and refined again at the current
+    /// base, as is anything that needs storage-witness discovery, a private
+    /// argument, or a proof. Every ingress therefore commits exactly what the
+    /// serial [`Self::invoke_admitted`] would.
+    ///
+    /// Linear and Raft transitions anchor to the exact revision, so every
+    /// wave member after the first is moved onto the base its predecessors
+    /// left by [`Self::chain_speculative`] before it accumulates. A CRDT root
+    /// holds only its root actor, so every CRDT ingress touches it and forms
+    /// a wave of its own.
+    pub fn invoke_admitted_batch(
+        &mut self,
+        invocations: &[super::InvocationId],
+    ) -> Result<
+        Vec<Result<CommittedRootTreeSliceV2, LocalRootTreeInvokeErrorV2>>,
+        LocalRootTreeInvokeErrorV2,
+    > {
+        self.prepare_admission_barrier()?;
+        let mut results = Vec::with_capacity(invocations.len());
+        let mut next = 0;
+        while next < invocations.len() {
+            let mut wave = Vec::new();
+            let mut touched = BTreeSet::new();
+            while next < invocations.len() && wave.len() < MAX_PARALLEL_REFINE_WAVE {
+                let Some((request, prepared)) = self.speculative_ingress(invocations[next]) else {
+                    break;
+                };
+                let actors = prepared
+                    .work
+                    .imported_actors
+                    .iter()
+                    .map(|imported| imported.actor)
+                    .collect::<BTreeSet<_>>();
+                if !touched.is_disjoint(&actors) {
+                    break;
+                }
+                touched.extend(actors);
+                wave.push((request, prepared));
+                next += 1;
+            }
+            if wave.is_empty() {
+                results.push(self.invoke_admitted_after_barrier(invocations[next]));
+                next += 1;
+                continue;
+            }
+            let refined = self.service.refine_actor_trees_after_barrier(
+                &wave
+                    .iter()
+                    .map(|(_, prepared)| (&prepared.work, &prepared.imports))
+                    .collect::<Vec<_>>(),
+            );
+            for ((request, prepared), refined) in wave.into_iter().zip(refined) {
+                let chained = refined
+                    .ok()
+                    .and_then(|refined| self.chain_speculative(&request, prepared, refined));
+                let committed = match chained {
+                    Some((prepared, refined)) => {
+                        self.accumulate_refined_after_barrier(prepared, refined, &[], None)
+                    }
+                    None => self.execute_admitted_after_barrier(request.clone(), None),
+                };
+                results.push(match committed {
+                    Err(LocalRootTreeInvokeErrorV2::Rejected(rejection))
+                        if rejection.is_stale_anchor() =>
+                    {
+                        self.execute_admitted_after_barrier(request, None)
+                    }
+                    committed => committed,
+                });
+            }
+        }
+        Ok(results)
+    }
+
+    /// Move a wave member's transition onto the base serial execution gives
+    /// it, which every earlier commit in the wave has advanced.
+    ///
+    /// The member is prepared again at the current base. When that work
+    /// differs from the speculative work only in its base, the actor ran on
+    /// identical inputs, so its writes, materializations and reply stand;
+    /// only the transition's base is replaced. A slice that checkpoints,
+    /// calls out, or carries a CRDT change returns `None` to run serially.
+    fn chain_speculative(
+        &self,
+        request: &LocalWorkRequestV2,
+        speculative: PreparedWorkV2,
+        mut refined: RefinedServiceOutputV2,
+    ) -> Option<(PreparedWorkV2, RefinedServiceOutputV2)> {
+        let current = self.prepare_request(request.clone(), None).ok()?;
+        if current.work == speculative.work {
+            return Some((speculative, refined));
+        }
+        let mut rebased = speculative.work;
+        rebased.base = current.work.base.clone();
+        rebased.base_causal_height = current.work.base_causal_height;
+        let transition = &mut refined.transition;
+        if rebased != current.work
+            || !transition.continuations.is_empty()
+            || !transition.inbox.is_empty()
+            || !transition.outbox.is_empty()
+        {
+            return None;
+        }
+        // A CRDT root holds only its root actor, since CRDT actors cannot
+        // spawn, so its waves never have a second member to move.
+        if transition.crdt_change.is_some() {
+            return None;
+        }
+        transition.base = current.work.base.clone();
+        Some((current, refined))
+    }
+
+    /// An unconsumed, unattested ingress with public arguments, prepared at
+    /// the current base, or `None`
+    /// when it must take the serial path.
+    fn speculative_ingress(
+        &self,
+        invocation: super::InvocationId,
+    ) -> Option<(LocalWorkRequestV2, PreparedWorkV2)> {
+        let record = self
+            .service
+            .accumulate_host()
+            .ingress_record(invocation)
+            .ok()??;
+        if record.consumed || record.ingress.private_arguments.is_some() {
+            return None;
+        }
+        let (request, _) = self.request_from_admitted_ingress(record).ok()?;
+        if request.proof_requested || self.recover_committed_invocation(&request).ok()?.is_some() {
+            return None;
+        }
+        let prepared = self.prepare_request(request.clone(), None).ok()?;
+        Some((request, prepared))
+    }
+
     pub(crate) fn invoke_admitted_attested_after_barrier<P: AttestationProofProducerV2 + ?Sized>(
         &mut self,
         invocation: super::InvocationId,
@@ -3322,6 +3704,21 @@ where
         proof_artifact: Option<ImportedBlobV2>,
     ) -> Result<CommittedRootTreeSliceV2, LocalRootTreeInvokeErrorV2> {
         let (prepared, refined) = self.refine_with_storage_witnesses_after_barrier(prepared)?;
+        self.accumulate_refined_after_barrier(
+            prepared,
+            refined,
+            receipt_verifications,
+            proof_artifact,
+        )
+    }
+
+    fn accumulate_refined_after_barrier(
+        &mut self,
+        prepared: PreparedWorkV2,
+        refined: RefinedServiceOutputV2,
+        receipt_verifications: &[super::ReceiptVerificationRequestV2],
+        proof_artifact: Option<ImportedBlobV2>,
+    ) -> Result<CommittedRootTreeSliceV2, LocalRootTreeInvokeErrorV2> {
         let PreparedWorkV2 {
             mut work,
             imports: _,
@@ -3664,16 +4061,6 @@ where
         )
     }
 
-    /// Finalized cross-root calls still waiting in the guest-owned inbox.
-    pub(crate) fn pending_inbox_calls(
-        &self,
-    ) -> Result<Vec<(super::CallId, u64)>, LocalRootTreeInvokeErrorV2> {
-        self.service
-            .accumulate_host()
-            .pending_inbox_calls()
-            .map_err(LocalRootTreeInvokeErrorV2::CorruptStore)
-    }
-
     /// Earliest durable call deadline, used only to avoid an unnecessary
     /// consensus barrier on every transport poll. The guest still decides
     /// expiration against the separately authenticated ambient slot.
@@ -3751,6 +4138,19 @@ where
             .map_err(LocalRootTreeInvokeErrorV2::CorruptStore)
     }
 
+    /// Queued inbox and ingress work in the root's weighted fair order.
+    pub fn pending_work(&self) -> Result<Vec<PendingWorkV2>, LocalRootTreeInvokeErrorV2> {
+        LocalWorkSchedulerV2::pending_work(self.service.accumulate_host().local_store())
+            .map(|pending| LocalWorkSchedulerV2::fair_order(pending, &self.work_fairness))
+            .map_err(LocalRootTreeInvokeErrorV2::Schedule)
+    }
+
+    pub fn backlog(&self) -> Result<RootBacklogV2, LocalRootTreeInvokeErrorV2> {
+        LocalWorkSchedulerV2::pending_work(self.service.accumulate_host().local_store())
+            .map(|pending| LocalWorkSchedulerV2::backlog(&pending, &self.work_fairness))
+            .map_err(LocalRootTreeInvokeErrorV2::Schedule)
+    }
+
     pub fn into_backend(self) -> B {
         let store = self.service.into_store();
         let (_, backend) = store.into_parts();
@@ -5,7 +5,7 @@
 //! service PVM's physical IC-5 Accumulate entry.
 
 use alloc::boxed::Box;
-use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::convert::Infallible;
@@ -23,7 +23,7 @@ use super::{
     DecodeError, DeliveryEnvelopeV2, DeliveryRecordV2, DirectIngressV2, ExternalActorDirectoryV2,
     ImportedActorV2, ImportedBlobV2, ImportedProgramV2, InboxRetirementV2, InvocationId,
     LocalJamStoreV2, LocalStoreReadErrorV2, MessageRecordV2, Origin, RefineImportsV2,
-    ServiceIdentityV2, StateKeyV2, V2Wire, WorkEnvelopeV2, WorkflowCheckpointV2,
+    ServiceIdentityV2, StateKeyV2, SubjectId, V2Wire, WorkEnvelopeV2, WorkflowCheckpointV2,
     WorkflowOperationV2, crdt_node_receipt_storage_key, crdt_node_storage_key,
     delivery_storage_key,
 };
@@ -104,6 +104,75 @@ impl From<LocalStoreReadErrorV2> for ScheduleErrorV2 {
     }
 }
 
+/// Dispatch class of queued work. Every queued item of a lower class runs
+/// before any item of a higher one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WorkPriorityV2 {
+    Operator,
+    Admin,
+    Normal,
+}
+
+/// Declared fairness inputs for one root. Every replica must use the same
+/// policy, since it decides the order in which queued work is refined.
+///
+/// `System` origins run as [`WorkPriorityV2::Operator`]; other origins are
+/// [`WorkPriorityV2::Normal`] unless declared. An actor's weight (default 1)
+/// is its share of dispatch slots against the other backlogged actors of
+/// the same class.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkFairnessPolicyV2 {
+    pub member_priorities: BTreeMap<SubjectId, WorkPriorityV2>,
+    pub actor_priorities: BTreeMap<ActorId, WorkPriorityV2>,
+    pub actor_weights: BTreeMap<ActorId, u32>,
+}
+
+impl WorkFairnessPolicyV2 {
+    pub fn priority(&self, origin: &Origin) -> WorkPriorityV2 {
+        match origin {
+            Origin::System => WorkPriorityV2::Operator,
+            Origin::Member(subject) => self.member_priorities.get(subject).copied(),
+            Origin::Actor(actor) => self.actor_priorities.get(actor).copied(),
+            Origin::Anonymous => None,
+        }
+        .unwrap_or(WorkPriorityV2::Normal)
+    }
+
+    pub fn weight(&self, actor: ActorId) -> u32 {
+        self.actor_weights.get(&actor).copied().unwrap_or(1).max(1)
+    }
+}
+
+/// Guest-committed row a queued work item is prepared from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PendingWorkSourceV2 {
+    Inbox(CallId),
+    Ingress(InvocationId),
+}
+
+/// One runnable queued item, read from guest-owned rows only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingWorkV2 {
+    pub source: PendingWorkSourceV2,
+    pub target: ActorId,
+    pub origin: Origin,
+    pub admitted_at: u64,
+    pub proof_requested: bool,
+}
+
+/// Queue depth of one root, as seen by [`LocalWorkSchedulerV2::pending_work`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RootBacklogV2 {
+    pub inbox: u64,
+    pub ingress: u64,
+    pub by_priority: BTreeMap<WorkPriorityV2, u64>,
+    pub by_actor: BTreeMap<ActorId, u64>,
+    pub oldest_admitted_at: Option<u64>,
+}
+
+/// Fixed-point unit of one dispatch slot in virtual finish tags.
+const FAIR_SHARE_SCALE: u128 = 1 << 32;
+
 pub struct LocalWorkSchedulerV2;
 
 impl LocalWorkSchedulerV2 {
@@ -634,6 +703,114 @@ impl LocalWorkSchedulerV2 {
         Ok(pending.into_iter().collect())
     }
 
+    /// Every runnable queued item of the root: finalized inbox rows and
+    /// admitted direct ingresses not yet consumed by an actor slice.
+    pub fn pending_work(store: &LocalJamStoreV2) -> Result<Vec<PendingWorkV2>, ScheduleErrorV2> {
+        let Some(header) = store.header()? else {
+            return Ok(Vec::new());
+        };
+        let mut pending = Vec::new();
+        for (call, admitted_at) in store.pending_inbox_calls()? {
+            let message = decode_row::<MessageRecordV2>(
+                store,
+                header.service_root,
+                &StateKeyV2::Inbox(call),
+            )?
+            .ok_or(ScheduleErrorV2::MissingInbox(call))?;
+            pending.push(PendingWorkV2 {
+                source: PendingWorkSourceV2::Inbox(call),
+                target: message.to,
+                origin: Origin::Actor(message.from),
+                admitted_at,
+                proof_requested: message.proof_requested,
+            });
+        }
+        for ingress in store.pending_ingresses()? {
+            pending.push(PendingWorkV2 {
+                source: PendingWorkSourceV2::Ingress(ingress.invocation),
+                target: ingress.target,
+                origin: ingress.origin,
+                admitted_at: ingress.logical_timeslot,
+                proof_requested: ingress.proof_requested,
+            });
+        }
+        Ok(pending)
+    }
+
+    /// Deterministic weighted fair order over queued work.
+    ///
+    /// Classes run strictly by priority. Within a class each target actor is
+    /// a flow whose `n`th item gets the virtual finish tag `n / weight`, and
+    /// items run by ascending tag, so a hot actor cannot delay another
+    /// backlogged actor by more than its share. Inside one actor's flow the
+    /// callers are interleaved round-robin, each caller keeping its own
+    /// admission order. The result depends only on the inputs, so replicas
+    /// that see the same committed queue pick the same work.
+    pub fn fair_order(
+        pending: Vec<PendingWorkV2>,
+        policy: &WorkFairnessPolicyV2,
+    ) -> Vec<PendingWorkV2> {
+        let mut flows: BTreeMap<(WorkPriorityV2, ActorId), BTreeMap<(u8, [u8; 32]), Vec<_>>> =
+            BTreeMap::new();
+        for work in pending {
+            flows
+                .entry((policy.priority(&work.origin), work.target))
+                .or_default()
+                .entry(caller_key(&work.origin))
+                .or_default()
+                .push(work);
+        }
+        let mut tagged = Vec::new();
+        for ((priority, actor), callers) in flows {
+            let mut queues: Vec<VecDeque<PendingWorkV2>> = callers
+                .into_values()
+                .map(|mut queue| {
+                    queue.sort_by_key(|work| (work.admitted_at, work.source));
+                    queue.into()
+                })
+                .collect();
+            let weight = u128::from(policy.weight(actor));
+            let mut slot = 0u128;
+            while !queues.is_empty() {
+                queues.retain_mut(|queue| {
+                    let Some(work) = queue.pop_front() else {
+                        return false;
+                    };
+                    slot += 1;
+                    tagged.push((
+                        (priority, slot * FAIR_SHARE_SCALE / weight, actor, slot),
+                        work,
+                    ));
+                    !queue.is_empty()
+                });
+            }
+        }
+        tagged.sort_unstable_by_key(|(tag, _)| *tag);
+        tagged.into_iter().map(|(_, work)| work).collect()
+    }
+
+    /// Summarize queued work for metrics.
+    pub fn backlog(pending: &[PendingWorkV2], policy: &WorkFairnessPolicyV2) -> RootBacklogV2 {
+        let mut backlog = RootBacklogV2::default();
+        for work in pending {
+            match work.source {
+                PendingWorkSourceV2::Inbox(_) => backlog.inbox += 1,
+                PendingWorkSourceV2::Ingress(_) => backlog.ingress += 1,
+            }
+            *backlog
+                .by_priority
+                .entry(policy.priority(&work.origin))
+                .or_default() += 1;
+            *backlog.by_actor.entry(work.target).or_default() += 1;
+            backlog.oldest_admitted_at = Some(
+                backlog
+                    .oldest_admitted_at
+                    .map_or(work.admitted_at, |oldest| oldest.min(work.admitted_at)),
+            );
+        }
+        backlog
+    }
+
     fn prepare_resume_outcome(
         store: &LocalJamStoreV2,
         invocation: InvocationId,
@@ -1282,6 +1459,15 @@ fn actor_states(
         })
 }
 
+fn caller_key(origin: &Origin) -> (u8, [u8; 32]) {
+    match origin {
+        Origin::Anonymous => (0, [0; 32]),
+        Origin::Member(subject) => (1, subject.0),
+        Origin::Actor(actor) => (2, actor.0),
+        Origin::System => (3, [0; 32]),
+    }
+}
+
 fn dynamic_method(payload: &[u8]) -> Option<String> {
     if payload.first() != Some(&crate::value::TAG_DYNAMIC) {
         return None;
@@ -1410,4 +1596,58 @@ mod tests {
             "ordinary reply resumes remain bound to their captured checkpoint"
         );
     }
+
+    #[test]
+    fn fair_order_shares_slots_by_weight_and_runs_declared_priorities_first() {
+        let (hot, light, audited) = (ActorId([1; 32]), ActorId([2; 32]), ActorId([3; 32]));
+        let (flood, other, admin) = (ActorId([4; 32]), ActorId([5; 32]), SubjectId([6; 32]));
+        let mut next = 0u8;
+        let mut work = |target, origin, admitted_at| {
+            next += 1;
+            PendingWorkV2 {
+                source: PendingWorkSourceV2::Inbox(CallId([next; 32])),
+                target,
+                origin,
+                admitted_at,
+                proof_requested: false,
+            }
+        };
+        let pending = vec![
+            work(hot, Origin::Actor(flood), 1),
+            work(hot, Origin::Actor(flood), 2),
+            work(hot, Origin::Actor(flood), 3),
+            work(hot, Origin::Actor(other), 4),
+            work(light, Origin::Anonymous, 5),
+            work(light, Origin::Anonymous, 6),
+            work(light, Origin::Anonymous, 7),
+            work(audited, Origin::Member(admin), 8),
+        ];
+        let mut policy = WorkFairnessPolicyV2::default();
+        policy
+            .member_priorities
+            .insert(admin, WorkPriorityV2::Admin);
+        policy.actor_weights.insert(light, 2);
+
+        let order: Vec<_> = LocalWorkSchedulerV2::fair_order(pending.clone(), &policy)
+            .into_iter()
+            .map(|work| work.admitted_at)
+            .collect();
+        // Admin first; then `light` at twice `hot`'s rate, with `hot`'s two
+        // callers interleaved in their own admission order.
+        assert_eq!(order, vec![8, 5, 1, 6, 7, 4, 2, 3]);
+
+        let mut reversed = pending.clone();
+        reversed.reverse();
+        assert_eq!(
+            LocalWorkSchedulerV2::fair_order(reversed, &policy),
+            LocalWorkSchedulerV2::fair_order(pending.clone(), &policy),
+            "the order depends only on the queued set"
+        );
+
+        let backlog = LocalWorkSchedulerV2::backlog(&pending, &policy);
+        assert_eq!((backlog.inbox, backlog.ingress), (8, 0));
+        assert_eq!(backlog.by_actor[&hot], 4);
+        assert_eq!(backlog.by_priority[&WorkPriorityV2::Admin], 1);
+        assert_eq!(backlog.oldest_admitted_at, Some(1));
+    }
 }
@@ -868,6 +868,61 @@ impl<R: RefineProtocolHostV2, A: AccumulateProtocolHostV2> JamServiceV2<R, A> {
         decode_refined_service_output(output)
     }
 
+    /// Refine independent work items concurrently, on up to one scoped
+    /// thread per available core. Refine is pure over its inputs, so every
+    /// result equals the serial [`Self::refine_actor_tree`] result for the
+    /// same item. Results are returned in input order.
+    pub fn refine_actor_trees(
+        &self,
+        items: &[(&WorkEnvelopeV2, &RefineImportsV2)],
+    ) -> Vec<Result<RefinedServiceOutputV2, ServiceDispatchError>>
+    where
+        R: Sync,
+    {
+        let mut results = Vec::with_capacity(items.len());
+        let mut runnable = Vec::with_capacity(items.len());
+        for (index, (work, _)) in items.iter().enumerate() {
+            match self.validate_service_identity(&work.service) {
+                Ok(()) => runnable.push(index),
+                Err(error) => results.push((index, Err(error))),
+            }
+        }
+        let workers = std::thread::available_parallelism()
+            .map_or(1, usize::from)
+            .clamp(1, runnable.len().max(1));
+        // Only the PVM image and the pure Refine host cross threads; the
+        // Accumulate host stays with the caller.
+        let (pvm, host, gas_limit) = (&self.pvm, &self.refine_host, self.gas_schedule.refine);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = runnable
+                .chunks(runnable.len().div_ceil(workers).max(1))
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|&index| {
+                                let (work, imports) = items[index];
+                                let refined = pvm
+                                    .refine_actor_tree(&work.encode(), imports, gas_limit, host)
+                                    .map_err(ServiceDispatchError::Pvm)
+                                    .and_then(decode_refined_service_output);
+                                (index, refined)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            for handle in handles {
+                match handle.join() {
+                    Ok(refined) => results.extend(refined),
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+        });
+        results.sort_unstable_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
     fn refine_actor_tree_traced(
         &self,
         work: &WorkEnvelopeV2,
@@ -1579,6 +1634,23 @@ where
             .map_err(ReplicatedServiceErrorV2::Dispatch)
     }
 
+    /// Concurrent variant of [`Self::refine_actor_tree_after_barrier`]. The
+    /// caller has already caught up behind the admission barrier.
+    #[cfg(feature = "storage")]
+    pub(crate) fn refine_actor_trees_after_barrier(
+        &self,
+        items: &[(&WorkEnvelopeV2, &RefineImportsV2)],
+    ) -> Vec<Result<RefinedServiceOutputV2, ReplicatedServiceErrorV2<L::Error>>>
+    where
+        R: Sync,
+    {
+        self.service
+            .refine_actor_trees(items)
+            .into_iter()
+            .map(|refined| refined.map_err(ReplicatedServiceErrorV2::Dispatch))
+            .collect()
+    }
+
     #[cfg(feature = "storage")]
     pub(crate) fn refine_actor_tree_after_barrier(
         &self,
@@ -0,0 +1,69 @@
+//! Wire constants of the `VTA1`/`VTR1` production trust socket.
+//!
+//! `docs/runtime-v2.md` is the specification. The space daemon's client and
+//! the reference `vos-authority` server both frame requests from this one
+//! table, so a new kind or result byte cannot drift between them.
+//!
+//! A request is `VTA1 | u16_le(version) | u8(kind) | u32_le(payload_len) |
+//! payload`; a response is `VTR1 | u16_le(version) | request_hash[32] |
+//! policy_id[32] | u8(result) | [u64_le(slot)]`.
+
+use super::Hash;
+
+pub const REQUEST_MAGIC: [u8; 4] = *b"VTA1";
+pub const RESPONSE_MAGIC: [u8; 4] = *b"VTR1";
+pub const PROTOCOL_VERSION: u16 = 1;
+/// Magic, version, kind and payload length.
+pub const REQUEST_HEADER_BYTES: usize = 11;
+/// A response without the trailing slot.
+pub const RESPONSE_BYTES: usize = 71;
+/// A current-slot response.
+pub const RESPONSE_WITH_SLOT_BYTES: usize = RESPONSE_BYTES + 8;
+/// Protocol ceiling on one request frame. Servers may enforce less.
+pub const MAX_FRAME_BYTES: usize = 64 * 1024 * 1024;
+
+pub const QUERY_POLICY: u8 = 0;
+pub const CURRENT_TIMESLOT: u8 = 1;
+pub const VERIFY_TIMESLOT: u8 = 2;
+pub const VERIFY_PROOF: u8 = 3;
+pub const VERIFY_INSTALL: u8 = 4;
+pub const VERIFY_UPGRADE: u8 = 5;
+pub const VERIFY_ROLE: u8 = 6;
+pub const VERIFY_RECEIPT: u8 = 7;
+
+pub const AUTHORIZED: u8 = 0;
+pub const DENIED: u8 = 1;
+pub const UNAVAILABLE: u8 = 2;
+pub const NO_TIMESLOT: u8 = 3;
+pub const TIMESLOT: u8 = 4;
+pub const POLICY: u8 = 5;
+
+/// The commitment every response repeats: BLAKE2b-256 over the domain
+/// followed by the complete request bytes.
+pub fn request_hash(request: &[u8]) -> Hash {
+    Hash::digest(b"vos/production-trust-socket/request/v1", &[request])
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn empty_policy_query_matches_the_documented_vector() {
+        let mut request = Vec::from(REQUEST_MAGIC);
+        request.extend_from_slice(&PROTOCOL_VERSION.to_le_bytes());
+        request.push(QUERY_POLICY);
+        request.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(request.len(), REQUEST_HEADER_BYTES);
+        assert_eq!(
+            request_hash(&request).0,
+            [
+                0xa5, 0xee, 0x8b, 0xe4, 0xab, 0xb9, 0x96, 0xfd, 0x37, 0x35, 0x97, 0x0c, 0xd7, 0xb5,
+                0xa5, 0x36, 0x32, 0xaf, 0xef, 0x7c, 0xb7, 0xa5, 0x48, 0xe1, 0x31, 0x4d, 0x9c, 0x6e,
+                0xf3, 0x9e, 0xce, 0x35,
+            ],
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Process-wide barrier between durable writes and online snapshots.
+//!
+//! Every durable write path (redb write transactions, v2 root image and
+//! side-store commits) holds a [`WriteGuard`] from just before it starts
+//! writing until its commit returns. [`quiesce`] waits for the in-flight
+//! guards to drain and blocks new ones, so whatever the caller copies while
+//! it holds [`Quiesced`] is a point-in-time image of every database and file
+//! this process persists: a committed state, never a torn one.
+//!
+//! Writers stall (rather than fail) while a snapshot holds the barrier, so
+//! the holder should only clone files and release it.
+
+use std::cell::Cell;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+static BARRIER: RwLock<()> = RwLock::new(());
+
+thread_local! {
+    /// Guards this thread already holds. A nested write path re-enters
+    /// without touching the lock: a second shared acquisition behind a
+    /// waiting [`quiesce`] would deadlock on writer-preferring platforms.
+    static HELD: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Held by one durable write. Acquire it last, immediately before the
+/// write transaction, and drop it as soon as the commit returns.
+pub struct WriteGuard {
+    _shared: Option<RwLockReadGuard<'static, ()>>,
+}
+
+impl Drop for WriteGuard {
+    fn drop(&mut self) {
+        HELD.with(|held| held.set(held.get() - 1));
+    }
+}
+
+/// Enter the barrier for one durable write.
+pub fn enter() -> WriteGuard {
+    let nested = HELD.with(|held| {
+        let depth = held.get();
+        held.set(depth + 1);
+        depth > 0
+    });
+    WriteGuard {
+        _shared: (!nested).then(|| BARRIER.read().unwrap_or_else(PoisonError::into_inner)),
+    }
+}
+
+/// Held while a snapshot copies persisted state; no durable write runs.
+pub struct Quiesced {
+    _exclusive: RwLockWriteGuard<'static, ()>,
+}
+
+/// Wait for in-flight durable writes to finish and hold off new ones until
+/// the returned guard drops. Must not be called from a thread holding a
+/// [`WriteGuard`].
+pub fn quiesce() -> Quiesced {
+    assert_eq!(
+        HELD.with(Cell::get),
+        0,
+        "write_barrier::quiesce called inside a durable write"
+    );
+    Quiesced {
+        _exclusive: BARRIER.write().unwrap_or_else(PoisonError::into_inner),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    // The barrier is process-global; serialize the tests that drive it.
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn quiesce_blocks_new_writes_until_released() {
+        let _serial = SERIAL.lock().unwrap_or_else(PoisonError::into_inner);
+        let quiesced = quiesce();
+        let wrote = Arc::new(AtomicBool::new(false));
+        let writer = {
+            let wrote = wrote.clone();
+            std::thread::spawn(move || {
+                let _guard = enter();
+                wrote.store(true, Ordering::SeqCst);
+            })
+        };
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!wrote.load(Ordering::SeqCst));
+        drop(quiesced);
+        writer.join().unwrap();
+        assert!(wrote.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn nested_writes_on_one_thread_do_not_reacquire() {
+        let _serial = SERIAL.lock().unwrap_or_else(PoisonError::into_inner);
+        let outer = enter();
+        let inner = enter();
+        assert!(inner._shared.is_none());
+        drop(inner);
+        drop(outer);
+        drop(quiesce());
+    }
+}
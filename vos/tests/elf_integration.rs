@@ -1890,6 +1890,9 @@ fn crdt_counter_converges_across_nodes_live() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -1905,6 +1908,9 @@ fn crdt_counter_converges_across_nodes_live() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     // ── Nodes + actors ─────────────────────────────────────────
@@ -2103,6 +2109,9 @@ fn crdt_scheduler_install_propagates_across_nodes() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -2116,6 +2125,9 @@ fn crdt_scheduler_install_propagates_across_nodes() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     // Empty initial children so install(N) is the only state
@@ -2296,6 +2308,9 @@ fn crdt_counter_burst_converges_under_concurrent_load() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -2311,6 +2326,9 @@ fn crdt_counter_burst_converges_under_concurrent_load() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     let mut node_a = VosNode::with_prefix(prefix_a);
@@ -3199,6 +3217,9 @@ fn crdt_counter_offline_node_catches_up_after_restart() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -3212,6 +3233,9 @@ fn crdt_counter_offline_node_catches_up_after_restart() {
         listen: vec![listen.clone()],
         bootstrap: vec![a_dial.clone()],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     // Snapshot B's bound address now so A's restart can dial
     // it directly — without that, A2 would only discover B
@@ -3316,6 +3340,9 @@ fn crdt_counter_offline_node_catches_up_after_restart() {
         listen: vec![listen],
         bootstrap: vec![b_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     let mut node_a2 = VosNode::with_prefix(prefix_a);
@@ -3877,6 +3904,9 @@ fn raft_counter_three_node_replicates_state_to_all_replicas() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -3891,6 +3921,9 @@ fn raft_counter_three_node_replicates_state_to_all_replicas() {
         listen: vec![listen.clone()],
         bootstrap: vec![a_dial.clone()],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let b_listen = wait_for(
         || net_b.listen_addrs().into_iter().next(),
@@ -3905,6 +3938,9 @@ fn raft_counter_three_node_replicates_state_to_all_replicas() {
         listen: vec![listen],
         bootstrap: vec![a_dial, b_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     // ── Three VosNodes. ──────────────────────────────────────
@@ -4158,6 +4194,9 @@ fn raft_three_node_cluster_compacts_log_after_replication() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -4172,6 +4211,9 @@ fn raft_three_node_cluster_compacts_log_after_replication() {
         listen: vec![listen.clone()],
         bootstrap: vec![a_dial.clone()],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let b_listen = wait_for(
         || net_b.listen_addrs().into_iter().next(),
@@ -4186,6 +4228,9 @@ fn raft_three_node_cluster_compacts_log_after_replication() {
         listen: vec![listen],
         bootstrap: vec![a_dial, b_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     let mut node_a = VosNode::with_prefix(prefix_a);
@@ -4575,6 +4620,9 @@ fn raft_dynamic_join_grows_single_node_cluster_to_three() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     }));
     let a_addr = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -4593,6 +4641,7 @@ fn raft_dynamic_join_grows_single_node_cluster_to_three() {
             replication_id: rep_id,
             election_timeout_ms: (50, 150),
             heartbeat_interval_ms: 20,
+            witnesses: Vec::new(),
         },
         Some(net_a.clone()),
         None,
@@ -4611,6 +4660,9 @@ fn raft_dynamic_join_grows_single_node_cluster_to_three() {
         listen: vec![listen.clone()],
         bootstrap: vec![a_dial.clone()],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     }));
     let b_addr = wait_for(
         || net_b.listen_addrs().into_iter().next(),
@@ -4642,6 +4694,7 @@ fn raft_dynamic_join_grows_single_node_cluster_to_three() {
             // Long timeout so B doesn't self-elect before joining.
             election_timeout_ms: (5_000, 10_000),
             heartbeat_interval_ms: 1_000,
+            witnesses: Vec::new(),
         },
         Some(net_b.clone()),
         None,
@@ -4678,6 +4731,9 @@ fn raft_dynamic_join_grows_single_node_cluster_to_three() {
         listen: vec![listen],
         bootstrap: vec![a_dial, b_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     }));
     wait_for(
         || {
@@ -4700,6 +4756,7 @@ fn raft_dynamic_join_grows_single_node_cluster_to_three() {
             replication_id: rep_id,
             election_timeout_ms: (5_000, 10_000),
             heartbeat_interval_ms: 1_000,
+            witnesses: Vec::new(),
         },
         Some(net_c.clone()),
         None,
@@ -4827,6 +4884,9 @@ fn raft_join_req_wire_path_grows_cluster_via_libp2p() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     }));
     let a_addr = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -4843,6 +4903,7 @@ fn raft_join_req_wire_path_grows_cluster_via_libp2p() {
             replication_id: rep_id,
             election_timeout_ms: (50, 150),
             heartbeat_interval_ms: 20,
+            witnesses: Vec::new(),
         },
         Some(net_a.clone()),
         None,
@@ -4863,6 +4924,9 @@ fn raft_join_req_wire_path_grows_cluster_via_libp2p() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     }));
     wait_for(
         || {
@@ -4900,6 +4964,7 @@ fn raft_join_req_wire_path_grows_cluster_via_libp2p() {
             replication_id: rep_id,
             election_timeout_ms: (5_000, 10_000),
             heartbeat_interval_ms: 1_000,
+            witnesses: Vec::new(),
         },
         Some(net_b.clone()),
         None,
@@ -4986,6 +5051,9 @@ fn raft_status_req_returns_absent_for_unknown_group() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     }));
     let a_addr = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -5005,6 +5073,7 @@ fn raft_status_req_returns_absent_for_unknown_group() {
             replication_id: group_x,
             election_timeout_ms: (5_000, 10_000),
             heartbeat_interval_ms: 1_000,
+            witnesses: Vec::new(),
         },
         Some(net_a.clone()),
         None,
@@ -5017,6 +5086,9 @@ fn raft_status_req_returns_absent_for_unknown_group() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     }));
     wait_for(
         || net_b.peer_for_prefix(prefix_a).is_some().then_some(()),
@@ -5102,6 +5174,9 @@ fn manifest_req_returns_installed_provider_payload() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     }));
     let a_addr = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -5137,6 +5212,9 @@ version = "0.1.0"
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     }));
     wait_for(
         || net_b.peer_for_prefix(prefix_a).is_some().then_some(()),
@@ -5247,6 +5325,9 @@ fn hyperspace_resolve_returns_remote_host_prefix() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -5262,6 +5343,9 @@ fn hyperspace_resolve_returns_remote_host_prefix() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     // ── Nodes + registries ─────────────────────────────────────
@@ -5451,6 +5535,9 @@ fn crdt_registry_writes_and_deletes_converge_across_replicas() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -5464,6 +5551,9 @@ fn crdt_registry_writes_and_deletes_converge_across_replicas() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     // ── Two registry replicas in one CRDT group ────────────────
@@ -6007,6 +6097,9 @@ fn cross_space_bridge_forward_dispatches_to_local_target() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -6022,6 +6115,9 @@ fn cross_space_bridge_forward_dispatches_to_local_target() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     // Each bridge registers under a unique name in the hyperspace
@@ -7889,6 +7985,9 @@ fn clerk_ledger_two_bank_federation() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -7904,6 +8003,9 @@ fn clerk_ledger_two_bank_federation() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     // ── ServiceIds derived from federation-visible names ────────
@@ -10140,6 +10242,9 @@ fn raft_clerk_ledger_operator_gate_under_leader_forward() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -10154,6 +10259,9 @@ fn raft_clerk_ledger_operator_gate_under_leader_forward() {
         listen: vec![listen.clone()],
         bootstrap: vec![a_dial.clone()],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     wait_for(
         || net_b.listen_addrs().into_iter().next(),
@@ -10442,6 +10550,9 @@ fn raft_clerk_ledger_operator_gate_under_leader_forward() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let mut node_b2 = VosNode::with_prefix(prefix_b);
     node_b2.attach_network(net_b2);
@@ -10599,6 +10710,9 @@ fn raft_clerk_settle_bilateral_settlement() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -10613,6 +10727,9 @@ fn raft_clerk_settle_bilateral_settlement() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     wait_for(
         || net_b.listen_addrs().into_iter().next(),
@@ -11030,6 +11147,9 @@ fn multi_node_three_space_settlement_capstone() {
             listen: vec![listen.clone()],
             bootstrap,
             auto_dial_mdns: true,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
         let addr = wait_for(
             || net.listen_addrs().into_iter().next(),
@@ -1,5 +1,5 @@
 use vos::prelude::*;
-use vos::storage::StorageMap;
+use vos::storage::{StorageMap, StorageSet};
 use vos::value::Value;
 
 #[actor]
@@ -7,6 +7,10 @@ pub struct WorkflowV2 {
     value: u32,
     #[storage(prefix = "workflow-v2/counters/")]
     counters: StorageMap<u64, u32>,
+    #[storage(prefix = "workflow-v2/batch-holds/")]
+    holds: StorageMap<[u8; 32], u32>,
+    #[storage(prefix = "workflow-v2/batch-settled/")]
+    settled: StorageSet<[u8; 32]>,
 }
 
 #[messages]
@@ -15,6 +19,8 @@ impl WorkflowV2 {
         Self {
             value: 0,
             counters: StorageMap::default(),
+            holds: StorageMap::default(),
+            settled: StorageSet::default(),
         }
     }
 
@@ -47,10 +53,68 @@ impl WorkflowV2 {
         let child = Self {
             value: initial,
             counters: StorageMap::default(),
+            holds: StorageMap::default(),
+            settled: StorageSet::default(),
         };
         ctx.spawn::<WorkflowV2Ref>(name, &child).await.is_ok()
     }
 
+    #[msg]
+    fn balance(&self) -> u32 {
+        self.value
+    }
+
+    /// Batch prepare: hold `amount` for `batch`, taking it out of the balance
+    /// when `debit`. A settled batch is never prepared again.
+    #[msg]
+    fn reserve(&mut self, batch: Vec<u8>, amount: u32, debit: bool) -> bool {
+        let Ok(batch) = <[u8; 32]>::try_from(batch) else {
+            return false;
+        };
+        if self.settled.contains(&batch) || (debit && self.value < amount) {
+            return false;
+        }
+        if debit {
+            self.value -= amount;
+        }
+        self.holds.insert(&batch, &amount);
+        true
+    }
+
+    /// Batch commit: a credit lands in the balance; a debit was taken at
+    /// reserve.
+    #[msg]
+    fn apply(&mut self, batch: Vec<u8>, debit: bool) -> bool {
+        let Ok(batch) = <[u8; 32]>::try_from(batch) else {
+            return false;
+        };
+        if let Some(amount) = self.holds.get(&batch) {
+            self.holds.remove(&batch);
+            if !debit {
+                self.value += amount;
+            }
+        }
+        self.settled.insert(&batch);
+        true
+    }
+
+    /// Batch compensate: return a debit hold, if any was taken, and tombstone
+    /// the batch so a late prepare is refused.
+    #[msg]
+    fn release(&mut self, batch: Vec<u8>, debit: bool) -> bool {
+        let Ok(batch) = <[u8; 32]>::try_from(batch) else {
+            return false;
+        };
+        if let Some(amount) = self.holds.get(&batch) {
+            self.holds.remove(&batch);
+            if debit {
+                self.value += amount;
+            }
+        }
+        self.settled.insert(&batch);
+        true
+    }
+
     #[msg]
     fn peer_value(&self) -> u32 {
         7
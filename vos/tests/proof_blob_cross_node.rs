@@ -63,6 +63,9 @@ fn proof_blob_fan_out_fetches_from_peer() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let a_listen = wait_for(
         || net_a.listen_addrs().into_iter().next(),
@@ -78,6 +81,9 @@ fn proof_blob_fan_out_fetches_from_peer() {
         listen: vec![listen],
         bootstrap: vec![a_dial],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     // ── Nodes ───────────────────────────────────────────────────
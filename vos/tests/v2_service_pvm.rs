@@ -27,23 +27,26 @@ use vos::v2::{
     CommittedAccumulateEntryV2, CommittedAccumulateLogV2, CommittedImageStoreV2,
     CommittedServiceImageHostV2, CommittedServiceSnapshotV2, ConsistencyBaseV2, ConsistencyModeV2,
     ContinuationChangeV2, ContinuationSnapshotV2, CrdtChangeV2, DeploymentId, DirectIngressV2,
-    DurableJamStoreV2, ExternalActorBindingV2, FileCommittedImageStoreV2, GasAccountingV2,
-    GasScheduleV2, Hash, ImportedActorV2, ImportedBlobV2, ImportedProgramV2, InboxDrainOutcomeV2,
-    InvocationId, JamServiceV2, LocalJamStoreHostV2, LocalJamStoreSnapshotV2, LocalJamStoreV2,
-    LocalRootTreeConfigErrorV2, LocalRootTreeConfigV2, LocalRootTreeInvokeErrorV2,
+    DurableJamStoreV2, ExternalActorBindingV2, FileCommittedImageStoreV2, FileRootBatchJournalV2,
+    GasAccountingV2, GasScheduleV2, Hash, ImportedActorV2, ImportedBlobV2, ImportedProgramV2,
+    InboxDrainOutcomeV2, InvocationId, JamServiceV2, LocalJamStoreHostV2, LocalJamStoreSnapshotV2,
+    LocalJamStoreV2, LocalRootTreeConfigErrorV2, LocalRootTreeConfigV2, LocalRootTreeInvokeErrorV2,
     LocalRootTreeOpenErrorV2, LocalRootTreeServiceV2, LocalTransportV2, LocalWorkRequestV2,
-    LocalWorkSchedulerV2, MessageRecordV2, MethodPolicyV2, NoRefineProtocolHostV2, Origin,
-    PackageManifestV2, PackageRolePoliciesV2, PackageTaskDependencyV2, PrivateIngressStagingV2,
-    ProducerId, ProductionTrustDecisionV2, ProductionTrustErrorV2, ProductionTrustV2, ProgramId,
-    ProofArtifactStoreV2, ProofVerificationRequestV2, PublishedEffectsV2,
-    ReceiptVerificationRequestV2, RefineImportsV2, RefineOutputV2, ReplicatedJamServiceV2,
-    ReplicatedServiceErrorV2, ReplyRecordV2, RoleAuthorityBindingV2,
-    RoleAuthorityInviteRedemptionV2, RoleAuthorityMutationV2, RoleAuthorizationClaimV2,
-    RoleCredentialV2, RoleCredentialVerificationRequestV2, RootServiceId, RootTreeAttestedResultV2,
-    RootTreeInvocationV2, ScheduleErrorV2, ServiceDispatchError, ServiceGenesisV2,
-    ServiceIdentityV2, ServicePvmErrorV2, ServicePvmV2, StateKeyV2, SubjectId, SystemCapabilityId,
-    TaskDependencyV2, TransitionV2, V2Wire, VosPackageV2, WorkEnvelopeV2, WorkflowOperationV2,
-    artifact_hash, public_policy_hash, space_role_policy_hash,
+    LocalWorkSchedulerV2, MemoryRootBatchJournalV2, MessageRecordV2, MethodPolicyV2,
+    NoRefineProtocolHostV2, Origin, PackageManifestV2, PackageRolePoliciesV2,
+    PackageTaskDependencyV2, PrivateIngressStagingV2, ProducerId, ProductionTrustDecisionV2,
+    ProductionTrustErrorV2, ProductionTrustV2, ProgramId, ProofArtifactStoreV2,
+    ProofVerificationRequestV2, PublishedEffectsV2, ReceiptVerificationRequestV2, RefineImportsV2,
+    RefineOutputV2, ReplicatedJamServiceV2, ReplicatedServiceErrorV2, ReplyRecordV2,
+    RoleAuthorityBindingV2, RoleAuthorityInviteRedemptionV2, RoleAuthorityMutationV2,
+    RoleAuthorizationClaimV2, RoleCredentialV2, RoleCredentialVerificationRequestV2,
+    RootBatchCallV2, RootBatchDecisionV2, RootBatchErrorV2, RootBatchJournalV2,
+    RootBatchParticipantV2, RootBatchPhaseV2, RootBatchRecoveryV2, RootBatchStepV2, RootBatchV2,
+    RootServiceId, RootTreeAttestedResultV2, RootTreeInvocationV2, ScheduleErrorV2,
+    ServiceDispatchError, ServiceGenesisV2, ServiceIdentityV2, ServicePvmErrorV2, ServicePvmV2,
+    StateKeyV2, SubjectId, SystemCapabilityId, TaskDependencyV2, TransitionV2, V2Wire,
+    VosPackageV2, WorkEnvelopeV2, WorkflowOperationV2, artifact_hash, public_policy_hash,
+    space_role_policy_hash,
 };
 use vos::{
     Decode, Encode,
@@ -1375,6 +1378,7 @@ fn attested_root_fixture(
         },
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let mut arguments = vec![vos::value::TAG_DYNAMIC];
     arguments.extend_from_slice(&Msg::new("attested_value").encode());
@@ -1702,6 +1706,7 @@ fn node_raft_registration_installs_production_trust_before_replay_and_promotion(
         heartbeat_interval_ms: 5,
         replication_id: [0x94; 32],
         propose_timeout_ms: 2_000,
+        witnesses: Vec::new(),
     };
     let trust = Arc::new(TestProductionTrust::new(0x95, 77, true));
     let proof = canonical_test_proof_manifest(0x96);
@@ -1770,6 +1775,7 @@ fn node_raft_registration_installs_production_trust_before_replay_and_promotion(
             heartbeat_interval_ms: 5,
             replication_id: [0x99; 32],
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         },
         ServiceId::new(member, 0x3394),
         false,
@@ -1811,6 +1817,9 @@ fn fresh_production_joiner_must_match_policy_before_voter_promotion() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let deadline = std::time::Instant::now() + Duration::from_secs(15);
     let leader_address = loop {
@@ -1826,6 +1835,9 @@ fn fresh_production_joiner_must_match_policy_before_voter_promotion() {
         listen: vec![listen],
         bootstrap: vec![leader_address],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     let directory = std::env::temp_dir().join(format!(
@@ -1868,6 +1880,7 @@ fn fresh_production_joiner_must_match_policy_before_voter_promotion() {
                 heartbeat_interval_ms: 5,
                 replication_id,
                 propose_timeout_ms: 2_000,
+                witnesses: Vec::new(),
             },
             route,
             true,
@@ -1918,6 +1931,7 @@ fn fresh_production_joiner_must_match_policy_before_voter_promotion() {
                 heartbeat_interval_ms: 20,
                 replication_id,
                 propose_timeout_ms: 2_000,
+                witnesses: Vec::new(),
             },
             joiner_route,
             true,
@@ -1998,6 +2012,9 @@ fn promoted_voter_keeps_raft_handler_while_application_catch_up_retries() {
         listen: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
         bootstrap: vec![],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let directory = std::env::temp_dir().join(format!(
         "vos-v2-promoted-catch-up-{}-{}",
@@ -2026,6 +2043,7 @@ fn promoted_voter_keeps_raft_handler_while_application_catch_up_retries() {
             heartbeat_interval_ms: 20,
             replication_id,
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         },
         route,
         true,
@@ -2076,6 +2094,9 @@ fn persisted_voter_keeps_raft_handler_while_service_open_retries() {
         listen: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
         bootstrap: vec![],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let directory = std::env::temp_dir().join(format!(
         "vos-v2-persisted-open-recovery-{}-{}",
@@ -2098,6 +2119,7 @@ fn persisted_voter_keeps_raft_handler_while_service_open_retries() {
         heartbeat_interval_ms: 5,
         replication_id,
         propose_timeout_ms: 2_000,
+        witnesses: Vec::new(),
     };
     let trust = Arc::new(TestProductionTrust::new(0xa4, 77, true));
     let log = RaftAccumulateLogV2::from_db_arc(db.clone(), raft_config.clone()).unwrap();
@@ -2169,6 +2191,9 @@ fn speculative_removal_keeps_persisted_voter_while_recovery_retries() {
         listen: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
         bootstrap: vec![],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let directory = std::env::temp_dir().join(format!(
         "vos-v2-speculative-removal-recovery-{}-{}",
@@ -2228,6 +2253,7 @@ fn speculative_removal_keeps_persisted_voter_while_recovery_retries() {
             heartbeat_interval_ms: 20,
             replication_id,
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         },
         route,
         true,
@@ -2275,6 +2301,9 @@ fn immediate_registration_keeps_speculative_joint_inclusion_private() {
         listen: vec!["/ip4/127.0.0.1/tcp/0".parse().unwrap()],
         bootstrap: vec![],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let directory = std::env::temp_dir().join(format!(
         "vos-v2-speculative-inclusion-recovery-{}-{}",
@@ -2300,6 +2329,7 @@ fn immediate_registration_keeps_speculative_joint_inclusion_private() {
         heartbeat_interval_ms: 5,
         replication_id,
         propose_timeout_ms: 2_000,
+        witnesses: Vec::new(),
     };
     drop(
         LocalRootTreeServiceV2::open_raft_production(
@@ -2364,6 +2394,7 @@ fn immediate_registration_keeps_speculative_joint_inclusion_private() {
             heartbeat_interval_ms: 20,
             replication_id,
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         },
         route,
         true,
@@ -2459,6 +2490,458 @@ fn same_actor_storage_reads_observe_earlier_inline_writes() {
     );
 }
 
+fn public_request(invocation: u8, target: ActorId, msg: Msg) -> LocalWorkRequestV2 {
+    let method = msg.name.clone();
+    let mut arguments = vec![vos::value::TAG_DYNAMIC];
+    arguments.extend_from_slice(&msg.encode());
+    LocalWorkRequestV2 {
+        invocation: InvocationId([invocation; 32]),
+        workflow_step: 0,
+        logical_timeslot: 20 + u64::from(invocation),
+        target,
+        method,
+        arguments,
+        origin: Origin::Anonymous,
+        authorization: AuthorizationEvidenceV2::Public,
+        causal_parent: None,
+        parent_call: None,
+        causal_context: None,
+        awaited_reply: None,
+        awaited_timeout: None,
+        imported_blobs: vec![],
+        proof_requested: false,
+    }
+}
+
+/// Commit `setup` one at a time, admit `queued`, then drain the queue either
+/// one ingress at a time or as one batch. Returns every drained receipt and
+/// reply with the final store header.
+fn drain_admitted(
+    config: LocalRootTreeConfigV2,
+    setup: &[LocalWorkRequestV2],
+    queued: &[LocalWorkRequestV2],
+    batched: bool,
+) -> (
+    Vec<(AccumulationReceiptV2, Option<Vec<u8>>)>,
+    vos::v2::StoreHeaderV2,
+) {
+    let mut service =
+        LocalRootTreeServiceV2::open(config, FailableCommittedImages::default()).unwrap();
+    for request in setup {
+        service.invoke(request.clone()).unwrap();
+    }
+    for request in queued {
+        assert!(!service.admit_ingress(request).unwrap());
+    }
+    let invocations = queued
+        .iter()
+        .map(|request| request.invocation)
+        .collect::<Vec<_>>();
+    let committed = if batched {
+        service.invoke_admitted_batch(&invocations).unwrap()
+    } else {
+        invocations
+            .iter()
+            .map(|invocation| service.invoke_admitted(*invocation))
+            .collect()
+    };
+    let drained = committed
+        .into_iter()
+        .map(|committed| {
+            let committed = committed.expect("every queued ingress commits");
+            let reply = committed.published.reply.map(|reply| reply.result);
+            (committed.receipt, reply)
+        })
+        .collect();
+    (drained, service.store().header().unwrap().unwrap())
+}
+
+#[test]
+fn batched_linear_ingresses_commit_exactly_the_serial_receipts() {
+    let (config, template) = attested_root_fixture(ConsistencyModeV2::Local, 0x5a);
+    let root = template.target;
+    let setup = ["a", "b"].map(|name| {
+        public_request(
+            name.as_bytes()[0],
+            root,
+            Msg::new("spawn_child")
+                .with("name", name)
+                .with("initial", 0u32),
+        )
+    });
+    let left = ActorId::owned_child(root, "a");
+    let right = ActorId::owned_child(root, "b");
+    // The first three touch disjoint actors and share a wave; the fourth
+    // conflicts with the first and starts the next one.
+    let queued = [
+        public_request(0x61, left, Msg::new("increment").with("amount", 1u32)),
+        public_request(0x62, right, Msg::new("increment").with("amount", 2u32)),
+        public_request(0x63, root, Msg::new("increment").with("amount", 3u32)),
+        public_request(0x64, left, Msg::new("increment").with("amount", 4u32)),
+    ];
+    let serial = drain_admitted(config.clone(), &setup, &queued, false);
+    let batched = drain_admitted(config, &setup, &queued, true);
+    assert_eq!(
+        serial
+            .0
+            .last()
+            .and_then(|(_, reply)| reply.as_deref())
+            .and_then(Value::try_decode),
+        Some(Value::U32(5)),
+        "the second increment of `a` observes the first"
+    );
+    assert_eq!(batched.0, serial.0, "each batched receipt matches serial");
+    assert_eq!(batched.1.state_root, serial.1.state_root);
+    assert_eq!(batched.1, serial.1);
+}
+
+/// A CRDT root holds only its root actor, so each ingress imports it and
+/// forms a wave of its own; the batch must still match serial execution.
+#[test]
+fn batched_crdt_ingresses_commit_exactly_the_serial_heads() {
+    let signer = libp2p::identity::Keypair::generate_ed25519();
+    let (package, actor_name) = signed_test_package(&crdt_counter_v2_elf(), &signer);
+    let root = ActorId([0x71; 32]);
+    let config = LocalRootTreeConfigV2 {
+        role_authority: None,
+        service_pvm: CANONICAL_SERVICE_PVM.to_vec(),
+        service: ServiceIdentityV2 {
+            space: vos::v2::SpaceId([0x72; 32]),
+            root_service: RootServiceId([0x73; 32]),
+            deployment: package.deployment_id(),
+            service_program: vos::v2::VOS_SERVICE_PROGRAM_ID,
+            service_abi: vos::v2::ABI_VERSION,
+            execution_semantics: vos::v2::EXECUTION_SEMANTICS_ID,
+            gas_schedule: TEST_GAS_SCHEDULE,
+        },
+        package,
+        root_actor: root,
+        actor_name,
+        consistency: ConsistencyModeV2::Crdt,
+        initial_state: vec![],
+        external_actors: vec![],
+        install_authorization: AuthorizationEvidenceV2::SystemCapability {
+            capability: SystemCapabilityId([0x74; 32]),
+            authenticator: vec![0x75],
+        },
+        refine_gas: TEST_GAS_SCHEDULE.refine,
+        accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
+    };
+    let queued = [1u64, 2, 3].map(|amount| {
+        public_request(
+            0x76 + amount as u8,
+            root,
+            Msg::new("increment").with("amount", amount),
+        )
+    });
+    let serial = drain_admitted(config.clone(), &[], &queued, false);
+    let batched = drain_admitted(config, &[], &queued, true);
+    assert_eq!(
+        serial
+            .0
+            .last()
+            .and_then(|(_, reply)| reply.as_deref())
+            .and_then(Value::try_decode),
+        Some(Value::I64(6)),
+    );
+    assert_eq!(batched.0, serial.0, "each batched receipt matches serial");
+    assert_eq!(
+        batched.1.crdt_heads.len(),
+        1,
+        "the batch builds one causal chain"
+    );
+    assert_eq!(batched.1, serial.1);
+}
+
+fn batch_call(msg: Msg) -> RootBatchCallV2 {
+    let method = msg.name.clone();
+    let mut arguments = vec![vos::value::TAG_DYNAMIC];
+    arguments.extend_from_slice(&msg.encode());
+    RootBatchCallV2 { method, arguments }
+}
+
+/// Move `amount` from the workflow root at index 0 to the one at index 1.
+fn transfer_batch(
+    id: u8,
+    origin: Origin,
+    from: ActorId,
+    to: ActorId,
+    amount: u32,
+    deadline_timeslot: u64,
+) -> RootBatchV2 {
+    let batch = vec![id; 32];
+    let step = |root, target, debit| RootBatchStepV2 {
+        root,
+        target,
+        origin,
+        authorization: AuthorizationEvidenceV2::Public,
+        prepare: batch_call(
+            Msg::new("reserve")
+                .with("batch", batch.clone())
+                .with("amount", amount)
+                .with("debit", debit),
+        ),
+        commit: batch_call(
+            Msg::new("apply")
+                .with("batch", batch.clone())
+                .with("debit", debit),
+        ),
+        compensate: batch_call(
+            Msg::new("release")
+                .with("batch", batch.clone())
+                .with("debit", debit),
+        ),
+    };
+    RootBatchV2 {
+        id: Hash([id; 32]),
+        deadline_timeslot,
+        steps: vec![step(0, from, true), step(1, to, false)],
+    }
+}
+
+fn local_balance<B>(service: &mut LocalRootTreeServiceV2<B>, target: ActorId, invocation: u8) -> u32
+where
+    B: CommittedImageStoreV2 + ProofArtifactStoreV2<Error = <B as CommittedImageStoreV2>::Error>,
+{
+    let committed = service
+        .invoke(public_request(invocation, target, Msg::new("balance")))
+        .unwrap();
+    match committed
+        .published
+        .reply
+        .and_then(|reply| Value::try_decode(&reply.result))
+    {
+        Some(Value::U32(balance)) => balance,
+        other => panic!("balance replied {other:?}"),
+    }
+}
+
+/// A root whose host is down: every phase call fails before admission.
+struct UnavailableRoot;
+
+impl RootBatchParticipantV2 for UnavailableRoot {
+    type Error = LocalRootTreeInvokeErrorV2;
+
+    fn invoke_phase(&mut self, _: LocalWorkRequestV2) -> Result<bool, Self::Error> {
+        Err(LocalRootTreeInvokeErrorV2::ServiceNotInstalled)
+    }
+}
+
+#[test]
+fn root_batch_moves_value_between_two_roots_or_compensates_both() {
+    let (debit_config, debit) = attested_root_fixture(ConsistencyModeV2::Local, 0xa1);
+    let (credit_config, credit) = attested_root_fixture(ConsistencyModeV2::Local, 0xb1);
+    let (debit, credit) = (debit.target, credit.target);
+    let mut from =
+        LocalRootTreeServiceV2::open(debit_config, FailableCommittedImages::default()).unwrap();
+    let mut to =
+        LocalRootTreeServiceV2::open(credit_config, FailableCommittedImages::default()).unwrap();
+    from.invoke(public_request(
+        0xa0,
+        debit,
+        Msg::new("increment").with("amount", 10u32),
+    ))
+    .unwrap();
+    let mut journal = MemoryRootBatchJournalV2::default();
+
+    let transfer = transfer_batch(1, Origin::Anonymous, debit, credit, 4, 1_000);
+    let record = transfer
+        .drive(&mut journal, &mut [&mut from, &mut to], 100)
+        .unwrap();
+    assert_eq!(record.decision, Some(RootBatchDecisionV2::Committed));
+    assert!(record.is_settled());
+    assert_eq!(local_balance(&mut from, debit, 0xa2), 6);
+    assert_eq!(local_balance(&mut to, credit, 0xb2), 4);
+    assert_eq!(
+        transfer
+            .drive(&mut journal, &mut [&mut from, &mut to], 101)
+            .unwrap(),
+        record,
+        "a settled batch is answered from the journal"
+    );
+
+    // The debit root refuses the overdraft, so the credit is never prepared
+    // and both roots tombstone the batch.
+    let overdraft = transfer_batch(2, Origin::Anonymous, debit, credit, 20, 1_000);
+    let record = overdraft
+        .drive(&mut journal, &mut [&mut from, &mut to], 102)
+        .unwrap();
+    assert_eq!(record.decision, Some(RootBatchDecisionV2::Compensated));
+    assert_eq!(record.votes, vec![Some(false), None]);
+    assert!(record.is_settled());
+    assert!(
+        !to.invoke_phase(overdraft.request(1, RootBatchPhaseV2::Prepare, 103))
+            .unwrap(),
+        "a compensated step refuses a late prepare"
+    );
+    assert_eq!(local_balance(&mut from, debit, 0xa3), 6);
+    assert_eq!(local_balance(&mut to, credit, 0xb3), 4);
+
+    // The credit root is down after the debit was reserved. The deadline
+    // sweep leaves the batch until its deadline, then returns the hold.
+    let stuck = transfer_batch(3, Origin::Anonymous, debit, credit, 3, 200);
+    assert!(matches!(
+        stuck.drive(&mut journal, &mut [&mut from, &mut UnavailableRoot], 150),
+        Err(RootBatchErrorV2::Root {
+            step: 1,
+            phase: RootBatchPhaseV2::Prepare,
+            ..
+        })
+    ));
+    assert_eq!(
+        local_balance(&mut from, debit, 0xa4),
+        3,
+        "the debit is held"
+    );
+    let early = stuck
+        .recover(
+            &mut journal,
+            &mut [&mut from, &mut to],
+            199,
+            RootBatchRecoveryV2::Deadline,
+        )
+        .unwrap();
+    assert_eq!(early.decision, None);
+    let aborted = stuck
+        .recover(
+            &mut journal,
+            &mut [&mut from, &mut to],
+            200,
+            RootBatchRecoveryV2::Deadline,
+        )
+        .unwrap();
+    assert_eq!(aborted.decision, Some(RootBatchDecisionV2::Compensated));
+    assert!(aborted.is_settled());
+    assert!(journal.unsettled().unwrap().is_empty());
+    assert_eq!(local_balance(&mut from, debit, 0xa5), 6);
+    assert_eq!(local_balance(&mut to, credit, 0xb5), 4);
+}
+
+fn node_balance(node: &VosNode, target: ActorId) -> u32 {
+    let mut arguments = vec![vos::value::TAG_DYNAMIC];
+    arguments.extend_from_slice(&Msg::new("balance").encode());
+    match Value::try_decode(&node.invoke_actor(target, arguments).unwrap()) {
+        Some(Value::U32(balance)) => balance,
+        other => panic!("balance replied {other:?}"),
+    }
+}
+
+/// Reaches a node root through ordinary invocations while `available`; the
+/// coordinator that used it crashed once it reached an unavailable one.
+struct CrashingNodeRoot<'a> {
+    node: &'a VosNode,
+    available: bool,
+}
+
+impl RootBatchParticipantV2 for CrashingNodeRoot<'_> {
+    type Error = ClientError;
+
+    fn invoke_phase(&mut self, request: LocalWorkRequestV2) -> Result<bool, Self::Error> {
+        if !self.available {
+            return Err(ClientError::Unreachable);
+        }
+        let reply = self.node.invoke_actor(request.target, request.arguments)?;
+        Ok(Value::try_decode(&reply) == Some(Value::Bool(true)))
+    }
+}
+
+#[test]
+fn node_drives_root_batches_exactly_once_and_compensates_them_on_restart() {
+    let (debit_config, debit) = attested_root_fixture(ConsistencyModeV2::Local, 0xc1);
+    let (credit_config, credit) = attested_root_fixture(ConsistencyModeV2::Local, 0xd1);
+    let (debit, credit) = (debit.target, credit.target);
+    let directory = std::env::temp_dir().join(format!(
+        "vos-v2-node-root-batches-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos(),
+    ));
+    let mut node = VosNode::new();
+    for (name, config, route) in [
+        ("batch-debit", debit_config, ServiceId::new(0, 0x3400)),
+        ("batch-credit", credit_config, ServiceId::new(0, 0x3401)),
+    ] {
+        let service = LocalRootTreeServiceV2::open(config, SharedCommittedImages::default())
+            .expect("batch root installs");
+        node.register_v2_root_at_id(name, service, route, false)
+            .expect("batch root registers");
+    }
+    let mut arguments = vec![vos::value::TAG_DYNAMIC];
+    arguments.extend_from_slice(&Msg::new("increment").with("amount", 10u32).encode());
+    node.invoke_actor(debit, arguments).unwrap();
+
+    let transfer = transfer_batch(1, Origin::System, debit, credit, 4, u64::MAX);
+    assert!(matches!(
+        node.drive_root_batch(&transfer),
+        Err(RootBatchErrorV2::Journal(_))
+    ));
+    node.attach_root_batch_journal(FileRootBatchJournalV2::new(directory.join("first")));
+    let record = node.drive_root_batch(&transfer).unwrap();
+    assert_eq!(record.decision, Some(RootBatchDecisionV2::Committed));
+    assert!(record.is_settled());
+    assert_eq!(
+        (node_balance(&node, debit), node_balance(&node, credit)),
+        (6, 4)
+    );
+
+    // A coordinator that lost its journal re-issues the same phase calls.
+    // The roots reattach their committed results, so nothing moves twice.
+    node.attach_root_batch_journal(FileRootBatchJournalV2::new(directory.join("second")));
+    assert_eq!(node.drive_root_batch(&transfer).unwrap(), record);
+    assert_eq!(
+        (node_balance(&node, debit), node_balance(&node, credit)),
+        (6, 4)
+    );
+
+    // A crash after the debit prepare leaves an undecided batch. The sweep
+    // keeps it before its deadline; restart recovery compensates it.
+    let stuck = transfer_batch(2, Origin::System, debit, credit, 3, u64::MAX);
+    let mut journal = FileRootBatchJournalV2::new(directory.join("second"));
+    let mut reachable = CrashingNodeRoot {
+        node: &node,
+        available: true,
+    };
+    let mut crashed = CrashingNodeRoot {
+        node: &node,
+        available: false,
+    };
+    assert!(
+        stuck
+            .drive(&mut journal, &mut [&mut reachable, &mut crashed], 1)
+            .is_err()
+    );
+    assert_eq!(node_balance(&node, debit), 3, "the debit is held");
+    let swept = node.recover_root_batches(RootBatchRecoveryV2::Deadline);
+    assert_eq!(swept.len(), 1);
+    assert_eq!(swept[0].as_ref().unwrap().decision, None);
+    let recovered = node.recover_root_batches(RootBatchRecoveryV2::Restart);
+    let recovered = recovered[0].as_ref().unwrap();
+    assert_eq!(recovered.decision, Some(RootBatchDecisionV2::Compensated));
+    assert!(recovered.is_settled());
+    assert!(
+        node.recover_root_batches(RootBatchRecoveryV2::Restart)
+            .is_empty()
+    );
+    assert_eq!(
+        (node_balance(&node, debit), node_balance(&node, credit)),
+        (6, 4)
+    );
+
+    // The host ingress admits only as the system origin.
+    let anonymous = transfer_batch(3, Origin::Anonymous, debit, credit, 1, u64::MAX);
+    assert!(matches!(
+        node.drive_root_batch(&anonymous),
+        Err(RootBatchErrorV2::Root {
+            error: ClientError::Forbidden,
+            ..
+        })
+    ));
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
 #[test]
 fn attested_root_driver_recovers_queued_and_committed_proofs_across_restart() {
     let (config, request) = attested_root_fixture(ConsistencyModeV2::Local, 0x41);
@@ -3232,6 +3715,7 @@ fn canonical_clerk_package_executes_a_private_provable_transfer_through_raft() {
         },
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let actor = config.root_actor;
     let directory = std::env::temp_dir().join(format!(
@@ -3439,6 +3923,7 @@ fn signed_task_dependency_actor_config(
         },
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     (config, binding)
 }
@@ -3519,6 +4004,7 @@ fn durable_root_tree_host_restores_guest_state_and_pending_publications() {
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let mut replicated_config = config.clone();
     replicated_config.consistency = ConsistencyModeV2::Raft;
@@ -3773,6 +4259,7 @@ fn canonical_space_authority_authorizes_a_physical_target_and_exact_retry() {
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let mut authority = LocalRootTreeServiceV2::open(config, FailableCommittedImages::default())
         .expect("the canonical authority installs through guest Accumulate");
@@ -4074,6 +4561,7 @@ fn canonical_space_authority_authorizes_a_physical_target_and_exact_retry() {
         },
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let mut target =
         LocalRootTreeServiceV2::open(target_config.clone(), FailableCommittedImages::default())
@@ -4239,6 +4727,7 @@ fn crdt_role_authorization_survives_causal_sync_restart_and_exact_retry() {
         },
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let mut source =
         LocalRootTreeServiceV2::open(config.clone(), FailableCommittedImages::default())
@@ -4466,6 +4955,7 @@ fn node_ingress_uses_canonical_authority_for_raft_and_crdt_targets() {
         },
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let directory = std::env::temp_dir().join(format!(
         "vos-v2-role-ingress-{}-{}",
@@ -4569,6 +5059,7 @@ fn node_ingress_uses_canonical_authority_for_raft_and_crdt_targets() {
             },
             refine_gas: TEST_GAS_SCHEDULE.refine,
             accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+            work_fairness: Default::default(),
         },
         FailableCommittedImages::default(),
         target_log,
@@ -4603,6 +5094,7 @@ fn node_ingress_uses_canonical_authority_for_raft_and_crdt_targets() {
         },
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let crdt_backend = SharedCommittedImages::default();
     let crdt_target = LocalRootTreeServiceV2::open(crdt_config.clone(), crdt_backend.clone())
@@ -4658,6 +5150,9 @@ fn node_ingress_uses_canonical_authority_for_raft_and_crdt_targets() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let deadline = std::time::Instant::now() + Duration::from_secs(15);
     let node_address = loop {
@@ -4673,6 +5168,9 @@ fn node_ingress_uses_canonical_authority_for_raft_and_crdt_targets() {
         listen: vec![listen.clone()],
         bootstrap: vec![node_address.clone()],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let denied_network = vos::network::Network::start(vos::network::NetworkConfig {
         keypair: denied_key,
@@ -4680,6 +5178,9 @@ fn node_ingress_uses_canonical_authority_for_raft_and_crdt_targets() {
         listen: vec![listen],
         bootstrap: vec![node_address],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     node.attach_network(node_network);
     let shutdown = node.shutdown_handle();
@@ -4868,6 +5369,7 @@ fn raft_root_tree_orders_genesis_apply_and_ack_through_physical_accumulate() {
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     assert!(matches!(
         LocalRootTreeServiceV2::open(config.clone(), FailableCommittedImages::default()),
@@ -4977,6 +5479,7 @@ fn node_registers_a_raft_root_through_the_canonical_request_log() {
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let directory = std::env::temp_dir().join(format!(
         "vos-v2-node-raft-{}-{}",
@@ -5202,6 +5705,7 @@ fn node_registers_a_raft_root_through_the_canonical_request_log() {
             heartbeat_interval_ms: 5,
             replication_id: [0xA6; 32],
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         },
         route,
         true,
@@ -5279,6 +5783,9 @@ fn network_ingress_to_a_raft_root_follower_redirects_to_the_leader() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let deadline = std::time::Instant::now() + Duration::from_secs(15);
     let address_a = loop {
@@ -5294,6 +5801,9 @@ fn network_ingress_to_a_raft_root_follower_redirects_to_the_leader() {
         listen: vec![listen.clone()],
         bootstrap: vec![address_a.clone()],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let deadline = std::time::Instant::now() + Duration::from_secs(15);
     let address_b = loop {
@@ -5309,6 +5819,9 @@ fn network_ingress_to_a_raft_root_follower_redirects_to_the_leader() {
         listen: vec![listen],
         bootstrap: vec![address_a, address_b],
         auto_dial_mdns: true,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     let mut node_a = VosNode::with_prefix(prefix_a);
@@ -5371,6 +5884,7 @@ fn network_ingress_to_a_raft_root_follower_redirects_to_the_leader() {
         heartbeat_interval_ms: 20,
         replication_id,
         propose_timeout_ms: 5_000,
+        witnesses: Vec::new(),
     };
     let (apply_a_tx, apply_a_rx) = std::sync::mpsc::channel();
     let (apply_b_tx, apply_b_rx) = std::sync::mpsc::channel();
@@ -5382,6 +5896,7 @@ fn network_ingress_to_a_raft_root_follower_redirects_to_the_leader() {
             replication_id,
             election_timeout_ms: election_timeout_for(prefix_a),
             heartbeat_interval_ms: 20,
+            witnesses: Vec::new(),
         },
         Some(network_a.clone()),
         Some(apply_a_tx),
@@ -5394,6 +5909,7 @@ fn network_ingress_to_a_raft_root_follower_redirects_to_the_leader() {
             replication_id,
             election_timeout_ms: election_timeout_for(prefix_b),
             heartbeat_interval_ms: 20,
+            witnesses: Vec::new(),
         },
         Some(network_b.clone()),
         Some(apply_b_tx),
@@ -5602,6 +6118,7 @@ fn raft_follower_registers_before_genesis_and_restores_caught_up_admission_time(
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let directory = std::env::temp_dir().join(format!(
         "vos-v2-root-follower-{}-{}",
@@ -5671,6 +6188,7 @@ fn raft_follower_registers_before_genesis_and_restores_caught_up_admission_time(
         heartbeat_interval_ms: 100,
         replication_id: [0xE2; 32],
         propose_timeout_ms: 2_000,
+        witnesses: Vec::new(),
     };
     let (apply_tx, apply_rx) = std::sync::mpsc::channel();
     let worker = RaftWorker::spawn(
@@ -5681,6 +6199,7 @@ fn raft_follower_registers_before_genesis_and_restores_caught_up_admission_time(
             replication_id: raft_config.replication_id,
             election_timeout_ms: raft_config.election_timeout_ms,
             heartbeat_interval_ms: raft_config.heartbeat_interval_ms,
+            witnesses: Vec::new(),
         },
         None,
         Some(apply_tx),
@@ -5777,6 +6296,7 @@ fn node_routes_canonical_actor_ids_through_the_guest_owned_root_service() {
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let backend = SharedCommittedImages::default();
     let mut service = LocalRootTreeServiceV2::open(config.clone(), backend.clone())
@@ -6060,6 +6580,7 @@ fn raft_registration_reverifies_current_conformance_proof_history_before_exposin
         heartbeat_interval_ms: 5,
         replication_id: [0x77; 32],
         propose_timeout_ms: 2_000,
+        witnesses: Vec::new(),
     };
     let log = RaftAccumulateLogV2::from_db_arc(db.clone(), raft_config.clone()).unwrap();
     let mut service = LocalRootTreeServiceV2::open_raft(config.clone(), backend.clone(), log)
@@ -6131,6 +6652,7 @@ fn node_attested_raft_root_orders_the_proved_apply() {
             heartbeat_interval_ms: 5,
             replication_id: [0x78; 32],
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         },
         route,
         false,
@@ -6203,6 +6725,7 @@ fn attested_node_transport_fixture(
         install_authorization: install_authorization.clone(),
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let destination = LocalRootTreeConfigV2 {
         role_authority: None,
@@ -6217,6 +6740,7 @@ fn attested_node_transport_fixture(
         install_authorization,
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     (source, destination, source_actor, destination_actor)
 }
@@ -6322,6 +6846,7 @@ fn node_raft_transport_orders_attested_inbox_and_reply_proof_on_both_roots() {
         heartbeat_interval_ms: 5,
         replication_id,
         propose_timeout_ms: 2_000,
+        witnesses: Vec::new(),
     };
     let proof = canonical_test_proof_manifest(0x84);
     let mut node = VosNode::new();
@@ -6464,6 +6989,7 @@ fn promoted_raft_voter_keeps_its_producer_after_taking_leadership() {
             heartbeat_interval_ms: 5,
             replication_id: [0xFA; 32],
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         },
         source_route,
         false,
@@ -6489,6 +7015,7 @@ fn promoted_raft_voter_keeps_its_producer_after_taking_leadership() {
             heartbeat_interval_ms: 20,
             replication_id: [0xFB; 32],
             propose_timeout_ms: 2_000,
+            witnesses: Vec::new(),
         },
         destination_route,
         false,
@@ -6628,6 +7155,7 @@ fn node_routes_an_ordinary_cross_root_await_through_guest_accumulate() {
         install_authorization: install_authorization.clone(),
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let destination_config = LocalRootTreeConfigV2 {
         role_authority: None,
@@ -6642,6 +7170,7 @@ fn node_routes_an_ordinary_cross_root_await_through_guest_accumulate() {
         install_authorization,
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let source_backend = SharedCommittedImages::default();
     let destination_backend = SharedCommittedImages::default();
@@ -6778,6 +7307,7 @@ fn node_routes_a_crdt_cross_root_await_and_acknowledges_both_publications() {
         install_authorization: install_authorization.clone(),
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let destination_config = LocalRootTreeConfigV2 {
         role_authority: None,
@@ -6792,6 +7322,7 @@ fn node_routes_a_crdt_cross_root_await_and_acknowledges_both_publications() {
         install_authorization,
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let source_backend = SharedCommittedImages::default();
     let destination_backend = SharedCommittedImages::default();
@@ -6903,6 +7434,7 @@ fn node_routes_networkless_single_voter_raft_roots() {
         install_authorization: install_authorization.clone(),
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let destination_config = LocalRootTreeConfigV2 {
         role_authority: None,
@@ -6917,6 +7449,7 @@ fn node_routes_networkless_single_voter_raft_roots() {
         install_authorization,
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
 
     let directory = std::env::temp_dir().join(format!(
@@ -6939,6 +7472,7 @@ fn node_routes_networkless_single_voter_raft_roots() {
         heartbeat_interval_ms: 10,
         replication_id,
         propose_timeout_ms: 5_000,
+        witnesses: Vec::new(),
     };
     let mut node = VosNode::new();
     node.register_v2_raft_root_at_id(
@@ -7042,6 +7576,7 @@ fn node_routes_raft_cross_root_reply_between_different_leaders() {
         install_authorization: install_authorization.clone(),
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let destination_config = LocalRootTreeConfigV2 {
         role_authority: None,
@@ -7056,6 +7591,7 @@ fn node_routes_raft_cross_root_reply_between_different_leaders() {
         install_authorization,
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
 
     let key_a = libp2p::identity::Keypair::generate_ed25519();
@@ -7076,6 +7612,9 @@ fn node_routes_raft_cross_root_reply_between_different_leaders() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let deadline = std::time::Instant::now() + Duration::from_secs(15);
     let address_a = loop {
@@ -7091,6 +7630,9 @@ fn node_routes_raft_cross_root_reply_between_different_leaders() {
         listen: vec![listen],
         bootstrap: vec![address_a],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     let mut node_a = VosNode::with_prefix(prefix_a);
@@ -7136,6 +7678,7 @@ fn node_routes_raft_cross_root_reply_between_different_leaders() {
         heartbeat_interval_ms: 20,
         replication_id,
         propose_timeout_ms: 5_000,
+        witnesses: Vec::new(),
     };
     node_a
         .register_v2_raft_root_at_id(
@@ -7271,6 +7814,7 @@ fn node_retries_a_direct_reply_publication_ack_after_the_caller_is_gone() {
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let backend = SharedFailingCommittedImages::default();
     let service = LocalRootTreeServiceV2::open(config.clone(), backend.clone())
@@ -7352,6 +7896,7 @@ fn node_expires_and_resumes_an_unreachable_durable_call() {
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let backend = SharedFailingCommittedImages::default();
     let service = LocalRootTreeServiceV2::open(config.clone(), backend.clone())
@@ -7444,6 +7989,7 @@ fn durable_crdt_root_tree_reattaches_an_exact_invocation_after_restart() {
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness: Default::default(),
     };
     let mut arguments = vec![vos::value::TAG_DYNAMIC];
     arguments.extend_from_slice(
@@ -7705,6 +8251,7 @@ fn node_anti_entropy_converges_authenticated_crdt_roots_across_restart() {
         },
         refine_gas: TEST_GAS_SCHEDULE.refine,
         accumulate_gas: TEST_GAS_SCHEDULE.accumulate,
+        work_fairness: Default::default(),
     };
     let backend_a = SharedCommittedImages::default();
     let backend_b = SharedCommittedImages::default();
@@ -7761,6 +8308,9 @@ fn node_anti_entropy_converges_authenticated_crdt_roots_across_restart() {
         listen: vec![listen.clone()],
         bootstrap: vec![],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
     let deadline = std::time::Instant::now() + Duration::from_secs(30);
     let address_a = loop {
@@ -7776,6 +8326,9 @@ fn node_anti_entropy_converges_authenticated_crdt_roots_across_restart() {
         listen: vec![listen],
         bootstrap: vec![address_a],
         auto_dial_mdns: false,
+        discovery_keys: Vec::new(),
+        relays: Vec::new(),
+        relay_server: false,
     });
 
     let mut node_a = VosNode::with_prefix(prefix_a);
@@ -17025,6 +17578,7 @@ fn redb_raft_log_drives_physical_guest_accumulate() {
         heartbeat_interval_ms: 500,
         replication_id: [0xD1; 32],
         propose_timeout_ms: 2_000,
+        witnesses: Vec::new(),
     };
     let (apply_tx, apply_rx) = std::sync::mpsc::channel();
     let worker = RaftWorker::spawn(
@@ -17035,6 +17589,7 @@ fn redb_raft_log_drives_physical_guest_accumulate() {
             replication_id: raft_config.replication_id,
             election_timeout_ms: raft_config.election_timeout_ms,
             heartbeat_interval_ms: raft_config.heartbeat_interval_ms,
+            witnesses: Vec::new(),
         },
         None,
         Some(apply_tx),
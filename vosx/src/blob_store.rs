@@ -238,6 +238,34 @@ pub fn cache_put(bytes: &[u8]) -> Result<BlobHash, BlobError> {
     Ok(h)
 }
 
+/// Re-hash every blob in the cache. Returns how many entries were
+/// checked and the hashes whose file no longer matches its name. A
+/// missing cache directory is an empty cache; files that aren't named
+/// by a hash (in-flight `*.tmp.*` writes) are skipped.
+pub fn audit_cache() -> Result<(usize, Vec<BlobHash>), BlobError> {
+    let entries = match fs::read_dir(cache_dir()) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok((0, Vec::new())),
+        Err(e) => return Err(BlobError::Io(e)),
+    };
+    let mut checked = 0;
+    let mut corrupt = Vec::new();
+    for entry in entries {
+        let name = entry?.file_name();
+        let Some(hash) = name.to_str().and_then(|n| BlobHash::from_hex(n).ok()) else {
+            continue;
+        };
+        checked += 1;
+        match cache_get(&hash) {
+            Err(BlobError::HashMismatch { .. }) => corrupt.push(hash),
+            Err(e) => return Err(e),
+            Ok(_) => {}
+        }
+    }
+    corrupt.sort_by_key(|h| h.0);
+    Ok((checked, corrupt))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -371,4 +399,19 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn audit_cache_reports_only_mismatched_blobs() {
+        with_isolated_cache(|_| {
+            assert_eq!(audit_cache().unwrap().0, 0);
+            let good = cache_put(b"kept").unwrap();
+            let bad = cache_put(b"the truth").unwrap();
+            std::fs::write(cache_path_for(&bad), b"a lie").unwrap();
+            std::fs::write(cache_dir().join("stray.tmp.1"), b"partial").unwrap();
+            let (checked, corrupt) = audit_cache().unwrap();
+            assert_eq!(checked, 2);
+            assert_eq!(corrupt, vec![bad]);
+            assert!(!corrupt.contains(&good));
+        });
+    }
 }
@@ -11,6 +11,8 @@ use vos::v2::{
     artifact_hash, task_dependencies_hash,
 };
 
+use crate::signer::Signer;
+
 const RUSTC_WRAPPER_MODE: &str = "VOSX_CANONICAL_RUSTC_WRAPPER";
 const RUSTC_WRAPPER_SOURCE_ROOT: &str = "VOSX_CANONICAL_SOURCE_ROOT";
 const RUSTC_UNIT_METADATA_DOMAIN: &[u8] = b"vos/rustc-unit-metadata/v2";
@@ -46,14 +48,55 @@ pub struct Args {
     pub tasks: Vec<PathBuf>,
     pub include_elf: bool,
     pub crdt: bool,
+    /// `--signer` spec for the deployment signature; see [`crate::signer`].
+    pub signer: Option<String>,
+}
+
+/// What a build wrote to its `out_dir`.
+pub(crate) struct Built {
+    pub(crate) pvm_path: PathBuf,
+    pub(crate) package_path: PathBuf,
+    pub(crate) package: VosPackageV2,
 }
 
 pub fn run(args: Args) -> anyhow::Result<()> {
-    let keypair = crate::identity::load_or_create()?;
-    run_with_signer(args, &keypair)
+    let signer = crate::signer::open(args.signer.as_deref())?;
+    run_with_signer(args, signer.as_ref())
 }
 
-fn run_with_signer(args: Args, keypair: &libp2p::identity::Keypair) -> anyhow::Result<()> {
+fn run_with_signer(args: Args, signer: &dyn Signer) -> anyhow::Result<()> {
+    let Built {
+        pvm_path,
+        package_path,
+        package,
+    } = package_with_signer(args, signer)?;
+    println!("built {}", package_path.display());
+    println!("  actor_pvm    = {}", pvm_path.display());
+    println!(
+        "  program_id   = {}",
+        hex::encode(package.manifest.actor_program.0)
+    );
+    println!(
+        "  deployment_id = {}",
+        hex::encode(package.deployment_id().0)
+    );
+    for (index, dependency) in package.task_dependencies.iter().enumerate() {
+        println!(
+            "  task[{index}]      = {}",
+            hex::encode(dependency.binding.task.0)
+        );
+    }
+    Ok(())
+}
+
+/// Build and sign with the `--signer` key, without printing — for
+/// callers that report the result their own way (`vosx dev watch`).
+pub(crate) fn package(args: Args) -> anyhow::Result<Built> {
+    let signer = crate::signer::open(args.signer.as_deref())?;
+    package_with_signer(args, signer.as_ref())
+}
+
+fn package_with_signer(args: Args, signer: &dyn Signer) -> anyhow::Result<Built> {
     let program = resolve_program_input(&args.program)?;
     let input = std::fs::read(&program).with_context(|| format!("read {}", program.display()))?;
     let is_pvm = program.extension().and_then(|x| x.to_str()) == Some("pvm");
@@ -128,7 +171,7 @@ fn run_with_signer(args: Args, keypair: &libp2p::identity::Keypair) -> anyhow::R
     let service_program = vos::v2::VOS_SERVICE_PROGRAM_ID;
     let actor_program = ProgramId::of_pvm(&actor_pvm);
 
-    let public_key = keypair.public().encode_protobuf();
+    let public_key = signer.public().encode_protobuf();
     let producer = ProducerId::of_public_key(&public_key);
     let mut package = VosPackageV2 {
         manifest: PackageManifestV2 {
@@ -160,9 +203,9 @@ fn run_with_signer(args: Args, keypair: &libp2p::identity::Keypair) -> anyhow::R
             signature: vec![0],
         },
     };
-    package.deployment_signature.signature = keypair
+    package.deployment_signature.signature = signer
         .sign(&package.signing_message())
-        .map_err(|error| anyhow!("sign deployment: {error}"))?;
+        .context("sign deployment")?;
     package.validate()?;
 
     std::fs::create_dir_all(&args.out_dir)
@@ -173,21 +216,11 @@ fn run_with_signer(args: Args, keypair: &libp2p::identity::Keypair) -> anyhow::R
         .with_context(|| format!("write {}", pvm_path.display()))?;
     std::fs::write(&package_path, package.encode())
         .with_context(|| format!("write {}", package_path.display()))?;
-
-    println!("built {}", package_path.display());
-    println!("  actor_pvm    = {}", pvm_path.display());
-    println!("  program_id   = {}", hex::encode(actor_program.0));
-    println!(
-        "  deployment_id = {}",
-        hex::encode(package.deployment_id().0)
-    );
-    for (index, dependency) in package.task_dependencies.iter().enumerate() {
-        println!(
-            "  task[{index}]      = {}",
-            hex::encode(dependency.binding.task.0)
-        );
-    }
-    Ok(())
+    Ok(Built {
+        pvm_path,
+        package_path,
+        package,
+    })
 }
 
 fn build_task_dependency(input: &Path) -> anyhow::Result<PackageTaskDependencyV2> {
@@ -859,6 +892,7 @@ mod tests {
             tasks: vec![],
             include_elf: false,
             crdt: false,
+            signer: None,
         };
         let first = temp.0.join("first");
         let second = temp.0.join("second");
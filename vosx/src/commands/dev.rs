@@ -0,0 +1,281 @@
+//! `vosx dev watch` — the actor hot-reload loop.
+//!
+//! Iterating on an actor by hand is `vosx build`, `space publish`, then
+//! `space upgrade-v2` for every change. `dev watch` runs that loop on each
+//! save: rebuild the canonical PVM and signed `.vos` package, publish it
+//! under the next `<version>-dev.<n>` tag, and upgrade the running
+//! instance through the guest-owned v2 transition, so the instance keeps
+//! its state. Between rebuilds it tails the instance's guest output and
+//! dispatch outcomes, as `space logs --follow` would.
+//!
+//! A failed build or refused upgrade is printed and the loop waits for the
+//! next save. A space whose daemon runs under the production trust
+//! profile is refused outright: dev tags and unreviewed upgrades don't
+//! belong there.
+//!
+//! Only `watch` is built in; every other `vosx dev …` verb still
+//! dispatches to the dev extension.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::Serialize;
+use vos::v2::V2Wire;
+
+use crate::commands::build;
+use crate::commands::dynamic::resolve_space;
+use crate::commands::space::client::DaemonClient;
+use crate::commands::space::{logs, publish, upgrade_v2};
+use crate::output;
+
+/// How often the project is checked for edits and the logs for new lines.
+const POLL: Duration = Duration::from_millis(500);
+
+#[derive(clap::Subcommand)]
+pub enum DevCommand {
+    /// Rebuild, publish and upgrade an installed actor on every save of
+    /// its project, keeping the instance's state, and print its guest
+    /// logs and dispatch errors as they arrive. Refuses spaces running
+    /// under the production trust profile.
+    Watch(WatchArgs),
+}
+
+#[derive(clap::Args)]
+pub struct WatchArgs {
+    /// Actor project directory (the crate holding its `Cargo.toml`).
+    project: PathBuf,
+    /// Space the instance runs in. Defaults to the current space
+    /// (`VOSX_SPACE` or the sole registered space).
+    #[arg(long)]
+    space: Option<String>,
+    /// Installed instance to upgrade after each rebuild. It must run from
+    /// a signed v2 package.
+    #[arg(long)]
+    agent: String,
+    /// Canonical Task project directory or ELF the actor depends on, as
+    /// for `vosx build --task`. May be repeated.
+    #[arg(long = "task")]
+    tasks: Vec<PathBuf>,
+    /// Where rebuilt artifacts go. Defaults to `<project>/target/vosx-dev`.
+    #[arg(long, value_name = "DIR")]
+    out_dir: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct ReloadView<'a> {
+    instance_name: &'a str,
+    program_name: &'a str,
+    program_version: &'a str,
+    program: &'a str,
+    revision: u64,
+}
+
+pub fn run(cmd: DevCommand) -> Result<()> {
+    match cmd {
+        DevCommand::Watch(args) => watch(args),
+    }
+}
+
+fn watch(args: WatchArgs) -> Result<()> {
+    let space = resolve_space(args.space.as_deref())?;
+    let project = std::fs::canonicalize(&args.project)
+        .with_context(|| format!("resolve actor project {}", args.project.display()))?;
+    if !project.join("Cargo.toml").is_file() {
+        bail!("{} has no Cargo.toml", project.display());
+    }
+    let out_dir = match args.out_dir {
+        Some(dir) => std::path::absolute(&dir)
+            .with_context(|| format!("resolve out dir {}", dir.display()))?,
+        None => project.join("target/vosx-dev"),
+    };
+    DaemonClient::with_connect(&space, |client| {
+        if client.endpoint.production {
+            bail!(
+                "space '{}' runs under the production trust profile; `dev watch` only \
+                 targets development spaces",
+                client.entry.name,
+            );
+        }
+        if client.agent(&args.agent)?.is_none() {
+            bail!("no agent named '{}' installed", args.agent);
+        }
+        if !client.is_v2_instance(&args.agent)? {
+            bail!(
+                "'{}' is not installed from a signed v2 package, so it can't be upgraded in \
+                 place; install it from a `vosx build` package first",
+                args.agent,
+            );
+        }
+
+        // Only lines logged from here on; the backlog is `space logs`'s job.
+        let since_ms = logs::unix_ms_now();
+        let mut after = 0;
+        let mut stamp = None;
+        loop {
+            let now = source_stamp(&project, &out_dir);
+            if stamp.as_ref() != Some(&now) {
+                // Let a multi-file save settle before building it.
+                if stamp.is_some() {
+                    std::thread::sleep(POLL);
+                }
+                stamp = Some(source_stamp(&project, &out_dir));
+                if let Err(e) = reload(client, &args, &project, &out_dir) {
+                    eprintln!("reload {}: {e:#}", args.agent);
+                }
+            }
+            loop {
+                let page =
+                    logs::parse_page(&client.logs(Some(args.agent.as_str()), after, since_ms)?)?;
+                if page.lines.is_empty() {
+                    break;
+                }
+                for line in &page.lines {
+                    logs::print_line(line, false);
+                }
+                after = page.next;
+            }
+            std::thread::sleep(POLL);
+        }
+    })
+}
+
+/// One rebuild → publish → upgrade pass.
+fn reload(client: &DaemonClient, args: &WatchArgs, project: &Path, out_dir: &Path) -> Result<()> {
+    let running = client
+        .agent(&args.agent)?
+        .ok_or_else(|| anyhow!("no agent named '{}' installed", args.agent))?;
+    let programs = client.programs()?;
+    let published: Vec<&str> = programs
+        .iter()
+        .filter(|p| p.name == running.program_name)
+        .map(|p| p.version.as_str())
+        .collect();
+    let version = next_dev_version(&running.program_version, &published);
+
+    let built = build::package(build::Args {
+        program: project.to_path_buf(),
+        name: Some(running.program_name.clone()),
+        version: version.clone(),
+        out_dir: out_dir.to_path_buf(),
+        interfaces: None,
+        role_policies: None,
+        schemas: None,
+        source_map: None,
+        tasks: args.tasks.clone(),
+        include_elf: false,
+        crdt: false,
+        signer: None,
+    })?;
+    let bytes = built.package.encode();
+    let package = upgrade_v2::check_package(&bytes, &args.agent)?;
+    publish::publish_package(client, &running.program_name, &version, bytes.clone())?;
+    let report = upgrade_v2::ship(client, &args.agent, &bytes, &package, &built.package_path)?;
+
+    if output::is_json() {
+        output::print_json(&ReloadView {
+            instance_name: &args.agent,
+            program_name: &running.program_name,
+            program_version: &version,
+            program: &report.program,
+            revision: report.revision,
+        });
+    } else {
+        println!(
+            "reloaded {} → {}:{version} (program {}, revision {})",
+            args.agent, running.program_name, report.program, report.revision,
+        );
+    }
+    Ok(())
+}
+
+/// The tag the next rebuild publishes under: the running version with any
+/// `-dev.<n>` suffix dropped, then one past the highest `-dev.<n>` of that
+/// base already in the catalog.
+fn next_dev_version(running: &str, published: &[&str]) -> String {
+    let base = match running.rsplit_once("-dev.") {
+        Some((base, n)) if n.parse::<u64>().is_ok() => base,
+        _ => running,
+    };
+    let prefix = format!("{base}-dev.");
+    let last = published
+        .iter()
+        .filter_map(|v| v.strip_prefix(&prefix)?.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0);
+    format!("{prefix}{}", last + 1)
+}
+
+/// Modification times of every file in the project, skipping `target/`,
+/// dot-directories and `out_dir` — the build's own output is not an edit.
+fn source_stamp(project: &Path, out_dir: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut stamp = Vec::new();
+    let mut dirs = vec![project.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                let name = entry.file_name();
+                if name != "target" && !name.to_string_lossy().starts_with('.') && path != out_dir {
+                    dirs.push(path);
+                }
+                continue;
+            }
+            let modified = entry.metadata().and_then(|m| m.modified()).ok();
+            stamp.push((path, modified));
+        }
+    }
+    stamp.sort();
+    stamp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dev_versions_count_up_from_the_running_base() {
+        assert_eq!(next_dev_version("0.1.0", &[]), "0.1.0-dev.1");
+        assert_eq!(
+            next_dev_version(
+                "0.1.0",
+                &["0.1.0", "0.1.0-dev.2", "0.1.0-dev.10", "0.2.0-dev.40"]
+            ),
+            "0.1.0-dev.11",
+        );
+        // Already on a dev tag: bump from its base, not onto it.
+        assert_eq!(
+            next_dev_version("0.1.0-dev.3", &["0.1.0-dev.3"]),
+            "0.1.0-dev.4"
+        );
+        // `-dev.` followed by a non-number is part of the version.
+        assert_eq!(next_dev_version("1.0-dev.x", &[]), "1.0-dev.x-dev.1");
+    }
+
+    #[test]
+    fn stamp_sees_source_edits_but_not_build_output() {
+        let dir = std::env::temp_dir().join(format!("vosx-dev-stamp-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("target")).unwrap();
+        std::fs::create_dir_all(dir.join("out")).unwrap();
+        std::fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "").unwrap();
+        let out = dir.join("out");
+
+        let before = source_stamp(&dir, &out);
+        let files: Vec<_> = before.iter().map(|(p, _)| p.clone()).collect();
+        assert_eq!(files, [dir.join("Cargo.toml"), dir.join("src/lib.rs")]);
+
+        std::fs::write(dir.join("target/a.elf"), "x").unwrap();
+        std::fs::write(out.join("a.vos"), "x").unwrap();
+        assert_eq!(source_stamp(&dir, &out), before);
+
+        std::fs::write(dir.join("src/new.rs"), "").unwrap();
+        assert_ne!(source_stamp(&dir, &out), before);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
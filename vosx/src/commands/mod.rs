@@ -4,20 +4,28 @@
 //! - `service_pvm` — build and validate the protocol infrastructure PVM.
 //! - `production_release` — package and independently verify the pinned
 //!   production service/authority artifacts.
+//! - `dev` — `vosx dev watch`, the actor hot-reload loop; the other
+//!   `vosx dev …` verbs dispatch to the dev extension.
+//! - `node` — one process hosting several spaces; `space up` and
+//!   `space down` attach to / detach from it while it runs.
 //! - `space::*` — everything space-related: lifecycle (new,
 //!   list, info, up, join, delete), program/agent management
 //!   (publish, install, upgrade, uninstall, programs, agents),
 //!   members, generic invoke (`call`), export.
+//! - `whoami` — `vosx whoami rotate`, operator identity key rotation.
 //! - `dynamic` — `vosx <agent-or-extension> <method> [args]`.
 //!   Schema-aware ergonomic surface that sits on the same
 //!   `DaemonClient::invoke_dyn` path `space call` uses. Routing
 //!   into this module is decided in `main` by peeking argv.
 
 pub mod build;
+pub mod dev;
 pub mod dynamic;
 pub mod new_project;
+pub mod node;
 pub mod production_release;
 pub mod run;
 pub mod service_pvm;
 pub mod space;
+pub mod whoami;
 pub mod zk;
@@ -0,0 +1,517 @@
+//! `vosx node *` — one process hosting several spaces.
+//!
+//! `node up` runs each attached space's daemon on a thread of its own,
+//! with the space's lock, data directory, registry and `.endpoint` file
+//! exactly as `space up` would have them, and answers a control socket
+//! at `<data_root>/node.sock`. While it runs, `space up <space>` attaches
+//! a space to it and `space down <space>` detaches one; `node status`
+//! lists what it hosts. SIGINT / SIGTERM detach every space and exit.
+//!
+//! Each space keeps its own libp2p swarm under its own `node.key`.
+//! Identities are per-space so membership in one space can't be linked
+//! to another (see `paths::node_key_path`), and a swarm carries exactly
+//! one identity; the spaces do share the process, its program blob
+//! cache and the operator's single control point. One swarm multiplexed
+//! across spaces is not implemented: it needs per-space identities and
+//! protocol namespaces inside one transport first (see "Shared
+//! multi-space swarm" in `docs/plans/roadmap.md`).
+//!
+//! The control socket is Unix-domain, so `node` commands need a Unix
+//! host; elsewhere `space up` keeps running one daemon per space.
+//!
+//! The control protocol is one JSON request line per connection,
+//! answered by one JSON reply line.
+
+#[cfg(unix)]
+use std::collections::BTreeMap;
+#[cfg(unix)]
+use std::io::{BufRead, BufReader, Write};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(unix)]
+use std::sync::{Arc, Mutex, PoisonError, mpsc};
+#[cfg(unix)]
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::space::up;
+use crate::output;
+#[cfg(unix)]
+use crate::{paths, spaces_index};
+
+/// How often the host checks its shutdown flag between control requests.
+#[cfg(unix)]
+const CONTROL_POLL: Duration = Duration::from_millis(100);
+/// Attaching opens the registry and spawns every installed agent before
+/// the host answers.
+const ATTACH_TIMEOUT: Duration = Duration::from_secs(300);
+/// Detaching waits for the space's agents to flush and exit.
+const DETACH_TIMEOUT: Duration = Duration::from_secs(120);
+const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Subcommand)]
+pub enum NodeCommand {
+    /// Host spaces in this process until Ctrl-C / SIGTERM. Attaches
+    /// the listed spaces at start; while the node runs, `space up`
+    /// and `space down` attach and detach others. Each space still
+    /// runs its own libp2p swarm; there is no shared swarm.
+    Up {
+        /// Space ids or names to attach at start.
+        spaces: Vec<String>,
+    },
+    /// List the spaces the running node hosts. `--format json` for
+    /// machine consumption.
+    Status,
+}
+
+pub fn run(command: NodeCommand) -> anyhow::Result<()> {
+    match command {
+        NodeCommand::Up { spaces } => up_node(spaces),
+        NodeCommand::Status => status(),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Request {
+    Attach(AttachRequest),
+    Detach { space: String },
+    Status,
+}
+
+/// `space up`'s per-run options, forwarded to the host. Paths are made
+/// absolute by the client, whose working directory the host doesn't share.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct AttachRequest {
+    space: String,
+    #[serde(default)]
+    listen: Vec<String>,
+    #[serde(default)]
+    connect: Vec<String>,
+    #[serde(default)]
+    relay: Vec<String>,
+    #[serde(default)]
+    relay_server: bool,
+    service_pvm: Option<PathBuf>,
+    production_trust_socket: Option<PathBuf>,
+    #[serde(default)]
+    allow_v2_conformance: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Reply {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(default)]
+    spaces: Vec<HostedSpace>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct HostedSpace {
+    name: String,
+    id: String,
+    /// Unix seconds at which the space attached.
+    attached_at: u64,
+}
+
+// ── Client side ─────────────────────────────────────────────────────
+
+/// Whether a `node up` host answers on the control socket. A socket file
+/// left by a crashed host refuses the connection and reads as `false`.
+#[cfg(unix)]
+pub fn is_running() -> bool {
+    UnixStream::connect(paths::node_socket_path()).is_ok()
+}
+
+/// The control socket is Unix-domain; elsewhere `space up` always runs a
+/// daemon of its own.
+#[cfg(not(unix))]
+pub fn is_running() -> bool {
+    false
+}
+
+/// Attach the space `lookup` names (already resolved by `space up`) to
+/// the running node with `args`' per-run options.
+pub fn attach(lookup: &str, args: &up::Args) -> anyhow::Result<()> {
+    let absolute = |path: &Option<PathBuf>| path.as_deref().map(std::path::absolute).transpose();
+    let request = Request::Attach(AttachRequest {
+        space: lookup.to_string(),
+        listen: args.listen.clone(),
+        connect: args.connect.clone(),
+        relay: args.relay.clone(),
+        relay_server: args.relay_server,
+        service_pvm: absolute(&args.service_pvm)?,
+        production_trust_socket: absolute(&args.production_trust_socket)?,
+        allow_v2_conformance: args.allow_v2_conformance,
+    });
+    for space in request_node(&request, ATTACH_TIMEOUT)? {
+        println!("attached space '{}' to the running node", space.name);
+    }
+    Ok(())
+}
+
+/// Detach one space from the running node, waiting for it to stop.
+pub fn detach(query: &str) -> anyhow::Result<()> {
+    let request = Request::Detach {
+        space: query.to_string(),
+    };
+    for space in request_node(&request, DETACH_TIMEOUT)? {
+        println!("detached space '{}' from the running node", space.name);
+    }
+    Ok(())
+}
+
+fn status() -> anyhow::Result<()> {
+    let spaces = request_node(&Request::Status, STATUS_TIMEOUT)?;
+    if output::is_json() {
+        output::print_json(&spaces);
+        return Ok(());
+    }
+    if spaces.is_empty() {
+        println!("the node hosts no spaces");
+        return Ok(());
+    }
+    println!("{:<24} {:<14} ATTACHED", "NAME", "ID");
+    for space in &spaces {
+        println!(
+            "{:<24} {:<14} {}",
+            space.name,
+            format!("{}…", &space.id[..12.min(space.id.len())]),
+            format_unix(space.attached_at)
+        );
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn request_node(request: &Request, timeout: Duration) -> anyhow::Result<Vec<HostedSpace>> {
+    let socket = paths::node_socket_path();
+    let mut stream = UnixStream::connect(&socket).map_err(|e| {
+        anyhow::anyhow!(
+            "no node running (control socket {}): {e}. Start one with `vosx node up`.",
+            socket.display()
+        )
+    })?;
+    stream.set_read_timeout(Some(timeout))?;
+    let json = serde_json::to_string(request)?;
+    writeln!(stream, "{json}")?;
+    let mut line = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut line)
+        .map_err(|e| anyhow::anyhow!("node did not answer within {timeout:?}: {e}"))?;
+    let reply: Reply = serde_json::from_str(&line)
+        .map_err(|e| anyhow::anyhow!("node sent a malformed reply: {e}"))?;
+    if let Some(error) = reply.error {
+        anyhow::bail!("{error}");
+    }
+    Ok(reply.spaces)
+}
+
+#[cfg(not(unix))]
+fn request_node(_request: &Request, _timeout: Duration) -> anyhow::Result<Vec<HostedSpace>> {
+    anyhow::bail!("`vosx node` talks over a Unix-domain socket and requires a Unix host")
+}
+
+// ── Host side ───────────────────────────────────────────────────────
+
+#[cfg(unix)]
+#[derive(Default)]
+struct Host {
+    /// Attached spaces by space id.
+    spaces: Mutex<BTreeMap<String, Attached>>,
+    /// Serializes attach and shutdown. Opening a space takes a while, and
+    /// two requests for the same space must not both start it.
+    attaching: Mutex<()>,
+}
+
+#[cfg(unix)]
+struct Attached {
+    view: HostedSpace,
+    /// The space node's own shutdown flag.
+    shutdown: Arc<AtomicBool>,
+    thread: JoinHandle<anyhow::Result<()>>,
+}
+
+#[cfg(not(unix))]
+fn up_node(_spaces: Vec<String>) -> anyhow::Result<()> {
+    anyhow::bail!("`vosx node up` serves a Unix-domain control socket and requires a Unix host")
+}
+
+#[cfg(unix)]
+fn up_node(spaces: Vec<String>) -> anyhow::Result<()> {
+    let socket = paths::node_socket_path();
+    if is_running() {
+        anyhow::bail!(
+            "a node is already running (control socket {})",
+            socket.display()
+        );
+    }
+    if let Some(dir) = socket.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // Left by a host that crashed; nothing answers on it.
+    let _ = std::fs::remove_file(&socket);
+    let listener = UnixListener::bind(&socket)
+        .map_err(|e| anyhow::anyhow!("bind {}: {e}", socket.display()))?;
+    // Attach and detach act as the operator; nobody else may connect.
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&socket, std::fs::Permissions::from_mode(0o600))?;
+    }
+    listener.set_nonblocking(true)?;
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    crate::shutdown::install(shutdown.clone());
+    let host = Arc::new(Host::default());
+    for space in spaces {
+        let request = AttachRequest {
+            space,
+            ..AttachRequest::default()
+        };
+        match host.attach(request) {
+            Ok(view) => println!("attached space '{}'", view.name),
+            Err(e) => {
+                let _ = std::fs::remove_file(&socket);
+                host.detach_all();
+                return Err(e);
+            }
+        }
+    }
+    tracing::info!(
+        "node running until shutdown (Ctrl-C / SIGTERM); control socket {}",
+        socket.display()
+    );
+
+    while !shutdown.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let host = host.clone();
+                std::thread::spawn(move || serve_control(&host, stream));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                host.reap();
+                std::thread::sleep(CONTROL_POLL);
+            }
+            Err(e) => tracing::warn!("node control socket: {e}"),
+        }
+    }
+    // Stop taking requests first, so a late `space up` starts a daemon of
+    // its own instead of attaching to a host that is going away.
+    let _ = std::fs::remove_file(&socket);
+    host.detach_all();
+    Ok(())
+}
+
+#[cfg(unix)]
+fn serve_control(host: &Host, mut stream: UnixStream) {
+    let _ = stream.set_nonblocking(false);
+    let mut line = String::new();
+    if BufReader::new(&stream).read_line(&mut line).is_err() {
+        return;
+    }
+    let result = match serde_json::from_str::<Request>(&line) {
+        Ok(Request::Attach(request)) => host.attach(request).map(|view| vec![view]),
+        Ok(Request::Detach { space }) => host.detach(&space).map(|view| vec![view]),
+        Ok(Request::Status) => Ok(host.status()),
+        Err(e) => Err(anyhow::anyhow!("malformed node request: {e}")),
+    };
+    let reply = match result {
+        Ok(spaces) => Reply {
+            error: None,
+            spaces,
+        },
+        Err(e) => Reply {
+            error: Some(format!("{e:#}")),
+            spaces: Vec::new(),
+        },
+    };
+    if let Ok(json) = serde_json::to_string(&reply) {
+        let _ = writeln!(stream, "{json}");
+    }
+}
+
+#[cfg(unix)]
+impl Host {
+    /// Start `request.space` on a thread of its own and wait until it has
+    /// published its endpoint, or failed trying.
+    fn attach(&self, request: AttachRequest) -> anyhow::Result<HostedSpace> {
+        let _serial = self
+            .attaching
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.reap();
+        let index = spaces_index::load()?;
+        let entry = spaces_index::find(&index, &request.space)?.clone();
+        if self.lock_spaces().contains_key(&entry.id) {
+            anyhow::bail!("space '{}' is already attached to this node", entry.name);
+        }
+
+        let (attached, on_attach) = mpsc::channel();
+        let args = up::Args {
+            query: entry.id.clone(),
+            once: false,
+            listen: request.listen,
+            connect: request.connect,
+            relay: request.relay,
+            relay_server: request.relay_server,
+            service_pvm: request.service_pvm,
+            production_trust_socket: request.production_trust_socket,
+            allow_v2_conformance: request.allow_v2_conformance,
+        };
+        let name = entry.name.clone();
+        let thread = std::thread::Builder::new()
+            .name(format!("space-{}", entry.name))
+            .spawn(move || {
+                let _span = tracing::info_span!("space", name = %name).entered();
+                up::serve(args, Some(up::Hosted { attached }))
+            })?;
+        let Ok(shutdown) = on_attach.recv() else {
+            // The space dropped its sender without attaching: it failed
+            // during boot, and its thread has the error.
+            return Err(match thread.join() {
+                Ok(Err(e)) => e.context(format!("space '{}' failed to start", entry.name)),
+                Ok(Ok(())) => anyhow::anyhow!("space '{}' exited before attaching", entry.name),
+                Err(_) => anyhow::anyhow!("space '{}' panicked while starting", entry.name),
+            });
+        };
+        let view = HostedSpace {
+            name: entry.name.clone(),
+            id: entry.id.clone(),
+            attached_at: unix_now(),
+        };
+        tracing::info!(space = %entry.name, "space attached");
+        self.lock_spaces().insert(
+            entry.id,
+            Attached {
+                view: view.clone(),
+                shutdown,
+                thread,
+            },
+        );
+        Ok(view)
+    }
+
+    /// Stop one space and wait for its daemon to exit.
+    fn detach(&self, query: &str) -> anyhow::Result<HostedSpace> {
+        let index = spaces_index::load()?;
+        let entry = spaces_index::find(&index, query)?;
+        let attached = self.lock_spaces().remove(&entry.id).ok_or_else(|| {
+            anyhow::anyhow!("space '{}' is not attached to this node", entry.name)
+        })?;
+        attached.shutdown.store(true, Ordering::Relaxed);
+        match attached.thread.join() {
+            Ok(Ok(())) => {
+                tracing::info!(space = %entry.name, "space detached");
+                Ok(attached.view)
+            }
+            Ok(Err(e)) => Err(e.context(format!("space '{}' stopped with an error", entry.name))),
+            Err(_) => anyhow::bail!("space '{}' panicked while stopping", entry.name),
+        }
+    }
+
+    fn status(&self) -> Vec<HostedSpace> {
+        self.reap();
+        self.lock_spaces()
+            .values()
+            .map(|attached| attached.view.clone())
+            .collect()
+    }
+
+    /// Stop every space, after any attach in flight has finished.
+    fn detach_all(&self) {
+        let _serial = self
+            .attaching
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let spaces = std::mem::take(&mut *self.lock_spaces());
+        for attached in spaces.values() {
+            attached.shutdown.store(true, Ordering::Relaxed);
+        }
+        for attached in spaces.into_values() {
+            log_exit(&attached.view.name, attached.thread.join());
+        }
+    }
+
+    /// Drop spaces whose daemon stopped on its own, logging why.
+    fn reap(&self) {
+        let finished: Vec<Attached> = {
+            let mut spaces = self.lock_spaces();
+            let ids: Vec<String> = spaces
+                .iter()
+                .filter(|(_, attached)| attached.thread.is_finished())
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.iter().filter_map(|id| spaces.remove(id)).collect()
+        };
+        for attached in finished {
+            log_exit(&attached.view.name, attached.thread.join());
+        }
+    }
+
+    fn lock_spaces(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Attached>> {
+        self.spaces.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(unix)]
+fn log_exit(name: &str, result: std::thread::Result<anyhow::Result<()>>) {
+    match result {
+        Ok(Ok(())) => tracing::info!(space = %name, "space stopped"),
+        Ok(Err(e)) => tracing::error!(space = %name, "space stopped: {e:#}"),
+        Err(_) => tracing::error!(space = %name, "space panicked"),
+    }
+}
+
+#[cfg(unix)]
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// `2026-03-01T12:00:05Z`, or the raw number if it is out of range.
+fn format_unix(unix: u64) -> String {
+    const FORMAT: &[time::format_description::FormatItem<'_>] =
+        time::macros::format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]Z");
+    i64::try_from(unix)
+        .ok()
+        .and_then(|unix| time::OffsetDateTime::from_unix_timestamp(unix).ok())
+        .and_then(|at| at.format(FORMAT).ok())
+        .unwrap_or_else(|| unix.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_requests_keep_their_wire_shape() {
+        let json = serde_json::to_string(&Request::Detach {
+            space: "demo".into(),
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"op":"detach","space":"demo"}"#);
+
+        // Options a client leaves out take `space up`'s defaults.
+        match serde_json::from_str(r#"{"op":"attach","space":"demo","relay_server":true}"#) {
+            Ok(Request::Attach(request)) => {
+                assert_eq!(request.space, "demo");
+                assert!(request.relay_server);
+                assert!(request.listen.is_empty());
+                assert!(request.service_pvm.is_none());
+            }
+            other => panic!("expected an attach request, got {other:?}"),
+        }
+
+        let reply: Reply =
+            serde_json::from_str(r#"{"error":"space 'x' is not attached"}"#).unwrap();
+        assert!(reply.spaces.is_empty());
+        assert_eq!(reply.error.as_deref(), Some("space 'x' is not attached"));
+    }
+}
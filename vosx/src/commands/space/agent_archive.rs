@@ -0,0 +1,1024 @@
+//! `space agent-export` / `space agent-import` — move one agent's state
+//! between spaces.
+//!
+//! An export is a directory, like a `space backup`, so operators can
+//! inspect it with ordinary tools:
+//!
+//! ```text
+//! <archive>/
+//!   manifest.json     # agent identity, install settings, file digests
+//!   signature.json    # exporting operator's key + signature over the manifest
+//!   program.bin       # exact catalog artifact the agent runs
+//!   vos_meta.bin      # `.vos_meta` schema, when the registry has one
+//!   state.rows        # `state` table: state blob and commit-strategy rows
+//!   storage.rows      # `agent_kv` table: the agent's storage rows
+//!   dag.rows          # `dag` table: Merkle-CRDT history (CRDT agents only)
+//! ```
+//!
+//! The rows are read from a point-in-time copy the daemon takes under its
+//! write barrier (the `space backup --online` snapshot). Only the portable
+//! tables travel: a Raft agent's snapshot is its applied state and storage
+//! rows, while its log, term and membership describe the source group and
+//! are left behind. Import verifies the signature and every digest before
+//! it touches the target space, seeds a fresh database for the new
+//! installation, installs it under a replication id bound to the archive
+//! digest, and records where the state came from in the registry.
+//!
+//! v2 root trees (`VOSP` programs) are not exportable: their images are
+//! bound to the `(space, root service)` identity they were created under.
+//! Export refuses them by name, pointing at `space backup`.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use redb::ReadableTable;
+use serde::{Deserialize, Serialize};
+use vos::commit::{DAG_TABLE, KV_TABLE, STATE_TABLE};
+use vos::node::Consistency;
+use vos::registry::{AgentRow, Status, SyncFloor};
+
+use crate::blob_store::{self, BlobHash};
+use crate::commands::space::backup::{self, PartialDirectory};
+use crate::commands::space::client::DaemonClient;
+use crate::commands::space::common::{
+    consistency_from_u8, consistency_name, imported_replication_id, instance_service_id,
+};
+use crate::output;
+use crate::spaces_index::SpaceEntry;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const SIGNATURE_FILE: &str = "signature.json";
+const PROGRAM_FILE: &str = "program.bin";
+const META_FILE: &str = "vos_meta.bin";
+const STATE_FILE: &str = "state.rows";
+const STORAGE_FILE: &str = "storage.rows";
+const DAG_FILE: &str = "dag.rows";
+const ARCHIVE_FORMAT: &str = "VOSA1";
+const ARCHIVE_VERSION: u32 = 1;
+const MAX_MANIFEST_BYTES: u64 = 1024 * 1024;
+const MAX_SIGNATURE_BYTES: u64 = 64 * 1024;
+/// Separates an archive signature from every other use of the operator key.
+const SIGNATURE_DOMAIN: &[u8] = b"vosx-agent-archive/v1";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AgentManifest {
+    format: String,
+    version: u32,
+    source_space: String,
+    source_space_name: String,
+    instance_name: String,
+    replication_id: String,
+    consistency: u8,
+    program_name: String,
+    program_version: String,
+    program_hash: String,
+    /// Signed catalog capability of the program, republished on import.
+    crdt: bool,
+    network_reachable: bool,
+    sync_role: String,
+    /// Hex of the rkyv `InitArgs` the agent was installed with. `on_start`
+    /// payloads are not carried: they already ran against this state.
+    install_args: String,
+    files: Vec<ArchiveFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArchiveFile {
+    path: String,
+    bytes: u64,
+    blake2b_256: String,
+    /// Row count of a `.rows` file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rows: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveSignature {
+    /// libp2p protobuf encoding of the exporting operator's public key.
+    public_key: String,
+    /// Signature over `SIGNATURE_DOMAIN || blake2b-256(manifest.json)`.
+    signature: String,
+}
+
+#[derive(Serialize)]
+struct ExportedView<'a> {
+    instance_name: &'a str,
+    archive: String,
+    manifest: String,
+    state_rows: u64,
+    storage_rows: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dag_rows: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct ImportedView<'a> {
+    instance_name: &'a str,
+    source_space: &'a str,
+    source_instance: &'a str,
+    program_hash: &'a str,
+    replication_id: String,
+    consistency: &'a str,
+    exporter: String,
+}
+
+pub struct ExportArgs {
+    pub space: String,
+    pub instance: String,
+    /// New archive directory. Existing paths are never overwritten.
+    pub output: PathBuf,
+}
+
+pub struct ImportArgs {
+    pub space: String,
+    pub archive: PathBuf,
+    /// Install under this name instead of the exported one.
+    pub name: Option<String>,
+    /// Refuse archives not signed by this PeerId.
+    pub signer: Option<String>,
+}
+
+pub fn run_export(args: ExportArgs) -> anyhow::Result<()> {
+    if backup::path_entry_exists(&args.output)? {
+        anyhow::bail!(
+            "archive destination already exists: {}",
+            args.output.display()
+        );
+    }
+    // The daemon writes its snapshot beside the archive, so resolve an
+    // absolute destination first.
+    let parent = backup::usable_parent(&args.output);
+    let parent = fs::canonicalize(parent)
+        .map_err(|error| anyhow::anyhow!("resolve {}: {error}", parent.display()))?;
+    let output = parent.join(
+        args.output
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("archive destination needs a final path component"))?,
+    );
+    let signer = crate::identity::load_or_create()?;
+
+    let (manifest, digest) = DaemonClient::with_connect(&args.space, |client| {
+        let data_dir = fs::canonicalize(&client.entry.data_dir)
+            .map_err(|error| anyhow::anyhow!("resolve {}: {error}", client.entry.data_dir))?;
+        if output.starts_with(&data_dir) {
+            anyhow::bail!("archive destination must be outside the space data directory");
+        }
+        let agent = client
+            .agent(&args.instance)?
+            .ok_or_else(|| anyhow::anyhow!("no agent named '{}' is installed", args.instance))?;
+        match consistency_from_u8(agent.consistency) {
+            Some(Consistency::Local | Consistency::Crdt | Consistency::Raft) => {}
+            Some(Consistency::Ephemeral) => anyhow::bail!(
+                "'{}' is ephemeral and holds no durable state to export",
+                agent.instance_name,
+            ),
+            None => anyhow::bail!(
+                "'{}' has unknown consistency {}",
+                agent.instance_name,
+                agent.consistency,
+            ),
+        }
+        let program = blob_store::cache_get(&BlobHash(agent.program_hash))
+            .map_err(|error| anyhow::anyhow!("program blob: {error}"))?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "program blob {} is not in the local cache",
+                    BlobHash(agent.program_hash),
+                )
+            })?;
+        if program.get(..4) == Some(b"VOSP") {
+            anyhow::bail!(
+                "'{}' is a v2 root tree; its image is bound to this space's service identity \
+                 and cannot be imported elsewhere; move it with `vosx space backup`",
+                agent.instance_name,
+            );
+        }
+        let catalog = client
+            .program(&agent.program_name, &agent.program_version)?
+            .filter(|row| row.hash == agent.program_hash)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "catalog entry {}:{} no longer matches the agent's pinned program",
+                    agent.program_name,
+                    agent.program_version,
+                )
+            })?;
+        let meta = client.meta_for_instance(&agent.instance_name)?;
+        let snapshot = backup::take_online_snapshot(client, "agent-snapshot")?;
+        let service = instance_service_id(&agent.instance_name, client.daemon_prefix());
+        let database = snapshot
+            .path()
+            .join("agents")
+            .join(format!("{:08x}.redb", service.0));
+        if !database.is_file() {
+            anyhow::bail!(
+                "'{}' has no state on this node yet; export from a node that runs it",
+                agent.instance_name,
+            );
+        }
+        let contents = ArchiveContents {
+            entry: &client.entry,
+            agent: &agent,
+            crdt: catalog.crdt,
+            program: &program,
+            meta: &meta,
+            database: &database,
+        };
+        write_archive(&output, &contents, &signer)
+    })?;
+
+    let row_count = |path: &str| {
+        manifest
+            .files
+            .iter()
+            .find(|file| file.path == path)
+            .and_then(|file| file.rows)
+    };
+    let view = ExportedView {
+        instance_name: &manifest.instance_name,
+        archive: output.display().to_string(),
+        manifest: hex::encode(digest),
+        state_rows: row_count(STATE_FILE).unwrap_or_default(),
+        storage_rows: row_count(STORAGE_FILE).unwrap_or_default(),
+        dag_rows: row_count(DAG_FILE),
+    };
+    if output::is_json() {
+        output::print_json(&view);
+    } else {
+        println!("exported {} to {}", view.instance_name, view.archive);
+        println!("  manifest     = {}", view.manifest);
+        println!("  state rows   = {}", view.state_rows);
+        println!("  storage rows = {}", view.storage_rows);
+        if let Some(dag_rows) = view.dag_rows {
+            println!("  dag rows     = {dag_rows}");
+        }
+    }
+    Ok(())
+}
+
+pub fn run_import(args: ImportArgs) -> anyhow::Result<()> {
+    let archive = verify_archive(&args.archive, args.signer.as_deref())?;
+    let manifest = &archive.manifest;
+    let instance_name = args.name.unwrap_or_else(|| manifest.instance_name.clone());
+    let program_hash = decode_32(&manifest.program_hash, "program_hash")?;
+    let source_space = decode_32(&manifest.source_space, "source_space")?;
+    let source_replication_id = decode_32(&manifest.replication_id, "replication_id")?;
+    let install_args = hex::decode(&manifest.install_args)
+        .map_err(|_| anyhow::anyhow!("archive install_args is not hex"))?;
+    let sync_role = SyncFloor::parse(&manifest.sync_role)
+        .ok_or_else(|| anyhow::anyhow!("archive sync floor '{}' is unknown", manifest.sync_role))?;
+    let program = fs::read(archive.root.join(PROGRAM_FILE))?;
+    blob_store::cache_put(&program).map_err(|error| anyhow::anyhow!("cache program: {error}"))?;
+    let meta = if archive.has(META_FILE) {
+        fs::read(archive.root.join(META_FILE))?
+    } else {
+        Vec::new()
+    };
+
+    DaemonClient::with_connect(&args.space, |client| {
+        let space_id = client
+            .entry
+            .id_bytes()
+            .ok_or_else(|| anyhow::anyhow!("space id in index is not 32 bytes of hex"))?;
+        if client.agent(&instance_name)?.is_some() {
+            anyhow::bail!(
+                "an agent named '{instance_name}' is already installed; pass --name to import \
+                 under another name",
+            );
+        }
+        ensure_program(client, manifest, &program_hash, &meta)?;
+
+        let service = instance_service_id(&instance_name, client.daemon_prefix());
+        let database = Path::new(&client.entry.data_dir)
+            .join("agents")
+            .join(format!("{:08x}.redb", service.0));
+        if backup::path_entry_exists(&database)? {
+            anyhow::bail!(
+                "{} already exists; refusing to overwrite another installation's state",
+                database.display(),
+            );
+        }
+        let mut placed = PlacedDatabase::new(database.clone());
+        let staging = backup::temporary_sibling(&database, "agent-import")?;
+        let seeded = seed_database(&archive, &staging).and_then(|()| {
+            fs::rename(&staging, &database)
+                .map_err(|error| anyhow::anyhow!("place {}: {error}", database.display()))
+        });
+        if let Err(error) = seeded {
+            let _ = fs::remove_file(&staging);
+            return Err(error);
+        }
+
+        let replication_id =
+            imported_replication_id(&space_id, &instance_name, &program_hash, &archive.digest);
+        let status = client.install(
+            instance_name.clone(),
+            manifest.program_name.clone(),
+            manifest.program_version.clone(),
+            program_hash.to_vec(),
+            replication_id.to_vec(),
+            manifest.consistency,
+            install_args,
+            Vec::new(),
+            manifest.network_reachable,
+            sync_role,
+        )?;
+        match status {
+            Status::Ok => placed.disarm(),
+            Status::InstanceExists => {
+                anyhow::bail!("an agent named '{instance_name}' was installed concurrently")
+            }
+            Status::ReplicationIdReused => anyhow::bail!(
+                "this archive was already imported as '{instance_name}' in this space; pass \
+                 --name to import another copy",
+            ),
+            Status::ConsistencyWidenDenied => anyhow::bail!(
+                "'{instance_name}' was previously installed at a more confined consistency tier; \
+                 pass --name to import under a fresh name",
+            ),
+            other => anyhow::bail!("install returned status {other}"),
+        }
+
+        let exporter = archive.exporter.to_bytes();
+        match client.record_provenance(
+            instance_name.clone(),
+            source_space.to_vec(),
+            manifest.instance_name.clone(),
+            source_replication_id.to_vec(),
+            archive.digest.to_vec(),
+            exporter,
+        )? {
+            Status::Ok => {}
+            other => anyhow::bail!(
+                "'{instance_name}' is installed, but recording its provenance returned \
+                 status {other}",
+            ),
+        }
+
+        let view = ImportedView {
+            instance_name: &instance_name,
+            source_space: &manifest.source_space_name,
+            source_instance: &manifest.instance_name,
+            program_hash: &manifest.program_hash,
+            replication_id: hex::encode(replication_id),
+            consistency: consistency_name(manifest.consistency),
+            exporter: archive.exporter.to_string(),
+        };
+        if output::is_json() {
+            output::print_json(&view);
+        } else {
+            println!(
+                "imported {} from {}/{}",
+                view.instance_name, view.source_space, view.source_instance,
+            );
+            println!(
+                "  program        = {}:{}",
+                manifest.program_name, manifest.program_version,
+            );
+            println!("  program_hash   = {}", view.program_hash);
+            println!("  replication_id = {}", view.replication_id);
+            println!("  consistency    = {}", view.consistency);
+            println!("  exported by    = {}", view.exporter);
+        }
+        Ok(())
+    })
+}
+
+/// Publish the archived program when the target catalog lacks it. A tag
+/// already bound to other bytes is immutable, so that is a hard error.
+fn ensure_program(
+    client: &DaemonClient,
+    manifest: &AgentManifest,
+    program_hash: &[u8; 32],
+    meta: &[u8],
+) -> anyhow::Result<()> {
+    let (name, version) = (&manifest.program_name, &manifest.program_version);
+    match client.program(name, version)? {
+        Some(row) if row.hash == *program_hash => {}
+        Some(row) => anyhow::bail!(
+            "{name}:{version} is already published here with hash {}, not the archived {}; \
+             tags are immutable",
+            hex::encode(row.hash),
+            manifest.program_hash,
+        ),
+        None => match client.publish(
+            name.clone(),
+            version.clone(),
+            program_hash.to_vec(),
+            manifest.crdt,
+        )? {
+            Status::Ok => {}
+            other => anyhow::bail!("publish {name}:{version} returned status {other}"),
+        },
+    }
+    // Best-effort, as in `space publish`: the schema only feeds dynamic
+    // dispatch and may already have arrived by sync.
+    if !meta.is_empty()
+        && let Err(error) = client.register_meta(program_hash.to_vec(), meta.to_vec())
+    {
+        tracing::debug!("register_meta for imported program skipped: {error}");
+    }
+    Ok(())
+}
+
+/// The agent database placed for an import, removed unless the install
+/// that adopts it succeeds.
+struct PlacedDatabase {
+    path: PathBuf,
+    armed: bool,
+}
+
+impl PlacedDatabase {
+    fn new(path: PathBuf) -> Self {
+        Self { path, armed: true }
+    }
+
+    fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for PlacedDatabase {
+    fn drop(&mut self) {
+        if self.armed {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Everything one export writes, gathered while connected to the daemon.
+struct ArchiveContents<'a> {
+    entry: &'a SpaceEntry,
+    agent: &'a AgentRow,
+    crdt: bool,
+    program: &'a [u8],
+    meta: &'a [u8],
+    /// The agent's database inside the daemon's snapshot.
+    database: &'a Path,
+}
+
+/// Build the archive in a hidden sibling and rename it into place once it
+/// is complete and signed. Returns the manifest and its digest.
+fn write_archive(
+    output: &Path,
+    contents: &ArchiveContents<'_>,
+    signer: &libp2p::identity::Keypair,
+) -> anyhow::Result<(AgentManifest, [u8; 32])> {
+    let agent = contents.agent;
+    let staging = backup::temporary_sibling(output, "agent-export")?;
+    fs::create_dir(&staging)
+        .map_err(|error| anyhow::anyhow!("create {}: {error}", staging.display()))?;
+    let mut partial = PartialDirectory::new(staging.clone());
+    backup::set_directory_private(&staging)?;
+
+    let mut files = vec![write_file(&staging, PROGRAM_FILE, contents.program)?];
+    if !contents.meta.is_empty() {
+        files.push(write_file(&staging, META_FILE, contents.meta)?);
+    }
+    let crdt_history = consistency_from_u8(agent.consistency) == Some(Consistency::Crdt);
+    files.extend(export_rows(contents.database, &staging, crdt_history)?);
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let manifest = AgentManifest {
+        format: ARCHIVE_FORMAT.into(),
+        version: ARCHIVE_VERSION,
+        source_space: contents.entry.id.clone(),
+        source_space_name: contents.entry.name.clone(),
+        instance_name: agent.instance_name.clone(),
+        replication_id: hex::encode(agent.replication_id),
+        consistency: agent.consistency,
+        program_name: agent.program_name.clone(),
+        program_version: agent.program_version.clone(),
+        program_hash: hex::encode(agent.program_hash),
+        crdt: contents.crdt,
+        network_reachable: agent.network_reachable,
+        sync_role: agent.sync_role.as_str().into(),
+        install_args: hex::encode(&agent.install_args),
+        files,
+    };
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)?;
+    let digest = backup::hash_bytes(&manifest_bytes);
+    let signature = ArchiveSignature {
+        public_key: hex::encode(signer.public().encode_protobuf()),
+        signature: hex::encode(
+            signer
+                .sign(&signing_message(&digest))
+                .map_err(|error| anyhow::anyhow!("sign agent archive: {error}"))?,
+        ),
+    };
+    write_file(&staging, MANIFEST_FILE, &manifest_bytes)?;
+    write_file(
+        &staging,
+        SIGNATURE_FILE,
+        &serde_json::to_vec_pretty(&signature)?,
+    )?;
+    backup::sync_directory(&staging)?;
+    fs::rename(&staging, output).map_err(|error| {
+        anyhow::anyhow!(
+            "rename {} -> {}: {error}",
+            staging.display(),
+            output.display()
+        )
+    })?;
+    partial.disarm();
+    backup::sync_directory(backup::usable_parent(output))?;
+    Ok((manifest, digest))
+}
+
+fn signing_message(manifest_digest: &[u8; 32]) -> Vec<u8> {
+    [SIGNATURE_DOMAIN, manifest_digest.as_slice()].concat()
+}
+
+fn write_file(root: &Path, name: &str, bytes: &[u8]) -> anyhow::Result<ArchiveFile> {
+    let path = root.join(name);
+    let mut file = fs::File::create(&path)
+        .map_err(|error| anyhow::anyhow!("create {}: {error}", path.display()))?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    Ok(ArchiveFile {
+        path: name.into(),
+        bytes: bytes.len() as u64,
+        blake2b_256: hex::encode(backup::hash_bytes(bytes)),
+        rows: None,
+    })
+}
+
+/// Stream the portable tables of an agent database into `.rows` files.
+/// Tables the agent never created export as empty files.
+fn export_rows(
+    database: &Path,
+    root: &Path,
+    crdt_history: bool,
+) -> anyhow::Result<Vec<ArchiveFile>> {
+    let db = redb::Database::open(database)
+        .map_err(|error| anyhow::anyhow!("open {}: {error}", database.display()))?;
+    let txn = db.begin_read()?;
+    let mut files = Vec::new();
+
+    let mut rows = RowsWriter::create(root, STATE_FILE)?;
+    match txn.open_table(STATE_TABLE) {
+        Ok(table) => {
+            for row in table.iter()? {
+                let (key, value) = row?;
+                rows.push(key.value().as_bytes(), value.value())?;
+            }
+        }
+        Err(redb::TableError::TableDoesNotExist(_)) => {}
+        Err(error) => return Err(error.into()),
+    }
+    files.push(rows.finish()?);
+
+    let mut tables = vec![(STORAGE_FILE, KV_TABLE)];
+    if crdt_history {
+        tables.push((DAG_FILE, DAG_TABLE));
+    }
+    for (name, definition) in tables {
+        let mut rows = RowsWriter::create(root, name)?;
+        match txn.open_table(definition) {
+            Ok(table) => {
+                for row in table.iter()? {
+                    let (key, value) = row?;
+                    rows.push(key.value(), value.value())?;
+                }
+            }
+            Err(redb::TableError::TableDoesNotExist(_)) => {}
+            Err(error) => return Err(error.into()),
+        }
+        files.push(rows.finish()?);
+    }
+    Ok(files)
+}
+
+/// Writes one `.rows` file: `[key_len: u32 LE][key][value_len: u32 LE]
+/// [value]` per row, in the table's key order.
+struct RowsWriter {
+    name: &'static str,
+    path: PathBuf,
+    out: BufWriter<fs::File>,
+    rows: u64,
+}
+
+impl RowsWriter {
+    fn create(root: &Path, name: &'static str) -> anyhow::Result<Self> {
+        let path = root.join(name);
+        let file = fs::File::create(&path)
+            .map_err(|error| anyhow::anyhow!("create {}: {error}", path.display()))?;
+        Ok(Self {
+            name,
+            path,
+            out: BufWriter::new(file),
+            rows: 0,
+        })
+    }
+
+    fn push(&mut self, key: &[u8], value: &[u8]) -> anyhow::Result<()> {
+        for field in [key, value] {
+            let len = u32::try_from(field.len())
+                .map_err(|_| anyhow::anyhow!("{} row exceeds 4 GiB", self.name))?;
+            self.out.write_all(&len.to_le_bytes())?;
+            self.out.write_all(field)?;
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> anyhow::Result<ArchiveFile> {
+        let file = self
+            .out
+            .into_inner()
+            .map_err(|error| anyhow::anyhow!("write {}: {error}", self.path.display()))?;
+        file.sync_all()?;
+        Ok(ArchiveFile {
+            path: self.name.into(),
+            bytes: file.metadata()?.len(),
+            blake2b_256: hex::encode(backup::hash_file(&self.path)?),
+            rows: Some(self.rows),
+        })
+    }
+}
+
+/// Visit every row of a verified `.rows` file, checking the framing and
+/// that keys strictly ascend as the exporting table emitted them.
+fn for_each_row(
+    path: &Path,
+    mut visit: impl FnMut(&[u8], &[u8]) -> anyhow::Result<()>,
+) -> anyhow::Result<u64> {
+    let mut remaining = fs::metadata(path)?.len();
+    let mut input = BufReader::new(fs::File::open(path)?);
+    let mut previous: Option<Vec<u8>> = None;
+    let mut rows = 0u64;
+    let mut read_field = |remaining: &mut u64| -> anyhow::Result<Vec<u8>> {
+        let mut len = [0u8; 4];
+        input.read_exact(&mut len)?;
+        let len = u64::from(u32::from_le_bytes(len));
+        *remaining = remaining
+            .checked_sub(4 + len)
+            .ok_or_else(|| anyhow::anyhow!("{} is truncated", path.display()))?;
+        let mut field = vec![0u8; len as usize];
+        input.read_exact(&mut field)?;
+        Ok(field)
+    };
+    while remaining > 0 {
+        let key = read_field(&mut remaining)?;
+        let value = read_field(&mut remaining)?;
+        if previous.as_ref().is_some_and(|previous| *previous >= key) {
+            anyhow::bail!(
+                "{} rows are not in strictly ascending key order",
+                path.display()
+            );
+        }
+        visit(&key, &value)?;
+        previous = Some(key);
+        rows += 1;
+    }
+    Ok(rows)
+}
+
+/// Write the archived tables into a new database for the importing
+/// installation.
+fn seed_database(archive: &VerifiedArchive, path: &Path) -> anyhow::Result<()> {
+    let db = redb::Database::create(path)
+        .map_err(|error| anyhow::anyhow!("create {}: {error}", path.display()))?;
+    let txn = db.begin_write()?;
+    {
+        let mut table = txn.open_table(STATE_TABLE)?;
+        for_each_row(&archive.root.join(STATE_FILE), |key, value| {
+            let key = std::str::from_utf8(key)
+                .map_err(|_| anyhow::anyhow!("{STATE_FILE} holds a non-UTF-8 key"))?;
+            table.insert(key, value)?;
+            Ok(())
+        })?;
+    }
+    let mut tables = vec![(STORAGE_FILE, KV_TABLE)];
+    if archive.has(DAG_FILE) {
+        tables.push((DAG_FILE, DAG_TABLE));
+    }
+    for (name, definition) in tables {
+        let mut table = txn.open_table(definition)?;
+        for_each_row(&archive.root.join(name), |key, value| {
+            table.insert(key, value)?;
+            Ok(())
+        })?;
+    }
+    txn.commit()?;
+    Ok(())
+}
+
+/// An archive whose signature, file set and digests all checked out.
+struct VerifiedArchive {
+    root: PathBuf,
+    manifest: AgentManifest,
+    /// BLAKE2b-256 of `manifest.json` as stored and signed.
+    digest: [u8; 32],
+    exporter: libp2p::PeerId,
+}
+
+impl VerifiedArchive {
+    fn has(&self, name: &str) -> bool {
+        self.manifest.files.iter().any(|file| file.path == name)
+    }
+}
+
+fn verify_archive(root: &Path, expected_signer: Option<&str>) -> anyhow::Result<VerifiedArchive> {
+    let manifest_bytes = read_bounded(&root.join(MANIFEST_FILE), MAX_MANIFEST_BYTES)?;
+    let signature: ArchiveSignature = serde_json::from_slice(&read_bounded(
+        &root.join(SIGNATURE_FILE),
+        MAX_SIGNATURE_BYTES,
+    )?)
+    .map_err(|error| anyhow::anyhow!("malformed {SIGNATURE_FILE}: {error}"))?;
+    let public_key = hex::decode(&signature.public_key)
+        .ok()
+        .and_then(|bytes| libp2p::identity::PublicKey::try_decode_protobuf(&bytes).ok())
+        .ok_or_else(|| anyhow::anyhow!("{SIGNATURE_FILE} carries a malformed public key"))?;
+    let signature_bytes = hex::decode(&signature.signature)
+        .map_err(|_| anyhow::anyhow!("{SIGNATURE_FILE} signature is not hex"))?;
+    let digest = backup::hash_bytes(&manifest_bytes);
+    if !public_key.verify(&signing_message(&digest), &signature_bytes) {
+        anyhow::bail!("agent archive signature does not verify");
+    }
+    let exporter = libp2p::PeerId::from(public_key);
+    if let Some(expected) = expected_signer {
+        let expected: libp2p::PeerId = expected
+            .parse()
+            .map_err(|error| anyhow::anyhow!("--signer is not a PeerId: {error}"))?;
+        if expected != exporter {
+            anyhow::bail!("agent archive is signed by {exporter}, not {expected}");
+        }
+    }
+
+    let manifest: AgentManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|error| anyhow::anyhow!("malformed {MANIFEST_FILE}: {error}"))?;
+    if manifest.format != ARCHIVE_FORMAT || manifest.version != ARCHIVE_VERSION {
+        anyhow::bail!(
+            "unsupported agent archive {} v{}",
+            manifest.format,
+            manifest.version,
+        );
+    }
+    let crdt_history = match consistency_from_u8(manifest.consistency) {
+        Some(Consistency::Local | Consistency::Raft) => false,
+        Some(Consistency::Crdt) => true,
+        _ => anyhow::bail!(
+            "archive consistency {} is not exportable",
+            manifest.consistency
+        ),
+    };
+
+    let mut expected = BTreeSet::from([PROGRAM_FILE, STATE_FILE, STORAGE_FILE]);
+    if crdt_history {
+        expected.insert(DAG_FILE);
+    }
+    let listed: BTreeSet<&str> = manifest
+        .files
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect();
+    if listed.len() != manifest.files.len() {
+        anyhow::bail!("{MANIFEST_FILE} lists a file twice");
+    }
+    let optional = BTreeSet::from([META_FILE]);
+    if !expected.is_subset(&listed)
+        || !listed.is_subset(&expected.union(&optional).copied().collect())
+    {
+        anyhow::bail!("{MANIFEST_FILE} does not list the expected archive files");
+    }
+    let mut present = BTreeSet::new();
+    for entry in
+        fs::read_dir(root).map_err(|error| anyhow::anyhow!("read {}: {error}", root.display()))?
+    {
+        let name = entry?.file_name();
+        present.insert(
+            name.to_str()
+                .ok_or_else(|| anyhow::anyhow!("archive holds a non-UTF-8 file name"))?
+                .to_string(),
+        );
+    }
+    let mut wanted: BTreeSet<String> = listed.iter().map(|name| name.to_string()).collect();
+    wanted.extend([MANIFEST_FILE.to_string(), SIGNATURE_FILE.to_string()]);
+    if present != wanted {
+        anyhow::bail!("archive files do not match {MANIFEST_FILE}");
+    }
+
+    for file in &manifest.files {
+        let path = root.join(&file.path);
+        let metadata = fs::symlink_metadata(&path)?;
+        if !metadata.is_file() || metadata.len() != file.bytes {
+            anyhow::bail!("{} does not match its manifest size", file.path);
+        }
+        if hex::encode(backup::hash_file(&path)?) != file.blake2b_256 {
+            anyhow::bail!("{} does not match its manifest digest", file.path);
+        }
+        if file.path.ends_with(".rows") {
+            let rows = for_each_row(&path, |_, _| Ok(()))?;
+            if file.rows != Some(rows) {
+                anyhow::bail!("{} does not match its manifest row count", file.path);
+            }
+        }
+    }
+    let program_hash = decode_32(&manifest.program_hash, "program_hash")?;
+    let program = fs::read(root.join(PROGRAM_FILE))?;
+    if BlobHash::of(&program).0 != program_hash {
+        anyhow::bail!("{PROGRAM_FILE} is not the program the agent was pinned to");
+    }
+    Ok(VerifiedArchive {
+        root: root.to_path_buf(),
+        manifest,
+        digest,
+        exporter,
+    })
+}
+
+fn read_bounded(path: &Path, limit: u64) -> anyhow::Result<Vec<u8>> {
+    let file = fs::File::open(path)
+        .map_err(|error| anyhow::anyhow!("read {}: {error}", path.display()))?;
+    let mut bytes = Vec::new();
+    file.take(limit + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > limit {
+        anyhow::bail!("{} exceeds {limit} bytes", path.display());
+    }
+    Ok(bytes)
+}
+
+fn decode_32(hex_value: &str, field: &str) -> anyhow::Result<[u8; 32]> {
+    hex::decode(hex_value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("archive {field} is not 32 bytes of hex"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(label: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "vosx-agent-archive-test-{label}-{}-{}",
+                std::process::id(),
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_nanos(),
+            ));
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            if !std::thread::panicking() {
+                let _ = fs::remove_dir_all(&self.0);
+            }
+        }
+    }
+
+    const PROGRAM: &[u8] = b"\x7fELF-agent-program";
+
+    fn agent(consistency: u8) -> AgentRow {
+        AgentRow {
+            instance_name: "ledger".into(),
+            program_hash: BlobHash::of(PROGRAM).0,
+            program_name: "ledger".into(),
+            program_version: "1".into(),
+            replication_id: [4; 32],
+            consistency,
+            network_reachable: false,
+            sync_role: SyncFloor::Member,
+            install_args: vec![1, 2, 3],
+            install_payloads: Vec::new(),
+        }
+    }
+
+    fn entry() -> SpaceEntry {
+        SpaceEntry {
+            id: hex::encode([0x51; 32]),
+            name: "source".into(),
+            created_at: "2026-10-01T00:00:00Z".into(),
+            data_dir: String::new(),
+            registry_hash: String::new(),
+            bootnodes: Vec::new(),
+            hyperspace: String::new(),
+            pending_recipe: String::new(),
+        }
+    }
+
+    /// A source database with rows in every portable table plus a Raft
+    /// log row that must stay behind.
+    fn source_database(path: &Path) {
+        let db = redb::Database::create(path).unwrap();
+        let txn = db.begin_write().unwrap();
+        {
+            let mut state = txn.open_table(STATE_TABLE).unwrap();
+            state.insert("actor", b"state-blob".as_slice()).unwrap();
+            state.insert("crdt_roots", b"roots".as_slice()).unwrap();
+            let mut kv = txn.open_table(KV_TABLE).unwrap();
+            kv.insert(b"k1".as_slice(), b"v1".as_slice()).unwrap();
+            kv.insert(b"k2".as_slice(), b"".as_slice()).unwrap();
+            let mut dag = txn.open_table(DAG_TABLE).unwrap();
+            dag.insert(b"cid".as_slice(), b"node".as_slice()).unwrap();
+            let mut log = txn.open_table(vos::raft::log::RAFT_LOG).unwrap();
+            log.insert(1, b"entry".as_slice()).unwrap();
+        }
+        txn.commit().unwrap();
+    }
+
+    fn export(root: &Path, consistency: u8, signer: &libp2p::identity::Keypair) -> PathBuf {
+        let database = root.join("source.redb");
+        source_database(&database);
+        let archive = root.join("archive");
+        let (entry, agent) = (entry(), agent(consistency));
+        write_archive(
+            &archive,
+            &ArchiveContents {
+                entry: &entry,
+                agent: &agent,
+                crdt: true,
+                program: PROGRAM,
+                meta: b"meta",
+                database: &database,
+            },
+            signer,
+        )
+        .unwrap();
+        archive
+    }
+
+    #[test]
+    fn crdt_archive_roundtrips_portable_tables_only() {
+        let temp = TempDir::new("roundtrip");
+        let signer = libp2p::identity::Keypair::generate_ed25519();
+        let archive = export(&temp.0, 2, &signer);
+        let verified =
+            verify_archive(&archive, Some(&signer.public().to_peer_id().to_string())).unwrap();
+        assert_eq!(verified.exporter, signer.public().to_peer_id());
+        assert!(verified.has(DAG_FILE) && verified.has(META_FILE));
+
+        let seeded = temp.0.join("seeded.redb");
+        seed_database(&verified, &seeded).unwrap();
+        let db = redb::Database::open(&seeded).unwrap();
+        let txn = db.begin_read().unwrap();
+        let state = txn.open_table(STATE_TABLE).unwrap();
+        assert_eq!(state.get("actor").unwrap().unwrap().value(), b"state-blob");
+        let kv = txn.open_table(KV_TABLE).unwrap();
+        assert_eq!(kv.iter().unwrap().count(), 2);
+        let dag = txn.open_table(DAG_TABLE).unwrap();
+        assert_eq!(
+            dag.get(b"cid".as_slice()).unwrap().unwrap().value(),
+            b"node"
+        );
+        assert!(matches!(
+            txn.open_table(vos::raft::log::RAFT_LOG),
+            Err(redb::TableError::TableDoesNotExist(_))
+        ));
+    }
+
+    #[test]
+    fn raft_archive_leaves_history_behind() {
+        let temp = TempDir::new("raft");
+        let signer = libp2p::identity::Keypair::generate_ed25519();
+        let archive = export(&temp.0, 3, &signer);
+        let verified = verify_archive(&archive, None).unwrap();
+        assert!(!verified.has(DAG_FILE));
+        assert!(!archive.join(DAG_FILE).exists());
+    }
+
+    #[test]
+    fn tampering_or_a_foreign_signer_is_refused() {
+        let temp = TempDir::new("tamper");
+        let signer = libp2p::identity::Keypair::generate_ed25519();
+        let archive = export(&temp.0, 2, &signer);
+
+        let other = libp2p::identity::Keypair::generate_ed25519();
+        let error = verify_archive(&archive, Some(&other.public().to_peer_id().to_string()))
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("signed by"), "{error}");
+
+        // Rewriting a row file breaks its digest.
+        fs::write(archive.join(STORAGE_FILE), b"").unwrap();
+        assert!(verify_archive(&archive, None).is_err());
+
+        // Editing the manifest breaks the signature over it.
+        let resign = TempDir::new("edited");
+        let archive = export(&resign.0, 2, &signer);
+        let manifest = fs::read_to_string(archive.join(MANIFEST_FILE)).unwrap();
+        fs::write(
+            archive.join(MANIFEST_FILE),
+            manifest.replace("\"ledger\"", "\"forged\""),
+        )
+        .unwrap();
+        let error = verify_archive(&archive, None).err().unwrap();
+        assert!(error.to_string().contains("signature"), "{error}");
+
+        // A file the manifest does not list is refused.
+        let extra = TempDir::new("extra");
+        let archive = export(&extra.0, 2, &signer);
+        fs::write(archive.join("extra.bin"), b"x").unwrap();
+        assert!(verify_archive(&archive, None).is_err());
+    }
+}
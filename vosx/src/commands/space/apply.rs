@@ -11,6 +11,9 @@
 //!   program tag (if missing) and installed (if no instance exists).
 //!   The bytes reach the daemon through the shared content-addressed
 //!   blob cache — `publish` only ships `(name, version, hash)`.
+//! - **Nodes** → the registry roster: each `[[node]]` is enrolled
+//!   with its `role` (`voter` / `observer` / `witness`) unless an
+//!   identical row already exists.
 //! - **Node-local half** → `local.toml`: per-agent `tick_ms` /
 //!   `intra_caps` / `device_secret`, the space `cap_policy`, and
 //!   `[[extension]]` entries. These never touch the `AgentRow`; boot
@@ -23,12 +26,28 @@
 //! `--diff` prints the plan and exits without touching anything.
 //! `--upgrade` re-points installed agents whose blob differs (otherwise
 //! a differing blob is flagged, never silently overwritten).
+//!
+//! `--prune` makes the recipe exhaustive: installed agents it doesn't
+//! declare are uninstalled, and catalog programs that neither the recipe
+//! (an `[[agent]]`'s program or a `[[program]]` row) nor a surviving
+//! agent references are unpublished. Pruning runs only after every
+//! install and upgrade succeeded, and refuses a recipe with no agents or
+//! one that shares no agent with the space — the signature of applying
+//! the wrong recipe, where a prune would empty the space. Uninstalling
+//! retires the agent's replication id, so a pruned agent comes back only
+//! with a fresh one; `--diff --prune` shows the list first.
+//!
+//! `space reconcile [--watch]` ([`run_reconcile`]) repeats the apply:
+//! whenever the recipe (or an agent ELF it points at) changes, and every
+//! `--interval` seconds, reporting any drift it corrects — so a recipe
+//! kept in git can be a space's single source of truth.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
 use serde::Serialize;
-use vos::registry::{Status, SyncFloor};
+use vos::registry::{AgentRow, ProgramRow, Status, SyncFloor};
 
 use crate::blob_store;
 use crate::commands::space::client::DaemonClient;
@@ -53,9 +72,12 @@ pub struct Args {
     /// Re-point installed agents whose recipe blob differs from the
     /// catalog. Without it, a differing blob is flagged, not applied.
     pub upgrade: bool,
+    /// Uninstall agents and unpublish programs the recipe doesn't
+    /// declare.
+    pub prune: bool,
 }
 
-#[derive(Serialize, Default)]
+#[derive(Serialize, Default, PartialEq)]
 pub(crate) struct ApplyReport {
     /// `name:version` newly published to the catalog.
     published: Vec<String>,
@@ -71,6 +93,12 @@ pub(crate) struct ApplyReport {
     /// Changed implicit `name:recipe` instances that need an explicit
     /// immutable target such as `program = "name:v2"`.
     version_required: Vec<String>,
+    /// `[[node]]` rows newly enrolled (or re-roled), as `prefix role`.
+    enrolled: Vec<String>,
+    /// Instances uninstalled by `--prune`.
+    uninstalled: Vec<String>,
+    /// `name:version` unpublished by `--prune`.
+    unpublished: Vec<String>,
     /// Whether `local.toml` changed (or would change, under `--diff`).
     local_changed: bool,
     /// `--diff` dry run — nothing was written.
@@ -94,12 +122,157 @@ pub fn run(args: Args) -> anyhow::Result<()> {
             &data_dir,
             args.diff,
             args.upgrade,
+            args.prune,
         )?;
         emit(&client.entry.name, &report);
         Ok(())
     })
 }
 
+/// How often `reconcile --watch` checks the recipe and its ELFs for edits.
+const WATCH_POLL: Duration = Duration::from_secs(1);
+
+pub struct ReconcileArgs {
+    pub space: String,
+    pub recipe: PathBuf,
+    /// Keep running: re-apply on every edit and every `interval`.
+    pub watch: bool,
+    /// Seconds between drift checks under `watch`.
+    pub interval: u64,
+    pub upgrade: bool,
+    pub prune: bool,
+}
+
+/// Why a reconcile pass ran.
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum Trigger {
+    Start,
+    /// The recipe, or an agent ELF it points at, changed.
+    Edit,
+    /// The `--interval` timer: whatever this pass changes is drift.
+    Interval,
+}
+
+/// One `--format json` line per reported pass.
+#[derive(Serialize)]
+struct PassView<'a> {
+    trigger: Trigger,
+    /// The space had drifted from a recipe that didn't change.
+    drift: bool,
+    #[serde(flatten)]
+    report: &'a ApplyReport,
+}
+
+/// `space reconcile`: apply the recipe, then (with `watch`) again on
+/// every edit and every `interval`. A failed pass under `watch` is
+/// reported and retried — a half-saved recipe shouldn't end the loop.
+pub fn run_reconcile(args: ReconcileArgs) -> anyhow::Result<()> {
+    let recipe_path = std::fs::canonicalize(&args.recipe).unwrap_or_else(|_| args.recipe.clone());
+    let interval = Duration::from_secs(args.interval.max(1));
+    DaemonClient::with_connect(&args.space, |client| {
+        let data_dir = PathBuf::from(&client.entry.data_dir);
+        let mut trigger = Trigger::Start;
+        let mut stamp = watch_stamp(&recipe_path);
+        let mut last: Option<ApplyReport> = None;
+        loop {
+            match reconcile_pass(client, &recipe_path, &data_dir, &args) {
+                Ok(report) => {
+                    // A timer pass that changed nothing and flagged nothing
+                    // new stays quiet.
+                    let quiet = matches!(trigger, Trigger::Interval)
+                        && !report.changes()
+                        && last.as_ref() == Some(&report);
+                    if !quiet {
+                        emit_pass(&client.entry.name, trigger, &report);
+                    }
+                    last = Some(report);
+                }
+                Err(e) if args.watch => {
+                    eprintln!("reconcile {}: {e:#}", client.entry.name);
+                    last = None;
+                }
+                Err(e) => return Err(e),
+            }
+            if !args.watch {
+                return Ok(());
+            }
+            let due = Instant::now() + interval;
+            trigger = loop {
+                std::thread::sleep(WATCH_POLL);
+                if watch_stamp(&recipe_path) != stamp {
+                    // Let a multi-step save settle before reading it.
+                    std::thread::sleep(WATCH_POLL);
+                    stamp = watch_stamp(&recipe_path);
+                    break Trigger::Edit;
+                }
+                if Instant::now() >= due {
+                    break Trigger::Interval;
+                }
+            };
+        }
+    })
+}
+
+fn reconcile_pass(
+    client: &DaemonClient,
+    recipe_path: &Path,
+    data_dir: &Path,
+    args: &ReconcileArgs,
+) -> anyhow::Result<ApplyReport> {
+    let (recipe, recipe_dir) = reconcile::parse_recipe_file(recipe_path)?;
+    reconcile::validate_recipe_names(&recipe)?;
+    apply_recipe(
+        client,
+        &recipe,
+        &recipe_dir,
+        data_dir,
+        false,
+        args.upgrade,
+        args.prune,
+    )
+}
+
+/// Modification times of the recipe and every agent ELF it points at.
+/// A recipe that doesn't parse stamps as just itself.
+fn watch_stamp(recipe_path: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut files = vec![recipe_path.to_path_buf()];
+    if let Ok((recipe, dir)) = reconcile::parse_recipe_file(recipe_path) {
+        files.extend(
+            flatten(&recipe.agents)
+                .into_iter()
+                .filter(|a| !a.path.is_empty())
+                .map(|a| dir.join(&a.path)),
+        );
+    }
+    files
+        .into_iter()
+        .map(|file| {
+            let modified = std::fs::metadata(&file).and_then(|m| m.modified()).ok();
+            (file, modified)
+        })
+        .collect()
+}
+
+fn emit_pass(space: &str, trigger: Trigger, report: &ApplyReport) {
+    let drift = matches!(trigger, Trigger::Interval) && report.changes();
+    if output::is_json() {
+        output::print_json(&PassView {
+            trigger,
+            drift,
+            report,
+        });
+        return;
+    }
+    let why = match trigger {
+        Trigger::Edit => " (recipe changed)",
+        Trigger::Interval if drift => " (drift corrected)",
+        Trigger::Start | Trigger::Interval => "",
+    };
+    println!("reconcile {space}{why}");
+    print_plan(report);
+}
+
 /// Reconcile `recipe` against the daemon `client` is connected to.
 /// Returns the plan (or, under `diff`, what the plan *would* be). The
 /// caller owns the `DaemonClient`; genesis apply (`space new
@@ -112,6 +285,7 @@ pub(crate) fn apply_recipe(
     data_dir: &Path,
     diff: bool,
     upgrade: bool,
+    prune: bool,
 ) -> anyhow::Result<ApplyReport> {
     let space_id = client
         .entry
@@ -127,13 +301,6 @@ pub(crate) fn apply_recipe(
         name_ids.insert(a.name.clone(), instance_service_id(&a.name, prefix).0);
     }
 
-    // Node-local half → local.toml. Recipe fields overwrite the recipe-
-    // owned sections (cap_policy, per-agent policy, extensions) while
-    // node-owned fields (subscriptions, listen) are preserved.
-    let mut cfg = subscriptions::load(data_dir)?;
-    let next = project_node_local(&cfg, recipe, recipe_dir);
-    let local_changed = next != cfg;
-
     // Validate the entire plan before the first cache, catalog, instance,
     // or local-config write.
     let mut plans = Vec::new();
@@ -156,10 +323,56 @@ pub(crate) fn apply_recipe(
         }
         plans.push(plan);
     }
+    let pruning = if prune {
+        plan_prune(client, recipe, &plans)?
+    } else {
+        Prune::default()
+    };
+
+    // Node-local half → local.toml. Recipe fields overwrite the recipe-
+    // owned sections (cap_policy, per-agent policy, extensions) while
+    // node-owned fields (subscriptions, listen) are preserved; a pruned
+    // agent's policy goes with it.
+    let mut cfg = subscriptions::load(data_dir)?;
+    let mut next = project_node_local(&cfg, recipe, recipe_dir);
+    next.agents
+        .retain(|name, _| !pruning.uninstall.contains(name));
+    let local_changed = next != cfg;
+
+    // Node enrollment: resolve every `[[node]]` up front so a bad
+    // peer id or role fails before any write.
+    let members = if recipe.nodes.is_empty() {
+        Vec::new()
+    } else {
+        client.members()?
+    };
+    let mut enrollments = Vec::new();
+    for node in &recipe.nodes {
+        let (prefix, key, role) = node.resolve()?;
+        let enrolled = members.iter().any(|m| {
+            m.kind == vos::registry::MEMBER_KIND_NODE
+                && m.prefix == prefix
+                && m.key == key
+                && m.role == role
+        });
+        if !enrolled {
+            enrollments.push((prefix, key, role, node.role.clone()));
+        }
+    }
 
     let mut report = ApplyReport {
         local_changed,
         diff,
+        enrolled: enrollments
+            .iter()
+            .map(|(prefix, _, _, role)| format!("{prefix:#06x} {role}"))
+            .collect(),
+        uninstalled: pruning.uninstall.clone(),
+        unpublished: pruning
+            .unpublish
+            .iter()
+            .map(|(name, version)| format!("{name}:{version}"))
+            .collect(),
         ..Default::default()
     };
     for plan in &plans {
@@ -192,9 +405,16 @@ pub(crate) fn apply_recipe(
             debug_assert_eq!(cached.0, plan.hash);
         }
     }
+    for (prefix, key, role, name) in enrollments {
+        let status = client.add_node(prefix as u32, key, role)?;
+        if status != Status::Ok {
+            anyhow::bail!("add_node({prefix:#06x}, {name}) returned status {status}");
+        }
+    }
     for plan in &plans {
         execute_one(client, plan, &mut report)?;
     }
+    execute_prune(client, &pruning, &mut report)?;
     if local_changed {
         cfg = next;
         subscriptions::save(data_dir, &cfg)?;
@@ -509,6 +729,134 @@ fn execute_one(
     }
 }
 
+/// What `--prune` removes, decided before anything is written.
+#[derive(Default)]
+struct Prune {
+    /// Installed instances the recipe doesn't declare.
+    uninstall: Vec<String>,
+    /// Catalog `(name, version)`s nothing will reference afterwards.
+    unpublish: Vec<(String, String)>,
+}
+
+/// Plan `--prune` against the registry's current agents and catalog.
+fn plan_prune(
+    client: &DaemonClient,
+    recipe: &Recipe,
+    plans: &[PreparedAgent],
+) -> anyhow::Result<Prune> {
+    let upgrading: BTreeSet<&str> = plans
+        .iter()
+        .filter(|p| matches!(p.action, ApplyAction::Upgrade))
+        .map(|p| p.instance_name.as_str())
+        .collect();
+    prune_plan(recipe, &upgrading, &client.agents()?, &client.programs()?)
+}
+
+/// Programs are kept when the recipe names them (an agent's program or
+/// a `[[program]]` row) or an agent that stays installed still runs them
+/// — unless this apply upgrades that agent off them. The v2 role
+/// authority, which `space up` installs itself, is never pruned.
+fn prune_plan(
+    recipe: &Recipe,
+    upgrading: &BTreeSet<&str>,
+    installed: &[AgentRow],
+    catalog: &[ProgramRow],
+) -> anyhow::Result<Prune> {
+    let (system, installed): (Vec<&AgentRow>, Vec<&AgentRow>) = installed
+        .iter()
+        .partition(|a| a.instance_name == vos::v2::ROLE_AUTHORITY_INSTANCE_V2);
+    let declared: BTreeSet<&str> = flatten(&recipe.agents)
+        .into_iter()
+        .map(|a| a.name.as_str())
+        .collect();
+    if declared.is_empty() {
+        anyhow::bail!(
+            "refusing to --prune: the recipe declares no agents, so every agent in the space \
+             would be uninstalled"
+        );
+    }
+    if !installed.is_empty()
+        && !installed
+            .iter()
+            .any(|a| declared.contains(a.instance_name.as_str()))
+    {
+        anyhow::bail!(
+            "refusing to --prune: the recipe shares no agent with this space (is it the right \
+             recipe?). Uninstall explicitly with `vosx space uninstall` if that is the intent"
+        );
+    }
+
+    let mut keep: BTreeSet<(String, String)> = BTreeSet::new();
+    for agent in flatten(&recipe.agents) {
+        let (name, version, _) = program_ref(agent)?;
+        keep.insert((name, version));
+    }
+    keep.extend(
+        recipe
+            .programs
+            .iter()
+            .map(|p| (p.name.clone(), p.version.clone())),
+    );
+    keep.extend(
+        system
+            .iter()
+            .map(|a| (a.program_name.clone(), a.program_version.clone())),
+    );
+    let mut uninstall = Vec::new();
+    for agent in installed {
+        if !declared.contains(agent.instance_name.as_str()) {
+            uninstall.push(agent.instance_name.clone());
+        } else if !upgrading.contains(agent.instance_name.as_str()) {
+            keep.insert((agent.program_name.clone(), agent.program_version.clone()));
+        }
+    }
+    let unpublish = catalog
+        .iter()
+        .map(|p| (p.name.clone(), p.version.clone()))
+        .filter(|tag| !keep.contains(tag))
+        .collect();
+    Ok(Prune {
+        uninstall,
+        unpublish,
+    })
+}
+
+/// Uninstall, then unpublish. Rows that vanished meanwhile (another
+/// admin got there first) are dropped from the report; a program that
+/// gained an instance meanwhile is left published.
+fn execute_prune(
+    client: &DaemonClient,
+    prune: &Prune,
+    report: &mut ApplyReport,
+) -> anyhow::Result<()> {
+    for name in &prune.uninstall {
+        match client.uninstall(name.clone())? {
+            Status::Ok => {}
+            Status::NotFound => report.uninstalled.retain(|n| n != name),
+            Status::Forbidden => anyhow::bail!(
+                "uninstall '{name}' refused (Status::Forbidden) — the operator key is not an \
+                 admin of this space. `apply --prune` is an admin op."
+            ),
+            other => anyhow::bail!("uninstall '{name}' returned status {other}"),
+        }
+    }
+    for (name, version) in &prune.unpublish {
+        match client.unpublish(name.clone(), version.clone())? {
+            Status::Ok => {}
+            Status::NotFound | Status::InUse => {
+                let tag = format!("{name}:{version}");
+                report.unpublished.retain(|t| t != &tag);
+            }
+            Status::Forbidden => anyhow::bail!(
+                "unpublish '{name}:{version}' refused (Status::Forbidden) — the operator key is \
+                 not an admin of this space. `apply --prune` is an admin op."
+            ),
+            other => anyhow::bail!("unpublish '{name}:{version}' returned status {other}"),
+        }
+    }
+    Ok(())
+}
+
 /// The program `(name, version)` an agent installs from. Changed agents
 /// must name an explicit immutable `program = "name:version"`; the
 /// implicit `<instance>:recipe` tag is only for initial/idempotent apply.
@@ -582,7 +930,11 @@ pub(crate) fn project_node_local(
     }
     // agents: upsert each recipe agent that carries node-local policy.
     for a in flatten(&recipe.agents) {
-        if a.tick_ms.is_none() && a.intra_caps.is_empty() && !a.device_secret {
+        if a.tick_ms.is_none()
+            && a.intra_caps.is_empty()
+            && !a.device_secret
+            && a.fairness.is_none()
+        {
             continue; // no node-local policy — leave any base entry intact
         }
         out.agents.insert(
@@ -591,6 +943,7 @@ pub(crate) fn project_node_local(
                 tick_ms: a.tick_ms,
                 intra_caps: a.intra_caps.clone(),
                 device_secret: a.device_secret,
+                fairness: a.fairness.clone(),
             },
         );
     }
@@ -603,6 +956,7 @@ pub(crate) fn project_node_local(
             cap_policy: e.cap_policy.clone(),
             relay_unauthenticated: e.relay_unauthenticated,
             intra_caps: e.intra_caps.clone(),
+            dispatch_as: e.dispatch_as,
             tick_ms: e.tick_ms,
             init: e.init.clone(),
         };
@@ -643,7 +997,6 @@ fn emit(space: &str, report: &ApplyReport) {
         output::print_json(report);
         return;
     }
-    let verb = if report.diff { "would " } else { "" };
     println!(
         "apply {space}{}",
         if report.diff {
@@ -652,6 +1005,11 @@ fn emit(space: &str, report: &ApplyReport) {
             ""
         }
     );
+    print_plan(report);
+}
+
+fn print_plan(report: &ApplyReport) {
+    let verb = if report.diff { "would " } else { "" };
     for p in &report.published {
         println!("  {verb}publish {p}");
     }
@@ -670,20 +1028,38 @@ fn emit(space: &str, report: &ApplyReport) {
     for s in &report.skipped {
         println!("  skip {s} (already installed)");
     }
+    for n in &report.enrolled {
+        println!("  {verb}enroll node {n}");
+    }
+    for u in &report.uninstalled {
+        println!("  {verb}uninstall {u} (not in the recipe)");
+    }
+    for p in &report.unpublished {
+        println!("  {verb}unpublish {p} (not in the recipe)");
+    }
     if report.local_changed {
         println!("  {verb}update local.toml (node-local policy)");
     }
-    if report.published.is_empty()
-        && report.installed.is_empty()
-        && report.upgraded.is_empty()
-        && report.upgrade_pending.is_empty()
-        && report.version_required.is_empty()
-        && !report.local_changed
+    if !report.changes() && report.upgrade_pending.is_empty() && report.version_required.is_empty()
     {
         println!("  nothing to do — registry already matches the recipe");
     }
 }
 
+impl ApplyReport {
+    /// Whether the apply wrote (or, under `--diff`, would write)
+    /// anything. Flagged-only differences don't count.
+    fn changes(&self) -> bool {
+        !(self.published.is_empty()
+            && self.installed.is_empty()
+            && self.upgraded.is_empty()
+            && self.enrolled.is_empty()
+            && self.uninstalled.is_empty()
+            && self.unpublished.is_empty()
+            && !self.local_changed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -782,12 +1158,16 @@ mod tests {
                 tick_ms: Some(500),
                 intra_caps: vec!["space-registry:member".into()],
                 device_secret: true,
+                fairness: None,
             },
         );
         let base = LocalConfig {
             subscriptions: vec!["messenger".into()],
             listen: vec![],
+            relays: vec![],
+            relay_server: false,
             cap_policy: Some("block".into()),
+            metrics: None,
             agents: existing_agents,
             extensions: vec![ExtensionLocal {
                 name: "gateway".into(),
@@ -813,6 +1193,101 @@ mod tests {
         );
     }
 
+    fn agent_row(name: &str, program: &str) -> AgentRow {
+        let (program_name, program_version) = program.split_once(':').unwrap();
+        AgentRow {
+            instance_name: name.into(),
+            program_hash: [1; 32],
+            program_name: program_name.into(),
+            program_version: program_version.into(),
+            replication_id: [2; 32],
+            consistency: 2,
+            network_reachable: false,
+            sync_role: SyncFloor::Member,
+            install_args: Vec::new(),
+            install_payloads: Vec::new(),
+        }
+    }
+
+    fn program_row(tag: &str) -> ProgramRow {
+        let (name, version) = tag.split_once(':').unwrap();
+        ProgramRow {
+            name: name.into(),
+            version: version.into(),
+            hash: [1; 32],
+            crdt: false,
+        }
+    }
+
+    #[test]
+    fn prune_removes_what_the_recipe_and_survivors_dont_use() {
+        let recipe = recipe_from(
+            r#"
+            [[agent]]
+            name = "counter"
+            path = "counter.elf"
+            program = "counter:v2"
+            [[agent]]
+            name = "greeter"
+            path = "greeter.elf"
+            [[program]]
+            name = "archived"
+            version = "v1"
+        "#,
+        );
+        let installed = [
+            agent_row("counter", "counter:v1"),
+            agent_row("greeter", "greeter:old"),
+            agent_row("stale", "stale:recipe"),
+            agent_row(vos::v2::ROLE_AUTHORITY_INSTANCE_V2, "space-authority:abc"),
+        ];
+        let catalog = [
+            "archived:v1",
+            "counter:v1",
+            "counter:v2",
+            "greeter:old",
+            "scratch:v0",
+            "space-authority:abc",
+            "stale:recipe",
+        ]
+        .map(program_row);
+
+        // `counter` is being upgraded off v1; `greeter` still runs `old`.
+        let upgrading = BTreeSet::from(["counter"]);
+        let prune = prune_plan(&recipe, &upgrading, &installed, &catalog).unwrap();
+        assert_eq!(prune.uninstall, ["stale"]);
+        let unpublish: Vec<String> = prune
+            .unpublish
+            .iter()
+            .map(|(n, v)| format!("{n}:{v}"))
+            .collect();
+        assert_eq!(unpublish, ["counter:v1", "scratch:v0", "stale:recipe"]);
+
+        // Without the upgrade, v1 is still running and stays.
+        let prune = prune_plan(&recipe, &BTreeSet::new(), &installed, &catalog).unwrap();
+        assert!(!prune.unpublish.contains(&("counter".into(), "v1".into())));
+    }
+
+    #[test]
+    fn prune_refuses_an_empty_or_unrelated_recipe() {
+        let installed = [agent_row("counter", "counter:v1")];
+        let empty = recipe_from("space = \"x\"");
+        let err = prune_plan(&empty, &BTreeSet::new(), &installed, &[]).unwrap_err();
+        assert!(err.to_string().contains("declares no agents"), "{err}");
+
+        let unrelated = recipe_from(
+            r#"
+            [[agent]]
+            name = "other"
+            path = "other.elf"
+        "#,
+        );
+        let err = prune_plan(&unrelated, &BTreeSet::new(), &installed, &[]).unwrap_err();
+        assert!(err.to_string().contains("shares no agent"), "{err}");
+        // A fresh space (nothing installed yet) is fine.
+        assert!(prune_plan(&unrelated, &BTreeSet::new(), &[], &[]).is_ok());
+    }
+
     #[test]
     fn project_node_local_is_idempotent() {
         // Re-projecting an already-projected config is a fixed point, so
@@ -0,0 +1,262 @@
+//! `vosx space audit` — the registry's admin audit log.
+//!
+//! Every signed mutation the registry accepts (catalog, members, roles,
+//! invites) appends a hash-chained row: the PeerId that signed it, the
+//! handler, what it acted on, the exact bytes the signature covers, the
+//! signature itself and the consensus admission time. Each row commits to
+//! the previous row's hash, so editing, dropping or reordering one breaks
+//! every later link.
+//!
+//! `space audit` drains the whole chain and, before filtering anything,
+//! re-walks it from seq 0 with [`verify_audit_chain`] and re-checks every
+//! row's ed25519 signature over its signed op ([`audit_op_matches`] binds
+//! that op to the logged action). Any failure fails the command with the
+//! seq it broke at.
+//!
+//! Row times and signatures are the same on every replica. The chain order
+//! is not: the registry is a CRDT, so `seq` is acceptance order on the
+//! daemon's own replica and two replicas' heads differ. `--export-head`
+//! writes this replica's head as `<seq>:<hash>`; a later `--pin` with that
+//! value fails unless the chain still holds that exact row at that seq —
+//! compare a pin only against the same daemon it was exported from.
+//!
+//! ```text
+//! vosx space audit <space>                    # every row
+//! vosx space audit <space> --since 7d         # rows admitted in the last week
+//! vosx space audit <space> --actor me         # rows signed by this identity
+//! vosx space audit <space> --export-head head # record the verified head
+//! vosx space audit <space> --pin "$(cat head)"
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use vos::registry::{AuditRow, audit_op_matches, ed25519_pubkey_from_peer_id, verify_audit_chain};
+
+use crate::commands::space::client::DaemonClient;
+use crate::commands::space::logs::{format_unix_ms, unix_ms_now};
+use crate::commands::space::role::{peer_id_label, resolve_peer};
+use crate::output;
+
+pub struct Args {
+    pub space: String,
+    /// Only rows admitted within this duration (`30m`, `24h`, `7d`).
+    pub since: Option<String>,
+    pub actor: Option<String>,
+    /// `<seq>:<hash>` the chain must still hold.
+    pub pin: Option<String>,
+    /// Write the verified head as `<seq>:<hash>` to this file.
+    pub export_head: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct AuditView {
+    seq: u64,
+    at_ms: Option<u64>,
+    actor: String,
+    action: String,
+    target: String,
+    detail: String,
+    op: String,
+    signature: String,
+    hash: String,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let actor = args
+        .actor
+        .as_deref()
+        .map(resolve_peer)
+        .transpose()?
+        .map(|peer| peer.to_bytes());
+    let since_ms = args
+        .since
+        .as_deref()
+        .map(|since| {
+            let window_ms = crate::token::parse_duration(since)?.saturating_mul(1000);
+            anyhow::Ok(unix_ms_now().saturating_sub(window_ms))
+        })
+        .transpose()?;
+    let pin = args.pin.as_deref().map(parse_head).transpose()?;
+    let rows = DaemonClient::with_connect(&args.space, |client| client.audit())?;
+    if let Err(seq) = verify_audit_chain(&rows) {
+        bail!(
+            "audit log of space '{}' fails verification at seq {seq}: a row was edited, \
+             dropped or reordered",
+            args.space,
+        );
+    }
+    if let Some(row) = rows.iter().find(|row| !signature_verifies(row)) {
+        bail!(
+            "audit log of space '{}' fails verification at seq {}: the {} signature does \
+             not cover the logged op",
+            args.space,
+            row.seq,
+            row.action,
+        );
+    }
+    if let Some((seq, hash)) = pin
+        && rows.get(seq as usize).is_none_or(|row| row.hash != hash)
+    {
+        bail!(
+            "audit log of space '{}' does not hold the pinned head {}: the chain was \
+             rewritten below it, or the pin came from another replica",
+            args.space,
+            format_head(seq, &hash),
+        );
+    }
+    let head = rows.last().map(|row| format_head(row.seq, &row.hash));
+    if let Some(path) = &args.export_head {
+        let Some(head) = &head else {
+            bail!(
+                "audit log of space '{}' is empty: no head to export",
+                args.space
+            );
+        };
+        std::fs::write(path, format!("{head}\n"))
+            .with_context(|| format!("write {}", path.display()))?;
+    }
+    let shown: Vec<&AuditRow> = rows
+        .iter()
+        .filter(|row| since_ms.is_none_or(|since| row.at_ms.is_some_and(|at| at >= since)))
+        .filter(|row| actor.as_ref().is_none_or(|actor| &row.actor == actor))
+        .collect();
+
+    if output::is_json() {
+        let view: Vec<AuditView> = shown
+            .iter()
+            .map(|row| AuditView {
+                seq: row.seq,
+                at_ms: row.at_ms,
+                actor: peer_id_label(&row.actor),
+                action: row.action.clone(),
+                target: target_label(&row.action, &row.target),
+                detail: row.detail.clone(),
+                op: hex::encode(&row.op),
+                signature: hex::encode(&row.signature),
+                hash: hex::encode(row.hash),
+            })
+            .collect();
+        output::print_json(&view);
+        return Ok(());
+    }
+    if shown.is_empty() {
+        println!("no matching audit rows");
+    } else {
+        println!(
+            "{:<6}  {:<24}  {:<24}  {:<52}  {:<52}  DETAIL",
+            "SEQ", "ADMITTED", "ACTION", "ACTOR", "TARGET"
+        );
+        for row in &shown {
+            println!(
+                "{:<6}  {:<24}  {:<24}  {:<52}  {:<52}  {}",
+                row.seq,
+                row.at_ms.map_or_else(|| "-".into(), format_unix_ms),
+                row.action,
+                peer_id_label(&row.actor),
+                target_label(&row.action, &row.target),
+                row.detail,
+            );
+        }
+    }
+    match head {
+        Some(head) => println!(
+            "chain and signatures verified: {} row(s), head {head} (this replica's order)",
+            rows.len(),
+        ),
+        None => println!("chain verified: empty"),
+    }
+    Ok(())
+}
+
+/// The row's signature verifies under its actor's key over its `op`, and
+/// that op is the one its action requires.
+fn signature_verifies(row: &AuditRow) -> bool {
+    audit_op_matches(row)
+        && ed25519_pubkey_from_peer_id(&row.actor)
+            .and_then(|key| libp2p::identity::ed25519::PublicKey::try_from_bytes(&key).ok())
+            .is_some_and(|key| key.verify(&row.op, &row.signature))
+}
+
+fn format_head(seq: u64, hash: &[u8; 32]) -> String {
+    format!("{seq}:{}", hex::encode(hash))
+}
+
+/// Parse a `<seq>:<hash>` head as `--export-head` writes it.
+fn parse_head(head: &str) -> Result<(u64, [u8; 32])> {
+    let parsed = head.trim().split_once(':').and_then(|(seq, hash)| {
+        let seq = seq.parse().ok()?;
+        let hash = hex::decode(hash).ok()?.try_into().ok()?;
+        Some((seq, hash))
+    });
+    parsed.with_context(|| format!("bad audit head '{head}': expected <seq>:<64 hex chars>"))
+}
+
+/// Catalog ops target a name; member, role and invite ops target a
+/// PeerId, a public key or a hash.
+fn target_label(action: &str, target: &[u8]) -> String {
+    match action {
+        "publish"
+        | "unpublish"
+        | "install"
+        | "uninstall"
+        | "upgrade"
+        | "record_provenance"
+        | "register_extension_meta" => String::from_utf8_lossy(target).into_owned(),
+        _ => peer_id_label(target),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_render_as_names_or_peers() {
+        assert_eq!(target_label("install", b"counter"), "counter");
+        let peer = libp2p::PeerId::random();
+        assert_eq!(
+            target_label("grant_role", &peer.to_bytes()),
+            peer.to_string()
+        );
+        assert_eq!(target_label("revoke_invite", &[0xab; 4]), "abababab");
+    }
+
+    #[test]
+    fn heads_round_trip_and_reject_malformed_pins() {
+        let head = format_head(12, &[0xcd; 32]);
+        assert_eq!(parse_head(&format!("{head}\n")).unwrap(), (12, [0xcd; 32]));
+        assert!(parse_head("12").is_err());
+        assert!(parse_head("x:cdcd").is_err());
+        assert!(parse_head("12:cdcd").is_err(), "short hash");
+    }
+
+    #[test]
+    fn row_signatures_verify_over_the_logged_op() {
+        let key = libp2p::identity::Keypair::generate_ed25519();
+        let actor = key.public().to_peer_id().to_bytes();
+        let op = vos::registry::canonical_op_bytes("revoke_invite", &[&[7; 32]]);
+        let row = AuditRow::new(
+            Some(1_700_000_000_000),
+            actor,
+            "revoke_invite".into(),
+            vec![7; 32],
+            String::new(),
+            op.clone(),
+            key.sign(&op).unwrap(),
+        )
+        .chained_after(None);
+        assert!(signature_verifies(&row));
+
+        let mut forged = row.clone();
+        forged.op = vos::registry::canonical_op_bytes("revoke_invite", &[&[8; 32]]);
+        assert!(!signature_verifies(&forged), "signature over other bytes");
+        let mut relabeled = row;
+        relabeled.action = "uninstall".into();
+        assert!(
+            !signature_verifies(&relabeled),
+            "op signed for another action"
+        );
+    }
+}
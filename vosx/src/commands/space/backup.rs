@@ -6,6 +6,17 @@
 //! stores, node identity and local policy) and `blobs/` (the content-addressed
 //! program cache). Restore verifies the complete archive before touching the
 //! destination and renames replaced state aside instead of deleting it.
+//!
+//! An incremental archive names a parent archive by path and manifest digest
+//! and records unchanged rows as `inherited` instead of copying them; restore
+//! walks and verifies the whole chain. An encrypted archive replaces
+//! `manifest.json` with `encryption.json` plus `manifest.sealed` and seals
+//! every stored file (see `backup_seal`).
+//!
+//! `--online` archives a running space: the daemon snapshots its data
+//! directory under `vos::write_barrier` (the reserved `__snapshot` op) into a
+//! private directory beside it, and the archive is built from that copy, so
+//! online and offline archives are identical in format.
 
 use std::collections::BTreeSet;
 use std::fs;
@@ -15,14 +26,21 @@ use std::path::{Component, Path, PathBuf};
 use serde::{Deserialize, Serialize};
 
 use crate::blob_store::{self, BlobHash};
+use crate::commands::space::backup_seal::{self, ArchiveKey, EncryptionHeader, Sealing, Unlock};
+use crate::commands::space::client::DaemonClient;
 use crate::commands::space::{endpoint, space_lock::SpaceDataLock};
 use crate::spaces_index::{self, SpaceEntry};
 
 const MANIFEST_FILE: &str = "manifest.json";
+const SEALED_MANIFEST_FILE: &str = "manifest.sealed";
+const ENCRYPTION_FILE: &str = "encryption.json";
 const ARCHIVE_FORMAT: &str = "VOSB1";
-const ARCHIVE_VERSION: u32 = 1;
+/// Version 2 adds `parent` and `inherited`; version 1 archives still restore.
+const ARCHIVE_VERSION: u32 = 2;
 const MAX_MANIFEST_BYTES: u64 = 16 * 1024 * 1024;
+const MAX_ENCRYPTION_HEADER_BYTES: u64 = 1024 * 1024;
 const MAX_ARCHIVE_FILES: usize = 100_000;
+const MAX_BACKUP_CHAIN: usize = 32;
 const NODE_KEY_WIRE: &str = "data/node.key";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,9 +48,30 @@ struct BackupManifest {
     format: String,
     version: u32,
     space: SpaceEntry,
+    /// Earlier archive this one is incremental against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent: Option<BackupParent>,
     files: Vec<BackupFile>,
 }
 
+impl BackupManifest {
+    /// Rows are verified strictly sorted before any lookup.
+    fn file(&self, path: &str) -> Option<&BackupFile> {
+        self.files
+            .binary_search_by(|file| file.path.as_str().cmp(path))
+            .ok()
+            .map(|index| &self.files[index])
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupParent {
+    /// Absolute path of the parent archive when this one was created.
+    path: String,
+    /// BLAKE2b-256 of the parent's stored manifest file.
+    manifest_blake2b_256: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct BackupFile {
     /// Slash-separated path rooted at `data/` or `blobs/`.
@@ -41,19 +80,112 @@ struct BackupFile {
     blake2b_256: String,
     /// Unix permission bits. `None` on non-Unix producers.
     mode: Option<u32>,
+    /// The bytes are unchanged from the parent archive's row for the same
+    /// path and are stored there (or further up the chain), not here.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    inherited: bool,
+}
+
+/// One verified archive of a chain, with its key when it is encrypted.
+struct OpenedArchive {
+    root: PathBuf,
+    manifest: BackupManifest,
+    /// BLAKE2b-256 of `manifest.json` or `manifest.sealed` as stored.
+    stored_manifest: [u8; 32],
+    key: Option<ArchiveKey>,
+}
+
+/// A verified archive followed by every ancestor it inherits from.
+struct BackupChain {
+    archives: Vec<OpenedArchive>,
+}
+
+impl BackupChain {
+    fn manifest(&self) -> &BackupManifest {
+        &self.archives[0].manifest
+    }
+
+    /// Resolve a head row to the archive that physically holds its bytes.
+    fn stored(&self, file: &BackupFile) -> anyhow::Result<StoredFile<'_>> {
+        for archive in &self.archives {
+            let Some(row) = archive.manifest.file(&file.path) else {
+                break;
+            };
+            if !row.inherited {
+                return Ok(StoredFile {
+                    path: wire_to_path(&archive.root, &row.path)?,
+                    key: archive.key.as_ref(),
+                });
+            }
+        }
+        anyhow::bail!("backup chain does not hold {}", file.path)
+    }
 }
 
-struct PartialDirectory {
+/// Where one manifest row's bytes live, plaintext or sealed.
+struct StoredFile<'a> {
+    path: PathBuf,
+    key: Option<&'a ArchiveKey>,
+}
+
+impl StoredFile<'_> {
+    /// Plaintext length and BLAKE2b-256, authenticating sealed files.
+    fn digest(&self, wire: &str) -> anyhow::Result<(u64, [u8; 32])> {
+        match self.key {
+            Some(key) => key.open_file(wire, &self.path, &mut std::io::sink()),
+            None => Ok((fs::metadata(&self.path)?.len(), hash_file(&self.path)?)),
+        }
+    }
+
+    fn extract(&self, wire: &str, destination: &Path) -> anyhow::Result<()> {
+        match self.key {
+            Some(key) => {
+                let mut output = fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(destination)
+                    .map_err(|error| {
+                        anyhow::anyhow!("create {}: {error}", destination.display())
+                    })?;
+                key.open_file(wire, &self.path, &mut output)?;
+            }
+            None => {
+                fs::copy(&self.path, destination).map_err(|error| {
+                    anyhow::anyhow!(
+                        "copy {} -> {}: {error}",
+                        self.path.display(),
+                        destination.display(),
+                    )
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How `create_archive` builds a new archive.
+#[derive(Default)]
+struct ArchiveOptions<'a> {
+    parent: Option<&'a Path>,
+    /// Point-in-time copy of the data directory taken by the live daemon;
+    /// archived in place of `entry.data_dir`.
+    snapshot: Option<&'a Path>,
+    sealing: Option<Sealing>,
+    /// Opens an encrypted parent chain.
+    unlock: Unlock,
+}
+
+pub(super) struct PartialDirectory {
     path: PathBuf,
     armed: bool,
 }
 
 impl PartialDirectory {
-    fn new(path: PathBuf) -> Self {
+    pub(super) fn new(path: PathBuf) -> Self {
         Self { path, armed: true }
     }
 
-    fn disarm(&mut self) {
+    pub(super) fn disarm(&mut self) {
         self.armed = false;
     }
 }
@@ -66,7 +198,29 @@ impl Drop for PartialDirectory {
     }
 }
 
-pub fn run_backup(query: &str, output: &Path) -> anyhow::Result<()> {
+pub fn run_backup(
+    query: &str,
+    output: &Path,
+    online: bool,
+    parent: Option<&Path>,
+    recipients: &[String],
+    passphrase_env: Option<&str>,
+    identity: Option<&Path>,
+) -> anyhow::Result<()> {
+    let unlock = Unlock::from_args(identity, passphrase_env)?;
+    let sealing = if !recipients.is_empty() {
+        Some(Sealing::Recipients(
+            recipients
+                .iter()
+                .map(|recipient| backup_seal::parse_recipient(recipient))
+                .collect::<anyhow::Result<_>>()?,
+        ))
+    } else {
+        unlock.passphrase.clone().map(Sealing::Passphrase)
+    };
+    if online {
+        return run_online_backup(query, output, parent, sealing, unlock);
+    }
     let index = spaces_index::load()?;
     let initial = spaces_index::find(&index, query)?;
     let space_id = initial
@@ -83,14 +237,272 @@ pub fn run_backup(query: &str, output: &Path) -> anyhow::Result<()> {
     }
     let data_dir = PathBuf::from(&entry.data_dir);
     refuse_live_daemon(&data_dir)?;
-    let digest = create_archive(&entry, output, &blob_store::cache_dir())?;
+    let encrypted = sealing.is_some();
+    let digest = create_archive(
+        &entry,
+        output,
+        &blob_store::cache_dir(),
+        &ArchiveOptions {
+            parent,
+            sealing,
+            unlock,
+            ..ArchiveOptions::default()
+        },
+    )?;
+    print_backup_summary(&entry, output, &digest, parent.is_some(), encrypted, false);
+    Ok(())
+}
+
+/// Back up a space whose daemon is running. The daemon snapshots its data
+/// directory into a private staging directory beside it, holding its
+/// writers at the write barrier only while it links and clones the file
+/// set; the archive is then built from that copy exactly as an offline
+/// one, and the copy is removed. The program cache needs no barrier: blobs
+/// are content-addressed and only ever appear by atomic rename.
+fn run_online_backup(
+    query: &str,
+    output: &Path,
+    parent: Option<&Path>,
+    sealing: Option<Sealing>,
+    unlock: Unlock,
+) -> anyhow::Result<()> {
+    let index = spaces_index::load()?;
+    let entry = spaces_index::find(&index, query)?.clone();
+    let data_dir = PathBuf::from(&entry.data_dir);
+    if !endpoint::read(&data_dir)?.is_some_and(|endpoint| endpoint::is_alive(&endpoint)) {
+        anyhow::bail!(
+            "space '{}' has no running daemon; drop --online to back up its stopped data directory",
+            entry.name,
+        );
+    }
+    if path_entry_exists(output)? {
+        anyhow::bail!("backup destination already exists: {}", output.display());
+    }
+    reject_nested_output(output, &data_dir, &blob_store::cache_dir())?;
+    let snapshot = DaemonClient::with_connect(query, |client| {
+        take_online_snapshot(client, "backup-snapshot")
+    })?;
+    let encrypted = sealing.is_some();
+    let digest = create_archive(
+        &entry,
+        output,
+        &blob_store::cache_dir(),
+        &ArchiveOptions {
+            parent,
+            snapshot: Some(snapshot.path()),
+            sealing,
+            unlock,
+        },
+    )?;
+    print_backup_summary(&entry, output, &digest, parent.is_some(), encrypted, true);
+    Ok(())
+}
+
+/// A point-in-time copy of a running space's data directory, removed on
+/// drop.
+pub(super) struct OnlineSnapshot {
+    directory: PartialDirectory,
+}
+
+impl OnlineSnapshot {
+    pub(super) fn path(&self) -> &Path {
+        &self.directory.path
+    }
+}
+
+/// Have the connected daemon snapshot its data directory into a fresh
+/// 0700 hidden sibling of it. The copy is plaintext even when the archive
+/// is sealed, so it stays beside the live state it duplicates — never
+/// beside the archive, which may sit on other media — and on the same
+/// filesystem, where the daemon can link and clone into it.
+pub(super) fn take_online_snapshot(
+    client: &DaemonClient,
+    label: &str,
+) -> anyhow::Result<OnlineSnapshot> {
+    let data_dir = fs::canonicalize(&client.entry.data_dir)
+        .map_err(|error| anyhow::anyhow!("resolve {}: {error}", client.entry.data_dir))?;
+    let path = temporary_sibling(&data_dir, label)?;
+    // Removed on every path, including a daemon that failed half-way.
+    let snapshot = OnlineSnapshot {
+        directory: PartialDirectory::new(path.clone()),
+    };
+    let report = client.snapshot(&path)?;
+    let report: SnapshotReport = serde_json::from_str(&report)
+        .map_err(|error| anyhow::anyhow!("daemon sent a malformed snapshot report: {error}"))?;
+    if let Some(error) = report.error {
+        anyhow::bail!("daemon could not snapshot '{}': {error}", client.entry.name);
+    }
+    tracing::info!(
+        files = report.files,
+        bytes = report.bytes,
+        paused_ms = report.paused_ms,
+        linked = report.linked,
+        cloned = report.cloned,
+        "online snapshot taken"
+    );
+    Ok(snapshot)
+}
+
+fn print_backup_summary(
+    entry: &SpaceEntry,
+    output: &Path,
+    digest: &str,
+    incremental: bool,
+    encrypted: bool,
+    online: bool,
+) {
     println!(
-        "backed up '{}' to {} (manifest {})",
+        "backed up '{}' to {} (manifest {}{}{}{})",
         entry.name,
         output.display(),
         digest,
+        if incremental { ", incremental" } else { "" },
+        if encrypted { ", encrypted" } else { "" },
+        if online { ", online" } else { "" },
     );
-    Ok(())
+}
+
+/// The daemon's answer to `__snapshot`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SnapshotReport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(default)]
+    files: u64,
+    #[serde(default)]
+    bytes: u64,
+    /// How long durable writes were held at the barrier.
+    #[serde(default)]
+    paused_ms: u64,
+    /// Files hard-linked under the barrier and copied after it.
+    #[serde(default)]
+    linked: u64,
+    /// Files cloned copy-on-write under the barrier.
+    #[serde(default)]
+    cloned: u64,
+}
+
+/// Daemon side of `space backup --online`, installed with
+/// `VosNode::set_snapshot_handler`. Copies `data_dir` (minus `.endpoint`)
+/// into `output`, a new 0700 directory at an absolute path outside it. The
+/// file set is taken while [`vos::write_barrier::quiesce`] holds every
+/// durable write (see [`copy_quiesced`]), so each redb database, v2 image
+/// and side-store file is the same committed instant. Copied databases run
+/// redb's crash recovery on first open, exactly as after a power loss.
+/// Returns the JSON [`SnapshotReport`].
+pub(super) fn serve_snapshot(data_dir: &Path, output: &str) -> String {
+    let report =
+        snapshot_data_dir(data_dir, Path::new(output)).unwrap_or_else(|error| SnapshotReport {
+            error: Some(error.to_string()),
+            ..SnapshotReport::default()
+        });
+    serde_json::to_string(&report).unwrap_or_else(|_| "{}".into())
+}
+
+fn snapshot_data_dir(data_dir: &Path, output: &Path) -> anyhow::Result<SnapshotReport> {
+    if !output.is_absolute() {
+        anyhow::bail!("snapshot directory must be an absolute path");
+    }
+    let data = fs::canonicalize(data_dir)
+        .map_err(|error| anyhow::anyhow!("resolve {}: {error}", data_dir.display()))?;
+    let parent = fs::canonicalize(usable_parent(output))
+        .map_err(|error| anyhow::anyhow!("resolve {}: {error}", usable_parent(output).display()))?;
+    if parent.starts_with(&data) {
+        anyhow::bail!("snapshot directory must be outside the space data directory");
+    }
+    // Creation refuses an existing entry, so nothing is overwritten.
+    create_private_directory(output)?;
+    let mut partial = PartialDirectory::new(output.to_path_buf());
+    let report = copy_quiesced(&data, output)?;
+    partial.disarm();
+    Ok(report)
+}
+
+/// Take the snapshot's file set under the barrier and fill it in after.
+///
+/// While writers are held, each file that is only ever replaced by rename
+/// (see [`replaced_by_rename`]) is hard-linked, which pins its committed
+/// inode, and every other file — redb rewrites its pages in place — is
+/// cloned where the filesystem can, else copied. Once writers resume, each
+/// link is swapped for a private copy, so the snapshot shares no inode with
+/// live state and the pause covers only links, clones and the in-place
+/// files a clone could not cover.
+fn copy_quiesced(data: &Path, output: &Path) -> anyhow::Result<SnapshotReport> {
+    let mut report = SnapshotReport::default();
+    let mut linked = Vec::new();
+    let quiesced = vos::write_barrier::quiesce();
+    let started = std::time::Instant::now();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        let directory = data.join(&relative);
+        for entry in fs::read_dir(&directory)
+            .map_err(|error| anyhow::anyhow!("read {}: {error}", directory.display()))?
+        {
+            let entry =
+                entry.map_err(|error| anyhow::anyhow!("read {}: {error}", directory.display()))?;
+            let child_relative = relative.join(entry.file_name());
+            if child_relative == Path::new(".endpoint") {
+                continue;
+            }
+            let metadata = fs::symlink_metadata(entry.path())
+                .map_err(|error| anyhow::anyhow!("inspect {}: {error}", entry.path().display()))?;
+            let target = output.join(&child_relative);
+            if metadata.is_dir() {
+                fs::create_dir(&target)
+                    .map_err(|error| anyhow::anyhow!("create {}: {error}", target.display()))?;
+                pending.push(child_relative);
+            } else if metadata.is_file() {
+                let source = entry.path();
+                if replaced_by_rename(&child_relative) && fs::hard_link(&source, &target).is_ok() {
+                    linked.push(target);
+                } else if try_reflink(&source, &target)? {
+                    report.cloned += 1;
+                } else {
+                    fs::copy(&source, &target)
+                        .map_err(|error| anyhow::anyhow!("copy {}: {error}", source.display()))?;
+                }
+                report.bytes += metadata.len();
+                report.files += 1;
+            } else {
+                // Archive creation names and refuses the same entries.
+                anyhow::bail!(
+                    "snapshot refuses non-regular file {}",
+                    entry.path().display()
+                );
+            }
+        }
+    }
+    report.paused_ms = started.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
+    drop(quiesced);
+    report.linked = linked.len() as u64;
+    for target in linked {
+        let copy = temporary_sibling(&target, "unlinked")?;
+        fs::copy(&target, &copy)
+            .and_then(|_| fs::rename(&copy, &target))
+            .map_err(|error| anyhow::anyhow!("copy {}: {error}", target.display()))?;
+    }
+    Ok(report)
+}
+
+/// Whether the daemon only ever replaces `relative` (a path under the data
+/// directory) by renaming a finished file over it, never writing it in
+/// place: committed v2 images and their proof, record and private-input
+/// side stores (`vos::v2::local_store`). Their `.v2-next` temporaries are
+/// not.
+fn replaced_by_rename(relative: &Path) -> bool {
+    let name = |path: Option<&Path>| {
+        path.and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let file = name(Some(relative));
+    let directory = name(relative.parent());
+    !file.ends_with(".v2-next")
+        && (file.ends_with(".v2")
+            || [".proofs", ".records", ".private-inputs"]
+                .iter()
+                .any(|side| directory.ends_with(side)))
 }
 
 pub fn run_restore(
@@ -98,12 +510,16 @@ pub fn run_restore(
     data_dir: Option<&Path>,
     replace: bool,
     name: Option<&str>,
+    identity: Option<&Path>,
+    passphrase_env: Option<&str>,
 ) -> anyhow::Result<()> {
+    let unlock = Unlock::from_args(identity, passphrase_env)?;
     // Verify once before selecting a lock/destination. `restore_archive`
     // verifies again while copying, so corruption between these phases still
     // fails before activation.
-    let manifest = verify_archive(archive)?;
-    let expected_manifest = hash_file(&archive.join(MANIFEST_FILE))?;
+    let chain = verify_archive(archive, &unlock)?;
+    let expected_manifest = chain.archives[0].stored_manifest;
+    let manifest = chain.manifest();
     let space_id = manifest
         .space
         .id_bytes()
@@ -116,6 +532,7 @@ pub fn run_restore(
     refuse_live_daemon(&destination)?;
     let replaced = restore_archive(
         archive,
+        &unlock,
         &destination,
         &blob_store::cache_dir(),
         &crate::paths::spaces_index_path(),
@@ -124,9 +541,13 @@ pub fn run_restore(
         name,
     )?;
     println!(
-        "restored '{}' to {}",
+        "restored '{}' to {}{}",
         name.unwrap_or(&manifest.space.name),
         destination.display(),
+        match chain.archives.len() {
+            1 => String::new(),
+            archives => format!(" from a chain of {archives} archives"),
+        },
     );
     if let Some(replaced) = replaced {
         println!("previous state retained at {}", replaced.display());
@@ -134,19 +555,43 @@ pub fn run_restore(
     Ok(())
 }
 
+/// Create an X25519 identity for `space backup --recipient`.
+pub fn run_keygen(output: &Path) -> anyhow::Result<()> {
+    if path_entry_exists(output)? {
+        anyhow::bail!("backup identity already exists: {}", output.display());
+    }
+    let (secret, recipient) = backup_seal::generate_identity()?;
+    crate::secure_file::write_owner_only_atomic(
+        output,
+        format!(
+            "# vosx space backup identity\n# recipient: {}\n{}\n",
+            hex::encode(recipient),
+            hex::encode(secret),
+        )
+        .as_bytes(),
+    )?;
+    println!("{}", hex::encode(recipient));
+    Ok(())
+}
+
 fn refuse_live_daemon(data_dir: &Path) -> anyhow::Result<()> {
     if let Some(endpoint) = endpoint::read(data_dir)?
         && endpoint::is_alive(&endpoint)
     {
         anyhow::bail!(
-            "space daemon pid {} is still running; stop it before backup or restore",
+            "space daemon pid {} is still running; stop it first (`space backup --online` does not need to)",
             endpoint.pid,
         );
     }
     Ok(())
 }
 
-fn create_archive(entry: &SpaceEntry, output: &Path, cache_dir: &Path) -> anyhow::Result<String> {
+fn create_archive(
+    entry: &SpaceEntry,
+    output: &Path,
+    cache_dir: &Path,
+    options: &ArchiveOptions,
+) -> anyhow::Result<String> {
     if path_entry_exists(output)? {
         anyhow::bail!("backup destination already exists: {}", output.display());
     }
@@ -170,18 +615,73 @@ fn create_archive(entry: &SpaceEntry, output: &Path, cache_dir: &Path) -> anyhow
         );
     }
     reject_nested_output(output, &data_dir, cache_dir)?;
-    let parent = usable_parent(output);
-    fs::create_dir_all(parent)
-        .map_err(|error| anyhow::anyhow!("create {}: {error}", parent.display()))?;
+    let parent_chain = options
+        .parent
+        .map(|parent| verify_archive(parent, &options.unlock))
+        .transpose()?;
+    let parent = match (&parent_chain, options.parent) {
+        (Some(chain), Some(path)) => {
+            if chain.manifest().space.id != entry.id {
+                anyhow::bail!(
+                    "parent backup {} is of space {}, not {}",
+                    path.display(),
+                    chain.manifest().space.id,
+                    entry.id,
+                );
+            }
+            // Inherited rows stay wherever the chain stored them, so an
+            // encrypted increment must not point at plaintext copies.
+            if options.sealing.is_some()
+                && chain.archives.iter().any(|archive| archive.key.is_none())
+            {
+                anyhow::bail!(
+                    "an encrypted backup cannot inherit from the unencrypted chain at {}",
+                    path.display(),
+                );
+            }
+            let path = fs::canonicalize(path)
+                .map_err(|error| anyhow::anyhow!("resolve {}: {error}", path.display()))?;
+            Some(BackupParent {
+                path: path
+                    .to_str()
+                    .ok_or_else(|| anyhow::anyhow!("parent backup path must be valid UTF-8"))?
+                    .into(),
+                manifest_blake2b_256: hex::encode(chain.archives[0].stored_manifest),
+            })
+        }
+        _ => None,
+    };
+    let key = options
+        .sealing
+        .as_ref()
+        .map(|_| ArchiveKey::generate())
+        .transpose()?;
+    let parent_dir = usable_parent(output);
+    fs::create_dir_all(parent_dir)
+        .map_err(|error| anyhow::anyhow!("create {}: {error}", parent_dir.display()))?;
     let stage = temporary_sibling(output, "backup-partial")?;
     fs::create_dir(&stage)
         .map_err(|error| anyhow::anyhow!("create {}: {error}", stage.display()))?;
     set_directory_private(&stage)?;
     let mut partial = PartialDirectory::new(stage.clone());
-
-    let mut files = Vec::new();
-    copy_tree_into_archive(&data_dir, &stage, "data", true, &mut files)?;
-    copy_cache_into_archive(cache_dir, &stage, &mut files)?;
+    // A fully inherited root is still present so the layout stays fixed.
+    fs::create_dir(stage.join("data"))?;
+    fs::create_dir(stage.join("blobs"))?;
+
+    let mut writer = ArchiveWriter {
+        root: &stage,
+        key: key.as_ref(),
+        parent: parent_chain.as_ref().map(BackupChain::manifest),
+        files: Vec::new(),
+    };
+    copy_tree_into_archive(
+        options.snapshot.unwrap_or(&data_dir),
+        "data",
+        true,
+        &mut writer,
+    )?;
+    copy_cache_into_archive(cache_dir, &mut writer)?;
+    let mut files = writer.files;
     files.sort_by(|left, right| left.path.cmp(&right.path));
     let registry_path = format!("blobs/{}", entry.registry_hash);
     if !files.iter().any(|file| file.path == registry_path) {
@@ -195,15 +695,35 @@ fn create_archive(entry: &SpaceEntry, output: &Path, cache_dir: &Path) -> anyhow
         format: ARCHIVE_FORMAT.into(),
         version: ARCHIVE_VERSION,
         space: entry.clone(),
+        parent,
         files,
     };
     let bytes = serde_json::to_vec_pretty(&manifest)
         .map_err(|error| anyhow::anyhow!("encode backup manifest: {error}"))?;
-    fs::write(stage.join(MANIFEST_FILE), &bytes)
-        .map_err(|error| anyhow::anyhow!("write backup manifest: {error}"))?;
-    fs::File::open(stage.join(MANIFEST_FILE))?.sync_all()?;
+    match (&key, &options.sealing) {
+        (Some(key), Some(sealing)) => {
+            let header = serde_json::to_vec_pretty(&key.header(sealing)?)
+                .map_err(|error| anyhow::anyhow!("encode backup encryption header: {error}"))?;
+            fs::write(stage.join(ENCRYPTION_FILE), header)
+                .map_err(|error| anyhow::anyhow!("write backup encryption header: {error}"))?;
+            fs::File::open(stage.join(ENCRYPTION_FILE))?.sync_all()?;
+            fs::write(
+                stage.join(SEALED_MANIFEST_FILE),
+                key.seal_bytes(MANIFEST_FILE, &bytes)?,
+            )
+            .map_err(|error| anyhow::anyhow!("write backup manifest: {error}"))?;
+            fs::File::open(stage.join(SEALED_MANIFEST_FILE))?.sync_all()?;
+        }
+        _ => {
+            fs::write(stage.join(MANIFEST_FILE), &bytes)
+                .map_err(|error| anyhow::anyhow!("write backup manifest: {error}"))?;
+            fs::File::open(stage.join(MANIFEST_FILE))?.sync_all()?;
+        }
+    }
     sync_tree_directories(&stage)?;
-    verify_archive(&stage)?;
+    // Recipient-only encryption cannot be reopened from the CLI
+    // credentials, so the stage is verified with the key still in hand.
+    verify_chain(read_manifest_with_key(&stage, key)?, &options.unlock)?;
     fs::rename(&stage, output).map_err(|error| {
         anyhow::anyhow!(
             "activate backup {} -> {}: {error}",
@@ -211,7 +731,7 @@ fn create_archive(entry: &SpaceEntry, output: &Path, cache_dir: &Path) -> anyhow
             output.display(),
         )
     })?;
-    sync_directory(parent)?;
+    sync_directory(parent_dir)?;
     partial.disarm();
     Ok(hex::encode(vos::crypto::blake2b_hash::<32>(
         b"vosx/space-backup-manifest/v1",
@@ -245,10 +765,9 @@ fn reject_nested_output(output: &Path, data_dir: &Path, cache_dir: &Path) -> any
 
 fn copy_tree_into_archive(
     source_root: &Path,
-    archive_root: &Path,
     prefix: &str,
     skip_endpoint: bool,
-    files: &mut Vec<BackupFile>,
+    writer: &mut ArchiveWriter,
 ) -> anyhow::Result<()> {
     let mut pending = vec![PathBuf::new()];
     while let Some(relative) = pending.pop() {
@@ -275,7 +794,7 @@ fn copy_tree_into_archive(
                 pending.push(child_relative);
             } else if metadata.is_file() {
                 let wire = wire_path(prefix, &child_relative)?;
-                copy_one_file(&entry.path(), archive_root, &wire, files, false)?;
+                writer.add(&entry.path(), &wire, false)?;
             } else {
                 anyhow::bail!("backup refuses special file {}", entry.path().display());
             }
@@ -284,11 +803,7 @@ fn copy_tree_into_archive(
     Ok(())
 }
 
-fn copy_cache_into_archive(
-    cache_dir: &Path,
-    archive_root: &Path,
-    files: &mut Vec<BackupFile>,
-) -> anyhow::Result<()> {
+fn copy_cache_into_archive(cache_dir: &Path, writer: &mut ArchiveWriter) -> anyhow::Result<()> {
     let cache_metadata = fs::symlink_metadata(cache_dir)
         .map_err(|error| anyhow::anyhow!("inspect blob cache {}: {error}", cache_dir.display()))?;
     if cache_metadata.file_type().is_symlink() || !cache_metadata.is_dir() {
@@ -330,50 +845,74 @@ fn copy_cache_into_archive(
         // deliberately not a hard link: later corruption of the live cache
         // must not mutate the archived inode too. Unsupported/cross-filesystem
         // destinations transparently fall back to a byte copy.
-        copy_one_file(
-            &entry.path(),
-            archive_root,
-            &format!("blobs/{name}"),
-            files,
-            true,
-        )?;
+        writer.add(&entry.path(), &format!("blobs/{name}"), true)?;
     }
     Ok(())
 }
 
-fn copy_one_file(
-    source: &Path,
-    archive_root: &Path,
-    wire: &str,
-    files: &mut Vec<BackupFile>,
-    allow_reflink: bool,
-) -> anyhow::Result<()> {
-    let destination = wire_to_path(archive_root, wire)?;
-    if let Some(parent) = destination.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|error| anyhow::anyhow!("create {}: {error}", parent.display()))?;
-    }
-    let cloned = allow_reflink && try_reflink(source, &destination)?;
-    if !cloned {
-        fs::copy(source, &destination).map_err(|error| {
-            anyhow::anyhow!(
-                "copy {} -> {}: {error}",
-                source.display(),
-                destination.display(),
-            )
-        })?;
+struct ArchiveWriter<'a> {
+    root: &'a Path,
+    key: Option<&'a ArchiveKey>,
+    /// Head manifest of the verified parent chain for incremental backups.
+    parent: Option<&'a BackupManifest>,
+    files: Vec<BackupFile>,
+}
+
+impl ArchiveWriter<'_> {
+    /// Record `source` at `wire`, inheriting it when the parent already holds
+    /// the same bytes. `content_addressed` marks blob-cache entries whose
+    /// name was just verified as their digest: they may be reflinked, and a
+    /// parent row with the same path is the same content.
+    fn add(&mut self, source: &Path, wire: &str, content_addressed: bool) -> anyhow::Result<()> {
+        let mode = file_mode(source)?;
+        if let Some(previous) = self.parent.and_then(|parent| parent.file(wire)) {
+            let unchanged = content_addressed || {
+                fs::metadata(source)?.len() == previous.bytes
+                    && hex::encode(hash_file(source)?) == previous.blake2b_256
+            };
+            if unchanged {
+                self.files.push(BackupFile {
+                    path: wire.into(),
+                    bytes: previous.bytes,
+                    blake2b_256: previous.blake2b_256.clone(),
+                    mode,
+                    inherited: true,
+                });
+                return Ok(());
+            }
+        }
+        let destination = wire_to_path(self.root, wire)?;
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| anyhow::anyhow!("create {}: {error}", parent.display()))?;
+        }
+        let (bytes, digest) = match self.key {
+            Some(key) => key.seal_file(wire, source, &destination)?,
+            None => {
+                let cloned = content_addressed && try_reflink(source, &destination)?;
+                if !cloned {
+                    fs::copy(source, &destination).map_err(|error| {
+                        anyhow::anyhow!(
+                            "copy {} -> {}: {error}",
+                            source.display(),
+                            destination.display(),
+                        )
+                    })?;
+                }
+                (fs::metadata(&destination)?.len(), hash_file(&destination)?)
+            }
+        };
+        set_file_mode(&destination, mode)?;
+        fs::File::open(&destination)?.sync_all()?;
+        self.files.push(BackupFile {
+            path: wire.into(),
+            bytes,
+            blake2b_256: hex::encode(digest),
+            mode,
+            inherited: false,
+        });
+        Ok(())
     }
-    let metadata = fs::metadata(&destination)?;
-    let mode = file_mode(source)?;
-    set_file_mode(&destination, mode)?;
-    fs::File::open(&destination)?.sync_all()?;
-    files.push(BackupFile {
-        path: wire.into(),
-        bytes: metadata.len(),
-        blake2b_256: hex::encode(hash_file(&destination)?),
-        mode,
-    });
-    Ok(())
 }
 
 #[cfg(target_os = "linux")]
@@ -405,32 +944,64 @@ fn try_reflink(_source: &Path, _destination: &Path) -> anyhow::Result<bool> {
     Ok(false)
 }
 
-fn verify_archive(archive: &Path) -> anyhow::Result<BackupManifest> {
+fn verify_archive(archive: &Path, unlock: &Unlock) -> anyhow::Result<BackupChain> {
+    verify_chain(read_manifest(archive, unlock)?, unlock)
+}
+
+fn read_manifest(archive: &Path, unlock: &Unlock) -> anyhow::Result<OpenedArchive> {
+    let header_path = archive.join(ENCRYPTION_FILE);
+    let key = if path_entry_exists(&header_path)? {
+        let header = read_bounded(&header_path, MAX_ENCRYPTION_HEADER_BYTES)?;
+        let header: EncryptionHeader = serde_json::from_slice(&header)
+            .map_err(|error| anyhow::anyhow!("decode {}: {error}", header_path.display()))?;
+        Some(ArchiveKey::open(&header, unlock)?)
+    } else {
+        None
+    };
+    read_manifest_with_key(archive, key)
+}
+
+/// Decode and structurally check one archive's manifest without hashing
+/// its files; `verify_chain` does that for the whole chain.
+fn read_manifest_with_key(
+    archive: &Path,
+    key: Option<ArchiveKey>,
+) -> anyhow::Result<OpenedArchive> {
     let archive_metadata = fs::symlink_metadata(archive)
         .map_err(|error| anyhow::anyhow!("inspect backup root {}: {error}", archive.display()))?;
     if archive_metadata.file_type().is_symlink() || !archive_metadata.is_dir() {
         anyhow::bail!("backup root must be a real directory");
     }
-    let manifest_path = archive.join(MANIFEST_FILE);
-    let manifest_metadata = fs::symlink_metadata(&manifest_path)
-        .map_err(|error| anyhow::anyhow!("inspect {}: {error}", manifest_path.display()))?;
-    if !manifest_metadata.is_file()
-        || manifest_metadata.file_type().is_symlink()
-        || manifest_metadata.len() > MAX_MANIFEST_BYTES
-    {
-        anyhow::bail!("backup manifest is not a bounded regular file");
-    }
-    let bytes = fs::read(&manifest_path)
-        .map_err(|error| anyhow::anyhow!("read {}: {error}", manifest_path.display()))?;
-    let manifest: BackupManifest = serde_json::from_slice(&bytes)
-        .map_err(|error| anyhow::anyhow!("decode {}: {error}", manifest_path.display()))?;
-    if manifest.format != ARCHIVE_FORMAT || manifest.version != ARCHIVE_VERSION {
+    let (stored, bytes) = match &key {
+        Some(key) => {
+            // The sealed manifest carries one tag per 64 KiB chunk.
+            let stored = read_bounded(
+                &archive.join(SEALED_MANIFEST_FILE),
+                MAX_MANIFEST_BYTES + MAX_MANIFEST_BYTES / 1024,
+            )?;
+            let bytes = key.open_bytes(MANIFEST_FILE, &stored)?;
+            (stored, bytes)
+        }
+        None => {
+            let bytes = read_bounded(&archive.join(MANIFEST_FILE), MAX_MANIFEST_BYTES)?;
+            (bytes.clone(), bytes)
+        }
+    };
+    let manifest: BackupManifest = serde_json::from_slice(&bytes).map_err(|error| {
+        anyhow::anyhow!("decode backup manifest in {}: {error}", archive.display())
+    })?;
+    if manifest.format != ARCHIVE_FORMAT || !(1..=ARCHIVE_VERSION).contains(&manifest.version) {
         anyhow::bail!(
             "unsupported backup format {} version {}",
             manifest.format,
             manifest.version,
         );
     }
+    if manifest.version < 2
+        && (manifest.parent.is_some() || manifest.files.iter().any(|file| file.inherited))
+    {
+        anyhow::bail!("version 1 backups cannot be incremental");
+    }
     if manifest.files.len() > MAX_ARCHIVE_FILES {
         anyhow::bail!("backup manifest contains too many files");
     }
@@ -444,7 +1015,6 @@ fn verify_archive(archive: &Path) -> anyhow::Result<BackupManifest> {
         anyhow::bail!("backup manifest registry blob hash is not canonical lowercase hex");
     }
     let mut previous: Option<&str> = None;
-    let mut expected = BTreeSet::new();
     for file in &manifest.files {
         if file.mode.is_some_and(|mode| mode > 0o7777) {
             anyhow::bail!("backup entry has invalid Unix mode: {}", file.path);
@@ -453,41 +1023,141 @@ fn verify_archive(archive: &Path) -> anyhow::Result<BackupManifest> {
             anyhow::bail!("backup manifest file paths must be strictly sorted");
         }
         previous = Some(&file.path);
-        let path = wire_to_path(archive, &file.path)?;
-        let metadata = fs::symlink_metadata(&path)
-            .map_err(|error| anyhow::anyhow!("inspect {}: {error}", path.display()))?;
-        if !metadata.is_file() || metadata.file_type().is_symlink() {
-            anyhow::bail!("backup entry is not a regular file: {}", file.path);
-        }
-        let actual_hash = hex::encode(hash_file(&path)?);
-        if metadata.len() != file.bytes || actual_hash != file.blake2b_256 {
-            anyhow::bail!("backup integrity mismatch: {}", file.path);
-        }
+        wire_to_path(archive, &file.path)?;
         if let Some(blob_hash) = file.path.strip_prefix("blobs/")
-            && (blob_hash.len() != 64 || blob_hash.contains('/') || actual_hash != blob_hash)
+            && (blob_hash.len() != 64 || blob_hash.contains('/') || file.blake2b_256 != blob_hash)
         {
             anyhow::bail!("backup blob path is not its content address: {}", file.path);
         }
-        expected.insert(file.path.clone());
     }
-    let actual = archive_file_set(archive)?;
-    if actual != expected {
-        anyhow::bail!("backup contains files absent from its integrity manifest");
+    if manifest.parent.is_none() && manifest.files.iter().any(|file| file.inherited) {
+        anyhow::bail!("backup inherits files but names no parent archive");
     }
+    Ok(OpenedArchive {
+        root: archive.to_path_buf(),
+        manifest,
+        stored_manifest: hash_bytes(&stored),
+        key,
+    })
+}
+
+/// Follow `head`'s parents and verify every archive: each stored byte
+/// against its row, every inherited row against the parent row it names,
+/// and the head's mandatory entries.
+fn verify_chain(head: OpenedArchive, unlock: &Unlock) -> anyhow::Result<BackupChain> {
+    let mut archives = vec![head];
+    loop {
+        let child = archives.last().expect("chain has a head");
+        let Some(parent) = &child.manifest.parent else {
+            break;
+        };
+        if archives.len() >= MAX_BACKUP_CHAIN {
+            anyhow::bail!("backup chain is longer than {MAX_BACKUP_CHAIN} archives");
+        }
+        let opened = open_parent(&child.root, parent, unlock)?;
+        if opened.manifest.space.id != child.manifest.space.id {
+            anyhow::bail!("parent backup {} is of a different space", parent.path);
+        }
+        archives.push(opened);
+    }
+    for (index, archive) in archives.iter().enumerate() {
+        let parent = archives.get(index + 1).map(|parent| &parent.manifest);
+        let mut expected = BTreeSet::new();
+        for file in &archive.manifest.files {
+            if file.inherited {
+                let Some(previous) = parent.and_then(|parent| parent.file(&file.path)) else {
+                    anyhow::bail!(
+                        "backup entry {} is inherited but absent from its parent",
+                        file.path
+                    );
+                };
+                if previous.bytes != file.bytes || previous.blake2b_256 != file.blake2b_256 {
+                    anyhow::bail!(
+                        "backup entry {} differs from the parent row it inherits",
+                        file.path
+                    );
+                }
+                continue;
+            }
+            let stored = StoredFile {
+                path: wire_to_path(&archive.root, &file.path)?,
+                key: archive.key.as_ref(),
+            };
+            let metadata = fs::symlink_metadata(&stored.path)
+                .map_err(|error| anyhow::anyhow!("inspect {}: {error}", stored.path.display()))?;
+            if !metadata.is_file() || metadata.file_type().is_symlink() {
+                anyhow::bail!("backup entry is not a regular file: {}", file.path);
+            }
+            let (bytes, digest) = stored.digest(&file.path)?;
+            if bytes != file.bytes || hex::encode(digest) != file.blake2b_256 {
+                anyhow::bail!("backup integrity mismatch: {}", file.path);
+            }
+            expected.insert(file.path.clone());
+        }
+        let actual = archive_file_set(&archive.root, archive.key.is_some())?;
+        if actual != expected {
+            anyhow::bail!("backup contains files absent from its integrity manifest");
+        }
+    }
+    let manifest = &archives[0].manifest;
     let registry_db = registry_db_wire();
     let registry_blob = format!("blobs/{}", manifest.space.registry_hash);
     for required in [NODE_KEY_WIRE, registry_db.as_str(), registry_blob.as_str()] {
-        if !expected.contains(required) {
-            anyhow::bail!("backup is structurally incomplete: missing {required}");
+        match manifest.file(required) {
+            None => anyhow::bail!("backup is structurally incomplete: missing {required}"),
+            Some(file) if file.bytes == 0 => {
+                anyhow::bail!("backup is structurally incomplete: {required} is empty")
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(BackupChain { archives })
+}
+
+/// Find the parent archive at its recorded path or, for a chain copied
+/// together to new storage, next to the child. A candidate counts only when
+/// its stored manifest has the digest the child recorded.
+fn open_parent(
+    child: &Path,
+    parent: &BackupParent,
+    unlock: &Unlock,
+) -> anyhow::Result<OpenedArchive> {
+    let recorded = PathBuf::from(&parent.path);
+    let mut candidates = vec![recorded.clone()];
+    if let Some(name) = recorded.file_name() {
+        candidates.push(usable_parent(child).join(name));
+    }
+    for candidate in candidates {
+        if !path_entry_exists(&candidate)? {
+            continue;
         }
-        if fs::metadata(wire_to_path(archive, required)?)?.len() == 0 {
-            anyhow::bail!("backup is structurally incomplete: {required} is empty");
+        let opened = read_manifest(&candidate, unlock)?;
+        if hex::encode(opened.stored_manifest) == parent.manifest_blake2b_256 {
+            return Ok(opened);
         }
     }
-    Ok(manifest)
+    anyhow::bail!(
+        "parent backup {} with manifest {} is missing; restore needs every archive of an incremental chain",
+        parent.path,
+        parent.manifest_blake2b_256,
+    )
+}
+
+fn read_bounded(path: &Path, limit: u64) -> anyhow::Result<Vec<u8>> {
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|error| anyhow::anyhow!("inspect {}: {error}", path.display()))?;
+    if !metadata.is_file() || metadata.file_type().is_symlink() || metadata.len() > limit {
+        anyhow::bail!("{} is not a bounded regular file", path.display());
+    }
+    fs::read(path).map_err(|error| anyhow::anyhow!("read {}: {error}", path.display()))
 }
 
-fn archive_file_set(archive: &Path) -> anyhow::Result<BTreeSet<String>> {
+fn archive_file_set(archive: &Path, encrypted: bool) -> anyhow::Result<BTreeSet<String>> {
+    let manifest_names: &[&str] = if encrypted {
+        &[ENCRYPTION_FILE, SEALED_MANIFEST_FILE]
+    } else {
+        &[MANIFEST_FILE]
+    };
     let mut root_names = fs::read_dir(archive)?
         .collect::<Result<Vec<_>, _>>()?
         .into_iter()
@@ -495,7 +1165,10 @@ fn archive_file_set(archive: &Path) -> anyhow::Result<BTreeSet<String>> {
         .collect::<Vec<_>>();
     root_names.sort();
     for name in &root_names {
-        if name != MANIFEST_FILE && name != "data" && name != "blobs" {
+        if !manifest_names.iter().any(|manifest| name == *manifest)
+            && name != "data"
+            && name != "blobs"
+        {
             anyhow::bail!(
                 "backup contains an unexpected root entry {}",
                 name.to_string_lossy(),
@@ -549,8 +1222,10 @@ fn collect_wire_files(root: &Path, prefix: &str, files: &mut Vec<String>) -> any
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn restore_archive(
     archive: &Path,
+    unlock: &Unlock,
     destination: &Path,
     cache_dir: &Path,
     index_path: &Path,
@@ -559,13 +1234,14 @@ fn restore_archive(
     name: Option<&str>,
 ) -> anyhow::Result<Option<PathBuf>> {
     let destination = spaces_index::normalize_data_directory(destination)?;
-    let manifest = verify_archive(archive)?;
-    if let Some(expected) = expected_manifest
-        && hash_file(&archive.join(MANIFEST_FILE))? != expected
-    {
+    let chain = verify_archive(archive, unlock)?;
+    let manifest = chain.manifest();
+    if expected_manifest.is_some_and(|expected| chain.archives[0].stored_manifest != expected) {
         anyhow::bail!("backup manifest changed while restore was acquiring its lock");
     }
-    reject_restore_overlap(archive, &destination)?;
+    for archive in &chain.archives {
+        reject_restore_overlap(&archive.root, &destination)?;
+    }
     let mut restored_entry = manifest.space.clone();
     if let Some(name) = name {
         if name.is_empty() {
@@ -601,10 +1277,9 @@ fn restore_archive(
     let mut partial = PartialDirectory::new(stage.clone());
 
     for file in &manifest.files {
-        let source = wire_to_path(archive, &file.path)?;
         if let Some(relative) = file.path.strip_prefix("data/") {
             let target = wire_to_path(&stage, relative)?;
-            copy_verified_file(&source, &target, file)?;
+            copy_verified_file(&chain.stored(file)?, &target, file)?;
         } else if file.path.strip_prefix("blobs/").is_some() {
             // Installed below through the explicit cache root. Keeping this
             // loop focused on data files avoids tests or embedded callers
@@ -614,7 +1289,7 @@ fn restore_archive(
         }
     }
 
-    restore_blobs_to_cache(archive, &manifest, cache_dir)?;
+    restore_blobs_to_cache(&chain, cache_dir)?;
     sync_tree_directories(&stage)?;
 
     let replaced = if destination_exists {
@@ -669,30 +1344,26 @@ fn restore_archive(
     Ok(replaced)
 }
 
-fn restore_blobs_to_cache(
-    archive: &Path,
-    manifest: &BackupManifest,
-    cache_dir: &Path,
-) -> anyhow::Result<()> {
+fn restore_blobs_to_cache(chain: &BackupChain, cache_dir: &Path) -> anyhow::Result<()> {
     fs::create_dir_all(cache_dir)?;
-    for file in &manifest.files {
+    for file in &chain.manifest().files {
         let Some(hash_hex) = file.path.strip_prefix("blobs/") else {
             continue;
         };
-        let source = wire_to_path(archive, &file.path)?;
-        let actual = BlobHash(hash_file(&source)?);
-        if actual.to_hex() != hash_hex {
-            anyhow::bail!("backup blob {hash_hex} has content address {actual}");
-        }
+        let expected = BlobHash::from_hex(hash_hex)
+            .map_err(|_| anyhow::anyhow!("invalid backup blob name {hash_hex}"))?;
         let target = cache_dir.join(hash_hex);
         if target.exists() {
-            if BlobHash(hash_file(&target)?) != actual {
+            if BlobHash(hash_file(&target)?) != expected {
                 anyhow::bail!("restore cache already contains corrupt blob {hash_hex}");
             }
         } else {
             let temporary = temporary_sibling(&target, "cache-partial")?;
-            fs::copy(&source, &temporary)?;
-            if BlobHash(hash_file(&temporary)?) != actual {
+            if let Err(error) = chain.stored(file)?.extract(&file.path, &temporary) {
+                let _ = fs::remove_file(&temporary);
+                return Err(error);
+            }
+            if BlobHash(hash_file(&temporary)?) != expected {
                 let _ = fs::remove_file(&temporary);
                 anyhow::bail!("backup blob {hash_hex} changed during restore");
             }
@@ -700,7 +1371,7 @@ fn restore_blobs_to_cache(
                 Ok(()) => {}
                 Err(error) if target.exists() => {
                     let _ = fs::remove_file(&temporary);
-                    if BlobHash(hash_file(&target)?) != actual {
+                    if BlobHash(hash_file(&target)?) != expected {
                         return Err(error.into());
                     }
                 }
@@ -759,18 +1430,18 @@ fn rollback_error(
     }
 }
 
-fn copy_verified_file(source: &Path, destination: &Path, file: &BackupFile) -> anyhow::Result<()> {
-    let metadata = fs::metadata(source)?;
-    if metadata.len() != file.bytes || hex::encode(hash_file(source)?) != file.blake2b_256 {
-        anyhow::bail!("backup changed during restore: {}", file.path);
-    }
+fn copy_verified_file(
+    source: &StoredFile,
+    destination: &Path,
+    file: &BackupFile,
+) -> anyhow::Result<()> {
     if let Some(parent) = destination
         .parent()
         .filter(|parent| !parent.as_os_str().is_empty())
     {
         fs::create_dir_all(parent)?;
     }
-    fs::copy(source, destination)?;
+    source.extract(&file.path, destination)?;
     if fs::metadata(destination)?.len() != file.bytes
         || hex::encode(hash_file(destination)?) != file.blake2b_256
     {
@@ -798,7 +1469,7 @@ fn reject_restore_overlap(archive: &Path, destination: &Path) -> anyhow::Result<
     Ok(())
 }
 
-fn hash_file(path: &Path) -> anyhow::Result<[u8; 32]> {
+pub(super) fn hash_file(path: &Path) -> anyhow::Result<[u8; 32]> {
     let mut file = fs::File::open(path)?;
     let mut state = blake2b_simd::Params::new().hash_length(32).to_state();
     let mut buffer = [0u8; 64 * 1024];
@@ -816,6 +1487,15 @@ fn hash_file(path: &Path) -> anyhow::Result<[u8; 32]> {
         .expect("32-byte digest"))
 }
 
+pub(super) fn hash_bytes(bytes: &[u8]) -> [u8; 32] {
+    blake2b_simd::Params::new()
+        .hash_length(32)
+        .hash(bytes)
+        .as_bytes()
+        .try_into()
+        .expect("32-byte digest")
+}
+
 fn sync_tree_directories(root: &Path) -> anyhow::Result<()> {
     #[cfg(unix)]
     {
@@ -840,13 +1520,13 @@ fn sync_tree_directories(root: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn sync_directory(path: &Path) -> anyhow::Result<()> {
+pub(super) fn sync_directory(path: &Path) -> anyhow::Result<()> {
     #[cfg(unix)]
     fs::File::open(path)?.sync_all()?;
     Ok(())
 }
 
-fn temporary_sibling(path: &Path, label: &str) -> anyhow::Result<PathBuf> {
+pub(super) fn temporary_sibling(path: &Path, label: &str) -> anyhow::Result<PathBuf> {
     let mut nonce = [0u8; 8];
     getrandom::getrandom(&mut nonce)
         .map_err(|error| anyhow::anyhow!("OS entropy for temporary path: {error}"))?;
@@ -861,7 +1541,7 @@ fn temporary_sibling(path: &Path, label: &str) -> anyhow::Result<PathBuf> {
     )))
 }
 
-fn path_entry_exists(path: &Path) -> anyhow::Result<bool> {
+pub(super) fn path_entry_exists(path: &Path) -> anyhow::Result<bool> {
     match fs::symlink_metadata(path) {
         Ok(_) => Ok(true),
         Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(false),
@@ -869,7 +1549,7 @@ fn path_entry_exists(path: &Path) -> anyhow::Result<bool> {
     }
 }
 
-fn usable_parent(path: &Path) -> &Path {
+pub(super) fn usable_parent(path: &Path) -> &Path {
     path.parent()
         .filter(|parent| !parent.as_os_str().is_empty())
         .unwrap_or_else(|| Path::new("."))
@@ -926,15 +1606,32 @@ fn set_file_mode(path: &Path, mode: Option<u32>) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Create a new directory only its owner can enter — mode 0700 from the
+/// start, so no plaintext lands in it while it is wider.
+#[cfg(unix)]
+fn create_private_directory(path: &Path) -> anyhow::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    fs::DirBuilder::new()
+        .mode(0o700)
+        .create(path)
+        .map_err(|error| anyhow::anyhow!("create {}: {error}", path.display()))?;
+    set_directory_private(path)
+}
+
+#[cfg(not(unix))]
+fn create_private_directory(path: &Path) -> anyhow::Result<()> {
+    fs::create_dir(path).map_err(|error| anyhow::anyhow!("create {}: {error}", path.display()))
+}
+
 #[cfg(unix)]
-fn set_directory_private(path: &Path) -> anyhow::Result<()> {
+pub(super) fn set_directory_private(path: &Path) -> anyhow::Result<()> {
     use std::os::unix::fs::PermissionsExt;
     fs::set_permissions(path, fs::Permissions::from_mode(0o700))?;
     Ok(())
 }
 
 #[cfg(not(unix))]
-fn set_directory_private(_path: &Path) -> anyhow::Result<()> {
+pub(super) fn set_directory_private(_path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
@@ -1026,8 +1723,9 @@ mod tests {
         let temp = TempDir::new("roundtrip");
         let (entry, _data, cache) = fixture(&temp.0);
         let archive = temp.0.join("backup");
-        create_archive(&entry, &archive, &cache).unwrap();
-        let manifest = verify_archive(&archive).unwrap();
+        create_archive(&entry, &archive, &cache, &ArchiveOptions::default()).unwrap();
+        let chain = verify_archive(&archive, &Unlock::default()).unwrap();
+        let manifest = chain.manifest();
         assert!(
             manifest
                 .files
@@ -1052,6 +1750,7 @@ mod tests {
         let index = temp.0.join("spaces.toml");
         restore_archive(
             &archive,
+            &Unlock::default(),
             &destination,
             &restored_cache,
             &index,
@@ -1082,11 +1781,12 @@ mod tests {
         let temp = TempDir::new("tamper");
         let (entry, _data, cache) = fixture(&temp.0);
         let archive = temp.0.join("backup");
-        create_archive(&entry, &archive, &cache).unwrap();
+        create_archive(&entry, &archive, &cache, &ArchiveOptions::default()).unwrap();
         fs::write(archive.join("data/node.key"), b"tampered").unwrap();
         let destination = temp.0.join("restored");
         let error = restore_archive(
             &archive,
+            &Unlock::default(),
             &destination,
             &temp.0.join("restored-cache"),
             &temp.0.join("spaces.toml"),
@@ -1104,7 +1804,7 @@ mod tests {
         let temp = TempDir::new("cache-independence");
         let (entry, _data, cache) = fixture(&temp.0);
         let archive = temp.0.join("backup");
-        create_archive(&entry, &archive, &cache).unwrap();
+        create_archive(&entry, &archive, &cache, &ArchiveOptions::default()).unwrap();
         let hash = entry.registry_hash;
         let archived = archive.join("blobs").join(&hash);
         let original = fs::read(&archived).unwrap();
@@ -1114,7 +1814,7 @@ mod tests {
         // cache is damaged after backup activation.
         fs::write(cache.join(&hash), vec![0xA5; original.len()]).unwrap();
         assert_eq!(fs::read(archived).unwrap(), original);
-        verify_archive(&archive).unwrap();
+        verify_archive(&archive, &Unlock::default()).unwrap();
     }
 
     #[test]
@@ -1122,25 +1822,29 @@ mod tests {
         let temp = TempDir::new("replace");
         let (entry, _data, cache) = fixture(&temp.0);
         let archive = temp.0.join("backup");
-        create_archive(&entry, &archive, &cache).unwrap();
-        let mut manifest = verify_archive(&archive).unwrap();
+        create_archive(&entry, &archive, &cache, &ArchiveOptions::default()).unwrap();
+        let mut manifest = verify_archive(&archive, &Unlock::default())
+            .unwrap()
+            .manifest()
+            .clone();
         manifest.files[0].path = "data/../escape".into();
         fs::write(
             archive.join(MANIFEST_FILE),
             serde_json::to_vec_pretty(&manifest).unwrap(),
         )
         .unwrap();
-        assert!(verify_archive(&archive).is_err());
+        assert!(verify_archive(&archive, &Unlock::default()).is_err());
 
         // Rebuild a clean archive and prove replacement is rename-aside, not
         // overwrite/delete.
         fs::remove_dir_all(&archive).unwrap();
-        create_archive(&entry, &archive, &cache).unwrap();
+        create_archive(&entry, &archive, &cache, &ArchiveOptions::default()).unwrap();
         let destination = temp.0.join("restored");
         fs::create_dir(&destination).unwrap();
         fs::write(destination.join("old"), b"recover me").unwrap();
         let replaced = restore_archive(
             &archive,
+            &Unlock::default(),
             &destination,
             &temp.0.join("restored-cache"),
             &temp.0.join("spaces.toml"),
@@ -1162,7 +1866,7 @@ mod tests {
         let temp = TempDir::new("owned-destination");
         let (entry, _data, cache) = fixture(&temp.0);
         let archive = temp.0.join("backup");
-        create_archive(&entry, &archive, &cache).unwrap();
+        create_archive(&entry, &archive, &cache, &ArchiveOptions::default()).unwrap();
 
         let destination = temp.0.join("other-space");
         fs::create_dir(&destination).unwrap();
@@ -1188,6 +1892,7 @@ mod tests {
 
         let error = restore_archive(
             &archive,
+            &Unlock::default(),
             &destination,
             &temp.0.join("restored-cache"),
             &index_path,
@@ -1208,7 +1913,7 @@ mod tests {
         let temp = TempDir::new("nested-destination");
         let (entry, _data, cache) = fixture(&temp.0);
         let archive = temp.0.join("backup");
-        create_archive(&entry, &archive, &cache).unwrap();
+        create_archive(&entry, &archive, &cache, &ArchiveOptions::default()).unwrap();
 
         let owned_parent = temp.0.join("owned-parent");
         let owned = owned_parent.join("owned-space");
@@ -1236,6 +1941,7 @@ mod tests {
         for destination in [owned.join("nested-a"), owned_parent.clone()] {
             let error = restore_archive(
                 &archive,
+                &Unlock::default(),
                 &destination,
                 &temp.0.join("restored-cache"),
                 &index_path,
@@ -1258,8 +1964,20 @@ mod tests {
         second.name = "second-backup".into();
         let first_archive = temp.0.join("first-backup");
         let second_archive = temp.0.join("second-backup");
-        create_archive(&first, &first_archive, &first_cache).unwrap();
-        create_archive(&second, &second_archive, &second_cache).unwrap();
+        create_archive(
+            &first,
+            &first_archive,
+            &first_cache,
+            &ArchiveOptions::default(),
+        )
+        .unwrap();
+        create_archive(
+            &second,
+            &second_archive,
+            &second_cache,
+            &ArchiveOptions::default(),
+        )
+        .unwrap();
 
         let index = temp.0.join("spaces.toml");
         let first_destination = temp.0.join("first-restored");
@@ -1271,6 +1989,7 @@ mod tests {
         let first_join = std::thread::spawn(move || {
             restore_archive(
                 &first_archive,
+                &Unlock::default(),
                 &first_destination,
                 &first_cache,
                 &first_index,
@@ -1282,6 +2001,7 @@ mod tests {
         let second_join = std::thread::spawn(move || {
             restore_archive(
                 &second_archive,
+                &Unlock::default(),
                 &second_destination,
                 &second_cache,
                 &second_index,
@@ -1299,6 +2019,203 @@ mod tests {
         assert!(index.spaces.iter().any(|entry| entry.id == second.id));
     }
 
+    #[test]
+    fn online_snapshot_feeds_the_same_archive_and_never_reuses_a_directory() {
+        let temp = TempDir::new("online");
+        let (entry, data, cache) = fixture(&temp.0);
+        let snapshot = temp.0.join("snapshot");
+        let report: SnapshotReport =
+            serde_json::from_str(&serve_snapshot(&data, snapshot.to_str().unwrap())).unwrap();
+        assert!(report.error.is_none(), "{:?}", report.error);
+        assert_eq!(report.files, 5);
+        assert_eq!(
+            report.linked, 2,
+            "only the rename-only side stores are linked"
+        );
+        assert!(!snapshot.join(".endpoint").exists());
+        // The links taken under the barrier were replaced by private copies.
+        fs::write(
+            data.join("v2-services/root.image.proofs/proof"),
+            b"rewritten",
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read(snapshot.join("v2-services/root.image.proofs/proof")).unwrap(),
+            b"proof-side-cas",
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&snapshot).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+
+        // A second request into the same directory, or one inside the data
+        // directory, is refused without touching what is already there.
+        let again: SnapshotReport =
+            serde_json::from_str(&serve_snapshot(&data, snapshot.to_str().unwrap())).unwrap();
+        assert!(again.error.is_some());
+        assert!(snapshot.join("node.key").exists());
+        let nested: SnapshotReport =
+            serde_json::from_str(&serve_snapshot(&data, data.join("inner").to_str().unwrap()))
+                .unwrap();
+        assert!(nested.error.is_some());
+        assert!(!data.join("inner").exists());
+
+        // The live tree moves on; the archive holds the snapshot instant.
+        fs::write(data.join("v2-services/root.image"), b"later-image").unwrap();
+        let archive = temp.0.join("archive");
+        create_archive(
+            &entry,
+            &archive,
+            &cache,
+            &ArchiveOptions {
+                snapshot: Some(&snapshot),
+                ..ArchiveOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read(archive.join("data/v2-services/root.image")).unwrap(),
+            b"committed-service-image",
+        );
+        assert_eq!(
+            read_manifest(&archive, &Unlock::default())
+                .unwrap()
+                .manifest
+                .space
+                .data_dir,
+            entry.data_dir,
+        );
+    }
+
+    #[test]
+    fn incremental_chain_inherits_unchanged_files_and_restores_through_parents() {
+        let temp = TempDir::new("incremental");
+        let (entry, data, cache) = fixture(&temp.0);
+        let chain_dir = temp.0.join("backups");
+        fs::create_dir(&chain_dir).unwrap();
+        let full = chain_dir.join("full");
+        create_archive(&entry, &full, &cache, &ArchiveOptions::default()).unwrap();
+        fs::write(
+            data.join("v2-services/root.image"),
+            b"service-image-after-commit",
+        )
+        .unwrap();
+        let increment = chain_dir.join("increment");
+        create_archive(
+            &entry,
+            &increment,
+            &cache,
+            &ArchiveOptions {
+                parent: Some(&full),
+                ..ArchiveOptions::default()
+            },
+        )
+        .unwrap();
+        let chain = verify_archive(&increment, &Unlock::default()).unwrap();
+        assert_eq!(chain.archives.len(), 2);
+        let copied = chain
+            .manifest()
+            .files
+            .iter()
+            .filter(|file| !file.inherited)
+            .map(|file| file.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(copied, ["data/v2-services/root.image"]);
+        assert!(!increment.join(NODE_KEY_WIRE).exists());
+
+        // The chain keeps resolving after both archives move together.
+        let moved = temp.0.join("moved");
+        fs::rename(&chain_dir, &moved).unwrap();
+        let destination = temp.0.join("restored");
+        restore_archive(
+            &moved.join("increment"),
+            &Unlock::default(),
+            &destination,
+            &temp.0.join("restored-cache"),
+            &temp.0.join("spaces.toml"),
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read(destination.join("v2-services/root.image")).unwrap(),
+            b"service-image-after-commit"
+        );
+        assert_eq!(
+            fs::read(destination.join("node.key")).unwrap(),
+            b"secret-node-key"
+        );
+
+        fs::write(moved.join("full/data/node.key"), b"tampered").unwrap();
+        let error = verify_archive(&moved.join("increment"), &Unlock::default()).unwrap_err();
+        assert!(error.to_string().contains("integrity mismatch"));
+    }
+
+    #[test]
+    fn encrypted_archive_hides_secrets_and_needs_the_identity_to_restore() {
+        let temp = TempDir::new("encrypted");
+        let (entry, _data, cache) = fixture(&temp.0);
+        let plain = temp.0.join("plain");
+        create_archive(&entry, &plain, &cache, &ArchiveOptions::default()).unwrap();
+        let (secret, recipient) = backup_seal::generate_identity().unwrap();
+        let refused = create_archive(
+            &entry,
+            &temp.0.join("refused"),
+            &cache,
+            &ArchiveOptions {
+                parent: Some(&plain),
+                sealing: Some(Sealing::Recipients(vec![recipient])),
+                ..ArchiveOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert!(refused.to_string().contains("unencrypted chain"));
+
+        let archive = temp.0.join("sealed");
+        create_archive(
+            &entry,
+            &archive,
+            &cache,
+            &ArchiveOptions {
+                sealing: Some(Sealing::Recipients(vec![recipient])),
+                ..ArchiveOptions::default()
+            },
+        )
+        .unwrap();
+        assert!(!archive.join(MANIFEST_FILE).exists());
+        let stored = fs::read(archive.join(NODE_KEY_WIRE)).unwrap();
+        assert!(
+            !stored
+                .windows(b"secret-node-key".len())
+                .any(|window| window == b"secret-node-key")
+        );
+        assert!(verify_archive(&archive, &Unlock::default()).is_err());
+
+        let unlock = Unlock {
+            identity: Some(secret),
+            ..Unlock::default()
+        };
+        let destination = temp.0.join("restored");
+        restore_archive(
+            &archive,
+            &unlock,
+            &destination,
+            &temp.0.join("restored-cache"),
+            &temp.0.join("spaces.toml"),
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read(destination.join("node.key")).unwrap(),
+            b"secret-node-key"
+        );
+    }
+
     #[test]
     fn structurally_incomplete_archive_is_rejected() {
         let temp = TempDir::new("incomplete");
@@ -1312,12 +2229,13 @@ mod tests {
                 format: ARCHIVE_FORMAT.into(),
                 version: ARCHIVE_VERSION,
                 space: entry,
+                parent: None,
                 files: Vec::new(),
             })
             .unwrap(),
         )
         .unwrap();
-        let error = verify_archive(&archive).unwrap_err();
+        let error = verify_archive(&archive, &Unlock::default()).unwrap_err();
         assert!(error.to_string().contains("structurally incomplete"));
     }
 
@@ -1330,12 +2248,12 @@ mod tests {
             let temp = TempDir::new(&format!("root-symlink-{prefix}"));
             let (entry, _data, cache) = fixture(&temp.0);
             let archive = temp.0.join("backup");
-            create_archive(&entry, &archive, &cache).unwrap();
+            create_archive(&entry, &archive, &cache, &ArchiveOptions::default()).unwrap();
             let root = archive.join(prefix);
             let external = temp.0.join(format!("external-{prefix}"));
             fs::rename(&root, &external).unwrap();
             symlink(&external, &root).unwrap();
-            let error = verify_archive(&archive).unwrap_err();
+            let error = verify_archive(&archive, &Unlock::default()).unwrap_err();
             assert!(error.to_string().contains("must be a real directory"));
         }
     }
@@ -0,0 +1,512 @@
+//! Authenticated encryption for `space backup` archives.
+//!
+//! Every encrypted archive has its own random 32-byte archive key. The key is
+//! wrapped in `encryption.json` for each unlock method, age-style: an X25519
+//! recipient stanza (ephemeral key agreement) or an Argon2id passphrase
+//! stanza. File bodies and the manifest are sealed with ChaCha20-Poly1305 in
+//! 64 KiB chunks (the STREAM construction: a big-endian chunk counter plus a
+//! final-chunk flag in the nonce), so multi-GiB redb files never have to fit
+//! in memory and truncation, reordering, or moving a file to another archive
+//! path fails authentication. Each wire path gets its own derived key.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+
+const CHUNK_BYTES: usize = 64 * 1024;
+const TAG_BYTES: usize = 16;
+const ARGON2_MEMORY_KIB: u32 = 64 * 1024;
+const ARGON2_ITERATIONS: u32 = 3;
+const ARGON2_LANES: u32 = 1;
+/// Bounds an attacker-supplied header so opening an archive cannot be made
+/// to allocate gigabytes or spin for minutes.
+const ARGON2_MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const ARGON2_MAX_ITERATIONS: u32 = 16;
+const ARGON2_MAX_LANES: u32 = 16;
+const MAX_RECIPIENTS: usize = 64;
+
+/// What a new archive is encrypted to.
+pub enum Sealing {
+    Passphrase(Vec<u8>),
+    Recipients(Vec<[u8; 32]>),
+}
+
+/// Credentials offered when opening encrypted archives. Every archive in an
+/// incremental chain is tried with the same credentials.
+#[derive(Default)]
+pub struct Unlock {
+    pub passphrase: Option<Vec<u8>>,
+    pub identity: Option<[u8; 32]>,
+}
+
+impl Unlock {
+    pub fn from_args(
+        identity: Option<&Path>,
+        passphrase_env: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            passphrase: passphrase_env.map(read_passphrase).transpose()?,
+            identity: identity.map(read_identity).transpose()?,
+        })
+    }
+}
+
+/// Plaintext `encryption.json`: one wrapped copy of the archive key per
+/// unlock method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptionHeader {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    recipients: Vec<RecipientStanza>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    passphrase: Option<PassphraseStanza>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecipientStanza {
+    ephemeral: String,
+    wrapped_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PassphraseStanza {
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    lanes: u32,
+    wrapped_key: String,
+}
+
+pub struct ArchiveKey([u8; 32]);
+
+impl ArchiveKey {
+    pub fn generate() -> anyhow::Result<Self> {
+        Ok(Self(random_bytes()?))
+    }
+
+    /// Wrap this key for every unlock method in `sealing`.
+    pub fn header(&self, sealing: &Sealing) -> anyhow::Result<EncryptionHeader> {
+        match sealing {
+            Sealing::Passphrase(passphrase) => {
+                let salt: [u8; 16] = random_bytes()?;
+                let wrap = passphrase_key(
+                    passphrase,
+                    &salt,
+                    ARGON2_MEMORY_KIB,
+                    ARGON2_ITERATIONS,
+                    ARGON2_LANES,
+                )?;
+                Ok(EncryptionHeader {
+                    recipients: Vec::new(),
+                    passphrase: Some(PassphraseStanza {
+                        salt: hex::encode(salt),
+                        memory_kib: ARGON2_MEMORY_KIB,
+                        iterations: ARGON2_ITERATIONS,
+                        lanes: ARGON2_LANES,
+                        wrapped_key: hex::encode(wrap_key(&wrap, &self.0)?),
+                    }),
+                })
+            }
+            Sealing::Recipients(recipients) => {
+                if recipients.is_empty() || recipients.len() > MAX_RECIPIENTS {
+                    anyhow::bail!("backup encryption needs 1..={MAX_RECIPIENTS} recipients");
+                }
+                let mut stanzas = Vec::with_capacity(recipients.len());
+                for recipient in recipients {
+                    let ephemeral = x25519_dalek::StaticSecret::from(random_bytes::<32>()?);
+                    let ephemeral_public = x25519_dalek::PublicKey::from(&ephemeral);
+                    let wrap = recipient_key(&ephemeral, &ephemeral_public, recipient)?;
+                    stanzas.push(RecipientStanza {
+                        ephemeral: hex::encode(ephemeral_public.as_bytes()),
+                        wrapped_key: hex::encode(wrap_key(&wrap, &self.0)?),
+                    });
+                }
+                Ok(EncryptionHeader {
+                    recipients: stanzas,
+                    passphrase: None,
+                })
+            }
+        }
+    }
+
+    /// Recover the archive key from `header` with whichever credential fits.
+    pub fn open(header: &EncryptionHeader, unlock: &Unlock) -> anyhow::Result<Self> {
+        if header.recipients.len() > MAX_RECIPIENTS {
+            anyhow::bail!("backup encryption header lists too many recipients");
+        }
+        if let Some(identity) = unlock.identity {
+            let secret = x25519_dalek::StaticSecret::from(identity);
+            let public = x25519_dalek::PublicKey::from(&secret);
+            for stanza in &header.recipients {
+                let ephemeral: [u8; 32] = decode_hex(&stanza.ephemeral, "recipient ephemeral")?;
+                let ephemeral = x25519_dalek::PublicKey::from(ephemeral);
+                let shared = secret.diffie_hellman(&ephemeral);
+                if !shared.was_contributory() {
+                    continue;
+                }
+                let wrap = vos::crypto::blake2b_hash::<32>(
+                    b"vosx/space-backup/recipient/v1",
+                    &[shared.as_bytes(), ephemeral.as_bytes(), public.as_bytes()],
+                );
+                if let Some(key) = unwrap_key(&wrap, &stanza.wrapped_key)? {
+                    return Ok(Self(key));
+                }
+            }
+        }
+        if let (Some(passphrase), Some(stanza)) = (&unlock.passphrase, &header.passphrase) {
+            if stanza.memory_kib > ARGON2_MAX_MEMORY_KIB
+                || stanza.iterations > ARGON2_MAX_ITERATIONS
+                || stanza.lanes > ARGON2_MAX_LANES
+            {
+                anyhow::bail!("backup passphrase parameters exceed the supported bounds");
+            }
+            let salt: [u8; 16] = decode_hex(&stanza.salt, "passphrase salt")?;
+            let wrap = passphrase_key(
+                passphrase,
+                &salt,
+                stanza.memory_kib,
+                stanza.iterations,
+                stanza.lanes,
+            )?;
+            if let Some(key) = unwrap_key(&wrap, &stanza.wrapped_key)? {
+                return Ok(Self(key));
+            }
+        }
+        match (header.recipients.is_empty(), header.passphrase.is_some()) {
+            (false, _) if unlock.identity.is_none() => {
+                anyhow::bail!("backup is encrypted to recipients; pass --identity")
+            }
+            (true, true) if unlock.passphrase.is_none() => {
+                anyhow::bail!("backup is passphrase-encrypted; pass --passphrase-env")
+            }
+            _ => anyhow::bail!("backup key does not unlock with the supplied credentials"),
+        }
+    }
+
+    pub fn seal_bytes(&self, wire: &str, bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut sealed = Vec::with_capacity(bytes.len() + TAG_BYTES);
+        self.seal_stream(wire, bytes, &mut sealed)?;
+        Ok(sealed)
+    }
+
+    pub fn open_bytes(&self, wire: &str, sealed: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(sealed.len());
+        self.open_stream(wire, sealed, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Encrypt `source` into the new file `destination`, returning the
+    /// plaintext length and BLAKE2b-256 digest that were sealed.
+    pub fn seal_file(
+        &self,
+        wire: &str,
+        source: &Path,
+        destination: &Path,
+    ) -> anyhow::Result<(u64, [u8; 32])> {
+        let input = fs::File::open(source)
+            .map_err(|error| anyhow::anyhow!("open {}: {error}", source.display()))?;
+        let mut output = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(destination)
+            .map_err(|error| anyhow::anyhow!("create {}: {error}", destination.display()))?;
+        let sealed = self.seal_stream(wire, input, &mut output)?;
+        output.sync_all()?;
+        Ok(sealed)
+    }
+
+    /// Authenticate and decrypt `source` into `sink`, returning the
+    /// plaintext length and BLAKE2b-256 digest. `sink` may have received a
+    /// prefix of the plaintext when this fails; callers write to staging.
+    pub fn open_file(
+        &self,
+        wire: &str,
+        source: &Path,
+        sink: &mut dyn Write,
+    ) -> anyhow::Result<(u64, [u8; 32])> {
+        let input = fs::File::open(source)
+            .map_err(|error| anyhow::anyhow!("open {}: {error}", source.display()))?;
+        self.open_stream(wire, input, sink)
+    }
+
+    fn file_cipher(&self, wire: &str) -> ChaCha20Poly1305 {
+        let key = vos::crypto::blake2b_hash::<32>(
+            b"vosx/space-backup/file-key/v1",
+            &[&self.0, wire.as_bytes()],
+        );
+        ChaCha20Poly1305::new(Key::from_slice(&key))
+    }
+
+    fn seal_stream(
+        &self,
+        wire: &str,
+        mut input: impl Read,
+        output: &mut dyn Write,
+    ) -> anyhow::Result<(u64, [u8; 32])> {
+        let cipher = self.file_cipher(wire);
+        let mut digest = blake2b_simd::Params::new().hash_length(32).to_state();
+        let mut current = vec![0u8; CHUNK_BYTES];
+        let mut next = vec![0u8; CHUNK_BYTES];
+        let mut filled = read_full(&mut input, &mut current)?;
+        let mut total = 0u64;
+        let mut counter = 0u64;
+        loop {
+            // A full chunk is only final when nothing follows it.
+            let next_filled = if filled == CHUNK_BYTES {
+                read_full(&mut input, &mut next)?
+            } else {
+                0
+            };
+            let last = next_filled == 0;
+            digest.update(&current[..filled]);
+            total += filled as u64;
+            let sealed = cipher
+                .encrypt(
+                    &chunk_nonce(counter, last),
+                    Payload {
+                        msg: &current[..filled],
+                        aad: &[],
+                    },
+                )
+                .map_err(|_| anyhow::anyhow!("seal backup entry {wire}"))?;
+            output.write_all(&sealed)?;
+            if last {
+                break;
+            }
+            std::mem::swap(&mut current, &mut next);
+            filled = next_filled;
+            counter = counter
+                .checked_add(1)
+                .ok_or_else(|| anyhow::anyhow!("backup entry {wire} is too large to seal"))?;
+        }
+        Ok((total, finalize(digest)))
+    }
+
+    fn open_stream(
+        &self,
+        wire: &str,
+        mut input: impl Read,
+        sink: &mut dyn Write,
+    ) -> anyhow::Result<(u64, [u8; 32])> {
+        let cipher = self.file_cipher(wire);
+        let mut digest = blake2b_simd::Params::new().hash_length(32).to_state();
+        let mut current = vec![0u8; CHUNK_BYTES + TAG_BYTES];
+        let mut next = vec![0u8; CHUNK_BYTES + TAG_BYTES];
+        let mut filled = read_full(&mut input, &mut current)?;
+        let mut total = 0u64;
+        let mut counter = 0u64;
+        loop {
+            let next_filled = if filled == current.len() {
+                read_full(&mut input, &mut next)?
+            } else {
+                0
+            };
+            let last = next_filled == 0;
+            let plain = cipher
+                .decrypt(
+                    &chunk_nonce(counter, last),
+                    Payload {
+                        msg: &current[..filled],
+                        aad: &[],
+                    },
+                )
+                .map_err(|_| anyhow::anyhow!("backup entry {wire} failed authentication"))?;
+            digest.update(&plain);
+            total += plain.len() as u64;
+            sink.write_all(&plain)?;
+            if last {
+                break;
+            }
+            std::mem::swap(&mut current, &mut next);
+            filled = next_filled;
+            counter = counter
+                .checked_add(1)
+                .ok_or_else(|| anyhow::anyhow!("backup entry {wire} is too large to open"))?;
+        }
+        Ok((total, finalize(digest)))
+    }
+}
+
+/// Create a new X25519 backup identity, returning `(secret, recipient)`.
+pub fn generate_identity() -> anyhow::Result<([u8; 32], [u8; 32])> {
+    let secret = x25519_dalek::StaticSecret::from(random_bytes::<32>()?);
+    let public = x25519_dalek::PublicKey::from(&secret);
+    Ok((secret.to_bytes(), public.to_bytes()))
+}
+
+/// Identity files hold the secret as one hex line; `#` lines are comments.
+pub fn read_identity(path: &Path) -> anyhow::Result<[u8; 32]> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| anyhow::anyhow!("read backup identity {}: {error}", path.display()))?;
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .ok_or_else(|| anyhow::anyhow!("backup identity {} is empty", path.display()))?;
+    decode_hex(line, "backup identity")
+}
+
+pub fn parse_recipient(recipient: &str) -> anyhow::Result<[u8; 32]> {
+    decode_hex(recipient, "backup recipient")
+}
+
+fn read_passphrase(variable: &str) -> anyhow::Result<Vec<u8>> {
+    let passphrase = std::env::var(variable)
+        .map_err(|_| anyhow::anyhow!("passphrase variable ${variable} is not set"))?;
+    if passphrase.is_empty() {
+        anyhow::bail!("passphrase variable ${variable} is empty");
+    }
+    Ok(passphrase.into_bytes())
+}
+
+fn recipient_key(
+    ephemeral: &x25519_dalek::StaticSecret,
+    ephemeral_public: &x25519_dalek::PublicKey,
+    recipient: &[u8; 32],
+) -> anyhow::Result<[u8; 32]> {
+    let recipient = x25519_dalek::PublicKey::from(*recipient);
+    let shared = ephemeral.diffie_hellman(&recipient);
+    if !shared.was_contributory() {
+        anyhow::bail!("backup recipient is a low-order X25519 point");
+    }
+    Ok(vos::crypto::blake2b_hash::<32>(
+        b"vosx/space-backup/recipient/v1",
+        &[
+            shared.as_bytes(),
+            ephemeral_public.as_bytes(),
+            recipient.as_bytes(),
+        ],
+    ))
+}
+
+fn passphrase_key(
+    passphrase: &[u8],
+    salt: &[u8; 16],
+    memory_kib: u32,
+    iterations: u32,
+    lanes: u32,
+) -> anyhow::Result<[u8; 32]> {
+    let params = argon2::Params::new(memory_kib, iterations, lanes, Some(32))
+        .map_err(|error| anyhow::anyhow!("backup passphrase parameters: {error}"))?;
+    let mut key = [0u8; 32];
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .hash_password_into(passphrase, salt, &mut key)
+        .map_err(|error| anyhow::anyhow!("derive backup passphrase key: {error}"))?;
+    Ok(key)
+}
+
+/// Each wrap key is used exactly once (fresh ephemeral or fresh salt), so
+/// the all-zero nonce is safe.
+fn wrap_key(wrap: &[u8; 32], key: &[u8; 32]) -> anyhow::Result<Vec<u8>> {
+    ChaCha20Poly1305::new(Key::from_slice(wrap))
+        .encrypt(&Nonce::default(), key.as_slice())
+        .map_err(|_| anyhow::anyhow!("wrap backup key"))
+}
+
+fn unwrap_key(wrap: &[u8; 32], wrapped: &str) -> anyhow::Result<Option<[u8; 32]>> {
+    let wrapped: [u8; 32 + TAG_BYTES] = decode_hex(wrapped, "wrapped backup key")?;
+    Ok(ChaCha20Poly1305::new(Key::from_slice(wrap))
+        .decrypt(&Nonce::default(), wrapped.as_slice())
+        .ok()
+        .map(|key| key.try_into().expect("32-byte wrapped key")))
+}
+
+fn chunk_nonce(counter: u64, last: bool) -> Nonce {
+    let mut nonce = [0u8; 12];
+    nonce[3..11].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = u8::from(last);
+    *Nonce::from_slice(&nonce)
+}
+
+fn read_full(input: &mut impl Read, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match input.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(filled)
+}
+
+fn finalize(state: blake2b_simd::State) -> [u8; 32] {
+    state
+        .finalize()
+        .as_bytes()
+        .try_into()
+        .expect("32-byte digest")
+}
+
+fn random_bytes<const N: usize>() -> anyhow::Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|error| anyhow::anyhow!("OS entropy for backup encryption: {error}"))?;
+    Ok(bytes)
+}
+
+fn decode_hex<const N: usize>(value: &str, what: &str) -> anyhow::Result<[u8; N]> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("{what} must be {N} bytes of hex"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunked_stream_rejects_truncation_and_path_swaps() {
+        let key = ArchiveKey::generate().unwrap();
+        for length in [0, 1, CHUNK_BYTES, CHUNK_BYTES + 1, 2 * CHUNK_BYTES] {
+            let plain = (0..length).map(|byte| byte as u8).collect::<Vec<_>>();
+            let sealed = key.seal_bytes("data/a", &plain).unwrap();
+            assert_eq!(key.open_bytes("data/a", &sealed).unwrap(), plain);
+            assert!(key.open_bytes("data/b", &sealed).is_err());
+            if length > CHUNK_BYTES {
+                // Dropping the final chunk leaves a well-formed but
+                // non-final prefix.
+                assert!(
+                    key.open_bytes("data/a", &sealed[..CHUNK_BYTES + TAG_BYTES])
+                        .is_err()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn archive_key_unwraps_only_with_a_matching_credential() {
+        let key = ArchiveKey::generate().unwrap();
+        let (secret, recipient) = generate_identity().unwrap();
+        let header = key.header(&Sealing::Recipients(vec![recipient])).unwrap();
+        let opened = ArchiveKey::open(
+            &header,
+            &Unlock {
+                identity: Some(secret),
+                ..Unlock::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(opened.0, key.0);
+        let (other, _) = generate_identity().unwrap();
+        assert!(
+            ArchiveKey::open(
+                &header,
+                &Unlock {
+                    identity: Some(other),
+                    ..Unlock::default()
+                },
+            )
+            .is_err()
+        );
+        assert!(ArchiveKey::open(&header, &Unlock::default()).is_err());
+    }
+}
@@ -17,13 +17,15 @@ use std::str::FromStr;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+use anyhow::Context;
 use vos::abi::service::ServiceId;
 use vos::node::VosNode;
-use vos::registry::{AgentRow, MemberRow, ProgramRow, RegistryRef, Status};
+use vos::registry::{AgentRow, MemberRow, ProgramRow, ProvenanceRow, RegistryRef, Status};
 
 use crate::commands::space::common::instance_service_id;
 use crate::commands::space::endpoint;
 use crate::commands::space::op_sign::op_auth;
+use crate::signer::Signer;
 use crate::spaces_index::{self, SpaceEntry};
 
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
@@ -32,6 +34,19 @@ const INVOKE_TIMEOUT_DEFAULT: Duration = Duration::from_secs(10);
 /// decision commit before the Local target executes. Match the libp2p
 /// request-response budget unless the operator supplied an explicit override.
 const ROLE_AUTHORIZED_INVOKE_TIMEOUT_DEFAULT: Duration = Duration::from_secs(300);
+/// An operator v2 upgrade waits for the Raft barrier, its commit, and every
+/// voter's apply before the daemon answers.
+const UPGRADE_V2_TIMEOUT: Duration = Duration::from_secs(300);
+/// An operator voter removal waits for private-ingress quiescence, the joint
+/// and final configuration commits, and every remaining voter's confirmation.
+const REMOVE_VOTER_TIMEOUT: Duration = Duration::from_secs(120);
+/// An online snapshot copies every database and image of the space on the
+/// daemon's side; match the libp2p request-response budget.
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The daemon asks every Raft voter and CRDT peer it knows before it
+/// answers `__doctor`, waiting up to a second for each.
+const DOCTOR_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Resolve the per-invoke timeout, honouring an env override.
 /// `VOSX_INVOKE_TIMEOUT_MS` lets the e2e suite shorten the wait
@@ -63,11 +78,13 @@ fn invoke_timeout_for_policy(policy: Option<&vos::v2::MethodPolicyV2>) -> Durati
 
 pub struct DaemonClient {
     node: VosNode,
-    /// The operator's libp2p identity key — signs the `auth`
-    /// blob on every gated registry mutation. The same key drives the
-    /// libp2p dial below, so the daemon sees a `Caller::Peer` whose
-    /// role it can check AND a signature its registry actor verifies.
-    signer: libp2p::identity::Keypair,
+    /// Signs the `auth` blob on every gated registry mutation. By
+    /// default the operator's libp2p identity key, which also drives the
+    /// dial, so the daemon sees a `Caller::Peer` whose role it can check
+    /// AND a signature its registry actor verifies. With `--signer` /
+    /// `VOSX_SIGNER` it is an external key (see [`crate::signer`]) and
+    /// only the signature carries its authority.
+    signer: Box<dyn Signer>,
     /// Cached so command handlers can access the entry the
     /// query resolved to (e.g. for printing the space name).
     pub entry: SpaceEntry,
@@ -124,7 +141,17 @@ fn encode_v2_invocation(
 }
 
 fn is_reserved_host_operation(method: &str) -> bool {
-    matches!(method, "__stop" | "__describe")
+    matches!(
+        method,
+        "__stop"
+            | "__describe"
+            | "__upgrade_v2"
+            | "__remove_voter"
+            | "__snapshot"
+            | "__metrics"
+            | "__logs"
+            | "__doctor"
+    )
 }
 
 fn upgrade_uses_v2_package(from: &[u8], to: &[u8]) -> bool {
@@ -164,6 +191,22 @@ impl DaemonClient {
     /// dial the running daemon. Errors fast if no daemon is
     /// running or the dial fails.
     pub fn connect(query: &str) -> anyhow::Result<Self> {
+        // Load the operator's persistent libp2p identity from
+        // $XDG_CONFIG_HOME/vosx/identity.key (auto-create on first
+        // call). The daemon recognises the same PeerId across
+        // invocations and consults its members ACL table.
+        let keypair = crate::identity::load_or_create()?;
+        Self::connect_as(query, keypair, crate::signer::open(None)?)
+    }
+
+    /// [`Self::connect`] dialing as `identity` and signing registry ops
+    /// with `signer` — `whoami rotate` runs under a key that isn't yet
+    /// the identity file's.
+    pub fn connect_as(
+        query: &str,
+        keypair: libp2p::identity::Keypair,
+        signer: Box<dyn Signer>,
+    ) -> anyhow::Result<Self> {
         let index = spaces_index::load()?;
         let entry = spaces_index::find(&index, query)?.clone();
         let data_dir = std::path::PathBuf::from(&entry.data_dir);
@@ -201,16 +244,8 @@ impl DaemonClient {
         let bootstrap: libp2p::Multiaddr = libp2p::Multiaddr::from_str(bootstrap_str)
             .map_err(|e| anyhow::anyhow!("bad daemon multiaddr '{bootstrap_str}': {e}"))?;
 
-        // Load the operator's persistent libp2p identity from
-        // $XDG_CONFIG_HOME/vosx/identity.key (auto-create on first
-        // call). The daemon recognises the same PeerId across
-        // invocations and consults its members ACL table.
-        let keypair = crate::identity::load_or_create()?;
         let peer_id = libp2p::PeerId::from(keypair.public());
         let local_prefix = vos::network::derive_node_prefix(&peer_id);
-        // Retain the key for signing registry mutations; the clone
-        // below is consumed by the libp2p stack.
-        let signer = keypair.clone();
 
         let net = vos::network::Network::start(vos::network::NetworkConfig {
             keypair,
@@ -222,6 +257,9 @@ impl DaemonClient {
             // avoids spurious "outgoing connection failed" logs
             // when unrelated libp2p apps are on the LAN.
             auto_dial_mdns: false,
+            discovery_keys: Vec::new(),
+            relays: Vec::new(),
+            relay_server: false,
         });
 
         let mut node = VosNode::with_prefix(local_prefix);
@@ -265,6 +303,26 @@ impl DaemonClient {
     /// inside `f` still tears down the libp2p peer cleanly
     /// rather than leaking the network thread.
     pub fn with_connect<T, F>(query: &str, f: F) -> anyhow::Result<T>
+    where
+        F: FnOnce(&Self) -> anyhow::Result<T>,
+    {
+        Self::with_client(Self::connect(query)?, f)
+    }
+
+    /// [`Self::with_connect`] over [`Self::connect_as`].
+    pub fn with_connect_as<T, F>(
+        query: &str,
+        keypair: libp2p::identity::Keypair,
+        signer: Box<dyn Signer>,
+        f: F,
+    ) -> anyhow::Result<T>
+    where
+        F: FnOnce(&Self) -> anyhow::Result<T>,
+    {
+        Self::with_client(Self::connect_as(query, keypair, signer)?, f)
+    }
+
+    fn with_client<T, F>(client: Self, f: F) -> anyhow::Result<T>
     where
         F: FnOnce(&Self) -> anyhow::Result<T>,
     {
@@ -276,7 +334,7 @@ impl DaemonClient {
                 }
             }
         }
-        let guard = Guard(Some(Self::connect(query)?));
+        let guard = Guard(Some(client));
         let client = guard
             .0
             .as_ref()
@@ -305,6 +363,12 @@ impl DaemonClient {
         self.daemon_prefix
     }
 
+    /// This client's own node prefix. The daemon counts the CLI among
+    /// its connected peers while a command runs.
+    pub fn local_prefix(&self) -> u16 {
+        self.node.network().map_or(0, |net| net.local_prefix())
+    }
+
     /// Resolve a user-supplied target string to a daemon-side
     /// `ServiceId`. Four forms supported, in lookup order:
     ///
@@ -364,13 +428,28 @@ impl DaemonClient {
         target: ServiceId,
         msg: &vos::value::Msg,
     ) -> anyhow::Result<vos::value::Value> {
+        let reply = self.invoke_dyn_bytes(target, msg)?;
+        if reply.is_empty() {
+            return Ok(vos::value::Value::Unit);
+        }
+        Ok(vos::Decode::decode(&reply))
+    }
+
+    /// [`Self::invoke_dyn`] without the decode: the raw reply bytes, under
+    /// the same default timeout, for callers that must tell the forbidden
+    /// envelope apart from a reply.
+    pub fn invoke_dyn_bytes(
+        &self,
+        target: ServiceId,
+        msg: &vos::value::Msg,
+    ) -> anyhow::Result<Vec<u8>> {
         let timeout = self
             .v2_targets
             .lock()
             .ok()
             .and_then(|targets| targets.get(&target.0).cloned())
             .and_then(|target| target.methods.get(&msg.name).cloned());
-        self.invoke_dyn_with_timeout(target, msg, invoke_timeout_for_policy(timeout.as_ref()))
+        self.invoke_dyn_bytes_with_timeout(target, msg, invoke_timeout_for_policy(timeout.as_ref()))
     }
 
     /// Like [`Self::invoke_dyn`] but with an explicit per-call timeout, for the
@@ -727,7 +806,7 @@ impl DaemonClient {
             })?;
         if upgrade_uses_v2_package(&from_artifact, &to_artifact) {
             anyhow::bail!(
-                "signed v2 package upgrades are not catalog mutations; guest-owned UpgradeActor support must land before this installation can be upgraded",
+                "signed v2 package upgrades are not catalog mutations; use `vosx space upgrade-v2`, which drives the guest-owned UpgradeActor transition",
             );
         }
         vos::block_on(self.registry().upgrade(
@@ -742,6 +821,144 @@ impl DaemonClient {
         .map_err(|e| anyhow::anyhow!("registry.upgrade(): {e}"))
     }
 
+    /// Whether `instance_name` runs from a signed v2 package — the only
+    /// kind of instance [`Self::upgrade_v2`] can replace.
+    pub fn is_v2_instance(&self, instance_name: &str) -> anyhow::Result<bool> {
+        let target = self.resolve_target(instance_name)?;
+        Ok(self.v2_targets.lock().unwrap().contains_key(&target.0))
+    }
+
+    /// Ask the daemon hosting `instance_name`'s v2 root to replace the root
+    /// actor with the signed `package` through guest `UpgradeActor`. Returns
+    /// the daemon's JSON report of the guest-owned post-state; the daemon
+    /// only answers once every Raft voter has applied the transition.
+    pub fn upgrade_v2(&self, instance_name: &str, package: Vec<u8>) -> anyhow::Result<String> {
+        let target = self.resolve_target(instance_name)?;
+        if !self.v2_targets.lock().unwrap().contains_key(&target.0) {
+            anyhow::bail!(
+                "'{instance_name}' is not installed from a signed v2 package cached on this machine"
+            );
+        }
+        let reply = self.invoke_dyn_bytes_with_timeout(
+            target,
+            &vos::value::Msg::new("__upgrade_v2").with("package", package),
+            UPGRADE_V2_TIMEOUT,
+        )?;
+        if reply.len() == 5 && reply[0] == vos::STATUS_FORBIDDEN && reply[1..] == [0, 0, 0, 0] {
+            anyhow::bail!(
+                "permission denied: only the daemon's own operator identity with the admin role may upgrade v2 actors"
+            );
+        }
+        match vos::Decode::try_decode(&reply) {
+            Some(vos::value::Value::Str(report)) => Ok(report),
+            _ => anyhow::bail!("daemon did not host a v2 root for '{instance_name}'"),
+        }
+    }
+
+    /// Ask the connected daemon, which must lead `instance_name`'s Raft
+    /// group, to drop `prefix` from that group's configuration. Returns the
+    /// daemon's JSON report (`error`, or the confirmed final members).
+    pub fn remove_voter(&self, instance_name: &str, prefix: u16) -> anyhow::Result<String> {
+        let target = self.resolve_target(instance_name)?;
+        let reply = self.invoke_dyn_bytes_with_timeout(
+            target,
+            &vos::value::Msg::new("__remove_voter").with("prefix", u32::from(prefix)),
+            REMOVE_VOTER_TIMEOUT,
+        )?;
+        if reply.len() == 5 && reply[0] == vos::STATUS_FORBIDDEN && reply[1..] == [0, 0, 0, 0] {
+            anyhow::bail!(
+                "permission denied: only the daemon's own operator identity with the admin role may remove voters"
+            );
+        }
+        match vos::Decode::try_decode(&reply) {
+            Some(vos::value::Value::Str(report)) => Ok(report),
+            _ => anyhow::bail!("daemon did not answer a voter removal for '{instance_name}'"),
+        }
+    }
+
+    /// Ask the connected daemon to copy a point-in-time image of its space
+    /// data directory into `output`, a fresh directory on the daemon's host.
+    /// Returns the daemon's JSON report (`error`, or what it copied).
+    pub fn snapshot(&self, output: &std::path::Path) -> anyhow::Result<String> {
+        let output = output
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("snapshot path {} is not UTF-8", output.display()))?;
+        let reply = self.invoke_dyn_bytes_with_timeout(
+            self.registry_id(),
+            &vos::value::Msg::new("__snapshot").with("output", output),
+            SNAPSHOT_TIMEOUT,
+        )?;
+        if reply.len() == 5 && reply[0] == vos::STATUS_FORBIDDEN && reply[1..] == [0, 0, 0, 0] {
+            anyhow::bail!(
+                "permission denied: only the daemon's own operator identity with the admin role may take online backups"
+            );
+        }
+        match vos::Decode::try_decode(&reply) {
+            Some(vos::value::Value::Str(report)) => Ok(report),
+            _ => anyhow::bail!("daemon did not answer an online snapshot"),
+        }
+    }
+
+    /// The daemon's Prometheus text exposition — the same text its
+    /// `local.toml` `metrics` exporter serves. Member-gated.
+    pub fn metrics(&self) -> anyhow::Result<String> {
+        let reply = self.invoke_dyn_bytes_with_timeout(
+            self.registry_id(),
+            &vos::value::Msg::new("__metrics"),
+            invoke_timeout(),
+        )?;
+        if reply.len() == 5 && reply[0] == vos::STATUS_FORBIDDEN && reply[1..] == [0, 0, 0, 0] {
+            anyhow::bail!("permission denied: only space members may read the daemon's metrics");
+        }
+        match vos::Decode::try_decode(&reply) {
+            Some(vos::value::Value::Str(text)) => Ok(text),
+            _ => anyhow::bail!("daemon did not answer a metrics scrape"),
+        }
+    }
+
+    /// One page of the daemon's buffered guest logs, as the JSON the
+    /// `__logs` op answers with: lines after the `after` cursor and no
+    /// older than `since_ms`, for one agent or (`None`) all. Requires the
+    /// DEVELOPER role or above.
+    pub fn logs(&self, agent: Option<&str>, after: u64, since_ms: u64) -> anyhow::Result<String> {
+        let reply = self.invoke_dyn_bytes_with_timeout(
+            self.registry_id(),
+            &vos::value::Msg::new("__logs")
+                .with("agent", agent.unwrap_or_default())
+                .with("after", after)
+                .with("since_ms", since_ms),
+            invoke_timeout(),
+        )?;
+        if reply.len() == 5 && reply[0] == vos::STATUS_FORBIDDEN && reply[1..] == [0, 0, 0, 0] {
+            anyhow::bail!("permission denied: reading actor logs requires the developer role");
+        }
+        match vos::Decode::try_decode(&reply) {
+            Some(vos::value::Value::Str(json)) => Ok(json),
+            _ => anyhow::bail!("daemon did not answer a logs request"),
+        }
+    }
+
+    /// The daemon's mesh health report for `space doctor`, as the JSON the
+    /// `__doctor` op answers with: connected peers, every hosted Raft group
+    /// as each voter reports it, and CRDT heads beside its peers'.
+    /// Member-gated.
+    pub fn doctor(&self) -> anyhow::Result<String> {
+        let reply = self.invoke_dyn_bytes_with_timeout(
+            self.registry_id(),
+            &vos::value::Msg::new("__doctor"),
+            DOCTOR_TIMEOUT,
+        )?;
+        if reply.len() == 5 && reply[0] == vos::STATUS_FORBIDDEN && reply[1..] == [0, 0, 0, 0] {
+            anyhow::bail!(
+                "permission denied: only space members may run the daemon's health checks"
+            );
+        }
+        match vos::Decode::try_decode(&reply) {
+            Some(vos::value::Value::Str(json)) => Ok(json),
+            _ => anyhow::bail!("daemon did not answer a health check"),
+        }
+    }
+
     pub fn uninstall(&self, instance_name: String) -> anyhow::Result<Status> {
         vos::block_on(
             self.registry()
@@ -750,9 +967,52 @@ impl DaemonClient {
         .map_err(|e| anyhow::anyhow!("registry.uninstall(): {e}"))
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_provenance(
+        &self,
+        instance_name: String,
+        source_space: Vec<u8>,
+        source_instance: String,
+        source_replication_id: Vec<u8>,
+        archive_digest: Vec<u8>,
+        exporter: Vec<u8>,
+    ) -> anyhow::Result<Status> {
+        let auth = op_auth(
+            self.signer.as_ref(),
+            "record_provenance",
+            &[
+                instance_name.as_bytes(),
+                &source_space,
+                source_instance.as_bytes(),
+                &source_replication_id,
+                &archive_digest,
+                &exporter,
+            ],
+        )?;
+        vos::block_on(self.registry().record_provenance(
+            &mut &self.node,
+            instance_name,
+            source_space,
+            source_instance,
+            source_replication_id,
+            archive_digest,
+            exporter,
+            auth,
+        ))
+        .map_err(|e| anyhow::anyhow!("registry.record_provenance(): {e}"))
+    }
+
+    pub fn provenance(&self, instance_name: &str) -> anyhow::Result<Option<ProvenanceRow>> {
+        vos::block_on(
+            self.registry()
+                .provenance(&mut &self.node, instance_name.to_string()),
+        )
+        .map_err(|e| anyhow::anyhow!("registry.provenance('{instance_name}'): {e}"))
+    }
+
     pub fn add_node(&self, prefix: u32, peer_id: Vec<u8>, role: u8) -> anyhow::Result<Status> {
         let auth = op_auth(
-            &self.signer,
+            self.signer.as_ref(),
             "add_node",
             &[&prefix.to_le_bytes(), &peer_id, &[role]],
         )?;
@@ -764,7 +1024,11 @@ impl DaemonClient {
     }
 
     pub fn remove_node(&self, prefix: u32) -> anyhow::Result<Status> {
-        let auth = op_auth(&self.signer, "remove_node", &[&prefix.to_le_bytes()])?;
+        let auth = op_auth(
+            self.signer.as_ref(),
+            "remove_node",
+            &[&prefix.to_le_bytes()],
+        )?;
         vos::block_on(self.registry().remove_node(&mut &self.node, prefix, auth))
             .map_err(|e| anyhow::anyhow!("registry.remove_node(): {e}"))
     }
@@ -776,7 +1040,7 @@ impl DaemonClient {
         proof_data: Vec<u8>,
     ) -> anyhow::Result<Status> {
         let auth = op_auth(
-            &self.signer,
+            self.signer.as_ref(),
             "add_identity",
             &[&public_key, &[proof_kind], &proof_data],
         )?;
@@ -791,7 +1055,7 @@ impl DaemonClient {
     }
 
     pub fn remove_identity(&self, public_key: Vec<u8>) -> anyhow::Result<Status> {
-        let auth = op_auth(&self.signer, "remove_identity", &[&public_key])?;
+        let auth = op_auth(self.signer.as_ref(), "remove_identity", &[&public_key])?;
         vos::block_on(
             self.registry()
                 .remove_identity(&mut &self.node, public_key, auth),
@@ -812,7 +1076,7 @@ impl DaemonClient {
         let epoch = self.peer_epoch(peer_id.clone())? + 1;
         let status = if let Some(authority) = authority {
             let auth = op_auth(
-                &self.signer,
+                self.signer.as_ref(),
                 "grant_role_v2",
                 &[&peer_id, &[role], &epoch.to_le_bytes(), &authority],
             )?;
@@ -827,7 +1091,7 @@ impl DaemonClient {
             .map_err(|e| anyhow::anyhow!("registry.grant_role_v2(): {e}"))?
         } else {
             let auth = op_auth(
-                &self.signer,
+                self.signer.as_ref(),
                 "grant_role",
                 &[&peer_id, &[role], &epoch.to_le_bytes()],
             )?;
@@ -859,7 +1123,7 @@ impl DaemonClient {
         let epoch = self.peer_epoch(peer_id.clone())? + 1;
         let status = if let Some(authority) = authority {
             let auth = op_auth(
-                &self.signer,
+                self.signer.as_ref(),
                 "revoke_role_v2",
                 &[&peer_id, &epoch.to_le_bytes(), &authority],
             )?;
@@ -873,7 +1137,7 @@ impl DaemonClient {
             .map_err(|e| anyhow::anyhow!("registry.revoke_role_v2(): {e}"))?
         } else {
             let auth = op_auth(
-                &self.signer,
+                self.signer.as_ref(),
                 "revoke_role",
                 &[&peer_id, &epoch.to_le_bytes()],
             )?;
@@ -896,6 +1160,49 @@ impl DaemonClient {
         Ok(status)
     }
 
+    /// Move everything this client's signer holds to `successor` and
+    /// revoke the signer, in one registry op both keys sign.
+    pub fn rotate_identity(&self, successor: &dyn Signer) -> anyhow::Result<Status> {
+        if self.role_authority_cutover_id()?.is_some() {
+            anyhow::bail!(
+                "this space is past the v2 role-authority cutover, where only the immutable root \
+                 signs role changes; ask it to grant {} and revoke {}",
+                successor.peer_id(),
+                self.signer.peer_id(),
+            );
+        }
+        let old_peer = self.signer.peer_id().to_bytes();
+        let new_peer = successor.peer_id().to_bytes();
+        let epoch = self
+            .peer_epoch(old_peer.clone())?
+            .max(self.peer_epoch(new_peer.clone())?)
+            + 1;
+        let fields: [&[u8]; 3] = [&old_peer, &new_peer, &epoch.to_le_bytes()];
+        let auth = op_auth(self.signer.as_ref(), "rotate_identity", &fields)?;
+        let new_sig = successor
+            .sign(&vos::registry::canonical_op_bytes(
+                "rotate_identity",
+                &fields,
+            ))
+            .context("sign the rotation with the successor key")?;
+        vos::block_on(self.registry().rotate_identity(
+            &mut &self.node,
+            old_peer,
+            new_peer,
+            epoch,
+            new_sig,
+            auth,
+        ))
+        .map_err(|e| anyhow::anyhow!("registry.rotate_identity(): {e}"))
+    }
+
+    /// Whether `peer_id` is this space's registry root.
+    pub fn is_root(&self, peer_id: &[u8]) -> anyhow::Result<bool> {
+        let root = vos::block_on(self.registry().root(&mut &self.node))
+            .map_err(|error| anyhow::anyhow!("registry.root(): {error}"))?;
+        Ok(!root.is_empty() && root == peer_id)
+    }
+
     pub fn role_authority_cutover_id(&self) -> anyhow::Result<Option<[u8; 32]>> {
         let marker = vos::block_on(self.registry().role_authority_cutover(&mut &self.node))
             .map_err(|error| anyhow::anyhow!("registry.role_authority_cutover(): {error}"))?;
@@ -914,7 +1221,7 @@ impl DaemonClient {
     fn require_v2_role_authority_root(&self) -> anyhow::Result<()> {
         let root = vos::block_on(self.registry().root(&mut &self.node))
             .map_err(|error| anyhow::anyhow!("registry.root(): {error}"))?;
-        let signer = libp2p::PeerId::from(self.signer.public()).to_bytes();
+        let signer = self.signer.peer_id().to_bytes();
         if root.is_empty() || signer != root {
             anyhow::bail!(
                 "v2 role mutations must be signed by this space's immutable root identity"
@@ -940,7 +1247,7 @@ impl DaemonClient {
         let signature = self
             .signer
             .sign(&mutation_bytes)
-            .map_err(|error| anyhow::anyhow!("sign v2 role mutation: {error}"))?;
+            .context("sign v2 role mutation")?;
         if signature.len() != vos::registry::OP_SIG_LEN {
             anyhow::bail!("v2 role authority requires an Ed25519 root identity");
         }
@@ -977,7 +1284,6 @@ impl DaemonClient {
         .map_err(|e| anyhow::anyhow!("registry.actor_epoch(): {e}"))
     }
 
-    #[allow(dead_code)] // exposed for tooling; CLI consumers use `space role list`.
     pub fn peer_role(&self, peer_id: Vec<u8>) -> anyhow::Result<u8> {
         vos::block_on(self.registry().peer_role(&mut &self.node, peer_id))
             .map_err(|e| anyhow::anyhow!("registry.peer_role(): {e}"))
@@ -1021,6 +1327,13 @@ impl DaemonClient {
         Ok(out)
     }
 
+    /// The registry's whole admin audit log, seq 0 onward — what
+    /// `space audit` re-walks with `verify_audit_chain`.
+    pub fn audit(&self) -> anyhow::Result<Vec<vos::registry::AuditRow>> {
+        vos::block_on(self.registry().audit_all(&mut &self.node))
+            .map_err(|e| anyhow::anyhow!("registry.audit(): {e}"))
+    }
+
     /// Flip an invite's `revoked` flag (grow-only, idempotent). The
     /// canonical is just `("revoke_invite", [token_pub])` — no epoch,
     /// unlike grant/revoke_role.
@@ -1032,7 +1345,7 @@ impl DaemonClient {
                 .map_err(|_| anyhow::anyhow!("invite token public key is not 32 bytes"))?;
             self.commit_v2_invite_revocation(token)?;
         }
-        let auth = op_auth(&self.signer, "revoke_invite", &[&token_pub])?;
+        let auth = op_auth(self.signer.as_ref(), "revoke_invite", &[&token_pub])?;
         vos::block_on(
             self.registry()
                 .revoke_invite(&mut &self.node, token_pub, auth),
@@ -1046,12 +1359,12 @@ impl DaemonClient {
         let revocation = vos::v2::RoleAuthorityInviteRevocationV2 {
             space: self.v2_space_id()?,
             token_pub,
-            admin_peer_id: libp2p::PeerId::from(self.signer.public()).to_bytes(),
+            admin_peer_id: self.signer.peer_id().to_bytes(),
         };
         let signature = self
             .signer
             .sign(&revocation.encode())
-            .map_err(|error| anyhow::anyhow!("sign v2 invite revocation: {error}"))?;
+            .context("sign v2 invite revocation")?;
         if signature.len() != vos::registry::OP_SIG_LEN {
             anyhow::bail!("v2 role authority requires an Ed25519 admin identity");
         }
@@ -1086,7 +1399,7 @@ impl DaemonClient {
         }
         let epoch = self.actor_epoch(peer_id.clone(), agent_name.clone())? + 1;
         let auth = op_auth(
-            &self.signer,
+            self.signer.as_ref(),
             "grant_actor_role",
             &[
                 &peer_id,
@@ -1118,7 +1431,7 @@ impl DaemonClient {
         }
         let epoch = self.actor_epoch(peer_id.clone(), agent_name.clone())? + 1;
         let auth = op_auth(
-            &self.signer,
+            self.signer.as_ref(),
             "revoke_actor_role",
             &[&peer_id, agent_name.as_bytes(), &epoch.to_le_bytes()],
         )?;
@@ -1236,6 +1549,12 @@ mod tests {
     fn reserved_lifecycle_operations_bypass_actor_package_dispatch() {
         assert!(is_reserved_host_operation("__stop"));
         assert!(is_reserved_host_operation("__describe"));
+        assert!(is_reserved_host_operation("__upgrade_v2"));
+        assert!(is_reserved_host_operation("__remove_voter"));
+        assert!(is_reserved_host_operation("__snapshot"));
+        assert!(is_reserved_host_operation("__metrics"));
+        assert!(is_reserved_host_operation("__logs"));
+        assert!(is_reserved_host_operation("__doctor"));
         assert!(!is_reserved_host_operation("stop"));
         assert!(!is_reserved_host_operation("value"));
     }
@@ -207,6 +207,34 @@ pub fn auto_replication_id(
     )
 }
 
+/// `replication_id` for an agent installed by `space agent-import`.
+/// Bound to the signed manifest digest of the imported archive as well as
+/// the install identity, so the import never joins the source agent's
+/// replication group or takes the id a direct `install` of the same name
+/// would use, and re-importing one archive under one name is refused by
+/// the registry's replication-id tombstone.
+pub fn imported_replication_id(
+    space_id: &[u8; 32],
+    instance_name: &str,
+    program_hash: &[u8; 32],
+    archive_digest: &[u8; 32],
+) -> [u8; 32] {
+    vos::crypto::blake2b_hash(
+        b"vos-replication-id/import/v1",
+        &[
+            space_id,
+            &[0u8],
+            instance_name.as_bytes(),
+            &[0u8],
+            program_hash,
+            archive_digest,
+        ],
+    )
+}
+
+/// Registry-stored consistency of a Raft-replicated agent.
+pub const RAFT_CONSISTENCY: u8 = 3;
+
 /// Render a registry-stored consistency u8 as the canonical
 /// CLI string. Inverse of `parse_consistency`.
 pub fn consistency_name(c: u8) -> &'static str {
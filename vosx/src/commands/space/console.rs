@@ -0,0 +1,176 @@
+//! `space console` — the sandboxed nushell console from `vos-shell`,
+//! driving a running daemon.
+//!
+//! Installed agents and extensions become commands (`counter add a=2`),
+//! composed with nu's pipelines, variables and control flow but with no
+//! filesystem, network or external commands. Every call goes through
+//! [`DaemonClient`] as the operator's identity, so the daemon's auth
+//! gate applies exactly as it does to `vosx <agent> <method>`.
+//!
+//! Interactive use opens the `vos-shell` TUI, with tab completion drawn
+//! from each agent's `.vos_meta` schema. `--script file.nu` evaluates a
+//! file instead, prints its result and exits non-zero if it fails — the
+//! automation form.
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use vos::abi::service::ServiceId;
+use vos::value::{Msg, Value};
+use vos_shell::{AgentInfo, BackendError, ConsoleEngine, SpaceClient, is_forbidden_envelope};
+
+use crate::commands::space::client::DaemonClient;
+
+pub struct Args {
+    pub space: String,
+    pub script: Option<PathBuf>,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    // Read the script before dialling so a typo'd path fails fast.
+    let script = match &args.script {
+        Some(path) => Some(
+            std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("read script {}: {e}", path.display()))?,
+        ),
+        None if !std::io::stdin().is_terminal() => {
+            anyhow::bail!("the console needs a terminal; use `--script <file.nu>` to run a script")
+        }
+        None => None,
+    };
+
+    let client = DaemonClient::connect(&args.space)?;
+    let label = client.entry.name.clone();
+    let backend = Arc::new(DaemonClientBackend::new(client));
+    let mut engine =
+        ConsoleEngine::new(backend).map_err(|e| anyhow::anyhow!("open console: {e}"))?;
+
+    let Some(script) = script else {
+        return vos_shell::run_tui(engine, &label);
+    };
+    let result = engine.eval(&script);
+    if result.is_error {
+        anyhow::bail!("{}", result.output);
+    }
+    if !result.output.is_empty() {
+        println!("{}", result.output);
+    }
+    Ok(())
+}
+
+/// [`SpaceClient`] over the local libp2p [`DaemonClient`]. The client's
+/// node isn't `Sync`, so calls take turns; the console issues one at a
+/// time anyway.
+struct DaemonClientBackend {
+    /// `None` once dropped, after the peer has been shut down.
+    client: Mutex<Option<DaemonClient>>,
+}
+
+impl DaemonClientBackend {
+    fn new(client: DaemonClient) -> Self {
+        Self {
+            client: Mutex::new(Some(client)),
+        }
+    }
+
+    fn with_client<T>(
+        &self,
+        f: impl FnOnce(&DaemonClient) -> anyhow::Result<T>,
+    ) -> Result<T, BackendError> {
+        let guard = self.client.lock().unwrap_or_else(PoisonError::into_inner);
+        let client = guard.as_ref().ok_or(BackendError::Unreachable)?;
+        f(client).map_err(backend_error)
+    }
+}
+
+impl Drop for DaemonClientBackend {
+    fn drop(&mut self) {
+        let slot = self
+            .client
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(client) = slot.take() {
+            let _ = client.shutdown();
+        }
+    }
+}
+
+impl SpaceClient for DaemonClientBackend {
+    fn list_agents(&self) -> Result<Vec<AgentInfo>, BackendError> {
+        self.with_client(|client| {
+            let mut agents: Vec<AgentInfo> = client
+                .agents()?
+                .into_iter()
+                .map(|agent| AgentInfo {
+                    instance_name: agent.instance_name,
+                    program_name: agent.program_name,
+                })
+                .collect();
+            // Extensions aren't registry agents; the endpoint file names
+            // the ones this daemon loaded.
+            agents.extend(client.endpoint.extensions.iter().map(|ext| AgentInfo {
+                instance_name: ext.name.clone(),
+                program_name: ext.name.clone(),
+            }));
+            Ok(agents)
+        })
+    }
+
+    fn resolve_target(&self, name: &str) -> Result<ServiceId, BackendError> {
+        self.with_client(|client| client.resolve_target(name))
+    }
+
+    fn raw_meta(&self, name: &str) -> Result<Vec<u8>, BackendError> {
+        self.with_client(|client| client.meta_for_instance(name))
+    }
+
+    fn invoke(&self, target: ServiceId, msg: &Msg) -> Result<Value, BackendError> {
+        let reply = self.with_client(|client| client.invoke_dyn_bytes(target, msg))?;
+        if is_forbidden_envelope(&reply) {
+            return Err(BackendError::Forbidden);
+        }
+        if reply.is_empty() {
+            return Ok(Value::Unit);
+        }
+        Ok(vos::Decode::decode(&reply))
+    }
+}
+
+/// Sort a [`DaemonClient`] error into the console's categories. The
+/// client reports these as text, so match the phrases it uses.
+fn backend_error(e: anyhow::Error) -> BackendError {
+    let text = format!("{e:#}");
+    if text.contains("didn't reply within") {
+        BackendError::Unreachable
+    } else if let Some(rest) = text.strip_prefix("no agent or extension named '") {
+        BackendError::NotFound(rest.split('\'').next().unwrap_or_default().to_string())
+    } else {
+        BackendError::Other(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_errors_map_onto_backend_errors() {
+        assert_eq!(
+            backend_error(anyhow::anyhow!(
+                "daemon at 0x00010000 didn't reply within 10s (target unreachable or timed out)"
+            )),
+            BackendError::Unreachable
+        );
+        assert_eq!(
+            backend_error(anyhow::anyhow!(
+                "no agent or extension named 'cuonter' is installed in this space"
+            )),
+            BackendError::NotFound("cuonter".into())
+        );
+        assert_eq!(
+            backend_error(anyhow::anyhow!("registry.agents(): decode")),
+            BackendError::Other("registry.agents(): decode".into())
+        );
+    }
+}
@@ -0,0 +1,695 @@
+//! `space doctor` — end-to-end health checks for one space.
+//!
+//! Runs what the runbooks otherwise have an operator check by hand and
+//! reports each as pass / warn / fail with a suggested fix:
+//!
+//! - the `.endpoint` file (stale files from a crashed daemon are removed);
+//! - daemon reachability;
+//! - connected peers vs the registry's node members, and prefix and
+//!   node-id collisions;
+//! - per Raft group: quorum, leader, and how far each voter trails;
+//! - per CRDT replica: whether its heads match the peers serving it;
+//! - clock skew between this host and the daemon;
+//! - the shared blob cache, re-hashed;
+//! - the production trust socket, when `--trust-socket` names one.
+//!
+//! The mesh checks come from one member-gated `__doctor` round trip: the
+//! daemon asks its voters and peers itself. Exits non-zero if any check
+//! fails; `--format json` prints the whole report as one object.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::space::client::DaemonClient;
+use crate::commands::space::endpoint;
+use crate::{blob_store, output, spaces_index};
+
+/// A voter whose applied index trails the leader's commit by more than
+/// this many entries is reported as lagging.
+const RAFT_LAG_WARN: u64 = 100;
+/// Clock skew (ms) at which the check warns, and at which it fails.
+const SKEW_WARN_MS: u64 = 2_000;
+const SKEW_FAIL_MS: u64 = 30_000;
+
+pub struct Args {
+    pub space: String,
+    /// Production trust sidecar socket to probe, if the daemon uses one.
+    pub trust_socket: Option<PathBuf>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Warn => "warn",
+            Status::Fail => "FAIL",
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct Check {
+    name: String,
+    status: Status,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Check {
+            name: name.into(),
+            status: Status::Pass,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Check {
+            name: name.into(),
+            status: Status::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Check {
+            name: name.into(),
+            status: Status::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    space: &'a str,
+    status: Status,
+    checks: &'a [Check],
+}
+
+/// The daemon's `__doctor` reply.
+#[derive(Deserialize, Debug)]
+struct MeshReport {
+    #[serde(default)]
+    prefix: u16,
+    #[serde(default)]
+    unix_ms: u64,
+    #[serde(default)]
+    peers: Vec<PeerView>,
+    #[serde(default)]
+    collisions: Vec<u16>,
+    #[serde(default)]
+    node_id_collisions: Vec<u32>,
+    #[serde(default)]
+    raft: Vec<RaftGroup>,
+    #[serde(default)]
+    crdt: Vec<CrdtReplica>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PeerView {
+    prefix: u16,
+}
+
+#[derive(Deserialize, Debug)]
+struct RaftGroup {
+    agent: String,
+    members: Vec<u16>,
+    voters: Vec<VoterView>,
+}
+
+#[derive(Deserialize, Debug)]
+struct VoterView {
+    prefix: u16,
+    reachable: bool,
+    #[serde(default)]
+    role: u8,
+    #[serde(default)]
+    term: u64,
+    #[serde(default)]
+    commit: u64,
+    #[serde(default)]
+    applied: u64,
+}
+
+#[derive(Deserialize, Debug)]
+struct CrdtReplica {
+    agent: String,
+    heads: Vec<String>,
+    peers: Vec<PeerHeads>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PeerHeads {
+    prefix: u16,
+    heads: Vec<String>,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    let index = spaces_index::load()?;
+    let entry = spaces_index::find(&index, &args.space)?.clone();
+    let data_dir = PathBuf::from(&entry.data_dir);
+
+    let mut checks = Vec::new();
+    let daemon_expected = check_endpoint(&entry.name, &data_dir, &mut checks);
+    if daemon_expected {
+        match DaemonClient::connect(&args.space) {
+            Ok(client) => {
+                checks.push(Check::pass(
+                    "daemon",
+                    format!(
+                        "reachable (node {:#06x}, pid {})",
+                        client.daemon_prefix(),
+                        client.endpoint.pid
+                    ),
+                ));
+                mesh_checks(&client, &mut checks);
+                let _ = client.shutdown();
+            }
+            Err(e) => checks.push(Check::fail(
+                "daemon",
+                format!("{e:#}"),
+                format!(
+                    "check the daemon's log; restart it with \
+                     `vosx space down {0}` then `vosx space up {0}`",
+                    entry.name
+                ),
+            )),
+        }
+    } else {
+        checks.push(Check::fail(
+            "daemon",
+            "no daemon running for this space",
+            format!("start it with `vosx space up {}`", entry.name),
+        ));
+    }
+    checks.push(check_blob_cache());
+    if let Some(socket) = &args.trust_socket {
+        checks.push(check_trust_socket(socket));
+    }
+
+    let status = checks
+        .iter()
+        .map(|check| check.status)
+        .max()
+        .unwrap_or(Status::Pass);
+    if output::is_json() {
+        output::print_json(&Report {
+            space: &entry.name,
+            status,
+            checks: &checks,
+        });
+    } else {
+        print_checks(&checks);
+    }
+    let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} checks failed", checks.len());
+    }
+    Ok(())
+}
+
+/// Inspect `<data_dir>/.endpoint`, removing it if its daemon is gone.
+/// Returns whether a live daemon should be answering.
+fn check_endpoint(space: &str, data_dir: &Path, checks: &mut Vec<Check>) -> bool {
+    let path = endpoint::path(data_dir);
+    match endpoint::read(data_dir) {
+        Ok(None) => false,
+        Ok(Some(ep)) if !endpoint::is_alive(&ep) => {
+            endpoint::delete(data_dir);
+            checks.push(Check::warn(
+                "endpoint",
+                format!(
+                    "{} was left by pid {}, which is no longer running; removed it",
+                    path.display(),
+                    ep.pid
+                ),
+                format!("the daemon exited uncleanly; `vosx space up {space}` starts a new one"),
+            ));
+            false
+        }
+        Ok(Some(ep)) => {
+            checks.push(Check::pass(
+                "endpoint",
+                format!("pid {} is running", ep.pid),
+            ));
+            true
+        }
+        Err(e) => {
+            checks.push(Check::fail(
+                "endpoint",
+                format!("{} is unreadable: {e:#}", path.display()),
+                format!(
+                    "stop any daemon for the space, delete {} and run `vosx space up {space}`",
+                    path.display()
+                ),
+            ));
+            false
+        }
+    }
+}
+
+/// Everything that needs the daemon: peers, Raft, CRDT and clock checks.
+fn mesh_checks(client: &DaemonClient, checks: &mut Vec<Check>) {
+    let sent_ms = unix_ms_now();
+    let reply = client.doctor();
+    let received_ms = unix_ms_now();
+    let report = match reply.and_then(|json| parse_report(&json)) {
+        Ok(report) => report,
+        Err(e) => {
+            checks.push(Check::fail(
+                "mesh",
+                format!("the daemon's health report is unavailable: {e:#}"),
+                "run as a space member against a daemon of the same vosx version",
+            ));
+            return;
+        }
+    };
+    match client.members() {
+        Ok(members) => {
+            let nodes: Vec<u16> = members
+                .iter()
+                .filter(|m| m.kind == vos::registry::MEMBER_KIND_NODE)
+                .map(|m| m.prefix)
+                .collect();
+            checks.push(check_peers(&report, &nodes, client.local_prefix()));
+        }
+        Err(e) => checks.push(Check::fail(
+            "peers",
+            format!("couldn't read the registry's members: {e:#}"),
+            "re-run once the registry answers; `vosx space members` shows the roster",
+        )),
+    }
+    if !report.collisions.is_empty() {
+        checks.push(Check::fail(
+            "prefix collisions",
+            format!(
+                "node prefixes claimed by more than one peer: {}",
+                prefixes(&report.collisions)
+            ),
+            "agents on the colliding nodes are unreachable from parts of the mesh; \
+             give one of each pair a new identity so it derives a new prefix",
+        ));
+    }
+    if !report.node_id_collisions.is_empty() {
+        let ids: Vec<String> = report
+            .node_id_collisions
+            .iter()
+            .map(|id| format!("{id:#010x}"))
+            .collect();
+        checks.push(Check::fail(
+            "node-id collisions",
+            format!("node ids claimed by more than one peer: {}", ids.join(", ")),
+            "the later claimant was refused at the handshake; give it a new identity",
+        ));
+    }
+    checks.extend(report.raft.iter().map(check_raft));
+    checks.extend(report.crdt.iter().map(check_crdt));
+    checks.push(check_clock(sent_ms, received_ms, report.unix_ms));
+}
+
+fn parse_report(json: &str) -> anyhow::Result<MeshReport> {
+    let report: MeshReport = serde_json::from_str(json)
+        .map_err(|e| anyhow::anyhow!("daemon sent a malformed health report: {e}"))?;
+    if let Some(error) = &report.error {
+        anyhow::bail!("{error}");
+    }
+    Ok(report)
+}
+
+/// Every node member other than the daemon itself should be connected.
+/// `client` is this CLI's own prefix, which the daemon also counts.
+fn check_peers(report: &MeshReport, nodes: &[u16], client: u16) -> Check {
+    let connected: Vec<u16> = report
+        .peers
+        .iter()
+        .map(|peer| peer.prefix)
+        .filter(|&prefix| prefix != client)
+        .collect();
+    let expected: Vec<u16> = nodes
+        .iter()
+        .copied()
+        .filter(|&prefix| prefix != report.prefix)
+        .collect();
+    if expected.is_empty() {
+        return Check::pass(
+            "peers",
+            format!(
+                "no other node members; {} peer(s) connected",
+                connected.len()
+            ),
+        );
+    }
+    let missing: Vec<u16> = expected
+        .iter()
+        .copied()
+        .filter(|prefix| !connected.contains(prefix))
+        .collect();
+    let detail = format!(
+        "{}/{} node members connected",
+        expected.len() - missing.len(),
+        expected.len()
+    );
+    if missing.is_empty() {
+        return Check::pass("peers", detail);
+    }
+    let detail = format!("{detail}; not connected: {}", prefixes(&missing));
+    let fix = "make sure those nodes are up (`vosx space up`) and reachable from this one \
+               (listen addresses, `relays` in local.toml)";
+    if missing.len() == expected.len() {
+        Check::fail("peers", detail, fix)
+    } else {
+        Check::warn("peers", detail, fix)
+    }
+}
+
+/// Quorum, leader and lag for one Raft group as its voters report it.
+fn check_raft(group: &RaftGroup) -> Check {
+    let name = format!("raft {}", group.agent);
+    let reachable: Vec<&VoterView> = group.voters.iter().filter(|v| v.reachable).collect();
+    let answering = group
+        .members
+        .iter()
+        .filter(|p| reachable.iter().any(|v| v.prefix == **p))
+        .count();
+    let quorum = group.members.len() / 2 + 1;
+    let unreachable: Vec<u16> = group
+        .voters
+        .iter()
+        .filter(|v| !v.reachable)
+        .map(|v| v.prefix)
+        .collect();
+    if answering < quorum {
+        return Check::fail(
+            name,
+            format!(
+                "quorum lost: {answering}/{} voters answer, {quorum} needed; unreachable: {}",
+                group.members.len(),
+                prefixes(&unreachable)
+            ),
+            "bring the unreachable voters back up, or retire dead ones with \
+             `vosx space voters <space> remove <prefix>`",
+        );
+    }
+    let leader_role = vos::network::RaftRole::Leader.to_wire();
+    let Some(leader) = reachable
+        .iter()
+        .filter(|v| v.role == leader_role)
+        .max_by_key(|v| v.term)
+    else {
+        return Check::fail(
+            name,
+            format!("no leader among {answering} answering voters"),
+            "an election may be in flight; re-run in a few seconds. If it persists, \
+             check the voters can reach each other",
+        );
+    };
+    let lagging: Vec<String> = reachable
+        .iter()
+        .filter(|v| leader.commit.saturating_sub(v.applied) > RAFT_LAG_WARN)
+        .map(|v| format!("{:#06x} ({})", v.prefix, leader.commit - v.applied))
+        .collect();
+    let detail = format!(
+        "leader {:#06x}, term {}, {answering}/{} voters answer",
+        leader.prefix,
+        leader.term,
+        group.members.len()
+    );
+    if !unreachable.is_empty() {
+        return Check::warn(
+            name,
+            format!("{detail}; unreachable: {}", prefixes(&unreachable)),
+            "the group has quorum but less fault tolerance; bring the unreachable voters back up",
+        );
+    }
+    if !lagging.is_empty() {
+        return Check::warn(
+            name,
+            format!(
+                "{detail}; entries behind the leader's commit: {}",
+                lagging.join(", ")
+            ),
+            "a lagging voter catches up on its own; if the gap keeps growing, \
+             check its load with `vosx space top` on that node",
+        );
+    }
+    Check::pass(name, detail)
+}
+
+/// Whether one CRDT replica's heads match every peer serving the group.
+fn check_crdt(replica: &CrdtReplica) -> Check {
+    let name = format!("crdt {}", replica.agent);
+    if replica.peers.is_empty() {
+        return Check::pass(name, "no connected peer serves this replica");
+    }
+    let mut local = replica.heads.clone();
+    local.sort();
+    let diverged: Vec<u16> = replica
+        .peers
+        .iter()
+        .filter(|peer| {
+            let mut heads = peer.heads.clone();
+            heads.sort();
+            heads != local
+        })
+        .map(|peer| peer.prefix)
+        .collect();
+    if diverged.is_empty() {
+        return Check::pass(
+            name,
+            format!(
+                "{} head(s), identical on {} peer(s)",
+                local.len(),
+                replica.peers.len()
+            ),
+        );
+    }
+    Check::warn(
+        name,
+        format!(
+            "heads differ from {}/{} peer(s): {}",
+            diverged.len(),
+            replica.peers.len(),
+            prefixes(&diverged)
+        ),
+        "replicas converge within a few sync rounds under writes; re-run, and if the \
+         heads stay apart check `vosx space top` for a growing sync backlog",
+    )
+}
+
+/// The daemon read its clock somewhere between `sent_ms` and
+/// `received_ms` of ours, so anything outside that window is skew.
+fn check_clock(sent_ms: u64, received_ms: u64, daemon_ms: u64) -> Check {
+    let skew = if daemon_ms < sent_ms {
+        sent_ms - daemon_ms
+    } else {
+        daemon_ms.saturating_sub(received_ms)
+    };
+    let detail = if skew == 0 {
+        format!(
+            "daemon clock agrees with this host's (within {} ms)",
+            received_ms.saturating_sub(sent_ms)
+        )
+    } else {
+        let direction = if daemon_ms < sent_ms {
+            "behind"
+        } else {
+            "ahead of"
+        };
+        format!("daemon clock is at least {skew} ms {direction} this host's")
+    };
+    let fix = "keep both hosts on NTP (e.g. `timedatectl set-ntp true`); log timestamps, \
+               `--since` and invite expiry all read the wall clock";
+    if skew >= SKEW_FAIL_MS {
+        Check::fail("clock skew", detail, fix)
+    } else if skew >= SKEW_WARN_MS {
+        Check::warn("clock skew", detail, fix)
+    } else {
+        Check::pass("clock skew", detail)
+    }
+}
+
+fn check_blob_cache() -> Check {
+    let dir = blob_store::cache_dir();
+    match blob_store::audit_cache() {
+        Ok((checked, corrupt)) if corrupt.is_empty() => Check::pass(
+            "blob cache",
+            format!("{checked} blob(s) in {} match their hashes", dir.display()),
+        ),
+        Ok((checked, corrupt)) => {
+            let paths: Vec<String> = corrupt
+                .iter()
+                .map(|hash| blob_store::cache_path_for(hash).display().to_string())
+                .collect();
+            Check::fail(
+                "blob cache",
+                format!(
+                    "{}/{checked} blob(s) no longer match their hash: {}",
+                    corrupt.len(),
+                    paths.join(", ")
+                ),
+                "delete the corrupt files; they are fetched again from peers or their source \
+                 on next use",
+            )
+        }
+        Err(e) => Check::fail(
+            "blob cache",
+            format!("couldn't scan {}: {e}", dir.display()),
+            "check the cache directory's permissions",
+        ),
+    }
+}
+
+fn check_trust_socket(socket: &Path) -> Check {
+    use vos::v2::ProductionTrustV2;
+
+    match super::production_trust::SocketProductionTrustV2::open(socket) {
+        Ok(trust) => Check::pass(
+            "trust socket",
+            format!(
+                "{} answers with policy {}",
+                socket.display(),
+                hex::encode(trust.policy_id().0)
+            ),
+        ),
+        Err(e) => Check::fail(
+            "trust socket",
+            format!("{}: {e}", socket.display()),
+            "start the production trust authority, or point --trust-socket at the socket \
+             the daemon was started with",
+        ),
+    }
+}
+
+fn print_checks(checks: &[Check]) {
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for check in checks {
+        println!(
+            "[{}] {:<width$}  {}",
+            check.status.label(),
+            check.name,
+            check.detail
+        );
+        if let Some(fix) = &check.fix {
+            println!("       {:<width$}  fix: {fix}", "");
+        }
+    }
+}
+
+fn prefixes(prefixes: &[u16]) -> String {
+    prefixes
+        .iter()
+        .map(|p| format!("{p:#06x}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn unix_ms_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voter(prefix: u16, role: u8, term: u64, commit: u64, applied: u64) -> VoterView {
+        VoterView {
+            prefix,
+            reachable: true,
+            role,
+            term,
+            commit,
+            applied,
+        }
+    }
+
+    #[test]
+    fn raft_check_reports_quorum_leader_and_lag() {
+        let mut group = RaftGroup {
+            agent: "ledger".into(),
+            members: vec![1, 2, 3],
+            voters: vec![
+                voter(1, 3, 4, 500, 500),
+                voter(2, 0, 4, 500, 499),
+                voter(3, 0, 4, 500, 300),
+            ],
+        };
+        let check = check_raft(&group);
+        assert_eq!(check.status, Status::Warn, "{check:?}");
+        assert!(check.detail.contains("0x0003 (200)"), "{check:?}");
+
+        group.voters[2].applied = 500;
+        assert_eq!(check_raft(&group).status, Status::Pass);
+
+        group.voters[1].reachable = false;
+        assert_eq!(check_raft(&group).status, Status::Warn);
+        group.voters[2].reachable = false;
+        let check = check_raft(&group);
+        assert_eq!(check.status, Status::Fail);
+        assert!(check.detail.contains("quorum lost"), "{check:?}");
+    }
+
+    #[test]
+    fn peers_check_ignores_self_and_client_and_flags_missing_members() {
+        let report = parse_report(
+            r#"{"prefix":1,"unix_ms":0,"peers":[{"prefix":2,"peer_id":"a"},{"prefix":9,"peer_id":"cli"}],
+                "collisions":[],"raft":[],"crdt":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(check_peers(&report, &[1, 2], 9).status, Status::Pass);
+        let check = check_peers(&report, &[1, 2, 3], 9);
+        assert_eq!(check.status, Status::Warn);
+        assert!(check.detail.contains("not connected: 0x0003"), "{check:?}");
+        assert_eq!(check_peers(&report, &[1, 3], 9).status, Status::Fail);
+    }
+
+    #[test]
+    fn crdt_check_compares_head_sets_and_clock_skew_is_bounded_by_round_trip() {
+        let replica = CrdtReplica {
+            agent: "notes".into(),
+            heads: vec!["bb".into(), "aa".into()],
+            peers: vec![
+                PeerHeads {
+                    prefix: 2,
+                    heads: vec!["aa".into(), "bb".into()],
+                },
+                PeerHeads {
+                    prefix: 3,
+                    heads: vec!["aa".into()],
+                },
+            ],
+        };
+        let check = check_crdt(&replica);
+        assert_eq!(check.status, Status::Warn);
+        assert!(check.detail.contains("1/2 peer(s): 0x0003"), "{check:?}");
+
+        assert_eq!(check_clock(1_000, 1_500, 1_200).status, Status::Pass);
+        assert_eq!(check_clock(10_000, 10_100, 7_000).status, Status::Warn);
+        let check = check_clock(10_000, 10_100, 50_100);
+        assert_eq!(check.status, Status::Fail);
+        assert!(check.detail.contains("40000 ms ahead"), "{check:?}");
+    }
+
+    #[test]
+    fn daemon_errors_surface_from_the_report() {
+        let err = parse_report(r#"{"error":"this daemon has no network attached"}"#).unwrap_err();
+        assert!(err.to_string().contains("no network"), "{err}");
+    }
+}
@@ -11,6 +11,9 @@
 //! exit within the grace window (default: 5 seconds). The grace
 //! window covers a daemon that's mid-commit on a slow disk;
 //! once persistence drains, it exits on its own.
+//!
+//! A space hosted by `vosx node up` is detached from its host over
+//! the node's control socket instead; the host keeps running.
 
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
@@ -45,6 +48,12 @@ pub fn run(args: Args) -> anyhow::Result<()> {
         return Ok(());
     }
 
+    // A space hosted by `vosx node up` shares the host's pid, so a
+    // signal would stop every space it runs. Detach just this one.
+    if ep.hosted {
+        return crate::commands::node::detach(&entry.id);
+    }
+
     // SIGTERM first. The daemon's signal handler is the same
     // path Ctrl-C takes — registry persists, sockets close,
     // `.endpoint` removed.
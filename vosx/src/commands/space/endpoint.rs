@@ -38,6 +38,17 @@ pub struct Endpoint {
     /// recipe-less daemons) readable.
     #[serde(default)]
     pub extensions: Vec<ExtensionCaps>,
+    /// Set when a `vosx node up` host runs this space in-process.
+    /// `pid` is then the host's, so `space down` detaches the space
+    /// from it rather than signalling the process.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hosted: bool,
+    /// Set when the daemon runs its v2 roots under the fail-closed
+    /// production trust profile (`--production-trust-socket`).
+    /// Developer loops such as `vosx dev watch` refuse to touch such a
+    /// space.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub production: bool,
 }
 
 /// One service extension's effective relay capabilities, as the
@@ -110,6 +121,8 @@ mod tests {
                     caps: vec![], // deny-all relay
                 },
             ],
+            hosted: false,
+            production: true,
         };
         let s = toml::to_string_pretty(&ep).unwrap();
         let back: Endpoint = toml::from_str(&s).unwrap();
@@ -117,6 +130,7 @@ mod tests {
         assert_eq!(back.extensions[0].name, "dev");
         assert_eq!(back.extensions[0].caps, vec!["space-registry:admin"]);
         assert!(back.extensions[1].caps.is_empty());
+        assert!(back.production);
     }
 
     #[test]
@@ -132,6 +146,8 @@ mod tests {
         "#;
         let ep: Endpoint = toml::from_str(legacy).unwrap();
         assert!(ep.extensions.is_empty());
+        assert!(!ep.hosted);
+        assert!(!ep.production);
         assert_eq!(ep.prefix, 7);
     }
 }
@@ -1,25 +1,31 @@
 //! `space invite` — mint a `vos1…` invite token.
 //!
 //! An invite is a pointer + credential, never policy (decision 3): it
-//! carries the space id, name, bootnodes, a role, an expiry, and an
-//! admin-signed delegated-grant chain. A joiner runs `space up <token>`
-//! and the daemon redeems it against a bootnode, which grants the
-//! joiner's node key the role — what that role may sync/spawn lives in
-//! the registry and can evolve after the token is minted.
+//! carries the space id, name, bootnodes, circuit relays, a role, an
+//! expiry, and an admin-signed delegated-grant chain. A joiner runs
+//! `space up <token>` and the daemon redeems it against a bootnode,
+//! which grants the joiner's node key the role — what that role may
+//! sync/spawn lives in the registry and can evolve after the token is
+//! minted.
 //!
 //! Minting requires the operator to hold ADMIN in the target space
 //! (the delegated-grant chain is admin → token → node). Offline-
 //! redeemable tiers are `member` and `developer`. Admin promotion uses
 //! the explicit online `space role grant` path.
 
+use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use clap::Subcommand;
+use libp2p::multiaddr::Protocol;
 use serde::Serialize;
 use vos::registry::{AUTH_ROLE_ADMIN, AUTH_ROLE_DEVELOPER, AUTH_ROLE_READONLY, Status};
 
 use crate::commands::space::client::DaemonClient;
+use crate::commands::space::endpoint::Endpoint;
+use crate::commands::space::subscriptions;
 use crate::output;
+use crate::spaces_index;
 use crate::token;
 
 #[derive(Subcommand, Debug)]
@@ -49,6 +55,10 @@ pub struct Args {
     /// Bootnode multiaddr(s) to embed. Defaults to the running daemon's
     /// published listen addrs. Mint only.
     pub bootnode: Vec<String>,
+    /// Circuit-relay multiaddr(s) to embed. Defaults to the relays this
+    /// node reserves on, plus the node itself when it serves relay
+    /// reservations. Mint only.
+    pub relay: Vec<String>,
     /// Optional subcommand — bare `space invite <space> …` mints.
     pub command: Option<InviteCommand>,
 }
@@ -60,6 +70,7 @@ struct InviteView<'a> {
     role: &'a str,
     expires_at: u64,
     bootnodes: &'a [String],
+    relays: &'a [String],
 }
 
 /// Map a user-facing role name to its `AUTH_ROLE_*` code (decision 12 —
@@ -79,9 +90,13 @@ pub fn run(args: Args) -> anyhow::Result<()> {
     };
     // The mint flags mean nothing to `list`/`revoke` — refuse them
     // instead of silently ignoring what the user typed.
-    if args.role.is_some() || args.expires.is_some() || !args.bootnode.is_empty() {
+    if args.role.is_some()
+        || args.expires.is_some()
+        || !args.bootnode.is_empty()
+        || !args.relay.is_empty()
+    {
         anyhow::bail!(
-            "--role/--expires/--bootnode only apply when minting — \
+            "--role/--expires/--bootnode/--relay only apply when minting — \
              bare `vosx space invite <space>` mints a token",
         );
     }
@@ -189,6 +204,14 @@ fn mint(args: Args) -> anyhow::Result<()> {
         b.parse::<libp2p::Multiaddr>()
             .map_err(|e| anyhow::anyhow!("bad --bootnode multiaddr '{b}': {e}"))?;
     }
+    for r in &args.relay {
+        let addr = r
+            .parse::<libp2p::Multiaddr>()
+            .map_err(|e| anyhow::anyhow!("bad --relay multiaddr '{r}': {e}"))?;
+        if !matches!(addr.iter().last(), Some(Protocol::P2p(_))) {
+            anyhow::bail!("--relay '{r}' must end in /p2p/<relay peer id>");
+        }
+    }
 
     // Load the operator key that signs the invite (the delegated-grant
     // chain's root). Same identity the daemon sees as `Caller::Peer`.
@@ -229,6 +252,12 @@ fn mint(args: Args) -> anyhow::Result<()> {
             );
         }
 
+        let relays = if args.relay.is_empty() {
+            default_relays(&client.entry, &client.endpoint)
+        } else {
+            args.relay.clone()
+        };
+
         let authority_replication_id = client.role_authority_cutover_id()?.ok_or_else(|| {
             anyhow::anyhow!(
                 "invite minting is disabled until the immutable root completes canonical role-authority cutover; start the root with --service-pvm and retry"
@@ -240,6 +269,7 @@ fn mint(args: Args) -> anyhow::Result<()> {
             space_id,
             client.entry.name.clone(),
             bootnodes.clone(),
+            relays.clone(),
             role,
             expires_at,
             authority_replication_id,
@@ -252,6 +282,7 @@ fn mint(args: Args) -> anyhow::Result<()> {
                 role: role_name,
                 expires_at,
                 bootnodes: &bootnodes,
+                relays: &relays,
             });
         } else {
             println!("{tok}");
@@ -260,6 +291,9 @@ fn mint(args: Args) -> anyhow::Result<()> {
             println!("  role      = {role_name}");
             println!("  expires   = {expires_at} (in {expires})");
             println!("  bootnodes = {}", bootnodes.join(", "));
+            if !relays.is_empty() {
+                println!("  relays    = {}", relays.join(", "));
+            }
             println!();
             println!("redeem with: `vosx space up <token>` (or `vosx space up -` to pipe it in).");
         }
@@ -267,6 +301,26 @@ fn mint(args: Args) -> anyhow::Result<()> {
     })
 }
 
+/// The relays a joiner should reserve on: whatever this node itself
+/// reserves on (`local.toml` `relays`), plus this node's own
+/// non-loopback addresses when it runs as a relay server.
+fn default_relays(entry: &spaces_index::SpaceEntry, endpoint: &Endpoint) -> Vec<String> {
+    let local = subscriptions::load(Path::new(&entry.data_dir)).unwrap_or_default();
+    let mut relays = local.relays;
+    if local.relay_server {
+        for a in &endpoint.multiaddrs {
+            let Ok(addr) = a.parse::<libp2p::Multiaddr>() else {
+                continue;
+            };
+            let own = format!("{a}/p2p/{}", endpoint.peer_id);
+            if vos::network::is_relayable_listen_addr(&addr) && !relays.contains(&own) {
+                relays.push(own);
+            }
+        }
+    }
+    relays
+}
+
 fn role_label(role: u8) -> &'static str {
     match role {
         AUTH_ROLE_ADMIN => "admin",
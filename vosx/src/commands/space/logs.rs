@@ -0,0 +1,147 @@
+//! `space logs` — read the connected daemon's per-agent guest logs.
+//!
+//! The daemon keeps a bounded ring of recent lines per agent: what the
+//! guest wrote through `DEBUG_WRITE` (`println!`, `log::*`), its panic
+//! messages, and one outcome line per dispatch. This pages through them
+//! with the DEVELOPER-gated `__logs` op; `--follow` keeps polling from the
+//! last line seen, and `--since 10m` skips anything older.
+
+use std::time::Duration;
+
+use crate::commands::space::client::DaemonClient;
+use crate::output;
+use serde::{Deserialize, Serialize};
+
+/// How often `--follow` asks the daemon for new lines.
+const FOLLOW_POLL: Duration = Duration::from_millis(500);
+
+pub struct Args {
+    pub space: String,
+    /// Instance name to filter to; `None` reads every agent.
+    pub agent: Option<String>,
+    pub follow: bool,
+    /// Only lines newer than this duration ago (`30s`, `10m`, `2h`).
+    pub since: Option<String>,
+}
+
+/// One `__logs` reply.
+#[derive(Deserialize, Debug, PartialEq)]
+pub(crate) struct LogPage {
+    #[serde(default)]
+    pub(crate) next: u64,
+    #[serde(default)]
+    pub(crate) lines: Vec<LogLine>,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct LogLine {
+    seq: u64,
+    unix_ms: u64,
+    id: u32,
+    agent: String,
+    kind: String,
+    text: String,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    let since_ms = match &args.since {
+        Some(since) => {
+            let window_ms = crate::token::parse_duration(since)?.saturating_mul(1000);
+            unix_ms_now().saturating_sub(window_ms)
+        }
+        None => 0,
+    };
+    DaemonClient::with_connect(&args.space, |client| {
+        let mut after = 0;
+        loop {
+            let page = parse_page(&client.logs(args.agent.as_deref(), after, since_ms)?)?;
+            if page.lines.is_empty() {
+                if !args.follow {
+                    return Ok(());
+                }
+                std::thread::sleep(FOLLOW_POLL);
+                continue;
+            }
+            for line in &page.lines {
+                print_line(line, args.agent.is_none());
+            }
+            after = page.next;
+        }
+    })
+}
+
+pub(crate) fn parse_page(json: &str) -> anyhow::Result<LogPage> {
+    let page: LogPage = serde_json::from_str(json)
+        .map_err(|e| anyhow::anyhow!("daemon sent a malformed logs reply: {e}"))?;
+    if let Some(error) = &page.error {
+        anyhow::bail!("{error}");
+    }
+    Ok(page)
+}
+
+pub(crate) fn print_line(line: &LogLine, show_agent: bool) {
+    if output::is_json() {
+        // One object per line, so `--follow --format json` streams NDJSON.
+        if let Ok(json) = serde_json::to_string(line) {
+            println!("{json}");
+        }
+        return;
+    }
+    let agent = if show_agent {
+        format!("{:<16} ", line.agent)
+    } else {
+        String::new()
+    };
+    let kind = match line.kind.as_str() {
+        "output" => "",
+        "panic" => "[panic] ",
+        _ => "[dispatch] ",
+    };
+    println!(
+        "{} {agent}{kind}{}",
+        format_unix_ms(line.unix_ms),
+        line.text
+    );
+}
+
+pub(crate) fn unix_ms_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// `2026-03-01T12:00:05.123Z`, or the raw number if it is out of range.
+pub(crate) fn format_unix_ms(unix_ms: u64) -> String {
+    const FORMAT: &[time::format_description::FormatItem<'_>] = time::macros::format_description!(
+        "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z"
+    );
+    time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(unix_ms) * 1_000_000)
+        .ok()
+        .and_then(|at| at.format(FORMAT).ok())
+        .unwrap_or_else(|| unix_ms.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_page_and_surfaces_daemon_errors() {
+        let page = parse_page(
+            r#"{"next":9,"lines":[{"seq":9,"unix_ms":5,"id":7,"agent":"chat","kind":"panic","text":"panic: \"boom\"\n"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(page.next, 9);
+        assert_eq!(page.lines[0].agent, "chat");
+        assert_eq!(page.lines[0].text, "panic: \"boom\"\n");
+
+        let err = parse_page(r#"{"error":"no agent 'nope' on this node"}"#).unwrap_err();
+        assert!(err.to_string().contains("no agent 'nope'"), "{err}");
+    }
+
+    #[test]
+    fn timestamps_render_as_utc_with_millis() {
+        assert_eq!(format_unix_ms(1_000_123), "1970-01-01T00:16:40.123Z");
+    }
+}
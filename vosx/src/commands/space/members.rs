@@ -9,7 +9,7 @@
 use clap::Subcommand;
 
 use vos::registry::{
-    MEMBER_KIND_IDENTITY, MEMBER_KIND_NODE, NODE_ROLE_OBSERVER, NODE_ROLE_VOTER,
+    MEMBER_KIND_IDENTITY, MEMBER_KIND_NODE, NODE_ROLE_OBSERVER, NODE_ROLE_VOTER, NODE_ROLE_WITNESS,
     PROOF_KIND_MERKLE_INCLUSION, PROOF_KIND_ZK, Status,
 };
 
@@ -119,7 +119,8 @@ pub enum MembersCommand {
         /// uses).
         #[arg(long)]
         prefix: Option<u32>,
-        /// `voter` (default) or `observer`.
+        /// `voter` (default), `observer`, or `witness` (votes for
+        /// quorum but stores no service state).
         #[arg(long, default_value = "voter")]
         role: String,
     },
@@ -257,6 +258,7 @@ fn role_name(r: u8) -> &'static str {
     match r {
         NODE_ROLE_VOTER => "voter",
         NODE_ROLE_OBSERVER => "observer",
+        NODE_ROLE_WITNESS => "witness",
         _ => "?",
     }
 }
@@ -269,7 +271,7 @@ fn proof_kind_name(k: u8) -> &'static str {
     }
 }
 
-fn add_node(
+pub(super) fn add_node(
     space: &str,
     peer_id_str: &str,
     prefix_override: Option<u32>,
@@ -283,11 +285,20 @@ fn add_node(
     let role = match role_str {
         "voter" => NODE_ROLE_VOTER,
         "observer" => NODE_ROLE_OBSERVER,
-        other => anyhow::bail!("unknown role '{other}', expected voter|observer"),
+        "witness" => NODE_ROLE_WITNESS,
+        other => anyhow::bail!("unknown role '{other}', expected voter|observer|witness"),
     };
 
     DaemonClient::with_connect(space, |client| {
         let status = client.add_node(prefix, peer_id.to_bytes(), role)?;
+        if status == Status::PrefixCollision {
+            anyhow::bail!(
+                "node prefix 0x{:04x} is already enrolled for a different peer; two nodes \
+                 sharing a prefix can't both be routed to. Remove the existing node first \
+                 (`space members {space} remove-node {prefix}`) if it is gone for good.",
+                prefix as u16,
+            );
+        }
         if status != Status::Ok {
             anyhow::bail!("add_node returned status {status}");
         }
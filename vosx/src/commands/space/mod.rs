@@ -10,24 +10,31 @@
 //! - **Daemon**: `up` runs the libp2p server that owns the
 //!   redb. One daemon per space, identified by an
 //!   `<data_dir>/.endpoint` file.
-//! - **Client**: `publish`, `install`, `upgrade`, `uninstall`,
-//!   `unpublish`, `programs`, `agents`, `members`, `call`.
-//!   Each spawns a tiny libp2p peer, dials the daemon's
-//!   endpoint, sends one registry invoke, and exits. Same
-//!   plumbing under `DaemonClient` — `call` is the floor
-//!   primitive, the rest are typed sugar.
+//! - **Client**: `publish`, `install`, `upgrade`, `upgrade-v2`,
+//!   `uninstall`, `unpublish`, `programs`, `agents`, `members`,
+//!   `voters`, `audit`, `top`, `logs`, `doctor`, `agent-export`,
+//!   `agent-import`, `call`. Each spawns a tiny libp2p peer, dials
+//!   the daemon's endpoint, sends one registry invoke, and exits.
+//!   Same plumbing under `DaemonClient` — `call` is the floor
+//!   primitive, the rest are typed sugar. `console` keeps the
+//!   connection open for an interactive nushell session.
 
 use clap::Subcommand;
 use std::path::PathBuf;
 
+pub mod agent_archive;
 pub mod agents;
 pub mod apply;
+pub mod audit;
 pub mod backup;
+mod backup_seal;
 pub mod call;
 pub mod caps;
 pub mod client;
 pub mod common;
+pub mod console;
 pub mod describe;
+pub mod doctor;
 pub mod down;
 pub mod endpoint;
 pub mod export;
@@ -36,6 +43,7 @@ pub mod info;
 pub mod install;
 pub mod invite;
 pub mod list;
+pub mod logs;
 pub mod members;
 pub mod new;
 pub mod op_sign;
@@ -48,11 +56,14 @@ pub mod reconcile;
 pub mod role;
 mod space_lock;
 pub mod subscriptions;
+pub mod top;
 pub mod uninstall;
 pub mod unpublish;
 pub mod up;
 pub mod upgrade;
+pub mod upgrade_v2;
 pub mod verify;
+pub mod voters;
 
 #[derive(Subcommand, Debug)]
 pub enum SpaceCommand {
@@ -111,6 +122,12 @@ pub enum SpaceCommand {
         /// running daemon's published listen addrs. Mint only.
         #[arg(long, value_name = "MULTIADDR")]
         bootnode: Vec<String>,
+        /// Circuit-relay multiaddr(s) (`…/p2p/<peer id>`) to embed, so a
+        /// joiner behind NAT stays reachable. Repeatable. Defaults to
+        /// this node's `local.toml` relays (plus itself when it runs
+        /// with `relay_server`). Mint only.
+        #[arg(long, value_name = "MULTIADDR")]
+        relay: Vec<String>,
         /// `list` / `revoke <token_pub-prefix>`; omit to mint.
         #[command(subcommand)]
         command: Option<invite::InviteCommand>,
@@ -129,9 +146,9 @@ pub enum SpaceCommand {
         /// Exit when the registry goes idle (smoke-test mode).
         #[arg(long)]
         once: bool,
-        /// libp2p multiaddr to listen on. Repeatable. Overrides
-        /// the saved `listen` field on the spaces.toml entry
-        /// for this run.
+        /// libp2p multiaddr to listen on: `/ip4/…/tcp/N` or
+        /// `/ip4/…/udp/N/quic-v1`. Repeatable. Overrides the saved
+        /// `listen` field on the spaces.toml entry for this run.
         #[arg(long, value_name = "MULTIADDR")]
         listen: Vec<String>,
         /// libp2p multiaddr to dial at startup. Repeatable.
@@ -139,6 +156,15 @@ pub enum SpaceCommand {
         /// spaces.toml entry for this run.
         #[arg(long, value_name = "MULTIADDR")]
         connect: Vec<String>,
+        /// Circuit-relay multiaddr (`…/p2p/<peer id>`) to reserve a
+        /// slot on, for nodes behind NAT. Repeatable. Extends the
+        /// saved `relays` field in `local.toml` for this run.
+        #[arg(long, value_name = "MULTIADDR")]
+        relay: Vec<String>,
+        /// Serve circuit-relay reservations for other members of the
+        /// space. Overrides `relay_server` in `local.toml` for this run.
+        #[arg(long)]
+        relay_server: bool,
         /// Exact protocol-pinned generic service PVM used by installed `.vos`
         /// v2 packages. Without it, v2 rows remain installed but are skipped.
         #[arg(long, value_name = "FILE")]
@@ -177,20 +203,45 @@ pub enum SpaceCommand {
         #[arg(long, default_value_t = 5)]
         grace: u64,
     },
-    /// Create a verified, self-contained offline backup of one space.
-    /// The daemon must be stopped; active state, private side stores, node
-    /// identity, local policy, and the content-addressed program cache are
-    /// copied under one integrity manifest.
+    /// Create a verified, self-contained backup of one space. The daemon
+    /// must be stopped unless `--online` is given; active state, private side
+    /// stores, node identity, local policy, and the content-addressed program
+    /// cache are copied under one integrity manifest.
     Backup {
         /// Space id or name from the local spaces index.
         space: String,
         /// New directory to create. Existing paths are never overwritten.
         output: PathBuf,
+        /// Back up while `space up` keeps running: its daemon copies a
+        /// point-in-time image of the data directory for the archive.
+        #[arg(long)]
+        online: bool,
+        /// Earlier backup of the same space to build on. Unchanged files are
+        /// recorded as inherited instead of copied; restore needs the chain.
+        #[arg(long, value_name = "DIR")]
+        parent: Option<PathBuf>,
+        /// Encrypt to an X25519 recipient from `space backup-keygen`.
+        /// Repeat for several recipients.
+        #[arg(long, value_name = "HEX", conflicts_with = "passphrase_env")]
+        recipient: Vec<String>,
+        /// Encrypt with the passphrase held in this environment variable.
+        /// Also unlocks a passphrase-encrypted `--parent`.
+        #[arg(long, value_name = "VAR")]
+        passphrase_env: Option<String>,
+        /// Identity file that unlocks a recipient-encrypted `--parent`.
+        #[arg(long, value_name = "FILE")]
+        identity: Option<PathBuf>,
+    },
+    /// Create an X25519 identity file for encrypted backups and print its
+    /// recipient.
+    BackupKeygen {
+        /// New owner-only identity file. Keep it apart from the backups.
+        output: PathBuf,
     },
     /// Verify and restore a `space backup` directory. Existing state is
     /// preserved under a recoverable sibling path when `--replace` is used.
     Restore {
-        /// Backup directory containing `manifest.json`.
+        /// Backup directory (the newest archive of an incremental chain).
         backup: PathBuf,
         /// Destination data directory. Defaults to the normal XDG path for
         /// the archived space id, not the source machine's absolute path.
@@ -203,6 +254,41 @@ pub enum SpaceCommand {
         /// Override the archived display name in the local spaces index.
         #[arg(long)]
         name: Option<String>,
+        /// Identity file for recipient-encrypted archives.
+        #[arg(long, value_name = "FILE")]
+        identity: Option<PathBuf>,
+        /// Environment variable holding the passphrase of encrypted archives.
+        #[arg(long, value_name = "VAR")]
+        passphrase_env: Option<String>,
+    },
+    /// Export one installed agent's program, schema and durable state as a
+    /// signed, self-verifying archive directory. The daemon must be
+    /// running; it snapshots the agent's state under its write barrier.
+    ///
+    /// v2 root trees (`VOSP` programs) are refused: their images are bound
+    /// to this space's service identity. Move them with `space backup` and
+    /// `space restore` instead.
+    AgentExport {
+        /// Space id (full hex) or name.
+        space: String,
+        /// Installed agent to export.
+        instance: String,
+        /// New directory to create. Existing paths are never overwritten.
+        output: PathBuf,
+    },
+    /// Verify an `agent-export` archive and install it into a space under
+    /// a fresh replication id, recording its provenance in the registry.
+    AgentImport {
+        /// Space id (full hex) or name.
+        space: String,
+        /// Archive directory written by `agent-export`.
+        archive: PathBuf,
+        /// Install under this name instead of the exported one.
+        #[arg(long)]
+        name: Option<String>,
+        /// Refuse the archive unless this PeerId signed it.
+        #[arg(long, value_name = "PEER_ID")]
+        signer: Option<String>,
     },
     /// Query a space's registry and emit a round-trippable
     /// TOML recipe to stdout.
@@ -215,6 +301,8 @@ pub enum SpaceCommand {
     /// the recipe's node-local half (`tick_ms` / `intra_caps` /
     /// `device_secret`, `cap_policy`, `[[extension]]`) into `local.toml`.
     /// Idempotent — a re-apply of the same recipe is all-skips.
+    /// `--prune` also removes agents and programs the recipe doesn't
+    /// declare.
     Apply {
         /// Space id (full hex) or name.
         space: String,
@@ -228,6 +316,33 @@ pub enum SpaceCommand {
         /// recipe. Without it, a differing blob is flagged, not applied.
         #[arg(long)]
         upgrade: bool,
+        /// Uninstall agents and unpublish programs absent from the
+        /// recipe. Refuses a recipe with no agents, or none in common
+        /// with the space.
+        #[arg(long)]
+        prune: bool,
+    },
+    /// Keep a running space in line with a recipe: apply it, then with
+    /// `--watch` re-apply whenever the recipe (or an agent ELF it
+    /// points at) changes and every `--interval` seconds, reporting any
+    /// drift it corrects.
+    Reconcile {
+        /// Space id (full hex) or name.
+        space: String,
+        /// Recipe TOML path.
+        recipe: PathBuf,
+        /// Keep running until interrupted.
+        #[arg(long)]
+        watch: bool,
+        /// Seconds between drift checks under `--watch`.
+        #[arg(long, default_value_t = 60)]
+        interval: u64,
+        /// As for `space apply --upgrade`.
+        #[arg(long)]
+        upgrade: bool,
+        /// As for `space apply --prune`.
+        #[arg(long)]
+        prune: bool,
     },
     /// Add a program (PVM blob) to the catalog with an
     /// immutable `(name, version)` tag.
@@ -298,6 +413,16 @@ pub enum SpaceCommand {
         /// New program ref: `name:version`.
         program_ref: String,
     },
+    /// Replace an installed v2 actor's program with a signed
+    /// replacement `.vos` through the guest-owned UpgradeActor
+    /// transition. Run against the root's leader; Raft roots
+    /// require the daemon's production trust policy.
+    UpgradeV2 {
+        space: String,
+        instance: String,
+        /// Signed replacement `.vos` package.
+        package: PathBuf,
+    },
     /// List installed agents.
     Agents { space: String },
     /// Show an installed agent's schema — message names, arg
@@ -331,6 +456,56 @@ pub enum SpaceCommand {
         /// Raft agent instance name (as in `vosx space agents`).
         instance: String,
     },
+    /// Live view of the connected daemon's node metrics — per-agent
+    /// dispatches, gas and queue depth, Raft and CRDT replication
+    /// progress, frame traffic and blob-store sizes. Member-gated;
+    /// `--format json` prints one scrape.
+    Top {
+        space: String,
+        /// Seconds between refreshes.
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+        /// Print one scrape and exit instead of refreshing.
+        #[arg(long)]
+        once: bool,
+    },
+    /// Print the daemon's buffered guest logs — each agent's
+    /// `println!`/`log::*` output, panics and dispatch outcomes.
+    /// Requires the developer role. `--format json` prints one
+    /// object per line.
+    Logs {
+        space: String,
+        /// Agent instance name; omit for every agent on the node.
+        agent: Option<String>,
+        /// Keep polling for new lines until interrupted.
+        #[arg(long, short = 'f')]
+        follow: bool,
+        /// Only lines newer than this (e.g. `30s`, `10m`, `2h`).
+        #[arg(long, value_name = "DURATION")]
+        since: Option<String>,
+    },
+    /// Run end-to-end health checks — daemon and endpoint file, peers
+    /// vs registry members, Raft quorum and lag, CRDT head divergence,
+    /// clock skew, blob-cache integrity and the trust socket — and
+    /// report each as pass/warn/fail with a fix. Exits non-zero when a
+    /// check fails; `--format json` for machine consumption.
+    Doctor {
+        space: String,
+        /// Production trust sidecar socket to probe (the daemon's
+        /// `--production-trust-socket`).
+        #[arg(long, value_name = "PATH")]
+        trust_socket: Option<PathBuf>,
+    },
+    /// Open a sandboxed nushell console on the space: installed
+    /// agents and extensions are commands, Tab completes them from
+    /// their schemas. `--script` runs a `.nu` file and exits instead.
+    Console {
+        space: String,
+        /// Evaluate this nu script non-interactively and print its
+        /// result; exits non-zero if it fails.
+        #[arg(long, value_name = "FILE")]
+        script: Option<PathBuf>,
+    },
     /// Manage Node + Identity members. Subcommands: list,
     /// add-node, remove-node, add-identity, remove-identity.
     /// Bare `space members <space>` lists.
@@ -339,6 +514,14 @@ pub enum SpaceCommand {
         #[command(subcommand)]
         command: Option<members::MembersCommand>,
     },
+    /// Replace a Raft voter with a new identity. Subcommands: add,
+    /// promote, remove. Keeps the registry's node rows and every Raft
+    /// root's configuration in step; run `remove` against the leader.
+    Voters {
+        space: String,
+        #[command(subcommand)]
+        command: voters::VotersCommand,
+    },
     /// Manage auth-role grants. Subcommands: list, grant, revoke.
     /// Bare `space role <space>` lists. When v2 is active, space-level
     /// mutations are also committed to the root-signed canonical authority;
@@ -348,6 +531,30 @@ pub enum SpaceCommand {
         #[command(subcommand)]
         command: Option<role::RoleCommand>,
     },
+    /// Print the registry's admin audit log — who signed each accepted
+    /// catalog, member, role and invite mutation, and when it was
+    /// admitted — after verifying its hash chain from seq 0 and every
+    /// row's signature. Fails, naming the seq, if a row was edited,
+    /// dropped, reordered or doesn't carry a valid signature.
+    Audit {
+        space: String,
+        /// Only list rows admitted within this window (e.g. `30m`, `24h`,
+        /// `7d`). The whole chain is verified either way.
+        #[arg(long, value_name = "DURATION")]
+        since: Option<String>,
+        /// Only list rows signed by this PeerId, or `me`.
+        #[arg(long, value_name = "PEER")]
+        actor: Option<String>,
+        /// Fail unless the chain still holds this `<seq>:<hash>` head, as
+        /// written by `--export-head` from the same daemon. Heads are
+        /// per-replica: another replica may order the same rows
+        /// differently.
+        #[arg(long, value_name = "SEQ:HASH")]
+        pin: Option<String>,
+        /// Write the verified head as `<seq>:<hash>` to this file.
+        #[arg(long, value_name = "FILE")]
+        export_head: Option<PathBuf>,
+    },
     /// Drop the local copy of a space — wipes the per-space
     /// data dir and the spaces.toml entry. The shared blob
     /// cache is kept and the space stays alive on its peers;
@@ -406,12 +613,14 @@ pub fn run(cmd: SpaceCommand) -> anyhow::Result<()> {
             role,
             expires,
             bootnode,
+            relay,
             command,
         } => invite::run(invite::Args {
             space,
             role,
             expires,
             bootnode,
+            relay,
             command,
         }),
         SpaceCommand::Up {
@@ -419,6 +628,8 @@ pub fn run(cmd: SpaceCommand) -> anyhow::Result<()> {
             once,
             listen,
             connect,
+            relay,
+            relay_server,
             service_pvm,
             production_trust_socket,
             allow_v2_conformance,
@@ -427,6 +638,8 @@ pub fn run(cmd: SpaceCommand) -> anyhow::Result<()> {
             once,
             listen,
             connect,
+            relay,
+            relay_server,
             service_pvm,
             production_trust_socket,
             allow_v2_conformance,
@@ -440,24 +653,87 @@ pub fn run(cmd: SpaceCommand) -> anyhow::Result<()> {
             force,
             grace_secs: grace,
         }),
-        SpaceCommand::Backup { space, output } => backup::run_backup(&space, &output),
+        SpaceCommand::Backup {
+            space,
+            output,
+            online,
+            parent,
+            recipient,
+            passphrase_env,
+            identity,
+        } => backup::run_backup(
+            &space,
+            &output,
+            online,
+            parent.as_deref(),
+            &recipient,
+            passphrase_env.as_deref(),
+            identity.as_deref(),
+        ),
+        SpaceCommand::BackupKeygen { output } => backup::run_keygen(&output),
         SpaceCommand::Restore {
             backup: archive,
             data_dir,
             replace,
             name,
-        } => backup::run_restore(&archive, data_dir.as_deref(), replace, name.as_deref()),
+            identity,
+            passphrase_env,
+        } => backup::run_restore(
+            &archive,
+            data_dir.as_deref(),
+            replace,
+            name.as_deref(),
+            identity.as_deref(),
+            passphrase_env.as_deref(),
+        ),
+        SpaceCommand::AgentExport {
+            space,
+            instance,
+            output,
+        } => agent_archive::run_export(agent_archive::ExportArgs {
+            space,
+            instance,
+            output,
+        }),
+        SpaceCommand::AgentImport {
+            space,
+            archive,
+            name,
+            signer,
+        } => agent_archive::run_import(agent_archive::ImportArgs {
+            space,
+            archive,
+            name,
+            signer,
+        }),
         SpaceCommand::Export { space } => export::run(export::Args { query: space }),
         SpaceCommand::Apply {
             space,
             recipe,
             diff,
             upgrade,
+            prune,
         } => apply::run(apply::Args {
             space,
             recipe,
             diff,
             upgrade,
+            prune,
+        }),
+        SpaceCommand::Reconcile {
+            space,
+            recipe,
+            watch,
+            interval,
+            upgrade,
+            prune,
+        } => apply::run_reconcile(apply::ReconcileArgs {
+            space,
+            recipe,
+            watch,
+            interval,
+            upgrade,
+            prune,
         }),
         SpaceCommand::Publish {
             space,
@@ -501,12 +777,63 @@ pub fn run(cmd: SpaceCommand) -> anyhow::Result<()> {
             instance,
             program_ref,
         }),
+        SpaceCommand::UpgradeV2 {
+            space,
+            instance,
+            package,
+        } => upgrade_v2::run(upgrade_v2::Args {
+            space,
+            instance,
+            package,
+        }),
         SpaceCommand::Agents { space } => agents::run(&space),
         SpaceCommand::Describe { space, instance } => describe::run(&space, &instance),
         SpaceCommand::Caps { space, instance } => caps::run(&space, instance.as_deref()),
         SpaceCommand::RaftStatus { space, instance } => raft_status::run(&space, &instance),
+        SpaceCommand::Top {
+            space,
+            interval,
+            once,
+        } => top::run(top::Args {
+            space,
+            interval,
+            once,
+        }),
+        SpaceCommand::Logs {
+            space,
+            agent,
+            follow,
+            since,
+        } => logs::run(logs::Args {
+            space,
+            agent,
+            follow,
+            since,
+        }),
+        SpaceCommand::Doctor {
+            space,
+            trust_socket,
+        } => doctor::run(doctor::Args {
+            space,
+            trust_socket,
+        }),
+        SpaceCommand::Console { space, script } => console::run(console::Args { space, script }),
         SpaceCommand::Members { space, command } => members::run(members::Args { space, command }),
+        SpaceCommand::Voters { space, command } => voters::run(voters::Args { space, command }),
         SpaceCommand::Role { space, command } => role::run(role::Args { space, command }),
+        SpaceCommand::Audit {
+            space,
+            since,
+            actor,
+            pin,
+            export_head,
+        } => audit::run(audit::Args {
+            space,
+            since,
+            actor,
+            pin,
+            export_head,
+        }),
         SpaceCommand::Forget { space, yes } => forget::run(forget::Args { space, yes }),
         SpaceCommand::Call {
             space,
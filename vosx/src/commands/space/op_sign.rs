@@ -4,14 +4,15 @@
 //! `install`, …) carries an `auth` blob the registry actor verifies
 //! at handler time and re-verifies on every peer's causal replay.
 //! The signer is the operator's libp2p identity key — held by the
-//! CLI on a `vosx space …` command, and by the daemon at boot for
+//! CLI on a `vosx space …` command (or an external key picked with
+//! `--signer`, see [`crate::signer`]), and by the daemon at boot for
 //! the genesis (`space new`) and recipe-reconcile paths.
 //!
 //! The canonical bytes are built by the shared
 //! [`vos::registry::canonical_op_bytes`], so the signer and the
 //! verifier stay in lockstep without re-encoding the wire `Msg`.
 
-use libp2p::identity::Keypair;
+use crate::signer::Signer;
 use vos::registry::{OP_SIG_LEN, canonical_op_bytes, pack_auth};
 
 /// Build the `auth` blob for a signed registry op: the signer's
@@ -20,23 +21,23 @@ use vos::registry::{OP_SIG_LEN, canonical_op_bytes, pack_auth};
 ///
 /// `fields` must match — byte for byte, in order — what the
 /// corresponding registry handler passes to `canonical_op_bytes`.
-pub fn op_auth(keypair: &Keypair, op: &str, fields: &[&[u8]]) -> anyhow::Result<Vec<u8>> {
+pub fn op_auth(signer: &dyn Signer, op: &str, fields: &[&[u8]]) -> anyhow::Result<Vec<u8>> {
     let canonical = canonical_op_bytes(op, fields);
-    let sig: [u8; OP_SIG_LEN] = keypair
+    let sig: [u8; OP_SIG_LEN] = signer
         .sign(&canonical)
-        .map_err(|e| anyhow::anyhow!("sign registry op '{op}': {e}"))?
+        .map_err(|e| anyhow::anyhow!("sign registry op '{op}': {e:#}"))?
         .as_slice()
         .try_into()
         .map_err(|_| {
             anyhow::anyhow!("registry op '{op}': expected a {OP_SIG_LEN}-byte ed25519 signature")
         })?;
-    let signer = libp2p::PeerId::from(keypair.public()).to_bytes();
-    Ok(pack_auth(&signer, &sig))
+    Ok(pack_auth(&signer.peer_id().to_bytes(), &sig))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use libp2p::identity::Keypair;
     use vos::registry::ed25519_pubkey_from_peer_id;
     // `verify_op_sig` (ed25519) deliberately stays in the actor crate so
     // its `ed25519-dalek` dep never reaches `vos`; this interop test is
@@ -19,27 +19,14 @@ use vos::v2::{
     ReceiptVerificationRequestV2, RoleCredentialVerificationRequestV2, ServiceGenesisV2, V2Wire,
 };
 
-const REQUEST_MAGIC: [u8; 4] = *b"VTA1";
-const RESPONSE_MAGIC: [u8; 4] = *b"VTR1";
-const PROTOCOL_VERSION: u16 = 1;
-const MAX_FRAME_BYTES: usize = 64 * 1024 * 1024;
-const IO_TIMEOUT: Duration = Duration::from_secs(5);
+use vos::v2::trust_socket::{
+    AUTHORIZED, CURRENT_TIMESLOT, DENIED, MAX_FRAME_BYTES, NO_TIMESLOT, POLICY, PROTOCOL_VERSION,
+    QUERY_POLICY, REQUEST_HEADER_BYTES, REQUEST_MAGIC, RESPONSE_BYTES, RESPONSE_MAGIC,
+    RESPONSE_WITH_SLOT_BYTES, TIMESLOT, UNAVAILABLE, VERIFY_INSTALL, VERIFY_PROOF, VERIFY_RECEIPT,
+    VERIFY_ROLE, VERIFY_TIMESLOT, VERIFY_UPGRADE, request_hash,
+};
 
-const QUERY_POLICY: u8 = 0;
-const CURRENT_TIMESLOT: u8 = 1;
-const VERIFY_TIMESLOT: u8 = 2;
-const VERIFY_PROOF: u8 = 3;
-const VERIFY_INSTALL: u8 = 4;
-const VERIFY_UPGRADE: u8 = 5;
-const VERIFY_ROLE: u8 = 6;
-const VERIFY_RECEIPT: u8 = 7;
-
-const AUTHORIZED: u8 = 0;
-const DENIED: u8 = 1;
-const UNAVAILABLE: u8 = 2;
-const NO_TIMESLOT: u8 = 3;
-const TIMESLOT: u8 = 4;
-const POLICY: u8 = 5;
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Debug)]
 pub(super) enum ProductionTrustSocketError {
@@ -166,12 +153,12 @@ impl ProductionTrustV2 for SocketProductionTrustV2 {
 }
 
 fn encode_request(tag: u8, payload: &[u8]) -> Result<Vec<u8>, ProductionTrustSocketError> {
-    if payload.len() > MAX_FRAME_BYTES.saturating_sub(11) {
+    if payload.len() > MAX_FRAME_BYTES.saturating_sub(REQUEST_HEADER_BYTES) {
         return Err(ProductionTrustSocketError::InvalidResponse);
     }
     let payload_len =
         u32::try_from(payload.len()).map_err(|_| ProductionTrustSocketError::InvalidResponse)?;
-    let mut request = Vec::with_capacity(11 + payload.len());
+    let mut request = Vec::with_capacity(REQUEST_HEADER_BYTES + payload.len());
     request.extend_from_slice(&REQUEST_MAGIC);
     request.extend_from_slice(&PROTOCOL_VERSION.to_le_bytes());
     request.push(tag);
@@ -184,7 +171,7 @@ fn encode_pair(left: &[u8], right: &[u8]) -> Option<Vec<u8>> {
     let left_len = u32::try_from(left.len()).ok()?;
     let right_len = u32::try_from(right.len()).ok()?;
     let total = 8usize.checked_add(left.len())?.checked_add(right.len())?;
-    if total > MAX_FRAME_BYTES.saturating_sub(11) {
+    if total > MAX_FRAME_BYTES.saturating_sub(REQUEST_HEADER_BYTES) {
         return None;
     }
     let mut payload = Vec::with_capacity(total);
@@ -209,7 +196,7 @@ fn exchange_with_timeout(
     let deadline = Instant::now()
         .checked_add(timeout)
         .ok_or(ProductionTrustSocketError::InvalidResponse)?;
-    let request_hash = Hash::digest(b"vos/production-trust-socket/request/v1", &[request]);
+    let request_hash = request_hash(request);
     let mut stream = connect_until(path, deadline).map_err(ProductionTrustSocketError::Connect)?;
     let request_len =
         u32::try_from(request.len()).map_err(|_| ProductionTrustSocketError::InvalidResponse)?;
@@ -220,7 +207,7 @@ fn exchange_with_timeout(
     let mut len = [0u8; 4];
     read_exact_until(&mut stream, &mut len, deadline).map_err(ProductionTrustSocketError::Io)?;
     let len = u32::from_le_bytes(len) as usize;
-    if len < 71 || len > 79 {
+    if !(RESPONSE_BYTES..=RESPONSE_WITH_SLOT_BYTES).contains(&len) {
         return Err(ProductionTrustSocketError::InvalidResponse);
     }
     let mut response = vec![0; len];
@@ -327,9 +314,13 @@ fn decode_response(
         .get(70)
         .ok_or(ProductionTrustSocketError::InvalidResponse)?;
     let timeslot = match result {
-        AUTHORIZED | DENIED | UNAVAILABLE | NO_TIMESLOT | POLICY if bytes.len() == 71 => None,
-        TIMESLOT if bytes.len() == 79 => Some(u64::from_le_bytes(
-            bytes[71..79]
+        AUTHORIZED | DENIED | UNAVAILABLE | NO_TIMESLOT | POLICY
+            if bytes.len() == RESPONSE_BYTES =>
+        {
+            None
+        }
+        TIMESLOT if bytes.len() == RESPONSE_WITH_SLOT_BYTES => Some(u64::from_le_bytes(
+            bytes[RESPONSE_BYTES..]
                 .try_into()
                 .map_err(|_| ProductionTrustSocketError::InvalidResponse)?,
         )),
@@ -373,8 +364,7 @@ mod tests {
             stream.read_exact(&mut len).unwrap();
             let mut request = vec![0; u32::from_le_bytes(len) as usize];
             stream.read_exact(&mut request).unwrap();
-            let mut request_hash =
-                Hash::digest(b"vos/production-trust-socket/request/v1", &[&request]);
+            let mut request_hash = request_hash(&request);
             if corrupt_request {
                 request_hash.0[0] ^= 1;
             }
@@ -409,7 +399,7 @@ mod tests {
         let request = encode_request(QUERY_POLICY, &[]).unwrap();
         assert_eq!(hex::encode(&request), "5654413101000000000000");
         assert_eq!(
-            hex::encode(Hash::digest(b"vos/production-trust-socket/request/v1", &[&request]).0),
+            hex::encode(request_hash(&request).0),
             "a5ee8be4abb996fd3735970cd7b5a53632afef7cb7a548e1314d9c6ef39ece35",
         );
     }
@@ -464,7 +454,7 @@ mod tests {
             stream.read_exact(&mut len).unwrap();
             let mut request = vec![0; u32::from_le_bytes(len) as usize];
             stream.read_exact(&mut request).unwrap();
-            let request_hash = Hash::digest(b"vos/production-trust-socket/request/v1", &[&request]);
+            let request_hash = request_hash(&request);
             let mut response = Vec::new();
             response.extend_from_slice(&RESPONSE_MAGIC);
             response.extend_from_slice(&PROTOCOL_VERSION.to_le_bytes());
@@ -83,6 +83,31 @@ pub fn run(args: Args) -> anyhow::Result<()> {
     })
 }
 
+/// Publish a signed `.vos` package as `name:version`: cache the exact
+/// bytes, add the catalog row and forward the package's schema. The
+/// per-rebuild publish of `vosx dev watch`.
+pub(crate) fn publish_package(
+    client: &DaemonClient,
+    name: &str,
+    version: &str,
+    package: Vec<u8>,
+) -> anyhow::Result<BlobHash> {
+    let source_hash = blob_store::cache_put(&package)
+        .map_err(|e| anyhow::anyhow!("cache {name}:{version}: {e}"))?;
+    let (hash, _, package_meta, crdt) = canonical_program(name, version, source_hash, package)?;
+    match client.publish(name.to_string(), version.to_string(), hash.0.to_vec(), crdt)? {
+        Status::Ok => {
+            forward_meta_blob(client, &hash, package_meta.as_deref().unwrap_or_default());
+            Ok(hash)
+        }
+        Status::TagConflict => anyhow::bail!(
+            "{name}:{version} already exists in the catalog with a different hash; \
+             tags are immutable",
+        ),
+        other => anyhow::bail!("publish returned status {other}"),
+    }
+}
+
 fn canonical_program(
     name: &str,
     version: &str,
@@ -7,13 +7,11 @@
 //! beat, and the watch view all read this.
 
 use crate::commands::space::client::DaemonClient;
-use crate::commands::space::common::consistency_name;
+use crate::commands::space::common::{RAFT_CONSISTENCY, consistency_name};
 use crate::output;
 use serde::Serialize;
 use vos::network::{RaftRole, RaftStatusReply};
 
-const RAFT_CONSISTENCY: u8 = 3;
-
 #[derive(Serialize)]
 struct RaftStatusView {
     instance: String,
@@ -70,6 +70,63 @@ pub struct Recipe {
     /// registry.
     #[serde(rename = "extension", default)]
     pub extensions: Vec<ExtensionDef>,
+    /// Consensus nodes to enroll. Each `[[node]]` maps onto one
+    /// registry `add_node` on `space apply`; already-enrolled rows
+    /// with the same peer and role are skipped.
+    #[serde(rename = "node", default)]
+    pub nodes: Vec<NodeDef>,
+    /// Catalog entries the recipe keeps even with no instance running
+    /// them — `space export` emits one per published program. Only
+    /// `space apply --prune` reads them: anything neither listed here
+    /// nor referenced by an `[[agent]]` is unpublished.
+    #[serde(rename = "program", default)]
+    pub programs: Vec<ProgramDef>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct ProgramDef {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct NodeDef {
+    /// Multibase-encoded libp2p PeerId (e.g. `12D3KooW…`).
+    pub peer_id: String,
+    /// 16-bit node prefix. Omitted → derived from `peer_id`.
+    pub prefix: Option<u16>,
+    /// `voter` (default), `observer`, or `witness`. A witness votes
+    /// in every Raft group but stores no entries or state, so a
+    /// cheap third process gives a two-replica group a quorum.
+    #[serde(default = "default_node_role")]
+    pub role: String,
+}
+
+impl NodeDef {
+    /// Resolve `(prefix, peer_id bytes, NODE_ROLE_*)` for `add_node`.
+    pub(crate) fn resolve(&self) -> anyhow::Result<(u16, Vec<u8>, u8)> {
+        let peer = self
+            .peer_id
+            .parse::<libp2p::PeerId>()
+            .map_err(|e| anyhow::anyhow!("node '{}': parse peer_id: {e}", self.peer_id))?;
+        let role = match self.role.as_str() {
+            "voter" => vos::registry::NODE_ROLE_VOTER,
+            "observer" => vos::registry::NODE_ROLE_OBSERVER,
+            "witness" => vos::registry::NODE_ROLE_WITNESS,
+            other => anyhow::bail!(
+                "node '{}': unknown role '{other}', expected voter|observer|witness",
+                self.peer_id,
+            ),
+        };
+        let prefix = self
+            .prefix
+            .unwrap_or_else(|| vos::network::derive_node_prefix(&peer));
+        Ok((prefix, peer.to_bytes(), role))
+    }
+}
+
+fn default_node_role() -> String {
+    "voter".into()
 }
 
 #[derive(Deserialize, Debug, Default)]
@@ -109,6 +166,12 @@ pub struct ExtensionDef {
     /// than silently dropping authority bounds.
     #[serde(default)]
     pub intra_caps: Vec<String>,
+    /// Grant a transport extension `ctx.ask_dispatch_as` — relaying calls
+    /// as the members who prove their SSH key to it (the SSH console),
+    /// bounded by `intra_caps`. Defaults to `false`: the host refuses every
+    /// such call, whatever the extension itself declares.
+    #[serde(default)]
+    pub dispatch_as: bool,
     /// Periodic `tick` interval in milliseconds. When set
     /// (and > 0), the host calls the extension's `tick` handler roughly this
     /// often, between inbound work — the actor-mode way to originate periodic
@@ -198,6 +261,11 @@ pub struct AgentDef {
     /// mirroring an extension's `intra_caps`.
     #[serde(default)]
     pub intra_caps: Vec<String>,
+    /// Work fairness of a v2 root — its dispatch weight and the priority
+    /// class of named callers. Projected into `local.toml` like the other
+    /// node-local fields, so every replica applying this recipe agrees.
+    #[serde(default)]
+    pub fairness: Option<crate::commands::space::subscriptions::FairnessLocal>,
 }
 
 #[derive(Deserialize, Debug, Default)]
@@ -363,6 +431,16 @@ pub(crate) fn register_extension(
     } else {
         cfg.with_intra_caps(intra_caps)
     };
+    let cfg = if ext.dispatch_as {
+        tracing::info!(
+            "extension '{}' dispatch_as granted: it may relay calls as members \
+             who sign in to it",
+            ext.name,
+        );
+        cfg.allow_dispatch_as()
+    } else {
+        cfg
+    };
 
     // Periodic `tick` cadence. `with_tick_ms` treats 0 as off.
     let cfg = match ext.tick_ms {
@@ -1168,9 +1246,9 @@ mod tests {
     #[test]
     fn export_output_parses_as_a_recipe() {
         // `space export` emits path-less agents (blobs are content-
-        // addressed) carrying `program` + `program_hash`, plus
-        // `[[program]]` / `space_id` / `[members]` blocks the recipe
-        // parser doesn't model. All of it must parse cleanly so
+        // addressed) carrying `program` + `program_hash`, `[[program]]`
+        // rows, plus `space_id` / `[members]` blocks the recipe parser
+        // doesn't model. All of it must parse cleanly so
         // `export | apply --diff` can round-trip.
         let s = r#"
             space    = "e2e"
@@ -1206,6 +1284,9 @@ mod tests {
         let m: Recipe = toml::from_str(s).expect("export output parses as a recipe");
         assert_eq!(m.space.as_deref(), Some("e2e"));
         assert_eq!(m.agents.len(), 1);
+        // `[[program]]` rows name the catalog entries `apply --prune` keeps.
+        assert_eq!(m.programs.len(), 1);
+        assert_eq!(m.programs[0].name, "counter");
         let a = &m.agents[0];
         assert_eq!(a.name, "counter");
         assert!(a.path.is_empty(), "exported agents carry no source path");
@@ -1215,6 +1296,39 @@ mod tests {
         assert!(a.network_reachable);
     }
 
+    #[test]
+    fn node_list_accepts_witness_role() {
+        let peer = libp2p::identity::Keypair::generate_ed25519()
+            .public()
+            .to_peer_id();
+        let s = format!(
+            r#"
+            [[node]]
+            peer_id = "{peer}"
+            role    = "witness"
+
+            [[node]]
+            peer_id = "{peer}"
+            prefix  = 7
+        "#
+        );
+        let m: Recipe = toml::from_str(&s).unwrap();
+        assert_eq!(m.nodes.len(), 2);
+        let (prefix, key, role) = m.nodes[0].resolve().unwrap();
+        assert_eq!(prefix, vos::network::derive_node_prefix(&peer));
+        assert_eq!(key, peer.to_bytes());
+        assert_eq!(role, vos::registry::NODE_ROLE_WITNESS);
+        let (prefix, _, role) = m.nodes[1].resolve().unwrap();
+        assert_eq!((prefix, role), (7, vos::registry::NODE_ROLE_VOTER));
+
+        let bad = NodeDef {
+            peer_id: peer.to_string(),
+            prefix: None,
+            role: "arbiter".into(),
+        };
+        assert!(bad.resolve().is_err());
+    }
+
     #[test]
     fn parses_minimal_recipe() {
         let s = r#"
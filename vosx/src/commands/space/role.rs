@@ -14,6 +14,7 @@
 //! vosx space role <space> list                                 # default
 //! vosx space role <space> grant <peer> <role>                  # space-level
 //! vosx space role <space> grant <peer> <role> --in <actor>     # actor-local
+//! vosx space role <space> grant <peer> <role> --signer ssh-agent
 //! vosx space role <space> revoke <peer>                        # space-level
 //! vosx space role <space> revoke <peer> --in <actor>           # actor-local
 //! ```
@@ -28,6 +29,11 @@
 //! role byte (parsed as a decimal `0..255`); the registry stores
 //! it opaquely. v1 doesn't query the actor's role-name table —
 //! operators reference the discriminant directly.
+//!
+//! `grant --signer <SPEC>` signs the grant with a key outside the
+//! identity file — an ssh-agent or PKCS#11 token (see
+//! [`crate::signer`]). The registry authorizes the grant by that key's
+//! own role; the connection is still made as `identity.key`.
 
 use clap::Subcommand;
 use serde::Serialize;
@@ -62,6 +68,11 @@ pub enum RoleCommand {
         /// overriding the space-level mapping for that actor.
         #[arg(long = "in", value_name = "ACTOR")]
         agent: Option<String>,
+        /// Key that signs the grant: `file`, `ssh-agent[:<socket>][#comment]`
+        /// or `pkcs11:<module.so>[#label]`. Defaults to `VOSX_SIGNER`, else
+        /// the identity key.
+        #[arg(long, value_name = "SPEC")]
+        signer: Option<String>,
     },
     /// Remove a peer's grant. Equivalent to `grant <peer> none`
     /// but also removes the table row so listings stay tidy.
@@ -89,9 +100,18 @@ struct GrantView {
 pub fn run(args: Args) -> anyhow::Result<()> {
     match args.command.unwrap_or(RoleCommand::List { agent: None }) {
         RoleCommand::List { agent } => list(&args.space, agent.as_deref()),
-        RoleCommand::Grant { peer, role, agent } => {
-            grant(&args.space, &peer, &role, agent.as_deref())
-        }
+        RoleCommand::Grant {
+            peer,
+            role,
+            agent,
+            signer,
+        } => grant(
+            &args.space,
+            &peer,
+            &role,
+            agent.as_deref(),
+            signer.as_deref(),
+        ),
         RoleCommand::Revoke { peer, agent } => revoke(&args.space, &peer, agent.as_deref()),
     }
 }
@@ -160,9 +180,17 @@ fn list(space: &str, agent: Option<&str>) -> anyhow::Result<()> {
     })
 }
 
-fn grant(space: &str, peer_arg: &str, role_str: &str, agent: Option<&str>) -> anyhow::Result<()> {
+fn grant(
+    space: &str,
+    peer_arg: &str,
+    role_str: &str,
+    agent: Option<&str>,
+    signer: Option<&str>,
+) -> anyhow::Result<()> {
     let peer_id = resolve_peer(peer_arg)?;
-    DaemonClient::with_connect(space, |client| match agent {
+    let identity = crate::identity::load_or_create()?;
+    let signer = crate::signer::open(signer)?;
+    DaemonClient::with_connect_as(space, identity, signer, |client| match agent {
         Some(name) => {
             // Actor-local raw role bytes are retained only for v1 actors.
             let role: u8 = role_str
@@ -243,7 +271,7 @@ fn revoke(space: &str, peer_arg: &str, agent: Option<&str>) -> anyhow::Result<()
     })
 }
 
-fn resolve_peer(arg: &str) -> anyhow::Result<libp2p::PeerId> {
+pub(crate) fn resolve_peer(arg: &str) -> anyhow::Result<libp2p::PeerId> {
     if arg == "me" {
         let kp = crate::identity::load_or_create()?;
         return Ok(libp2p::PeerId::from(kp.public()));
@@ -262,7 +290,7 @@ fn parse_role(s: &str) -> anyhow::Result<u8> {
     }
 }
 
-fn role_name(r: u8) -> &'static str {
+pub(crate) fn role_name(r: u8) -> &'static str {
     match r {
         AUTH_ROLE_NONE => "none",
         AUTH_ROLE_READONLY => "read",
@@ -275,7 +303,7 @@ fn role_name(r: u8) -> &'static str {
 /// Best-effort decode for display: libp2p PeerId if the bytes
 /// parse, else hex. Operators paste PeerId strings, so the
 /// happy path is the multibase form.
-fn peer_id_label(bytes: &[u8]) -> String {
+pub(crate) fn peer_id_label(bytes: &[u8]) -> String {
     match libp2p::PeerId::from_bytes(bytes) {
         Ok(p) => p.to_string(),
         Err(_) => hex::encode(bytes),
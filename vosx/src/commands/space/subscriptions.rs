@@ -64,17 +64,35 @@ pub struct LocalConfig {
     #[serde(default)]
     pub subscriptions: Vec<String>,
     /// libp2p multiaddrs the daemon should bind on every
-    /// `space up`. Empty (default) = bind a loopback auto-port
-    /// for local-only client commands. `--listen` on
+    /// `space up` — TCP, QUIC (`/udp/N/quic-v1`), or both; peers
+    /// that see both dial QUIC first. Empty (default) = bind a
+    /// loopback auto-port for local-only client commands. `--listen` on
     /// `space up` overrides for one run.
     #[serde(default)]
     pub listen: Vec<String>,
+    /// Circuit-relay multiaddrs (`…/p2p/<relay peer id>`) to hold a
+    /// reservation on, so the node stays dialable from behind NAT.
+    /// `space up <token>` appends the relays an invite carries;
+    /// `--relay` on `space up` extends the list for one run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relays: Vec<String>,
+    /// Serve circuit-relay reservations for other members. Only
+    /// worth enabling on a node with a publicly reachable `listen`
+    /// address; `--relay-server` on `space up` enables it for one run.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub relay_server: bool,
     /// Space-level default extension `cap_policy` (`"log"` / `"block"`
     /// / `"kill"`); per-extension overrides live on each
     /// [`ExtensionLocal`]. Node-local — a recipe declares it, `apply`
     /// projects it here, boot reads it. `None` → host default.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cap_policy: Option<String>,
+    /// `host:port` to serve the node's Prometheus text exposition on
+    /// (`GET /metrics`) while `space up` runs. `None` (default) = no
+    /// exporter; `space top` still reads the same numbers over the
+    /// daemon socket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<String>,
     /// Per-agent node-local policy, keyed by instance name. Only agents
     /// that declare at least one node-local field get an entry — a bare
     /// agent isn't listed. Written by `apply`, applied at every boot so
@@ -91,8 +109,8 @@ pub struct LocalConfig {
 
 /// The node-local half of an `[[agent]]` recipe entry. Everything else
 /// on the entry (program, consistency, sync floor, init, on_start) is
-/// replicated through the registry `install`; these three fields never
-/// touch the `AgentRow`.
+/// replicated through the registry `install`; these fields never touch
+/// the `AgentRow`. `fairness` is last because it serializes as a table.
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct AgentLocal {
     /// Periodic `tick` cadence in ms (0 / omitted → no ticking).
@@ -106,6 +124,31 @@ pub struct AgentLocal {
     /// every spawn (the messenger's MLS root). Never leaves the node.
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub device_secret: bool,
+    /// Dispatch priorities and weight for a v2 root's queued work.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fairness: Option<FairnessLocal>,
+}
+
+/// The `[agent.fairness]` table of a v2 root. Every replica of the root
+/// must declare the same table: it decides the order in which replicas
+/// refine queued work, so a recipe is the place to set it.
+///
+/// ```toml
+/// [agent.fairness]
+/// weight = 4                            # this root's dispatch share
+/// members = { "<peer hex>" = "admin" }  # callers' priority class
+/// actors = { billing = "operator" }     # calling roots, by agent name
+/// ```
+///
+/// Classes are `operator`, `admin` and `normal` (the default).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FairnessLocal {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub members: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub actors: BTreeMap<String, String>,
 }
 
 /// A native `.so` extension registration — the node-local mirror of a
@@ -123,6 +166,8 @@ pub struct ExtensionLocal {
     pub relay_unauthenticated: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub intra_caps: Vec<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dispatch_as: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tick_ms: Option<u64>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
@@ -302,12 +347,16 @@ mod tests {
         let cfg = LocalConfig {
             subscriptions: vec!["a".into(), "b".into()],
             listen: vec!["/ip4/0.0.0.0/tcp/4811".into()],
+            relays: vec!["/ip4/5.6.7.8/tcp/4001/p2p/12D3KooWRelay".into()],
+            relay_server: true,
             ..Default::default()
         };
         save(&tmp, &cfg).unwrap();
         let back = load(&tmp).unwrap();
         assert_eq!(back.subscriptions, cfg.subscriptions);
         assert_eq!(back.listen, cfg.listen);
+        assert_eq!(back.relays, cfg.relays);
+        assert!(back.relay_server);
         let _ = std::fs::remove_dir_all(&tmp);
     }
 
@@ -334,6 +383,7 @@ mod tests {
                 tick_ms: Some(500),
                 intra_caps: vec!["space-registry:member".into()],
                 device_secret: true,
+                fairness: None,
             },
         );
         let mut init = BTreeMap::new();
@@ -341,7 +391,10 @@ mod tests {
         let cfg = LocalConfig {
             subscriptions: vec!["messenger".into()],
             listen: vec![],
+            relays: vec![],
+            relay_server: false,
             cap_policy: Some("block".into()),
+            metrics: None,
             agents,
             extensions: vec![ExtensionLocal {
                 name: "gateway".into(),
@@ -349,6 +402,7 @@ mod tests {
                 cap_policy: Some("log".into()),
                 relay_unauthenticated: true,
                 intra_caps: vec![],
+                dispatch_as: false,
                 tick_ms: None,
                 init,
             }],
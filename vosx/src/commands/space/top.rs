@@ -0,0 +1,447 @@
+//! `space top` — a live view of the connected daemon's node metrics.
+//!
+//! Scrapes the daemon's `__metrics` op (the same Prometheus text the
+//! opt-in `local.toml` `metrics` exporter serves), parses it with
+//! [`vos::metrics::parse`], and redraws per-agent dispatch/gas/queue,
+//! Raft progress, CRDT sync, network traffic and blob-store tables every
+//! `--interval` seconds. `--once` (or `--format json`) prints one scrape
+//! and exits.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::commands::space::client::DaemonClient;
+use crate::commands::space::common::truncate;
+use crate::output;
+use serde::Serialize;
+use vos::metrics::Sample;
+
+pub struct Args {
+    pub space: String,
+    pub interval: u64,
+    pub once: bool,
+}
+
+#[derive(Serialize, Default, Debug, Clone, PartialEq)]
+struct TopView {
+    node: Option<String>,
+    peers: Option<u64>,
+    agents: Vec<AgentRow>,
+    raft: Vec<RaftRow>,
+    crdt: Vec<CrdtRow>,
+    frames: Vec<FrameRow>,
+    blobs: Vec<BlobRow>,
+}
+
+#[derive(Serialize, Default, Debug, Clone, PartialEq)]
+struct AgentRow {
+    agent: String,
+    id: String,
+    tells: u64,
+    invokes: u64,
+    gas: u64,
+    queued: u64,
+    /// `(inbox, ingress)` durable backlog — v2 roots only.
+    root_backlog: Option<(u64, u64)>,
+}
+
+impl AgentRow {
+    fn dispatches(&self) -> u64 {
+        self.tells.saturating_add(self.invokes)
+    }
+}
+
+#[derive(Serialize, Default, Debug, Clone, PartialEq)]
+struct RaftRow {
+    agent: String,
+    group: String,
+    term: u64,
+    commit_index: u64,
+    last_applied: u64,
+    apply_lag: u64,
+    leader: bool,
+    members: u64,
+}
+
+#[derive(Serialize, Default, Debug, Clone, PartialEq)]
+struct CrdtRow {
+    agent: String,
+    group: String,
+    heads: u64,
+    merged: u64,
+    backlog: u64,
+}
+
+#[derive(Serialize, Default, Debug, Clone, PartialEq)]
+struct FrameRow {
+    kind: String,
+    sent_frames: u64,
+    sent_bytes: u64,
+    received_frames: u64,
+    received_bytes: u64,
+}
+
+#[derive(Serialize, Default, Debug, Clone, PartialEq)]
+struct BlobRow {
+    store: String,
+    blobs: u64,
+    bytes: u64,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    let interval = Duration::from_secs(args.interval.max(1));
+    DaemonClient::with_connect(&args.space, |client| {
+        let mut previous: Option<(TopView, Instant)> = None;
+        loop {
+            let view = build_view(&vos::metrics::parse(&client.metrics()?));
+            if output::is_json() {
+                output::print_json(&view);
+                return Ok(());
+            }
+            let now = Instant::now();
+            if !args.once {
+                // Clear the screen and home the cursor before each redraw.
+                print!("\x1b[2J\x1b[H");
+            }
+            let prev = previous
+                .as_ref()
+                .map(|(view, at)| (view, now.duration_since(*at)));
+            print_view(&args.space, &view, prev, interval, args.once);
+            if args.once {
+                return Ok(());
+            }
+            previous = Some((view, now));
+            std::thread::sleep(interval);
+        }
+    })
+}
+
+/// Fold a scrape's samples into the tables `space top` renders. Unknown
+/// metric names are ignored so an older `vosx` reads a newer daemon.
+fn build_view(samples: &[Sample]) -> TopView {
+    let mut view = TopView::default();
+    let mut agents: BTreeMap<String, AgentRow> = BTreeMap::new();
+    let mut raft: BTreeMap<String, RaftRow> = BTreeMap::new();
+    let mut crdt: BTreeMap<String, CrdtRow> = BTreeMap::new();
+    let mut frames: BTreeMap<String, FrameRow> = BTreeMap::new();
+    let mut blobs: BTreeMap<String, BlobRow> = BTreeMap::new();
+
+    for sample in samples {
+        let value = sample.value.max(0.0) as u64;
+        let label = |key: &str| sample.label(key).unwrap_or_default().to_string();
+        match sample.name.as_str() {
+            "vos_node_info" => view.node = sample.label("prefix").map(str::to_string),
+            "vos_network_peers" => view.peers = Some(value),
+            "vos_agent_dispatches_total"
+            | "vos_agent_gas_total"
+            | "vos_agent_queue_depth"
+            | "vos_root_backlog" => {
+                let row = agents.entry(label("id")).or_insert_with(|| AgentRow {
+                    agent: label("agent"),
+                    id: label("id"),
+                    ..AgentRow::default()
+                });
+                match (
+                    sample.name.as_str(),
+                    sample.label("kind"),
+                    sample.label("queue"),
+                ) {
+                    ("vos_agent_dispatches_total", Some("tell"), _) => row.tells = value,
+                    ("vos_agent_dispatches_total", Some("invoke"), _) => row.invokes = value,
+                    ("vos_agent_gas_total", ..) => row.gas = value,
+                    ("vos_agent_queue_depth", ..) => row.queued = value,
+                    ("vos_root_backlog", _, Some("inbox")) => {
+                        row.root_backlog.get_or_insert_default().0 = value
+                    }
+                    ("vos_root_backlog", _, Some("ingress")) => {
+                        row.root_backlog.get_or_insert_default().1 = value
+                    }
+                    _ => {}
+                }
+            }
+            name if name.starts_with("vos_raft_") => {
+                let row = raft.entry(label("group")).or_insert_with(|| RaftRow {
+                    agent: label("agent"),
+                    group: label("group"),
+                    ..RaftRow::default()
+                });
+                match name {
+                    "vos_raft_term" => row.term = value,
+                    "vos_raft_commit_index" => row.commit_index = value,
+                    "vos_raft_last_applied" => row.last_applied = value,
+                    "vos_raft_apply_lag" => row.apply_lag = value,
+                    "vos_raft_is_leader" => row.leader = value != 0,
+                    "vos_raft_members" => row.members = value,
+                    _ => {}
+                }
+            }
+            name if name.starts_with("vos_crdt_") => {
+                let row = crdt.entry(label("group")).or_insert_with(|| CrdtRow {
+                    agent: label("agent"),
+                    group: label("group"),
+                    ..CrdtRow::default()
+                });
+                match name {
+                    "vos_crdt_heads" => row.heads = value,
+                    "vos_crdt_merged_nodes_total" => row.merged = value,
+                    "vos_crdt_sync_backlog" => row.backlog = value,
+                    _ => {}
+                }
+            }
+            name @ ("vos_network_frames_total" | "vos_network_frame_bytes_total") => {
+                let row = frames.entry(label("kind")).or_insert_with(|| FrameRow {
+                    kind: label("kind"),
+                    ..FrameRow::default()
+                });
+                let bytes = name == "vos_network_frame_bytes_total";
+                match (sample.label("direction"), bytes) {
+                    (Some("sent"), false) => row.sent_frames = value,
+                    (Some("sent"), true) => row.sent_bytes = value,
+                    (Some("received"), false) => row.received_frames = value,
+                    (Some("received"), true) => row.received_bytes = value,
+                    _ => {}
+                }
+            }
+            name @ ("vos_blob_store_bytes" | "vos_blob_store_blobs") => {
+                let row = blobs.entry(label("store")).or_insert_with(|| BlobRow {
+                    store: label("store"),
+                    ..BlobRow::default()
+                });
+                if name == "vos_blob_store_bytes" {
+                    row.bytes = value;
+                } else {
+                    row.blobs = value;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    view.agents = agents.into_values().collect();
+    // Busiest first, like `top`: the agents doing the most work lead.
+    view.agents
+        .sort_by(|a, b| b.dispatches().cmp(&a.dispatches()).then(a.id.cmp(&b.id)));
+    view.raft = raft.into_values().collect();
+    view.crdt = crdt.into_values().collect();
+    view.frames = frames.into_values().collect();
+    view.frames.sort_by(|a, b| {
+        let total = |f: &FrameRow| f.sent_bytes.saturating_add(f.received_bytes);
+        total(b).cmp(&total(a)).then(a.kind.cmp(&b.kind))
+    });
+    view.blobs = blobs.into_values().collect();
+    view
+}
+
+/// Dispatches per second for `row` since the previous scrape, when there
+/// was one and the agent was already in it.
+fn dispatch_rate(row: &AgentRow, previous: Option<(&TopView, Duration)>) -> Option<f64> {
+    let (view, elapsed) = previous?;
+    let before = view.agents.iter().find(|a| a.id == row.id)?;
+    let secs = elapsed.as_secs_f64();
+    (secs > 0.0).then(|| row.dispatches().saturating_sub(before.dispatches()) as f64 / secs)
+}
+
+fn print_view(
+    space: &str,
+    view: &TopView,
+    previous: Option<(&TopView, Duration)>,
+    interval: Duration,
+    once: bool,
+) {
+    let node = view.node.as_deref().unwrap_or("?");
+    let peers = view
+        .peers
+        .map_or_else(|| "-".to_string(), |peers| peers.to_string());
+    if once {
+        println!("space {space}  node {node}  peers {peers}");
+    } else {
+        println!(
+            "space {space}  node {node}  peers {peers}  (every {}s, Ctrl-C to quit)",
+            interval.as_secs(),
+        );
+    }
+
+    println!();
+    println!(
+        "{:<20}  {:<10}  {:>9}  {:>9}  {:>8}  {:>14}  {:>6}  BACKLOG",
+        "AGENT", "ID", "TELLS", "INVOKES", "DISP/S", "GAS", "QUEUE",
+    );
+    for row in &view.agents {
+        let rate = dispatch_rate(row, previous).map_or_else(|| "-".into(), |r| format!("{r:.1}"));
+        let backlog = row.root_backlog.map_or_else(
+            || "-".into(),
+            |(inbox, ingress)| format!("{inbox}+{ingress}"),
+        );
+        println!(
+            "{:<20}  {:<10}  {:>9}  {:>9}  {:>8}  {:>14}  {:>6}  {backlog}",
+            truncate(&row.agent, 20),
+            row.id,
+            row.tells,
+            row.invokes,
+            rate,
+            row.gas,
+            row.queued,
+        );
+    }
+
+    if !view.raft.is_empty() {
+        println!();
+        println!(
+            "{:<20}  {:<12}  {:>6}  {:>9}  {:>9}  {:>6}  {:<8}  MEMBERS",
+            "RAFT", "GROUP", "TERM", "COMMIT", "APPLIED", "LAG", "ROLE",
+        );
+        for row in &view.raft {
+            println!(
+                "{:<20}  {:<12}  {:>6}  {:>9}  {:>9}  {:>6}  {:<8}  {}",
+                truncate(&row.agent, 20),
+                truncate(&row.group, 12),
+                row.term,
+                row.commit_index,
+                row.last_applied,
+                row.apply_lag,
+                if row.leader { "leader" } else { "follower" },
+                row.members,
+            );
+        }
+    }
+
+    if !view.crdt.is_empty() {
+        println!();
+        println!(
+            "{:<20}  {:<12}  {:>6}  {:>9}  BACKLOG",
+            "CRDT", "GROUP", "HEADS", "MERGED",
+        );
+        for row in &view.crdt {
+            println!(
+                "{:<20}  {:<12}  {:>6}  {:>9}  {}",
+                truncate(&row.agent, 20),
+                truncate(&row.group, 12),
+                row.heads,
+                row.merged,
+                row.backlog,
+            );
+        }
+    }
+
+    if !view.frames.is_empty() {
+        println!();
+        println!(
+            "{:<24}  {:>9}  {:>12}  {:>9}  {:>12}",
+            "FRAME", "SENT", "SENT B", "RECV", "RECV B",
+        );
+        for row in &view.frames {
+            println!(
+                "{:<24}  {:>9}  {:>12}  {:>9}  {:>12}",
+                truncate(&row.kind, 24),
+                row.sent_frames,
+                row.sent_bytes,
+                row.received_frames,
+                row.received_bytes,
+            );
+        }
+    }
+
+    if !view.blobs.is_empty() {
+        println!();
+        println!("{:<10}  {:>9}  {:>14}", "BLOBS", "COUNT", "BYTES");
+        for row in &view.blobs {
+            println!("{:<10}  {:>9}  {:>14}", row.store, row.blobs, row.bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRAPE: &str = r#"# HELP vos_node_info Always 1; the prefix label identifies this node.
+# TYPE vos_node_info gauge
+vos_node_info{prefix="0x1a2b"} 1
+vos_agent_dispatches_total{agent="chat",id="0x1a2b0007",kind="tell"} 3
+vos_agent_dispatches_total{agent="chat",id="0x1a2b0007",kind="invoke"} 4
+vos_agent_dispatches_total{agent="registry",id="0x1a2b0000",kind="tell"} 1
+vos_agent_dispatches_total{agent="registry",id="0x1a2b0000",kind="invoke"} 20
+vos_agent_gas_total{agent="chat",id="0x1a2b0007"} 900
+vos_agent_queue_depth{agent="chat",id="0x1a2b0007"} 2
+vos_root_backlog{agent="chat",id="0x1a2b0007",queue="inbox"} 5
+vos_root_backlog{agent="chat",id="0x1a2b0007",queue="ingress"} 1
+vos_network_peers 2
+vos_network_frames_total{kind="hello",direction="sent"} 2
+vos_network_frame_bytes_total{kind="hello",direction="sent"} 128
+vos_network_frames_total{kind="hello",direction="received"} 3
+vos_raft_term{group="aa",agent="ledger"} 7
+vos_raft_commit_index{group="aa",agent="ledger"} 40
+vos_raft_last_applied{group="aa",agent="ledger"} 38
+vos_raft_apply_lag{group="aa",agent="ledger"} 2
+vos_raft_is_leader{group="aa",agent="ledger"} 1
+vos_raft_members{group="aa",agent="ledger"} 3
+vos_crdt_heads{group="bb",agent="notes"} 2
+vos_crdt_sync_backlog{group="bb",agent="notes"} 6
+vos_blob_store_bytes{store="program"} 4096
+vos_blob_store_blobs{store="program"} 2
+vos_future_metric 9
+"#;
+
+    #[test]
+    fn scrape_folds_into_tables() {
+        let view = build_view(&vos::metrics::parse(SCRAPE));
+        assert_eq!(view.node.as_deref(), Some("0x1a2b"));
+        assert_eq!(view.peers, Some(2));
+
+        // Busiest agent first.
+        let ids: Vec<&str> = view.agents.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, ["0x1a2b0000", "0x1a2b0007"]);
+        let chat = &view.agents[1];
+        assert_eq!(
+            (chat.agent.as_str(), chat.tells, chat.invokes),
+            ("chat", 3, 4)
+        );
+        assert_eq!((chat.gas, chat.queued), (900, 2));
+        assert_eq!(chat.root_backlog, Some((5, 1)));
+        assert_eq!(view.agents[0].root_backlog, None);
+
+        assert_eq!(
+            view.raft,
+            vec![RaftRow {
+                agent: "ledger".into(),
+                group: "aa".into(),
+                term: 7,
+                commit_index: 40,
+                last_applied: 38,
+                apply_lag: 2,
+                leader: true,
+                members: 3,
+            }]
+        );
+        assert_eq!((view.crdt[0].heads, view.crdt[0].backlog), (2, 6));
+        assert_eq!(
+            view.frames[0],
+            FrameRow {
+                kind: "hello".into(),
+                sent_frames: 2,
+                sent_bytes: 128,
+                received_frames: 3,
+                received_bytes: 0,
+            }
+        );
+        assert_eq!((view.blobs[0].blobs, view.blobs[0].bytes), (2, 4096));
+    }
+
+    #[test]
+    fn dispatch_rate_needs_a_previous_scrape_of_the_same_agent() {
+        let view = build_view(&vos::metrics::parse(SCRAPE));
+        let chat = &view.agents[1];
+        assert_eq!(dispatch_rate(chat, None), None);
+
+        let mut earlier = view.clone();
+        earlier.agents[1].invokes = 0;
+        let rate = dispatch_rate(chat, Some((&earlier, Duration::from_secs(2))));
+        assert_eq!(rate, Some(2.0));
+
+        earlier.agents.retain(|a| a.id != chat.id);
+        assert_eq!(
+            dispatch_rate(chat, Some((&earlier, Duration::from_secs(2)))),
+            None
+        );
+    }
+}
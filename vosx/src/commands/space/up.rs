@@ -6,7 +6,7 @@
 //! with `Consistency::Crdt`, and hands the node off to
 //! `run_forever` (or `run` for `--once`).
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -30,6 +30,8 @@ pub struct Args {
     pub once: bool,
     pub listen: Vec<String>,
     pub connect: Vec<String>,
+    pub relay: Vec<String>,
+    pub relay_server: bool,
     pub service_pvm: Option<PathBuf>,
     pub production_trust_socket: Option<PathBuf>,
     pub allow_v2_conformance: bool,
@@ -262,7 +264,36 @@ fn ensure_v2_role_authority(node: &VosNode, space_id: [u8; 32]) -> anyhow::Resul
     Ok(())
 }
 
+/// Hooks a `vosx node up` host hands a space it runs in-process. Only the
+/// Unix host constructs one.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub(crate) struct Hosted {
+    /// Receives the space's shutdown flag once its endpoint is published;
+    /// the host flips it to detach the space.
+    pub attached: std::sync::mpsc::Sender<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+}
+
 pub fn run(args: Args) -> anyhow::Result<()> {
+    // With a `vosx node up` host running, `space up` attaches the space to
+    // it instead of starting a daemon of its own. The target is resolved
+    // here, where relative recipe paths and `-` (stdin) still mean
+    // something; the host then opens it by name.
+    if !args.once && crate::commands::node::is_running() {
+        validate_v2_trust_mode(
+            args.service_pvm.is_some(),
+            args.production_trust_socket.is_some(),
+            args.allow_v2_conformance,
+        )?;
+        let lookup = resolve_up_target(&args)?;
+        return crate::commands::node::attach(&lookup, &args);
+    }
+    serve(args, None)
+}
+
+/// Run one space's daemon until shutdown: the whole of `space up`, and
+/// what a `vosx node up` host runs on a thread per attached space. A
+/// hosted space leaves signal handling to its host.
+pub(crate) fn serve(args: Args, host: Option<Hosted>) -> anyhow::Result<()> {
     // Validate and open the requested v2 execution profile before resolving
     // the trivalent target. Target resolution may scaffold a recipe space,
     // create its node identity, or persist an invite bearer, so an invalid
@@ -394,7 +425,14 @@ pub fn run(args: Args) -> anyhow::Result<()> {
     // Always attach a libp2p network — even local-only spaces
     // bind a loopback port so client commands (`space publish`,
     // `space install`, etc.) have an endpoint to dial.
-    let network = build_network_for_daemon(entry, &data_dir, &args.listen, &args.connect)?;
+    let network = build_network_for_daemon(
+        entry,
+        &data_dir,
+        &args.listen,
+        &args.connect,
+        &args.relay,
+        args.relay_server,
+    )?;
     let local_prefix = network.local_prefix();
 
     // Serve program blobs (actor ELFs) to space members from the same
@@ -491,6 +529,12 @@ pub fn run(args: Args) -> anyhow::Result<()> {
         );
     }
 
+    // `space backup --online`: the operator asks this daemon to copy its
+    // data directory under the write barrier instead of stopping it.
+    let snapshot_source = data_dir.clone();
+    node.set_snapshot_handler(move |output: &str| {
+        crate::commands::space::backup::serve_snapshot(&snapshot_source, output)
+    });
     node.attach_network(network);
 
     tracing::info!(
@@ -526,6 +570,9 @@ pub fn run(args: Args) -> anyhow::Result<()> {
     let local_cfg = subscriptions::load(&data_dir).unwrap_or_default();
     let agent_policies = agent_policies_from_local(&local_cfg)?;
     let device_secret_agents = device_secret_agents_from_local(&local_cfg);
+    if let Some(addr) = &local_cfg.metrics {
+        spawn_metrics_exporter(addr, node.metrics_handle())?;
+    }
     // Shared across bootstrap and runtime reconciliation. Bootstrap opens at
     // most one v2 root synchronously; remaining rows inherit the same global
     // window/backoff and are opened only after the endpoint is published.
@@ -556,6 +603,14 @@ pub fn run(args: Args) -> anyhow::Result<()> {
         &mut v2_registration_backoff,
     )?;
 
+    // Multi-root batches journal under the data dir. A batch that was
+    // undecided when the daemon stopped is compensated now (presumed abort);
+    // one whose root is not open yet stays journaled for the tick's sweep.
+    node.attach_root_batch_journal(vos::v2::FileRootBatchJournalV2::new(
+        data_dir.join("root-batches"),
+    ));
+    settle_root_batches(&node, vos::v2::RootBatchRecoveryV2::Restart);
+
     // Provision device-local secret seeds for agents that declared
     // `device_secret = true` (the messenger's MLS CSPRNG root). Runs after
     // spawn so the targets are live; the seed never touches the replicated
@@ -569,7 +624,17 @@ pub fn run(args: Args) -> anyhow::Result<()> {
     // Wait for the swarm to bind, then publish endpoint info
     // so client commands (`space publish`, `space install`, …)
     // can dial us. Removed in the cleanup block at the end.
-    publish_endpoint(&node, &data_dir, local_prefix, extension_caps)?;
+    publish_endpoint(
+        &node,
+        &data_dir,
+        local_prefix,
+        extension_caps,
+        host.is_some(),
+        production_trust.is_some(),
+    )?;
+    if let Some(host) = &host {
+        let _ = host.attached.send(node.shutdown_handle());
+    }
 
     if args.once {
         // The redeem loop and spawn-reconcile live only in the
@@ -595,8 +660,10 @@ pub fn run(args: Args) -> anyhow::Result<()> {
         // without losing in-flight commits or leaking the
         // endpoint file. The handler flips the same
         // AtomicBool that `run_forever`'s poll loop watches.
-        crate::shutdown::install(node.shutdown_handle());
-        tracing::info!("running until shutdown (Ctrl-C / SIGTERM)");
+        if host.is_none() {
+            crate::shutdown::install(node.shutdown_handle());
+            tracing::info!("running until shutdown (Ctrl-C / SIGTERM)");
+        }
 
         // Spawn-reconcile from the router tick hook: agents
         // installed after boot — `space install`, `dev new`, an
@@ -701,6 +768,7 @@ pub fn run(args: Args) -> anyhow::Result<()> {
                 }
                 Err(e) => tracing::debug!("spawn-reconcile: {e}"),
             }
+            settle_root_batches(n, vos::v2::RootBatchRecoveryV2::Deadline);
         });
     }
 
@@ -732,6 +800,22 @@ pub fn run(args: Args) -> anyhow::Result<()> {
 /// across a timeout and is retried on the next reconciliation tick.
 const REDEEM_REGISTRY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(35);
 
+/// Compensate or finish journaled multi-root batches. A batch whose root is
+/// unavailable stays journaled, so failures are retried by the next sweep.
+fn settle_root_batches(node: &VosNode, recovery: vos::v2::RootBatchRecoveryV2) {
+    for result in node.recover_root_batches(recovery) {
+        match result {
+            Ok(record) if record.is_settled() => tracing::info!(
+                batch = %hex::encode(record.batch.id.0),
+                decision = ?record.decision,
+                "root batch settled",
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::debug!("root batch recovery: {e}"),
+        }
+    }
+}
+
 // ── Trivalent `up` positional (decision 1) ───────────────────────────
 
 /// Resolve `args.query` to the space lookup key, handling the recipe /
@@ -845,6 +929,17 @@ fn resolve_token(token_str: &str) -> anyhow::Result<String> {
     }
     let data_dir = PathBuf::from(&entry.data_dir);
     index.save()?;
+    // Relays are node-local reachability policy, so they land in
+    // local.toml next to `listen` rather than on the index entry.
+    if !payload.relays.is_empty() {
+        let mut local = subscriptions::load(&data_dir)?;
+        for r in &payload.relays {
+            if !local.relays.contains(r) {
+                local.relays.push(r.clone());
+            }
+        }
+        subscriptions::save(&data_dir, &local)?;
+    }
     save_pending_token(&data_dir, token_str)?;
     // Return the space_id, not the name: the token's space is unambiguous
     // by id, and a name can collide with another already-known space.
@@ -944,6 +1039,7 @@ fn register_extensions_from_local(
             cap_policy: e.cap_policy.clone(),
             relay_unauthenticated: e.relay_unauthenticated,
             intra_caps: e.intra_caps.clone(),
+            dispatch_as: e.dispatch_as,
             tick_ms: e.tick_ms,
         };
         let effective = reconcile::register_extension(
@@ -973,6 +1069,128 @@ struct TimedNode<'a> {
     timeout: std::time::Duration,
 }
 
+/// Serve the node's Prometheus text exposition on `addr` (`metrics` in
+/// `local.toml`) for the life of the daemon. Each scrape gets its own
+/// thread, so a stalled client can't hold up the next scraper, up to
+/// [`SCRAPE_CONNECTIONS`] at once; anything but `GET /metrics` gets a 404.
+fn spawn_metrics_exporter(addr: &str, metrics: vos::node::MetricsHandle) -> anyhow::Result<()> {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    let listener = std::net::TcpListener::bind(addr)
+        .map_err(|e| anyhow::anyhow!("bind metrics exporter on {addr}: {e}"))?;
+    tracing::info!("metrics on http://{}/metrics", listener.local_addr()?);
+    let open = Arc::new(AtomicUsize::new(0));
+    std::thread::Builder::new()
+        .name("vos-metrics".into())
+        .spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if open.fetch_add(1, Ordering::AcqRel) >= SCRAPE_CONNECTIONS {
+                    open.fetch_sub(1, Ordering::AcqRel);
+                    tracing::debug!("metrics scrape: too many open connections, dropped");
+                    continue;
+                }
+                let (metrics, open) = (metrics.clone(), open.clone());
+                let spawned = std::thread::Builder::new()
+                    .name("vos-metrics-scrape".into())
+                    .spawn(move || {
+                        if let Err(e) = serve_scrape(stream, &metrics) {
+                            tracing::debug!("metrics scrape: {e}");
+                        }
+                        open.fetch_sub(1, Ordering::AcqRel);
+                    });
+                if let Err(e) = spawned {
+                    tracing::debug!("metrics scrape: spawn: {e}");
+                }
+            }
+        })?;
+    Ok(())
+}
+
+/// Scrape connections served at once; more are closed unanswered.
+const SCRAPE_CONNECTIONS: usize = 16;
+/// Whole-connection budget for a scrape: request in, exposition out.
+const SCRAPE_DEADLINE: std::time::Duration = std::time::Duration::from_secs(5);
+/// Cap on the request line plus headers; a scraper sends a few hundred bytes.
+const SCRAPE_HEAD_LIMIT: u64 = 8 * 1024;
+
+/// A scrape connection whose reads share one deadline, so a client
+/// trickling a byte at a time still runs out of time.
+struct ScrapeStream {
+    stream: std::net::TcpStream,
+    deadline: std::time::Instant,
+}
+
+impl ScrapeStream {
+    fn remaining(&self) -> std::io::Result<std::time::Duration> {
+        let left = self
+            .deadline
+            .saturating_duration_since(std::time::Instant::now());
+        if left.is_zero() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "scrape deadline passed",
+            ));
+        }
+        Ok(left)
+    }
+}
+
+impl std::io::Read for ScrapeStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let left = self.remaining()?;
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+fn serve_scrape(
+    stream: std::net::TcpStream,
+    metrics: &vos::node::MetricsHandle,
+) -> std::io::Result<()> {
+    use std::io::{BufRead, Read, Write};
+    let mut conn = ScrapeStream {
+        stream,
+        deadline: std::time::Instant::now() + SCRAPE_DEADLINE,
+    };
+    let mut reader = std::io::BufReader::new(&mut conn).take(SCRAPE_HEAD_LIMIT);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers up to the blank line (or the head cap); the body
+    // (if any) is ignored.
+    let mut header = String::new();
+    loop {
+        header.clear();
+        if reader.read_line(&mut header)? <= 2 {
+            break;
+        }
+    }
+    let truncated = reader.limit() == 0;
+    drop(reader);
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+    let (status, body) = if truncated {
+        (
+            "431 Request Header Fields Too Large",
+            "request too large\n".to_string(),
+        )
+    } else if method == "GET" && path == "/metrics" {
+        ("200 OK", metrics.render())
+    } else {
+        ("404 Not Found", "not found\n".to_string())
+    };
+    let left = conn.remaining()?;
+    conn.stream.set_write_timeout(Some(left))?;
+    write!(
+        conn.stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len(),
+    )?;
+    conn.stream.flush()
+}
+
 fn decode_timed_node_reply(outcome: Option<Vec<u8>>) -> Result<vos::value::Value, ClientError> {
     match outcome {
         Some(b) if b.len() == 5 && b[0] == vos::STATUS_FORBIDDEN && b[1..] == [0, 0, 0, 0] => {
@@ -1205,6 +1423,8 @@ fn build_network_for_daemon(
     data_dir: &std::path::Path,
     listen_override: &[String],
     connect_extra: &[String],
+    relay_extra: &[String],
+    relay_server: bool,
 ) -> anyhow::Result<vos::network::Network> {
     let parse = |s: &str, kind: &str| -> anyhow::Result<libp2p::Multiaddr> {
         libp2p::Multiaddr::from_str(s)
@@ -1235,6 +1455,13 @@ fn build_network_for_daemon(
     for s in connect_extra {
         bootstrap.push(parse(s, "connect")?);
     }
+    let relays: Vec<libp2p::Multiaddr> = local_cfg
+        .relays
+        .iter()
+        .chain(relay_extra)
+        .map(|s| parse(s, "relay"))
+        .collect::<anyhow::Result<_>>()?;
+    let relay_server = relay_server || local_cfg.relay_server;
 
     let key_path = data_dir.join("node.key");
     let key_bytes = std::fs::read(&key_path)
@@ -1251,29 +1478,122 @@ fn build_network_for_daemon(
     // it so test daemons don't latch onto unrelated libp2p apps
     // (IPFS / Substrate / etc.) on the dev machine.
     let auto_dial_mdns = std::env::var("VOSX_DISABLE_MDNS").is_err();
+    // Announce the space (and its hyperspace, if any) on the DHT so
+    // members beyond the LAN and the saved bootnodes find this node.
+    let mut discovery_keys = Vec::new();
+    if let Some(space_id) = hex::decode(&entry.id)
+        .ok()
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+    {
+        discovery_keys.push(space_id);
+    }
+    if !entry.hyperspace.is_empty() {
+        discovery_keys.push(derive_hyperspace_id(&entry.hyperspace));
+    }
     Ok(vos::network::Network::start(vos::network::NetworkConfig {
         keypair,
         local_prefix,
         listen,
         bootstrap,
         auto_dial_mdns,
+        discovery_keys,
+        relays,
+        relay_server,
     }))
 }
 
 /// Node-local per-agent policy from the recipe (never replicated): the
-/// periodic `tick_ms` and the parsed `intra_caps` relay bound.
+/// periodic `tick_ms`, the parsed `intra_caps` relay bound and a v2 root's
+/// work fairness.
 #[derive(Default, Clone)]
 struct AgentLocalPolicy {
     tick_ms: Option<u64>,
     intra_caps: Vec<vos::IntraCap>,
     device_secret: bool,
+    fairness: Option<FairnessPolicy>,
+}
+
+/// A parsed `[agent.fairness]` table. Calling agents stay named until the
+/// root's config is built, where the installed catalog resolves each to
+/// its root actor.
+#[derive(Default, Clone, Debug, PartialEq)]
+struct FairnessPolicy {
+    weight: Option<u32>,
+    members: BTreeMap<vos::v2::SubjectId, vos::v2::WorkPriorityV2>,
+    actors: BTreeMap<String, vos::v2::WorkPriorityV2>,
+}
+
+fn parse_work_priority(name: &str, class: &str) -> anyhow::Result<vos::v2::WorkPriorityV2> {
+    Ok(match class {
+        "operator" => vos::v2::WorkPriorityV2::Operator,
+        "admin" => vos::v2::WorkPriorityV2::Admin,
+        "normal" => vos::v2::WorkPriorityV2::Normal,
+        other => anyhow::bail!(
+            "agent '{name}': fairness class '{other}' is not operator, admin or normal"
+        ),
+    })
+}
+
+fn fairness_policy_from_local(
+    name: &str,
+    local: &subscriptions::FairnessLocal,
+) -> anyhow::Result<FairnessPolicy> {
+    if local.weight == Some(0) {
+        anyhow::bail!("agent '{name}': fairness weight must be at least 1");
+    }
+    let mut members = BTreeMap::new();
+    for (peer, class) in &local.members {
+        let peer_id = hex::decode(peer)
+            .map_err(|e| anyhow::anyhow!("agent '{name}': fairness member '{peer}': {e}"))?;
+        members.insert(
+            vos::v2::SubjectId::of_authenticated_peer(&peer_id),
+            parse_work_priority(name, class)?,
+        );
+    }
+    let mut actors = BTreeMap::new();
+    for (caller, class) in &local.actors {
+        actors.insert(caller.clone(), parse_work_priority(name, class)?);
+    }
+    Ok(FairnessPolicy {
+        weight: local.weight,
+        members,
+        actors,
+    })
+}
+
+/// Resolve a root's fairness against the installed catalog. `None` while a
+/// named calling agent is not installed yet.
+fn work_fairness_for_root(
+    space: vos::v2::SpaceId,
+    root_actor: vos::v2::ActorId,
+    fairness: Option<&FairnessPolicy>,
+    installed_agents: &[vos::registry::AgentRow],
+) -> Option<vos::v2::WorkFairnessPolicyV2> {
+    let mut policy = vos::v2::WorkFairnessPolicyV2::default();
+    let Some(fairness) = fairness else {
+        return Some(policy);
+    };
+    if let Some(weight) = fairness.weight {
+        policy.actor_weights.insert(root_actor, weight);
+    }
+    policy.member_priorities = fairness.members.clone();
+    for (caller, priority) in &fairness.actors {
+        let row = installed_agents
+            .iter()
+            .find(|row| &row.instance_name == caller)?;
+        let service = v2_root_service_id(space, caller, row.replication_id);
+        policy
+            .actor_priorities
+            .insert(v2_root_actor_id(service, caller), *priority);
+    }
+    Some(policy)
 }
 
 type AgentPolicies = std::collections::BTreeMap<String, AgentLocalPolicy>;
 
-/// Collect the `tick_ms` / `intra_caps` policy for each agent from
-/// `local.toml`. Parses the `intra_caps` strings eagerly so a malformed
-/// cap fails the boot (like the extension path).
+/// Collect the `tick_ms` / `intra_caps` / `fairness` policy for each agent
+/// from `local.toml`. Parses the `intra_caps` strings and fairness classes
+/// eagerly so a malformed entry fails the boot (like the extension path).
 fn agent_policies_from_local(cfg: &subscriptions::LocalConfig) -> anyhow::Result<AgentPolicies> {
     let mut map = AgentPolicies::new();
     for (name, a) in &cfg.agents {
@@ -1285,13 +1605,19 @@ fn agent_policies_from_local(cfg: &subscriptions::LocalConfig) -> anyhow::Result
             );
         }
         let tick_ms = a.tick_ms.filter(|ms| *ms > 0);
-        if tick_ms.is_some() || !intra_caps.is_empty() || a.device_secret {
+        let fairness = a
+            .fairness
+            .as_ref()
+            .map(|fairness| fairness_policy_from_local(name, fairness))
+            .transpose()?;
+        if tick_ms.is_some() || !intra_caps.is_empty() || a.device_secret || fairness.is_some() {
             map.insert(
                 name.clone(),
                 AgentLocalPolicy {
                     tick_ms,
                     intra_caps,
                     device_secret: a.device_secret,
+                    fairness,
                 },
             );
         }
@@ -1393,6 +1719,8 @@ fn publish_endpoint(
     data_dir: &std::path::Path,
     prefix: u16,
     extensions: Vec<crate::commands::space::endpoint::ExtensionCaps>,
+    hosted: bool,
+    production: bool,
 ) -> anyhow::Result<()> {
     use std::time::{Duration, Instant};
 
@@ -1418,6 +1746,8 @@ fn publish_endpoint(
         prefix,
         pid: std::process::id(),
         extensions,
+        hosted,
+        production,
     };
     crate::commands::space::endpoint::write(data_dir, &ep)?;
     tracing::info!("endpoint published on {} address(es)", multiaddrs.len());
@@ -1553,11 +1883,15 @@ fn spawn_installed_agents(
                 continue;
             }
             let db_path = raft_db_path_for_row(data_dir, svc_id, &prepared);
-            match raft_members_for_row(node, &db_path, &a, local_prefix, &mut boot_grace) {
+            let policy = is_v2
+                .then(|| production_trust.as_ref().map(|trust| trust.policy_id()))
+                .flatten();
+            match raft_members_for_row(node, &db_path, &a, local_prefix, &mut boot_grace, policy) {
                 Ok(RaftSeed::Join {
                     leader,
                     known,
                     voter_peer_ids,
+                    witnesses,
                 }) if !is_v2 => {
                     let Some(network) = node.network() else {
                         tracing::info!(
@@ -1573,16 +1907,26 @@ fn spawn_installed_agents(
                         leader,
                         known,
                         voter_peer_ids,
+                        witnesses,
                     )? {
                         seed @ RaftSeed::Members { .. } => Some(seed),
                         RaftSeed::Defer(reason) => {
                             tracing::info!("agent '{}' (raft) deferred: {reason}", a.instance_name);
                             continue;
                         }
-                        RaftSeed::Join { .. } => unreachable!(),
+                        RaftSeed::Join { .. } | RaftSeed::Witness { .. } => unreachable!(),
                     }
                 }
                 Ok(seed @ (RaftSeed::Members { .. } | RaftSeed::Join { .. })) => Some(seed),
+                Ok(RaftSeed::Witness { members, witnesses }) => {
+                    match host_raft_witness(node, &db_path, &a, local_prefix, members, witnesses) {
+                        Ok(()) => tracing::info!("agent '{}' (raft) witnessed", a.instance_name),
+                        Err(e) => {
+                            tracing::warn!("agent '{}' (raft) deferred: {e}", a.instance_name)
+                        }
+                    }
+                    continue;
+                }
                 Ok(RaftSeed::Defer(reason)) => {
                     tracing::info!(
                         "agent '{}' (raft) deferred to the runtime reconciler: {reason}",
@@ -1601,8 +1945,12 @@ fn spawn_installed_agents(
         match prepared {
             RowConfig::Ready(cfg) => {
                 let mut cfg = *cfg;
-                if let Some(RaftSeed::Members { members, .. }) = raft_seed {
+                if let Some(RaftSeed::Members {
+                    members, witnesses, ..
+                }) = raft_seed
+                {
                     cfg.members = members;
+                    cfg.witnesses = witnesses;
                 }
                 let id = node.register_at_id(cfg, svc_id);
                 tracing::info!(
@@ -1891,7 +2239,7 @@ enum RoleAuthorityResolution {
     MissingAgent,
 }
 
-fn validate_exact_v2_package(
+pub(super) fn validate_exact_v2_package(
     exact_package: &[u8],
     instance_name: &str,
 ) -> anyhow::Result<vos::v2::VosPackageV2> {
@@ -2135,6 +2483,12 @@ fn agent_config_from_row(
         if !policy.intra_caps.is_empty() {
             cfg = cfg.with_intra_caps(policy.intra_caps.clone());
         }
+        if policy.fairness.is_some() {
+            tracing::warn!(
+                "agent '{}' declares work fairness, which only v2 roots schedule; ignoring",
+                a.instance_name,
+            );
+        }
     }
     Ok(RowConfig::Ready(Box::new(cfg)))
 }
@@ -2216,6 +2570,18 @@ fn v2_config_from_row(
     } else {
         Vec::new()
     };
+    let Some(work_fairness) = work_fairness_for_root(
+        space,
+        root_actor,
+        policies
+            .get(&row.instance_name)
+            .and_then(|policy| policy.fairness.as_ref()),
+        installed_agents,
+    ) else {
+        return Ok(RowConfig::Deferred(
+            "an agent named by [agent.fairness] is not installed yet".into(),
+        ));
+    };
     let state_path = data_dir
         .join("v2-services")
         .join(format!("{}.image", hex::encode(root_service.0)));
@@ -2255,6 +2621,7 @@ fn v2_config_from_row(
         },
         refine_gas: 1_000_000_000,
         accumulate_gas: 5_000_000_000,
+        work_fairness,
     };
     config
         .validate()
@@ -2394,17 +2761,19 @@ fn register_v2_root_from_row(
             redb::Database::create(&raft_path)
                 .map_err(|error| anyhow::anyhow!("open {}: {error}", raft_path.display()))?,
         );
-        let make_config = |members, voter_peer_ids| vos::raft::RaftConfig {
+        let make_config = |members, voter_peer_ids, witnesses| vos::raft::RaftConfig {
             me: local_prefix,
             members,
             voter_peer_ids,
             replication_id,
+            witnesses,
             ..vos::raft::RaftConfig::default()
         };
         return match seed {
             RaftSeed::Members {
                 members,
                 voter_peer_ids,
+                witnesses,
             } => match production_trust {
                 Some(trust) => node
                     .register_v2_raft_root_at_id_production(
@@ -2412,7 +2781,7 @@ fn register_v2_root_from_row(
                         config,
                         backend,
                         db,
-                        make_config(members, voter_peer_ids),
+                        make_config(members, voter_peer_ids, witnesses),
                         svc_id,
                         network_reachable,
                         trust,
@@ -2430,7 +2799,7 @@ fn register_v2_root_from_row(
                         config,
                         backend,
                         db,
-                        make_config(members, voter_peer_ids),
+                        make_config(members, voter_peer_ids, witnesses),
                         svc_id,
                         network_reachable,
                     )
@@ -2440,13 +2809,14 @@ fn register_v2_root_from_row(
                 leader,
                 known,
                 voter_peer_ids,
+                witnesses,
             } => {
                 let network = node
                     .network()
                     .ok_or_else(|| anyhow::anyhow!("v2 Raft join requires an attached network"))?;
                 let promotion_name = instance_name.clone();
                 let promotion_voter_peer_ids = voter_peer_ids.clone();
-                let raft_config = make_config(known.clone(), voter_peer_ids);
+                let raft_config = make_config(known.clone(), voter_peer_ids, witnesses);
                 match production_trust {
                     Some(trust) => node
                         .register_v2_raft_root_at_id_after_local_attach_production(
@@ -2510,6 +2880,9 @@ fn register_v2_root_from_row(
             RaftSeed::Defer(reason) => Err(anyhow::anyhow!(
                 "v2 Raft root tree '{instance_name}' remains deferred: {reason}"
             )),
+            RaftSeed::Witness { .. } => Err(anyhow::anyhow!(
+                "v2 Raft root tree '{instance_name}' is witnessed here, not hosted"
+            )),
         };
     }
 
@@ -2638,7 +3011,9 @@ enum RaftPlan {
 }
 
 /// Pure decision table for one raft row. `voters` is the sorted,
-/// deduped `NODE_ROLE_VOTER` prefix set from the registry;
+/// deduped `NODE_ROLE_VOTER` + `NODE_ROLE_WITNESS` prefix set from
+/// the registry, and `witnesses` the log-less subset of it (a witness
+/// never bootstraps: it cannot lead);
 /// `anchored` means the agent's local db already records a member
 /// configuration (so the persisted config — not our seed — governs
 /// on spawn); `probes` holds the status answers from every OTHER
@@ -2648,6 +3023,7 @@ enum RaftPlan {
 fn decide_raft_spawn(
     local: u16,
     voters: &[u16],
+    witnesses: &[u16],
     anchored: bool,
     probes: &[(u16, vos::network::RaftStatusReply)],
     other_voters: usize,
@@ -2664,7 +3040,7 @@ fn decide_raft_spawn(
         // voter set just has to be non-empty to spawn the worker.
         return RaftPlan::Spawn(voters.to_vec());
     }
-    if voters == [local] {
+    if voters == [local] && !witnesses.contains(&local) {
         return RaftPlan::Bootstrap { contested: false };
     }
 
@@ -2702,7 +3078,14 @@ fn decide_raft_spawn(
     // can still re-genesis a group it can't see; the durable fix is
     // a bootstrap anchor in the registry row (with signed registry
     // ops), not reachable from this layer.
-    let smallest = *voters.iter().min().expect("voters contains local");
+    let Some(smallest) = voters
+        .iter()
+        .copied()
+        .filter(|v| !witnesses.contains(v))
+        .min()
+    else {
+        return RaftPlan::Defer("no full voter enrolled — a witness cannot bootstrap".into());
+    };
     if smallest != local {
         return RaftPlan::Defer(format!(
             "waiting for voter {smallest:#06x} to bootstrap the group",
@@ -2723,6 +3106,7 @@ enum RaftSeed {
     Members {
         members: Vec<u16>,
         voter_peer_ids: Vec<(u16, Vec<u8>)>,
+        witnesses: Vec<u16>,
     },
     /// A live group exists, but this replica is not a voter yet. The v2 path
     /// starts its worker and validates its route before sending the join;
@@ -2731,6 +3115,14 @@ enum RaftSeed {
         leader: u16,
         known: Vec<u16>,
         voter_peer_ids: Vec<(u16, Vec<u8>)>,
+        witnesses: Vec<u16>,
+    },
+    /// This node is an enrolled witness already counted in the group:
+    /// host a log-less worker (see [`host_raft_witness`]) instead of
+    /// spawning the agent.
+    Witness {
+        members: Vec<u16>,
+        witnesses: Vec<u16>,
     },
     Defer(String),
 }
@@ -2852,22 +3244,35 @@ fn raft_members_for_row(
     a: &vos::registry::AgentRow,
     local_prefix: u16,
     boot_grace: &mut BootGrace,
+    production_trust_policy: Option<vos::v2::Hash>,
 ) -> anyhow::Result<RaftSeed> {
-    use vos::registry::{MEMBER_KIND_NODE, NODE_ROLE_VOTER, RegistryRef};
+    use vos::registry::{MEMBER_KIND_NODE, NODE_ROLE_VOTER, NODE_ROLE_WITNESS, RegistryRef};
 
     let reg = RegistryRef::at(ServiceId::REGISTRY);
     let rows = vos::block_on(reg.members_all(&mut &*node))
         .map_err(|e| anyhow::anyhow!("query members: {e}"))?;
+    // Witnesses vote like any Raft member; they are only told apart
+    // so they never bootstrap and never host the agent.
+    let is_raft_member = |m: &vos::registry::MemberRow| {
+        m.kind == MEMBER_KIND_NODE && matches!(m.role, NODE_ROLE_VOTER | NODE_ROLE_WITNESS)
+    };
     let mut voters: Vec<u16> = rows
         .iter()
-        .filter(|m| m.kind == MEMBER_KIND_NODE && m.role == NODE_ROLE_VOTER)
+        .filter(|m| is_raft_member(m))
         .map(|m| m.prefix)
         .collect();
     voters.sort_unstable();
     voters.dedup();
+    let mut witnesses: Vec<u16> = rows
+        .iter()
+        .filter(|m| m.kind == MEMBER_KIND_NODE && m.role == NODE_ROLE_WITNESS)
+        .map(|m| m.prefix)
+        .collect();
+    witnesses.sort_unstable();
+    witnesses.dedup();
     let mut voter_peer_ids = rows
         .iter()
-        .filter(|member| member.kind == MEMBER_KIND_NODE && member.role == NODE_ROLE_VOTER)
+        .filter(|member| is_raft_member(member))
         .map(|member| (member.prefix, member.key.clone()))
         .collect::<Vec<_>>();
     voter_peer_ids.sort_by_key(|(prefix, _)| *prefix);
@@ -2913,14 +3318,33 @@ fn raft_members_for_row(
     }
 
     let grace_key = (a.instance_name.clone(), a.program_hash);
-    let plan = decide_raft_spawn(local_prefix, &voters, anchored, &probes, other_voters);
+    let plan = decide_raft_spawn(
+        local_prefix,
+        &voters,
+        &witnesses,
+        anchored,
+        &probes,
+        other_voters,
+    );
     if !matches!(plan, RaftPlan::Bootstrap { contested: true }) {
         boot_grace.remove(&grace_key);
     }
+    if witnesses.contains(&local_prefix) {
+        return witness_seed_for_row(
+            net.as_ref(),
+            a,
+            local_prefix,
+            plan,
+            witnesses,
+            voter_peer_ids,
+            production_trust_policy,
+        );
+    }
     match plan {
         RaftPlan::Spawn(members) => Ok(RaftSeed::Members {
             members,
             voter_peer_ids,
+            witnesses,
         }),
         RaftPlan::Defer(reason) => Ok(RaftSeed::Defer(reason)),
         RaftPlan::Bootstrap { contested } => {
@@ -2951,12 +3375,14 @@ fn raft_members_for_row(
             Ok(RaftSeed::Members {
                 members: vec![local_prefix],
                 voter_peer_ids,
+                witnesses,
             })
         }
         RaftPlan::Join { leader, known } => Ok(RaftSeed::Join {
             leader,
             known,
             voter_peer_ids,
+            witnesses,
         }),
     }
 }
@@ -3089,6 +3515,7 @@ fn join_raft_group(
     leader: u16,
     known: Vec<u16>,
     voter_peer_ids: Vec<(u16, Vec<u8>)>,
+    witnesses: Vec<u16>,
 ) -> anyhow::Result<RaftSeed> {
     Ok(
         match request_raft_join(
@@ -3104,12 +3531,88 @@ fn join_raft_group(
             Ok(joined) => RaftSeed::Members {
                 members: joined.members,
                 voter_peer_ids,
+                witnesses,
             },
             Err(rejection) => RaftSeed::Defer(rejection.reason),
         },
     )
 }
 
+/// Finish [`raft_members_for_row`] for a local witness. A witness
+/// joins a live group eagerly (it has no agent whose attach could
+/// gate the handshake) and never bootstraps one. Its join commits to
+/// the same production trust policy a voter's would, so the leader
+/// admits it through the same policy match.
+fn witness_seed_for_row(
+    net: Option<&std::sync::Arc<vos::network::Network>>,
+    a: &vos::registry::AgentRow,
+    local_prefix: u16,
+    plan: RaftPlan,
+    witnesses: Vec<u16>,
+    voter_peer_ids: Vec<(u16, Vec<u8>)>,
+    production_trust_policy: Option<vos::v2::Hash>,
+) -> anyhow::Result<RaftSeed> {
+    match plan {
+        RaftPlan::Spawn(members) => Ok(RaftSeed::Witness { members, witnesses }),
+        RaftPlan::Join { leader, known } => {
+            let Some(net) = net else {
+                return Ok(RaftSeed::Defer("witness join requires a network".into()));
+            };
+            Ok(
+                match request_raft_join(
+                    net,
+                    &a.instance_name,
+                    a.replication_id,
+                    local_prefix,
+                    leader,
+                    known,
+                    &voter_peer_ids,
+                    production_trust_policy,
+                )? {
+                    Ok(joined) => RaftSeed::Witness {
+                        members: joined.members,
+                        witnesses,
+                    },
+                    Err(rejection) => RaftSeed::Defer(rejection.reason),
+                },
+            )
+        }
+        RaftPlan::Bootstrap { .. } => Ok(RaftSeed::Defer(
+            "a witness cannot bootstrap a Raft group".into(),
+        )),
+        RaftPlan::Defer(reason) => Ok(RaftSeed::Defer(reason)),
+    }
+}
+
+/// Host the log-less witness worker for one raft row at the row's
+/// usual Raft db path, so a restart finds its anchored membership
+/// exactly as a full voter would.
+fn host_raft_witness(
+    node: &VosNode,
+    db_path: &Path,
+    a: &vos::registry::AgentRow,
+    local_prefix: u16,
+    members: Vec<u16>,
+    witnesses: Vec<u16>,
+) -> anyhow::Result<()> {
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let defaults = vos::raft::RaftConfig::default();
+    node.register_raft_witness(
+        db_path,
+        vos::raft::WorkerConfig {
+            me: local_prefix,
+            members,
+            replication_id: a.replication_id,
+            election_timeout_ms: defaults.election_timeout_ms,
+            heartbeat_interval_ms: defaults.heartbeat_interval_ms,
+            witnesses,
+        },
+    )
+    .map_err(|e| anyhow::anyhow!("host witness for '{}': {e}", a.instance_name))
+}
+
 fn promote_prepared_v2_raft_root(
     net: &std::sync::Arc<vos::network::Network>,
     instance_name: &str,
@@ -3464,6 +3967,9 @@ fn reconcile_installed_agents(
             continue;
         }
         let svc_id = instance_service_id(&a.instance_name, local_prefix);
+        if node.has_raft_witness(&a.replication_id) {
+            continue;
+        }
         if node.has_agent(svc_id) {
             // Usually this row's own agent. A *different* occupying
             // name means a ~15-bit instance-name hash collision:
@@ -3525,11 +4031,15 @@ fn reconcile_installed_agents(
                 continue;
             }
             let db_path = raft_db_path_for_row(data_dir, svc_id, &prepared);
-            match raft_members_for_row(node, &db_path, &a, local_prefix, boot_grace) {
+            let policy = is_v2
+                .then(|| production_trust.as_ref().map(|trust| trust.policy_id()))
+                .flatten();
+            match raft_members_for_row(node, &db_path, &a, local_prefix, boot_grace, policy) {
                 Ok(RaftSeed::Join {
                     leader,
                     known,
                     voter_peer_ids,
+                    witnesses,
                 }) if !is_v2 => {
                     let Some(network) = node.network() else {
                         continue;
@@ -3541,6 +4051,7 @@ fn reconcile_installed_agents(
                         leader,
                         known,
                         voter_peer_ids,
+                        witnesses,
                     )? {
                         seed @ RaftSeed::Members { .. } => {
                             damped.remove(&key(RowNote::RaftWaiting));
@@ -3555,13 +4066,28 @@ fn reconcile_installed_agents(
                             }
                             continue;
                         }
-                        RaftSeed::Join { .. } => unreachable!(),
+                        RaftSeed::Join { .. } | RaftSeed::Witness { .. } => unreachable!(),
                     }
                 }
                 Ok(seed @ (RaftSeed::Members { .. } | RaftSeed::Join { .. })) => {
                     damped.remove(&key(RowNote::RaftWaiting));
                     Some(seed)
                 }
+                Ok(RaftSeed::Witness { members, witnesses }) => {
+                    damped.remove(&key(RowNote::RaftWaiting));
+                    match host_raft_witness(node, &db_path, &a, local_prefix, members, witnesses) {
+                        Ok(()) => tracing::info!(
+                            "agent '{}' (raft) witnessed at runtime",
+                            a.instance_name
+                        ),
+                        Err(e) => {
+                            if damped.insert(key(RowNote::RaftWaiting)) {
+                                tracing::warn!("agent '{}' (raft) deferred: {e}", a.instance_name);
+                            }
+                        }
+                    }
+                    continue;
+                }
                 Ok(RaftSeed::Defer(reason)) => {
                     if damped.insert(key(RowNote::RaftWaiting)) {
                         tracing::warn!("agent '{}' (raft) deferred: {reason}", a.instance_name);
@@ -3583,8 +4109,12 @@ fn reconcile_installed_agents(
         match prepared {
             RowConfig::Ready(cfg) => {
                 let mut cfg = *cfg;
-                if let Some(RaftSeed::Members { members, .. }) = raft_seed {
+                if let Some(RaftSeed::Members {
+                    members, witnesses, ..
+                }) = raft_seed
+                {
                     cfg.members = members;
+                    cfg.witnesses = witnesses;
                 }
                 let id = node.register_at_id(cfg, svc_id);
                 spawned_this_pass += 1;
@@ -4276,6 +4806,136 @@ mod tests {
         assert_eq!(config.consistency, vos::v2::ConsistencyModeV2::Raft);
     }
 
+    #[test]
+    fn daemon_v2_roots_open_with_the_local_fairness_policy() {
+        let directory = std::env::temp_dir().join(format!(
+            "vosx-v2-fairness-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos(),
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let service_pvm = std::fs::read(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../services/vos-service/vos-service.pvm"),
+        )
+        .unwrap();
+        let package = signed_v2_package(vos::v2::VOS_SERVICE_PROGRAM_ID);
+        let row = vos::registry::AgentRow {
+            instance_name: "fair-counter".into(),
+            program_hash: [51; 32],
+            program_name: "counter".into(),
+            program_version: "2.0.0".into(),
+            replication_id: [52; 32],
+            consistency: Consistency::Local as u8,
+            network_reachable: false,
+            sync_role: vos::registry::SyncFloor::Public,
+            install_args: vec![],
+            install_payloads: vec![],
+        };
+        let billing = vos::registry::AgentRow {
+            instance_name: "billing".into(),
+            replication_id: [53; 32],
+            ..row.clone()
+        };
+        let admin = [54u8; 32];
+        let mut local = subscriptions::LocalConfig::default();
+        local.agents.insert(
+            row.instance_name.clone(),
+            subscriptions::AgentLocal {
+                fairness: Some(subscriptions::FairnessLocal {
+                    weight: Some(3),
+                    members: [(hex::encode(admin), "admin".to_string())].into(),
+                    actors: [("billing".to_string(), "operator".to_string())].into(),
+                }),
+                ..Default::default()
+            },
+        );
+        let policies = agent_policies_from_local(&local).unwrap();
+        let pinned = PinnedV2Service {
+            pvm: std::sync::Arc::new(service_pvm),
+        };
+        let resolve = |installed: &[vos::registry::AgentRow]| {
+            v2_config_from_row(
+                &directory,
+                [55; 32],
+                &row,
+                installed,
+                &policies,
+                Consistency::Local,
+                package.encode(),
+                Some(&pinned),
+                &[],
+            )
+            .unwrap()
+        };
+        assert!(
+            matches!(resolve(std::slice::from_ref(&row)), RowConfig::Deferred(_)),
+            "a named caller that is not installed yet defers the root"
+        );
+        let RowConfig::V2 {
+            config,
+            state_path,
+            network_reachable,
+        } = resolve(&[row.clone(), billing.clone()])
+        else {
+            panic!("signed package did not resolve to a v2 root")
+        };
+        let space = vos::v2::SpaceId([55; 32]);
+        let billing_actor = v2_root_actor_id(
+            v2_root_service_id(space, "billing", billing.replication_id),
+            "billing",
+        );
+        let mut expected = vos::v2::WorkFairnessPolicyV2::default();
+        expected.actor_weights.insert(config.root_actor, 3);
+        expected.member_priorities.insert(
+            vos::v2::SubjectId::of_authenticated_peer(&admin),
+            vos::v2::WorkPriorityV2::Admin,
+        );
+        expected
+            .actor_priorities
+            .insert(billing_actor, vos::v2::WorkPriorityV2::Operator);
+        assert_eq!(config.work_fairness, expected);
+
+        let reopen_config = (*config).clone();
+        let route = ServiceId::new(56, 57);
+        let mut node = VosNode::with_prefix(56);
+        register_v2_root_from_row(
+            &mut node,
+            &directory,
+            row.instance_name.clone(),
+            row.replication_id,
+            *config,
+            state_path.clone(),
+            None,
+            56,
+            route,
+            network_reachable,
+            None,
+        )
+        .unwrap();
+        assert!(node.has_agent(route));
+        drop(node);
+        let reopened = vos::v2::LocalRootTreeServiceV2::open(
+            reopen_config,
+            vos::v2::FileCommittedImageStoreV2::new(state_path),
+        )
+        .unwrap();
+        assert_eq!(reopened.work_fairness(), &expected);
+
+        local.agents.get_mut("fair-counter").unwrap().fairness =
+            Some(subscriptions::FairnessLocal {
+                actors: [("billing".to_string(), "urgent".to_string())].into(),
+                ..Default::default()
+            });
+        assert!(
+            agent_policies_from_local(&local).is_err(),
+            "an unknown class fails the boot"
+        );
+        let _ = std::fs::remove_dir_all(directory);
+    }
+
     #[test]
     fn daemon_local_registration_uses_the_supplied_production_policy() {
         let directory = std::env::temp_dir().join(format!(
@@ -4502,6 +5162,7 @@ mod tests {
             Some(RaftSeed::Members {
                 members: vec![member],
                 voter_peer_ids: Vec::new(),
+                witnesses: Vec::new(),
             }),
             member,
             route,
@@ -4609,6 +5270,7 @@ mod tests {
                 tick_ms: Some(250),
                 intra_caps: vec!["space-registry:member".into()],
                 device_secret: true,
+                fairness: None,
             },
         );
         cfg.agents.insert(
@@ -4617,6 +5279,7 @@ mod tests {
                 tick_ms: Some(0), // 0 = off → no policy
                 intra_caps: vec![],
                 device_secret: false,
+                fairness: None,
             },
         );
         let policies = agent_policies_from_local(&cfg).unwrap();
@@ -4637,6 +5300,7 @@ mod tests {
                 tick_ms: None,
                 intra_caps: vec!["not a valid cap token !!".into()],
                 device_secret: false,
+                fairness: None,
             },
         );
         assert!(agent_policies_from_local(&cfg).is_err());
@@ -4844,7 +5508,7 @@ mod tests {
 
     #[test]
     fn non_voter_defers() {
-        let plan = decide_raft_spawn(0x0003, &[0x0001, 0x0002], false, &[], 2);
+        let plan = decide_raft_spawn(0x0003, &[0x0001, 0x0002], &[], false, &[], 2);
         assert!(matches!(plan, RaftPlan::Defer(_)));
     }
 
@@ -4852,13 +5516,13 @@ mod tests {
     fn anchored_db_spawns_with_voter_seed() {
         // The persisted config governs; the seed just has to be the
         // current voter set so the worker spawns in multi-mode.
-        let plan = decide_raft_spawn(0x0001, &[0x0001, 0x0002], true, &[], 1);
+        let plan = decide_raft_spawn(0x0001, &[0x0001, 0x0002], &[], true, &[], 1);
         assert_eq!(plan, RaftPlan::Spawn(vec![0x0001, 0x0002]));
     }
 
     #[test]
     fn sole_voter_bootstraps_immediately() {
-        let plan = decide_raft_spawn(0x0001, &[0x0001], false, &[], 0);
+        let plan = decide_raft_spawn(0x0001, &[0x0001], &[], false, &[], 0);
         assert_eq!(plan, RaftPlan::Bootstrap { contested: false });
     }
 
@@ -4886,14 +5550,14 @@ mod tests {
             0x0001,
             status(RaftRole::Leader, vec![0x0001, 0x0002], Some(0x0001)),
         )];
-        let plan = decide_raft_spawn(0x0002, &[0x0001, 0x0002], false, &probes, 1);
+        let plan = decide_raft_spawn(0x0002, &[0x0001, 0x0002], &[], false, &probes, 1);
         assert_eq!(plan, RaftPlan::Spawn(vec![0x0001, 0x0002]));
     }
 
     #[test]
     fn live_group_led_by_probed_voter_joins_there() {
         let probes = vec![(0x0001, status(RaftRole::Leader, vec![0x0001], Some(0x0001)))];
-        let plan = decide_raft_spawn(0x0002, &[0x0001, 0x0002], false, &probes, 1);
+        let plan = decide_raft_spawn(0x0002, &[0x0001, 0x0002], &[], false, &probes, 1);
         assert_eq!(
             plan,
             RaftPlan::Join {
@@ -4911,7 +5575,7 @@ mod tests {
             0x0002,
             status(RaftRole::Follower, vec![0x0001, 0x0002], Some(0x0001)),
         )];
-        let plan = decide_raft_spawn(0x0003, &[0x0001, 0x0002, 0x0003], false, &probes, 2);
+        let plan = decide_raft_spawn(0x0003, &[0x0001, 0x0002, 0x0003], &[], false, &probes, 2);
         assert_eq!(
             plan,
             RaftPlan::Join {
@@ -4924,18 +5588,18 @@ mod tests {
     #[test]
     fn live_group_without_leader_defers() {
         let probes = vec![(0x0001, status(RaftRole::Candidate, vec![0x0001], None))];
-        let plan = decide_raft_spawn(0x0002, &[0x0001, 0x0002], false, &probes, 1);
+        let plan = decide_raft_spawn(0x0002, &[0x0001, 0x0002], &[], false, &probes, 1);
         assert!(matches!(plan, RaftPlan::Defer(_)));
     }
 
     #[test]
     fn absent_everywhere_only_smallest_voter_bootstraps_contested() {
         let probes = vec![(0x0002, absent())];
-        let plan = decide_raft_spawn(0x0001, &[0x0001, 0x0002], false, &probes, 1);
+        let plan = decide_raft_spawn(0x0001, &[0x0001, 0x0002], &[], false, &probes, 1);
         assert_eq!(plan, RaftPlan::Bootstrap { contested: true });
 
         let probes = vec![(0x0001, absent())];
-        let plan = decide_raft_spawn(0x0002, &[0x0001, 0x0002], false, &probes, 1);
+        let plan = decide_raft_spawn(0x0002, &[0x0001, 0x0002], &[], false, &probes, 1);
         assert!(matches!(plan, RaftPlan::Defer(_)));
     }
 
@@ -4945,7 +5609,35 @@ mod tests {
         // confirmation, no bootstrap — the group may live on the
         // silent one.
         let probes = vec![(0x0002, absent())];
-        let plan = decide_raft_spawn(0x0001, &[0x0001, 0x0002, 0x0003], false, &probes, 2);
+        let plan = decide_raft_spawn(0x0001, &[0x0001, 0x0002, 0x0003], &[], false, &probes, 2);
+        assert!(matches!(plan, RaftPlan::Defer(_)));
+    }
+
+    #[test]
+    fn witness_never_bootstraps_and_yields_to_smallest_full_voter() {
+        // A lone witness cannot lead, so it waits for a full voter.
+        let plan = decide_raft_spawn(0x0001, &[0x0001], &[0x0001], false, &[], 0);
+        assert!(matches!(plan, RaftPlan::Defer(_)));
+
+        // The witness holds the smallest prefix: the smallest FULL
+        // voter bootstraps instead, and the witness defers.
+        let voters = [0x0001, 0x0002, 0x0003];
+        let probes = vec![(0x0001, absent()), (0x0003, absent())];
+        let plan = decide_raft_spawn(0x0002, &voters, &[0x0001], false, &probes, 2);
+        assert_eq!(plan, RaftPlan::Bootstrap { contested: true });
+        let probes = vec![(0x0002, absent()), (0x0003, absent())];
+        let plan = decide_raft_spawn(0x0001, &voters, &[0x0001], false, &probes, 2);
         assert!(matches!(plan, RaftPlan::Defer(_)));
+
+        // Once the group is live the witness joins like any voter.
+        let probes = vec![(0x0002, status(RaftRole::Leader, vec![0x0002], Some(0x0002)))];
+        let plan = decide_raft_spawn(0x0001, &voters, &[0x0001], false, &probes, 2);
+        assert_eq!(
+            plan,
+            RaftPlan::Join {
+                leader: 0x0002,
+                known: vec![0x0002],
+            },
+        );
     }
 }
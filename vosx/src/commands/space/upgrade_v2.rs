@@ -0,0 +1,141 @@
+//! `space upgrade-v2` — replace an installed v2 actor's program with a
+//! signed replacement package through one guest-owned `UpgradeActor`.
+//!
+//! The package is verified here, then shipped to the daemon hosting the
+//! actor's root. The daemon binds the actor's current deployment/program as
+//! the expected pair, reads an exact linear base after the current-term
+//! barrier, and proposes the transition with the replacement PVM attached as
+//! request availability, so the committed entry carries it to every voter.
+//! A production root asks its trust policy to authorize the exact request
+//! bytes. The daemon answers once every voter has applied the entry, and
+//! this command checks the reported guest-owned post-state against the
+//! package before declaring success.
+//!
+//! The catalog row keeps naming the genesis package: the root's service
+//! identity is pinned to it, and `space up` still installs fresh voters from
+//! it before they replay the upgrade.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::space::client::DaemonClient;
+use crate::commands::space::up::validate_exact_v2_package;
+use crate::output;
+
+pub struct Args {
+    pub space: String,
+    pub instance: String,
+    /// Signed replacement `.vos` package.
+    pub package: PathBuf,
+}
+
+/// The daemon's `__upgrade_v2` reply. Every field is read back from
+/// guest-owned state after Accumulate; `error` replaces all of them when
+/// the transition was not applied.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct UpgradeReport {
+    #[serde(default, skip_serializing)]
+    error: Option<String>,
+    #[serde(default)]
+    actor: String,
+    #[serde(default)]
+    previous_deployment: String,
+    #[serde(default)]
+    previous_program: String,
+    #[serde(default)]
+    deployment: String,
+    #[serde(default)]
+    pub(crate) program: String,
+    #[serde(default)]
+    producer: String,
+    #[serde(default)]
+    methods: Vec<String>,
+    #[serde(default)]
+    pub(crate) revision: u64,
+    #[serde(default)]
+    duplicate: bool,
+    /// Raft voter prefixes that reported applying the upgrade; empty for a
+    /// Local root.
+    #[serde(default)]
+    voters: Vec<u16>,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    let bytes = std::fs::read(&args.package)
+        .map_err(|e| anyhow::anyhow!("read {}: {e}", args.package.display()))?;
+    let package = check_package(&bytes, &args.instance)?;
+
+    let report = DaemonClient::with_connect(&args.space, |client| {
+        ship(client, &args.instance, &bytes, &package, &args.package)
+    })?;
+
+    if output::is_json() {
+        output::print_json(&report);
+        return Ok(());
+    }
+    let verb = if report.duplicate {
+        "already upgraded"
+    } else {
+        "upgraded"
+    };
+    println!(
+        "{verb} {} → program {} (revision {})",
+        args.instance, report.program, report.revision,
+    );
+    println!("previous   program {}", report.previous_program);
+    println!("deployment {}", report.deployment);
+    println!("methods    {}", report.methods.join(", "));
+    if report.voters.is_empty() {
+        println!("applied    local root (single replica)");
+    } else {
+        let voters: Vec<String> = report.voters.iter().map(|p| format!("{p:#06x}")).collect();
+        println!("applied    on voters {}", voters.join(", "));
+    }
+    Ok(())
+}
+
+/// Verify a replacement package locally, before any daemon sees it.
+pub(crate) fn check_package(bytes: &[u8], instance: &str) -> anyhow::Result<vos::v2::VosPackageV2> {
+    let package = validate_exact_v2_package(bytes, instance)?;
+    if package.manifest.crdt {
+        anyhow::bail!("#[actor(crdt)] packages cannot be upgraded; CRDT upgrades are unsupported");
+    }
+    Ok(package)
+}
+
+/// Send a checked package (`bytes`, read from `source`) to the daemon and
+/// confirm the guest-owned post-state it reports.
+pub(crate) fn ship(
+    client: &DaemonClient,
+    instance: &str,
+    bytes: &[u8],
+    package: &vos::v2::VosPackageV2,
+    source: &Path,
+) -> anyhow::Result<UpgradeReport> {
+    use vos::v2::V2Wire;
+
+    let policies = vos::v2::PackageRolePoliciesV2::decode(&package.role_policies)
+        .map_err(|e| anyhow::anyhow!("decode replacement policies: {e}"))?;
+    let report = client.upgrade_v2(instance, bytes.to_vec())?;
+    let report: UpgradeReport = serde_json::from_str(&report)
+        .map_err(|e| anyhow::anyhow!("daemon sent a malformed upgrade report: {e}"))?;
+    if let Some(error) = &report.error {
+        anyhow::bail!("upgrade of '{instance}' was not confirmed: {error}");
+    }
+
+    // Trust only what the guest committed: the actor must now run exactly
+    // the replacement package, with its producer and method surface.
+    let methods: Vec<String> = policies.methods.into_iter().map(|p| p.method).collect();
+    if report.deployment != hex::encode(package.deployment_id().0)
+        || report.program != hex::encode(package.manifest.actor_program.0)
+        || report.producer != hex::encode(package.deployment_signature.producer.0)
+        || report.methods != methods
+    {
+        anyhow::bail!(
+            "daemon reported a post-upgrade actor that does not match {}; do not distribute a release that expects it",
+            source.display(),
+        );
+    }
+    Ok(report)
+}
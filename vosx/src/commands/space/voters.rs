@@ -0,0 +1,354 @@
+//! `space voters` — replace a Raft voter with a new identity.
+//!
+//! Raft membership lives in two places: the registry's node rows
+//! (who *may* join) and each Raft group's committed configuration
+//! (who *does* vote). This command keeps them in step:
+//!
+//! - `add` enrolls the new identity as a voter (or witness) in the
+//!   registry. The new node then runs `space up`, which joins every
+//!   Raft root through joint consensus on its own.
+//! - `promote` waits until the new identity is a steady-state member
+//!   of every Raft root's active configuration.
+//! - `remove` drops the old identity from every Raft root through the
+//!   leader's `change_membership`, then deletes its registry row so it
+//!   cannot rejoin, and confirms with `raft-status` that it is gone.
+//!
+//! Removal is refused when it would shrink a group's fault tolerance
+//! (unless `--allow-reduced-fault-tolerance`), and the leader refuses
+//! it when a majority of the remaining voters is not live. Run
+//! `remove` against the daemon that leads the Raft roots; a follower
+//! answers with the leader's node prefix instead.
+
+use std::time::{Duration, Instant};
+
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use vos::registry::{AgentRow, MEMBER_KIND_NODE, NODE_ROLE_OBSERVER};
+
+use crate::commands::space::client::DaemonClient;
+use crate::commands::space::common::RAFT_CONSISTENCY;
+use crate::commands::space::members;
+use crate::output;
+
+#[derive(Subcommand, Debug)]
+pub enum VotersCommand {
+    /// Enroll a new node identity as a voter. Start it with
+    /// `vosx space up` afterwards; it joins each Raft root itself.
+    Add {
+        /// Multibase-encoded libp2p PeerId (e.g. `12D3KooW…`).
+        peer_id: String,
+        /// 16-bit node prefix. Defaults to deriving from the peer_id.
+        #[arg(long)]
+        prefix: Option<u32>,
+        /// Enroll as a witness (votes for quorum, stores no state).
+        #[arg(long)]
+        witness: bool,
+    },
+    /// Wait until a newly enrolled voter is a steady-state member of
+    /// every Raft root.
+    Promote {
+        /// 16-bit prefix of the new voter.
+        prefix: u32,
+        /// Give up after this many seconds.
+        #[arg(long, default_value_t = 300)]
+        timeout: u64,
+    },
+    /// Remove a voter identity from every Raft root, then from the
+    /// registry.
+    Remove {
+        /// 16-bit prefix of the voter being retired.
+        prefix: u32,
+        /// Permit a removal that lowers how many voter failures a
+        /// group tolerates (e.g. 3 voters → 2).
+        #[arg(long)]
+        allow_reduced_fault_tolerance: bool,
+    },
+}
+
+pub struct Args {
+    pub space: String,
+    pub command: VotersCommand,
+}
+
+/// The daemon's `__remove_voter` reply. `error` replaces every other
+/// field when the removal did not happen.
+#[derive(Deserialize)]
+struct RemoveReport {
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    joint_index: u64,
+    #[serde(default)]
+    members: Vec<u16>,
+}
+
+#[derive(Serialize)]
+struct RootView {
+    instance: String,
+    /// `0` when the voter was already absent from this root.
+    joint_index: u64,
+    members: Vec<u16>,
+}
+
+#[derive(Serialize)]
+struct VotersView {
+    prefix: u16,
+    roots: Vec<RootView>,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    match args.command {
+        VotersCommand::Add {
+            peer_id,
+            prefix,
+            witness,
+        } => add(&args.space, &peer_id, prefix, witness),
+        VotersCommand::Promote { prefix, timeout } => {
+            promote(&args.space, voter_prefix(prefix)?, timeout)
+        }
+        VotersCommand::Remove {
+            prefix,
+            allow_reduced_fault_tolerance,
+        } => remove(
+            &args.space,
+            voter_prefix(prefix)?,
+            allow_reduced_fault_tolerance,
+        ),
+    }
+}
+
+fn voter_prefix(prefix: u32) -> anyhow::Result<u16> {
+    u16::try_from(prefix).map_err(|_| anyhow::anyhow!("prefix {prefix} does not fit in 16 bits"))
+}
+
+fn add(space: &str, peer_id: &str, prefix: Option<u32>, witness: bool) -> anyhow::Result<()> {
+    let role = if witness { "witness" } else { "voter" };
+    members::add_node(space, peer_id, prefix, role)?;
+    if !output::is_json() {
+        println!(
+            "next: start the node with `vosx space up`, then wait for it with \
+             `vosx space voters {space} promote <prefix>`"
+        );
+    }
+    Ok(())
+}
+
+fn raft_roots(client: &DaemonClient) -> anyhow::Result<Vec<AgentRow>> {
+    Ok(client
+        .agents()?
+        .into_iter()
+        .filter(|agent| agent.consistency == RAFT_CONSISTENCY)
+        .collect())
+}
+
+fn promote(space: &str, prefix: u16, timeout: u64) -> anyhow::Result<()> {
+    DaemonClient::with_connect(space, |client| {
+        let enrolled = client.members()?.into_iter().any(|m| {
+            m.kind == MEMBER_KIND_NODE && m.prefix == prefix && m.role != NODE_ROLE_OBSERVER
+        });
+        if !enrolled {
+            anyhow::bail!(
+                "node {prefix:#06x} is not enrolled as a voter; run `vosx space voters {space} add` first"
+            );
+        }
+        let roots = raft_roots(client)?;
+        let deadline = Instant::now() + Duration::from_secs(timeout);
+        let mut views = Vec::with_capacity(roots.len());
+        for root in &roots {
+            loop {
+                let status = client.raft_status(root.replication_id)?;
+                if status.present && status.joint_old.is_none() && status.members.contains(&prefix)
+                {
+                    views.push(RootView {
+                        instance: root.instance_name.clone(),
+                        joint_index: 0,
+                        members: status.members,
+                    });
+                    break;
+                }
+                if Instant::now() >= deadline {
+                    anyhow::bail!(
+                        "node {prefix:#06x} is not yet a steady-state voter of '{}' (members {:?}, joint-old {:?}); \
+                         check that it is running `vosx space up`",
+                        root.instance_name,
+                        status.members,
+                        status.joint_old,
+                    );
+                }
+                std::thread::sleep(Duration::from_millis(500));
+            }
+        }
+        print_view(
+            &VotersView {
+                prefix,
+                roots: views,
+            },
+            "is a voter of",
+        );
+        Ok(())
+    })
+}
+
+fn remove(space: &str, prefix: u16, allow_reduced_fault_tolerance: bool) -> anyhow::Result<()> {
+    DaemonClient::with_connect(space, |client| {
+        let roots = raft_roots(client)?;
+        // Check every root before touching any, so a removal this side
+        // can already tell is unsafe changes nothing. The leader's own
+        // liveness check (or the connection) can still fail partway; the
+        // error then names the roots already changed, and a rerun skips
+        // them since the voter is absent there.
+        for root in &roots {
+            let status = client.raft_status(root.replication_id)?;
+            if !status.present {
+                anyhow::bail!(
+                    "the connected daemon does not run Raft root '{}'; run this against the roots' leader",
+                    root.instance_name,
+                );
+            }
+            if let Some(old) = &status.joint_old {
+                anyhow::bail!(
+                    "Raft root '{}' is mid-change (joint-old {old:?}); retry once it settles",
+                    root.instance_name,
+                );
+            }
+            if status.members.contains(&prefix)
+                && !allow_reduced_fault_tolerance
+                && reduces_fault_tolerance(status.members.len())
+            {
+                anyhow::bail!(
+                    "removing {prefix:#06x} leaves '{}' with {} voters, tolerating {} failure(s) instead of {}; \
+                     add and promote its replacement first, or pass --allow-reduced-fault-tolerance",
+                    root.instance_name,
+                    status.members.len() - 1,
+                    tolerated_failures(status.members.len() - 1),
+                    tolerated_failures(status.members.len()),
+                );
+            }
+        }
+
+        let mut views = Vec::with_capacity(roots.len());
+        for root in &roots {
+            let report = client
+                .remove_voter(&root.instance_name, prefix)
+                .and_then(|report| {
+                    serde_json::from_str::<RemoveReport>(&report)
+                        .map_err(|e| anyhow::anyhow!("daemon sent a malformed removal report: {e}"))
+                })
+                .and_then(|report| match report.error {
+                    Some(error) => Err(anyhow::anyhow!(error)),
+                    None => Ok(report),
+                });
+            let report = match report {
+                Ok(report) => report,
+                Err(e) => anyhow::bail!(
+                    "removing {prefix:#06x} from '{}' failed: {e}; {}",
+                    root.instance_name,
+                    already_removed(&views),
+                ),
+            };
+            views.push(RootView {
+                instance: root.instance_name.clone(),
+                joint_index: report.joint_index,
+                members: report.members,
+            });
+        }
+
+        // Only once no configuration needs the old identity does its
+        // registry row go; without it the node cannot rejoin.
+        match client.remove_node(u32::from(prefix))? {
+            vos::registry::Status::Ok | vos::registry::Status::NotFound => {}
+            other => anyhow::bail!("remove_node returned status {other}"),
+        }
+
+        for root in &roots {
+            let status = client.raft_status(root.replication_id)?;
+            if status.joint_old.is_some() || status.members.contains(&prefix) {
+                anyhow::bail!(
+                    "raft-status for '{}' still lists {prefix:#06x} (members {:?}, joint-old {:?})",
+                    root.instance_name,
+                    status.members,
+                    status.joint_old,
+                );
+            }
+        }
+        print_view(
+            &VotersView {
+                prefix,
+                roots: views,
+            },
+            "was removed from",
+        );
+        Ok(())
+    })
+}
+
+/// What a removal that failed partway had already done: the roots it
+/// changed, and that the registry row stays until a rerun finishes.
+fn already_removed(views: &[RootView]) -> String {
+    if views.is_empty() {
+        return "no earlier root was changed".into();
+    }
+    let changed: Vec<&str> = views.iter().map(|v| v.instance.as_str()).collect();
+    format!(
+        "already removed from {}; the registry row is kept, rerun `remove` to finish",
+        changed.join(", "),
+    )
+}
+
+/// Voter failures a group of `voters` survives while still committing.
+fn tolerated_failures(voters: usize) -> usize {
+    voters.saturating_sub(1) / 2
+}
+
+/// Whether dropping one voter from a group of `voters` lowers the
+/// failures it tolerates. 4 → 3 keeps one; 3 → 2 loses it.
+fn reduces_fault_tolerance(voters: usize) -> bool {
+    tolerated_failures(voters.saturating_sub(1)) < tolerated_failures(voters)
+}
+
+fn print_view(view: &VotersView, verb: &str) {
+    if output::is_json() {
+        output::print_json(view);
+        return;
+    }
+    if view.roots.is_empty() {
+        println!("no Raft roots in this space");
+        return;
+    }
+    for root in &view.roots {
+        let members: Vec<String> = root.members.iter().map(|p| format!("{p:#06x}")).collect();
+        println!(
+            "{:#06x} {verb} {:<24} members {}",
+            view.prefix,
+            root.instance,
+            members.join(", "),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fault_tolerance_drops_only_at_odd_sizes() {
+        assert!(reduces_fault_tolerance(3));
+        assert!(!reduces_fault_tolerance(4));
+        assert!(reduces_fault_tolerance(5));
+        assert!(!reduces_fault_tolerance(2));
+        assert!(!reduces_fault_tolerance(1));
+    }
+
+    #[test]
+    fn a_partial_removal_names_the_roots_it_changed() {
+        assert_eq!(already_removed(&[]), "no earlier root was changed");
+        let view = |instance: &str| RootView {
+            instance: instance.into(),
+            joint_index: 7,
+            members: vec![1, 2],
+        };
+        let message = already_removed(&[view("ledger"), view("orders")]);
+        assert!(message.starts_with("already removed from ledger, orders;"));
+        assert!(message.contains("rerun `remove`"));
+    }
+}
@@ -0,0 +1,222 @@
+//! `vosx whoami rotate` — replace an operator key.
+//!
+//! In every target space one registry op, signed by both the old key and
+//! its successor, hands the successor everything the old key holds and
+//! revokes the old key. The successor's grants keep their original
+//! grantors, so nothing chains through the retired key; grants the old
+//! key delegated move to the successor, and a root key re-roots the space.
+//!
+//! The retired key is the identity file, or an external one named with
+//! `--signer` (see [`crate::signer`]). The successor is a fresh identity
+//! file key, or an external key named with `--to`. A new identity file
+//! only becomes `identity.key` once every space accepted; the old one is
+//! kept beside it as `identity.key.old-<unix secs>`.
+//!
+//! An interrupted or partly failed rotate leaves the successor pending at
+//! `identity.key.new` and the old key current; running it again picks up
+//! the same successor, and spaces already rotated have nothing left to
+//! move. A space past the v2 role-authority cutover refuses: its root is
+//! immutable and only it signs role changes there.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use libp2p::PeerId;
+use libp2p::identity::Keypair;
+use serde::Serialize;
+use vos::registry::{AUTH_ROLE_NONE, Status};
+
+use crate::commands::space::client::DaemonClient;
+use crate::commands::space::role::role_name;
+use crate::signer::Signer;
+use crate::{identity, output, paths, spaces_index};
+
+#[derive(clap::Subcommand)]
+pub enum WhoamiCommand {
+    /// Move every space role and actor-local grant of the current key to
+    /// a new one and revoke the current key. The new key is a fresh
+    /// identity key unless `--to` names an external one.
+    Rotate(RotateArgs),
+}
+
+#[derive(clap::Args)]
+pub struct RotateArgs {
+    /// Space to rotate the key in. May be repeated. Defaults to every
+    /// space in the index, each of which must have its daemon running.
+    #[arg(long = "space", value_name = "SPACE")]
+    spaces: Vec<String>,
+    /// The key to retire, as a signer spec (`ssh-agent[#comment]`,
+    /// `pkcs11:<module.so>[#label]`). Defaults to `VOSX_SIGNER`, else the
+    /// identity file.
+    #[arg(long, value_name = "SPEC")]
+    signer: Option<String>,
+    /// Rotate onto this external key instead of a new identity file key.
+    /// Required when the retired key is external.
+    #[arg(long, value_name = "SPEC")]
+    to: Option<String>,
+}
+
+#[derive(Serialize)]
+struct RotateView {
+    peer_id: String,
+    previous_peer_id: String,
+    /// The new identity file and where the old one went; `None` when the
+    /// successor is an external key.
+    path: Option<String>,
+    retired: Option<String>,
+    spaces: Vec<LinkedView>,
+}
+
+#[derive(Serialize)]
+struct LinkedView {
+    space: String,
+    /// The old key was the space root.
+    root: bool,
+    /// `None` when the old key held no space role there.
+    role: Option<&'static str>,
+    actor_grants: usize,
+}
+
+pub fn run(cmd: WhoamiCommand) -> Result<()> {
+    match cmd {
+        WhoamiCommand::Rotate(args) => rotate(args),
+    }
+}
+
+fn rotate(args: RotateArgs) -> Result<()> {
+    let path = paths::client_identity_path();
+    let dial_key = identity::load_or_create()?;
+    let old: Arc<dyn Signer> = crate::signer::open(args.signer.as_deref())?.into();
+    let old_is_identity = old.peer_id() == PeerId::from(dial_key.public());
+    let pending = identity::pending_path(&path);
+    let new: Box<dyn Signer> = match args.to.as_deref() {
+        Some(_) if old_is_identity => bail!(
+            "{} is the identity file's key, which also dials every daemon; rotate it to a new \
+             identity file (drop `--to`)",
+            old.peer_id(),
+        ),
+        Some(to) => crate::signer::open(Some(to))?,
+        None if !old_is_identity => {
+            bail!("the key to retire is external; name its successor with `--to <SPEC>`")
+        }
+        None => Box::new(identity::load_or_create_at(&pending)?),
+    };
+    let old_peer = old.peer_id();
+    let new_peer = new.peer_id();
+    if old_peer == new_peer {
+        bail!("{old_peer} is already the successor key");
+    }
+
+    let spaces = if args.spaces.is_empty() {
+        spaces_index::load()
+            .context("loading spaces index")?
+            .spaces
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect()
+    } else {
+        args.spaces
+    };
+
+    let mut linked = Vec::new();
+    let mut failed = Vec::new();
+    for space in &spaces {
+        match link(space, &dial_key, &old, new.as_ref()) {
+            Ok(view) => linked.push(view),
+            Err(e) => {
+                eprintln!("{space}: {e:#}");
+                failed.push(space.as_str());
+            }
+        }
+    }
+    if !failed.is_empty() {
+        let waiting = if args.to.is_some() {
+            String::new()
+        } else {
+            format!(" and the new key waits at {}", pending.display())
+        };
+        bail!(
+            "couldn't rotate the key in {}; {old_peer} stays current there{waiting} — run \
+             `vosx whoami rotate` again",
+            failed.join(", "),
+        );
+    }
+
+    let retired = match args.to {
+        Some(_) => None,
+        None => Some(identity::promote_at(&path)?),
+    };
+    if output::is_json() {
+        output::print_json(&RotateView {
+            peer_id: new_peer.to_string(),
+            previous_peer_id: old_peer.to_string(),
+            path: retired.as_ref().map(|_| path.display().to_string()),
+            retired: retired.as_ref().map(|r| r.display().to_string()),
+            spaces: linked,
+        });
+    } else {
+        for view in &linked {
+            let mut moved = Vec::new();
+            if view.root {
+                moved.push("the space root".to_string());
+            }
+            if let Some(role) = view.role {
+                moved.push(role.to_string());
+            }
+            if view.actor_grants > 0 {
+                moved.push(format!("{} actor-local grant(s)", view.actor_grants));
+            }
+            if moved.is_empty() {
+                println!("{}: nothing to move", view.space);
+            } else {
+                println!(
+                    "{}: moved {} and revoked {old_peer}",
+                    view.space,
+                    moved.join(", ")
+                );
+            }
+        }
+        println!("peer_id = {new_peer}");
+        match &retired {
+            Some(retired) => {
+                println!("retired = {old_peer} ({})", retired.display());
+                println!("restart daemons you run so they boot with the new identity");
+            }
+            None => println!("retired = {old_peer}; sign with `--signer` for the new key"),
+        }
+    }
+    Ok(())
+}
+
+/// Rotate `old` onto `new` in `space`, dialing as the identity file.
+/// A space where `old` holds nothing (or was already rotated) is left
+/// alone.
+fn link(
+    space: &str,
+    dial_key: &Keypair,
+    old: &Arc<dyn Signer>,
+    new: &dyn Signer,
+) -> Result<LinkedView> {
+    let old_peer = old.peer_id().to_bytes();
+    DaemonClient::with_connect_as(space, dial_key.clone(), Box::new(old.clone()), |client| {
+        let root = client.is_root(&old_peer)?;
+        let role = client.peer_role(old_peer.clone())?;
+        let actor_grants = client
+            .actor_acls()?
+            .into_iter()
+            .filter(|grant| grant.peer_id == old_peer)
+            .count();
+        if root || role != AUTH_ROLE_NONE || actor_grants > 0 {
+            let status = client.rotate_identity(new)?;
+            if status != Status::Ok {
+                bail!("rotate_identity returned status {status}");
+            }
+        }
+        Ok(LinkedView {
+            space: client.entry.name.clone(),
+            root,
+            role: (role != AUTH_ROLE_NONE).then(|| role_name(role)),
+            actor_grants,
+        })
+    })
+}
@@ -22,8 +22,14 @@
 //! scope; the auth check trusts the PeerId, and the registry's
 //! `members` table is the source of truth for what that PeerId
 //! is allowed to do.
+//!
+//! `vosx whoami rotate` replaces the key in two steps: the
+//! successor is created at `identity.key.new` (reused if an earlier
+//! rotate was interrupted), and only once every space has granted
+//! it does [`promote_at`] retire the old key to
+//! `identity.key.old-<unix secs>` and move the successor into place.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use libp2p::identity::Keypair;
 
@@ -90,6 +96,35 @@ fn write_owner_only(path: &Path, bytes: &[u8]) -> anyhow::Result<()> {
     std::fs::write(path, bytes).map_err(|e| anyhow::anyhow!("write {}: {e}", path.display()))
 }
 
+/// Where a rotation's successor key waits until it is promoted.
+pub fn pending_path(path: &Path) -> PathBuf {
+    let mut pending = path.as_os_str().to_owned();
+    pending.push(".new");
+    PathBuf::from(pending)
+}
+
+/// Make the pending successor at [`pending_path`] the identity,
+/// keeping the current key as `<path>.old-<unix secs>`. Returns the
+/// retired key's path.
+pub fn promote_at(path: &Path) -> anyhow::Result<PathBuf> {
+    let pending = pending_path(path);
+    if !pending.is_file() {
+        anyhow::bail!("no pending identity at {}", pending.display());
+    }
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut retired = path.as_os_str().to_owned();
+    retired.push(format!(".old-{secs}"));
+    let retired = PathBuf::from(retired);
+    std::fs::rename(path, &retired)
+        .map_err(|e| anyhow::anyhow!("retire {} to {}: {e}", path.display(), retired.display()))?;
+    std::fs::rename(&pending, path)
+        .map_err(|e| anyhow::anyhow!("promote {} to {}: {e}", pending.display(), path.display()))?;
+    Ok(retired)
+}
+
 /// Convenience: format the PeerId for the operator's persistent
 /// identity. Surfaced in error messages and `vosx space members`
 /// listings.
@@ -156,6 +191,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn promote_swaps_in_the_pending_key_and_keeps_the_old_one() {
+        let tmp = TempPath::new("promote");
+        let path = tmp.0.join("identity.key");
+        let old = libp2p::PeerId::from(load_or_create_at(&path).unwrap().public());
+        assert!(promote_at(&path).is_err(), "nothing pending yet");
+
+        let pending = pending_path(&path);
+        assert_eq!(pending, tmp.0.join("identity.key.new"));
+        let new = libp2p::PeerId::from(load_or_create_at(&pending).unwrap().public());
+        // An interrupted rotate resumes with the same successor.
+        let again = libp2p::PeerId::from(load_or_create_at(&pending).unwrap().public());
+        assert_eq!(new, again);
+
+        let retired = promote_at(&path).expect("promote");
+        assert!(!pending.exists());
+        let current = libp2p::PeerId::from(load_or_create_at(&path).unwrap().public());
+        let kept = libp2p::PeerId::from(load_or_create_at(&retired).unwrap().public());
+        assert_eq!(current, new);
+        assert_eq!(kept, old);
+    }
+
     #[test]
     fn corrupt_file_surfaces_clear_error() {
         let tmp = TempPath::new("corrupt");
@@ -29,6 +29,7 @@ mod output;
 mod paths;
 mod secure_file;
 mod shutdown;
+mod signer;
 mod spaces_index;
 mod token;
 
@@ -114,6 +115,11 @@ enum Command {
         /// For raw PVM input only; ELF builds derive this from `#[actor(crdt)]`.
         #[arg(long)]
         crdt: bool,
+        /// Key that signs the package: `file`, `ssh-agent[:<socket>][#comment]`
+        /// or `pkcs11:<module.so>[#label]`. Defaults to `VOSX_SIGNER`, else
+        /// the identity key.
+        #[arg(long, value_name = "SPEC")]
+        signer: Option<String>,
     },
     /// Transpile and validate the protocol-pinned generic service PVM.
     ServicePvm {
@@ -143,6 +149,19 @@ enum Command {
         #[arg(long, default_value_t = 100_000_000)]
         gas: u64,
     },
+    /// Actor development loop. Only `vosx dev watch` is built in: it
+    /// rebuilds, publishes and upgrades an installed actor on every save.
+    /// Other `vosx dev …` verbs dispatch to the dev extension.
+    Dev {
+        #[command(subcommand)]
+        command: commands::dev::DevCommand,
+    },
+    /// Host several spaces in one process: `vosx node up` runs
+    /// them, `space up` / `space down` attach and detach while it does.
+    Node {
+        #[command(subcommand)]
+        command: commands::node::NodeCommand,
+    },
     /// Per-space lifecycle and operations.
     Space {
         #[command(subcommand)]
@@ -169,11 +188,14 @@ enum Command {
     /// dials it. Creates the keypair on first run at
     /// `$XDG_CONFIG_HOME/vosx/identity.key`. Useful for
     /// enrolling the operator into a space's `members` ACL.
+    /// `vosx whoami rotate` replaces the key.
     Whoami {
         /// Emit JSON `{"peer_id": "...", "path": "..."}` instead
         /// of plain text. Pipe into `jq` for scripting.
         #[arg(long)]
         json: bool,
+        #[command(subcommand)]
+        command: Option<commands::whoami::WhoamiCommand>,
     },
 }
 
@@ -281,6 +303,7 @@ fn main() {
             tasks,
             include_elf,
             crdt,
+            signer,
         }) => {
             if let Err(error) = commands::build::run(commands::build::Args {
                 program,
@@ -294,6 +317,7 @@ fn main() {
                 tasks,
                 include_elf,
                 crdt,
+                signer,
             }) {
                 report_error(error);
             }
@@ -316,6 +340,16 @@ fn main() {
         }) => {
             commands::run::run(&program, &payload, &hex, gas);
         }
+        Some(Command::Dev { command }) => {
+            if let Err(e) = commands::dev::run(command) {
+                report_error(e);
+            }
+        }
+        Some(Command::Node { command }) => {
+            if let Err(e) = commands::node::run(command) {
+                report_error(e);
+            }
+        }
         Some(Command::Space { command }) => {
             if let Err(e) = commands::space::run(command) {
                 report_error(e);
@@ -336,7 +370,18 @@ fn main() {
                 }
             }
         }
-        Some(Command::Whoami { json }) => {
+        Some(Command::Whoami {
+            command: Some(command),
+            ..
+        }) => {
+            if let Err(e) = commands::whoami::run(command) {
+                report_error(e);
+            }
+        }
+        Some(Command::Whoami {
+            json,
+            command: None,
+        }) => {
             let path = paths::client_identity_path();
             let kp = match identity::load_or_create() {
                 Ok(kp) => kp,
@@ -417,6 +462,7 @@ fn should_dynamic_dispatch(argv: &[String]) -> bool {
         "service-pvm",
         "release",
         "run",
+        "node",
         "space",
         "zk",
         "help-schema",
@@ -451,6 +497,11 @@ fn should_dynamic_dispatch(argv: &[String]) -> bool {
                 if BUILTIN_VERBS.contains(&a.as_str()) {
                     return false;
                 }
+                // `dev watch` is built in; the rest of `dev` belongs to
+                // the dev extension.
+                if a == "dev" && argv.get(i + 1).is_some_and(|v| v == "watch") {
+                    return false;
+                }
                 if a.contains('/') || a.contains('\\') || a.starts_with('.') {
                     return false;
                 }
@@ -537,6 +588,7 @@ mod routing_tests {
             "service-pvm",
             "release",
             "run",
+            "node",
             "space",
             "zk",
             "help-schema",
@@ -552,6 +604,16 @@ mod routing_tests {
         assert!(should_dynamic_dispatch(&s(&["dev", "compile"])));
     }
 
+    #[test]
+    fn dev_watch_is_builtin_but_other_dev_verbs_are_not() {
+        assert!(!should_dynamic_dispatch(&s(&[
+            "dev", "watch", "counter", "--agent", "counter"
+        ])));
+        assert!(!should_dynamic_dispatch(&s(&["-v", "dev", "watch"])));
+        assert!(should_dynamic_dispatch(&s(&["dev"])));
+        assert!(should_dynamic_dispatch(&s(&["dev", "status"])));
+    }
+
     #[test]
     fn path_like_first_positional_runs_one_shot() {
         // The existing `vosx ./foo.elf` shape must keep working.
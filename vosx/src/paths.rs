@@ -5,6 +5,7 @@
 //! Standard filesystem layout for `vosx` state.
 //!
 //! ```text
+//! ~/.local/share/vosx/node.sock      # `vosx node up` control socket
 //! ~/.local/share/vosx/<space_id>/    # per-space state
 //!   node.key                          # libp2p keypair (per-space)
 //!   agents/{svc_id:08x}.redb         # per-agent CRDT/Raft databases
@@ -118,6 +119,12 @@ pub fn client_identity_path() -> PathBuf {
     config_root().join("identity.key")
 }
 
+/// Control socket of a running `vosx node up` host; `space up` and
+/// `space down` attach and detach spaces through it.
+pub fn node_socket_path() -> PathBuf {
+    data_root().join("node.sock")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
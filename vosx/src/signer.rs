@@ -0,0 +1,521 @@
+//! Pluggable operator signing keys.
+//!
+//! The operator identity (`identity.rs`) is an ed25519 key on disk. The
+//! signatures that carry authority — registry ops such as `space role
+//! grant`, and the deployment signature `vosx build` puts on a package —
+//! can instead come from a key that never touches the disk:
+//!
+//! ```text
+//! file                        # $XDG_CONFIG_HOME/vosx/identity.key (default)
+//! ssh-agent                   # the agent at $SSH_AUTH_SOCK
+//! ssh-agent:/run/agent.sock   # an agent-protocol socket
+//! ssh-agent#laptop            # …its ed25519 key whose comment is `laptop`
+//! pkcs11:/usr/lib/softhsm/libsofthsm2.so#vosx   # token key labelled `vosx`
+//! ```
+//!
+//! `--signer <SPEC>` picks one per command; `VOSX_SIGNER` sets the default.
+//! The key must be ed25519 either way. A PKCS#11 token is logged into with
+//! the user PIN from `VOSX_PKCS11_PIN`.
+//!
+//! Only signatures move. The libp2p connection to a daemon is still
+//! authenticated by the identity file, so the daemon's per-call role gate
+//! sees that PeerId, while the registry checks the op's signature against
+//! the signer's own role.
+
+#[cfg(unix)]
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow, bail};
+use libp2p::PeerId;
+use libp2p::identity::{Keypair, PublicKey};
+
+/// Environment default for `--signer`.
+const SIGNER_ENV: &str = "VOSX_SIGNER";
+/// User PIN for a `pkcs11:` signer's token.
+const PKCS11_PIN_ENV: &str = "VOSX_PKCS11_PIN";
+
+/// A key that signs on the operator's behalf.
+pub trait Signer: Send + Sync {
+    /// The key's public half.
+    fn public(&self) -> PublicKey;
+
+    /// An ed25519 signature over `msg`.
+    fn sign(&self, msg: &[u8]) -> anyhow::Result<Vec<u8>>;
+
+    fn peer_id(&self) -> PeerId {
+        PeerId::from(self.public())
+    }
+}
+
+impl Signer for Keypair {
+    fn public(&self) -> PublicKey {
+        Keypair::public(self)
+    }
+
+    fn sign(&self, msg: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Keypair::sign(self, msg).map_err(|e| anyhow!("sign with the identity key: {e}"))
+    }
+}
+
+impl<S: Signer + ?Sized> Signer for std::sync::Arc<S> {
+    fn public(&self) -> PublicKey {
+        (**self).public()
+    }
+
+    fn sign(&self, msg: &[u8]) -> anyhow::Result<Vec<u8>> {
+        (**self).sign(msg)
+    }
+}
+
+/// Where a signer's key lives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerSpec {
+    /// The identity file.
+    File,
+    /// An ssh-agent-protocol socket; `None` is `$SSH_AUTH_SOCK`. With
+    /// `comment`, the ed25519 key carrying that comment, else the agent's
+    /// only ed25519 key.
+    SshAgent {
+        socket: Option<PathBuf>,
+        comment: Option<String>,
+    },
+    /// A PKCS#11 module and the label of its ed25519 key (the token's only
+    /// one when `None`).
+    Pkcs11 {
+        module: PathBuf,
+        label: Option<String>,
+    },
+}
+
+impl FromStr for SignerSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> anyhow::Result<Self> {
+        let (head, key) = match spec.split_once('#') {
+            Some((head, key)) if !key.is_empty() => (head, Some(key.to_string())),
+            Some(_) => bail!("signer '{spec}' names an empty key after `#`"),
+            None => (spec, None),
+        };
+        let (kind, location) = match head.split_once(':') {
+            Some((kind, location)) => (kind, Some(location)),
+            None => (head, None),
+        };
+        match (kind, location) {
+            ("file", None) if key.is_none() => Ok(Self::File),
+            ("ssh-agent", socket) => Ok(Self::SshAgent {
+                socket: socket.filter(|s| !s.is_empty()).map(PathBuf::from),
+                comment: key,
+            }),
+            ("pkcs11", Some(module)) if !module.is_empty() => Ok(Self::Pkcs11 {
+                module: PathBuf::from(module),
+                label: key,
+            }),
+            ("pkcs11", _) => bail!("signer '{spec}' needs a module: `pkcs11:<module.so>[#label]`"),
+            _ => bail!(
+                "unknown signer '{spec}' (expected `file`, `ssh-agent[:<socket>][#comment]` or \
+                 `pkcs11:<module.so>[#label]`)"
+            ),
+        }
+    }
+}
+
+/// Open the signer `spec` names — or `VOSX_SIGNER`'s, or the identity
+/// file.
+pub fn open(spec: Option<&str>) -> anyhow::Result<Box<dyn Signer>> {
+    let from_env = std::env::var(SIGNER_ENV).ok().filter(|s| !s.is_empty());
+    let spec = match spec.map(str::to_string).or(from_env) {
+        Some(spec) => spec.parse()?,
+        None => SignerSpec::File,
+    };
+    let signer: Box<dyn Signer> = match spec {
+        SignerSpec::File => Box::new(crate::identity::load_or_create()?),
+        SignerSpec::SshAgent { socket, comment } => {
+            let socket = match socket {
+                Some(socket) => socket,
+                None => std::env::var_os("SSH_AUTH_SOCK")
+                    .map(PathBuf::from)
+                    .ok_or_else(|| {
+                        anyhow!("signer `ssh-agent` needs SSH_AUTH_SOCK or a socket path")
+                    })?,
+            };
+            Box::new(SshAgentSigner::open(socket, comment.as_deref())?)
+        }
+        SignerSpec::Pkcs11 { module, label } => {
+            let pin = std::env::var(PKCS11_PIN_ENV).map_err(|_| {
+                anyhow!("a pkcs11 signer needs the token's user PIN in {PKCS11_PIN_ENV}")
+            })?;
+            Box::new(Pkcs11Signer::open(&module, label.as_deref(), pin)?)
+        }
+    };
+    Ok(signer)
+}
+
+fn ed25519_public(key: &[u8]) -> anyhow::Result<PublicKey> {
+    let key = libp2p::identity::ed25519::PublicKey::try_from_bytes(key)
+        .map_err(|e| anyhow!("not an ed25519 public key: {e}"))?;
+    Ok(PublicKey::from(key))
+}
+
+// ── ssh-agent ─────────────────────────────────────────────────────────
+
+const SSH_AGENT_FAILURE: u8 = 5;
+const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+const SSH_AGENTC_SIGN_REQUEST: u8 = 13;
+const SSH_AGENT_SIGN_RESPONSE: u8 = 14;
+
+/// An ed25519 key held by an ssh-agent (or anything speaking its
+/// protocol). One connection per signature, as `ssh` itself does.
+struct SshAgentSigner {
+    socket: PathBuf,
+    /// The key's `ssh-ed25519` wire blob, which names it to the agent.
+    blob: Vec<u8>,
+    public: PublicKey,
+}
+
+impl SshAgentSigner {
+    fn open(socket: PathBuf, comment: Option<&str>) -> anyhow::Result<Self> {
+        let reply = agent_request(&socket, &[SSH_AGENTC_REQUEST_IDENTITIES])?;
+        let keys = parse_identities(&reply)?;
+        let mut matching = keys
+            .into_iter()
+            .filter(|(_, key, c)| key.is_some() && comment.is_none_or(|want| c == want));
+        let (blob, key, _) = match (matching.next(), matching.next()) {
+            (Some(only), None) => only,
+            (None, _) => match comment {
+                Some(comment) => bail!(
+                    "the agent at {} holds no ed25519 key `{comment}`",
+                    socket.display()
+                ),
+                None => bail!("the agent at {} holds no ed25519 key", socket.display()),
+            },
+            (Some(_), Some(_)) => bail!(
+                "the agent at {} holds several ed25519 keys; pick one with `ssh-agent#<comment>`",
+                socket.display()
+            ),
+        };
+        let public = ed25519_public(&key.expect("filtered to ed25519 keys"))?;
+        Ok(Self {
+            socket,
+            blob,
+            public,
+        })
+    }
+}
+
+impl Signer for SshAgentSigner {
+    fn public(&self) -> PublicKey {
+        self.public.clone()
+    }
+
+    fn sign(&self, msg: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut request = vec![SSH_AGENTC_SIGN_REQUEST];
+        put_string(&mut request, &self.blob);
+        put_string(&mut request, msg);
+        request.extend_from_slice(&0u32.to_be_bytes());
+        let reply = agent_request(&self.socket, &request)?;
+        let signature = match reply.split_first() {
+            Some((&SSH_AGENT_SIGN_RESPONSE, rest)) => Reader(rest)
+                .string()
+                .ok_or_else(|| anyhow!("agent sent a truncated signature"))?,
+            Some((&SSH_AGENT_FAILURE, _)) => bail!("the ssh agent refused to sign"),
+            _ => bail!("the ssh agent sent an unexpected reply to a sign request"),
+        };
+        // `string "ssh-ed25519" || string sig` (RFC 8709 §6).
+        let mut r = Reader(signature);
+        match (r.string(), r.string()) {
+            (Some(b"ssh-ed25519"), Some(sig)) if sig.len() == 64 => Ok(sig.to_vec()),
+            _ => bail!("the ssh agent returned a signature that is not ed25519"),
+        }
+    }
+}
+
+/// One agent round trip: a length-framed request, its framed reply.
+#[cfg(unix)]
+fn agent_request(socket: &std::path::Path, request: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut stream = std::os::unix::net::UnixStream::connect(socket)
+        .with_context(|| format!("connect to ssh agent at {}", socket.display()))?;
+    let mut framed = (request.len() as u32).to_be_bytes().to_vec();
+    framed.extend_from_slice(request);
+    stream.write_all(&framed).context("write to ssh agent")?;
+    let mut len = [0; 4];
+    stream.read_exact(&mut len).context("read from ssh agent")?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > 256 * 1024 {
+        bail!("ssh agent reply of {len} bytes is too large");
+    }
+    let mut reply = vec![0; len];
+    stream
+        .read_exact(&mut reply)
+        .context("read from ssh agent")?;
+    Ok(reply)
+}
+
+#[cfg(not(unix))]
+fn agent_request(socket: &std::path::Path, _request: &[u8]) -> anyhow::Result<Vec<u8>> {
+    bail!(
+        "ssh agent at {}: agent sockets need a unix platform",
+        socket.display()
+    )
+}
+
+/// An `IDENTITIES_ANSWER` as `(key blob, ed25519 key if it is one,
+/// comment)`.
+fn parse_identities(reply: &[u8]) -> anyhow::Result<Vec<(Vec<u8>, Option<Vec<u8>>, String)>> {
+    let Some((&SSH_AGENT_IDENTITIES_ANSWER, rest)) = reply.split_first() else {
+        bail!("the ssh agent did not list its keys");
+    };
+    let mut r = Reader(rest);
+    let count = r
+        .u32()
+        .ok_or_else(|| anyhow!("truncated ssh agent key list"))?;
+    let mut keys = Vec::new();
+    for _ in 0..count {
+        let (Some(blob), Some(comment)) = (r.string(), r.string()) else {
+            bail!("truncated ssh agent key list");
+        };
+        let mut b = Reader(blob);
+        let ed25519 = match (b.string(), b.string()) {
+            (Some(b"ssh-ed25519"), Some(key)) if key.len() == 32 => Some(key.to_vec()),
+            _ => None,
+        };
+        keys.push((
+            blob.to_vec(),
+            ed25519,
+            String::from_utf8_lossy(comment).into_owned(),
+        ));
+    }
+    Ok(keys)
+}
+
+fn put_string(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+/// Cursor over SSH wire encoding.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn u32(&mut self) -> Option<u32> {
+        let (head, rest) = self.0.split_first_chunk::<4>()?;
+        self.0 = rest;
+        Some(u32::from_be_bytes(*head))
+    }
+
+    fn string(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32()? as usize;
+        if self.0.len() < len {
+            return None;
+        }
+        let (s, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(s)
+    }
+}
+
+// ── PKCS#11 ───────────────────────────────────────────────────────────
+
+/// An ed25519 key on a PKCS#11 token (SoftHSM or a hardware token),
+/// signing with `CKM_EDDSA` over a logged-in session.
+struct Pkcs11Signer {
+    session: std::sync::Mutex<cryptoki::session::Session>,
+    key: cryptoki::object::ObjectHandle,
+    public: PublicKey,
+}
+
+impl Pkcs11Signer {
+    fn open(module: &std::path::Path, label: Option<&str>, pin: String) -> anyhow::Result<Self> {
+        use cryptoki::context::{CInitializeArgs, Pkcs11};
+        use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass};
+        use cryptoki::session::UserType;
+        use cryptoki::types::AuthPin;
+
+        let p11 = Pkcs11::new(module)
+            .with_context(|| format!("load PKCS#11 module {}", module.display()))?;
+        p11.initialize(CInitializeArgs::OsThreads)
+            .context("initialize PKCS#11 module")?;
+        let pin = AuthPin::new(pin);
+        let mut template = vec![Attribute::KeyType(KeyType::EC_EDWARDS)];
+        if let Some(label) = label {
+            template.push(Attribute::Label(label.as_bytes().to_vec()));
+        }
+        let mut private = vec![Attribute::Class(ObjectClass::PRIVATE_KEY)];
+        private.extend(template.iter().cloned());
+        for slot in p11.get_slots_with_token().context("list PKCS#11 slots")? {
+            let session = p11.open_ro_session(slot).context("open PKCS#11 session")?;
+            session
+                .login(UserType::User, Some(&pin))
+                .context("log in to PKCS#11 token")?;
+            let keys = session
+                .find_objects(&private)
+                .context("find PKCS#11 keys")?;
+            let key = match keys.as_slice() {
+                [] => continue,
+                [key] => *key,
+                _ => {
+                    bail!("the PKCS#11 token holds several ed25519 keys; pick one with `#<label>`")
+                }
+            };
+            // The public half sits on the matching public-key object.
+            let id = session
+                .get_attributes(key, &[AttributeType::Id])
+                .context("read PKCS#11 key id")?;
+            let mut public = vec![Attribute::Class(ObjectClass::PUBLIC_KEY)];
+            public.extend(template.iter().cloned());
+            public.extend(id);
+            let point = session
+                .find_objects(&public)
+                .context("find PKCS#11 public key")?
+                .first()
+                .map(|object| session.get_attributes(*object, &[AttributeType::EcPoint]))
+                .transpose()
+                .context("read PKCS#11 public key")?
+                .into_iter()
+                .flatten()
+                .find_map(|attribute| match attribute {
+                    Attribute::EcPoint(point) => Some(point),
+                    _ => None,
+                })
+                .ok_or_else(|| anyhow!("the PKCS#11 key has no public-key object"))?;
+            return Ok(Self {
+                session: std::sync::Mutex::new(session),
+                key,
+                public: ed25519_public(ec_point_key(&point))?,
+            });
+        }
+        match label {
+            Some(label) => bail!("no PKCS#11 token holds an ed25519 key labelled `{label}`"),
+            None => bail!("no PKCS#11 token holds an ed25519 key"),
+        }
+    }
+}
+
+impl Signer for Pkcs11Signer {
+    fn public(&self) -> PublicKey {
+        self.public.clone()
+    }
+
+    fn sign(&self, msg: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let session = self
+            .session
+            .lock()
+            .map_err(|_| anyhow!("PKCS#11 session is unavailable"))?;
+        session
+            .sign(&cryptoki::mechanism::Mechanism::Eddsa, self.key, msg)
+            .context("sign on the PKCS#11 token")
+    }
+}
+
+/// `CKA_EC_POINT` for an Edwards key is the raw 32 bytes, or (as SoftHSM
+/// stores it) those bytes wrapped in a DER OCTET STRING.
+fn ec_point_key(point: &[u8]) -> &[u8] {
+    match point {
+        [0x04, 32, key @ ..] if key.len() == 32 => key,
+        _ => point,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_signer_specs() {
+        assert_eq!("file".parse::<SignerSpec>().unwrap(), SignerSpec::File);
+        assert_eq!(
+            "ssh-agent".parse::<SignerSpec>().unwrap(),
+            SignerSpec::SshAgent {
+                socket: None,
+                comment: None
+            }
+        );
+        assert_eq!(
+            "ssh-agent:/run/a.sock#laptop"
+                .parse::<SignerSpec>()
+                .unwrap(),
+            SignerSpec::SshAgent {
+                socket: Some("/run/a.sock".into()),
+                comment: Some("laptop".into()),
+            }
+        );
+        assert_eq!(
+            "pkcs11:/usr/lib/softhsm/libsofthsm2.so#vosx"
+                .parse::<SignerSpec>()
+                .unwrap(),
+            SignerSpec::Pkcs11 {
+                module: "/usr/lib/softhsm/libsofthsm2.so".into(),
+                label: Some("vosx".into()),
+            }
+        );
+        for bad in ["pkcs11", "pkcs11:", "ssh-agent#", "file#x", "yubikey"] {
+            assert!(bad.parse::<SignerSpec>().is_err(), "{bad}");
+        }
+    }
+
+    /// A one-shot agent on a socket pair: lists one RSA and one ed25519
+    /// key, then signs with the ed25519 one.
+    #[cfg(unix)]
+    #[test]
+    fn ssh_agent_signer_picks_the_ed25519_key_and_signs() {
+        use std::os::unix::net::UnixListener;
+
+        let key = Keypair::generate_ed25519();
+        let ed = key.clone().try_into_ed25519().unwrap();
+        let mut blob = Vec::new();
+        put_string(&mut blob, b"ssh-ed25519");
+        put_string(&mut blob, &ed.public().to_bytes());
+        let mut rsa = Vec::new();
+        put_string(&mut rsa, b"ssh-rsa");
+        put_string(&mut rsa, &[1, 0, 1]);
+
+        let dir = std::env::temp_dir().join(format!("vosx-agent-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket = dir.join("agent.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let agent = std::thread::spawn(move || {
+            for _ in 0..2 {
+                let (mut conn, _) = listener.accept().unwrap();
+                let mut len = [0; 4];
+                conn.read_exact(&mut len).unwrap();
+                let mut request = vec![0; u32::from_be_bytes(len) as usize];
+                conn.read_exact(&mut request).unwrap();
+                let reply = match request[0] {
+                    SSH_AGENTC_REQUEST_IDENTITIES => {
+                        let mut reply = vec![SSH_AGENT_IDENTITIES_ANSWER];
+                        reply.extend_from_slice(&2u32.to_be_bytes());
+                        put_string(&mut reply, &rsa);
+                        put_string(&mut reply, b"old");
+                        put_string(&mut reply, &blob);
+                        put_string(&mut reply, b"laptop");
+                        reply
+                    }
+                    _ => {
+                        let mut r = Reader(&request[1..]);
+                        assert_eq!(r.string(), Some(&blob[..]));
+                        let data = r.string().unwrap();
+                        let mut sig = Vec::new();
+                        put_string(&mut sig, b"ssh-ed25519");
+                        put_string(&mut sig, &ed.sign(data));
+                        let mut reply = vec![SSH_AGENT_SIGN_RESPONSE];
+                        put_string(&mut reply, &sig);
+                        reply
+                    }
+                };
+                let mut framed = (reply.len() as u32).to_be_bytes().to_vec();
+                framed.extend_from_slice(&reply);
+                conn.write_all(&framed).unwrap();
+            }
+        });
+
+        let signer = SshAgentSigner::open(socket, None).unwrap();
+        assert_eq!(signer.peer_id(), PeerId::from(key.public()));
+        let sig = Signer::sign(&signer, b"grant").unwrap();
+        assert!(key.public().verify(b"grant", &sig));
+        agent.join().unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -2,7 +2,7 @@
 //! into a space with one command (`vosx space up <token>`).
 //!
 //! A token is a *pointer + credential, never policy*: it names the space
-//! (id, human name, bootnodes) and carries a single-use redemption
+//! (id, human name, bootnodes, relays) and carries a single-use redemption
 //! secret plus an admin's signature delegating a role. What the role
 //! *means* lives in the registry and can evolve after the token is
 //! minted.
@@ -48,10 +48,10 @@ pub const TOKEN_HRP: &str = "vos1";
 
 /// Current token format version — the first raw byte inside the bs58
 /// blob. Bump on any breaking `InvitePayload` layout change.
-pub const TOKEN_VERSION: u8 = 3;
+pub const TOKEN_VERSION: u8 = 4;
 
 /// Domain tag for the token's integrity checksum.
-const CHECKSUM_DOMAIN: &[u8] = b"vos-invite/v3";
+const CHECKSUM_DOMAIN: &[u8] = b"vos-invite/v4";
 
 /// Trailing checksum length (bytes of a domain-separated blake2b).
 const CHECKSUM_LEN: usize = 4;
@@ -71,6 +71,10 @@ pub struct InvitePayload {
     pub name: String,
     /// Bootnode multiaddrs to dial for the redeem invoke + first sync.
     pub bootnodes: Vec<String>,
+    /// Circuit-relay multiaddrs (`…/p2p/<relay>`) the joiner reserves a
+    /// slot on, so a node behind NAT stays reachable after it joins.
+    /// Empty when the minting side knows of no relay.
+    pub relays: Vec<String>,
     /// `AUTH_ROLE_*` the token grants.
     pub role: u8,
     /// Expiry (unix seconds). Bound into the signed invite canonical,
@@ -105,11 +109,13 @@ pub struct InvitePayload {
 /// (The genesis root can't distinguish two spaces one operator runs — it
 /// is the shared operator identity — but each space's `space_id` differs,
 /// since it derives from a fresh per-space genesis origin.)
+#[allow(clippy::too_many_arguments)]
 pub fn mint(
     operator: &Keypair,
     space_id: [u8; 32],
     name: String,
     bootnodes: Vec<String>,
+    relays: Vec<String>,
     role: u8,
     expires_at: u64,
     authority_replication_id: [u8; 32],
@@ -146,6 +152,7 @@ pub fn mint(
         space_id,
         name,
         bootnodes,
+        relays,
         role,
         expires_at,
         authority_replication_id,
@@ -295,6 +302,7 @@ mod tests {
             sample_space_id(),
             "demo".into(),
             vec!["/ip4/1.2.3.4/tcp/9000".into()],
+            vec!["/ip4/5.6.7.8/tcp/4001/p2p/12D3KooWRelay".into()],
             AUTH_ROLE_READONLY,
             2_000_000_000,
             [0xa7; 32],
@@ -305,6 +313,10 @@ mod tests {
         assert_eq!(p.space_id, sample_space_id());
         assert_eq!(p.name, "demo");
         assert_eq!(p.bootnodes, vec!["/ip4/1.2.3.4/tcp/9000".to_string()]);
+        assert_eq!(
+            p.relays,
+            vec!["/ip4/5.6.7.8/tcp/4001/p2p/12D3KooWRelay".to_string()]
+        );
         assert_eq!(p.role, AUTH_ROLE_READONLY);
         assert_eq!(p.expires_at, 2_000_000_000);
         assert_eq!(
@@ -321,6 +333,7 @@ mod tests {
             sample_space_id(),
             "x".into(),
             vec![],
+            vec![],
             AUTH_ROLE_READONLY,
             1,
             [0xa7; 32],
@@ -342,6 +355,7 @@ mod tests {
             sample_space_id(),
             "x".into(),
             vec![],
+            vec![],
             AUTH_ROLE_ADMIN,
             1,
             [0xa7; 32],
@@ -358,6 +372,7 @@ mod tests {
             sample_space_id(),
             "x".into(),
             vec![],
+            vec![],
             AUTH_ROLE_READONLY,
             1,
             [0xa7; 32],
@@ -381,6 +396,7 @@ mod tests {
             sample_space_id(),
             "x".into(),
             vec![],
+            vec![],
             AUTH_ROLE_READONLY,
             1,
             [0; 32],
@@ -394,6 +410,7 @@ mod tests {
             sample_space_id(),
             "x".into(),
             vec![],
+            vec![],
             AUTH_ROLE_READONLY,
             1,
             [0xa7; 32],
@@ -417,6 +434,7 @@ mod tests {
             sample_space_id(),
             "x".into(),
             vec![],
+            vec![],
             AUTH_ROLE_READONLY,
             1,
             [0xa7; 32],
@@ -461,6 +479,7 @@ mod tests {
             space_id,
             "demo".into(),
             vec![],
+            vec![],
             role,
             expires_at,
             authority,